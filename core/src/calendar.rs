@@ -0,0 +1,182 @@
+use chrono::{Duration, NaiveDate};
+
+/// A named stretch of the academic year, inclusive of both dates.
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct Period {
+    pub name: String,
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+}
+
+impl Period {
+    /// Whether the Monday-to-Sunday week starting `week_start` overlaps the period.
+    fn overlaps_week(&self, week_start: NaiveDate) -> bool {
+        self.start <= week_start + Duration::days(6) && self.end >= week_start
+    }
+}
+
+/// Part of the academic year a week falls in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Season<'a> {
+    /// Ordinary term time, or any week when no calendar is configured.
+    Term,
+    Exams(&'a str),
+    /// A configured break, or a week outside every configured term.
+    Break(Option<&'a str>),
+}
+
+/// Terms, exam weeks, and breaks, with how much signal volume is expected to move in each, so
+/// trend flags can tell finals week from a program-wide crisis.
+///
+/// An empty calendar treats every week as term time and changes nothing.
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AcademicCalendar {
+    pub terms: Vec<Period>,
+    pub exams: Vec<Period>,
+    pub breaks: Vec<Period>,
+    /// Expected weekly signal volume in exam weeks, relative to term time.
+    pub exam_factor: f64,
+    /// Expected weekly signal volume in breaks and between terms, relative to term time.
+    pub break_factor: f64,
+}
+
+impl Default for AcademicCalendar {
+    fn default() -> Self {
+        Self {
+            terms: Vec::new(),
+            exams: Vec::new(),
+            breaks: Vec::new(),
+            exam_factor: 1.5,
+            break_factor: 0.5,
+        }
+    }
+}
+
+impl AcademicCalendar {
+    pub fn validate(&self) -> anyhow::Result<()> {
+        for (kind, periods) in [
+            ("terms", &self.terms),
+            ("exams", &self.exams),
+            ("breaks", &self.breaks),
+        ] {
+            for period in periods {
+                if period.end < period.start {
+                    anyhow::bail!(
+                        "calendar {kind} entry {} ends ({}) before it starts ({})",
+                        period.name,
+                        period.end,
+                        period.start
+                    );
+                }
+            }
+        }
+        for (name, factor) in [
+            ("exam_factor", self.exam_factor),
+            ("break_factor", self.break_factor),
+        ] {
+            if !factor.is_finite() || factor <= 0.0 {
+                anyhow::bail!("calendar {name} must be positive, got {factor}");
+            }
+        }
+        Ok(())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty() && self.exams.is_empty() && self.breaks.is_empty()
+    }
+
+    /// Season of the week starting `week_start`. Exams win over breaks where both overlap it.
+    pub fn season(&self, week_start: NaiveDate) -> Season<'_> {
+        if let Some(name) = overlapping(&self.exams, week_start) {
+            return Season::Exams(name);
+        }
+        if let Some(name) = overlapping(&self.breaks, week_start) {
+            return Season::Break(Some(name));
+        }
+        if !self.terms.is_empty() && overlapping(&self.terms, week_start).is_none() {
+            return Season::Break(None);
+        }
+        Season::Term
+    }
+
+    /// Expected signal volume in the week starting `week_start`, relative to term time.
+    pub fn factor(&self, week_start: NaiveDate) -> f64 {
+        match self.season(week_start) {
+            Season::Term => 1.0,
+            Season::Exams(_) => self.exam_factor,
+            Season::Break(_) => self.break_factor,
+        }
+    }
+
+    /// Short note for week labels, e.g. "exam week: Fall finals"; empty in term time.
+    pub fn label(&self, week_start: NaiveDate) -> String {
+        match self.season(week_start) {
+            Season::Term => String::new(),
+            Season::Exams(name) => format!("exam week: {name}"),
+            Season::Break(Some(name)) => format!("break: {name}"),
+            Season::Break(None) => "between terms".to_string(),
+        }
+    }
+
+    /// `count` in the week starting `week_start` scaled to its term-time equivalent.
+    pub fn adjust(&self, week_start: NaiveDate, count: f64) -> f64 {
+        count / self.factor(week_start)
+    }
+}
+
+/// Name of the first period overlapping the week starting `week_start`.
+fn overlapping(periods: &[Period], week_start: NaiveDate) -> Option<&str> {
+    periods
+        .iter()
+        .find(|period| period.overlaps_week(week_start))
+        .map(|period| period.name.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, month, day).unwrap()
+    }
+
+    fn period(name: &str, start: NaiveDate, end: NaiveDate) -> Period {
+        Period {
+            name: name.to_string(),
+            start,
+            end,
+        }
+    }
+
+    #[test]
+    fn classifies_weeks_by_calendar() {
+        let calendar = AcademicCalendar {
+            terms: vec![period("Fall", date(9, 1), date(12, 18))],
+            exams: vec![period("Fall finals", date(12, 14), date(12, 18))],
+            breaks: vec![period("Thanksgiving", date(11, 26), date(11, 27))],
+            ..AcademicCalendar::default()
+        };
+        calendar.validate().unwrap();
+
+        assert_eq!(calendar.season(date(10, 5)), Season::Term);
+        assert_eq!(calendar.season(date(12, 14)), Season::Exams("Fall finals"));
+        // The Thanksgiving break falls on Thursday and Friday of this week.
+        assert_eq!(calendar.label(date(11, 23)), "break: Thanksgiving");
+        assert_eq!(calendar.season(date(12, 21)), Season::Break(None));
+        assert_eq!(calendar.adjust(date(12, 14), 30.0), 20.0);
+        assert_eq!(calendar.adjust(date(12, 21), 5.0), 10.0);
+
+        // Without a calendar every week is term time.
+        let empty = AcademicCalendar::default();
+        assert!(empty.is_empty());
+        assert_eq!(empty.factor(date(12, 14)), 1.0);
+
+        let backwards = AcademicCalendar {
+            exams: vec![period("Finals", date(12, 18), date(12, 14))],
+            ..AcademicCalendar::default()
+        };
+        assert!(backwards.validate().is_err());
+    }
+}
@@ -0,0 +1,275 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use crate::models::{ScholarScore, SignalRecord};
+
+/// k-means stops after this many rounds even if scholars are still moving between clusters.
+const MAX_ITERATIONS: usize = 100;
+
+/// Types making up at least this share of a cluster's signals name it.
+const LABEL_SHARE: f64 = 0.25;
+
+/// Scholars whose signals look alike: the same types, at similar severity.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct Cluster {
+    /// Short description such as "attendance + financial, high severity".
+    pub label: String,
+    /// Share of each member's signals per type, averaged over the members.
+    pub mix: BTreeMap<String, f64>,
+    /// Mean of the members' average severities.
+    pub avg_severity: f64,
+    /// Member emails, sorted.
+    pub scholars: Vec<String>,
+}
+
+impl Cluster {
+    /// Types in `mix`, largest share first.
+    pub fn ranked_mix(&self) -> Vec<(&str, f64)> {
+        let mut mix: Vec<(&str, f64)> = self
+            .mix
+            .iter()
+            .filter(|(_, share)| **share > 0.0)
+            .map(|(signal_type, share)| (signal_type.as_str(), *share))
+            .collect();
+        mix.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+        mix
+    }
+
+    /// Mean risk score of the members found in `scores`.
+    pub fn avg_score(&self, scores: &[ScholarScore]) -> f64 {
+        let members: BTreeSet<&str> = self.scholars.iter().map(String::as_str).collect();
+        let scores: Vec<f64> = scores
+            .iter()
+            .filter(|score| members.contains(score.scholar_email.as_str()))
+            .map(|score| score.score)
+            .collect();
+        if scores.is_empty() {
+            0.0
+        } else {
+            scores.iter().sum::<f64>() / scores.len() as f64
+        }
+    }
+}
+
+/// Groups the scholars behind `signals` into at most `k` clusters, largest first, by k-means
+/// over each scholar's share of signals per type and average severity.
+pub fn cluster(signals: &[SignalRecord], k: usize) -> Vec<Cluster> {
+    let types: Vec<&str> = signals
+        .iter()
+        .map(|signal| signal.signal_type.as_str())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+    let column: HashMap<&str, usize> = types
+        .iter()
+        .enumerate()
+        .map(|(index, signal_type)| (*signal_type, index))
+        .collect();
+
+    // Per scholar: signals per type and the severity total.
+    let mut tallies: BTreeMap<&str, (Vec<f64>, f64)> = BTreeMap::new();
+    for signal in signals {
+        let (counts, severity) = tallies
+            .entry(&signal.scholar_email)
+            .or_insert_with(|| (vec![0.0; types.len()], 0.0));
+        counts[column[signal.signal_type.as_str()]] += 1.0;
+        *severity += f64::from(signal.severity);
+    }
+    let emails: Vec<&str> = tallies.keys().copied().collect();
+    // Type shares and severity, rescaled from 1-5 to 0-1 so no feature dominates the distance.
+    let points: Vec<Vec<f64>> = tallies
+        .values()
+        .map(|(counts, severity)| {
+            let total: f64 = counts.iter().sum();
+            let mut point: Vec<f64> = counts.iter().map(|count| count / total).collect();
+            point.push((severity / total - 1.0) / 4.0);
+            point
+        })
+        .collect();
+    if points.is_empty() || k == 0 {
+        return Vec::new();
+    }
+
+    let assignment = kmeans(&points, k);
+    let mut clusters: Vec<Cluster> = (0..=assignment.iter().copied().max().unwrap_or(0))
+        .filter_map(|index| {
+            let members: Vec<usize> = (0..points.len())
+                .filter(|point| assignment[*point] == index)
+                .collect();
+            let center = mean(members.iter().map(|member| &points[*member]))?;
+            let mix: BTreeMap<String, f64> = types
+                .iter()
+                .zip(&center)
+                .map(|(signal_type, share)| (signal_type.to_string(), *share))
+                .collect();
+            let avg_severity = 1.0 + center[types.len()] * 4.0;
+            let mut cluster = Cluster {
+                label: String::new(),
+                mix,
+                avg_severity,
+                scholars: members
+                    .iter()
+                    .map(|member| emails[*member].to_string())
+                    .collect(),
+            };
+            cluster.label = label(&cluster);
+            Some(cluster)
+        })
+        .collect();
+    clusters.sort_by(|a, b| {
+        b.scholars
+            .len()
+            .cmp(&a.scholars.len())
+            .then_with(|| b.avg_severity.total_cmp(&a.avg_severity))
+    });
+    clusters
+}
+
+fn label(cluster: &Cluster) -> String {
+    let dominant: Vec<&str> = cluster
+        .ranked_mix()
+        .into_iter()
+        .filter(|(_, share)| *share >= LABEL_SHARE)
+        .take(2)
+        .map(|(signal_type, _)| signal_type)
+        .collect();
+    let types = match dominant.as_slice() {
+        [] => "mixed".to_string(),
+        types => types.join(" + "),
+    };
+    let severity = match cluster.avg_severity {
+        severity if severity >= 3.5 => "high",
+        severity if severity < 2.5 => "low",
+        _ => "moderate",
+    };
+    format!("{types}, {severity} severity")
+}
+
+/// Cluster index for each point. Seeds are picked farthest-first from the first point, so the
+/// same input always clusters the same way.
+fn kmeans(points: &[Vec<f64>], k: usize) -> Vec<usize> {
+    let mut centers = vec![points[0].clone()];
+    while centers.len() < k {
+        let Some(next) = points
+            .iter()
+            .max_by(|a, b| distance_to(a, &centers).total_cmp(&distance_to(b, &centers)))
+            .filter(|point| distance_to(point, &centers) > 0.0)
+        else {
+            // Fewer distinct profiles than clusters asked for.
+            break;
+        };
+        centers.push(next.clone());
+    }
+
+    let mut assignment = Vec::new();
+    for _ in 0..MAX_ITERATIONS {
+        let next: Vec<usize> = points
+            .iter()
+            .map(|point| nearest(point, &centers))
+            .collect();
+        if next == assignment {
+            break;
+        }
+        assignment = next;
+        for (index, center) in centers.iter_mut().enumerate() {
+            let members = points
+                .iter()
+                .zip(&assignment)
+                .filter(|(_, cluster)| **cluster == index)
+                .map(|(point, _)| point);
+            if let Some(mean) = mean(members) {
+                *center = mean;
+            }
+        }
+    }
+    assignment
+}
+
+fn squared_distance(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(a, b)| (a - b) * (a - b)).sum()
+}
+
+fn distance_to(point: &[f64], centers: &[Vec<f64>]) -> f64 {
+    centers
+        .iter()
+        .map(|center| squared_distance(point, center))
+        .fold(f64::INFINITY, f64::min)
+}
+
+fn nearest(point: &[f64], centers: &[Vec<f64>]) -> usize {
+    (0..centers.len())
+        .min_by(|a, b| {
+            squared_distance(point, &centers[*a]).total_cmp(&squared_distance(point, &centers[*b]))
+        })
+        .unwrap_or(0)
+}
+
+fn mean<'a>(points: impl Iterator<Item = &'a Vec<f64>>) -> Option<Vec<f64>> {
+    let mut count = 0.0;
+    let mut sum: Vec<f64> = Vec::new();
+    for point in points {
+        sum.resize(point.len(), 0.0);
+        for (total, value) in sum.iter_mut().zip(point) {
+            *total += value;
+        }
+        count += 1.0;
+    }
+    (count > 0.0).then(|| sum.into_iter().map(|total| total / count).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn signal(email: &str, signal_type: &str, severity: i32) -> SignalRecord {
+        SignalRecord {
+            scholar_id: Uuid::nil(),
+            scholar_name: email.to_string(),
+            scholar_email: email.to_string(),
+            cohort: "2026".to_string(),
+            signal_type: signal_type.to_string(),
+            severity,
+            occurred_at: NaiveDate::from_ymd_opt(2026, 3, 2).unwrap(),
+            note: String::new(),
+            advisor: None,
+            submitted_by: None,
+            attachments: Vec::new(),
+            note_categories: Vec::new(),
+            note_sentiment: None,
+        }
+    }
+
+    #[test]
+    fn separates_and_labels_distinct_profiles() {
+        let signals = vec![
+            signal("a@x.org", "attendance", 5),
+            signal("a@x.org", "financial", 4),
+            signal("b@x.org", "attendance", 4),
+            signal("b@x.org", "financial", 5),
+            signal("b@x.org", "attendance", 4),
+            signal("c@x.org", "attendance", 5),
+            signal("c@x.org", "financial", 4),
+            signal("d@x.org", "wellbeing", 1),
+            signal("e@x.org", "wellbeing", 2),
+        ];
+
+        let clusters = cluster(&signals, 2);
+        assert_eq!(clusters.len(), 2);
+        assert_eq!(clusters[0].scholars, ["a@x.org", "b@x.org", "c@x.org"]);
+        assert_eq!(clusters[0].label, "attendance + financial, high severity");
+        assert_eq!(clusters[1].scholars, ["d@x.org", "e@x.org"]);
+        assert_eq!(clusters[1].label, "wellbeing, low severity");
+        assert!((clusters[1].avg_severity - 1.5).abs() < 1e-9);
+        assert_eq!(clusters[1].ranked_mix(), [("wellbeing", 1.0)]);
+
+        // Asking for more clusters than there are distinct profiles yields one per profile.
+        let same = [
+            signal("a@x.org", "academic", 3),
+            signal("b@x.org", "academic", 3),
+        ];
+        assert_eq!(cluster(&same, 4).len(), 1);
+        assert!(cluster(&[], 3).is_empty());
+    }
+}
@@ -0,0 +1,4491 @@
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use anyhow::Context;
+use chrono::{DateTime, NaiveDate, Utc};
+use futures_util::TryStreamExt;
+use sha2::{Digest, Sha256};
+use sqlx::migrate::{Migrate, Migrator};
+use sqlx::postgres::{PgConnectOptions, PgPoolOptions, PgSslMode};
+use sqlx::{PgConnection, PgPool, Postgres, QueryBuilder, Row};
+use uuid::Uuid;
+
+use crate::enrich::NoteEnrichment;
+use crate::export::{ExportRow, SnapshotRow};
+use crate::import::{CohortAliases, CsvRow, OnConflict, RowOutcome, SeverityBounds, SignalTypes};
+use crate::models::{
+    Advisor, Attachment, CohortAggregate, CohortHealth, CohortTrend, DatabaseStats, ImportBatch,
+    Intervention, MergeSummary, Organization, Outcome, OutcomeType, PurgeSummary, RetentionMode,
+    RetentionPlan, Scholar, ScholarScore, ScholarStatus, ScoreHistoryPoint, ScoreRun, SearchHit,
+    SignalRecord, SignalTrend, SignalType, SnapshotScore,
+};
+use crate::notes;
+use crate::raters::RatedSignal;
+use crate::risk::{
+    self, MultiWindowAccumulator, ScoreAccumulator, ScoringConfig, Tier, TypeAggregate, WindowScore,
+};
+
+#[derive(Debug, Clone)]
+pub struct PoolSettings {
+    pub max_connections: u32,
+    pub acquire_timeout: Duration,
+    pub statement_timeout: Option<Duration>,
+    pub ssl_mode: Option<PgSslMode>,
+    pub ssl_root_cert: Option<PathBuf>,
+}
+
+/// How a write is retried after a transient database error such as a dropped connection: up to
+/// `attempts` more tries, waiting `initial_delay` before the first and doubling the wait, up to
+/// `max_delay`, before each after it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    pub attempts: u32,
+    pub initial_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            attempts: 5,
+            initial_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// How long to wait before retry `attempt` (from 0) after `err`, or `None` when the error
+    /// would only recur or the attempts are used up.
+    pub fn backoff(&self, err: &anyhow::Error, attempt: u32) -> Option<Duration> {
+        if attempt >= self.attempts || !is_transient(err) {
+            return None;
+        }
+        let factor = 2u32.saturating_pow(attempt);
+        Some(
+            self.initial_delay
+                .saturating_mul(factor)
+                .min(self.max_delay),
+        )
+    }
+}
+
+/// Whether `err` came from a lost or refused connection, or a conflict with another transaction,
+/// rather than from what was sent, so that sending it again may succeed.
+pub fn is_transient(err: &anyhow::Error) -> bool {
+    match err
+        .chain()
+        .find_map(|cause| cause.downcast_ref::<sqlx::Error>())
+    {
+        Some(sqlx::Error::Io(_) | sqlx::Error::PoolTimedOut) => true,
+        // Connection exceptions, serialization failures, deadlocks, too many connections, and
+        // server shutdown or startup.
+        Some(sqlx::Error::Database(db)) => db.code().is_some_and(|code| {
+            code.starts_with("08")
+                || matches!(
+                    code.as_ref(),
+                    "40001" | "40P01" | "53300" | "57P01" | "57P02" | "57P03"
+                )
+        }),
+        _ => false,
+    }
+}
+
+#[tracing::instrument(skip_all)]
+pub async fn connect(database_url: &str, settings: &PoolSettings) -> anyhow::Result<PgPool> {
+    let pool = PgPoolOptions::new()
+        .max_connections(settings.max_connections)
+        .acquire_timeout(settings.acquire_timeout)
+        .connect_with(connect_options(database_url, settings)?)
+        .await
+        .context("failed to connect to Postgres")?;
+    Ok(pool)
+}
+
+/// Connection options for `database_url` with the TLS and timeout settings applied.
+pub fn connect_options(
+    database_url: &str,
+    settings: &PoolSettings,
+) -> anyhow::Result<PgConnectOptions> {
+    let mut options = PgConnectOptions::from_str(database_url)
+        .context("DATABASE_URL is not a valid Postgres URL")?;
+
+    if let Some(mode) = settings.ssl_mode {
+        options = options.ssl_mode(mode);
+    }
+    if let Some(path) = &settings.ssl_root_cert {
+        options = options.ssl_root_cert(path);
+    }
+    if let Some(timeout) = settings.statement_timeout {
+        let millis = timeout.as_millis().to_string();
+        options = options.options([("statement_timeout", millis.as_str())]);
+    }
+    Ok(options)
+}
+
+static MIGRATOR: Migrator = sqlx::migrate!("../migrations");
+
+/// Migration that creates the audit log; reverting below it leaves nowhere to record the revert.
+pub const AUDIT_LOG_MIGRATION: i64 = 6;
+
+#[tracing::instrument(skip_all)]
+pub async fn init_db(pool: &PgPool) -> anyhow::Result<()> {
+    MIGRATOR.run(pool).await?;
+    Ok(())
+}
+
+/// Reads the range out of a check constraint definition as `pg_get_constraintdef` prints it, e.g.
+/// `CHECK (((severity >= 1) AND (severity <= 5)))`. Negative bounds print as `'-1'::integer`.
+fn parse_severity_check(definition: &str) -> Option<SeverityBounds> {
+    let bound = |operator: &str| -> Option<i32> {
+        let (_, rest) = definition.split_once(operator)?;
+        let value = rest.trim_start().split(')').next()?;
+        value
+            .trim_end_matches("::integer")
+            .trim_matches('\'')
+            .parse()
+            .ok()
+    };
+    Some(SeverityBounds {
+        min: bound(">=")?,
+        max: bound("<=")?,
+    })
+}
+
+/// Range the `signals_severity_range` check constraint enforces, or `None` before migration 034
+/// named it.
+#[tracing::instrument(skip_all)]
+pub async fn severity_constraint(pool: &PgPool) -> anyhow::Result<Option<SeverityBounds>> {
+    let definition: Option<String> = sqlx::query_scalar(
+        "SELECT pg_get_constraintdef(oid) FROM pg_constraint \
+         WHERE conname = 'signals_severity_range' \
+           AND conrelid = 'cohort_early_warning.signals'::regclass",
+    )
+    .fetch_optional(pool)
+    .await?;
+    definition
+        .map(|definition| {
+            parse_severity_check(&definition).with_context(|| {
+                format!("cannot read the severity range from constraint {definition}")
+            })
+        })
+        .transpose()
+}
+
+/// Makes the `signals_severity_range` constraint enforce `bounds`. Returns the range it enforced
+/// before when that changed, and `None` when it already matched or the schema predates it.
+///
+/// Fails without changing anything while stored signals fall outside `bounds`.
+#[tracing::instrument(skip_all)]
+pub async fn apply_severity_bounds(
+    pool: &PgPool,
+    bounds: SeverityBounds,
+) -> anyhow::Result<Option<SeverityBounds>> {
+    let Some(current) = severity_constraint(pool).await? else {
+        return Ok(None);
+    };
+    if current == bounds {
+        return Ok(None);
+    }
+    let outside: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM cohort_early_warning.signals \
+         WHERE severity NOT BETWEEN $1 AND $2",
+    )
+    .bind(bounds.min)
+    .bind(bounds.max)
+    .fetch_one(pool)
+    .await?;
+    if outside > 0 {
+        anyhow::bail!(
+            "{outside} signals have a severity outside {bounds}; correct them (see `validate`) \
+             or widen [severity] before applying it"
+        );
+    }
+    let mut tx = pool.begin().await?;
+    sqlx::query("ALTER TABLE cohort_early_warning.signals DROP CONSTRAINT signals_severity_range")
+        .execute(&mut *tx)
+        .await?;
+    // DDL takes no bind parameters; the bounds are integers, so formatting them in is safe.
+    sqlx::query(&format!(
+        "ALTER TABLE cohort_early_warning.signals ADD CONSTRAINT signals_severity_range \
+         CHECK (severity BETWEEN {} AND {})",
+        bounds.min, bounds.max
+    ))
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await?;
+    Ok(Some(current))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MigrationState {
+    Pending,
+    Applied,
+    /// Applied, but the migration file has changed since.
+    Modified,
+    /// Started but did not finish; needs manual repair before anything else can run.
+    Failed,
+}
+
+impl MigrationState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MigrationState::Pending => "pending",
+            MigrationState::Applied => "applied",
+            MigrationState::Modified => "modified",
+            MigrationState::Failed => "failed",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MigrationStatus {
+    pub version: i64,
+    pub description: String,
+    pub state: MigrationState,
+    pub installed_on: Option<DateTime<Utc>>,
+    pub reversible: bool,
+}
+
+#[tracing::instrument(skip_all)]
+pub async fn migration_status(pool: &PgPool) -> anyhow::Result<Vec<MigrationStatus>> {
+    let table_exists: bool =
+        sqlx::query_scalar("SELECT to_regclass('_sqlx_migrations') IS NOT NULL")
+            .fetch_one(pool)
+            .await?;
+    let mut applied = HashMap::new();
+    if table_exists {
+        let rows = sqlx::query(
+            "SELECT version, installed_on, success, checksum FROM _sqlx_migrations ORDER BY version",
+        )
+        .fetch_all(pool)
+        .await?;
+        for row in rows {
+            let version: i64 = row.get("version");
+            let installed_on: DateTime<Utc> = row.get("installed_on");
+            let success: bool = row.get("success");
+            let checksum: Vec<u8> = row.get("checksum");
+            applied.insert(version, (installed_on, success, checksum));
+        }
+    }
+
+    let mut statuses = Vec::new();
+    for migration in MIGRATOR
+        .iter()
+        .filter(|migration| migration.migration_type.is_up_migration())
+    {
+        let (state, installed_on) = match applied.get(&migration.version) {
+            None => (MigrationState::Pending, None),
+            Some((installed_on, false, _)) => (MigrationState::Failed, Some(*installed_on)),
+            Some((installed_on, true, checksum)) if *checksum != *migration.checksum => {
+                (MigrationState::Modified, Some(*installed_on))
+            }
+            Some((installed_on, true, _)) => (MigrationState::Applied, Some(*installed_on)),
+        };
+        statuses.push(MigrationStatus {
+            version: migration.version,
+            description: migration.description.to_string(),
+            state,
+            installed_on,
+            reversible: MIGRATOR.iter().any(|down| {
+                down.version == migration.version && down.migration_type.is_down_migration()
+            }),
+        });
+    }
+    Ok(statuses)
+}
+
+/// Indexes each migration creates, as `(version, index name)`, for checking that none went missing.
+pub fn migration_indexes() -> Vec<(i64, String)> {
+    const CREATE: &str = "CREATE INDEX IF NOT EXISTS ";
+    let mut indexes = Vec::new();
+    for migration in MIGRATOR
+        .iter()
+        .filter(|migration| migration.migration_type.is_up_migration())
+    {
+        for (start, _) in migration.sql.match_indices(CREATE) {
+            let name: String = migration.sql[start + CREATE.len()..]
+                .chars()
+                .take_while(|c| c.is_alphanumeric() || *c == '_')
+                .collect();
+            indexes.push((migration.version, name));
+        }
+    }
+    indexes
+}
+
+/// Names of the indexes that exist in the application schema.
+#[tracing::instrument(skip_all)]
+pub async fn existing_indexes(pool: &PgPool) -> anyhow::Result<Vec<String>> {
+    Ok(sqlx::query_scalar(
+        "SELECT indexname::text FROM pg_indexes WHERE schemaname = 'cohort_early_warning'",
+    )
+    .fetch_all(pool)
+    .await?)
+}
+
+/// A query behind scoring or reports, as Postgres currently plans it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryPlan {
+    pub query: &'static str,
+    /// Tables the plan reads in full, with their estimated row counts.
+    pub seq_scans: Vec<(String, i64)>,
+}
+
+/// Plans, without running, the queries behind scoring and reports over a 30-day window of
+/// `org_id`'s signals, both org-wide and for its largest cohort.
+#[tracing::instrument(skip_all)]
+pub async fn explain_core_queries(pool: &PgPool, org_id: Uuid) -> anyhow::Result<Vec<QueryPlan>> {
+    const EXPLAIN: &str = "EXPLAIN (FORMAT JSON) ";
+    let org = SignalFilter {
+        org_id: Some(org_id),
+        ..SignalFilter::new(risk::cutoff_date(30))
+    };
+    let largest_cohort: Option<String> = sqlx::query_scalar(
+        "SELECT cohort FROM cohort_early_warning.scholars \
+         WHERE org_id = $1 AND deleted_at IS NULL \
+         GROUP BY cohort ORDER BY COUNT(*) DESC, cohort LIMIT 1",
+    )
+    .bind(org_id)
+    .fetch_optional(pool)
+    .await?;
+
+    let mut queries = vec![
+        (
+            "signal stream",
+            signal_page_query(EXPLAIN, &org, None, DEFAULT_PAGE_SIZE),
+        ),
+        (
+            "signal aggregates",
+            type_aggregates_query(EXPLAIN, &org, Utc::now().date_naive()),
+        ),
+        ("weekly trends", weekly_trends_query(EXPLAIN, &org)),
+    ];
+    if let Some(cohort) = largest_cohort {
+        let cohort = SignalFilter {
+            cohorts: vec![cohort],
+            ..org.clone()
+        };
+        queries.push((
+            "cohort signal stream",
+            signal_page_query(EXPLAIN, &cohort, None, DEFAULT_PAGE_SIZE),
+        ));
+    }
+
+    let mut plans = Vec::new();
+    for (name, mut query) in queries {
+        let row = query.build().fetch_one(pool).await?;
+        plans.push((name, row));
+    }
+    let contacts = sqlx::query(&format!("{EXPLAIN}{LAST_CONTACTS}"))
+        .bind(org_id)
+        .fetch_one(pool)
+        .await?;
+    plans.push(("last contacts", contacts));
+
+    let mut explained = Vec::new();
+    for (query, row) in plans {
+        // EXPLAIN returns `json`, whose wire format is its text.
+        let plan: serde_json::Value =
+            serde_json::from_str(&row.try_get_unchecked::<String, _>(0)?)?;
+        let relations = seq_scan_relations(&plan);
+        let rows: HashMap<String, i64> = sqlx::query_as(
+            "SELECT c.relname::text, GREATEST(c.reltuples, COALESCE(st.n_live_tup, 0), 0)::bigint \
+             FROM pg_class c \
+             JOIN pg_namespace n ON n.oid = c.relnamespace \
+             LEFT JOIN pg_stat_user_tables st ON st.relid = c.oid \
+             WHERE n.nspname = 'cohort_early_warning' AND c.relname = ANY($1)",
+        )
+        .bind(&relations)
+        .fetch_all(pool)
+        .await?
+        .into_iter()
+        .collect();
+        explained.push(QueryPlan {
+            query,
+            seq_scans: relations
+                .into_iter()
+                .map(|relation| {
+                    let estimate = rows.get(&relation).copied().unwrap_or(0);
+                    (relation, estimate)
+                })
+                .collect(),
+        });
+    }
+    Ok(explained)
+}
+
+/// Tables a `FORMAT JSON` plan scans sequentially, each named once, in plan order.
+fn seq_scan_relations(plan: &serde_json::Value) -> Vec<String> {
+    fn walk(node: &serde_json::Value, found: &mut Vec<String>) {
+        if node["Node Type"] == "Seq Scan" {
+            if let Some(relation) = node["Relation Name"].as_str() {
+                if !found.iter().any(|seen| seen == relation) {
+                    found.push(relation.to_string());
+                }
+            }
+        }
+        for child in node["Plans"].as_array().into_iter().flatten() {
+            walk(child, found);
+        }
+    }
+    let mut found = Vec::new();
+    for statement in plan.as_array().into_iter().flatten() {
+        walk(&statement["Plan"], &mut found);
+    }
+    found
+}
+
+/// Row counts of the main tables, skipping any a pending migration has yet to create.
+#[tracing::instrument(skip_all)]
+pub async fn table_counts(pool: &PgPool) -> anyhow::Result<Vec<(&'static str, i64)>> {
+    let mut counts = Vec::new();
+    for table in [
+        "scholars",
+        "signals",
+        "signals_archive",
+        "interventions",
+        "outcomes",
+        "cohort_health",
+        "score_runs",
+        "audit_log",
+    ] {
+        let qualified = format!("cohort_early_warning.{table}");
+        let exists: bool = sqlx::query_scalar("SELECT to_regclass($1) IS NOT NULL")
+            .bind(&qualified)
+            .fetch_one(pool)
+            .await?;
+        if exists {
+            let count: i64 = sqlx::query_scalar(&format!("SELECT COUNT(*) FROM {qualified}"))
+                .fetch_one(pool)
+                .await?;
+            counts.push((table, count));
+        }
+    }
+    Ok(counts)
+}
+
+/// Refuses to touch a schema with a half-applied migration or edited migration files.
+async fn applied_migrations(conn: &mut PgConnection) -> anyhow::Result<HashMap<i64, Vec<u8>>> {
+    conn.ensure_migrations_table().await?;
+    if let Some(version) = conn.dirty_version().await? {
+        anyhow::bail!("migration {version} failed partway; repair the schema by hand first");
+    }
+
+    let mut applied = HashMap::new();
+    for migration in conn.list_applied_migrations().await? {
+        let known = MIGRATOR
+            .iter()
+            .find(|known| {
+                known.version == migration.version && known.migration_type.is_up_migration()
+            })
+            .with_context(|| {
+                format!(
+                    "migration {} is applied but missing from this build",
+                    migration.version
+                )
+            })?;
+        if *known.checksum != *migration.checksum {
+            anyhow::bail!(
+                "migration {} was edited after it was applied",
+                migration.version
+            );
+        }
+        applied.insert(migration.version, migration.checksum.into_owned());
+    }
+    Ok(applied)
+}
+
+/// Applies pending migrations up to and including `target` (all when `None`); returns their versions.
+#[tracing::instrument(skip_all)]
+pub async fn migrate_up(pool: &PgPool, target: Option<i64>) -> anyhow::Result<Vec<i64>> {
+    let mut conn = pool.acquire().await?;
+    conn.lock().await?;
+    let result = async {
+        let applied = applied_migrations(&mut conn).await?;
+        let mut versions = Vec::new();
+        for migration in MIGRATOR.iter().filter(|migration| {
+            migration.migration_type.is_up_migration()
+                && !applied.contains_key(&migration.version)
+                && target.is_none_or(|target| migration.version <= target)
+        }) {
+            conn.apply(migration).await?;
+            versions.push(migration.version);
+        }
+        Ok(versions)
+    }
+    .await;
+    conn.unlock().await?;
+    result
+}
+
+/// Reverts applied migrations newer than `target`, newest first; returns their versions.
+///
+/// Without a target only the most recent migration is reverted.
+#[tracing::instrument(skip_all)]
+pub async fn migrate_revert(pool: &PgPool, target: Option<i64>) -> anyhow::Result<Vec<i64>> {
+    let mut conn = pool.acquire().await?;
+    conn.lock().await?;
+    let result = async {
+        let applied = applied_migrations(&mut conn).await?;
+        let mut versions: Vec<i64> = applied.keys().copied().collect();
+        versions.sort_unstable_by(|a, b| b.cmp(a));
+        let target = match target {
+            Some(target) => target,
+            None => versions.get(1).copied().unwrap_or(0),
+        };
+
+        let mut reverted = Vec::new();
+        for version in versions.into_iter().filter(|version| *version > target) {
+            let down = MIGRATOR
+                .iter()
+                .find(|migration| {
+                    migration.version == version && migration.migration_type.is_down_migration()
+                })
+                .with_context(|| format!("migration {version} has no down script"))?;
+            conn.revert(down).await?;
+            reverted.push(version);
+        }
+        Ok(reverted)
+    }
+    .await;
+    conn.unlock().await?;
+    result
+}
+
+/// Organization created by the tenancy migration; pre-tenancy data belongs to it.
+pub const DEFAULT_ORG_ID: Uuid = Uuid::from_u128(1);
+
+#[tracing::instrument(skip_all)]
+pub async fn resolve_org(pool: &PgPool, slug: &str) -> anyhow::Result<Uuid> {
+    let row = sqlx::query("SELECT id FROM cohort_early_warning.organizations WHERE slug = $1")
+        .bind(slug)
+        .fetch_optional(pool)
+        .await?
+        .with_context(|| format!("unknown organization {slug}; create it with `org add`"))?;
+    Ok(row.get("id"))
+}
+
+#[tracing::instrument(skip_all)]
+pub async fn create_org(pool: &PgPool, slug: &str, name: &str) -> anyhow::Result<Uuid> {
+    let id = Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO cohort_early_warning.organizations (id, slug, name) VALUES ($1, $2, $3)",
+    )
+    .bind(id)
+    .bind(slug)
+    .bind(name)
+    .execute(pool)
+    .await?;
+    Ok(id)
+}
+
+#[tracing::instrument(skip_all)]
+pub async fn list_orgs(pool: &PgPool) -> anyhow::Result<Vec<Organization>> {
+    let rows = sqlx::query(
+        "SELECT id, slug, name, created_at FROM cohort_early_warning.organizations ORDER BY slug",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| Organization {
+            id: row.get("id"),
+            slug: row.get("slug"),
+            name: row.get("name"),
+            created_at: row.get("created_at"),
+        })
+        .collect())
+}
+
+/// How `upsert_advisor` treats the advisor's alert channel.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AdvisorChannel {
+    /// Leave it as it is (none for a new advisor).
+    Keep,
+    Set(String),
+    Clear,
+}
+
+/// Registers an advisor, updating the name (and channel, unless kept) if the email already exists.
+#[tracing::instrument(skip_all)]
+pub async fn upsert_advisor(
+    pool: &PgPool,
+    org_id: Uuid,
+    email: &str,
+    full_name: &str,
+    channel: &AdvisorChannel,
+) -> anyhow::Result<Uuid> {
+    let (keep, channel) = match channel {
+        AdvisorChannel::Keep => (true, None),
+        AdvisorChannel::Set(channel) => (false, Some(channel.as_str())),
+        AdvisorChannel::Clear => (false, None),
+    };
+    let row = sqlx::query(
+        "INSERT INTO cohort_early_warning.advisors (id, org_id, full_name, email, channel) \
+         VALUES ($1, $2, $3, $4, $5) \
+         ON CONFLICT (org_id, email) DO UPDATE SET full_name = EXCLUDED.full_name, \
+         channel = CASE WHEN $6 THEN advisors.channel ELSE EXCLUDED.channel END \
+         RETURNING id",
+    )
+    .bind(Uuid::new_v4())
+    .bind(org_id)
+    .bind(full_name)
+    .bind(email)
+    .bind(channel)
+    .bind(keep)
+    .fetch_one(pool)
+    .await?;
+    Ok(row.get("id"))
+}
+
+#[tracing::instrument(skip_all)]
+pub async fn list_advisors(pool: &PgPool, org_id: Uuid) -> anyhow::Result<Vec<Advisor>> {
+    let rows = sqlx::query(
+        "SELECT a.id, a.full_name, a.email, a.channel, \
+         COUNT(sc.id) FILTER (WHERE sc.deleted_at IS NULL) AS scholar_count \
+         FROM cohort_early_warning.advisors a \
+         LEFT JOIN cohort_early_warning.scholars sc ON sc.advisor_id = a.id \
+         WHERE a.org_id = $1 \
+         GROUP BY a.id ORDER BY a.email",
+    )
+    .bind(org_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| Advisor {
+            id: row.get("id"),
+            full_name: row.get("full_name"),
+            email: row.get("email"),
+            scholar_count: row.get("scholar_count"),
+            channel: row.get("channel"),
+        })
+        .collect())
+}
+
+/// Assigns a scholar to the advisor with `advisor_email`, or clears the assignment with `None`.
+#[tracing::instrument(skip_all)]
+pub async fn assign_advisor(
+    pool: &PgPool,
+    org_id: Uuid,
+    scholar_email: &str,
+    advisor_email: Option<&str>,
+) -> anyhow::Result<()> {
+    let advisor_id: Option<Uuid> = match advisor_email {
+        Some(email) => Some(
+            sqlx::query(
+                "SELECT id FROM cohort_early_warning.advisors WHERE org_id = $1 AND email = $2",
+            )
+            .bind(org_id)
+            .bind(email)
+            .fetch_optional(pool)
+            .await?
+            .with_context(|| format!("no advisor found with email {email}"))?
+            .get("id"),
+        ),
+        None => None,
+    };
+
+    let result = sqlx::query(
+        "UPDATE cohort_early_warning.scholars SET advisor_id = $1 \
+         WHERE org_id = $2 AND email = $3 AND deleted_at IS NULL",
+    )
+    .bind(advisor_id)
+    .bind(org_id)
+    .bind(scholar_email)
+    .execute(pool)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        anyhow::bail!("no scholar found with email {scholar_email}");
+    }
+    Ok(())
+}
+
+/// Upserts the demo roster and returns how many seed signals were newly inserted.
+#[tracing::instrument(skip_all)]
+pub async fn seed(pool: &PgPool, org_id: Uuid) -> anyhow::Result<u64> {
+    let scholars = vec![
+        (
+            Uuid::parse_str("3d7f5d6f-24f7-4e8e-8b4b-3e7e44b4a7b2")?,
+            "Avery Lee",
+            "avery.lee@groupscholar.com",
+            "2026",
+        ),
+        (
+            Uuid::parse_str("0c22f1f1-9184-4fd4-9b21-28c68a6a89dc")?,
+            "Jules Moreno",
+            "jules.moreno@groupscholar.com",
+            "2025",
+        ),
+        (
+            Uuid::parse_str("d5a0a1a2-2a3c-44c2-8f73-60b7897a9dd2")?,
+            "Kiara Patel",
+            "kiara.patel@groupscholar.com",
+            "2026",
+        ),
+    ];
+
+    for (id, name, email, cohort) in scholars {
+        // The fixed ids belong to the default organization's roster.
+        let id = if org_id == DEFAULT_ORG_ID {
+            id
+        } else {
+            Uuid::new_v4()
+        };
+        sqlx::query(
+            r#"
+            INSERT INTO cohort_early_warning.scholars (id, full_name, email, cohort, org_id)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (org_id, email) DO UPDATE
+            SET full_name = EXCLUDED.full_name, cohort = EXCLUDED.cohort
+            RETURNING id
+            "#,
+        )
+        .bind(id)
+        .bind(name)
+        .bind(email)
+        .bind(cohort)
+        .bind(org_id)
+        .fetch_one(pool)
+        .await?;
+    }
+
+    let signals = vec![
+        (
+            "seed-001",
+            "avery.lee@groupscholar.com",
+            "attendance",
+            3,
+            "Missed last two sessions",
+            NaiveDate::from_ymd_opt(2026, 2, 2).context("invalid date")?,
+        ),
+        (
+            "seed-002",
+            "jules.moreno@groupscholar.com",
+            "engagement",
+            2,
+            "Slow response to outreach",
+            NaiveDate::from_ymd_opt(2026, 1, 30).context("invalid date")?,
+        ),
+        (
+            "seed-003",
+            "kiara.patel@groupscholar.com",
+            "academic",
+            4,
+            "Reported GPA dip",
+            NaiveDate::from_ymd_opt(2026, 1, 28).context("invalid date")?,
+        ),
+    ];
+
+    let mut inserted = 0;
+    for (source_key, email, signal_type, severity, note, occurred_at) in signals {
+        let scholar_id: Uuid = sqlx::query(
+            "SELECT id FROM cohort_early_warning.scholars WHERE org_id = $1 AND email = $2",
+        )
+        .bind(org_id)
+        .bind(email)
+        .fetch_one(pool)
+        .await?
+        .get("id");
+
+        inserted += sqlx::query(
+            r#"
+            INSERT INTO cohort_early_warning.signals
+            (id, scholar_id, signal_type, severity, note, occurred_at, source_key, org_id)
+            SELECT $1, $2, $3, $4, $5, $6, $7, $8
+            WHERE NOT EXISTS (
+                SELECT 1 FROM cohort_early_warning.signals_archive
+                WHERE org_id = $8 AND source_key = $7
+            )
+            ON CONFLICT (org_id, source_key) DO NOTHING
+            "#,
+        )
+        .bind(Uuid::new_v4())
+        .bind(scholar_id)
+        .bind(signal_type)
+        .bind(severity)
+        .bind(notes::seal(note))
+        .bind(occurred_at)
+        .bind(source_key)
+        .bind(org_id)
+        .execute(pool)
+        .await?
+        .rows_affected();
+    }
+
+    Ok(inserted)
+}
+
+/// Inserts a generated roster and its signals as one import batch, so `import undo` can remove
+/// the signals again. Scholars are upserted by email, and signals whose source key is already
+/// present are skipped. Returns the batch id and the number of signals inserted.
+#[tracing::instrument(skip_all)]
+pub async fn seed_synthetic(
+    pool: &PgPool,
+    org_id: Uuid,
+    scholars: &[crate::synthetic::SyntheticScholar],
+    source: &str,
+) -> anyhow::Result<(Uuid, u64)> {
+    for scholar in scholars {
+        sqlx::query(
+            "INSERT INTO cohort_early_warning.scholars (id, full_name, email, cohort, org_id) \
+             VALUES ($1, $2, $3, $4, $5) \
+             ON CONFLICT (org_id, email) DO UPDATE \
+             SET full_name = EXCLUDED.full_name, cohort = EXCLUDED.cohort",
+        )
+        .bind(Uuid::new_v4())
+        .bind(&scholar.full_name)
+        .bind(&scholar.email)
+        .bind(&scholar.cohort)
+        .bind(org_id)
+        .execute(pool)
+        .await?;
+    }
+
+    let batch_id = create_import_batch(pool, org_id, source).await?;
+    let mut inserted = 0;
+    for row in scholars.iter().flat_map(|scholar| &scholar.signals) {
+        if insert_signal_row(pool, org_id, row, batch_id).await? {
+            inserted += 1;
+        }
+    }
+    Ok((batch_id, inserted))
+}
+
+/// Scope shared by the signal-level queries behind Score and Report.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct SignalFilter {
+    /// `None` spans every organization; the CLI always sets it.
+    pub org_id: Option<Uuid>,
+    pub since_date: NaiveDate,
+    /// Keep only scholars in any of these cohorts (aliases included); empty means every cohort.
+    pub cohorts: Vec<String>,
+    /// Keep only scholars with any of these emails; empty means every scholar.
+    pub emails: Vec<String>,
+    /// Limit to scholars assigned to the advisor with this email.
+    pub advisor: Option<String>,
+    pub include_inactive: bool,
+    /// Keep only signals carrying at least one of these tags; empty means no tag filter.
+    pub tags: Vec<String>,
+    /// Keep only signals of these types, matched case-insensitively; empty means every type.
+    pub signal_types: Vec<String>,
+    /// Drop signals of these types, matched case-insensitively.
+    pub exclude_signal_types: Vec<String>,
+    /// Keep only signals at least this severe.
+    pub min_severity: Option<i32>,
+    /// Keep only signals whose notes `enrich` tagged with at least one of these categories.
+    pub note_categories: Vec<String>,
+    /// Keep only signals whose note sentiment is at or below this; unenriched signals drop out.
+    pub max_note_sentiment: Option<f64>,
+    /// Read archived signals too; normal scoring and reporting leave them out.
+    pub include_archived: bool,
+    /// Keep only signals logged (not occurred) after this instant.
+    pub created_after: Option<DateTime<Utc>>,
+    /// Keep only signals that occurred on or before this date, and score as of it rather than
+    /// today, so a fixed date range scores the same whenever it is rerun.
+    pub until_date: Option<NaiveDate>,
+}
+
+impl SignalFilter {
+    pub fn new(since_date: NaiveDate) -> Self {
+        Self {
+            org_id: None,
+            since_date,
+            cohorts: Vec::new(),
+            emails: Vec::new(),
+            advisor: None,
+            include_inactive: false,
+            tags: Vec::new(),
+            signal_types: Vec::new(),
+            exclude_signal_types: Vec::new(),
+            min_severity: None,
+            note_categories: Vec::new(),
+            max_note_sentiment: None,
+            include_archived: false,
+            created_after: None,
+            until_date: None,
+        }
+    }
+
+    /// Whether the filter narrows to particular cohorts, scholars, or an advisor's caseload, as
+    /// opposed to everyone percentiles are ranked against.
+    pub fn is_scoped(&self) -> bool {
+        !self.cohorts.is_empty() || !self.emails.is_empty() || self.advisor.is_some()
+    }
+
+    /// The filter without its cohort, email, and advisor scope.
+    pub fn population(&self) -> Self {
+        Self {
+            cohorts: Vec::new(),
+            emails: Vec::new(),
+            advisor: None,
+            ..self.clone()
+        }
+    }
+
+    /// The day scores are computed as of: `until_date` when set, else today.
+    pub fn as_of(&self) -> NaiveDate {
+        self.until_date.unwrap_or_else(|| Utc::now().date_naive())
+    }
+
+    fn signals_table(&self) -> &'static str {
+        if self.include_archived {
+            "cohort_early_warning.signals_with_archive"
+        } else {
+            "cohort_early_warning.signals"
+        }
+    }
+
+    fn push_conditions(&self, query: &mut QueryBuilder<'_, Postgres>) {
+        query.push(" WHERE s.deleted_at IS NULL AND sc.deleted_at IS NULL");
+        query.push(" AND s.occurred_at >= ");
+        query.push_bind(self.since_date);
+        if let Some(until_date) = self.until_date {
+            query.push(" AND s.occurred_at <= ");
+            query.push_bind(until_date);
+        }
+
+        if let Some(org_id) = self.org_id {
+            query.push(" AND sc.org_id = ");
+            query.push_bind(org_id);
+        }
+
+        if !self.cohorts.is_empty() {
+            query.push(" AND sc.cohort = ANY(ARRAY(SELECT cohort_early_warning.canonical_cohort(");
+            match self.org_id {
+                Some(org_id) => query.push_bind(org_id),
+                None => query.push("sc.org_id"),
+            };
+            query.push(", c) FROM UNNEST(");
+            query.push_bind(self.cohorts.clone());
+            query.push("::text[]) AS c))");
+        }
+
+        if !self.emails.is_empty() {
+            query.push(" AND sc.email = ANY(");
+            query.push_bind(self.emails.clone());
+            query.push(")");
+        }
+
+        if let Some(advisor) = &self.advisor {
+            query.push(
+                " AND sc.advisor_id IN \
+                 (SELECT a.id FROM cohort_early_warning.advisors a WHERE a.email = ",
+            );
+            query.push_bind(advisor.clone());
+            query.push(")");
+        }
+
+        if !self.include_inactive {
+            query.push(" AND sc.status = 'active'");
+        }
+
+        if !self.tags.is_empty() {
+            query.push(" AND s.tags && ");
+            query.push_bind(self.tags.clone());
+        }
+
+        if !self.signal_types.is_empty() {
+            query.push(" AND lower(s.signal_type) = ANY(");
+            query.push_bind(lowercase(&self.signal_types));
+            query.push(")");
+        }
+
+        if !self.exclude_signal_types.is_empty() {
+            query.push(" AND lower(s.signal_type) <> ALL(");
+            query.push_bind(lowercase(&self.exclude_signal_types));
+            query.push(")");
+        }
+
+        if let Some(min_severity) = self.min_severity {
+            query.push(" AND s.severity >= ");
+            query.push_bind(min_severity);
+        }
+
+        if !self.note_categories.is_empty() {
+            query.push(" AND s.note_categories && ");
+            query.push_bind(lowercase(&self.note_categories));
+        }
+
+        if let Some(max_sentiment) = self.max_note_sentiment {
+            query.push(" AND s.note_sentiment <= ");
+            query.push_bind(max_sentiment);
+        }
+
+        if let Some(created_after) = self.created_after {
+            // The archive view has no created_at, and archived signals are never new anyway.
+            query.push(
+                " AND s.id IN (SELECT n.id FROM cohort_early_warning.signals n \
+                 WHERE n.created_at > ",
+            );
+            query.push_bind(created_after);
+            query.push(")");
+        }
+    }
+}
+
+fn lowercase(names: &[String]) -> Vec<String> {
+    names
+        .iter()
+        .map(|name| name.trim().to_lowercase())
+        .collect()
+}
+
+/// Rows fetched per round trip when the caller doesn't choose a page size.
+pub const DEFAULT_PAGE_SIZE: i64 = 5_000;
+
+/// Position after the last row of a page, ordered by `(occurred_at, id)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SignalCursor {
+    pub occurred_at: NaiveDate,
+    pub signal_id: Uuid,
+}
+
+fn attachments_from_arrays(
+    urls: Vec<String>,
+    labels: Vec<String>,
+    kinds: Vec<String>,
+) -> Vec<Attachment> {
+    urls.into_iter()
+        .zip(labels)
+        .zip(kinds)
+        .map(|((url, label), kind)| Attachment { url, label, kind })
+        .collect()
+}
+
+/// Fetches every signal in scope, one keyset page of `page_size` rows at a time so no single
+/// query holds a long-running snapshot.
+#[tracing::instrument(skip_all)]
+pub async fn fetch_signals(
+    pool: &PgPool,
+    filter: &SignalFilter,
+    page_size: i64,
+) -> anyhow::Result<Vec<SignalRecord>> {
+    let mut signals = Vec::new();
+    for_each_signal(pool, filter, page_size, |signal| signals.push(signal)).await?;
+    Ok(signals)
+}
+
+/// Streams every signal in scope to `visit` without collecting them, reading keyset pages of
+/// `page_size` rows. Returns the number of signals visited.
+#[tracing::instrument(skip_all)]
+pub async fn for_each_signal<F>(
+    pool: &PgPool,
+    filter: &SignalFilter,
+    page_size: i64,
+    mut visit: F,
+) -> anyhow::Result<u64>
+where
+    F: FnMut(SignalRecord),
+{
+    anyhow::ensure!(page_size > 0, "page size must be positive");
+    let mut after = None;
+    let mut visited = 0;
+
+    loop {
+        let mut query = signal_page_query("", filter, after, page_size);
+        let mut rows = query.build().fetch(pool);
+        let mut page_rows = 0;
+        while let Some(row) = rows.try_next().await? {
+            page_rows += 1;
+            let signal = SignalRecord {
+                scholar_id: row.get("scholar_id"),
+                scholar_name: row.get("full_name"),
+                scholar_email: row.get("email"),
+                cohort: row.get("cohort"),
+                signal_type: row.get("signal_type"),
+                severity: row.get("severity"),
+                occurred_at: row.get("occurred_at"),
+                note: notes::open(row.get("note"))?,
+                advisor: row.get("advisor_name"),
+                submitted_by: row.get("submitted_by"),
+                attachments: attachments_from_arrays(
+                    row.get("attachment_urls"),
+                    row.get("attachment_labels"),
+                    row.get("attachment_kinds"),
+                ),
+                note_categories: row.get("note_categories"),
+                note_sentiment: row.get("note_sentiment"),
+            };
+            after = Some(SignalCursor {
+                occurred_at: signal.occurred_at,
+                signal_id: row.get("signal_id"),
+            });
+            visit(signal);
+        }
+        visited += page_rows;
+
+        // A short page means there is nothing after it.
+        if page_rows < page_size as u64 {
+            return Ok(visited);
+        }
+    }
+}
+
+/// One page of [`for_each_signal`], after `head` (empty, or an `EXPLAIN`).
+fn signal_page_query(
+    head: &str,
+    filter: &SignalFilter,
+    after: Option<SignalCursor>,
+    page_size: i64,
+) -> QueryBuilder<'static, Postgres> {
+    let mut query = QueryBuilder::new(format!(
+        "{head}SELECT s.id AS signal_id, sc.id as scholar_id, sc.full_name, sc.email, sc.cohort, \
+         s.signal_type, s.severity, s.note, s.occurred_at, s.submitted_by, \
+         s.note_categories, s.note_sentiment, adv.full_name AS advisor_name, \
+         ARRAY(SELECT a.url FROM cohort_early_warning.signal_attachments a \
+               WHERE a.signal_id = s.id ORDER BY a.created_at, a.id) AS attachment_urls, \
+         ARRAY(SELECT a.label FROM cohort_early_warning.signal_attachments a \
+               WHERE a.signal_id = s.id ORDER BY a.created_at, a.id) AS attachment_labels, \
+         ARRAY(SELECT a.kind FROM cohort_early_warning.signal_attachments a \
+               WHERE a.signal_id = s.id ORDER BY a.created_at, a.id) AS attachment_kinds \
+         FROM {} s \
+         JOIN cohort_early_warning.scholars sc ON sc.id = s.scholar_id \
+         LEFT JOIN cohort_early_warning.advisors adv ON adv.id = sc.advisor_id",
+        filter.signals_table()
+    ));
+    filter.push_conditions(&mut query);
+    push_page(&mut query, after, page_size);
+    query
+}
+
+/// Streams every signal in scope joined with its scholar, for `export`. Pages like
+/// [`for_each_signal`]; stops at the first error from `visit`.
+#[tracing::instrument(skip_all)]
+pub async fn for_each_export_row<F>(
+    pool: &PgPool,
+    filter: &SignalFilter,
+    page_size: i64,
+    mut visit: F,
+) -> anyhow::Result<u64>
+where
+    F: FnMut(ExportRow) -> anyhow::Result<()>,
+{
+    anyhow::ensure!(page_size > 0, "page size must be positive");
+    let mut after = None;
+    let mut visited = 0;
+
+    loop {
+        let mut query = QueryBuilder::new(format!(
+            "SELECT s.id AS signal_id, sc.full_name, sc.email, sc.cohort, sc.status, \
+             s.signal_type, s.severity, s.note, s.occurred_at, s.source_key, s.tags, \
+             s.submitted_by, adv.full_name AS advisor_name, \
+             ARRAY(SELECT a.url FROM cohort_early_warning.signal_attachments a \
+                   WHERE a.signal_id = s.id ORDER BY a.created_at, a.id) AS attachment_urls, \
+             ARRAY(SELECT a.label FROM cohort_early_warning.signal_attachments a \
+                   WHERE a.signal_id = s.id ORDER BY a.created_at, a.id) AS attachment_labels, \
+             ARRAY(SELECT a.kind FROM cohort_early_warning.signal_attachments a \
+                   WHERE a.signal_id = s.id ORDER BY a.created_at, a.id) AS attachment_kinds \
+             FROM {} s \
+             JOIN cohort_early_warning.scholars sc ON sc.id = s.scholar_id \
+             LEFT JOIN cohort_early_warning.advisors adv ON adv.id = sc.advisor_id",
+            filter.signals_table()
+        ));
+        filter.push_conditions(&mut query);
+        push_page(&mut query, after, page_size);
+
+        let mut rows = query.build().fetch(pool);
+        let mut page_rows = 0;
+        while let Some(row) = rows.try_next().await? {
+            page_rows += 1;
+            let export = ExportRow {
+                full_name: row.get("full_name"),
+                email: row.get("email"),
+                cohort: row.get("cohort"),
+                signal_type: row.get("signal_type"),
+                severity: row.get("severity"),
+                note: notes::open(row.get("note"))?,
+                occurred_at: row.get("occurred_at"),
+                source_key: row.get("source_key"),
+                tags: row.get("tags"),
+                submitted_by: row.get("submitted_by"),
+                attachments: attachments_from_arrays(
+                    row.get("attachment_urls"),
+                    row.get("attachment_labels"),
+                    row.get("attachment_kinds"),
+                ),
+                status: row.get("status"),
+                advisor: row.get("advisor_name"),
+            };
+            after = Some(SignalCursor {
+                occurred_at: export.occurred_at,
+                signal_id: row.get("signal_id"),
+            });
+            visit(export)?;
+        }
+        visited += page_rows;
+
+        if page_rows < page_size as u64 {
+            return Ok(visited);
+        }
+    }
+}
+
+/// Scores every scholar in scope, folding signals into a [`ScoreAccumulator`] as they stream in
+/// so memory stays proportional to scholars, not signals.
+///
+/// Percentiles rank against every scholar the filter would cover without its cohort, email, and
+/// advisor scope, which costs a second pass when one of those is set.
+#[tracing::instrument(skip_all)]
+pub async fn score_scope(
+    pool: &PgPool,
+    filter: &SignalFilter,
+    since_days: i64,
+    scoring: &ScoringConfig,
+    page_size: i64,
+) -> anyhow::Result<Vec<ScholarScore>> {
+    let started = Instant::now();
+    let mut accumulator = ScoreAccumulator::new(since_days, filter.as_of(), scoring);
+    for_each_signal(pool, filter, page_size, |signal| accumulator.push(&signal)).await?;
+    let mut scores = accumulator.finish();
+    let score_ms = started.elapsed().as_millis() as u64;
+
+    let started = Instant::now();
+    if filter.is_scoped() {
+        let population = filter.population();
+        let mut accumulator = ScoreAccumulator::new(since_days, filter.as_of(), scoring);
+        for_each_signal(pool, &population, page_size, |signal| {
+            accumulator.push(&signal)
+        })
+        .await?;
+        risk::assign_percentiles(&mut scores, &accumulator.finish());
+    }
+    let percentile_ms = started.elapsed().as_millis() as u64;
+
+    let started = Instant::now();
+    if let Some(org_id) = filter.org_id {
+        let contacts = last_contacts(pool, org_id).await?;
+        for score in &mut scores {
+            score.last_contact = contacts.get(&score.scholar_email).copied();
+        }
+    }
+    tracing::info!(
+        scholars = scores.len(),
+        since_days,
+        score_ms,
+        percentile_ms,
+        contacts_ms = started.elapsed().as_millis() as u64,
+        "scored scope"
+    );
+    Ok(scores)
+}
+
+/// Sums each scholar's signals of each type in the filter scope inside the database, weighting
+/// severities by [`risk::recency_weight`] as of `today`, so one row comes back per scholar and
+/// type rather than one per signal.
+#[tracing::instrument(skip_all)]
+pub async fn type_aggregates(
+    pool: &PgPool,
+    filter: &SignalFilter,
+    today: NaiveDate,
+) -> anyhow::Result<Vec<TypeAggregate>> {
+    let rows = type_aggregates_query("", filter, today)
+        .build()
+        .fetch_all(pool)
+        .await?;
+    Ok(rows
+        .into_iter()
+        .map(|row| TypeAggregate {
+            scholar_id: row.get("scholar_id"),
+            scholar_name: row.get("full_name"),
+            scholar_email: row.get("email"),
+            cohort: row.get("cohort"),
+            advisor: row.get("advisor_name"),
+            signal_type: row.get("signal_type"),
+            weighted_severity: row.get("weighted_severity"),
+            signal_count: row.get::<i64, _>("signal_count") as usize,
+            max_severity: row.get("max_severity"),
+            last_signal: row.get("last_signal"),
+        })
+        .collect())
+}
+
+fn type_aggregates_query(
+    head: &str,
+    filter: &SignalFilter,
+    today: NaiveDate,
+) -> QueryBuilder<'static, Postgres> {
+    let mut query = QueryBuilder::new(head);
+    push_type_aggregates(&mut query, filter, today);
+    query.push(" ORDER BY sc.id, s.signal_type");
+    query
+}
+
+fn push_type_aggregates(
+    query: &mut QueryBuilder<'_, Postgres>,
+    filter: &SignalFilter,
+    today: NaiveDate,
+) {
+    let weight = risk::recency_weight_sql(&format!("(DATE '{today}' - s.occurred_at)"));
+    query.push(format!(
+        "SELECT sc.id AS scholar_id, sc.full_name, sc.email, sc.cohort, \
+         adv.full_name AS advisor_name, s.signal_type, \
+         SUM(s.severity * {weight})::float8 AS weighted_severity, \
+         COUNT(*) AS signal_count, MAX(s.severity) AS max_severity, \
+         MAX(s.occurred_at) AS last_signal \
+         FROM {} s \
+         JOIN cohort_early_warning.scholars sc ON sc.id = s.scholar_id \
+         LEFT JOIN cohort_early_warning.advisors adv ON adv.id = sc.advisor_id",
+        filter.signals_table()
+    ));
+    filter.push_conditions(query);
+    query.push(" GROUP BY sc.id, sc.full_name, sc.email, sc.cohort, adv.full_name, s.signal_type");
+}
+
+/// [`score_scope`] with the weighting done by [`type_aggregates`], for scopes with too many
+/// signals to stream. Fails when the scoring config collapses staff duplicates or weighs notes.
+#[tracing::instrument(skip_all)]
+pub async fn aggregate_scope(
+    pool: &PgPool,
+    filter: &SignalFilter,
+    scoring: &ScoringConfig,
+) -> anyhow::Result<Vec<ScholarScore>> {
+    let today = filter.as_of();
+    let mut scores = risk::score_aggregates(&type_aggregates(pool, filter, today).await?, scoring)?;
+
+    if filter.is_scoped() {
+        let population = filter.population();
+        let aggregates = type_aggregates(pool, &population, today).await?;
+        risk::assign_percentiles(&mut scores, &risk::score_aggregates(&aggregates, scoring)?);
+    }
+
+    if let Some(org_id) = filter.org_id {
+        let contacts = last_contacts(pool, org_id).await?;
+        for score in &mut scores {
+            score.last_contact = contacts.get(&score.scholar_email).copied();
+        }
+    }
+    Ok(scores)
+}
+
+/// The top `limit` scholars of [`aggregate_scope`], ranked, cut, and given percentiles inside the
+/// database, so only they come back. Fails when the scoring config collapses staff duplicates
+/// or weighs notes.
+#[tracing::instrument(skip_all)]
+pub async fn top_scores(
+    pool: &PgPool,
+    filter: &SignalFilter,
+    scoring: &ScoringConfig,
+    limit: i64,
+) -> anyhow::Result<Vec<ScholarScore>> {
+    if scoring.staff_dedup_days.is_some() {
+        anyhow::bail!("staff_dedup_days needs every signal; score without ranking in the database");
+    }
+    if scoring.notes.is_active() {
+        anyhow::bail!("[scoring.notes] needs every signal; score without ranking in the database");
+    }
+    const TIERS: [Tier; 4] = [Tier::Low, Tier::Moderate, Tier::High, Tier::Critical];
+    let today = filter.as_of();
+    // Percentiles rank against everyone the filter covers without its scope, as in score_scope.
+    let population = filter.population();
+
+    let mut query = QueryBuilder::new("WITH per_type AS (");
+    push_type_aggregates(&mut query, &population, today);
+    query.push(
+        "), escalated AS ( \
+         SELECT p.scholar_id, e.tier, p.signal_type, p.max_severity, \
+         ROW_NUMBER() OVER (PARTITION BY p.scholar_id ORDER BY e.tier DESC, p.signal_type) AS pick \
+         FROM per_type p \
+         JOIN UNNEST(",
+    );
+    query.push_bind(
+        scoring
+            .escalations
+            .iter()
+            .map(|e| e.signal_type.clone())
+            .collect::<Vec<_>>(),
+    );
+    query.push("::text[], ");
+    query.push_bind(
+        scoring
+            .escalations
+            .iter()
+            .map(|e| e.min_severity)
+            .collect::<Vec<_>>(),
+    );
+    query.push("::int[], ");
+    query.push_bind(
+        scoring
+            .escalations
+            .iter()
+            .map(|e| e.tier as i32)
+            .collect::<Vec<_>>(),
+    );
+    query.push(
+        "::int[]) AS e(signal_type, min_severity, tier) \
+         ON e.signal_type = p.signal_type AND p.max_severity >= e.min_severity \
+         ), scored AS ( \
+         SELECT scholar_id, full_name, email, cohort, advisor_name, \
+         SUM(weighted_severity::numeric)::float8 AS score, SUM(signal_count)::int8 AS signal_count, \
+         MAX(last_signal) AS last_signal \
+         FROM per_type GROUP BY scholar_id, full_name, email, cohort, advisor_name \
+         ), ranked AS ( \
+         SELECT sd.*, e.signal_type AS escalated_type, e.max_severity AS escalated_severity, \
+         COALESCE(e.tier, 0) AS escalation_tier, \
+         CASE WHEN sd.score >= ",
+    );
+    query.push_bind(scoring.tiers.critical);
+    query.push(" THEN 3 WHEN sd.score >= ");
+    query.push_bind(scoring.tiers.high);
+    query.push(" THEN 2 WHEN sd.score >= ");
+    query.push_bind(scoring.tiers.moderate);
+    query.push(
+        " THEN 1 ELSE 0 END AS score_tier, \
+         (cume_dist() OVER (ORDER BY sd.score) * 100)::float8 AS percentile, \
+         (cume_dist() OVER (PARTITION BY sd.cohort ORDER BY sd.score) * 100)::float8 \
+         AS cohort_percentile \
+         FROM scored sd \
+         LEFT JOIN escalated e ON e.scholar_id = sd.scholar_id AND e.pick = 1 \
+         ) \
+         SELECT *, GREATEST(score_tier, escalation_tier) AS tier FROM ranked",
+    );
+    if filter.is_scoped() {
+        query.push(format!(
+            " WHERE scholar_id IN (SELECT s.scholar_id FROM {} s \
+             JOIN cohort_early_warning.scholars sc ON sc.id = s.scholar_id",
+            filter.signals_table()
+        ));
+        filter.push_conditions(&mut query);
+        query.push(")");
+    }
+    query.push(" ORDER BY tier DESC, score DESC, email LIMIT ");
+    query.push_bind(limit);
+
+    let rows = query.build().fetch_all(pool).await?;
+    let contacts = match filter.org_id {
+        Some(org_id) => last_contacts(pool, org_id).await?,
+        None => HashMap::new(),
+    };
+    Ok(rows
+        .into_iter()
+        .map(|row| {
+            let tier: i32 = row.get("tier");
+            let escalated = row.get::<i32, _>("escalation_tier") > row.get("score_tier");
+            let email: String = row.get("email");
+            ScholarScore {
+                scholar_name: row.get("full_name"),
+                last_contact: contacts.get(&email).copied(),
+                scholar_email: email,
+                cohort: row.get("cohort"),
+                score: row.get("score"),
+                signal_count: row.get::<i64, _>("signal_count") as usize,
+                advisor: row.get("advisor_name"),
+                tier: TIERS[tier as usize],
+                escalated_by: escalated.then(|| {
+                    format!(
+                        "{} severity {}",
+                        row.get::<String, _>("escalated_type"),
+                        row.get::<i32, _>("escalated_severity")
+                    )
+                }),
+                collapsed_signals: 0,
+                percentile: row.get("percentile"),
+                cohort_percentile: row.get("cohort_percentile"),
+                last_signal: row.get("last_signal"),
+            }
+        })
+        .collect())
+}
+
+/// Scores each of `windows` (in days) over the filter scope in one pass, reading signals back to
+/// the longest of them. Keyed by scholar email, as from [`MultiWindowAccumulator::finish`].
+#[tracing::instrument(skip_all)]
+pub async fn score_windows(
+    pool: &PgPool,
+    filter: &SignalFilter,
+    windows: &[i64],
+    scoring: &ScoringConfig,
+    page_size: i64,
+) -> anyhow::Result<HashMap<String, Vec<WindowScore>>> {
+    let longest = windows.iter().copied().max().unwrap_or(1);
+    let today = filter.as_of();
+    let filter = SignalFilter {
+        since_date: today - chrono::Duration::days(longest.max(1)),
+        ..filter.clone()
+    };
+    let mut accumulator = MultiWindowAccumulator::new(windows, today, scoring);
+    for_each_signal(pool, &filter, page_size, |signal| accumulator.push(&signal)).await?;
+    Ok(accumulator.finish())
+}
+
+/// Value of the counter that moves on with every write to a table scores and reports read.
+#[tracing::instrument(skip_all)]
+pub async fn data_version(pool: &PgPool) -> anyhow::Result<i64> {
+    Ok(
+        sqlx::query_scalar("SELECT last_value FROM cohort_early_warning.data_version")
+            .fetch_one(pool)
+            .await?,
+    )
+}
+
+/// What `score` and `GET /scores` cache scores under, so the CLI and the API share entries.
+#[derive(Debug, Clone, Copy, serde::Serialize)]
+pub struct ScoreScope<'a> {
+    pub filter: &'a SignalFilter,
+    pub since_days: i64,
+    pub scoring: &'a ScoringConfig,
+}
+
+/// Key of the `kind` of result computed for `scope` on `date`: a SHA-256 digest of the scope and
+/// date as JSON, so any process and build computing the same scope lands on the same entry.
+pub fn cache_key(
+    kind: &str,
+    scope: &impl serde::Serialize,
+    date: NaiveDate,
+) -> anyhow::Result<String> {
+    let digest = Sha256::digest(serde_json::to_vec(&(scope, date))?);
+    let hex: String = digest.iter().map(|byte| format!("{byte:02x}")).collect();
+    Ok(format!("{kind}:{hex}"))
+}
+
+/// Key of a cache entry for `scope`, which also records whether a note key is installed: a process
+/// without the key never looks up entries written by one that had it.
+fn entry_key(
+    kind: &str,
+    scope: &impl serde::Serialize,
+    date: NaiveDate,
+    note_key: Option<&notes::NoteKey>,
+) -> anyhow::Result<String> {
+    cache_key(kind, &(scope, note_key.is_some()), date)
+}
+
+/// `value` as a cache entry body: JSON, sealed under `note_key` when there is one.
+fn entry_body(
+    value: &impl serde::Serialize,
+    note_key: Option<&notes::NoteKey>,
+) -> anyhow::Result<String> {
+    let body = serde_json::to_string(value)?;
+    Ok(match note_key {
+        Some(key) => key.seal(&body),
+        None => body,
+    })
+}
+
+/// The value a cache entry `body` holds, or `None` when it does not open under `note_key` or no
+/// longer parses.
+fn entry_value<T: serde::de::DeserializeOwned>(
+    body: String,
+    note_key: Option<&notes::NoteKey>,
+) -> Option<T> {
+    let body = match note_key {
+        Some(key) => key.open(body).ok()?,
+        None if notes::is_sealed(&body) => return None,
+        None => body,
+    };
+    serde_json::from_str(&body).ok()
+}
+
+/// Returns the `kind` of result (say `"scores"`) computed for `scope` within `ttl` if no data has
+/// changed since, or else runs `compute` and caches its result. Entries are for today only, as
+/// recency weights move with the date, and are sealed under the note key when one is installed,
+/// as reports carry decrypted notes. A zero `ttl` turns caching off.
+#[tracing::instrument(skip_all)]
+pub async fn cached<T, F>(
+    pool: &PgPool,
+    org_id: Uuid,
+    ttl: Duration,
+    kind: &str,
+    scope: &impl serde::Serialize,
+    compute: F,
+) -> anyhow::Result<T>
+where
+    T: serde::Serialize + serde::de::DeserializeOwned,
+    F: std::future::Future<Output = anyhow::Result<T>>,
+{
+    if ttl.is_zero() {
+        return compute.await;
+    }
+    let note_key = notes::key();
+    let key = entry_key(kind, scope, Utc::now().date_naive(), note_key)?;
+    // Read before computing, so a write that lands meanwhile leaves the new entry already stale.
+    let version = data_version(pool).await?;
+    let ttl_secs = ttl.as_secs_f64();
+
+    let hit: Option<String> = sqlx::query_scalar(
+        "SELECT body FROM cohort_early_warning.score_cache \
+         WHERE org_id = $1 AND cache_key = $2 AND data_version = $3 \
+           AND computed_at > NOW() - make_interval(secs => $4)",
+    )
+    .bind(org_id)
+    .bind(&key)
+    .bind(version)
+    .bind(ttl_secs)
+    .fetch_optional(pool)
+    .await?;
+    // An entry written by an older build may no longer parse; recompute over it.
+    if let Some(value) = hit.and_then(|body| entry_value(body, note_key)) {
+        return Ok(value);
+    }
+
+    let value = compute.await?;
+    sqlx::query(
+        "INSERT INTO cohort_early_warning.score_cache (org_id, cache_key, data_version, body) \
+         VALUES ($1, $2, $3, $4) \
+         ON CONFLICT (org_id, cache_key) DO UPDATE \
+         SET data_version = EXCLUDED.data_version, body = EXCLUDED.body, computed_at = NOW()",
+    )
+    .bind(org_id)
+    .bind(&key)
+    .bind(version)
+    .bind(entry_body(&value, note_key)?)
+    .execute(pool)
+    .await?;
+    sqlx::query(
+        "DELETE FROM cohort_early_warning.score_cache \
+         WHERE org_id = $1 AND (data_version < $2 OR computed_at <= NOW() - make_interval(secs => $3))",
+    )
+    .bind(org_id)
+    .bind(version)
+    .bind(ttl_secs)
+    .execute(pool)
+    .await?;
+    Ok(value)
+}
+
+const LAST_CONTACTS: &str = "SELECT email, last_contact FROM ( \
+         SELECT sc.email, GREATEST( \
+             (SELECT MAX(i.occurred_on) FROM cohort_early_warning.interventions i \
+              WHERE i.scholar_id = sc.id), \
+             (SELECT MAX(sub.submitted_at)::date \
+              FROM cohort_early_warning.self_report_submissions sub \
+              JOIN cohort_early_warning.self_report_tokens t ON t.id = sub.token_id \
+              WHERE t.scholar_id = sc.id) \
+         ) AS last_contact \
+         FROM cohort_early_warning.scholars sc \
+         WHERE sc.org_id = $1 AND sc.deleted_at IS NULL \
+     ) contacts \
+     WHERE last_contact IS NOT NULL";
+
+/// Date each scholar in the organization was last reached, by email: their latest intervention
+/// or self-report check-in. Scholars never reached are left out.
+#[tracing::instrument(skip_all)]
+pub async fn last_contacts(
+    pool: &PgPool,
+    org_id: Uuid,
+) -> anyhow::Result<HashMap<String, NaiveDate>> {
+    let rows = sqlx::query(LAST_CONTACTS)
+        .bind(org_id)
+        .fetch_all(pool)
+        .await?;
+    Ok(rows
+        .into_iter()
+        .map(|row| (row.get("email"), row.get("last_contact")))
+        .collect())
+}
+
+fn push_page(query: &mut QueryBuilder<'_, Postgres>, after: Option<SignalCursor>, page_size: i64) {
+    if let Some(cursor) = after {
+        query.push(" AND (s.occurred_at, s.id) > (");
+        query.push_bind(cursor.occurred_at);
+        query.push(", ");
+        query.push_bind(cursor.signal_id);
+        query.push(")");
+    }
+    query.push(" ORDER BY s.occurred_at, s.id LIMIT ");
+    query.push_bind(page_size);
+}
+
+#[tracing::instrument(skip_all)]
+pub async fn fetch_weekly_trends(
+    pool: &PgPool,
+    filter: &SignalFilter,
+) -> anyhow::Result<Vec<SignalTrend>> {
+    let records = weekly_trends_query("", filter)
+        .build()
+        .fetch_all(pool)
+        .await?;
+    let mut trends = Vec::new();
+
+    for row in records {
+        trends.push(SignalTrend {
+            week_start: row.get("week_start"),
+            signal_count: row.get("signal_count"),
+            avg_severity: row.get("avg_severity"),
+            scholar_count: row.get("scholar_count"),
+        });
+    }
+
+    Ok(trends)
+}
+
+fn weekly_trends_query(head: &str, filter: &SignalFilter) -> QueryBuilder<'static, Postgres> {
+    let mut query = QueryBuilder::new(format!(
+        "{head}SELECT date_trunc('week', s.occurred_at)::date AS week_start, \
+         COUNT(*) AS signal_count, \
+         AVG(s.severity)::float8 AS avg_severity, \
+         COUNT(DISTINCT sc.id) AS scholar_count \
+         FROM {} s \
+         JOIN cohort_early_warning.scholars sc ON sc.id = s.scholar_id",
+        filter.signals_table()
+    ));
+    filter.push_conditions(&mut query);
+    query.push(" GROUP BY week_start ORDER BY week_start ASC");
+    query
+}
+
+/// Rebuilds the weekly trend cache without blocking readers.
+#[tracing::instrument(skip_all)]
+pub async fn refresh_weekly_trends(pool: &PgPool) -> anyhow::Result<()> {
+    sqlx::query("REFRESH MATERIALIZED VIEW CONCURRENTLY cohort_early_warning.weekly_trends_mv")
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+/// Weekly trends read from the cache built by `refresh_weekly_trends`.
+///
+/// The cache only knows org, cohort, and status, and it stores whole weeks, so the first week
+/// counts every signal in it even when `since_date` falls mid-week.
+#[tracing::instrument(skip_all)]
+pub async fn fetch_cached_weekly_trends(
+    pool: &PgPool,
+    filter: &SignalFilter,
+) -> anyhow::Result<Vec<SignalTrend>> {
+    if !filter.emails.is_empty()
+        || filter.advisor.is_some()
+        || !filter.tags.is_empty()
+        || !filter.signal_types.is_empty()
+        || !filter.exclude_signal_types.is_empty()
+        || filter.min_severity.is_some()
+        || !filter.note_categories.is_empty()
+        || filter.max_note_sentiment.is_some()
+        || filter.include_archived
+    {
+        anyhow::bail!(
+            "cached trends only support org, cohort, and status filters; drop --cached-trends"
+        );
+    }
+
+    let mut query = QueryBuilder::new(
+        "SELECT week_start, SUM(signal_count)::int8 AS signal_count, \
+         (SUM(severity_sum)::float8 / SUM(signal_count)) AS avg_severity, \
+         SUM(scholar_count)::int8 AS scholar_count \
+         FROM cohort_early_warning.weekly_trends_mv \
+         WHERE week_start >= date_trunc('week', ",
+    );
+    query.push_bind(filter.since_date);
+    query.push("::date)::date");
+    if let Some(until_date) = filter.until_date {
+        query.push(" AND week_start <= ");
+        query.push_bind(until_date);
+    }
+
+    if let Some(org_id) = filter.org_id {
+        query.push(" AND org_id = ");
+        query.push_bind(org_id);
+    }
+
+    if !filter.cohorts.is_empty() {
+        query.push(" AND cohort = ANY(ARRAY(SELECT cohort_early_warning.canonical_cohort(");
+        match filter.org_id {
+            Some(org_id) => query.push_bind(org_id),
+            None => query.push("org_id"),
+        };
+        query.push(", c) FROM UNNEST(");
+        query.push_bind(filter.cohorts.clone());
+        query.push("::text[]) AS c))");
+    }
+
+    if !filter.include_inactive {
+        query.push(" AND status = 'active'");
+    }
+    query.push(" GROUP BY week_start ORDER BY week_start ASC");
+
+    let records = query.build().fetch_all(pool).await?;
+    Ok(records
+        .into_iter()
+        .map(|row| SignalTrend {
+            week_start: row.get("week_start"),
+            signal_count: row.get("signal_count"),
+            avg_severity: row.get("avg_severity"),
+            scholar_count: row.get("scholar_count"),
+        })
+        .collect())
+}
+
+/// Stores weekly health points from `health::weekly`, replacing any already stored for the same
+/// cohort and week.
+#[tracing::instrument(skip_all)]
+pub async fn save_cohort_health(
+    pool: &PgPool,
+    org_id: Uuid,
+    points: &[CohortHealth],
+) -> anyhow::Result<u64> {
+    let cohorts: Vec<&str> = points.iter().map(|p| p.cohort.as_str()).collect();
+    let weeks: Vec<NaiveDate> = points.iter().map(|p| p.week_start).collect();
+    let indexes: Vec<f64> = points.iter().map(|p| p.health_index).collect();
+    let scholars: Vec<i32> = points.iter().map(|p| p.scholars as i32).collect();
+    let at_risk: Vec<i32> = points.iter().map(|p| p.at_risk as i32).collect();
+    let result = sqlx::query(
+        "INSERT INTO cohort_early_warning.cohort_health \
+         (org_id, cohort, week_start, health_index, scholars, at_risk) \
+         SELECT $1, v.cohort, v.week_start, v.health_index, v.scholars, v.at_risk \
+         FROM UNNEST($2::text[], $3::date[], $4::float8[], $5::int[], $6::int[]) \
+              AS v(cohort, week_start, health_index, scholars, at_risk) \
+         ON CONFLICT (org_id, cohort, week_start) DO UPDATE SET \
+         health_index = EXCLUDED.health_index, scholars = EXCLUDED.scholars, \
+         at_risk = EXCLUDED.at_risk, computed_at = NOW()",
+    )
+    .bind(org_id)
+    .bind(&cohorts)
+    .bind(&weeks)
+    .bind(&indexes)
+    .bind(&scholars)
+    .bind(&at_risk)
+    .execute(pool)
+    .await?;
+    Ok(result.rows_affected())
+}
+
+/// Stored weekly health points from `since_date` on, by cohort then week, limited to `cohorts`
+/// unless it is empty.
+#[tracing::instrument(skip_all)]
+pub async fn fetch_cohort_health(
+    pool: &PgPool,
+    org_id: Uuid,
+    cohorts: &[String],
+    since_date: NaiveDate,
+) -> anyhow::Result<Vec<CohortHealth>> {
+    let records = sqlx::query(
+        "SELECT cohort, week_start, health_index, scholars::int8 AS scholars, \
+         at_risk::int8 AS at_risk \
+         FROM cohort_early_warning.cohort_health \
+         WHERE org_id = $1 AND week_start >= $2 \
+         AND (cardinality($3::text[]) = 0 OR cohort = ANY($3)) \
+         ORDER BY cohort, week_start",
+    )
+    .bind(org_id)
+    .bind(since_date)
+    .bind(cohorts)
+    .fetch_all(pool)
+    .await?;
+    Ok(records
+        .into_iter()
+        .map(|row| CohortHealth {
+            cohort: row.get("cohort"),
+            week_start: row.get("week_start"),
+            health_index: row.get("health_index"),
+            scholars: row.get("scholars"),
+            at_risk: row.get("at_risk"),
+        })
+        .collect())
+}
+
+/// Weekly trends split by cohort, limited to `cohorts` on top of the filter scope.
+#[tracing::instrument(skip_all)]
+pub async fn fetch_cohort_weekly_trends(
+    pool: &PgPool,
+    filter: &SignalFilter,
+    cohorts: &[String],
+) -> anyhow::Result<Vec<CohortTrend>> {
+    let mut query = QueryBuilder::new(format!(
+        "SELECT sc.cohort, date_trunc('week', s.occurred_at)::date AS week_start, \
+         COUNT(*) AS signal_count, \
+         AVG(s.severity)::float8 AS avg_severity, \
+         COUNT(DISTINCT sc.id) AS scholar_count \
+         FROM {} s \
+         JOIN cohort_early_warning.scholars sc ON sc.id = s.scholar_id",
+        filter.signals_table()
+    ));
+    filter.push_conditions(&mut query);
+    query.push(" AND sc.cohort = ANY(");
+    query.push_bind(cohorts.to_vec());
+    query.push(") GROUP BY sc.cohort, week_start ORDER BY sc.cohort, week_start ASC");
+
+    let records = query.build().fetch_all(pool).await?;
+    let mut trends = Vec::new();
+
+    for row in records {
+        trends.push(CohortTrend {
+            cohort: row.get("cohort"),
+            week_start: row.get("week_start"),
+            signal_count: row.get("signal_count"),
+            avg_severity: row.get("avg_severity"),
+            scholar_count: row.get("scholar_count"),
+        });
+    }
+
+    Ok(trends)
+}
+
+#[tracing::instrument(skip_all)]
+pub async fn set_cohort_start(
+    pool: &PgPool,
+    org_id: Uuid,
+    cohort: &str,
+    start_date: NaiveDate,
+) -> anyhow::Result<()> {
+    sqlx::query(
+        "INSERT INTO cohort_early_warning.cohorts (org_id, name, start_date) \
+         VALUES ($1, cohort_early_warning.canonical_cohort($1, $2), $3) \
+         ON CONFLICT (org_id, name) DO UPDATE SET start_date = EXCLUDED.start_date",
+    )
+    .bind(org_id)
+    .bind(cohort)
+    .bind(start_date)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+#[tracing::instrument(skip_all)]
+pub async fn cohort_aliases(pool: &PgPool, org_id: Uuid) -> anyhow::Result<CohortAliases> {
+    let aliases: Vec<(String, String)> = sqlx::query_as(
+        "SELECT alias, cohort FROM cohort_early_warning.cohort_aliases WHERE org_id = $1",
+    )
+    .bind(org_id)
+    .fetch_all(pool)
+    .await?;
+    Ok(aliases.into_iter().collect())
+}
+
+/// Maps `alias` to `cohort`, replacing any earlier mapping, and moves scholars stored under the
+/// alias into `cohort`. Returns how many scholars moved.
+#[tracing::instrument(skip_all)]
+pub async fn add_cohort_alias(
+    pool: &PgPool,
+    org_id: Uuid,
+    alias: &str,
+    cohort: &str,
+) -> anyhow::Result<u64> {
+    let key = CohortAliases::key(alias);
+    if key.is_empty() {
+        anyhow::bail!("alias must not be blank");
+    }
+    if key == CohortAliases::key(cohort) {
+        anyhow::bail!("{alias:?} only differs from {cohort:?} in case or spacing");
+    }
+    let aliases = cohort_aliases(pool, org_id).await?;
+    if aliases.canonical(cohort) != cohort {
+        anyhow::bail!(
+            "{cohort:?} is itself an alias of {:?}; map {alias:?} to that instead",
+            aliases.canonical(cohort)
+        );
+    }
+    if let Some((other, _)) = aliases
+        .iter()
+        .find(|(_, target)| CohortAliases::key(target) == key)
+    {
+        anyhow::bail!(
+            "{other:?} is an alias of {alias:?}; remove it or map it to {cohort:?} first"
+        );
+    }
+
+    let mut tx = pool.begin().await?;
+    sqlx::query(
+        "INSERT INTO cohort_early_warning.cohort_aliases (org_id, alias, cohort) VALUES ($1, $2, $3) \
+         ON CONFLICT (org_id, alias) DO UPDATE SET cohort = EXCLUDED.cohort",
+    )
+    .bind(org_id)
+    .bind(&key)
+    .bind(cohort)
+    .execute(&mut *tx)
+    .await?;
+    let moved = sqlx::query(
+        "UPDATE cohort_early_warning.scholars SET cohort = $3 \
+         WHERE org_id = $1 AND lower(btrim(cohort)) = $2",
+    )
+    .bind(org_id)
+    .bind(&key)
+    .bind(cohort)
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+    tx.commit().await?;
+    Ok(moved)
+}
+
+/// Returns whether `alias` was mapped.
+#[tracing::instrument(skip_all)]
+pub async fn remove_cohort_alias(pool: &PgPool, org_id: Uuid, alias: &str) -> anyhow::Result<bool> {
+    let result = sqlx::query(
+        "DELETE FROM cohort_early_warning.cohort_aliases WHERE org_id = $1 AND alias = $2",
+    )
+    .bind(org_id)
+    .bind(CohortAliases::key(alias))
+    .execute(pool)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+#[tracing::instrument(skip_all)]
+pub async fn signal_types(pool: &PgPool, org_id: Uuid) -> anyhow::Result<SignalTypes> {
+    let types: Vec<(String, bool)> = sqlx::query_as(
+        "SELECT name, active FROM cohort_early_warning.signal_types WHERE org_id = $1",
+    )
+    .bind(org_id)
+    .fetch_all(pool)
+    .await?;
+    Ok(types.into_iter().collect())
+}
+
+/// Registered signal types in name order, each with how many live signals use it.
+#[tracing::instrument(skip_all)]
+pub async fn list_signal_types(pool: &PgPool, org_id: Uuid) -> anyhow::Result<Vec<SignalType>> {
+    let rows = sqlx::query(
+        "SELECT t.name, t.description, t.default_weight, t.active, t.created_at, \
+                COUNT(s.id) AS signals \
+         FROM cohort_early_warning.signal_types t \
+         LEFT JOIN cohort_early_warning.signals s \
+                ON s.org_id = t.org_id AND lower(btrim(s.signal_type)) = t.name \
+               AND s.deleted_at IS NULL \
+         WHERE t.org_id = $1 \
+         GROUP BY t.name, t.description, t.default_weight, t.active, t.created_at \
+         ORDER BY t.name",
+    )
+    .bind(org_id)
+    .fetch_all(pool)
+    .await?;
+    Ok(rows
+        .into_iter()
+        .map(|row| SignalType {
+            name: row.get("name"),
+            description: row.get("description"),
+            default_weight: row.get("default_weight"),
+            active: row.get("active"),
+            created_at: row.get("created_at"),
+            signals: row.get("signals"),
+        })
+        .collect())
+}
+
+fn check_signal_type_weight(weight: f64) -> anyhow::Result<()> {
+    if !weight.is_finite() || weight < 0.0 {
+        anyhow::bail!("default weight must be zero or more, got {weight}");
+    }
+    Ok(())
+}
+
+/// Registers `name` as an active signal type. Fails if it is blank or already registered.
+#[tracing::instrument(skip_all)]
+pub async fn add_signal_type(
+    pool: &PgPool,
+    org_id: Uuid,
+    name: &str,
+    description: &str,
+    default_weight: f64,
+) -> anyhow::Result<()> {
+    let key = SignalTypes::key(name);
+    if key.is_empty() {
+        anyhow::bail!("signal type name must not be blank");
+    }
+    check_signal_type_weight(default_weight)?;
+    let added = sqlx::query(
+        "INSERT INTO cohort_early_warning.signal_types (org_id, name, description, default_weight) \
+         VALUES ($1, $2, $3, $4) ON CONFLICT (org_id, name) DO NOTHING",
+    )
+    .bind(org_id)
+    .bind(&key)
+    .bind(description)
+    .bind(default_weight)
+    .execute(pool)
+    .await?
+    .rows_affected();
+    if added == 0 {
+        anyhow::bail!("signal type {key} is already registered");
+    }
+    Ok(())
+}
+
+/// Changes whichever of a signal type's description, default weight, and active flag are given.
+#[tracing::instrument(skip_all)]
+pub async fn update_signal_type(
+    pool: &PgPool,
+    org_id: Uuid,
+    name: &str,
+    description: Option<&str>,
+    default_weight: Option<f64>,
+    active: Option<bool>,
+) -> anyhow::Result<()> {
+    if let Some(weight) = default_weight {
+        check_signal_type_weight(weight)?;
+    }
+    let key = SignalTypes::key(name);
+    let updated = sqlx::query(
+        "UPDATE cohort_early_warning.signal_types \
+         SET description = COALESCE($3, description), \
+             default_weight = COALESCE($4, default_weight), \
+             active = COALESCE($5, active) \
+         WHERE org_id = $1 AND name = $2",
+    )
+    .bind(org_id)
+    .bind(&key)
+    .bind(description)
+    .bind(default_weight)
+    .bind(active)
+    .execute(pool)
+    .await?
+    .rows_affected();
+    if updated == 0 {
+        anyhow::bail!("no signal type named {key}");
+    }
+    Ok(())
+}
+
+/// Registers each of `names` as an active signal type, leaving any already registered as they are.
+#[tracing::instrument(skip_all)]
+pub async fn register_signal_types(
+    conn: impl sqlx::PgExecutor<'_>,
+    org_id: Uuid,
+    names: &[String],
+) -> anyhow::Result<()> {
+    let keys: Vec<String> = names.iter().map(|name| SignalTypes::key(name)).collect();
+    sqlx::query(
+        "INSERT INTO cohort_early_warning.signal_types (org_id, name) \
+         SELECT $1, name FROM UNNEST($2::text[]) AS name \
+         ON CONFLICT (org_id, name) DO NOTHING",
+    )
+    .bind(org_id)
+    .bind(&keys)
+    .execute(conn)
+    .await?;
+    Ok(())
+}
+
+/// Registers every type that live signals already use, so switching the taxonomy on does not
+/// reject current data. Returns the names added.
+#[tracing::instrument(skip_all)]
+pub async fn adopt_signal_types(pool: &PgPool, org_id: Uuid) -> anyhow::Result<Vec<String>> {
+    let mut added: Vec<String> = sqlx::query_scalar(
+        "INSERT INTO cohort_early_warning.signal_types (org_id, name) \
+         SELECT DISTINCT $1::uuid, lower(btrim(signal_type)) \
+         FROM cohort_early_warning.signals \
+         WHERE org_id = $1 AND deleted_at IS NULL AND btrim(signal_type) <> '' \
+         ON CONFLICT (org_id, name) DO NOTHING \
+         RETURNING name",
+    )
+    .bind(org_id)
+    .fetch_all(pool)
+    .await?;
+    added.sort();
+    Ok(added)
+}
+
+/// Start date per cohort, falling back to the cohort's earliest signal when none was set.
+#[tracing::instrument(skip_all)]
+pub async fn fetch_cohort_starts(
+    pool: &PgPool,
+    org_id: Uuid,
+    cohorts: &[String],
+) -> anyhow::Result<HashMap<String, NaiveDate>> {
+    let records = sqlx::query(
+        "SELECT sc.cohort, COALESCE(MAX(c.start_date), MIN(s.occurred_at)) AS start_date \
+         FROM cohort_early_warning.scholars sc \
+         JOIN cohort_early_warning.signals s ON s.scholar_id = sc.id AND s.deleted_at IS NULL \
+         LEFT JOIN cohort_early_warning.cohorts c ON c.org_id = sc.org_id AND c.name = sc.cohort \
+         WHERE sc.org_id = $1 AND sc.deleted_at IS NULL AND sc.cohort = ANY($2) \
+         GROUP BY sc.cohort",
+    )
+    .bind(org_id)
+    .bind(cohorts)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(records
+        .into_iter()
+        .map(|row| (row.get("cohort"), row.get("start_date")))
+        .collect())
+}
+
+/// Staff-entered signals in the filter scope, for the inter-rater consistency analysis.
+#[tracing::instrument(skip_all)]
+pub async fn fetch_rated_signals(
+    pool: &PgPool,
+    filter: &SignalFilter,
+) -> anyhow::Result<Vec<RatedSignal>> {
+    let mut query = QueryBuilder::new(format!(
+        "SELECT s.submitted_by, s.signal_type, s.severity \
+         FROM {} s \
+         JOIN cohort_early_warning.scholars sc ON sc.id = s.scholar_id",
+        filter.signals_table()
+    ));
+    filter.push_conditions(&mut query);
+    query.push(" AND s.submitted_by IS NOT NULL");
+
+    let records = query.build().fetch_all(pool).await?;
+    Ok(records
+        .into_iter()
+        .map(|row| RatedSignal {
+            submitted_by: row.get("submitted_by"),
+            signal_type: row.get("signal_type"),
+            severity: row.get("severity"),
+        })
+        .collect())
+}
+
+/// Signals in scope whose note matches `terms` (web search syntax: quoted phrases, `or`, `-word`),
+/// best matches first. Encrypted notes are only ciphertext to the database, so never match.
+#[tracing::instrument(skip_all)]
+pub async fn search_signals(
+    pool: &PgPool,
+    filter: &SignalFilter,
+    terms: &str,
+    limit: i64,
+) -> anyhow::Result<Vec<SearchHit>> {
+    let mut query = QueryBuilder::new(format!(
+        "SELECT sc.full_name, sc.email, sc.cohort, s.signal_type, s.severity, s.occurred_at, \
+         s.source_key, \
+         ts_headline('english', s.note, q.query, \
+                     'StartSel=**, StopSel=**, MaxWords=24, MinWords=8') AS snippet \
+         FROM {} s \
+         JOIN cohort_early_warning.scholars sc ON sc.id = s.scholar_id \
+         CROSS JOIN websearch_to_tsquery('english', ",
+        filter.signals_table()
+    ));
+    query.push_bind(terms.to_string());
+    query.push(") AS q(query)");
+    filter.push_conditions(&mut query);
+    // Must match the expression of the note search indexes.
+    query.push(
+        " AND to_tsvector('english', s.note) @@ q.query \
+         ORDER BY ts_rank(to_tsvector('english', s.note), q.query) DESC, s.occurred_at DESC \
+         LIMIT ",
+    );
+    query.push_bind(limit);
+
+    let rows = query.build().fetch_all(pool).await?;
+    Ok(rows
+        .into_iter()
+        .map(|row| SearchHit {
+            scholar_name: row.get("full_name"),
+            scholar_email: row.get("email"),
+            cohort: row.get("cohort"),
+            signal_type: row.get("signal_type"),
+            severity: row.get("severity"),
+            occurred_at: row.get("occurred_at"),
+            source_key: row.get("source_key"),
+            snippet: row.get("snippet"),
+        })
+        .collect())
+}
+
+#[tracing::instrument(skip_all)]
+pub async fn set_scholar_status(
+    pool: &PgPool,
+    org_id: Uuid,
+    email: &str,
+    status: ScholarStatus,
+) -> anyhow::Result<()> {
+    let result = sqlx::query(
+        "UPDATE cohort_early_warning.scholars SET status = $1 \
+         WHERE org_id = $2 AND email = $3 AND deleted_at IS NULL",
+    )
+    .bind(status.as_str())
+    .bind(org_id)
+    .bind(email)
+    .execute(pool)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        anyhow::bail!("no scholar found with email {email}");
+    }
+    Ok(())
+}
+
+#[tracing::instrument(skip_all)]
+pub async fn database_stats(pool: &PgPool, org_id: Uuid) -> anyhow::Result<DatabaseStats> {
+    let cohorts = sqlx::query(
+        "SELECT cohort, COUNT(*) AS scholars, \
+         COUNT(*) FILTER (WHERE status = 'active') AS active \
+         FROM cohort_early_warning.scholars \
+         WHERE org_id = $1 AND deleted_at IS NULL \
+         GROUP BY cohort ORDER BY cohort",
+    )
+    .bind(org_id)
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|row| (row.get("cohort"), row.get("scholars"), row.get("active")))
+    .collect();
+
+    let signal_types = sqlx::query(
+        "SELECT s.signal_type, COUNT(*) AS signals \
+         FROM cohort_early_warning.signals s \
+         JOIN cohort_early_warning.scholars sc ON sc.id = s.scholar_id \
+         WHERE sc.org_id = $1 AND s.deleted_at IS NULL AND sc.deleted_at IS NULL \
+         GROUP BY s.signal_type ORDER BY signals DESC, s.signal_type",
+    )
+    .bind(org_id)
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|row| (row.get("signal_type"), row.get("signals")))
+    .collect();
+
+    let signals = sqlx::query(
+        "SELECT COUNT(*) AS signals, MIN(s.occurred_at) AS first_occurred_at, \
+         MAX(s.occurred_at) AS last_occurred_at, \
+         COUNT(*) FILTER (WHERE s.created_at >= NOW() - INTERVAL '7 days') AS added_7, \
+         COUNT(*) FILTER (WHERE s.created_at >= NOW() - INTERVAL '30 days') AS added_30 \
+         FROM cohort_early_warning.signals s \
+         JOIN cohort_early_warning.scholars sc ON sc.id = s.scholar_id \
+         WHERE sc.org_id = $1 AND s.deleted_at IS NULL AND sc.deleted_at IS NULL",
+    )
+    .bind(org_id)
+    .fetch_one(pool)
+    .await?;
+
+    let scholars = sqlx::query(
+        "SELECT COUNT(*) FILTER (WHERE created_at >= NOW() - INTERVAL '7 days') AS added_7, \
+         COUNT(*) FILTER (WHERE created_at >= NOW() - INTERVAL '30 days') AS added_30 \
+         FROM cohort_early_warning.scholars WHERE org_id = $1 AND deleted_at IS NULL",
+    )
+    .bind(org_id)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(DatabaseStats {
+        cohorts,
+        signal_types,
+        signal_count: signals.get("signals"),
+        first_occurred_at: signals.get("first_occurred_at"),
+        last_occurred_at: signals.get("last_occurred_at"),
+        scholars_added: [scholars.get("added_7"), scholars.get("added_30")],
+        signals_added: [signals.get("added_7"), signals.get("added_30")],
+    })
+}
+
+/// Persists one scoring run and every scholar's result; returns the run id.
+#[tracing::instrument(skip_all)]
+pub async fn save_score_run(
+    pool: &PgPool,
+    org_id: Uuid,
+    since_days: i64,
+    trigger: &str,
+    scores: &[ScholarScore],
+) -> anyhow::Result<Uuid> {
+    let run_id = Uuid::new_v4();
+    let mut tx = pool.begin().await?;
+    sqlx::query(
+        "INSERT INTO cohort_early_warning.score_runs (id, org_id, since_days, trigger) \
+         VALUES ($1, $2, $3, $4)",
+    )
+    .bind(run_id)
+    .bind(org_id)
+    .bind(since_days as i32)
+    .bind(trigger)
+    .execute(&mut *tx)
+    .await?;
+
+    let emails: Vec<&str> = scores.iter().map(|s| s.scholar_email.as_str()).collect();
+    let values: Vec<f64> = scores.iter().map(|s| s.score).collect();
+    let tiers: Vec<&str> = scores.iter().map(|s| s.tier.as_str()).collect();
+    let counts: Vec<i32> = scores.iter().map(|s| s.signal_count as i32).collect();
+    sqlx::query(
+        "INSERT INTO cohort_early_warning.score_snapshots \
+         (run_id, scholar_id, score, tier, signal_count) \
+         SELECT $1, sc.id, v.score, v.tier, v.signal_count \
+         FROM UNNEST($2::text[], $3::float8[], $4::text[], $5::int[]) \
+              AS v(email, score, tier, signal_count) \
+         JOIN cohort_early_warning.scholars sc ON sc.org_id = $6 AND sc.email = v.email",
+    )
+    .bind(run_id)
+    .bind(&emails)
+    .bind(&values)
+    .bind(&tiers)
+    .bind(&counts)
+    .bind(org_id)
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await?;
+    Ok(run_id)
+}
+
+/// Tier of each scholar, by email, in the organization's most recent scoring run.
+#[tracing::instrument(skip_all)]
+pub async fn latest_run_tiers(
+    pool: &PgPool,
+    org_id: Uuid,
+) -> anyhow::Result<HashMap<String, Tier>> {
+    let rows = sqlx::query(
+        "SELECT sc.email, ss.tier FROM cohort_early_warning.score_snapshots ss \
+         JOIN cohort_early_warning.scholars sc ON sc.id = ss.scholar_id \
+         WHERE ss.run_id = (SELECT id FROM cohort_early_warning.score_runs \
+                            WHERE org_id = $1 ORDER BY ran_at DESC LIMIT 1)",
+    )
+    .bind(org_id)
+    .fetch_all(pool)
+    .await?;
+
+    rows.into_iter()
+        .map(|row| {
+            let tier: String = row.get("tier");
+            let tier = <Tier as clap::ValueEnum>::from_str(&tier, true)
+                .map_err(|_| anyhow::anyhow!("unknown tier {tier} in score_snapshots"))?;
+            Ok((row.get("email"), tier))
+        })
+        .collect()
+}
+
+/// The organization's most recent scoring run that started before `before`.
+#[tracing::instrument(skip_all)]
+pub async fn score_run_before(
+    pool: &PgPool,
+    org_id: Uuid,
+    before: DateTime<Utc>,
+) -> anyhow::Result<Option<ScoreRun>> {
+    let row = sqlx::query(
+        "SELECT id, ran_at, trigger FROM cohort_early_warning.score_runs \
+         WHERE org_id = $1 AND ran_at < $2 ORDER BY ran_at DESC LIMIT 1",
+    )
+    .bind(org_id)
+    .bind(before)
+    .fetch_optional(pool)
+    .await?;
+    Ok(row.map(|row| ScoreRun {
+        id: row.get("id"),
+        ran_at: row.get("ran_at"),
+        trigger: row.get("trigger"),
+    }))
+}
+
+/// Every scholar's saved result in one scoring run.
+#[tracing::instrument(skip_all)]
+pub async fn run_snapshot(pool: &PgPool, run_id: Uuid) -> anyhow::Result<Vec<SnapshotScore>> {
+    let rows = sqlx::query(
+        "SELECT sc.full_name, sc.email, sc.cohort, ss.score, ss.tier \
+         FROM cohort_early_warning.score_snapshots ss \
+         JOIN cohort_early_warning.scholars sc ON sc.id = ss.scholar_id \
+         WHERE ss.run_id = $1",
+    )
+    .bind(run_id)
+    .fetch_all(pool)
+    .await?;
+
+    rows.into_iter()
+        .map(|row| {
+            let tier: String = row.get("tier");
+            let tier = <Tier as clap::ValueEnum>::from_str(&tier, true)
+                .map_err(|_| anyhow::anyhow!("unknown tier {tier} in score_snapshots"))?;
+            Ok(SnapshotScore {
+                scholar_name: row.get("full_name"),
+                scholar_email: row.get("email"),
+                cohort: row.get("cohort"),
+                score: row.get("score"),
+                tier,
+            })
+        })
+        .collect()
+}
+
+/// The scholar's results in the organization's last `runs` scoring runs since they joined, oldest
+/// first. A run that didn't score them (no signals in its window) reads as a zero, low-tier score.
+#[tracing::instrument(skip_all)]
+pub async fn score_history(
+    pool: &PgPool,
+    org_id: Uuid,
+    email: &str,
+    runs: i64,
+) -> anyhow::Result<Vec<ScoreHistoryPoint>> {
+    let rows = sqlx::query(
+        "SELECT r.ran_at, r.trigger, COALESCE(ss.score, 0) AS score, \
+                COALESCE(ss.tier, 'low') AS tier, COALESCE(ss.signal_count, 0) AS signal_count \
+         FROM cohort_early_warning.score_runs r \
+         JOIN cohort_early_warning.scholars sc ON sc.org_id = r.org_id AND sc.email = $2 \
+         LEFT JOIN cohort_early_warning.score_snapshots ss \
+                ON ss.run_id = r.id AND ss.scholar_id = sc.id \
+         WHERE r.org_id = $1 AND r.ran_at >= sc.created_at \
+         ORDER BY r.ran_at DESC LIMIT $3",
+    )
+    .bind(org_id)
+    .bind(email)
+    .bind(runs)
+    .fetch_all(pool)
+    .await?;
+
+    let mut points = rows
+        .into_iter()
+        .map(|row| {
+            let tier: String = row.get("tier");
+            let tier = <Tier as clap::ValueEnum>::from_str(&tier, true)
+                .map_err(|_| anyhow::anyhow!("unknown tier {tier} in score_snapshots"))?;
+            Ok(ScoreHistoryPoint {
+                ran_at: row.get("ran_at"),
+                trigger: row.get("trigger"),
+                score: row.get("score"),
+                tier,
+                signal_count: row.get::<i32, _>("signal_count") as i64,
+            })
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    points.reverse();
+    Ok(points)
+}
+
+/// Adds a scholar to the roster; fails if the email is already registered in the organization.
+#[tracing::instrument(skip_all)]
+pub async fn add_scholar(
+    pool: &PgPool,
+    org_id: Uuid,
+    email: &str,
+    full_name: &str,
+    cohort: &str,
+) -> anyhow::Result<()> {
+    let result = sqlx::query(
+        "INSERT INTO cohort_early_warning.scholars (id, full_name, email, cohort, org_id) \
+         VALUES ($1, $2, $3, cohort_early_warning.canonical_cohort($5, $4), $5) \
+         ON CONFLICT (org_id, email) DO NOTHING",
+    )
+    .bind(Uuid::new_v4())
+    .bind(full_name)
+    .bind(email)
+    .bind(cohort)
+    .bind(org_id)
+    .execute(pool)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        anyhow::bail!("scholar {email} already exists; use `scholar update` to change it");
+    }
+    Ok(())
+}
+
+/// Filters for [`list_scholars`]; unset fields match every scholar.
+#[derive(Debug, Clone, Default)]
+pub struct ScholarFilter {
+    pub cohort: Option<String>,
+    pub email: Option<String>,
+    pub status: Option<ScholarStatus>,
+    /// Only scholars assigned to the advisor with this email.
+    pub advisor: Option<String>,
+    pub include_deleted: bool,
+}
+
+#[tracing::instrument(skip_all)]
+pub async fn list_scholars(
+    pool: &PgPool,
+    org_id: Uuid,
+    filter: &ScholarFilter,
+) -> anyhow::Result<Vec<Scholar>> {
+    let mut query = QueryBuilder::<Postgres>::new(
+        "SELECT sc.full_name, sc.email, sc.cohort, sc.status, a.full_name AS advisor, \
+         sc.created_at, sc.deleted_at IS NOT NULL AS deleted \
+         FROM cohort_early_warning.scholars sc \
+         LEFT JOIN cohort_early_warning.advisors a ON a.id = sc.advisor_id \
+         WHERE sc.org_id = ",
+    );
+    query.push_bind(org_id);
+    if let Some(cohort) = &filter.cohort {
+        query
+            .push(" AND sc.cohort = cohort_early_warning.canonical_cohort(")
+            .push_bind(org_id)
+            .push(", ")
+            .push_bind(cohort)
+            .push(")");
+    }
+    if let Some(email) = &filter.email {
+        query.push(" AND sc.email = ").push_bind(email);
+    }
+    if let Some(status) = filter.status {
+        query.push(" AND sc.status = ").push_bind(status.as_str());
+    }
+    if let Some(advisor) = &filter.advisor {
+        query.push(" AND a.email = ").push_bind(advisor);
+    }
+    if !filter.include_deleted {
+        query.push(" AND sc.deleted_at IS NULL");
+    }
+    query.push(" ORDER BY sc.cohort, sc.full_name, sc.email");
+
+    let rows = query.build().fetch_all(pool).await?;
+    Ok(rows
+        .into_iter()
+        .map(|row| Scholar {
+            full_name: row.get("full_name"),
+            email: row.get("email"),
+            cohort: row.get("cohort"),
+            status: row.get("status"),
+            advisor: row.get("advisor"),
+            created_at: row.get("created_at"),
+            deleted: row.get("deleted"),
+        })
+        .collect())
+}
+
+/// Streams each saved score snapshot from runs since `since` to `visit`, oldest run first,
+/// optionally limited to one cohort. Returns the number of snapshots visited.
+#[tracing::instrument(skip_all)]
+pub async fn for_each_snapshot_row<F>(
+    pool: &PgPool,
+    org_id: Uuid,
+    since: DateTime<Utc>,
+    cohort: Option<&str>,
+    mut visit: F,
+) -> anyhow::Result<u64>
+where
+    F: FnMut(SnapshotRow) -> anyhow::Result<()>,
+{
+    let mut query = QueryBuilder::<Postgres>::new(
+        "SELECT r.id AS run_id, r.ran_at, r.trigger, r.since_days, sc.full_name, sc.email, \
+         sc.cohort, ss.score, ss.tier, ss.signal_count \
+         FROM cohort_early_warning.score_snapshots ss \
+         JOIN cohort_early_warning.score_runs r ON r.id = ss.run_id \
+         JOIN cohort_early_warning.scholars sc ON sc.id = ss.scholar_id \
+         WHERE sc.deleted_at IS NULL AND r.org_id = ",
+    );
+    query.push_bind(org_id);
+    query.push(" AND r.ran_at >= ").push_bind(since);
+    if let Some(cohort) = cohort {
+        query
+            .push(" AND sc.cohort = cohort_early_warning.canonical_cohort(")
+            .push_bind(org_id)
+            .push(", ")
+            .push_bind(cohort)
+            .push(")");
+    }
+    query.push(" ORDER BY r.ran_at, r.id, sc.email");
+
+    let mut rows = query.build().fetch(pool);
+    let mut visited = 0;
+    while let Some(row) = rows.try_next().await? {
+        let tier: String = row.get("tier");
+        visit(SnapshotRow {
+            run_id: row.get("run_id"),
+            ran_at: row.get("ran_at"),
+            trigger: row.get("trigger"),
+            since_days: row.get("since_days"),
+            full_name: row.get("full_name"),
+            email: row.get("email"),
+            cohort: row.get("cohort"),
+            score: row.get("score"),
+            tier: <Tier as clap::ValueEnum>::from_str(&tier, true)
+                .map_err(|_| anyhow::anyhow!("unknown tier {tier} in score_snapshots"))?,
+            signal_count: row.get("signal_count"),
+        })?;
+        visited += 1;
+    }
+    Ok(visited)
+}
+
+/// The database's clock, which sync cursors are kept in so they agree with `created_at` and
+/// `ran_at` regardless of this machine's clock.
+#[tracing::instrument(skip_all)]
+pub async fn database_now(pool: &PgPool) -> anyhow::Result<DateTime<Utc>> {
+    Ok(sqlx::query_scalar("SELECT NOW()").fetch_one(pool).await?)
+}
+
+/// Where the last run of the sync `target` stopped for the organization, or `None` before its
+/// first run.
+#[tracing::instrument(skip_all)]
+pub async fn sync_cursor(
+    pool: &PgPool,
+    org_id: Uuid,
+    target: &str,
+) -> anyhow::Result<Option<DateTime<Utc>>> {
+    Ok(sqlx::query_scalar(
+        "SELECT synced_through FROM cohort_early_warning.sync_cursors \
+         WHERE org_id = $1 AND target = $2",
+    )
+    .bind(org_id)
+    .bind(target)
+    .fetch_optional(pool)
+    .await?)
+}
+
+/// Records that the sync `target` has pushed everything logged before `synced_through`.
+#[tracing::instrument(skip_all)]
+pub async fn set_sync_cursor(
+    pool: &PgPool,
+    org_id: Uuid,
+    target: &str,
+    synced_through: DateTime<Utc>,
+) -> anyhow::Result<()> {
+    sqlx::query(
+        "INSERT INTO cohort_early_warning.sync_cursors (org_id, target, synced_through) \
+         VALUES ($1, $2, $3) \
+         ON CONFLICT (org_id, target) DO UPDATE \
+         SET synced_through = EXCLUDED.synced_through, updated_at = NOW()",
+    )
+    .bind(org_id)
+    .bind(target)
+    .bind(synced_through)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Last stream sequence `consume` checkpointed for `stream` and `consumer`, or `None` before it
+/// finished a batch.
+#[tracing::instrument(skip_all)]
+pub async fn consumer_checkpoint(
+    pool: &PgPool,
+    org_id: Uuid,
+    stream: &str,
+    consumer: &str,
+) -> anyhow::Result<Option<i64>> {
+    Ok(sqlx::query_scalar(
+        "SELECT sequence FROM cohort_early_warning.consumer_checkpoints \
+         WHERE org_id = $1 AND stream = $2 AND consumer = $3",
+    )
+    .bind(org_id)
+    .bind(stream)
+    .bind(consumer)
+    .fetch_optional(pool)
+    .await?)
+}
+
+/// Records that every message of `stream` up to `sequence` was handled. Never moves backwards.
+#[tracing::instrument(skip_all)]
+pub async fn set_consumer_checkpoint(
+    pool: &PgPool,
+    org_id: Uuid,
+    stream: &str,
+    consumer: &str,
+    sequence: i64,
+) -> anyhow::Result<()> {
+    sqlx::query(
+        "INSERT INTO cohort_early_warning.consumer_checkpoints (org_id, stream, consumer, sequence) \
+         VALUES ($1, $2, $3, $4) \
+         ON CONFLICT (org_id, stream, consumer) DO UPDATE \
+         SET sequence = GREATEST(consumer_checkpoints.sequence, EXCLUDED.sequence), \
+         updated_at = NOW()",
+    )
+    .bind(org_id)
+    .bind(stream)
+    .bind(consumer)
+    .bind(sequence)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Changes a scholar's name, cohort, or email; `None` keeps the current value.
+#[tracing::instrument(skip_all)]
+pub async fn update_scholar(
+    pool: &PgPool,
+    org_id: Uuid,
+    email: &str,
+    full_name: Option<&str>,
+    cohort: Option<&str>,
+    new_email: Option<&str>,
+) -> anyhow::Result<()> {
+    let result = sqlx::query(
+        "UPDATE cohort_early_warning.scholars \
+         SET full_name = COALESCE($1, full_name), \
+         cohort = COALESCE(cohort_early_warning.canonical_cohort($4, $2), cohort), \
+         email = COALESCE($3, email) \
+         WHERE org_id = $4 AND email = $5 AND deleted_at IS NULL",
+    )
+    .bind(full_name)
+    .bind(cohort)
+    .bind(new_email)
+    .bind(org_id)
+    .bind(email)
+    .execute(pool)
+    .await
+    .map_err(|err| match &err {
+        sqlx::Error::Database(db) if db.is_unique_violation() => {
+            anyhow::anyhow!(
+                "another scholar already uses {}",
+                new_email.unwrap_or(email)
+            )
+        }
+        _ => err.into(),
+    })?;
+
+    if result.rows_affected() == 0 {
+        anyhow::bail!("no scholar found with email {email}");
+    }
+    Ok(())
+}
+
+/// Sets or clears `deleted_at` on a scholar; their signals are hidden along with them.
+#[tracing::instrument(skip_all)]
+pub async fn set_scholar_deleted(
+    pool: &PgPool,
+    org_id: Uuid,
+    email: &str,
+    deleted: bool,
+) -> anyhow::Result<()> {
+    let result = sqlx::query(
+        "UPDATE cohort_early_warning.scholars \
+         SET deleted_at = CASE WHEN $1 THEN NOW() ELSE NULL END \
+         WHERE org_id = $2 AND email = $3 AND (deleted_at IS NULL) = $1",
+    )
+    .bind(deleted)
+    .bind(org_id)
+    .bind(email)
+    .execute(pool)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        let state = if deleted { "active" } else { "deleted" };
+        anyhow::bail!("no {state} scholar found with email {email}");
+    }
+    Ok(())
+}
+
+#[tracing::instrument(skip_all)]
+pub async fn set_signal_deleted(
+    pool: &PgPool,
+    org_id: Uuid,
+    source_key: &str,
+    deleted: bool,
+) -> anyhow::Result<()> {
+    let result = sqlx::query(
+        "UPDATE cohort_early_warning.signals \
+         SET deleted_at = CASE WHEN $1 THEN NOW() ELSE NULL END \
+         WHERE source_key = $2 AND org_id = $3 AND (deleted_at IS NULL) = $1",
+    )
+    .bind(deleted)
+    .bind(source_key)
+    .bind(org_id)
+    .execute(pool)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        let state = if deleted { "active" } else { "deleted" };
+        anyhow::bail!("no {state} signal found with source key {source_key}");
+    }
+    Ok(())
+}
+
+/// Adds or removes tags on one signal, returning its resulting tag list.
+#[tracing::instrument(skip_all)]
+pub async fn update_signal_tags(
+    pool: &PgPool,
+    org_id: Uuid,
+    source_key: &str,
+    tags: &[String],
+    add: bool,
+) -> anyhow::Result<Vec<String>> {
+    let update = if add {
+        "ARRAY(SELECT DISTINCT t FROM unnest(tags || $1) AS t ORDER BY t)"
+    } else {
+        "ARRAY(SELECT t FROM unnest(tags) AS t WHERE t <> ALL($1) ORDER BY t)"
+    };
+    let row = sqlx::query(&format!(
+        "UPDATE cohort_early_warning.signals SET tags = {update} \
+         WHERE source_key = $2 AND org_id = $3 AND deleted_at IS NULL \
+         RETURNING tags"
+    ))
+    .bind(tags)
+    .bind(source_key)
+    .bind(org_id)
+    .fetch_optional(pool)
+    .await?;
+
+    match row {
+        Some(row) => Ok(row.get("tags")),
+        None => anyhow::bail!("no active signal found with source key {source_key}"),
+    }
+}
+
+/// Hard-deletes every scholar in the cohort along with their signals and stored health index.
+#[tracing::instrument(skip_all)]
+pub async fn delete_cohort(pool: &PgPool, org_id: Uuid, cohort: &str) -> anyhow::Result<u64> {
+    // Attachments have no foreign key to cascade through.
+    sqlx::query(
+        "DELETE FROM cohort_early_warning.signal_attachments WHERE signal_id IN ( \
+         SELECT s.id FROM cohort_early_warning.signals_with_archive s \
+         JOIN cohort_early_warning.scholars sc ON sc.id = s.scholar_id \
+         WHERE sc.org_id = $1 AND sc.cohort = $2)",
+    )
+    .bind(org_id)
+    .bind(cohort)
+    .execute(pool)
+    .await?;
+
+    sqlx::query("DELETE FROM cohort_early_warning.cohort_health WHERE org_id = $1 AND cohort = $2")
+        .bind(org_id)
+        .bind(cohort)
+        .execute(pool)
+        .await?;
+
+    let result =
+        sqlx::query("DELETE FROM cohort_early_warning.scholars WHERE org_id = $1 AND cohort = $2")
+            .bind(org_id)
+            .bind(cohort)
+            .execute(pool)
+            .await?;
+    Ok(result.rows_affected())
+}
+
+#[tracing::instrument(skip_all)]
+pub async fn fetch_cohort_aggregates(
+    pool: &PgPool,
+    org_id: Uuid,
+    since_date: NaiveDate,
+    cohort: Option<&str>,
+) -> anyhow::Result<Vec<CohortAggregate>> {
+    let mut query = String::from(
+        "SELECT sc.cohort, s.signal_type, \
+         COUNT(DISTINCT sc.id) AS scholar_count, \
+         COUNT(*) AS signal_count, \
+         AVG(s.severity)::float8 AS avg_severity \
+         FROM cohort_early_warning.signals s \
+         JOIN cohort_early_warning.scholars sc ON sc.id = s.scholar_id \
+         WHERE s.deleted_at IS NULL AND sc.deleted_at IS NULL \
+         AND sc.org_id = $1 AND s.occurred_at >= $2",
+    );
+
+    if cohort.is_some() {
+        query.push_str(" AND sc.cohort = cohort_early_warning.canonical_cohort($1, $3)");
+    }
+
+    query.push_str(" GROUP BY sc.cohort, s.signal_type ORDER BY sc.cohort, s.signal_type");
+
+    let mut rows = sqlx::query(&query).bind(org_id).bind(since_date);
+
+    if let Some(value) = cohort {
+        rows = rows.bind(value);
+    }
+
+    let records = rows.fetch_all(pool).await?;
+    let mut aggregates = Vec::new();
+
+    for row in records {
+        aggregates.push(CohortAggregate {
+            cohort: row.get("cohort"),
+            signal_type: row.get("signal_type"),
+            scholar_count: row.get("scholar_count"),
+            signal_count: row.get("signal_count"),
+            avg_severity: row.get("avg_severity"),
+        });
+    }
+
+    Ok(aggregates)
+}
+
+#[tracing::instrument(skip_all)]
+pub async fn create_import_batch(
+    conn: impl sqlx::PgExecutor<'_>,
+    org_id: Uuid,
+    source: &str,
+) -> anyhow::Result<Uuid> {
+    let batch_id = Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO cohort_early_warning.import_batches (id, source, org_id) VALUES ($1, $2, $3)",
+    )
+    .bind(batch_id)
+    .bind(source)
+    .bind(org_id)
+    .execute(conn)
+    .await?;
+    Ok(batch_id)
+}
+
+#[tracing::instrument(skip_all)]
+pub async fn list_import_batches(
+    pool: &PgPool,
+    org_id: Uuid,
+    limit: i64,
+) -> anyhow::Result<Vec<ImportBatch>> {
+    let rows = sqlx::query(
+        r#"
+        SELECT b.id, b.source, b.started_at, b.undone_at,
+               COUNT(s.id) FILTER (WHERE s.deleted_at IS NULL) AS signal_count
+        FROM cohort_early_warning.import_batches b
+        LEFT JOIN cohort_early_warning.signals s ON s.batch_id = b.id
+        WHERE b.org_id = $1
+        GROUP BY b.id
+        ORDER BY b.started_at DESC
+        LIMIT $2
+        "#,
+    )
+    .bind(org_id)
+    .bind(limit)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| ImportBatch {
+            id: row.get("id"),
+            source: row.get("source"),
+            started_at: row.get("started_at"),
+            undone_at: row.get("undone_at"),
+            signal_count: row.get("signal_count"),
+        })
+        .collect())
+}
+
+/// Soft-deletes every signal written by the batch and marks the batch as undone.
+#[tracing::instrument(skip_all)]
+pub async fn undo_import_batch(pool: &PgPool, org_id: Uuid, batch_id: Uuid) -> anyhow::Result<u64> {
+    let mut tx = pool.begin().await?;
+
+    let batch = sqlx::query(
+        "SELECT undone_at FROM cohort_early_warning.import_batches WHERE id = $1 AND org_id = $2",
+    )
+    .bind(batch_id)
+    .bind(org_id)
+    .fetch_optional(&mut *tx)
+    .await?
+    .with_context(|| format!("import batch {batch_id} not found"))?;
+    let undone_at: Option<chrono::DateTime<chrono::Utc>> = batch.get("undone_at");
+    if undone_at.is_some() {
+        anyhow::bail!("import batch {batch_id} was already undone");
+    }
+
+    let deleted = sqlx::query(
+        "UPDATE cohort_early_warning.signals SET deleted_at = NOW() \
+         WHERE batch_id = $1 AND deleted_at IS NULL",
+    )
+    .bind(batch_id)
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+
+    sqlx::query("UPDATE cohort_early_warning.import_batches SET undone_at = NOW() WHERE id = $1")
+        .bind(batch_id)
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+    Ok(deleted)
+}
+
+/// Moves signals that occurred before `before` into the archive table and returns how many moved.
+///
+/// Signals linked to an intervention stay in the hot table so the link survives. Overflowed notes
+/// are archived with their full text.
+#[tracing::instrument(skip_all)]
+pub async fn archive_signals(
+    pool: &PgPool,
+    org_id: Uuid,
+    before: NaiveDate,
+) -> anyhow::Result<u64> {
+    let mut tx = pool.begin().await?;
+
+    let archived = sqlx::query(
+        "WITH moved AS ( \
+             DELETE FROM cohort_early_warning.signals s \
+             WHERE s.org_id = $1 AND s.occurred_at < $2 \
+             AND NOT EXISTS (SELECT 1 FROM cohort_early_warning.intervention_signals link \
+                             WHERE link.signal_id = s.id) \
+             RETURNING s.*, (SELECT o.full_note FROM cohort_early_warning.signal_note_overflow o \
+                             WHERE o.signal_id = s.id) AS full_note \
+         ) \
+         INSERT INTO cohort_early_warning.signals_archive \
+         (id, scholar_id, signal_type, severity, note, occurred_at, source_key, created_at, \
+          batch_id, deleted_at, org_id, tags, submitted_by, note_categories, note_sentiment, \
+          enriched_at) \
+         SELECT id, scholar_id, signal_type, severity, COALESCE(full_note, note), occurred_at, \
+                source_key, created_at, batch_id, deleted_at, org_id, tags, submitted_by, \
+                note_categories, note_sentiment, enriched_at \
+         FROM moved",
+    )
+    .bind(org_id)
+    .bind(before)
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+
+    tx.commit().await?;
+    Ok(archived)
+}
+
+/// Tags signal notes of the organization, hot and archived, with what `enrich` finds in them:
+/// only notes not enriched yet, or every note with `all`. Overflowed notes are read in full.
+/// Each page of `page_size` is written back on its own, so an interrupted run keeps its progress.
+/// Returns how many signals were enriched.
+#[tracing::instrument(skip_all)]
+pub async fn enrich_signals<F>(
+    pool: &PgPool,
+    org_id: Uuid,
+    all: bool,
+    page_size: i64,
+    enrich: F,
+) -> anyhow::Result<u64>
+where
+    F: Fn(&str) -> NoteEnrichment,
+{
+    anyhow::ensure!(page_size > 0, "page size must be positive");
+    let tables = [
+        (
+            "SELECT s.id, COALESCE(o.full_note, s.note) AS note \
+             FROM cohort_early_warning.signals s \
+             LEFT JOIN cohort_early_warning.signal_note_overflow o ON o.signal_id = s.id \
+             WHERE s.org_id = $1 AND ($2 OR s.enriched_at IS NULL) \
+             AND ($3::uuid IS NULL OR s.id > $3) ORDER BY s.id LIMIT $4",
+            "cohort_early_warning.signals",
+        ),
+        (
+            "SELECT s.id, s.note FROM cohort_early_warning.signals_archive s \
+             WHERE s.org_id = $1 AND ($2 OR s.enriched_at IS NULL) \
+             AND ($3::uuid IS NULL OR s.id > $3) ORDER BY s.id LIMIT $4",
+            "cohort_early_warning.signals_archive",
+        ),
+    ];
+    let mut enriched = 0;
+    for (select, table) in tables {
+        let mut after: Option<Uuid> = None;
+        loop {
+            let rows = sqlx::query(select)
+                .bind(org_id)
+                .bind(all)
+                .bind(after)
+                .bind(page_size)
+                .fetch_all(pool)
+                .await?;
+            let (mut ids, mut categories, mut sentiments) = (Vec::new(), Vec::new(), Vec::new());
+            for row in &rows {
+                let note: String = row.get("note");
+                if notes::is_sealed(&note) && notes::key().is_none() {
+                    anyhow::bail!("signal notes are encrypted; set the note key ([notes] key_env or key_command) to enrich them");
+                }
+                let enrichment = enrich(&notes::open(note)?);
+                ids.push(row.get::<Uuid, _>("id"));
+                // Category names are validated to hold no commas.
+                categories.push(enrichment.categories.join(","));
+                sentiments.push(enrichment.sentiment);
+            }
+            if !ids.is_empty() {
+                enriched += sqlx::query(&format!(
+                    "UPDATE {table} t SET \
+                     note_categories = COALESCE(string_to_array(NULLIF(v.categories, ''), ','), '{{}}'), \
+                     note_sentiment = v.sentiment, enriched_at = NOW() \
+                     FROM UNNEST($1::uuid[], $2::text[], $3::float8[]) AS v(id, categories, sentiment) \
+                     WHERE t.id = v.id"
+                ))
+                .bind(&ids)
+                .bind(&categories)
+                .bind(&sentiments)
+                .execute(pool)
+                .await?
+                .rows_affected();
+            }
+            after = ids.last().copied();
+            if (rows.len() as i64) < page_size {
+                break;
+            }
+        }
+    }
+    Ok(enriched)
+}
+
+/// Passes every stored signal note of the organization, hot, archived, and overflowed, through
+/// `rewrite`, such as encrypting notes stored before a key was set. Returns how many changed.
+#[tracing::instrument(skip_all)]
+pub async fn rewrite_notes<F>(pool: &PgPool, org_id: Uuid, rewrite: F) -> anyhow::Result<u64>
+where
+    F: Fn(String) -> anyhow::Result<String>,
+{
+    let tables = [
+        (
+            "SELECT id, note FROM cohort_early_warning.signals WHERE org_id = $1",
+            "UPDATE cohort_early_warning.signals t SET note = v.note \
+             FROM UNNEST($1::uuid[], $2::text[]) AS v(id, note) WHERE t.id = v.id",
+        ),
+        (
+            "SELECT id, note FROM cohort_early_warning.signals_archive WHERE org_id = $1",
+            "UPDATE cohort_early_warning.signals_archive t SET note = v.note \
+             FROM UNNEST($1::uuid[], $2::text[]) AS v(id, note) WHERE t.id = v.id",
+        ),
+        (
+            "SELECT o.signal_id AS id, o.full_note AS note \
+             FROM cohort_early_warning.signal_note_overflow o \
+             JOIN cohort_early_warning.signals s ON s.id = o.signal_id WHERE s.org_id = $1",
+            "UPDATE cohort_early_warning.signal_note_overflow t SET full_note = v.note \
+             FROM UNNEST($1::uuid[], $2::text[]) AS v(id, note) WHERE t.signal_id = v.id",
+        ),
+    ];
+    let mut tx = pool.begin().await?;
+    let mut changed = 0;
+    for (select, update) in tables {
+        let (mut ids, mut rewritten) = (Vec::<Uuid>::new(), Vec::new());
+        for row in sqlx::query(select).bind(org_id).fetch_all(&mut *tx).await? {
+            let note: String = row.get("note");
+            let new_note = rewrite(note.clone())?;
+            if new_note != note {
+                ids.push(row.get("id"));
+                rewritten.push(new_note);
+            }
+        }
+        if !ids.is_empty() {
+            changed += sqlx::query(update)
+                .bind(&ids)
+                .bind(&rewritten)
+                .execute(&mut *tx)
+                .await?
+                .rows_affected();
+        }
+    }
+    tx.commit().await?;
+    Ok(changed)
+}
+
+/// Counts the signals `archive_signals` would move, for dry runs.
+#[tracing::instrument(skip_all)]
+pub async fn count_archivable_signals(
+    pool: &PgPool,
+    org_id: Uuid,
+    before: NaiveDate,
+) -> anyhow::Result<i64> {
+    let row = sqlx::query(
+        "SELECT COUNT(*) AS total FROM cohort_early_warning.signals s \
+         WHERE s.org_id = $1 AND s.occurred_at < $2 \
+         AND NOT EXISTS (SELECT 1 FROM cohort_early_warning.intervention_signals link \
+                         WHERE link.signal_id = s.id)",
+    )
+    .bind(org_id)
+    .bind(before)
+    .fetch_one(pool)
+    .await?;
+    Ok(row.get("total"))
+}
+
+/// Folds the `absorb` scholar into `keep`: signals (live and archived), interventions, outcomes,
+/// self-report links, and score snapshots move to the kept record, which also inherits the advisor
+/// and self-report consent if it has none. The absorbed record is then soft-deleted. Where both
+/// were scored in the same run the kept record's snapshot wins, since the combined score was never
+/// computed.
+#[tracing::instrument(skip_all)]
+pub async fn merge_scholars(
+    pool: &PgPool,
+    org_id: Uuid,
+    keep: &str,
+    absorb: &str,
+) -> anyhow::Result<MergeSummary> {
+    if keep == absorb {
+        anyhow::bail!("--keep and --absorb name the same scholar");
+    }
+    let mut tx = pool.begin().await?;
+
+    let mut ids = [Uuid::nil(); 2];
+    for (email, id) in [keep, absorb].into_iter().zip(&mut ids) {
+        *id = sqlx::query_scalar(
+            "SELECT id FROM cohort_early_warning.scholars \
+             WHERE org_id = $1 AND email = $2 AND deleted_at IS NULL FOR UPDATE",
+        )
+        .bind(org_id)
+        .bind(email)
+        .fetch_optional(&mut *tx)
+        .await?
+        .with_context(|| format!("no active scholar found with email {email}"))?;
+    }
+    let [keep_id, absorb_id] = ids;
+
+    let overlapping_snapshots = sqlx::query(
+        "DELETE FROM cohort_early_warning.score_snapshots a \
+         USING cohort_early_warning.score_snapshots k \
+         WHERE a.scholar_id = $2 AND k.scholar_id = $1 AND k.run_id = a.run_id",
+    )
+    .bind(keep_id)
+    .bind(absorb_id)
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+
+    let tables = [
+        "signals",
+        "signals_archive",
+        "interventions",
+        "score_snapshots",
+        "self_report_tokens",
+        "sent_alerts",
+        "notification_scholars",
+        "outcomes",
+    ];
+    let mut moved = [0u64; 8];
+    for (table, count) in tables.into_iter().zip(&mut moved) {
+        *count = sqlx::query(&format!(
+            "UPDATE cohort_early_warning.{table} SET scholar_id = $1 WHERE scholar_id = $2"
+        ))
+        .bind(keep_id)
+        .bind(absorb_id)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+    }
+
+    sqlx::query(
+        "UPDATE cohort_early_warning.scholars k \
+         SET advisor_id = COALESCE(k.advisor_id, a.advisor_id), \
+             self_report_consent_at = COALESCE(k.self_report_consent_at, a.self_report_consent_at) \
+         FROM cohort_early_warning.scholars a WHERE k.id = $1 AND a.id = $2",
+    )
+    .bind(keep_id)
+    .bind(absorb_id)
+    .execute(&mut *tx)
+    .await?;
+    sqlx::query("UPDATE cohort_early_warning.scholars SET deleted_at = NOW() WHERE id = $1")
+        .bind(absorb_id)
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+    Ok(MergeSummary {
+        signals: moved[0] + moved[1],
+        interventions: moved[2],
+        snapshots: moved[3],
+        overlapping_snapshots,
+    })
+}
+
+/// Replaces a scholar's name and email with pseudonyms; the id keeps the email unique.
+const ANONYMIZE_SCHOLAR: &str = "full_name = 'Scholar ' || left(id::text, 8), \
+     email = 'anonymized-' || id || '@invalid', anonymized_at = NOW()";
+
+/// Drops every cached result of the org. Erasure calls this in its transaction, as cached report
+/// bodies still name the scholars it anonymizes or deletes.
+async fn clear_cache(tx: &mut PgConnection, org_id: Uuid) -> anyhow::Result<()> {
+    sqlx::query("DELETE FROM cohort_early_warning.score_cache WHERE org_id = $1")
+        .bind(org_id)
+        .execute(&mut *tx)
+        .await?;
+    Ok(())
+}
+
+/// Wraps a statement returning signal ids so their overflowed notes and attachments are removed
+/// too; the result is a single `total` of signals the statement touched.
+fn drop_notes_of(touched: &str) -> String {
+    format!(
+        "WITH touched AS ({touched}), \
+         overflow AS (DELETE FROM cohort_early_warning.signal_note_overflow o \
+                      USING touched WHERE o.signal_id = touched.id), \
+         attachments AS (DELETE FROM cohort_early_warning.signal_attachments a \
+                         USING touched WHERE a.signal_id = touched.id) \
+         SELECT COUNT(*) AS total FROM touched"
+    )
+}
+
+/// Irreversibly replaces a scholar's name and email with pseudonyms and blanks the free text
+/// written about them (signal, intervention, and outcome notes, attachments). Signal types,
+/// severities, and dates stay so aggregate statistics and trends are unchanged. Cached results of
+/// the org are dropped with them. Returns the pseudonymous email and how many signals were
+/// scrubbed.
+#[tracing::instrument(skip_all)]
+pub async fn anonymize_scholar(
+    pool: &PgPool,
+    org_id: Uuid,
+    email: &str,
+) -> anyhow::Result<(String, u64)> {
+    let mut tx = pool.begin().await?;
+
+    let row = sqlx::query(&format!(
+        "UPDATE cohort_early_warning.scholars SET {ANONYMIZE_SCHOLAR} \
+         WHERE org_id = $1 AND email = $2 AND anonymized_at IS NULL \
+         RETURNING id, email"
+    ))
+    .bind(org_id)
+    .bind(email)
+    .fetch_optional(&mut *tx)
+    .await?
+    .with_context(|| format!("no scholar found with email {email}"))?;
+    let scholar_id: Uuid = row.get("id");
+
+    let mut signals = 0;
+    for table in ["signals", "signals_archive"] {
+        let row = sqlx::query(&drop_notes_of(&format!(
+            "UPDATE cohort_early_warning.{table} SET note = '' WHERE scholar_id = $1 RETURNING id"
+        )))
+        .bind(scholar_id)
+        .fetch_one(&mut *tx)
+        .await?;
+        signals += row.get::<i64, _>("total") as u64;
+    }
+    for table in ["interventions", "outcomes"] {
+        sqlx::query(&format!(
+            "UPDATE cohort_early_warning.{table} SET note = '' WHERE scholar_id = $1"
+        ))
+        .bind(scholar_id)
+        .execute(&mut *tx)
+        .await?;
+    }
+    clear_cache(&mut tx, org_id).await?;
+
+    tx.commit().await?;
+    Ok((row.get("email"), signals))
+}
+
+/// Applies `plan` in one transaction. Signals, live and archived, that occurred before the
+/// signals cutoff are deleted or anonymized, then the scholars with no signals, interventions, or
+/// outcomes since; notes of signals older than the notes cutoff are blanked; and interventions
+/// older than theirs are deleted or have their notes blanked; cached results of the org are dropped
+/// too. With `dry_run` the changes are
+/// rolled back, so the summary reports exactly what a real run would touch.
+///
+/// Anonymizing blanks signal notes and drops their attachments but keeps type, severity, date,
+/// and tags so aggregate statistics stay intact.
+#[tracing::instrument(skip_all)]
+pub async fn purge_expired(
+    pool: &PgPool,
+    org_id: Uuid,
+    plan: &RetentionPlan,
+    dry_run: bool,
+) -> anyhow::Result<PurgeSummary> {
+    let mut tx = pool.begin().await?;
+    let mut summary = PurgeSummary::default();
+
+    if let Some((before, mode)) = plan.signals {
+        let (signals, archived_signals) = purge_signals(&mut tx, org_id, before, mode).await?;
+        summary.signals = signals;
+        summary.archived_signals = archived_signals;
+        summary.scholars = purge_scholars(&mut tx, org_id, before, mode).await?;
+    }
+    if let Some(before) = plan.notes {
+        let (signals, archived_signals) =
+            purge_signals(&mut tx, org_id, before, RetentionMode::Anonymize).await?;
+        summary.notes = signals + archived_signals;
+    }
+    if let Some((before, mode)) = plan.interventions {
+        let touched = match mode {
+            RetentionMode::Delete => {
+                "DELETE FROM cohort_early_warning.interventions \
+                 WHERE org_id = $1 AND occurred_on < $2"
+            }
+            RetentionMode::Anonymize => {
+                "UPDATE cohort_early_warning.interventions SET note = '' \
+                 WHERE org_id = $1 AND occurred_on < $2 AND note <> ''"
+            }
+        };
+        summary.interventions = sqlx::query(touched)
+            .bind(org_id)
+            .bind(before)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+    }
+    clear_cache(&mut tx, org_id).await?;
+
+    if dry_run {
+        tx.rollback().await?;
+    } else {
+        tx.commit().await?;
+    }
+    Ok(summary)
+}
+
+/// Deletes or anonymizes live and archived signals that occurred before `before`, returning how
+/// many of each were touched.
+async fn purge_signals(
+    tx: &mut PgConnection,
+    org_id: Uuid,
+    before: NaiveDate,
+    mode: RetentionMode,
+) -> anyhow::Result<(u64, u64)> {
+    let mut counts = [0u64; 2];
+    for (table, count) in ["signals", "signals_archive"].into_iter().zip(&mut counts) {
+        let touched = match mode {
+            RetentionMode::Delete => format!(
+                "DELETE FROM cohort_early_warning.{table} s \
+                 WHERE s.org_id = $1 AND s.occurred_at < $2 RETURNING s.id"
+            ),
+            RetentionMode::Anonymize => format!(
+                "UPDATE cohort_early_warning.{table} s SET note = '' \
+                 WHERE s.org_id = $1 AND s.occurred_at < $2 \
+                 AND (s.note <> '' OR EXISTS (SELECT 1 FROM cohort_early_warning.signal_attachments a \
+                                              WHERE a.signal_id = s.id)) \
+                 RETURNING s.id"
+            ),
+        };
+        let row = sqlx::query(&drop_notes_of(&touched))
+            .bind(org_id)
+            .bind(before)
+            .fetch_one(&mut *tx)
+            .await?;
+        *count = row.get::<i64, _>("total") as u64;
+    }
+    Ok((counts[0], counts[1]))
+}
+
+/// Deletes or anonymizes scholars created before `before` with no signals, interventions, or
+/// outcomes since, returning how many were touched.
+async fn purge_scholars(
+    tx: &mut PgConnection,
+    org_id: Uuid,
+    before: NaiveDate,
+    mode: RetentionMode,
+) -> anyhow::Result<u64> {
+    let expired = "sc.org_id = $1 AND sc.created_at < $2 \
+         AND NOT EXISTS (SELECT 1 FROM cohort_early_warning.signals_with_archive s \
+                         WHERE s.scholar_id = sc.id AND s.occurred_at >= $2) \
+         AND NOT EXISTS (SELECT 1 FROM cohort_early_warning.interventions i \
+                         WHERE i.scholar_id = sc.id AND i.occurred_on >= $2) \
+         AND NOT EXISTS (SELECT 1 FROM cohort_early_warning.outcomes o \
+                         WHERE o.scholar_id = sc.id AND o.occurred_on >= $2)";
+    let scholars = match mode {
+        RetentionMode::Delete => {
+            format!("DELETE FROM cohort_early_warning.scholars sc WHERE {expired}")
+        }
+        RetentionMode::Anonymize => format!(
+            "UPDATE cohort_early_warning.scholars sc SET {ANONYMIZE_SCHOLAR} \
+             WHERE {expired} AND sc.anonymized_at IS NULL"
+        ),
+    };
+    Ok(sqlx::query(&scholars)
+        .bind(org_id)
+        .bind(before)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected())
+}
+
+#[derive(Debug, Clone)]
+pub struct NewIntervention {
+    pub scholar_email: String,
+    pub occurred_on: NaiveDate,
+    pub intervention_type: String,
+    pub outcome: Option<String>,
+    pub note: String,
+    /// Source keys of the signals being responded to; each must belong to the scholar.
+    pub signal_keys: Vec<String>,
+}
+
+/// Records an intervention and links it to its signals in one transaction.
+#[tracing::instrument(skip_all)]
+pub async fn add_intervention(
+    pool: &PgPool,
+    org_id: Uuid,
+    intervention: &NewIntervention,
+) -> anyhow::Result<Uuid> {
+    let mut tx = pool.begin().await?;
+
+    let scholar_id: Uuid = sqlx::query(
+        "SELECT id FROM cohort_early_warning.scholars \
+         WHERE org_id = $1 AND email = $2 AND deleted_at IS NULL",
+    )
+    .bind(org_id)
+    .bind(&intervention.scholar_email)
+    .fetch_optional(&mut *tx)
+    .await?
+    .with_context(|| format!("no scholar found with email {}", intervention.scholar_email))?
+    .get("id");
+
+    let intervention_id = Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO cohort_early_warning.interventions \
+         (id, org_id, scholar_id, occurred_on, intervention_type, outcome, note) \
+         VALUES ($1, $2, $3, $4, $5, $6, $7)",
+    )
+    .bind(intervention_id)
+    .bind(org_id)
+    .bind(scholar_id)
+    .bind(intervention.occurred_on)
+    .bind(&intervention.intervention_type)
+    .bind(&intervention.outcome)
+    .bind(&intervention.note)
+    .execute(&mut *tx)
+    .await?;
+
+    for source_key in &intervention.signal_keys {
+        let linked = sqlx::query(
+            "INSERT INTO cohort_early_warning.intervention_signals (intervention_id, signal_id) \
+             SELECT $1, id FROM cohort_early_warning.signals \
+             WHERE org_id = $2 AND source_key = $3 AND scholar_id = $4 AND deleted_at IS NULL \
+             ON CONFLICT DO NOTHING",
+        )
+        .bind(intervention_id)
+        .bind(org_id)
+        .bind(source_key)
+        .bind(scholar_id)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+        if linked == 0 {
+            anyhow::bail!(
+                "no active signal {source_key} found for {}",
+                intervention.scholar_email
+            );
+        }
+    }
+
+    tx.commit().await?;
+    Ok(intervention_id)
+}
+
+/// Interventions newest first, optionally limited to one scholar or cohort.
+#[tracing::instrument(skip_all)]
+pub async fn list_interventions(
+    pool: &PgPool,
+    org_id: Uuid,
+    email: Option<&str>,
+    cohort: Option<&str>,
+    limit: i64,
+) -> anyhow::Result<Vec<Intervention>> {
+    let mut query = QueryBuilder::new(
+        "SELECT i.id, sc.full_name, sc.email, i.occurred_on, i.intervention_type, i.outcome, i.note, \
+         i.closed_at, COALESCE(ARRAY_AGG(s.source_key ORDER BY s.source_key) \
+         FILTER (WHERE s.id IS NOT NULL), '{}') AS signal_keys \
+         FROM cohort_early_warning.interventions i \
+         JOIN cohort_early_warning.scholars sc ON sc.id = i.scholar_id \
+         LEFT JOIN cohort_early_warning.intervention_signals link ON link.intervention_id = i.id \
+         LEFT JOIN cohort_early_warning.signals s ON s.id = link.signal_id \
+         WHERE sc.deleted_at IS NULL AND i.org_id = ",
+    );
+    query.push_bind(org_id);
+
+    if let Some(email) = email {
+        query.push(" AND sc.email = ");
+        query.push_bind(email.to_string());
+    }
+
+    if let Some(cohort) = cohort {
+        query.push(" AND sc.cohort = cohort_early_warning.canonical_cohort(");
+        query.push_bind(org_id);
+        query.push(", ");
+        query.push_bind(cohort.to_string());
+        query.push(")");
+    }
+
+    query.push(" GROUP BY i.id, sc.full_name, sc.email ORDER BY i.occurred_on DESC, i.created_at DESC LIMIT ");
+    query.push_bind(limit);
+
+    let records = query.build().fetch_all(pool).await?;
+    Ok(records
+        .into_iter()
+        .map(|row| Intervention {
+            id: row.get("id"),
+            scholar_name: row.get("full_name"),
+            scholar_email: row.get("email"),
+            occurred_on: row.get("occurred_on"),
+            intervention_type: row.get("intervention_type"),
+            outcome: row.get("outcome"),
+            note: row.get("note"),
+            signal_keys: row.get("signal_keys"),
+            closed_at: row.get("closed_at"),
+        })
+        .collect())
+}
+
+/// Records the final outcome of an open intervention and marks it closed.
+#[tracing::instrument(skip_all)]
+pub async fn close_intervention(
+    pool: &PgPool,
+    org_id: Uuid,
+    id: Uuid,
+    outcome: &str,
+) -> anyhow::Result<()> {
+    let result = sqlx::query(
+        "UPDATE cohort_early_warning.interventions SET outcome = $1, closed_at = NOW() \
+         WHERE id = $2 AND org_id = $3 AND closed_at IS NULL",
+    )
+    .bind(outcome)
+    .bind(id)
+    .bind(org_id)
+    .execute(pool)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        anyhow::bail!("no open intervention found with id {id}");
+    }
+    Ok(())
+}
+
+/// Email of each assigned scholar's advisor, by scholar email.
+#[tracing::instrument(skip_all)]
+pub async fn scholar_advisor_emails(
+    pool: &PgPool,
+    org_id: Uuid,
+) -> anyhow::Result<HashMap<String, String>> {
+    Ok(sqlx::query_as(
+        "SELECT sc.email, a.email FROM cohort_early_warning.scholars sc \
+         JOIN cohort_early_warning.advisors a ON a.id = sc.advisor_id \
+         WHERE sc.org_id = $1 AND sc.deleted_at IS NULL",
+    )
+    .bind(org_id)
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .collect())
+}
+
+/// Follow-up statuses last pulled from `source`, by the tracker's record ID.
+#[tracing::instrument(skip_all)]
+pub async fn external_follow_ups(
+    pool: &PgPool,
+    org_id: Uuid,
+    source: &str,
+) -> anyhow::Result<HashMap<String, String>> {
+    let rows = sqlx::query(
+        "SELECT record_id, status FROM cohort_early_warning.external_follow_ups \
+         WHERE org_id = $1 AND source = $2",
+    )
+    .bind(org_id)
+    .bind(source)
+    .fetch_all(pool)
+    .await?;
+    Ok(rows
+        .into_iter()
+        .map(|row| (row.get("record_id"), row.get("status")))
+        .collect())
+}
+
+/// Records a follow-up status pulled from `source` and the intervention it was logged as.
+#[tracing::instrument(skip_all)]
+pub async fn set_external_follow_up(
+    pool: &PgPool,
+    org_id: Uuid,
+    source: &str,
+    record_id: &str,
+    status: &str,
+    intervention_id: Uuid,
+) -> anyhow::Result<()> {
+    sqlx::query(
+        "INSERT INTO cohort_early_warning.external_follow_ups \
+         (org_id, source, record_id, status, intervention_id) \
+         VALUES ($1, $2, $3, $4, $5) \
+         ON CONFLICT (org_id, source, record_id) DO UPDATE \
+         SET status = EXCLUDED.status, intervention_id = EXCLUDED.intervention_id, \
+         updated_at = NOW()",
+    )
+    .bind(org_id)
+    .bind(source)
+    .bind(record_id)
+    .bind(status)
+    .bind(intervention_id)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+#[derive(Debug, Clone)]
+pub struct NewOutcome {
+    pub scholar_email: String,
+    pub outcome_type: OutcomeType,
+    pub occurred_on: NaiveDate,
+    /// Required for GPA milestones, refused for the others.
+    pub gpa: Option<f64>,
+    pub note: String,
+}
+
+/// Records an outcome. Withdrawals and graduations also set the scholar's status, in the same
+/// transaction.
+#[tracing::instrument(skip_all)]
+pub async fn add_outcome(
+    pool: &PgPool,
+    org_id: Uuid,
+    outcome: &NewOutcome,
+) -> anyhow::Result<Uuid> {
+    match (outcome.outcome_type, outcome.gpa) {
+        (OutcomeType::Gpa, None) => anyhow::bail!("a gpa outcome needs --gpa"),
+        (OutcomeType::Gpa, Some(gpa)) if !(0.0..=5.0).contains(&gpa) => {
+            anyhow::bail!("GPA {gpa} is outside 0-5")
+        }
+        (OutcomeType::Withdrawal | OutcomeType::Graduation, Some(_)) => {
+            anyhow::bail!("--gpa only applies to gpa outcomes")
+        }
+        _ => {}
+    }
+    let mut tx = pool.begin().await?;
+
+    let scholar_id: Uuid = sqlx::query_scalar(
+        "SELECT id FROM cohort_early_warning.scholars \
+         WHERE org_id = $1 AND email = $2 AND deleted_at IS NULL",
+    )
+    .bind(org_id)
+    .bind(&outcome.scholar_email)
+    .fetch_optional(&mut *tx)
+    .await?
+    .with_context(|| format!("no scholar found with email {}", outcome.scholar_email))?;
+
+    let id = Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO cohort_early_warning.outcomes \
+         (id, org_id, scholar_id, outcome_type, occurred_on, gpa, note) \
+         VALUES ($1, $2, $3, $4, $5, $6, $7)",
+    )
+    .bind(id)
+    .bind(org_id)
+    .bind(scholar_id)
+    .bind(outcome.outcome_type.as_str())
+    .bind(outcome.occurred_on)
+    .bind(outcome.gpa)
+    .bind(&outcome.note)
+    .execute(&mut *tx)
+    .await?;
+
+    if let Some(status) = outcome.outcome_type.status() {
+        sqlx::query("UPDATE cohort_early_warning.scholars SET status = $1 WHERE id = $2")
+            .bind(status.as_str())
+            .bind(scholar_id)
+            .execute(&mut *tx)
+            .await?;
+    }
+
+    tx.commit().await?;
+    Ok(id)
+}
+
+/// Outcomes newest first, optionally limited to one scholar or cohort.
+#[tracing::instrument(skip_all)]
+pub async fn list_outcomes(
+    pool: &PgPool,
+    org_id: Uuid,
+    email: Option<&str>,
+    cohort: Option<&str>,
+    limit: i64,
+) -> anyhow::Result<Vec<Outcome>> {
+    let mut query = QueryBuilder::new(
+        "SELECT o.id, sc.full_name, sc.email, sc.cohort, o.outcome_type, o.occurred_on, o.gpa, \
+         o.note \
+         FROM cohort_early_warning.outcomes o \
+         JOIN cohort_early_warning.scholars sc ON sc.id = o.scholar_id \
+         WHERE sc.deleted_at IS NULL AND o.org_id = ",
+    );
+    query.push_bind(org_id);
+
+    if let Some(email) = email {
+        query.push(" AND sc.email = ");
+        query.push_bind(email.to_string());
+    }
+
+    if let Some(cohort) = cohort {
+        query.push(" AND sc.cohort = cohort_early_warning.canonical_cohort(");
+        query.push_bind(org_id);
+        query.push(", ");
+        query.push_bind(cohort.to_string());
+        query.push(")");
+    }
+
+    query.push(" ORDER BY o.occurred_on DESC, o.created_at DESC LIMIT ");
+    query.push_bind(limit);
+
+    query
+        .build()
+        .fetch_all(pool)
+        .await?
+        .into_iter()
+        .map(|row| {
+            Ok(Outcome {
+                id: row.get("id"),
+                scholar_name: row.get("full_name"),
+                scholar_email: row.get("email"),
+                cohort: row.get("cohort"),
+                outcome_type: OutcomeType::parse(row.get("outcome_type"))?,
+                occurred_on: row.get("occurred_on"),
+                gpa: row.get("gpa"),
+                note: row.get("note"),
+            })
+        })
+        .collect()
+}
+
+/// Name and cohort of the scholar registered under `email`, if any.
+#[tracing::instrument(skip_all)]
+pub async fn scholar_profile(
+    pool: &PgPool,
+    org_id: Uuid,
+    email: &str,
+) -> anyhow::Result<Option<(String, String)>> {
+    let row = sqlx::query(
+        "SELECT full_name, cohort FROM cohort_early_warning.scholars \
+         WHERE org_id = $1 AND email = $2",
+    )
+    .bind(org_id)
+    .bind(email)
+    .fetch_optional(pool)
+    .await?;
+    Ok(row.map(|row| (row.get("full_name"), row.get("cohort"))))
+}
+
+#[tracing::instrument(skip_all)]
+pub async fn insert_signal_row(
+    pool: &PgPool,
+    org_id: Uuid,
+    row: &CsvRow,
+    batch_id: Uuid,
+) -> anyhow::Result<bool> {
+    let scholar_id: Uuid = sqlx::query(
+        r#"
+        INSERT INTO cohort_early_warning.scholars
+        (id, full_name, email, cohort, org_id)
+        VALUES ($1, $2, $3, $4, $5)
+        ON CONFLICT (org_id, email) DO UPDATE
+        SET full_name = EXCLUDED.full_name, cohort = EXCLUDED.cohort
+        RETURNING id
+        "#,
+    )
+    .bind(Uuid::new_v4())
+    .bind(&row.full_name)
+    .bind(&row.email)
+    .bind(&row.cohort)
+    .bind(org_id)
+    .fetch_one(pool)
+    .await?
+    .get("id");
+
+    let source_key = row
+        .source_key
+        .clone()
+        .unwrap_or_else(|| format!("import-{}", Uuid::new_v4()));
+
+    let signal_id = Uuid::new_v4();
+    let result = sqlx::query(
+        r#"
+        INSERT INTO cohort_early_warning.signals
+        (id, scholar_id, signal_type, severity, note, occurred_at, source_key, batch_id, org_id, tags,
+         submitted_by)
+        SELECT $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11
+        -- Archived source keys count as already imported.
+        WHERE NOT EXISTS (
+            SELECT 1 FROM cohort_early_warning.signals_archive
+            WHERE org_id = $9 AND source_key = $7
+        )
+        ON CONFLICT (org_id, source_key) DO NOTHING
+        "#,
+    )
+    .bind(signal_id)
+    .bind(scholar_id)
+    .bind(&row.signal_type)
+    .bind(row.severity)
+    .bind(notes::seal(&row.note))
+    .bind(row.occurred_at)
+    .bind(source_key)
+    .bind(batch_id)
+    .bind(org_id)
+    .bind(&row.tags)
+    .bind(row.submitted_by.as_deref().map(str::trim).filter(|name| !name.is_empty()))
+    .execute(pool)
+    .await?;
+
+    let inserted = result.rows_affected() > 0;
+    if inserted {
+        if let Some(full_note) = &row.original_note {
+            sqlx::query(
+                "INSERT INTO cohort_early_warning.signal_note_overflow (signal_id, full_note) \
+                 VALUES ($1, $2)",
+            )
+            .bind(signal_id)
+            .bind(notes::seal(full_note))
+            .execute(pool)
+            .await?;
+        }
+        for attachment in &row.attachments {
+            sqlx::query(
+                "INSERT INTO cohort_early_warning.signal_attachments \
+                 (id, org_id, signal_id, url, label, kind) VALUES ($1, $2, $3, $4, $5, $6)",
+            )
+            .bind(Uuid::new_v4())
+            .bind(org_id)
+            .bind(signal_id)
+            .bind(&attachment.url)
+            .bind(&attachment.label)
+            .bind(&attachment.kind)
+            .execute(pool)
+            .await?;
+        }
+    }
+
+    Ok(inserted)
+}
+
+/// Scholars an import run has already written, by email, with the id, name, and cohort stored,
+/// so each scholar is upserted once per run rather than once per row.
+#[derive(Debug, Default)]
+pub struct ScholarCache {
+    scholars: HashMap<String, (Uuid, String, String)>,
+}
+
+impl ScholarCache {
+    /// The last row for each scholar in `rows` whose name or cohort the database may not hold
+    /// yet. Later rows win, as they would written one at a time.
+    fn pending<'a>(&self, rows: &'a [CsvRow]) -> Vec<&'a CsvRow> {
+        let mut latest: BTreeMap<&str, &CsvRow> = BTreeMap::new();
+        for row in rows {
+            latest.insert(&row.email, row);
+        }
+        latest
+            .into_values()
+            .filter(|row| {
+                self.scholars
+                    .get(&row.email)
+                    .is_none_or(|(_, name, cohort)| *name != row.full_name || *cohort != row.cohort)
+            })
+            .collect()
+    }
+}
+
+/// Writes a chunk of import rows in one transaction with a statement per table instead of per
+/// row: scholars the cache has not seen (or whose name or cohort changed), signals, and any note
+/// overflow and attachments. Returns what happened to each row's signal, in order; a source key
+/// already stored is skipped or updated per `on_conflict`. A failure leaves nothing from the chunk
+/// behind.
+#[tracing::instrument(skip_all)]
+pub async fn insert_signal_chunk(
+    pool: &PgPool,
+    org_id: Uuid,
+    rows: &[CsvRow],
+    batch_id: Uuid,
+    cache: &mut ScholarCache,
+    on_conflict: OnConflict,
+) -> anyhow::Result<Vec<RowOutcome>> {
+    let mut tx = pool.begin().await?;
+    let (outcomes, written) =
+        write_signal_chunk(&mut tx, org_id, rows, batch_id, cache, on_conflict).await?;
+    tx.commit().await?;
+    cache.scholars.extend(written.scholars);
+    Ok(outcomes)
+}
+
+/// [`insert_signal_chunk`] inside the caller's transaction `tx`, which decides whether the chunk
+/// is kept. `cache` learns the chunk's scholars straight away, so drop it if `tx` rolls back.
+#[tracing::instrument(skip_all)]
+pub async fn insert_signal_chunk_in(
+    tx: &mut PgConnection,
+    org_id: Uuid,
+    rows: &[CsvRow],
+    batch_id: Uuid,
+    cache: &mut ScholarCache,
+    on_conflict: OnConflict,
+) -> anyhow::Result<Vec<RowOutcome>> {
+    let (outcomes, written) =
+        write_signal_chunk(tx, org_id, rows, batch_id, cache, on_conflict).await?;
+    cache.scholars.extend(written.scholars);
+    Ok(outcomes)
+}
+
+/// Writes the chunk within `tx` without committing it. Returns what happened to each row's signal
+/// and the scholars it wrote, for the caller to add to `cache` once they are committed.
+async fn write_signal_chunk(
+    tx: &mut PgConnection,
+    org_id: Uuid,
+    rows: &[CsvRow],
+    batch_id: Uuid,
+    cache: &ScholarCache,
+    on_conflict: OnConflict,
+) -> anyhow::Result<(Vec<RowOutcome>, ScholarCache)> {
+    let pending = cache.pending(rows);
+    let mut written: HashMap<&str, (Uuid, &CsvRow)> = HashMap::new();
+    if !pending.is_empty() {
+        let ids: Vec<Uuid> = pending.iter().map(|_| Uuid::new_v4()).collect();
+        let names: Vec<&str> = pending.iter().map(|row| row.full_name.as_str()).collect();
+        let emails: Vec<&str> = pending.iter().map(|row| row.email.as_str()).collect();
+        let cohorts: Vec<&str> = pending.iter().map(|row| row.cohort.as_str()).collect();
+        let stored = sqlx::query(
+            "INSERT INTO cohort_early_warning.scholars (id, full_name, email, cohort, org_id) \
+             SELECT v.id, v.full_name, v.email, v.cohort, $5 \
+             FROM UNNEST($1::uuid[], $2::text[], $3::text[], $4::text[]) \
+                  AS v(id, full_name, email, cohort) \
+             ON CONFLICT (org_id, email) DO UPDATE \
+             SET full_name = EXCLUDED.full_name, cohort = EXCLUDED.cohort \
+             RETURNING id, email",
+        )
+        .bind(&ids)
+        .bind(&names)
+        .bind(&emails)
+        .bind(&cohorts)
+        .bind(org_id)
+        .fetch_all(&mut *tx)
+        .await?;
+        let stored: HashMap<String, Uuid> = stored
+            .into_iter()
+            .map(|row| (row.get("email"), row.get("id")))
+            .collect();
+        for row in pending {
+            written.insert(&row.email, (stored[&row.email], row));
+        }
+    }
+    let scholar_id = |email: &str| {
+        written
+            .get(email)
+            .map(|(id, _)| *id)
+            .or_else(|| cache.scholars.get(email).map(|(id, _, _)| *id))
+            .expect("every scholar in the chunk was cached or just written")
+    };
+
+    let ids: Vec<Uuid> = rows.iter().map(|_| Uuid::new_v4()).collect();
+    let scholar_ids: Vec<Uuid> = rows.iter().map(|row| scholar_id(&row.email)).collect();
+    let signal_types: Vec<&str> = rows.iter().map(|row| row.signal_type.as_str()).collect();
+    let severities: Vec<i32> = rows.iter().map(|row| row.severity).collect();
+    let sealed_notes: Vec<String> = rows.iter().map(|row| notes::seal(&row.note)).collect();
+    let occurred: Vec<NaiveDate> = rows.iter().map(|row| row.occurred_at).collect();
+    let source_keys: Vec<String> = rows
+        .iter()
+        .map(|row| {
+            row.source_key
+                .clone()
+                .unwrap_or_else(|| format!("import-{}", Uuid::new_v4()))
+        })
+        .collect();
+    // Rows carry different numbers of tags, which a two-dimensional array cannot hold.
+    let tags: Vec<String> = rows
+        .iter()
+        .map(|row| serde_json::to_string(&row.tags))
+        .collect::<Result<_, _>>()?;
+    let submitted_by: Vec<Option<&str>> = rows
+        .iter()
+        .map(|row| {
+            row.submitted_by
+                .as_deref()
+                .map(str::trim)
+                .filter(|name| !name.is_empty())
+        })
+        .collect();
+    // Of rows sharing a source key, only the one that would win written a row at a time is sent:
+    // the first when skipping, the last when updating. A statement can't update a row twice.
+    let mut sent: HashMap<&str, usize> = HashMap::new();
+    for (index, key) in source_keys.iter().enumerate() {
+        match on_conflict {
+            OnConflict::Skip => {
+                sent.entry(key).or_insert(index);
+            }
+            OnConflict::Update => {
+                sent.insert(key, index);
+            }
+        }
+    }
+    let positions: Vec<i64> = sent.values().map(|&index| index as i64 + 1).collect();
+    let conflict = match on_conflict {
+        OnConflict::Skip => "DO NOTHING",
+        OnConflict::Update => {
+            "DO UPDATE SET severity = EXCLUDED.severity, note = EXCLUDED.note, \
+             occurred_at = EXCLUDED.occurred_at, \
+             enriched_at = CASE WHEN s.note = EXCLUDED.note THEN s.enriched_at END \
+             WHERE (s.severity, s.note, s.occurred_at) \
+                   IS DISTINCT FROM (EXCLUDED.severity, EXCLUDED.note, EXCLUDED.occurred_at)"
+        }
+    };
+    // Archived source keys count as already imported.
+    let stored: HashMap<String, Uuid> = sqlx::query(&format!(
+        "INSERT INTO cohort_early_warning.signals AS s \
+         (id, scholar_id, signal_type, severity, note, occurred_at, source_key, batch_id, org_id, \
+          tags, submitted_by) \
+         SELECT v.id, v.scholar_id, v.signal_type, v.severity, v.note, v.occurred_at, \
+                v.source_key, $9, $10, ARRAY(SELECT jsonb_array_elements_text(v.tags::jsonb)), \
+                v.submitted_by \
+         FROM UNNEST($1::uuid[], $2::uuid[], $3::text[], $4::int[], $5::text[], $6::date[], \
+                     $7::text[], $8::text[], $11::text[]) WITH ORDINALITY \
+              AS v(id, scholar_id, signal_type, severity, note, occurred_at, source_key, tags, \
+                   submitted_by, position) \
+         WHERE v.position = ANY($12) AND NOT EXISTS ( \
+             SELECT 1 FROM cohort_early_warning.signals_archive a \
+             WHERE a.org_id = $10 AND a.source_key = v.source_key) \
+         ORDER BY v.position \
+         ON CONFLICT (org_id, source_key) {conflict} \
+         RETURNING s.id, s.source_key",
+    ))
+    .bind(&ids)
+    .bind(&scholar_ids)
+    .bind(&signal_types)
+    .bind(&severities)
+    .bind(&sealed_notes)
+    .bind(&occurred)
+    .bind(&source_keys)
+    .bind(&tags)
+    .bind(batch_id)
+    .bind(org_id)
+    .bind(&submitted_by)
+    .bind(&positions)
+    .fetch_all(&mut *tx)
+    .await?
+    .into_iter()
+    .map(|row| (row.get("source_key"), row.get("id")))
+    .collect();
+    let outcomes: Vec<RowOutcome> = source_keys
+        .iter()
+        .enumerate()
+        .map(|(index, key)| match stored.get(key) {
+            Some(id) if sent[key.as_str()] == index && *id == ids[index] => RowOutcome::Inserted,
+            Some(_) if sent[key.as_str()] == index => RowOutcome::Updated,
+            _ => RowOutcome::Skipped,
+        })
+        .collect();
+
+    // An updated signal's overflow is replaced along with its note.
+    let updated: Vec<Uuid> = source_keys
+        .iter()
+        .zip(&outcomes)
+        .filter(|(_, outcome)| **outcome == RowOutcome::Updated)
+        .map(|(key, _)| stored[key])
+        .collect();
+    if !updated.is_empty() {
+        sqlx::query(
+            "DELETE FROM cohort_early_warning.signal_note_overflow WHERE signal_id = ANY($1)",
+        )
+        .bind(&updated)
+        .execute(&mut *tx)
+        .await?;
+    }
+    let (mut overflow_ids, mut overflow_notes) = (Vec::new(), Vec::new());
+    let (mut attachment_signals, mut urls, mut labels, mut kinds) =
+        (Vec::new(), Vec::new(), Vec::new(), Vec::new());
+    for ((row, key), outcome) in rows.iter().zip(&source_keys).zip(&outcomes) {
+        if *outcome == RowOutcome::Skipped {
+            continue;
+        }
+        let id = stored[key];
+        if let Some(full_note) = &row.original_note {
+            overflow_ids.push(id);
+            overflow_notes.push(notes::seal(full_note));
+        }
+        if *outcome == RowOutcome::Updated {
+            continue;
+        }
+        for attachment in &row.attachments {
+            attachment_signals.push(id);
+            urls.push(attachment.url.as_str());
+            labels.push(attachment.label.as_str());
+            kinds.push(attachment.kind.as_str());
+        }
+    }
+    if !overflow_ids.is_empty() {
+        sqlx::query(
+            "INSERT INTO cohort_early_warning.signal_note_overflow (signal_id, full_note) \
+             SELECT * FROM UNNEST($1::uuid[], $2::text[])",
+        )
+        .bind(&overflow_ids)
+        .bind(&overflow_notes)
+        .execute(&mut *tx)
+        .await?;
+    }
+    if !attachment_signals.is_empty() {
+        let attachment_ids: Vec<Uuid> = urls.iter().map(|_| Uuid::new_v4()).collect();
+        sqlx::query(
+            "INSERT INTO cohort_early_warning.signal_attachments \
+             (id, org_id, signal_id, url, label, kind) \
+             SELECT v.id, $2, v.signal_id, v.url, v.label, v.kind \
+             FROM UNNEST($1::uuid[], $3::uuid[], $4::text[], $5::text[], $6::text[]) \
+                  AS v(id, signal_id, url, label, kind)",
+        )
+        .bind(&attachment_ids)
+        .bind(org_id)
+        .bind(&attachment_signals)
+        .bind(&urls)
+        .bind(&labels)
+        .bind(&kinds)
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    let written = ScholarCache {
+        scholars: written
+            .into_iter()
+            .map(|(email, (id, row))| {
+                (
+                    email.to_string(),
+                    (id, row.full_name.clone(), row.cohort.clone()),
+                )
+            })
+            .collect(),
+    };
+    Ok((outcomes, written))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filter_sql(filter: &SignalFilter) -> String {
+        let mut query = QueryBuilder::new("SELECT 1 FROM t");
+        filter.push_conditions(&mut query);
+        query.sql().to_string()
+    }
+
+    #[test]
+    fn signal_filter_excludes_inactive_scholars_by_default() {
+        let mut filter = SignalFilter::new(NaiveDate::from_ymd_opt(2026, 1, 1).unwrap());
+        filter.org_id = Some(DEFAULT_ORG_ID);
+        filter.cohorts = vec!["2026".to_string()];
+
+        assert_eq!(
+            filter_sql(&filter),
+            "SELECT 1 FROM t WHERE s.deleted_at IS NULL AND sc.deleted_at IS NULL \
+             AND s.occurred_at >= $1 AND sc.org_id = $2 \
+             AND sc.cohort = ANY(ARRAY(SELECT cohort_early_warning.canonical_cohort($3, c) \
+             FROM UNNEST($4::text[]) AS c)) \
+             AND sc.status = 'active'"
+        );
+
+        filter.include_inactive = true;
+        assert!(!filter_sql(&filter).contains("sc.status"));
+    }
+
+    #[test]
+    fn cache_keys_hash_the_serialized_scope_and_date() {
+        let day = NaiveDate::from_ymd_opt(2026, 3, 10).unwrap();
+        // SHA-256 of `[["2026",30],"2026-03-10"]`, the same in every build.
+        assert_eq!(
+            cache_key("scores", &("2026", 30), day).unwrap(),
+            "scores:a86c1cfa01045be5466ff7c41122ea42b963edefb799f3a4e95cb79d42b7d917"
+        );
+        assert_ne!(
+            cache_key("scores", &("2026", 30), day).unwrap(),
+            cache_key("scores", &("2026", 30), day.succ_opt().unwrap()).unwrap()
+        );
+        assert_ne!(
+            cache_key("scores", &("2026", 30), day).unwrap(),
+            cache_key("scores", &("2026", 60), day).unwrap()
+        );
+    }
+
+    #[test]
+    fn keyless_reads_never_see_notes_cached_under_a_key() {
+        let day = NaiveDate::from_ymd_opt(2026, 3, 10).unwrap();
+        let key = notes::NoteKey::parse(&notes::NoteKey::generate()).unwrap();
+        let report = "# Cohort Early Warning Report\n- Family moved; missed two weeks\n";
+
+        assert_ne!(
+            entry_key("report", &("2026", 30), day, Some(&key)).unwrap(),
+            entry_key("report", &("2026", 30), day, None).unwrap()
+        );
+        let body = entry_body(&report, Some(&key)).unwrap();
+        assert!(!body.contains("Family"));
+        assert_eq!(entry_value::<String>(body.clone(), None), None);
+        let other = notes::NoteKey::parse(&notes::NoteKey::generate()).unwrap();
+        assert_eq!(entry_value::<String>(body.clone(), Some(&other)), None);
+        assert_eq!(
+            entry_value::<String>(body, Some(&key)).as_deref(),
+            Some(report)
+        );
+
+        let keyless = entry_body(&"[encrypted note]", None).unwrap();
+        assert_eq!(
+            entry_value::<String>(keyless, None).as_deref(),
+            Some("[encrypted note]")
+        );
+    }
+
+    #[test]
+    fn backs_off_exponentially_on_transient_errors() {
+        let policy = RetryPolicy {
+            attempts: 4,
+            initial_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(5),
+        };
+        let dropped = anyhow::Error::from(sqlx::Error::Io(std::io::Error::from(
+            std::io::ErrorKind::ConnectionReset,
+        )))
+        .context("failed to write chunk");
+        let delays: Vec<Option<u64>> = (0..5)
+            .map(|attempt| policy.backoff(&dropped, attempt).map(|d| d.as_secs()))
+            .collect();
+        assert_eq!(delays, [Some(1), Some(2), Some(4), Some(5), None]);
+
+        let bad_row = anyhow::Error::from(sqlx::Error::RowNotFound);
+        assert_eq!(policy.backoff(&bad_row, 0), None);
+    }
+
+    #[test]
+    fn reads_severity_bounds_from_the_check_constraint() {
+        assert_eq!(
+            parse_severity_check("CHECK (((severity >= 1) AND (severity <= 5)))"),
+            Some(SeverityBounds { min: 1, max: 5 })
+        );
+        assert_eq!(
+            parse_severity_check("CHECK (((severity >= '-2'::integer) AND (severity <= 10)))"),
+            Some(SeverityBounds { min: -2, max: 10 })
+        );
+        assert_eq!(parse_severity_check("CHECK ((severity > 0))"), None);
+    }
+
+    #[test]
+    fn finds_sequential_scans_in_a_plan() {
+        let plan = serde_json::json!([{
+            "Plan": {
+                "Node Type": "Hash Join",
+                "Plans": [
+                    {"Node Type": "Seq Scan", "Relation Name": "signals"},
+                    {"Node Type": "Hash", "Plans": [
+                        {"Node Type": "Index Scan", "Relation Name": "scholars"},
+                        {"Node Type": "Seq Scan", "Relation Name": "signals"}
+                    ]}
+                ]
+            }
+        }]);
+        assert_eq!(seq_scan_relations(&plan), ["signals"]);
+    }
+
+    #[test]
+    fn signal_filter_scopes_to_advisor_caseload() {
+        let mut filter = SignalFilter::new(NaiveDate::from_ymd_opt(2026, 1, 1).unwrap());
+        filter.advisor = Some("jordan@groupscholar.com".to_string());
+        assert!(filter_sql(&filter).contains(
+            "AND sc.advisor_id IN (SELECT a.id FROM cohort_early_warning.advisors a WHERE a.email = $2)"
+        ));
+    }
+
+    #[test]
+    fn pages_continue_after_cursor() {
+        let filter = SignalFilter::new(NaiveDate::from_ymd_opt(2026, 1, 1).unwrap());
+        let mut query = QueryBuilder::new("SELECT 1 FROM t");
+        filter.push_conditions(&mut query);
+        push_page(
+            &mut query,
+            Some(SignalCursor {
+                occurred_at: NaiveDate::from_ymd_opt(2026, 2, 1).unwrap(),
+                signal_id: Uuid::nil(),
+            }),
+            100,
+        );
+        assert!(query.sql().ends_with(
+            "AND (s.occurred_at, s.id) > ($2, $3) ORDER BY s.occurred_at, s.id LIMIT $4"
+        ));
+    }
+
+    #[test]
+    fn signal_filter_matches_any_requested_tag() {
+        let mut filter = SignalFilter::new(NaiveDate::from_ymd_opt(2026, 1, 1).unwrap());
+        assert!(!filter_sql(&filter).contains("tags"));
+
+        filter.tags = vec!["summer-bridge".to_string()];
+        assert!(filter_sql(&filter).ends_with("AND s.tags && $2"));
+    }
+
+    #[test]
+    fn signal_filter_includes_and_excludes_types() {
+        let mut filter = SignalFilter::new(NaiveDate::from_ymd_opt(2026, 1, 1).unwrap());
+        filter.signal_types = vec!["Attendance".to_string(), "academic".to_string()];
+        filter.exclude_signal_types = vec!["engagement".to_string()];
+        assert!(filter_sql(&filter)
+            .ends_with("AND lower(s.signal_type) = ANY($2) AND lower(s.signal_type) <> ALL($3)"));
+    }
+
+    #[test]
+    fn scholar_cache_upserts_each_changed_scholar_once() {
+        let row = |name: &str, email: &str| CsvRow {
+            full_name: name.to_string(),
+            email: email.to_string(),
+            cohort: "2026".to_string(),
+            signal_type: "attendance".to_string(),
+            severity: 3,
+            note: String::new(),
+            occurred_at: NaiveDate::from_ymd_opt(2026, 2, 2).unwrap(),
+            source_key: None,
+            tags: Vec::new(),
+            submitted_by: None,
+            attachments: Vec::new(),
+            original_note: None,
+        };
+        let mut cache = ScholarCache::default();
+        cache.scholars.insert(
+            "avery@x.org".to_string(),
+            (Uuid::nil(), "Avery Lee".to_string(), "2026".to_string()),
+        );
+        cache.scholars.insert(
+            "blake@x.org".to_string(),
+            (Uuid::nil(), "Blake Ng".to_string(), "2026".to_string()),
+        );
+        let rows = [
+            row("Avery Lee", "avery@x.org"),
+            row("Blake Ng", "blake@x.org"),
+            row("Casey Ruiz", "casey@x.org"),
+            row("Casey R.", "casey@x.org"),
+            // Renamed since it was cached.
+            row("Blake Ngo", "blake@x.org"),
+        ];
+        let pending: Vec<(&str, &str)> = cache
+            .pending(&rows)
+            .iter()
+            .map(|row| (row.email.as_str(), row.full_name.as_str()))
+            .collect();
+        assert_eq!(
+            pending,
+            [("blake@x.org", "Blake Ngo"), ("casey@x.org", "Casey R.")]
+        );
+    }
+
+    #[test]
+    fn every_migration_can_be_reverted() {
+        for migration in MIGRATOR.iter() {
+            assert!(
+                MIGRATOR
+                    .iter()
+                    .any(|other| other.version == migration.version
+                        && other.migration_type.is_down_migration()
+                            != migration.migration_type.is_down_migration()),
+                "migration {} needs both an up and a down script",
+                migration.version
+            );
+        }
+    }
+}
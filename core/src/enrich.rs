@@ -0,0 +1,254 @@
+use std::collections::BTreeMap;
+
+/// Words that flip the sentiment of the word right after them, e.g. "not improving".
+const NEGATORS: [&str; 4] = ["not", "no", "never", "without"];
+
+/// Keyword categories and the word lists behind the note sentiment score, from `[enrichment]`.
+///
+/// Keywords match whole words regardless of case; several words match as a phrase, and a trailing
+/// `*` matches any word starting with the rest, e.g. `evict*`.
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EnrichmentConfig {
+    /// Keywords by category name, e.g. `housing = ["rent", "evict*"]`.
+    pub categories: BTreeMap<String, Vec<String>>,
+    pub positive_words: Vec<String>,
+    pub negative_words: Vec<String>,
+}
+
+impl Default for EnrichmentConfig {
+    fn default() -> Self {
+        let words = |words: &[&str]| words.iter().map(|word| word.to_string()).collect();
+        Self {
+            categories: BTreeMap::from([
+                (
+                    "financial".to_string(),
+                    words(&[
+                        "tuition",
+                        "fafsa",
+                        "financial aid",
+                        "loan*",
+                        "debt*",
+                        "bill*",
+                        "fees",
+                        "afford*",
+                        "paycheck",
+                        "money",
+                    ]),
+                ),
+                (
+                    "health".to_string(),
+                    words(&[
+                        "sick*",
+                        "ill",
+                        "illness",
+                        "hospital*",
+                        "doctor*",
+                        "clinic",
+                        "medication*",
+                        "injur*",
+                        "surgery",
+                        "anxiety",
+                        "depress*",
+                    ]),
+                ),
+                (
+                    "housing".to_string(),
+                    words(&[
+                        "rent",
+                        "evict*",
+                        "homeless*",
+                        "housing",
+                        "landlord",
+                        "shelter",
+                        "couch surfing",
+                        "moved out",
+                    ]),
+                ),
+            ]),
+            positive_words: words(&[
+                "improv*",
+                "better",
+                "engaged",
+                "progress*",
+                "motivated",
+                "resolved",
+                "on track",
+                "caught up",
+                "great",
+                "good",
+            ]),
+            negative_words: words(&[
+                "miss*",
+                "fail*",
+                "struggl*",
+                "worr*",
+                "absent",
+                "behind",
+                "stress*",
+                "overwhelm*",
+                "upset",
+                "withdr*",
+            ]),
+        }
+    }
+}
+
+impl EnrichmentConfig {
+    pub fn validate(&self) -> anyhow::Result<()> {
+        for (category, keywords) in &self.categories {
+            if category.is_empty()
+                || !category
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_')
+            {
+                anyhow::bail!(
+                    "category {category:?} must be lowercase letters, digits, '-', or '_'"
+                );
+            }
+            if !keywords.iter().all(|keyword| is_valid_keyword(keyword)) {
+                anyhow::bail!("category {category} has a blank or bare `*` keyword");
+            }
+        }
+        for (list, name) in [
+            (&self.positive_words, "positive_words"),
+            (&self.negative_words, "negative_words"),
+        ] {
+            if !list.iter().all(|word| is_valid_keyword(word)) {
+                anyhow::bail!("{name} has a blank or bare `*` entry");
+            }
+        }
+        Ok(())
+    }
+}
+
+/// What enrichment found in one note.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct NoteEnrichment {
+    /// Categories with a keyword in the note, in name order.
+    pub categories: Vec<String>,
+    /// From -1 (only negative words) to 1 (only positive ones); `None` for a blank note.
+    pub sentiment: Option<f64>,
+}
+
+/// Lowercased words of `text`, split on anything but letters, digits, and apostrophes.
+fn words(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric() && c != '\'')
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Words of a configured keyword, keeping the `*` that marks a prefix.
+fn keyword_words(keyword: &str) -> Vec<String> {
+    keyword
+        .split(|c: char| !c.is_alphanumeric() && c != '\'' && c != '*')
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Whether a keyword has words, none of them a bare `*` that would match anything.
+fn is_valid_keyword(keyword: &str) -> bool {
+    let words = keyword_words(keyword);
+    !words.is_empty() && words.iter().all(|word| word != "*")
+}
+
+/// Whether `keyword` matches the words of the note starting at `start`.
+fn matches_at(words: &[String], start: usize, keyword: &[String]) -> bool {
+    keyword.len() <= words.len() - start
+        && keyword
+            .iter()
+            .zip(&words[start..])
+            .all(|(key, word)| match key.strip_suffix('*') {
+                Some(prefix) => word.starts_with(prefix),
+                None => word == key,
+            })
+}
+
+fn contains(words: &[String], keyword: &[String]) -> bool {
+    (0..words.len()).any(|start| matches_at(words, start, keyword))
+}
+
+/// Tags `note` with its keyword categories and scores its sentiment.
+pub fn enrich_note(note: &str, config: &EnrichmentConfig) -> NoteEnrichment {
+    let note_words = words(note);
+    if note_words.is_empty() {
+        return NoteEnrichment::default();
+    }
+    let categories = config
+        .categories
+        .iter()
+        .filter(|(_, keywords)| {
+            keywords
+                .iter()
+                .any(|keyword| contains(&note_words, &keyword_words(keyword)))
+        })
+        .map(|(category, _)| category.clone())
+        .collect();
+
+    let positive: Vec<Vec<String>> = config
+        .positive_words
+        .iter()
+        .map(|w| keyword_words(w))
+        .collect();
+    let negative: Vec<Vec<String>> = config
+        .negative_words
+        .iter()
+        .map(|w| keyword_words(w))
+        .collect();
+    let (mut up, mut down) = (0u32, 0u32);
+    for start in 0..note_words.len() {
+        let negated = start > 0 && NEGATORS.contains(&note_words[start - 1].as_str());
+        let hit = |list: &[Vec<String>]| {
+            list.iter()
+                .any(|keyword| matches_at(&note_words, start, keyword))
+        };
+        match (hit(&positive), hit(&negative), negated) {
+            (true, false, false) | (false, true, true) => up += 1,
+            (false, true, false) | (true, false, true) => down += 1,
+            _ => {}
+        }
+    }
+    let sentiment = match up + down {
+        0 => 0.0,
+        total => (up as f64 - down as f64) / total as f64,
+    };
+    NoteEnrichment {
+        categories,
+        sentiment: Some(sentiment),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tags_categories_by_words_phrases_and_prefixes() {
+        let config = EnrichmentConfig::default();
+        let enrichment = enrich_note(
+            "Facing EVICTION next month; waiting on financial aid. Missed two sessions.",
+            &config,
+        );
+        assert_eq!(enrichment.categories, ["financial", "housing"]);
+        assert_eq!(enrichment.sentiment, Some(-1.0));
+
+        // "ill" is a whole word, not a prefix of "illinois".
+        let enrichment = enrich_note("Visiting family in Illinois", &config);
+        assert!(enrichment.categories.is_empty());
+        assert_eq!(enrichment.sentiment, Some(0.0));
+
+        assert_eq!(enrich_note("  ", &config), NoteEnrichment::default());
+    }
+
+    #[test]
+    fn negation_flips_sentiment() {
+        let config = EnrichmentConfig::default();
+        let sentiment = |note| enrich_note(note, &config).sentiment.unwrap();
+        assert_eq!(sentiment("Attendance improved and she is motivated"), 1.0);
+        assert_eq!(sentiment("Not improving; still behind"), -1.0);
+        assert_eq!(sentiment("Never missed a session, grades improving"), 1.0);
+        assert_eq!(sentiment("Caught up on work but stressed"), 0.0);
+    }
+}
@@ -0,0 +1,466 @@
+use std::io::Write;
+#[cfg(feature = "parquet")]
+use std::sync::Arc;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use uuid::Uuid;
+
+use crate::models::{Attachment, Scholar};
+use crate::risk::Tier;
+
+/// What a column holds. CSV and JSONL write each row's own representation; Parquet files get the
+/// column types [`ColumnType::arrow`] maps these to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnType {
+    Text,
+    /// Text that may be null.
+    OptionalText,
+    Int32,
+    Float64,
+    Boolean,
+    Date,
+    /// An instant in UTC, to the microsecond.
+    Timestamp,
+    /// A list of text values.
+    TextList,
+    /// A list of `{url, label, kind}` text structs.
+    Attachments,
+}
+
+/// A column of an export: its name, which is also the JSONL key, and what it holds.
+pub type Column = (&'static str, ColumnType);
+
+/// Column order of `export`, shared by CSV headers, JSONL keys, and Parquet fields. The first
+/// columns match the import layout, so an exported CSV can be imported elsewhere as-is.
+pub const EXPORT_COLUMNS: [Column; 13] = [
+    ("full_name", ColumnType::Text),
+    ("email", ColumnType::Text),
+    ("cohort", ColumnType::Text),
+    ("signal_type", ColumnType::Text),
+    ("severity", ColumnType::Int32),
+    ("note", ColumnType::Text),
+    ("occurred_at", ColumnType::Date),
+    ("source_key", ColumnType::Text),
+    ("tags", ColumnType::TextList),
+    ("submitted_by", ColumnType::OptionalText),
+    ("attachments", ColumnType::Attachments),
+    ("status", ColumnType::Text),
+    ("advisor", ColumnType::OptionalText),
+];
+
+/// Column order of `export --dataset scholars`.
+pub const SCHOLAR_COLUMNS: [Column; 7] = [
+    ("full_name", ColumnType::Text),
+    ("email", ColumnType::Text),
+    ("cohort", ColumnType::Text),
+    ("status", ColumnType::Text),
+    ("advisor", ColumnType::OptionalText),
+    ("created_at", ColumnType::Timestamp),
+    ("deleted", ColumnType::Boolean),
+];
+
+/// Column order of `export --dataset snapshots`.
+pub const SNAPSHOT_COLUMNS: [Column; 10] = [
+    ("run_id", ColumnType::Text),
+    ("ran_at", ColumnType::Timestamp),
+    ("trigger", ColumnType::Text),
+    ("since_days", ColumnType::Int32),
+    ("full_name", ColumnType::Text),
+    ("email", ColumnType::Text),
+    ("cohort", ColumnType::Text),
+    ("score", ColumnType::Float64),
+    ("tier", ColumnType::Text),
+    ("signal_count", ColumnType::Int32),
+];
+
+/// Names of `columns`, as a CSV header.
+pub fn column_names(columns: &[Column]) -> Vec<&'static str> {
+    columns.iter().map(|(name, _)| *name).collect()
+}
+
+#[cfg(feature = "parquet")]
+impl ColumnType {
+    pub fn arrow(self) -> arrow_schema::DataType {
+        use arrow_schema::{DataType, Field, Fields, TimeUnit};
+
+        let text_item = || Arc::new(Field::new("item", DataType::Utf8, false));
+        match self {
+            ColumnType::Text | ColumnType::OptionalText => DataType::Utf8,
+            ColumnType::Int32 => DataType::Int32,
+            ColumnType::Float64 => DataType::Float64,
+            ColumnType::Boolean => DataType::Boolean,
+            ColumnType::Date => DataType::Date32,
+            ColumnType::Timestamp => DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into())),
+            ColumnType::TextList => DataType::List(text_item()),
+            ColumnType::Attachments => {
+                let fields: Fields = ["url", "label", "kind"]
+                    .into_iter()
+                    .map(|name| Field::new(name, DataType::Utf8, false))
+                    .collect();
+                DataType::List(Arc::new(Field::new(
+                    "item",
+                    DataType::Struct(fields),
+                    false,
+                )))
+            }
+        }
+    }
+}
+
+/// The Arrow schema Parquet exports of `columns` are written with.
+#[cfg(feature = "parquet")]
+pub fn arrow_schema(columns: &[Column]) -> arrow_schema::Schema {
+    arrow_schema::Schema::new(
+        columns
+            .iter()
+            .map(|(name, column)| {
+                arrow_schema::Field::new(*name, column.arrow(), *column == ColumnType::OptionalText)
+            })
+            .collect::<Vec<_>>(),
+    )
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ExportFormat {
+    Csv,
+    /// One JSON object per line.
+    Jsonl,
+    /// Snappy-compressed Parquet, for loading into a data warehouse.
+    #[cfg(feature = "parquet")]
+    Parquet,
+    /// iCalendar follow-up reminders (see [`crate::ics`]) rather than rows.
+    Ics,
+}
+
+/// What `export` writes, one row per item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ExportDataset {
+    /// Signals joined with their scholars.
+    Signals,
+    /// The roster.
+    Scholars,
+    /// Each scholar's result in every saved scoring run.
+    Snapshots,
+}
+
+impl ExportDataset {
+    pub fn columns(self) -> &'static [Column] {
+        match self {
+            ExportDataset::Signals => &EXPORT_COLUMNS,
+            ExportDataset::Scholars => &SCHOLAR_COLUMNS,
+            ExportDataset::Snapshots => &SNAPSHOT_COLUMNS,
+        }
+    }
+}
+
+/// One signal joined with its scholar. Field order must follow [`EXPORT_COLUMNS`].
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ExportRow {
+    pub full_name: String,
+    pub email: String,
+    pub cohort: String,
+    pub signal_type: String,
+    pub severity: i32,
+    pub note: String,
+    pub occurred_at: NaiveDate,
+    pub source_key: String,
+    pub tags: Vec<String>,
+    pub submitted_by: Option<String>,
+    pub attachments: Vec<Attachment>,
+    pub status: String,
+    pub advisor: Option<String>,
+}
+
+/// A row [`ExportWriter`] can write: serialized as-is for JSONL, and as its CSV cells otherwise.
+pub trait ExportRecord: serde::Serialize {
+    /// Cells in the order of the header the writer was created with.
+    fn csv_record(&self) -> Vec<String>;
+}
+
+impl ExportRecord for ExportRow {
+    /// CSV cells, with tags and attachments in the semicolon-separated import syntax.
+    fn csv_record(&self) -> Vec<String> {
+        let attachments: Vec<String> = self
+            .attachments
+            .iter()
+            .map(|attachment| {
+                format!(
+                    "{}|{}|{}",
+                    attachment.kind, attachment.label, attachment.url
+                )
+            })
+            .collect();
+        vec![
+            self.full_name.clone(),
+            self.email.clone(),
+            self.cohort.clone(),
+            self.signal_type.clone(),
+            self.severity.to_string(),
+            self.note.clone(),
+            self.occurred_at.to_string(),
+            self.source_key.clone(),
+            self.tags.join(";"),
+            self.submitted_by.clone().unwrap_or_default(),
+            attachments.join(";"),
+            self.status.clone(),
+            self.advisor.clone().unwrap_or_default(),
+        ]
+    }
+}
+
+impl ExportRecord for Scholar {
+    fn csv_record(&self) -> Vec<String> {
+        vec![
+            self.full_name.clone(),
+            self.email.clone(),
+            self.cohort.clone(),
+            self.status.clone(),
+            self.advisor.clone().unwrap_or_default(),
+            self.created_at.to_rfc3339(),
+            self.deleted.to_string(),
+        ]
+    }
+}
+
+/// One scholar's result in a saved scoring run. Field order must follow [`SNAPSHOT_COLUMNS`].
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct SnapshotRow {
+    pub run_id: Uuid,
+    pub ran_at: DateTime<Utc>,
+    /// What started the run, e.g. `watch`.
+    pub trigger: String,
+    pub since_days: i32,
+    pub full_name: String,
+    pub email: String,
+    pub cohort: String,
+    pub score: f64,
+    pub tier: Tier,
+    pub signal_count: i32,
+}
+
+impl ExportRecord for SnapshotRow {
+    fn csv_record(&self) -> Vec<String> {
+        vec![
+            self.run_id.to_string(),
+            self.ran_at.to_rfc3339(),
+            self.trigger.clone(),
+            self.since_days.to_string(),
+            self.full_name.clone(),
+            self.email.clone(),
+            self.cohort.clone(),
+            self.score.to_string(),
+            self.tier.as_str().to_string(),
+            self.signal_count.to_string(),
+        ]
+    }
+}
+
+/// Rows buffered per Parquet row group batch.
+#[cfg(feature = "parquet")]
+const PARQUET_BATCH_ROWS: usize = 8_192;
+
+/// Builds Arrow record batches from serialized rows and writes them to a Parquet file.
+#[cfg(feature = "parquet")]
+pub struct ParquetSink<W: Write + Send> {
+    decoder: arrow_json::reader::Decoder,
+    writer: parquet::arrow::ArrowWriter<W>,
+}
+
+#[cfg(feature = "parquet")]
+impl<W: Write + Send> ParquetSink<W> {
+    fn new(output: W, columns: &[Column]) -> anyhow::Result<Self> {
+        let schema = Arc::new(arrow_schema(columns));
+        let decoder = arrow_json::ReaderBuilder::new(schema.clone())
+            .with_batch_size(PARQUET_BATCH_ROWS)
+            .build_decoder()?;
+        let properties = parquet::file::properties::WriterProperties::builder()
+            .set_compression(parquet::basic::Compression::SNAPPY)
+            .build();
+        let writer = parquet::arrow::ArrowWriter::try_new(output, schema, Some(properties))?;
+        Ok(Self { decoder, writer })
+    }
+
+    fn write(&mut self, row: &impl ExportRecord) -> anyhow::Result<()> {
+        self.decoder.serialize(std::slice::from_ref(row))?;
+        if self.decoder.len() >= PARQUET_BATCH_ROWS {
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> anyhow::Result<()> {
+        if let Some(batch) = self.decoder.flush()? {
+            self.writer.write(&batch)?;
+        }
+        Ok(())
+    }
+
+    fn finish(mut self) -> anyhow::Result<()> {
+        self.flush()?;
+        self.writer.close()?;
+        Ok(())
+    }
+}
+
+/// Writes export rows in any [`ExportFormat`]; CSV output starts with the header row.
+pub enum ExportWriter<W: Write + Send> {
+    Csv(Box<csv::Writer<W>>),
+    Jsonl(W),
+    #[cfg(feature = "parquet")]
+    Parquet(Box<ParquetSink<W>>),
+}
+
+impl<W: Write + Send> ExportWriter<W> {
+    pub fn new(output: W, format: ExportFormat) -> anyhow::Result<Self> {
+        Self::with_columns(output, format, &EXPORT_COLUMNS)
+    }
+
+    /// A writer for rows other than [`ExportRow`], laid out as `columns`.
+    pub fn with_columns(
+        output: W,
+        format: ExportFormat,
+        columns: &[Column],
+    ) -> anyhow::Result<Self> {
+        Ok(match format {
+            ExportFormat::Csv => {
+                let mut writer = csv::Writer::from_writer(output);
+                writer.write_record(column_names(columns))?;
+                ExportWriter::Csv(Box::new(writer))
+            }
+            ExportFormat::Jsonl => ExportWriter::Jsonl(output),
+            #[cfg(feature = "parquet")]
+            ExportFormat::Parquet => {
+                ExportWriter::Parquet(Box::new(ParquetSink::new(output, columns)?))
+            }
+            ExportFormat::Ics => anyhow::bail!("ics exports are written by `ics::write_calendar`"),
+        })
+    }
+
+    pub fn write(&mut self, row: &impl ExportRecord) -> anyhow::Result<()> {
+        match self {
+            ExportWriter::Csv(writer) => writer.write_record(row.csv_record())?,
+            ExportWriter::Jsonl(output) => {
+                serde_json::to_writer(&mut *output, row)?;
+                output.write_all(b"\n")?;
+            }
+            #[cfg(feature = "parquet")]
+            ExportWriter::Parquet(sink) => sink.write(row)?,
+        }
+        Ok(())
+    }
+
+    pub fn finish(self) -> anyhow::Result<()> {
+        match self {
+            ExportWriter::Csv(mut writer) => writer.flush()?,
+            ExportWriter::Jsonl(mut output) => output.flush()?,
+            #[cfg(feature = "parquet")]
+            ExportWriter::Parquet(sink) => sink.finish()?,
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::import::{ChunkedReader, ImportLimits};
+
+    fn sample_row() -> ExportRow {
+        ExportRow {
+            full_name: "Ada Park".to_string(),
+            email: "ada@example.edu".to_string(),
+            cohort: "2026".to_string(),
+            signal_type: "attendance".to_string(),
+            severity: 3,
+            note: "Missed lab, said \"back next week\"".to_string(),
+            occurred_at: NaiveDate::from_ymd_opt(2026, 2, 10).unwrap(),
+            source_key: "sis-1".to_string(),
+            tags: vec!["summer-bridge".to_string(), "tutoring".to_string()],
+            submitted_by: Some("lee".to_string()),
+            attachments: vec![Attachment {
+                url: "https://lms.example.edu/a/1".to_string(),
+                label: "LMS log".to_string(),
+                kind: "lms".to_string(),
+            }],
+            status: "active".to_string(),
+            advisor: None,
+        }
+    }
+
+    #[test]
+    fn csv_export_can_be_imported_again() {
+        let mut output = Vec::new();
+        let mut writer = ExportWriter::new(&mut output, ExportFormat::Csv).unwrap();
+        writer.write(&sample_row()).unwrap();
+        writer.finish().unwrap();
+
+        let text = String::from_utf8(output.clone()).unwrap();
+        assert!(text.starts_with(&column_names(&EXPORT_COLUMNS).join(",")));
+
+        let rows = ChunkedReader::new(output.as_slice(), ImportLimits::default())
+            .next_chunk()
+            .unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].note, sample_row().note);
+        assert_eq!(rows[0].tags, sample_row().tags);
+        assert_eq!(rows[0].attachments, sample_row().attachments);
+        assert_eq!(rows[0].source_key.as_deref(), Some("sis-1"));
+    }
+
+    #[test]
+    fn jsonl_keys_follow_column_order() {
+        let mut output = Vec::new();
+        let mut writer = ExportWriter::new(&mut output, ExportFormat::Jsonl).unwrap();
+        writer.write(&sample_row()).unwrap();
+        writer.write(&sample_row()).unwrap();
+        writer.finish().unwrap();
+
+        let text = String::from_utf8(output).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 2);
+
+        let positions: Vec<usize> = EXPORT_COLUMNS
+            .iter()
+            .map(|(column, _)| lines[0].find(&format!("\"{column}\":")).unwrap())
+            .collect();
+        assert!(positions.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[cfg(feature = "parquet")]
+    #[test]
+    fn parquet_export_follows_the_documented_schema() {
+        use arrow_array::cast::AsArray;
+        use arrow_array::types::{Date32Type, Int32Type};
+        use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+        let path = std::env::temp_dir().join(format!("cew-export-{}.parquet", Uuid::new_v4()));
+        let file = std::fs::File::create(&path).unwrap();
+        let mut writer = ExportWriter::new(file, ExportFormat::Parquet).unwrap();
+        for _ in 0..3 {
+            writer.write(&sample_row()).unwrap();
+        }
+        writer.finish().unwrap();
+
+        let reader =
+            ParquetRecordBatchReaderBuilder::try_new(std::fs::File::open(&path).unwrap()).unwrap();
+        assert_eq!(reader.schema().as_ref(), &arrow_schema(&EXPORT_COLUMNS));
+        let batches: Vec<_> = reader.build().unwrap().collect::<Result<_, _>>().unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(batches.len(), 1);
+        let batch = &batches[0];
+        assert_eq!(batch.num_rows(), 3);
+        assert_eq!(batch.column(0).as_string::<i32>().value(0), "Ada Park");
+        assert_eq!(batch.column(4).as_primitive::<Int32Type>().value(0), 3);
+        assert_eq!(
+            batch
+                .column(6)
+                .as_primitive::<Date32Type>()
+                .value_as_date(0),
+            Some(sample_row().occurred_at)
+        );
+        assert_eq!(batch.column(8).as_list::<i32>().value(0).len(), 2);
+        assert!(batch.column(12).is_null(0));
+    }
+}
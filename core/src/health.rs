@@ -0,0 +1,124 @@
+use chrono::{Duration, NaiveDate};
+
+use crate::models::{CohortHealth, ScholarScore, SignalRecord};
+use crate::risk::{self, ScoringConfig, Tier};
+
+const SPARKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// How much of one scholar's share of the index each tier takes away.
+fn burden(tier: Tier) -> f64 {
+    match tier {
+        Tier::Critical => 1.0,
+        Tier::High => 0.6,
+        Tier::Moderate => 0.25,
+        Tier::Low => 0.0,
+    }
+}
+
+/// Health of a cohort of `scholars` active scholars: 100 when none is at risk, 0 when all are
+/// critical. Scholars without a score count as low risk.
+pub fn index(scores: &[ScholarScore], scholars: i64) -> f64 {
+    if scholars <= 0 {
+        return 100.0;
+    }
+    let burden: f64 = scores.iter().map(|score| burden(score.tier)).sum();
+    (100.0 * (1.0 - burden / scholars as f64)).clamp(0.0, 100.0)
+}
+
+/// Scores `cohort` as of the Sunday ending each week in `weeks`, using the `since_days` of
+/// signals up to it.
+pub fn weekly(
+    cohort: &str,
+    signals: &[SignalRecord],
+    scholars: i64,
+    weeks: &[NaiveDate],
+    since_days: i64,
+    scoring: &ScoringConfig,
+) -> Vec<CohortHealth> {
+    let signals: Vec<&SignalRecord> = signals
+        .iter()
+        .filter(|signal| signal.cohort == cohort)
+        .collect();
+    weeks
+        .iter()
+        .map(|&week_start| {
+            let as_of = week_start + Duration::days(6);
+            let prior: Vec<SignalRecord> = signals
+                .iter()
+                .filter(|signal| signal.occurred_at <= as_of)
+                .map(|signal| (*signal).clone())
+                .collect();
+            let scores = risk::score_signals_with(&prior, since_days, as_of, scoring);
+            CohortHealth {
+                cohort: cohort.to_string(),
+                week_start,
+                health_index: index(&scores, scholars),
+                scholars,
+                at_risk: scores.iter().filter(|s| s.tier >= Tier::High).count() as i64,
+            }
+        })
+        .collect()
+}
+
+/// One block per value on a fixed 0 to 100 scale, so lines for different cohorts compare.
+pub fn sparkline(values: &[f64]) -> String {
+    values
+        .iter()
+        .map(|value| {
+            let step = (value.clamp(0.0, 100.0) / 100.0 * (SPARKS.len() - 1) as f64).round();
+            SPARKS[step as usize]
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn date(month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, month, day).unwrap()
+    }
+
+    fn signal(email: &str, cohort: &str, occurred_at: NaiveDate) -> SignalRecord {
+        SignalRecord {
+            scholar_id: Uuid::from_u128(u128::from(email.as_bytes()[0])),
+            scholar_name: email.to_string(),
+            scholar_email: email.to_string(),
+            cohort: cohort.to_string(),
+            signal_type: "attendance".to_string(),
+            severity: 5,
+            occurred_at,
+            note: String::new(),
+            advisor: None,
+            submitted_by: None,
+            attachments: Vec::new(),
+            note_categories: Vec::new(),
+            note_sentiment: None,
+        }
+    }
+
+    #[test]
+    fn tracks_cohort_health_week_by_week() {
+        let signals = [
+            signal("a@x.org", "2026", date(9, 15)),
+            signal("a@x.org", "2026", date(9, 16)),
+            signal("a@x.org", "2026", date(9, 17)),
+            // Another cohort's scholar never counts against this one.
+            signal("b@x.org", "2025", date(9, 8)),
+        ];
+        let weeks = [date(9, 7), date(9, 14), date(10, 26)];
+
+        let health = weekly("2026", &signals, 4, &weeks, 30, &ScoringConfig::default());
+        assert_eq!(health.len(), 3);
+        assert_eq!((health[0].health_index, health[0].at_risk), (100.0, 0));
+        // One critical scholar of four.
+        assert_eq!((health[1].health_index, health[1].at_risk), (75.0, 1));
+        // The signals have aged out of the window.
+        assert_eq!(health[2].health_index, 100.0);
+
+        assert_eq!(sparkline(&[0.0, 50.0, 75.0, 100.0]), "▁▅▆█");
+        assert_eq!(index(&[], 0), 100.0);
+    }
+}
@@ -0,0 +1,272 @@
+use std::collections::HashMap;
+use std::io::Write;
+
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+
+use crate::models::ScholarScore;
+use crate::risk::Tier;
+
+/// Longest content line, in octets, before it is folded onto a continuation line.
+const MAX_LINE_OCTETS: usize = 75;
+
+/// Which scholars need a follow-up, and when.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FollowUpOptions {
+    /// Scholars at or above this tier get a reminder.
+    pub min_tier: Tier,
+    /// Days after the last contact a follow-up comes due.
+    pub contact_days: i64,
+}
+
+/// A reminder to contact one scholar.
+#[derive(Debug, Clone)]
+pub struct FollowUp<'a> {
+    pub score: &'a ScholarScore,
+    /// `contact_days` after the last contact, or today when that has passed or nobody has been in
+    /// touch yet.
+    pub due: NaiveDate,
+    /// Email of the scholar's advisor, invited to the event.
+    pub advisor_email: Option<&'a str>,
+}
+
+/// A reminder for every scholar at or above `min_tier`, soonest first. `advisor_emails` maps
+/// scholar emails to their advisor's.
+pub fn follow_ups<'a>(
+    scores: &'a [ScholarScore],
+    advisor_emails: &'a HashMap<String, String>,
+    today: NaiveDate,
+    options: FollowUpOptions,
+) -> Vec<FollowUp<'a>> {
+    let mut follow_ups: Vec<FollowUp> = scores
+        .iter()
+        .filter(|score| score.tier >= options.min_tier)
+        .map(|score| FollowUp {
+            score,
+            due: score
+                .last_contact
+                .map(|contact| contact + Duration::days(options.contact_days))
+                .filter(|due| *due > today)
+                .unwrap_or(today),
+            advisor_email: advisor_emails.get(&score.scholar_email).map(String::as_str),
+        })
+        .collect();
+    follow_ups.sort_by(|a, b| {
+        a.due
+            .cmp(&b.due)
+            .then(b.score.tier.cmp(&a.score.tier))
+            .then(b.score.score.total_cmp(&a.score.score))
+    });
+    follow_ups
+}
+
+/// Escapes TEXT property values: backslashes, semicolons, commas, and newlines.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' | ';' | ',' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Writes `line` with CRLF, folded so no line exceeds [`MAX_LINE_OCTETS`] without splitting a
+/// character.
+fn write_line(output: &mut impl Write, line: &str) -> std::io::Result<()> {
+    let mut start = 0;
+    let mut limit = MAX_LINE_OCTETS;
+    while line.len() - start > limit {
+        let mut end = start + limit;
+        while !line.is_char_boundary(end) {
+            end -= 1;
+        }
+        output.write_all(&line.as_bytes()[start..end])?;
+        output.write_all(b"\r\n ")?;
+        start = end;
+        // Continuation lines spend an octet on the leading space.
+        limit = MAX_LINE_OCTETS - 1;
+    }
+    output.write_all(&line.as_bytes()[start..])?;
+    output.write_all(b"\r\n")
+}
+
+/// Writes `follow_ups` as an iCalendar file of all-day events on their due dates. Event UIDs stay
+/// the same for a scholar and due date, so re-importing an updated export replaces events rather
+/// than duplicating them.
+pub fn write_calendar(
+    output: &mut impl Write,
+    org: &str,
+    follow_ups: &[FollowUp<'_>],
+    generated_at: DateTime<Utc>,
+) -> std::io::Result<()> {
+    let stamp = generated_at.format("%Y%m%dT%H%M%SZ").to_string();
+    write_line(output, "BEGIN:VCALENDAR")?;
+    write_line(output, "VERSION:2.0")?;
+    write_line(output, "PRODID:-//Group Scholar//Cohort Early Warning//EN")?;
+    write_line(output, "CALSCALE:GREGORIAN")?;
+    write_line(
+        output,
+        &format!("X-WR-CALNAME:{}", escape(&format!("{org} follow-ups"))),
+    )?;
+    for follow_up in follow_ups {
+        let score = follow_up.score;
+        write_line(output, "BEGIN:VEVENT")?;
+        write_line(
+            output,
+            &format!(
+                "UID:follow-up-{}-{}@cohort-early-warning",
+                score.scholar_email,
+                follow_up.due.format("%Y%m%d")
+            ),
+        )?;
+        write_line(output, &format!("DTSTAMP:{stamp}"))?;
+        write_line(
+            output,
+            &format!("DTSTART;VALUE=DATE:{}", follow_up.due.format("%Y%m%d")),
+        )?;
+        write_line(
+            output,
+            &format!(
+                "DTEND;VALUE=DATE:{}",
+                (follow_up.due + Duration::days(1)).format("%Y%m%d")
+            ),
+        )?;
+        write_line(
+            output,
+            &format!(
+                "SUMMARY:{}",
+                escape(&format!(
+                    "Follow up with {} ({})",
+                    score.scholar_name,
+                    score.tier.as_str()
+                ))
+            ),
+        )?;
+        let last_contact = match score.last_contact {
+            Some(day) => format!("last contact {day}"),
+            None => "no contact recorded".to_string(),
+        };
+        let mut description = format!(
+            "{} in cohort {} scores {:.1} ({}) from {} signals; {last_contact}.",
+            score.scholar_email,
+            score.cohort,
+            score.score,
+            score.tier.as_str(),
+            score.signal_count
+        );
+        if let Some(signal) = &score.escalated_by {
+            description.push_str(&format!("\nEscalated by {signal}."));
+        }
+        write_line(output, &format!("DESCRIPTION:{}", escape(&description)))?;
+        write_line(
+            output,
+            &format!("CATEGORIES:{}", escape(score.tier.as_str())),
+        )?;
+        if let Some(email) = follow_up.advisor_email {
+            let name = score.advisor.as_deref().unwrap_or(email).replace('"', "'");
+            write_line(
+                output,
+                &format!("ATTENDEE;CN=\"{name}\";ROLE=REQ-PARTICIPANT:mailto:{email}"),
+            )?;
+        }
+        write_line(output, "TRANSP:TRANSPARENT")?;
+        write_line(output, "END:VEVENT")?;
+    }
+    write_line(output, "END:VCALENDAR")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn score(name: &str, tier: Tier, last_contact: Option<NaiveDate>) -> ScholarScore {
+        ScholarScore {
+            scholar_name: name.to_string(),
+            scholar_email: format!("{}@example.edu", name.to_lowercase()),
+            cohort: "2026".to_string(),
+            score: 10.0,
+            signal_count: 3,
+            advisor: Some("Lee, Jordan".to_string()),
+            tier,
+            escalated_by: None,
+            collapsed_signals: 0,
+            percentile: 90.0,
+            cohort_percentile: 90.0,
+            last_contact,
+            last_signal: None,
+        }
+    }
+
+    fn day(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 3, d).unwrap()
+    }
+
+    #[test]
+    fn reminders_fall_due_after_the_last_contact() {
+        let scores = [
+            score("Ada", Tier::High, Some(day(10))),
+            score("Ben", Tier::Critical, None),
+            score("Cy", Tier::Moderate, None),
+            score("Di", Tier::Critical, Some(day(1))),
+        ];
+        let options = FollowUpOptions {
+            min_tier: Tier::High,
+            contact_days: 14,
+        };
+        let advisors =
+            HashMap::from([("ada@example.edu".to_string(), "lee@example.edu".to_string())]);
+        let due: Vec<(&str, NaiveDate, Option<&str>)> =
+            follow_ups(&scores, &advisors, day(20), options)
+                .iter()
+                .map(|f| (f.score.scholar_name.as_str(), f.due, f.advisor_email))
+                .collect();
+        assert_eq!(
+            due,
+            [
+                ("Ben", day(20), None),
+                ("Di", day(20), None),
+                ("Ada", day(24), Some("lee@example.edu")),
+            ]
+        );
+    }
+
+    #[test]
+    fn writes_escaped_and_folded_events() {
+        let mut ada = score("Ada", Tier::Critical, None);
+        ada.escalated_by = Some("wellbeing severity 5; self-reported".to_string());
+        ada.cohort =
+            "Class of 2026, section B with a very long name that pushes the line".to_string();
+        let advisors =
+            HashMap::from([("ada@example.edu".to_string(), "lee@example.edu".to_string())]);
+        let options = FollowUpOptions {
+            min_tier: Tier::High,
+            contact_days: 14,
+        };
+        let scores = [ada];
+        let events = follow_ups(&scores, &advisors, day(20), options);
+        let mut output = Vec::new();
+        let generated_at = day(20).and_hms_opt(8, 0, 0).unwrap().and_utc();
+        write_calendar(&mut output, "demo", &events, generated_at).unwrap();
+        let text = String::from_utf8(output).unwrap();
+
+        assert!(text.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+        assert!(text.ends_with("END:VCALENDAR\r\n"));
+        assert!(text
+            .lines()
+            .all(|line| line.trim_end_matches('\r').len() <= MAX_LINE_OCTETS));
+        assert!(text.contains("DTSTART;VALUE=DATE:20260320\r\nDTEND;VALUE=DATE:20260321\r\n"));
+        assert!(text.contains("SUMMARY:Follow up with Ada (critical)\r\n"));
+        assert!(text
+            .contains("ATTENDEE;CN=\"Lee, Jordan\";ROLE=REQ-PARTICIPANT:mailto:lee@example.edu"));
+
+        let unfolded = text.replace("\r\n ", "");
+        assert!(unfolded.contains("Class of 2026\\, section B"));
+        assert!(unfolded.contains("\\nEscalated by wellbeing severity 5\\; self-reported."));
+    }
+}
@@ -0,0 +1,2366 @@
+use std::collections::BTreeMap;
+use std::fs::File;
+#[cfg(feature = "postgres")]
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::io::{Read, Take};
+use std::path::{Path, PathBuf};
+#[cfg(feature = "postgres")]
+use std::time::{Duration, Instant};
+
+use anyhow::{bail, Context};
+use chrono::NaiveDate;
+#[cfg(feature = "postgres")]
+use sqlx::PgPool;
+use unicode_normalization::UnicodeNormalization;
+use uuid::Uuid;
+
+#[cfg(feature = "postgres")]
+use crate::db;
+use crate::models::Attachment;
+#[cfg(feature = "postgres")]
+use crate::models::NewSignal;
+#[cfg(feature = "postgres")]
+use crate::notes::{self, NoteKey};
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+pub struct CsvRow {
+    pub full_name: String,
+    pub email: String,
+    pub cohort: String,
+    pub signal_type: String,
+    pub severity: i32,
+    pub note: String,
+    pub occurred_at: NaiveDate,
+    pub source_key: Option<String>,
+    /// Optional `tags` column holding semicolon-separated labels such as `summer-bridge`.
+    #[serde(default, deserialize_with = "deserialize_tags")]
+    pub tags: Vec<String>,
+    /// Optional `submitted_by` column naming the staff member who entered the signal.
+    #[serde(default)]
+    pub submitted_by: Option<String>,
+    /// Optional `attachments` column; see [`parse_attachments`].
+    #[serde(default, deserialize_with = "deserialize_attachments")]
+    pub attachments: Vec<Attachment>,
+    /// Full note text when the note policy shortened `note` and overflow storage is enabled.
+    #[serde(skip_deserializing)]
+    pub original_note: Option<String>,
+}
+
+/// A [`CsvRow`] as written to a spool file, where tags and attachments are JSON lists rather than
+/// the CSV's semicolon-separated text.
+#[cfg(feature = "postgres")]
+#[derive(serde::Deserialize)]
+struct SpooledRow {
+    full_name: String,
+    email: String,
+    cohort: String,
+    signal_type: String,
+    severity: i32,
+    note: String,
+    occurred_at: NaiveDate,
+    source_key: Option<String>,
+    tags: Vec<String>,
+    submitted_by: Option<String>,
+    attachments: Vec<Attachment>,
+    original_note: Option<String>,
+}
+
+#[cfg(feature = "postgres")]
+impl From<SpooledRow> for CsvRow {
+    fn from(row: SpooledRow) -> Self {
+        Self {
+            full_name: row.full_name,
+            email: row.email,
+            cohort: row.cohort,
+            signal_type: row.signal_type,
+            severity: row.severity,
+            note: row.note,
+            occurred_at: row.occurred_at,
+            source_key: row.source_key,
+            tags: row.tags,
+            submitted_by: row.submitted_by,
+            attachments: row.attachments,
+            original_note: row.original_note,
+        }
+    }
+}
+
+/// Email as the roster stores and compares it: trimmed, lowercased, and in Unicode NFC, so that
+/// case or encoding variants of one address land on one scholar.
+pub fn normalize_email(email: &str) -> String {
+    email.trim().to_lowercase().nfc().collect()
+}
+
+/// Normalizes `email` with [`normalize_email`], failing unless it has one `@` between a non-empty
+/// local part and a dotted domain, and no whitespace or control characters.
+pub fn parse_email(email: &str) -> anyhow::Result<String> {
+    let normalized = normalize_email(email);
+    let well_formed = normalized.len() <= 254
+        && !normalized
+            .chars()
+            .any(|c| c.is_whitespace() || c.is_control())
+        && normalized.split_once('@').is_some_and(|(local, domain)| {
+            !local.is_empty()
+                && !domain.contains('@')
+                && domain
+                    .split('.')
+                    .all(|label| !label.is_empty() && !label.starts_with('-'))
+                && domain.contains('.')
+        });
+    if !well_formed {
+        bail!("{email:?} is not a valid email address");
+    }
+    Ok(normalized)
+}
+
+/// Other spellings of cohort names, keyed trimmed and lowercased, mapped to the name scholars are
+/// stored under. Managed with `cohort aliases`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CohortAliases(BTreeMap<String, String>);
+
+impl CohortAliases {
+    /// How an alias is stored and matched.
+    pub fn key(alias: &str) -> String {
+        alias.trim().to_lowercase()
+    }
+
+    /// The canonical name for `cohort`, or `cohort` itself when it is not an alias.
+    pub fn canonical<'a>(&'a self, cohort: &'a str) -> &'a str {
+        self.0
+            .get(&Self::key(cohort))
+            .map_or(cohort, String::as_str)
+    }
+
+    /// Aliases in alphabetical order, each with its canonical name.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.0
+            .iter()
+            .map(|(alias, cohort)| (alias.as_str(), cohort.as_str()))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Replaces each row's cohort with its canonical name.
+    pub fn apply(&self, rows: &mut [CsvRow]) {
+        for row in rows {
+            if let Some(cohort) = self.0.get(&Self::key(&row.cohort)) {
+                row.cohort.clone_from(cohort);
+            }
+        }
+    }
+}
+
+impl FromIterator<(String, String)> for CohortAliases {
+    fn from_iter<I: IntoIterator<Item = (String, String)>>(aliases: I) -> Self {
+        Self(
+            aliases
+                .into_iter()
+                .map(|(alias, cohort)| (Self::key(&alias), cohort))
+                .collect(),
+        )
+    }
+}
+
+/// The signal types an organization recognizes, keyed trimmed and lowercased, with whether each is
+/// active. Managed with `signal-types`; while it is empty every type is accepted as written.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SignalTypes(BTreeMap<String, bool>);
+
+impl SignalTypes {
+    /// How a type name is stored and matched.
+    pub fn key(name: &str) -> String {
+        name.trim().to_lowercase()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Whether `name` is active, or `None` when it is not registered.
+    pub fn get(&self, name: &str) -> Option<bool> {
+        self.0.get(&Self::key(name)).copied()
+    }
+
+    /// Registers `name` as active, returning whether it was new.
+    pub fn register(&mut self, name: &str) -> bool {
+        match self.0.entry(Self::key(name)) {
+            std::collections::btree_map::Entry::Occupied(_) => false,
+            std::collections::btree_map::Entry::Vacant(entry) => {
+                entry.insert(true);
+                true
+            }
+        }
+    }
+
+    /// The registered name for `signal_type`, failing when it is unknown or inactive. Any type
+    /// passes unchanged while no types are registered.
+    pub fn check(&self, signal_type: &str) -> anyhow::Result<String> {
+        match self.get(signal_type) {
+            _ if self.is_empty() => Ok(signal_type.to_string()),
+            Some(true) => Ok(Self::key(signal_type)),
+            Some(false) => bail!("signal type {signal_type:?} is inactive"),
+            None => bail!("signal type {signal_type:?} is not registered"),
+        }
+    }
+}
+
+impl FromIterator<(String, bool)> for SignalTypes {
+    fn from_iter<I: IntoIterator<Item = (String, bool)>>(types: I) -> Self {
+        Self(
+            types
+                .into_iter()
+                .map(|(name, active)| (Self::key(&name), active))
+                .collect(),
+        )
+    }
+}
+
+/// Splits a semicolon-separated tag list, trimming and lowercasing each tag.
+pub fn parse_tags(raw: &str) -> Vec<String> {
+    let mut tags: Vec<String> = raw
+        .split(';')
+        .map(|tag| tag.trim().to_lowercase())
+        .filter(|tag| !tag.is_empty())
+        .collect();
+    tags.sort();
+    tags.dedup();
+    tags
+}
+
+fn deserialize_tags<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let raw: Option<String> = serde::Deserialize::deserialize(deserializer)?;
+    Ok(raw.as_deref().map(parse_tags).unwrap_or_default())
+}
+
+/// Parses a semicolon-separated attachment list. Each entry is `url`, `label|url`, or
+/// `kind|label|url`; URLs must be http(s).
+pub fn parse_attachments(raw: &str) -> anyhow::Result<Vec<Attachment>> {
+    let mut attachments = Vec::new();
+    for entry in raw
+        .split(';')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+    {
+        let parts: Vec<&str> = entry.splitn(3, '|').map(str::trim).collect();
+        let (kind, label, url) = match parts.as_slice() {
+            [url] => ("link", *url, *url),
+            [label, url] => ("link", *label, *url),
+            [kind, label, url] => (*kind, *label, *url),
+            _ => unreachable!("splitn yields one to three parts"),
+        };
+        if !(url.starts_with("https://") || url.starts_with("http://")) {
+            bail!("attachment {entry:?} needs an http(s) URL");
+        }
+        attachments.push(Attachment {
+            url: url.to_string(),
+            label: if label.is_empty() { url } else { label }.to_string(),
+            kind: if kind.is_empty() { "link" } else { kind }.to_lowercase(),
+        });
+    }
+    Ok(attachments)
+}
+
+fn deserialize_attachments<'de, D>(deserializer: D) -> Result<Vec<Attachment>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let raw: Option<String> = serde::Deserialize::deserialize(deserializer)?;
+    raw.as_deref()
+        .map(parse_attachments)
+        .transpose()
+        .map(Option::unwrap_or_default)
+        .map_err(serde::de::Error::custom)
+}
+
+/// What to do with notes longer than the configured maximum.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum, serde::Deserialize, serde::Serialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum NotePolicy {
+    /// Fail the import on the first oversized note
+    Reject,
+    /// Cut the note at the limit
+    Truncate,
+    /// Keep the first sentences, truncating if they are still too long
+    Summarize,
+}
+
+/// What an import does with a row whose source key is already stored.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    clap::ValueEnum,
+    serde::Deserialize,
+    serde::Serialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum OnConflict {
+    /// Keep the stored signal as it is
+    #[default]
+    Skip,
+    /// Overwrite the stored signal's severity, note, and date when they differ
+    Update,
+}
+
+/// What writing one import row did.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RowOutcome {
+    Inserted,
+    /// The stored signal with the row's source key took the row's severity, note, and date.
+    Updated,
+    /// The source key was archived, or already stored and left as it was.
+    Skipped,
+}
+
+/// How an import treats rows that don't validate as written.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Validation {
+    /// Leave out rows with an out-of-range severity or date and warn about them.
+    #[default]
+    Standard,
+    /// Fail on an empty file or the first row the standard mode would leave out.
+    Strict,
+    /// Trim whitespace, clamp severities into range, and read other common date formats, counting
+    /// each fix in [`Coercions`].
+    Lenient,
+}
+
+/// Date layouts a lenient import reads besides `YYYY-MM-DD`. Slashed dates are month first.
+const LENIENT_DATE_FORMATS: [&str; 7] = [
+    "%Y/%m/%d",
+    "%m/%d/%Y",
+    "%d.%m.%Y",
+    "%Y%m%d",
+    "%d %b %Y",
+    "%d-%b-%Y",
+    "%b %d, %Y",
+];
+
+/// Fixes a lenient import applied, counted by row.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize)]
+pub struct Coercions {
+    /// Rows with leading or trailing whitespace trimmed from a field.
+    pub trimmed: usize,
+    pub severities_clamped: usize,
+    /// Rows whose date was read in another format and rewritten as `YYYY-MM-DD`.
+    pub dates_reparsed: usize,
+}
+
+impl Coercions {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Rewrites `record`, laid out per `headers`, the way [`Validation::Lenient`] reads it. Fields it
+/// cannot fix are left for deserialization to reject.
+fn coerce(
+    record: &mut csv::StringRecord,
+    headers: &csv::StringRecord,
+    severity: SeverityBounds,
+    coercions: &mut Coercions,
+) {
+    let mut trimmed = false;
+    let mut clamped = false;
+    let mut reparsed = false;
+    let fields: Vec<String> = record
+        .iter()
+        .zip(headers.iter().chain(std::iter::repeat("")))
+        .map(|(field, header)| {
+            let value = field.trim();
+            trimmed |= value.len() != field.len();
+            match header {
+                "severity" => match value.parse::<i32>() {
+                    Ok(level) if !severity.contains(level) => {
+                        clamped = true;
+                        level.clamp(severity.min, severity.max).to_string()
+                    }
+                    _ => value.to_string(),
+                },
+                "occurred_at" if NaiveDate::parse_from_str(value, "%Y-%m-%d").is_err() => {
+                    let date = LENIENT_DATE_FORMATS
+                        .iter()
+                        .find_map(|format| NaiveDate::parse_from_str(value, format).ok());
+                    reparsed |= date.is_some();
+                    date.map_or_else(|| value.to_string(), |date| date.to_string())
+                }
+                _ => value.to_string(),
+            }
+        })
+        .collect();
+    *record = csv::StringRecord::from(fields);
+    coercions.trimmed += usize::from(trimmed);
+    coercions.severities_clamped += usize::from(clamped);
+    coercions.dates_reparsed += usize::from(reparsed);
+}
+
+/// Severities a signal may carry, configured under `[severity]`. The database enforces the same
+/// range with the `signals_severity_range` check constraint; see `db::apply_severity_bounds`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SeverityBounds {
+    pub min: i32,
+    pub max: i32,
+}
+
+impl Default for SeverityBounds {
+    fn default() -> Self {
+        Self { min: 1, max: 5 }
+    }
+}
+
+impl SeverityBounds {
+    pub fn contains(&self, severity: i32) -> bool {
+        (self.min..=self.max).contains(&severity)
+    }
+
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if self.min > self.max {
+            bail!(
+                "[severity] min ({}) must not be above max ({})",
+                self.min,
+                self.max
+            );
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for SeverityBounds {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}", self.min, self.max)
+    }
+}
+
+/// How far from today a signal may be dated, configured under `[dates]`. Imports leave out rows
+/// dated outside the window and `signal add` rejects them, unless `--allow-any-date` is given.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DateWindow {
+    /// Days after today a signal may be dated, for time zones ahead of UTC.
+    pub max_future_days: u32,
+    /// Days before today a signal may be dated.
+    pub max_backdate_days: u32,
+}
+
+impl Default for DateWindow {
+    fn default() -> Self {
+        Self {
+            max_future_days: 0,
+            max_backdate_days: 730,
+        }
+    }
+}
+
+impl DateWindow {
+    /// The dates accepted on `today`.
+    pub fn range(&self, today: NaiveDate) -> DateRange {
+        DateRange {
+            earliest: today - chrono::Duration::days(i64::from(self.max_backdate_days)),
+            latest: today + chrono::Duration::days(i64::from(self.max_future_days)),
+        }
+    }
+}
+
+/// First and last date a [`DateWindow`] accepts on a given day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+pub struct DateRange {
+    pub earliest: NaiveDate,
+    pub latest: NaiveDate,
+}
+
+impl DateRange {
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        (self.earliest..=self.latest).contains(&date)
+    }
+}
+
+impl std::fmt::Display for DateRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} to {}", self.earliest, self.latest)
+    }
+}
+
+/// Out-of-range rows an import summary lists by line before counting the rest.
+const MAX_LISTED_ROWS: usize = 10;
+
+/// `rows` joined for a warning, cut off after [`MAX_LISTED_ROWS`] with a count of the rest.
+fn list_rows(rows: impl ExactSizeIterator<Item = String>) -> String {
+    let total = rows.len();
+    let mut listed: Vec<String> = rows.take(MAX_LISTED_ROWS).collect();
+    if total > MAX_LISTED_ROWS {
+        listed.push(format!("and {} more", total - MAX_LISTED_ROWS));
+    }
+    listed.join(", ")
+}
+
+/// A row left out of an import because its severity is outside the configured bounds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+pub struct OutOfRangeRow {
+    pub line: u64,
+    pub severity: i32,
+}
+
+/// A row left out of an import because it is dated outside the configured [`DateWindow`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+pub struct OutOfWindowRow {
+    pub line: u64,
+    pub occurred_at: NaiveDate,
+}
+
+/// A row left out of an import because its signal type is not registered or is inactive.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct UnregisteredTypeRow {
+    pub line: u64,
+    pub signal_type: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct ImportLimits {
+    pub max_file_bytes: u64,
+    pub max_rows: usize,
+    pub max_note_chars: usize,
+    pub chunk_size: usize,
+    pub note_policy: NotePolicy,
+    pub summary_sentences: usize,
+    pub keep_note_overflow: bool,
+    pub severity: SeverityBounds,
+    /// Dates rows must fall within; `None` accepts any date.
+    pub dates: Option<DateWindow>,
+    pub validation: Validation,
+    pub on_conflict: OnConflict,
+    /// Add signal types missing from [`SignalTypes`] instead of leaving their rows out.
+    pub register_types: bool,
+}
+
+impl Default for ImportLimits {
+    fn default() -> Self {
+        Self {
+            max_file_bytes: 64 * 1024 * 1024,
+            max_rows: 100_000,
+            max_note_chars: 2_000,
+            chunk_size: 500,
+            note_policy: NotePolicy::Reject,
+            summary_sentences: 2,
+            keep_note_overflow: false,
+            severity: SeverityBounds::default(),
+            dates: None,
+            validation: Validation::Standard,
+            on_conflict: OnConflict::Skip,
+            register_types: false,
+        }
+    }
+}
+
+/// How a partner's CSV layout maps onto ours, configured under `[sources.<name>]`.
+#[derive(Debug, Clone, Default, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SourceMapping {
+    /// Our column name to the partner's header, for headers that differ.
+    pub columns: BTreeMap<String, String>,
+    /// Cohort for every row when the file has no cohort column.
+    pub cohort: Option<String>,
+}
+
+impl SourceMapping {
+    /// Renames mapped headers and appends a `cohort` header when a fixed cohort fills it in.
+    /// Returns the headers and whether each record needs the fixed cohort appended.
+    fn apply(&self, headers: &csv::StringRecord) -> (csv::StringRecord, bool) {
+        let mut mapped: csv::StringRecord = headers
+            .iter()
+            .map(|header| {
+                self.columns
+                    .iter()
+                    .find(|(_, theirs)| theirs.as_str() == header)
+                    .map_or(header, |(ours, _)| ours.as_str())
+            })
+            .collect();
+        let fill_cohort = self.cohort.is_some() && !mapped.iter().any(|header| header == "cohort");
+        if fill_cohort {
+            mapped.push_field("cohort");
+        }
+        (mapped, fill_cohort)
+    }
+}
+
+/// Outcome of one import, also written as JSON by `import --summary-out`.
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct ImportSummary {
+    pub batch_id: Uuid,
+    pub rows_read: usize,
+    pub inserted: usize,
+    /// Rows that overwrote a stored signal under [`OnConflict::Update`].
+    pub updated: usize,
+    /// Rows whose source key was already imported or archived.
+    pub skipped: usize,
+    pub notes_shortened: usize,
+    pub future_dated: usize,
+    /// Rows read per signal type.
+    pub signal_types: BTreeMap<String, usize>,
+    pub min_occurred_at: Option<NaiveDate>,
+    pub max_occurred_at: Option<NaiveDate>,
+    /// Rows left unwritten in `spool` because the database stayed unreachable.
+    pub spooled: usize,
+    pub spool: Option<PathBuf>,
+    /// Rows left out because their severity was outside the configured bounds.
+    pub out_of_range: Vec<OutOfRangeRow>,
+    pub severity: SeverityBounds,
+    /// Rows left out because they were dated outside `dates`.
+    pub out_of_window: Vec<OutOfWindowRow>,
+    pub dates: Option<DateRange>,
+    /// Fixes applied by a lenient import.
+    pub coercions: Coercions,
+    /// Rows left out because their signal type is not registered or is inactive.
+    pub unregistered: Vec<UnregisteredTypeRow>,
+    /// Signal types added under `register_types`.
+    pub registered_types: Vec<String>,
+    pub warnings: Vec<String>,
+}
+
+impl ImportSummary {
+    pub fn record(&mut self, row: &CsvRow, outcome: RowOutcome, today: NaiveDate) {
+        match outcome {
+            RowOutcome::Inserted => self.inserted += 1,
+            RowOutcome::Updated => self.updated += 1,
+            RowOutcome::Skipped => self.skipped += 1,
+        }
+        if row.occurred_at > today {
+            self.future_dated += 1;
+        }
+        *self
+            .signal_types
+            .entry(row.signal_type.clone())
+            .or_default() += 1;
+        self.min_occurred_at = Some(
+            self.min_occurred_at
+                .map_or(row.occurred_at, |min| min.min(row.occurred_at)),
+        );
+        self.max_occurred_at = Some(
+            self.max_occurred_at
+                .map_or(row.occurred_at, |max| max.max(row.occurred_at)),
+        );
+    }
+
+    /// Fills `warnings` from the counts gathered so far.
+    pub fn collect_warnings(&mut self) {
+        self.warnings.clear();
+        if self.rows_read == 0 {
+            self.warnings.push("file contained no rows".to_string());
+        }
+        if self.skipped > 0 {
+            self.warnings.push(format!(
+                "{} rows skipped because their source key was already imported or archived",
+                self.skipped
+            ));
+        }
+        if self.notes_shortened > 0 {
+            self.warnings.push(format!(
+                "{} notes shortened to fit the note length limit",
+                self.notes_shortened
+            ));
+        }
+        if self.future_dated > 0 {
+            self.warnings
+                .push(format!("{} rows dated in the future", self.future_dated));
+        }
+        if !self.out_of_range.is_empty() {
+            self.warnings.push(format!(
+                "{} rows left out because their severity is outside {}: {}",
+                self.out_of_range.len(),
+                self.severity,
+                list_rows(
+                    self.out_of_range
+                        .iter()
+                        .map(|row| format!("line {} ({})", row.line, row.severity))
+                )
+            ));
+        }
+        if let (false, Some(dates)) = (self.out_of_window.is_empty(), self.dates) {
+            self.warnings.push(format!(
+                "{} rows left out because they are dated outside {dates}: {}; import them with \
+                 --allow-any-date if the dates are right",
+                self.out_of_window.len(),
+                list_rows(
+                    self.out_of_window
+                        .iter()
+                        .map(|row| format!("line {} ({})", row.line, row.occurred_at))
+                )
+            ));
+        }
+        if !self.unregistered.is_empty() {
+            self.warnings.push(format!(
+                "{} rows left out because their signal type is not registered or is inactive: {}; \
+                 add the type with `signal-types add`, or import with --register-types",
+                self.unregistered.len(),
+                list_rows(
+                    self.unregistered
+                        .iter()
+                        .map(|row| format!("line {} ({})", row.line, row.signal_type))
+                )
+            ));
+        }
+        if !self.registered_types.is_empty() {
+            self.warnings.push(format!(
+                "registered {} new signal types: {}",
+                self.registered_types.len(),
+                self.registered_types.join(", ")
+            ));
+        }
+        if !self.coercions.is_empty() {
+            let Coercions {
+                trimmed,
+                severities_clamped,
+                dates_reparsed,
+            } = self.coercions;
+            self.warnings.push(format!(
+                "--lenient trimmed whitespace in {trimmed} rows, clamped {severities_clamped} \
+                 severities into {}, and reparsed {dates_reparsed} dates as YYYY-MM-DD",
+                self.severity
+            ));
+        }
+        if let Some(spool) = &self.spool {
+            self.warnings.push(format!(
+                "{} rows spooled to {} while the database was unreachable; write them with \
+                 `import resume`",
+                self.spooled,
+                spool.display()
+            ));
+        }
+    }
+}
+
+/// The files an import of `path` reads: `path` itself, or every `.csv` file directly inside it
+/// when it is a directory, in name order.
+pub fn csv_files(path: &Path) -> anyhow::Result<Vec<PathBuf>> {
+    if !path.is_dir() {
+        return Ok(vec![path.to_path_buf()]);
+    }
+    let mut files = Vec::new();
+    for entry in
+        std::fs::read_dir(path).with_context(|| format!("failed to read {}", path.display()))?
+    {
+        let file = entry?.path();
+        let is_csv = file
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("csv"));
+        if is_csv && file.is_file() {
+            files.push(file);
+        }
+    }
+    if files.is_empty() {
+        bail!("{} contains no .csv files", path.display());
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Reads rows in bounded chunks so neither the file nor a single row can grow without limit.
+pub struct ChunkedReader<R: Read> {
+    reader: csv::Reader<R>,
+    limits: ImportLimits,
+    mapping: SourceMapping,
+    rows_read: usize,
+    notes_shortened: usize,
+    out_of_range: Vec<OutOfRangeRow>,
+    dates: Option<DateRange>,
+    out_of_window: Vec<OutOfWindowRow>,
+    coercions: Coercions,
+    signal_types: SignalTypes,
+    unregistered: Vec<UnregisteredTypeRow>,
+    registered: Vec<String>,
+}
+
+impl ChunkedReader<Take<File>> {
+    pub fn open(path: &Path, limits: ImportLimits) -> anyhow::Result<Self> {
+        let size = std::fs::metadata(path)
+            .with_context(|| format!("failed to read {}", path.display()))?
+            .len();
+        if size > limits.max_file_bytes {
+            bail!(
+                "{} is {} bytes, above the {} byte import limit",
+                path.display(),
+                size,
+                limits.max_file_bytes
+            );
+        }
+
+        // Cap reads at the limit as well in case the file grows while we import it.
+        let file = File::open(path)?.take(limits.max_file_bytes);
+        Ok(Self::new(file, limits))
+    }
+}
+
+impl<R: Read> ChunkedReader<R> {
+    pub fn new(source: R, limits: ImportLimits) -> Self {
+        let today = chrono::Utc::now().date_naive();
+        Self {
+            reader: csv::Reader::from_reader(source),
+            dates: limits.dates.map(|window| window.range(today)),
+            out_of_window: Vec::new(),
+            coercions: Coercions::default(),
+            signal_types: SignalTypes::default(),
+            unregistered: Vec::new(),
+            registered: Vec::new(),
+            limits,
+            mapping: SourceMapping::default(),
+            rows_read: 0,
+            notes_shortened: 0,
+            out_of_range: Vec::new(),
+        }
+    }
+
+    /// Reads a partner layout through `mapping` instead of our own column names.
+    pub fn with_mapping(mut self, mapping: SourceMapping) -> Self {
+        self.mapping = mapping;
+        self
+    }
+
+    /// Checks each row's signal type against `types`, storing it under the registered name.
+    pub fn with_signal_types(mut self, types: SignalTypes) -> Self {
+        self.signal_types = types;
+        self
+    }
+
+    pub fn rows_read(&self) -> usize {
+        self.rows_read
+    }
+
+    /// Notes cut down by the truncate or summarize policy so far.
+    pub fn notes_shortened(&self) -> usize {
+        self.notes_shortened
+    }
+
+    /// Rows skipped so far because their severity was outside `limits.severity`.
+    pub fn out_of_range(&self) -> &[OutOfRangeRow] {
+        &self.out_of_range
+    }
+
+    /// Dates rows must fall within, fixed when the reader was created.
+    pub fn dates(&self) -> Option<DateRange> {
+        self.dates
+    }
+
+    /// Rows skipped so far because their signal type is not registered or is inactive.
+    pub fn unregistered(&self) -> &[UnregisteredTypeRow] {
+        &self.unregistered
+    }
+
+    /// Signal types registered under `limits.register_types` since the last call, for the caller
+    /// to store before writing the chunk that uses them.
+    pub fn take_registered(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.registered)
+    }
+
+    /// Fixes applied so far under [`Validation::Lenient`].
+    pub fn coercions(&self) -> Coercions {
+        self.coercions
+    }
+
+    /// Rows skipped so far because they were dated outside [`Self::dates`].
+    pub fn out_of_window(&self) -> &[OutOfWindowRow] {
+        &self.out_of_window
+    }
+
+    /// Returns the next chunk of validated rows, or an empty vector once the input is exhausted.
+    pub fn next_chunk(&mut self) -> anyhow::Result<Vec<CsvRow>> {
+        let mut chunk = Vec::with_capacity(self.limits.chunk_size.max(1));
+        let mut record = csv::StringRecord::new();
+        let (headers, fill_cohort) = self.mapping.apply(self.reader.headers()?);
+
+        while chunk.len() < self.limits.chunk_size.max(1) {
+            if !self.reader.read_record(&mut record)? {
+                if self.rows_read == 0 && self.limits.validation == Validation::Strict {
+                    bail!("file contained no rows");
+                }
+                break;
+            }
+
+            self.rows_read += 1;
+            if self.rows_read > self.limits.max_rows {
+                bail!("import exceeds the {} row limit", self.limits.max_rows);
+            }
+
+            let line = record.position().map(|p| p.line()).unwrap_or(0);
+            if let (true, Some(cohort)) = (fill_cohort, &self.mapping.cohort) {
+                record.push_field(cohort);
+            }
+            if self.limits.validation == Validation::Lenient {
+                coerce(
+                    &mut record,
+                    &headers,
+                    self.limits.severity,
+                    &mut self.coercions,
+                );
+            }
+            let mut row: CsvRow = record
+                .deserialize(Some(&headers))
+                .with_context(|| format!("invalid row on line {line}"))?;
+            row.email =
+                parse_email(&row.email).with_context(|| format!("invalid row on line {line}"))?;
+            let strict = self.limits.validation == Validation::Strict;
+            if !self.limits.severity.contains(row.severity) {
+                if strict {
+                    bail!(
+                        "invalid row on line {line}: severity {} is outside {}",
+                        row.severity,
+                        self.limits.severity
+                    );
+                }
+                self.out_of_range.push(OutOfRangeRow {
+                    line,
+                    severity: row.severity,
+                });
+                continue;
+            }
+            if let Some(dates) = self.dates.filter(|dates| !dates.contains(row.occurred_at)) {
+                if strict {
+                    bail!(
+                        "invalid row on line {line}: dated {}, outside {dates}",
+                        row.occurred_at
+                    );
+                }
+                self.out_of_window.push(OutOfWindowRow {
+                    line,
+                    occurred_at: row.occurred_at,
+                });
+                continue;
+            }
+            if self.limits.register_types && self.signal_types.register(&row.signal_type) {
+                self.registered.push(SignalTypes::key(&row.signal_type));
+            }
+            match self.signal_types.check(&row.signal_type) {
+                Ok(name) => row.signal_type = name,
+                Err(err) if strict => {
+                    return Err(err.context(format!("invalid row on line {line}")))
+                }
+                Err(_) => {
+                    self.unregistered.push(UnregisteredTypeRow {
+                        line,
+                        signal_type: row.signal_type,
+                    });
+                    continue;
+                }
+            }
+            let note_len = row.note.len();
+            apply_note_policy(&mut row, &self.limits)
+                .with_context(|| format!("invalid row on line {line}"))?;
+            if row.note.len() != note_len {
+                self.notes_shortened += 1;
+            }
+            chunk.push(row);
+        }
+
+        Ok(chunk)
+    }
+}
+
+pub fn apply_note_policy(row: &mut CsvRow, limits: &ImportLimits) -> anyhow::Result<()> {
+    if let Some(shortened) = shortened_note(&row.note, limits)? {
+        let original = std::mem::replace(&mut row.note, shortened);
+        if limits.keep_note_overflow {
+            row.original_note = Some(original);
+        }
+    }
+    Ok(())
+}
+
+/// `note` shortened under the note policy of `limits`, or `None` when it fits.
+fn shortened_note(note: &str, limits: &ImportLimits) -> anyhow::Result<Option<String>> {
+    let note_chars = note.chars().count();
+    if note_chars <= limits.max_note_chars {
+        return Ok(None);
+    }
+
+    let shortened = match limits.note_policy {
+        NotePolicy::Reject => bail!(
+            "note is {} characters, above the {} character limit",
+            note_chars,
+            limits.max_note_chars
+        ),
+        NotePolicy::Truncate => truncate_note(note, limits.max_note_chars),
+        NotePolicy::Summarize => truncate_note(
+            &first_sentences(note, limits.summary_sentences),
+            limits.max_note_chars,
+        ),
+    };
+    Ok(Some(shortened))
+}
+
+/// Cuts `note` to at most `max_chars` characters, marking the cut with an ellipsis.
+pub fn truncate_note(note: &str, max_chars: usize) -> String {
+    if note.chars().count() <= max_chars {
+        return note.to_string();
+    }
+    if max_chars == 0 {
+        return String::new();
+    }
+
+    let mut truncated: String = note.chars().take(max_chars - 1).collect();
+    truncated.truncate(truncated.trim_end().len());
+    truncated.push('…');
+    truncated
+}
+
+pub fn first_sentences(note: &str, count: usize) -> String {
+    let mut end = 0;
+    let mut found = 0;
+    let mut chars = note.char_indices().peekable();
+
+    while let Some((index, ch)) = chars.next() {
+        if !matches!(ch, '.' | '!' | '?') {
+            continue;
+        }
+        let at_boundary = chars.peek().is_none_or(|(_, next)| next.is_whitespace());
+        if at_boundary {
+            found += 1;
+            end = index + ch.len_utf8();
+            if found >= count {
+                break;
+            }
+        }
+    }
+
+    if found == 0 {
+        note.trim().to_string()
+    } else {
+        note[..end].trim().to_string()
+    }
+}
+
+/// Checks a hand-entered signal against the severities, dates, and note policy of `limits` as of
+/// `today`, shortening its note as the policy says. Returns the date it occurred on and, with
+/// `keep_note_overflow`, the note as given.
+#[cfg(feature = "postgres")]
+fn check_new_signal(
+    signal: &mut NewSignal,
+    limits: &ImportLimits,
+    today: NaiveDate,
+) -> anyhow::Result<(NaiveDate, Option<String>)> {
+    let severity = limits.severity;
+    if !severity.contains(signal.severity) {
+        bail!(
+            "severity must be between {} and {}, got {}",
+            severity.min,
+            severity.max,
+            signal.severity
+        );
+    }
+    let occurred_at = signal.occurred_at.unwrap_or(today);
+    if let Some(range) = limits.dates.map(|window| window.range(today)) {
+        if !range.contains(occurred_at) {
+            bail!("{occurred_at} is outside the accepted signal dates, {range}");
+        }
+    }
+    let mut original_note = None;
+    if let Some(shortened) = shortened_note(&signal.note, limits)? {
+        let original = std::mem::replace(&mut signal.note, shortened);
+        if limits.keep_note_overflow {
+            original_note = Some(original);
+        }
+    }
+    Ok((occurred_at, original_note))
+}
+
+/// Records one hand-entered signal with the same upsert rules as an import, in its own batch
+/// labelled `source`. Returns the row as stored and whether it was new. Fails when the severity or
+/// date is outside `limits`, the note too long under a rejecting note policy, or the type is not a
+/// registered and active one of [`db::signal_types`].
+#[cfg(feature = "postgres")]
+pub async fn add_signal(
+    pool: &PgPool,
+    org_id: Uuid,
+    mut signal: NewSignal,
+    source: &str,
+    limits: &ImportLimits,
+) -> anyhow::Result<(CsvRow, bool)> {
+    let (occurred_at, original_note) =
+        check_new_signal(&mut signal, limits, chrono::Utc::now().date_naive())?;
+    let signal_type = db::signal_types(pool, org_id)
+        .await?
+        .check(&signal.signal_type)?;
+    let email = parse_email(&signal.email)?;
+    let (full_name, cohort) = match db::scholar_profile(pool, org_id, &email).await? {
+        Some((current_name, current_cohort)) => (
+            signal.full_name.unwrap_or(current_name),
+            signal.cohort.unwrap_or(current_cohort),
+        ),
+        None => (
+            signal
+                .full_name
+                .with_context(|| format!("{email} is not on the roster, so a name is required"))?,
+            signal.cohort.with_context(|| {
+                format!("{email} is not on the roster, so a cohort is required")
+            })?,
+        ),
+    };
+    let cohort = db::cohort_aliases(pool, org_id)
+        .await?
+        .canonical(&cohort)
+        .to_string();
+    let row = CsvRow {
+        full_name,
+        email,
+        cohort,
+        signal_type,
+        severity: signal.severity,
+        note: signal.note,
+        occurred_at,
+        source_key: Some(
+            signal
+                .source_key
+                .unwrap_or_else(|| format!("manual-{}", Uuid::new_v4())),
+        ),
+        tags: parse_tags(&signal.tags.join(";")),
+        submitted_by: signal.submitted_by,
+        attachments: Vec::new(),
+        original_note,
+    };
+
+    let batch_id = db::create_import_batch(pool, org_id, source).await?;
+    let inserted = db::insert_signal_row(pool, org_id, &row, batch_id).await?;
+    Ok((row, inserted))
+}
+
+/// How an import rides out a database that drops away partway through.
+#[cfg(feature = "postgres")]
+#[derive(Debug, Clone, Default)]
+pub struct Recovery {
+    pub retry: db::RetryPolicy,
+    /// Where to spool the rows still unwritten once retrying gives up, for [`resume_spool`]. Without
+    /// it the import fails instead.
+    pub spool_dir: Option<PathBuf>,
+}
+
+/// First line of a spool file, naming where its rows belong.
+#[cfg(feature = "postgres")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct SpoolHeader {
+    org_id: Uuid,
+    batch_id: Uuid,
+    source: String,
+    /// Absent from files spooled before `--on-conflict` existed, which all skipped.
+    #[serde(default)]
+    on_conflict: OnConflict,
+    /// Signal types `--register-types` added while reading rows the database never took, registered
+    /// on resume before the rows are written.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    register_types: Vec<String>,
+    /// Notes in the rows are sealed with the note key, as they would be in the database. Absent
+    /// from files spooled without one.
+    #[serde(default)]
+    sealed_notes: bool,
+}
+
+/// Writes a spool file through temporary names, so a half-written one is never resumed. Rows go
+/// out as they come, with their notes sealed under `key` if there is one; the header goes first
+/// once [`Self::finish`] knows every type to register.
+#[cfg(feature = "postgres")]
+struct SpoolWriter {
+    path: PathBuf,
+    rows_path: PathBuf,
+    out: BufWriter<File>,
+    rows: usize,
+    key: Option<NoteKey>,
+}
+
+#[cfg(feature = "postgres")]
+impl SpoolWriter {
+    fn create(path: PathBuf, key: Option<&NoteKey>) -> anyhow::Result<Self> {
+        let rows_path = path.with_extension("jsonl.rows");
+        let file = File::create(&rows_path)
+            .with_context(|| format!("failed to create {}", rows_path.display()))?;
+        Ok(Self {
+            path,
+            rows_path,
+            out: BufWriter::new(file),
+            rows: 0,
+            key: key.cloned(),
+        })
+    }
+
+    /// Whether the rows are written with their notes sealed, for [`SpoolHeader::sealed_notes`].
+    fn seals_notes(&self) -> bool {
+        self.key.is_some()
+    }
+
+    fn write(&mut self, rows: &[CsvRow]) -> anyhow::Result<()> {
+        for row in rows {
+            let sealed;
+            let row = match &self.key {
+                Some(key) => {
+                    sealed = CsvRow {
+                        note: key.seal(&row.note),
+                        original_note: row.original_note.as_deref().map(|note| key.seal(note)),
+                        ..row.clone()
+                    };
+                    &sealed
+                }
+                None => row,
+            };
+            serde_json::to_writer(&mut self.out, row)?;
+            self.out.write_all(b"\n")?;
+        }
+        self.rows += rows.len();
+        Ok(())
+    }
+
+    fn finish(self, header: &SpoolHeader) -> anyhow::Result<(PathBuf, usize)> {
+        self.out.into_inner().map_err(|err| err.into_error())?;
+        let partial = self.path.with_extension("jsonl.partial");
+        let file = File::create(&partial)
+            .with_context(|| format!("failed to create {}", partial.display()))?;
+        let mut out = BufWriter::new(file);
+        serde_json::to_writer(&mut out, header)?;
+        out.write_all(b"\n")?;
+        std::io::copy(&mut File::open(&self.rows_path)?, &mut out)?;
+        out.into_inner()
+            .map_err(|err| err.into_error())?
+            .sync_all()?;
+        std::fs::rename(&partial, &self.path)
+            .with_context(|| format!("failed to write {}", self.path.display()))?;
+        std::fs::remove_file(&self.rows_path)
+            .with_context(|| format!("failed to remove {}", self.rows_path.display()))?;
+        Ok((self.path, self.rows))
+    }
+}
+
+/// The header and rows of a spool file written by [`SpoolWriter`], with notes opened under `key`
+/// when they were spooled sealed.
+#[cfg(feature = "postgres")]
+fn read_spool(path: &Path, key: Option<&NoteKey>) -> anyhow::Result<(SpoolHeader, Vec<CsvRow>)> {
+    let file = File::open(path).with_context(|| format!("failed to open {}", path.display()))?;
+    let mut lines = BufReader::new(file).lines();
+    let header: SpoolHeader = match lines.next() {
+        Some(line) => serde_json::from_str(&line?)
+            .with_context(|| format!("{} has no spool header", path.display()))?,
+        None => bail!("{} is empty", path.display()),
+    };
+    let key = match key {
+        _ if !header.sealed_notes => None,
+        Some(key) => Some(key),
+        None => bail!(
+            "{} was spooled with encrypted notes; set the note key to resume it",
+            path.display()
+        ),
+    };
+    let rows = lines
+        .enumerate()
+        .map(|(index, line)| {
+            let mut row = CsvRow::from(
+                serde_json::from_str::<SpooledRow>(&line?)
+                    .with_context(|| format!("{} line {}", path.display(), index + 2))?,
+            );
+            if let Some(key) = key {
+                row.note = key.open(row.note)?;
+                row.original_note = row.original_note.map(|note| key.open(note)).transpose()?;
+            }
+            Ok(row)
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    Ok((header, rows))
+}
+
+#[cfg(feature = "postgres")]
+fn add_tags(chunk: &mut [CsvRow], extra_tags: &[String]) {
+    if extra_tags.is_empty() {
+        return;
+    }
+    for row in chunk {
+        row.tags.extend_from_slice(extra_tags);
+        row.tags.sort();
+        row.tags.dedup();
+    }
+}
+
+/// Writes `chunk` with [`db::insert_signal_chunk`], retrying transient failures per `retry`. A
+/// failed chunk's transaction rolls back whole, so a retry never writes a row twice.
+#[cfg(feature = "postgres")]
+async fn write_chunk(
+    pool: &PgPool,
+    org_id: Uuid,
+    chunk: &[CsvRow],
+    batch_id: Uuid,
+    scholars: &mut db::ScholarCache,
+    on_conflict: OnConflict,
+    retry: &db::RetryPolicy,
+) -> anyhow::Result<Vec<RowOutcome>> {
+    let mut attempt = 0;
+    loop {
+        let written =
+            db::insert_signal_chunk(pool, org_id, chunk, batch_id, scholars, on_conflict).await;
+        let err = match written {
+            Ok(outcomes) => return Ok(outcomes),
+            Err(err) => err,
+        };
+        let Some(delay) = retry.backoff(&err, attempt) else {
+            return Err(err);
+        };
+        tracing::warn!(
+            batch = %batch_id,
+            attempt = attempt + 1,
+            delay_ms = delay.as_millis() as u64,
+            error = format!("{err:#}"),
+            "retrying import chunk after a transient database error"
+        );
+        tokio::time::sleep(delay).await;
+        attempt += 1;
+    }
+}
+
+/// Time an import spent reading and validating rows and writing them, logged at info level when
+/// it finishes; each chunk written is logged at debug level.
+#[cfg(feature = "postgres")]
+#[derive(Debug, Default)]
+struct StageTimes {
+    parse: Duration,
+    write: Duration,
+}
+
+#[cfg(feature = "postgres")]
+impl StageTimes {
+    fn chunk_written(&mut self, batch_id: Uuid, rows: usize, started: Instant) {
+        let elapsed = started.elapsed();
+        self.write += elapsed;
+        tracing::debug!(
+            batch = %batch_id,
+            rows,
+            write_ms = elapsed.as_millis() as u64,
+            "import chunk written"
+        );
+    }
+
+    fn finished(&self, csv_path: &Path, summary: &ImportSummary) {
+        tracing::info!(
+            file = %csv_path.display(),
+            batch = %summary.batch_id,
+            rows = summary.rows_read,
+            inserted = summary.inserted,
+            updated = summary.updated,
+            skipped = summary.skipped,
+            parse_ms = self.parse.as_millis() as u64,
+            write_ms = self.write.as_millis() as u64,
+            "import finished"
+        );
+    }
+}
+
+#[cfg(feature = "postgres")]
+/// Imports every row of `csv_path` read through `mapping`, adding `extra_tags` (already normalized
+/// with [`parse_tags`]) to each signal and storing cohorts under their canonical name from
+/// [`db::cohort_aliases`]. Each chunk of `limits.chunk_size` rows is written in one transaction by
+/// [`db::insert_signal_chunk`], which skips or updates rows whose source key is already stored
+/// per `limits.on_conflict`.
+///
+/// A chunk that fails on a transient database error is retried per `recovery.retry`. If it still
+/// fails and `recovery.spool_dir` is set, that chunk and the rest of the file are spooled to
+/// `<batch_id>.jsonl` there and the summary reports them in `spooled`.
+pub async fn import_csv(
+    pool: &PgPool,
+    org_id: Uuid,
+    csv_path: &Path,
+    limits: ImportLimits,
+    mapping: &SourceMapping,
+    extra_tags: &[String],
+    recovery: &Recovery,
+) -> anyhow::Result<ImportSummary> {
+    let (severity, on_conflict) = (limits.severity, limits.on_conflict);
+    let mut reader = ChunkedReader::open(csv_path, limits)?
+        .with_mapping(mapping.clone())
+        .with_signal_types(db::signal_types(pool, org_id).await?);
+    let batch_id = db::create_import_batch(pool, org_id, &csv_path.display().to_string()).await?;
+    let mut summary = ImportSummary {
+        batch_id,
+        severity,
+        dates: reader.dates(),
+        ..ImportSummary::default()
+    };
+    let today = chrono::Utc::now().date_naive();
+    let mut scholars = db::ScholarCache::default();
+    let aliases = db::cohort_aliases(pool, org_id).await?;
+
+    let mut times = StageTimes::default();
+    loop {
+        let started = Instant::now();
+        let mut chunk = reader.next_chunk()?;
+        times.parse += started.elapsed();
+        // Types are only registered for rows the chunk keeps, so an empty chunk registers none.
+        let registered = reader.take_registered();
+        if chunk.is_empty() {
+            break;
+        }
+
+        aliases.apply(&mut chunk);
+        add_tags(&mut chunk, extra_tags);
+        let started = Instant::now();
+        let written = match db::register_signal_types(pool, org_id, &registered).await {
+            Ok(()) => {
+                write_chunk(
+                    pool,
+                    org_id,
+                    &chunk,
+                    batch_id,
+                    &mut scholars,
+                    on_conflict,
+                    &recovery.retry,
+                )
+                .await
+            }
+            Err(err) => Err(err),
+        };
+        let err = match written {
+            Ok(outcomes) => {
+                summary.registered_types.extend(registered);
+                times.chunk_written(batch_id, chunk.len(), started);
+                for (row, outcome) in chunk.iter().zip(outcomes) {
+                    summary.record(row, outcome, today);
+                }
+                continue;
+            }
+            Err(err) => err,
+        };
+        let Some(dir) = recovery
+            .spool_dir
+            .as_deref()
+            .filter(|_| db::is_transient(&err))
+        else {
+            return Err(err);
+        };
+        let mut spool = SpoolWriter::create(dir.join(format!("{batch_id}.jsonl")), notes::key())?;
+        let mut register_types = registered;
+        while !chunk.is_empty() {
+            spool.write(&chunk)?;
+            chunk = reader.next_chunk()?;
+            register_types.extend(reader.take_registered());
+            aliases.apply(&mut chunk);
+            add_tags(&mut chunk, extra_tags);
+        }
+        let header = SpoolHeader {
+            org_id,
+            batch_id,
+            source: csv_path.display().to_string(),
+            on_conflict,
+            register_types,
+            sealed_notes: spool.seals_notes(),
+        };
+        let (path, rows) = spool.finish(&header)?;
+        summary.spooled = rows;
+        summary.spool = Some(path);
+        break;
+    }
+
+    summary.rows_read = reader.rows_read();
+    summary.notes_shortened = reader.notes_shortened();
+    summary.out_of_range = reader.out_of_range().to_vec();
+    summary.out_of_window = reader.out_of_window().to_vec();
+    summary.coercions = reader.coercions();
+    summary.unregistered = reader.unregistered().to_vec();
+    summary.collect_warnings();
+    times.finished(csv_path, &summary);
+    Ok(summary)
+}
+
+#[cfg(feature = "postgres")]
+/// Imports `csv_path` like [`import_csv`], but in a single transaction: an invalid row, a row whose
+/// severity, date, or signal type is not accepted, or a database error rolls back the whole file,
+/// batch included.
+/// Transient failures are not retried, since the transaction does not survive them.
+pub async fn import_csv_atomic(
+    pool: &PgPool,
+    org_id: Uuid,
+    csv_path: &Path,
+    limits: ImportLimits,
+    mapping: &SourceMapping,
+    extra_tags: &[String],
+) -> anyhow::Result<ImportSummary> {
+    let (severity, on_conflict) = (limits.severity, limits.on_conflict);
+    let mut reader = ChunkedReader::open(csv_path, limits)?
+        .with_mapping(mapping.clone())
+        .with_signal_types(db::signal_types(pool, org_id).await?);
+    let aliases = db::cohort_aliases(pool, org_id).await?;
+    let mut tx = pool.begin().await?;
+    let batch_id =
+        db::create_import_batch(&mut *tx, org_id, &csv_path.display().to_string()).await?;
+    let mut summary = ImportSummary {
+        batch_id,
+        severity,
+        dates: reader.dates(),
+        ..ImportSummary::default()
+    };
+    let today = chrono::Utc::now().date_naive();
+    let mut scholars = db::ScholarCache::default();
+
+    let mut times = StageTimes::default();
+    loop {
+        let started = Instant::now();
+        let mut chunk = reader.next_chunk()?;
+        times.parse += started.elapsed();
+        let registered = reader.take_registered();
+        if !registered.is_empty() {
+            db::register_signal_types(&mut *tx, org_id, &registered).await?;
+            summary.registered_types.extend(registered);
+        }
+        if chunk.is_empty() {
+            break;
+        }
+        aliases.apply(&mut chunk);
+        add_tags(&mut chunk, extra_tags);
+        let started = Instant::now();
+        let outcomes = db::insert_signal_chunk_in(
+            &mut tx,
+            org_id,
+            &chunk,
+            batch_id,
+            &mut scholars,
+            on_conflict,
+        )
+        .await?;
+        times.chunk_written(batch_id, chunk.len(), started);
+        for (row, outcome) in chunk.iter().zip(outcomes) {
+            summary.record(row, outcome, today);
+        }
+    }
+    if let Some(first) = reader.out_of_range().first() {
+        bail!(
+            "{} rows have a severity outside {severity}, the first on line {} ({}); nothing was \
+             imported",
+            reader.out_of_range().len(),
+            first.line,
+            first.severity
+        );
+    }
+    if let (Some(first), Some(dates)) = (reader.out_of_window().first(), reader.dates()) {
+        bail!(
+            "{} rows are dated outside {dates}, the first on line {} ({}); nothing was imported",
+            reader.out_of_window().len(),
+            first.line,
+            first.occurred_at
+        );
+    }
+    if let Some(first) = reader.unregistered().first() {
+        bail!(
+            "{} rows have a signal type that is not registered or is inactive, the first on line {} \
+             ({}); nothing was imported",
+            reader.unregistered().len(),
+            first.line,
+            first.signal_type
+        );
+    }
+    let started = Instant::now();
+    tx.commit().await?;
+    times.write += started.elapsed();
+
+    summary.rows_read = reader.rows_read();
+    summary.notes_shortened = reader.notes_shortened();
+    summary.coercions = reader.coercions();
+    summary.unregistered = reader.unregistered().to_vec();
+    summary.collect_warnings();
+    times.finished(csv_path, &summary);
+    Ok(summary)
+}
+
+/// Writes the rows spooled to `path` by [`import_csv`] into their original batch, in chunks of
+/// `chunk_size`, and removes the file. If the database drops away again the rows still unwritten
+/// are spooled back to `path` and reported in `spooled`; any other failure leaves them there too
+/// before returning the error.
+#[cfg(feature = "postgres")]
+pub async fn resume_spool(
+    pool: &PgPool,
+    org_id: Uuid,
+    path: &Path,
+    chunk_size: usize,
+    retry: &db::RetryPolicy,
+) -> anyhow::Result<ImportSummary> {
+    let (header, rows) = read_spool(path, notes::key())?;
+    if header.org_id != org_id {
+        bail!(
+            "{} was spooled for organization {}, not {org_id}",
+            path.display(),
+            header.org_id
+        );
+    }
+
+    let mut summary = ImportSummary {
+        batch_id: header.batch_id,
+        rows_read: rows.len(),
+        ..ImportSummary::default()
+    };
+    let today = chrono::Utc::now().date_naive();
+    let mut scholars = db::ScholarCache::default();
+    let chunk_size = chunk_size.max(1);
+    // Types are registered before any row is written. Registering twice is harmless, so a file
+    // spooled back keeps them in its header.
+    let mut failed = None;
+    if !header.register_types.is_empty() {
+        match db::register_signal_types(pool, org_id, &header.register_types).await {
+            Ok(()) => summary.registered_types = header.register_types.clone(),
+            Err(err) => failed = Some((0, err)),
+        }
+    }
+    if failed.is_none() {
+        for (index, chunk) in rows.chunks(chunk_size).enumerate() {
+            let written = write_chunk(
+                pool,
+                org_id,
+                chunk,
+                header.batch_id,
+                &mut scholars,
+                header.on_conflict,
+                retry,
+            )
+            .await;
+            match written {
+                Ok(outcomes) => {
+                    for (row, outcome) in chunk.iter().zip(outcomes) {
+                        summary.record(row, outcome, today);
+                    }
+                }
+                Err(err) => {
+                    failed = Some((index * chunk_size, err));
+                    break;
+                }
+            }
+        }
+    }
+    if let Some((unwritten, err)) = failed {
+        let mut spool = SpoolWriter::create(path.to_path_buf(), notes::key())?;
+        spool.write(&rows[unwritten..])?;
+        let header = SpoolHeader {
+            sealed_notes: spool.seals_notes(),
+            ..header
+        };
+        let (path, rows) = spool.finish(&header)?;
+        if !db::is_transient(&err) {
+            return Err(err);
+        }
+        summary.spooled = rows;
+        summary.spool = Some(path);
+        summary.collect_warnings();
+        return Ok(summary);
+    }
+    std::fs::remove_file(path).with_context(|| format!("failed to remove {}", path.display()))?;
+    summary.collect_warnings();
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HEADER: &str =
+        "full_name,email,cohort,signal_type,severity,note,occurred_at,source_key\n";
+
+    fn csv_with_rows(count: usize, note: &str) -> String {
+        let mut data = String::from(HEADER);
+        for i in 0..count {
+            data.push_str(&format!(
+                "Avery Lee,avery@example.com,2026,attendance,3,{note},2026-02-02,key-{i}\n"
+            ));
+        }
+        data
+    }
+
+    fn limits(max_rows: usize, chunk_size: usize) -> ImportLimits {
+        ImportLimits {
+            max_rows,
+            chunk_size,
+            ..ImportLimits::default()
+        }
+    }
+
+    #[test]
+    fn lists_csv_files_in_a_directory() {
+        let dir = std::env::temp_dir().join(format!("cew-import-{}", Uuid::new_v4()));
+        std::fs::create_dir(&dir).unwrap();
+        for name in ["b.csv", "a.CSV", "notes.txt"] {
+            std::fs::write(dir.join(name), HEADER).unwrap();
+        }
+        std::fs::create_dir(dir.join("nested.csv")).unwrap();
+
+        let files = csv_files(&dir).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(files, [dir.join("a.CSV"), dir.join("b.csv")]);
+        assert_eq!(
+            csv_files(Path::new("one.csv")).unwrap(),
+            [PathBuf::from("one.csv")]
+        );
+    }
+
+    #[test]
+    fn reads_rows_in_chunks() {
+        let data = csv_with_rows(5, "missed session");
+        let mut reader = ChunkedReader::new(data.as_bytes(), limits(100, 2));
+
+        let sizes: Vec<usize> = std::iter::from_fn(|| {
+            let chunk = reader.next_chunk().unwrap();
+            (!chunk.is_empty()).then_some(chunk.len())
+        })
+        .collect();
+
+        assert_eq!(sizes, vec![2, 2, 1]);
+        assert_eq!(reader.rows_read(), 5);
+    }
+
+    #[test]
+    fn rejects_files_over_row_limit() {
+        let data = csv_with_rows(4, "missed session");
+        let mut reader = ChunkedReader::new(data.as_bytes(), limits(3, 10));
+        let err = reader.next_chunk().unwrap_err();
+        assert!(err.to_string().contains("3 row limit"));
+    }
+
+    #[test]
+    fn truncates_or_summarizes_long_notes() {
+        let note = "Called family. Mentioned a job change. Will follow up next week.";
+        let mut row = CsvRow {
+            full_name: "Avery Lee".to_string(),
+            email: "avery@example.com".to_string(),
+            cohort: "2026".to_string(),
+            signal_type: "engagement".to_string(),
+            severity: 2,
+            note: note.to_string(),
+            occurred_at: NaiveDate::from_ymd_opt(2026, 2, 2).unwrap(),
+            source_key: None,
+            tags: Vec::new(),
+            submitted_by: None,
+            attachments: Vec::new(),
+            original_note: None,
+        };
+        let mut limits = ImportLimits {
+            max_note_chars: 40,
+            note_policy: NotePolicy::Summarize,
+            summary_sentences: 2,
+            keep_note_overflow: true,
+            ..ImportLimits::default()
+        };
+
+        let mut summarized = row.clone();
+        apply_note_policy(&mut summarized, &limits).unwrap();
+        assert_eq!(summarized.note, "Called family. Mentioned a job change.");
+        assert_eq!(summarized.original_note.as_deref(), Some(note));
+
+        limits.note_policy = NotePolicy::Truncate;
+        limits.keep_note_overflow = false;
+        apply_note_policy(&mut row, &limits).unwrap();
+        assert!(row.note.chars().count() <= 40);
+        assert!(row.note.ends_with('…'));
+        assert!(row.original_note.is_none());
+    }
+
+    #[test]
+    fn reads_partner_layout_through_mapping() {
+        let data = "Student,Mail,Kind,Level,Comment,Date\n\
+                    Avery Lee,avery@example.com,attendance,3,missed,2026-02-02\n";
+        let mapping = SourceMapping {
+            columns: BTreeMap::from([
+                ("full_name".to_string(), "Student".to_string()),
+                ("email".to_string(), "Mail".to_string()),
+                ("signal_type".to_string(), "Kind".to_string()),
+                ("severity".to_string(), "Level".to_string()),
+                ("note".to_string(), "Comment".to_string()),
+                ("occurred_at".to_string(), "Date".to_string()),
+            ]),
+            cohort: Some("2027".to_string()),
+        };
+        let mut reader =
+            ChunkedReader::new(data.as_bytes(), ImportLimits::default()).with_mapping(mapping);
+        let rows = reader.next_chunk().unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].email, "avery@example.com");
+        assert_eq!(rows[0].cohort, "2027");
+        assert_eq!(rows[0].severity, 3);
+    }
+
+    #[test]
+    fn parses_optional_tags_column() {
+        let data = "full_name,email,cohort,signal_type,severity,note,occurred_at,source_key,tags\n\
+                    Avery Lee,avery@example.com,2026,attendance,3,missed,2026-02-02,k1, Summer-Bridge ;stem;\n\
+                    Avery Lee,avery@example.com,2026,attendance,3,missed,2026-02-03,k2,\n";
+        let mut reader = ChunkedReader::new(data.as_bytes(), ImportLimits::default());
+        let rows = reader.next_chunk().unwrap();
+        assert_eq!(rows[0].tags, vec!["stem", "summer-bridge"]);
+        assert!(rows[1].tags.is_empty());
+
+        let untagged = csv_with_rows(1, "missed session");
+        let mut reader = ChunkedReader::new(untagged.as_bytes(), ImportLimits::default());
+        assert!(reader.next_chunk().unwrap()[0].tags.is_empty());
+    }
+
+    #[test]
+    fn rejects_notes_over_length_limit() {
+        let data = csv_with_rows(1, &"x".repeat(50));
+        let mut reader = ChunkedReader::new(
+            data.as_bytes(),
+            ImportLimits {
+                max_note_chars: 10,
+                ..ImportLimits::default()
+            },
+        );
+        let err = reader.next_chunk().unwrap_err();
+        assert!(format!("{err:#}").contains("10 character limit"));
+    }
+
+    #[test]
+    fn parses_attachment_entries() {
+        let attachments = parse_attachments(
+            "https://lms.example.org/a/1; Gradebook|https://lms.example.org/g ;lms|Quiz 3|https://x.org/q?a=1|b",
+        )
+        .unwrap();
+        assert_eq!(attachments.len(), 3);
+        assert_eq!(attachments[0].label, "https://lms.example.org/a/1");
+        assert_eq!(attachments[0].kind, "link");
+        assert_eq!(attachments[1].label, "Gradebook");
+        assert_eq!(
+            (attachments[2].kind.as_str(), attachments[2].url.as_str()),
+            ("lms", "https://x.org/q?a=1|b")
+        );
+
+        assert!(parse_attachments("notes|javascript:alert(1)").is_err());
+        assert!(parse_attachments("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn summary_counts_outcomes_types_and_dates() {
+        let data = csv_with_rows(3, &"x".repeat(50));
+        let mut reader = ChunkedReader::new(
+            data.as_bytes(),
+            ImportLimits {
+                max_note_chars: 10,
+                note_policy: NotePolicy::Truncate,
+                ..ImportLimits::default()
+            },
+        );
+        let rows = reader.next_chunk().unwrap();
+        let today = NaiveDate::from_ymd_opt(2026, 2, 1).unwrap();
+
+        let mut summary = ImportSummary::default();
+        for (index, row) in rows.iter().enumerate() {
+            let outcome = match index {
+                0 => RowOutcome::Skipped,
+                1 => RowOutcome::Updated,
+                _ => RowOutcome::Inserted,
+            };
+            summary.record(row, outcome, today);
+        }
+        summary.rows_read = reader.rows_read();
+        summary.notes_shortened = reader.notes_shortened();
+        summary.collect_warnings();
+
+        assert_eq!(
+            (summary.inserted, summary.updated, summary.skipped),
+            (1, 1, 1)
+        );
+        assert_eq!(summary.signal_types.get("attendance"), Some(&3));
+        assert_eq!(summary.min_occurred_at, NaiveDate::from_ymd_opt(2026, 2, 2));
+        assert_eq!(summary.future_dated, 3);
+        assert_eq!(summary.warnings.len(), 3);
+        assert!(summary.warnings[1].starts_with("3 notes shortened"));
+    }
+
+    #[test]
+    fn maps_cohort_aliases_to_the_canonical_name() {
+        let aliases: CohortAliases = [
+            ("Class of 2026".to_string(), "2026".to_string()),
+            ("c2026".to_string(), "2026".to_string()),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(aliases.canonical(" CLASS OF 2026"), "2026");
+        assert_eq!(aliases.canonical("2025"), "2025");
+
+        let data = csv_with_rows(2, "ok").replacen(",2026,", ",C2026,", 1);
+        let mut rows = ChunkedReader::new(data.as_bytes(), ImportLimits::default())
+            .next_chunk()
+            .unwrap();
+        aliases.apply(&mut rows);
+        assert!(rows.iter().all(|row| row.cohort == "2026"));
+    }
+
+    #[test]
+    fn normalizes_and_validates_emails() {
+        // "é" precomposed, and as "e" plus a combining acute accent.
+        assert_eq!(
+            parse_email("  Ren\u{e9}e.Diaz@Example.ORG ").unwrap(),
+            parse_email("RENE\u{301}E.diaz@example.org").unwrap()
+        );
+        assert_eq!(
+            parse_email(" Avery@Example.com").unwrap(),
+            "avery@example.com"
+        );
+        for invalid in [
+            "avery",
+            "@example.com",
+            "avery@example",
+            "a@b@example.com",
+            "a b@x.org",
+        ] {
+            assert!(parse_email(invalid).is_err(), "{invalid}");
+        }
+
+        let data = csv_with_rows(2, "ok").replacen("avery@example.com", "AVERY@Example.com ", 1);
+        let rows = ChunkedReader::new(data.as_bytes(), ImportLimits::default())
+            .next_chunk()
+            .unwrap();
+        assert!(rows.iter().all(|row| row.email == "avery@example.com"));
+
+        let data = csv_with_rows(2, "ok").replacen("avery@example.com", "avery.example.com", 1);
+        let err = ChunkedReader::new(data.as_bytes(), ImportLimits::default())
+            .next_chunk()
+            .unwrap_err();
+        assert!(
+            format!("{err:#}").contains("invalid row on line 2"),
+            "{err:#}"
+        );
+    }
+
+    #[test]
+    fn leaves_out_and_reports_rows_outside_the_severity_bounds() {
+        let data = csv_with_rows(4, "ok")
+            .replacen(",attendance,3,", ",attendance,0,", 1)
+            .replacen(",attendance,3,", ",attendance,9,", 1);
+        let mut reader = ChunkedReader::new(data.as_bytes(), ImportLimits::default());
+        let rows = reader.next_chunk().unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(reader.rows_read(), 4);
+        assert_eq!(
+            reader.out_of_range(),
+            [
+                OutOfRangeRow {
+                    line: 2,
+                    severity: 0
+                },
+                OutOfRangeRow {
+                    line: 3,
+                    severity: 9
+                }
+            ]
+        );
+
+        let mut summary = ImportSummary {
+            rows_read: 4,
+            out_of_range: reader.out_of_range().to_vec(),
+            ..ImportSummary::default()
+        };
+        summary.collect_warnings();
+        assert_eq!(
+            summary.warnings,
+            ["2 rows left out because their severity is outside 1-5: line 2 (0), line 3 (9)"]
+        );
+
+        let wider = ImportLimits {
+            severity: SeverityBounds { min: 0, max: 10 },
+            ..ImportLimits::default()
+        };
+        let mut reader = ChunkedReader::new(data.as_bytes(), wider);
+        assert_eq!(reader.next_chunk().unwrap().len(), 4);
+        assert!(SeverityBounds { min: 5, max: 1 }.validate().is_err());
+    }
+
+    #[test]
+    fn leaves_out_rows_dated_outside_the_date_window() {
+        let today = chrono::Utc::now().date_naive();
+        let future = today + chrono::Duration::days(1);
+        let stale = today - chrono::Duration::days(731);
+        let data = csv_with_rows(3, "ok")
+            .replacen("2026-02-02", &future.to_string(), 1)
+            .replacen("2026-02-02", &stale.to_string(), 1)
+            .replacen("2026-02-02", &today.to_string(), 1);
+        let limits = ImportLimits {
+            dates: Some(DateWindow::default()),
+            ..ImportLimits::default()
+        };
+        let mut reader = ChunkedReader::new(data.as_bytes(), limits);
+        assert_eq!(reader.next_chunk().unwrap().len(), 1);
+        assert_eq!(
+            reader.out_of_window(),
+            [
+                OutOfWindowRow {
+                    line: 2,
+                    occurred_at: future
+                },
+                OutOfWindowRow {
+                    line: 3,
+                    occurred_at: stale
+                }
+            ]
+        );
+
+        let dates = reader.dates().unwrap();
+        assert_eq!(
+            (dates.earliest, dates.latest),
+            (stale + chrono::Duration::days(1), today)
+        );
+        let mut summary = ImportSummary {
+            rows_read: 3,
+            out_of_window: reader.out_of_window().to_vec(),
+            dates: Some(dates),
+            ..ImportSummary::default()
+        };
+        summary.collect_warnings();
+        assert_eq!(
+            summary.warnings,
+            [format!(
+                "2 rows left out because they are dated outside {} to {today}: line 2 ({future}), \
+                 line 3 ({stale}); import them with --allow-any-date if the dates are right",
+                dates.earliest
+            )]
+        );
+
+        // Without a window every date is read, as with --allow-any-date.
+        let mut reader = ChunkedReader::new(data.as_bytes(), ImportLimits::default());
+        assert_eq!(reader.next_chunk().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn checks_signal_types_against_the_registered_taxonomy() {
+        let data = csv_with_rows(4, "ok")
+            .replacen(",attendance,", ",Attendance ,", 1)
+            .replacen(",attendance,", ",attendence,", 1)
+            .replacen(",attendance,", ",tardy,", 1);
+        let types: SignalTypes = [
+            ("attendance".to_string(), true),
+            ("Tardy".to_string(), false),
+        ]
+        .into_iter()
+        .collect();
+
+        let mut reader = ChunkedReader::new(data.as_bytes(), ImportLimits::default())
+            .with_signal_types(types.clone());
+        let rows = reader.next_chunk().unwrap();
+        assert_eq!(
+            rows.iter()
+                .map(|row| row.signal_type.as_str())
+                .collect::<Vec<_>>(),
+            ["attendance", "attendance"]
+        );
+        let unregistered: Vec<(u64, &str)> = reader
+            .unregistered()
+            .iter()
+            .map(|row| (row.line, row.signal_type.as_str()))
+            .collect();
+        assert_eq!(unregistered, [(3, "attendence"), (4, "tardy")]);
+
+        // Registering adds unknown types but still refuses inactive ones.
+        let limits = ImportLimits {
+            register_types: true,
+            ..ImportLimits::default()
+        };
+        let mut reader = ChunkedReader::new(data.as_bytes(), limits).with_signal_types(types);
+        assert_eq!(reader.next_chunk().unwrap().len(), 3);
+        assert_eq!(reader.take_registered(), ["attendence"]);
+        assert!(reader.take_registered().is_empty());
+        assert_eq!(reader.unregistered().len(), 1);
+
+        // With nothing registered every type is accepted as written.
+        let mut reader = ChunkedReader::new(data.as_bytes(), ImportLimits::default());
+        assert_eq!(reader.next_chunk().unwrap()[0].signal_type, "Attendance ");
+    }
+
+    #[cfg(feature = "postgres")]
+    #[test]
+    fn spools_keep_types_registered_while_spooling() {
+        let data = csv_with_rows(4, "ok").replacen(",attendance,", ",tutoring,", 3);
+        let types: SignalTypes = [("attendance".to_string(), true)].into_iter().collect();
+        let limits = ImportLimits {
+            chunk_size: 2,
+            register_types: true,
+            ..ImportLimits::default()
+        };
+        let mut reader = ChunkedReader::new(data.as_bytes(), limits).with_signal_types(types);
+        let path = std::env::temp_dir().join(format!("cew-spool-{}.jsonl", Uuid::new_v4()));
+        let mut spool = SpoolWriter::create(path.clone(), None).unwrap();
+        let mut register_types = Vec::new();
+        loop {
+            let chunk = reader.next_chunk().unwrap();
+            register_types.extend(reader.take_registered());
+            if chunk.is_empty() {
+                break;
+            }
+            spool.write(&chunk).unwrap();
+        }
+        let header = SpoolHeader {
+            org_id: Uuid::nil(),
+            batch_id: Uuid::nil(),
+            source: "signals.csv".to_string(),
+            on_conflict: OnConflict::Skip,
+            register_types,
+            sealed_notes: false,
+        };
+        assert_eq!(spool.finish(&header).unwrap(), (path.clone(), 4));
+
+        let (header, rows) = read_spool(&path, None).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(header.register_types, ["tutoring"]);
+        assert_eq!(
+            rows.iter()
+                .map(|row| row.signal_type.as_str())
+                .collect::<Vec<_>>(),
+            ["tutoring", "tutoring", "tutoring", "attendance"]
+        );
+    }
+
+    #[cfg(feature = "postgres")]
+    #[test]
+    fn spools_seal_notes_under_the_note_key() {
+        let key = NoteKey::parse(&NoteKey::generate()).unwrap();
+        let data = csv_with_rows(2, "Family moved");
+        let mut reader = ChunkedReader::new(data.as_bytes(), ImportLimits::default());
+        let path = std::env::temp_dir().join(format!("cew-spool-{}.jsonl", Uuid::new_v4()));
+        let mut spool = SpoolWriter::create(path.clone(), Some(&key)).unwrap();
+        spool.write(&reader.next_chunk().unwrap()).unwrap();
+        let header = SpoolHeader {
+            org_id: Uuid::nil(),
+            batch_id: Uuid::nil(),
+            source: "signals.csv".to_string(),
+            on_conflict: OnConflict::Skip,
+            register_types: Vec::new(),
+            sealed_notes: spool.seals_notes(),
+        };
+        spool.finish(&header).unwrap();
+
+        let written = std::fs::read_to_string(&path).unwrap();
+        let keyless = read_spool(&path, None).map(|_| ());
+        let (header, rows) = read_spool(&path, Some(&key)).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert!(!written.contains("Family"));
+        assert!(keyless.is_err());
+        assert!(header.sealed_notes);
+        assert!(rows.iter().all(|row| row.note == "Family moved"));
+    }
+
+    #[test]
+    fn strict_fails_and_lenient_coerces_what_standard_leaves_out() {
+        let data = csv_with_rows(3, "ok")
+            .replacen(",attendance,3,", ",attendance,9,", 1)
+            .replacen("2026-02-02,key-1", "02/03/2026,key-1", 1)
+            .replacen(
+                "avery@example.com,2026,attendance,3",
+                " avery@example.com ,2026,attendance,3",
+                1,
+            );
+        let mode = |validation| ImportLimits {
+            validation,
+            ..ImportLimits::default()
+        };
+
+        let err = ChunkedReader::new(data.as_bytes(), mode(Validation::Standard))
+            .next_chunk()
+            .unwrap_err();
+        assert_eq!(err.to_string(), "invalid row on line 3");
+        let err = ChunkedReader::new(data.as_bytes(), mode(Validation::Strict))
+            .next_chunk()
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid row on line 2: severity 9 is outside 1-5"
+        );
+        let header = data.lines().next().unwrap();
+        let err = ChunkedReader::new(header.as_bytes(), mode(Validation::Strict))
+            .next_chunk()
+            .unwrap_err();
+        assert_eq!(err.to_string(), "file contained no rows");
+
+        let mut reader = ChunkedReader::new(data.as_bytes(), mode(Validation::Lenient));
+        let rows = reader.next_chunk().unwrap();
+        assert_eq!(
+            rows.iter()
+                .map(|row| (
+                    row.email.as_str(),
+                    row.severity,
+                    row.occurred_at.to_string()
+                ))
+                .collect::<Vec<_>>(),
+            [
+                ("avery@example.com", 5, "2026-02-02".to_string()),
+                ("avery@example.com", 3, "2026-02-03".to_string()),
+                ("avery@example.com", 3, "2026-02-02".to_string()),
+            ]
+        );
+        let coercions = reader.coercions();
+        assert_eq!(
+            coercions,
+            Coercions {
+                trimmed: 1,
+                severities_clamped: 1,
+                dates_reparsed: 1
+            }
+        );
+        let mut summary = ImportSummary {
+            rows_read: 3,
+            coercions,
+            ..ImportSummary::default()
+        };
+        summary.collect_warnings();
+        assert_eq!(
+            summary.warnings,
+            ["--lenient trimmed whitespace in 1 rows, clamped 1 severities into 1-5, and reparsed 1 \
+              dates as YYYY-MM-DD"]
+        );
+    }
+
+    #[cfg(feature = "postgres")]
+    #[test]
+    fn hand_entered_signals_follow_the_note_policy() {
+        let today = NaiveDate::from_ymd_opt(2026, 3, 10).unwrap();
+        let signal = || NewSignal {
+            email: "avery@example.com".to_string(),
+            signal_type: "attendance".to_string(),
+            severity: 3,
+            note: "Missed class. Then missed the makeup too. Parent called.".to_string(),
+            occurred_at: None,
+            full_name: None,
+            cohort: None,
+            tags: Vec::new(),
+            submitted_by: None,
+            source_key: None,
+        };
+        let policy = |note_policy| ImportLimits {
+            max_note_chars: 30,
+            note_policy,
+            ..ImportLimits::default()
+        };
+
+        let err = check_new_signal(&mut signal(), &policy(NotePolicy::Reject), today).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "note is 56 characters, above the 30 character limit"
+        );
+
+        let mut truncated = signal();
+        let (occurred_at, original) =
+            check_new_signal(&mut truncated, &policy(NotePolicy::Truncate), today).unwrap();
+        assert_eq!(occurred_at, today);
+        assert_eq!(truncated.note, "Missed class. Then missed the…");
+        assert_eq!(original, None);
+
+        let mut summarized = signal();
+        let limits = ImportLimits {
+            summary_sentences: 1,
+            keep_note_overflow: true,
+            ..policy(NotePolicy::Summarize)
+        };
+        let (_, original) = check_new_signal(&mut summarized, &limits, today).unwrap();
+        assert_eq!(summarized.note, "Missed class.");
+        assert_eq!(original, Some(signal().note));
+    }
+
+    mod properties {
+        use super::*;
+        use proptest::prelude::*;
+
+        fn row_strategy() -> impl Strategy<Value = CsvRow> {
+            (
+                "[A-Za-z][A-Za-z ,'\"-]{0,30}",
+                "[a-z]{1,10}@[a-z]{1,10}\\.org",
+                "[0-9]{4}",
+                "[a-z_]{1,12}",
+                1..=5i32,
+                "\\PC{0,120}",
+                0..3_000i64,
+                proptest::option::of("[a-z0-9-]{1,16}"),
+            )
+                .prop_map(
+                    |(
+                        full_name,
+                        email,
+                        cohort,
+                        signal_type,
+                        severity,
+                        note,
+                        offset,
+                        source_key,
+                    )| {
+                        CsvRow {
+                            full_name,
+                            email,
+                            cohort,
+                            signal_type,
+                            severity,
+                            note,
+                            occurred_at: NaiveDate::from_ymd_opt(2020, 1, 1).unwrap()
+                                + chrono::Duration::days(offset),
+                            source_key,
+                            tags: Vec::new(),
+                            submitted_by: None,
+                            attachments: Vec::new(),
+                            original_note: None,
+                        }
+                    },
+                )
+        }
+
+        fn to_csv(rows: &[CsvRow]) -> Vec<u8> {
+            let mut writer = csv::Writer::from_writer(Vec::new());
+            writer.write_record(HEADER.trim_end().split(',')).unwrap();
+            for row in rows {
+                writer
+                    .write_record([
+                        row.full_name.clone(),
+                        row.email.clone(),
+                        row.cohort.clone(),
+                        row.signal_type.clone(),
+                        row.severity.to_string(),
+                        row.note.clone(),
+                        row.occurred_at.to_string(),
+                        row.source_key.clone().unwrap_or_default(),
+                    ])
+                    .unwrap();
+            }
+            writer.into_inner().unwrap()
+        }
+
+        proptest! {
+            #[test]
+            fn arbitrary_input_never_panics(data in proptest::collection::vec(any::<u8>(), 0..2_048)) {
+                let mut reader = ChunkedReader::new(data.as_slice(), limits(50, 7));
+                while let Ok(chunk) = reader.next_chunk() {
+                    if chunk.is_empty() {
+                        break;
+                    }
+                }
+                prop_assert!(reader.rows_read() <= 51);
+            }
+
+            #[test]
+            fn written_rows_parse_back_unchanged(rows in proptest::collection::vec(row_strategy(), 0..20)) {
+                let data = to_csv(&rows);
+                let mut reader = ChunkedReader::new(data.as_slice(), limits(100, 6));
+                let mut parsed = Vec::new();
+                loop {
+                    let chunk = reader.next_chunk().unwrap();
+                    if chunk.is_empty() {
+                        break;
+                    }
+                    parsed.extend(chunk);
+                }
+
+                prop_assert_eq!(parsed.len(), rows.len());
+                for (expected, actual) in rows.iter().zip(&parsed) {
+                    prop_assert_eq!(&expected.full_name, &actual.full_name);
+                    prop_assert_eq!(&expected.email, &actual.email);
+                    prop_assert_eq!(expected.severity, actual.severity);
+                    prop_assert_eq!(&expected.note, &actual.note);
+                    prop_assert_eq!(expected.occurred_at, actual.occurred_at);
+                    prop_assert_eq!(&expected.source_key, &actual.source_key);
+                }
+            }
+
+            #[test]
+            fn truncated_notes_respect_the_limit(note in "\\PC{0,200}", max_chars in 0..80usize) {
+                let truncated = truncate_note(&note, max_chars);
+                prop_assert!(truncated.chars().count() <= max_chars);
+                let kept = truncated.trim_end_matches('…');
+                prop_assert!(note.starts_with(kept));
+            }
+        }
+    }
+}
@@ -0,0 +1,83 @@
+//! Scoring, reporting, and storage behind the cohort early warning CLI, for services that want to
+//! score scholars or render reports in-process instead of shelling out to
+//! `groupscholar-cohort-early-warning`.
+//!
+//! - [`risk`] turns signals into weighted scores and tiers, configured by [`risk::ScoringConfig`].
+//! - [`report`] renders the markdown report printed by `report`.
+//! - [`models`] holds the records both of them read and return.
+//! - [`db`] loads and stores those records in the cohort early warning schema, and carries its
+//!   migrations. It needs the default `postgres` feature; without it the crate has no database
+//!   dependency at all.
+//! - [`notes`] encrypts signal notes before they reach the database, when given a key. It comes
+//!   with the `postgres` feature.
+//! - [`import`] and [`export`] read and write the CSV layouts the CLI accepts and emits.
+//! - [`ics`] writes follow-up reminders as calendar events.
+//! - [`recommendations`] matches scholars to the playbooks that suggest their next steps.
+//! - [`clusters`] groups scholars with similar signal profiles.
+//! - [`enrich`] tags signal notes with keyword categories and a sentiment score.
+//! - [`calendar`] describes the academic year, so seasonal swings are not mistaken for trends.
+//! - [`health`] condenses a cohort's scores into a weekly health index.
+//! - [`survival`] estimates how long scholars in each risk tier stay in the program.
+//!
+//! Scoring needs nothing but the signals:
+//!
+//! ```
+//! use chrono::NaiveDate;
+//! use groupscholar_early_warning_core::models::SignalRecord;
+//! use groupscholar_early_warning_core::report::{self, ReportOptions};
+//! use groupscholar_early_warning_core::risk::{self, ScoringConfig, Tier};
+//! use uuid::Uuid;
+//!
+//! let today = NaiveDate::from_ymd_opt(2026, 3, 31).unwrap();
+//! let signal = |signal_type: &str, severity, day| SignalRecord {
+//!     scholar_id: Uuid::nil(),
+//!     scholar_name: "Avery Lee".to_string(),
+//!     scholar_email: "avery@example.org".to_string(),
+//!     cohort: "2026".to_string(),
+//!     signal_type: signal_type.to_string(),
+//!     severity,
+//!     occurred_at: NaiveDate::from_ymd_opt(2026, 3, day).unwrap(),
+//!     note: String::new(),
+//!     advisor: None,
+//!     submitted_by: None,
+//!     attachments: Vec::new(),
+//!     note_categories: Vec::new(),
+//!     note_sentiment: None,
+//! };
+//! let signals = [signal("attendance", 4, 28), signal("missed_checkin", 5, 30)];
+//!
+//! let scores = risk::score_signals_with(&signals, 30, today, &ScoringConfig::default());
+//! assert_eq!(scores[0].scholar_email, "avery@example.org");
+//! assert_ne!(scores[0].tier, Tier::Low);
+//!
+//! let cutoff = today - chrono::Duration::days(30);
+//! let markdown = report::build_report(
+//!     Some("2026"),
+//!     30,
+//!     cutoff,
+//!     &signals,
+//!     &[],
+//!     &ReportOptions::default(),
+//! );
+//! assert!(markdown.starts_with("# Cohort Early Warning Report"));
+//! ```
+
+pub mod calendar;
+pub mod clusters;
+#[cfg(feature = "postgres")]
+pub mod db;
+pub mod enrich;
+pub mod export;
+pub mod health;
+pub mod ics;
+pub mod import;
+pub mod models;
+#[cfg(feature = "postgres")]
+pub mod notes;
+pub mod raters;
+pub mod recommendations;
+pub mod report;
+pub mod risk;
+pub mod survival;
+pub mod synthetic;
+pub mod trends;
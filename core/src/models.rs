@@ -0,0 +1,452 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum, serde::Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "kebab-case")]
+pub enum ScholarStatus {
+    Active,
+    Withdrawn,
+    Graduated,
+    OnLeave,
+}
+
+impl ScholarStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ScholarStatus::Active => "active",
+            ScholarStatus::Withdrawn => "withdrawn",
+            ScholarStatus::Graduated => "graduated",
+            ScholarStatus::OnLeave => "on-leave",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SignalRecord {
+    pub scholar_id: Uuid,
+    pub scholar_name: String,
+    pub scholar_email: String,
+    pub cohort: String,
+    pub signal_type: String,
+    pub severity: i32,
+    pub occurred_at: NaiveDate,
+    pub note: String,
+    /// Name of the scholar's assigned advisor, if any.
+    pub advisor: Option<String>,
+    /// Staff member who entered the signal by hand; `None` for system-generated signals.
+    pub submitted_by: Option<String>,
+    pub attachments: Vec<Attachment>,
+    /// Keyword categories `enrich` found in the note; empty until it has run.
+    pub note_categories: Vec<String>,
+    /// Note sentiment from `enrich`, -1 to 1; `None` until it has run or for a blank note.
+    pub note_sentiment: Option<f64>,
+}
+
+/// One signal entered by hand, through `signal add` or `POST /signals`.
+#[derive(Debug, Clone, serde::Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(deny_unknown_fields)]
+pub struct NewSignal {
+    /// Unknown scholars also need `full_name` and `cohort`.
+    pub email: String,
+    pub signal_type: String,
+    /// Within the server's `[severity]` bounds, 1-5 unless configured otherwise.
+    pub severity: i32,
+    pub note: String,
+    /// Defaults to today.
+    #[serde(default)]
+    pub occurred_at: Option<NaiveDate>,
+    /// Name for a scholar not on the roster yet.
+    #[serde(default)]
+    pub full_name: Option<String>,
+    /// Cohort for a scholar not on the roster yet, or to move an existing one.
+    #[serde(default)]
+    pub cohort: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub submitted_by: Option<String>,
+    /// Stable key; recording the same key twice is a no-op. Generated when not given.
+    #[serde(default)]
+    pub source_key: Option<String>,
+}
+
+/// Link to the source evidence behind a signal.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct Attachment {
+    pub url: String,
+    pub label: String,
+    /// Free-form category such as `lms` or `case-note`; `link` when not given.
+    pub kind: String,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ScholarScore {
+    pub scholar_name: String,
+    pub scholar_email: String,
+    pub cohort: String,
+    pub score: f64,
+    pub signal_count: usize,
+    pub advisor: Option<String>,
+    pub tier: crate::risk::Tier,
+    /// Signal that lifted the tier above what the score alone implies, e.g. "wellbeing severity 4".
+    pub escalated_by: Option<String>,
+    /// Staff-entered signals folded into another of the same type by the dedup window.
+    pub collapsed_signals: usize,
+    /// Share of all scored scholars, 0-100, whose score is at or below this one.
+    pub percentile: f64,
+    /// Share of scored scholars in the same cohort, 0-100, whose score is at or below this one.
+    pub cohort_percentile: f64,
+    /// Latest intervention or self-report check-in. Scoring alone leaves it empty;
+    /// `db::score_scope` fills it in.
+    pub last_contact: Option<NaiveDate>,
+    /// Day of the latest scored signal.
+    #[serde(default)]
+    pub last_signal: Option<NaiveDate>,
+}
+
+/// Values of a [`ScholarScore`] that `[salesforce.fields]` and `[airtable.fields]` entries can
+/// name.
+pub const SCORE_FIELDS: [&str; 10] = [
+    "full_name",
+    "email",
+    "cohort",
+    "score",
+    "tier",
+    "signal_count",
+    "advisor",
+    "escalated_by",
+    "last_signal",
+    "summary",
+];
+
+impl ScholarScore {
+    /// One line naming the scholar, their score, and what put them in their tier.
+    pub fn summary(&self) -> String {
+        let mut line = format!(
+            "{} ({}) entered the {} tier with a score of {:.1}",
+            self.scholar_name,
+            self.cohort,
+            self.tier.as_str(),
+            self.score
+        );
+        if let Some(signal) = &self.escalated_by {
+            line.push_str(&format!(", escalated by {signal}"));
+        }
+        line
+    }
+
+    /// The value of one of [`SCORE_FIELDS`], as JSON for an external system; null for any other
+    /// name. Scores are rounded to two decimals.
+    pub fn field(&self, name: &str) -> serde_json::Value {
+        match name {
+            "full_name" => self.scholar_name.clone().into(),
+            "email" => self.scholar_email.clone().into(),
+            "cohort" => self.cohort.clone().into(),
+            "score" => ((self.score * 100.0).round() / 100.0).into(),
+            "tier" => self.tier.as_str().into(),
+            "signal_count" => self.signal_count.into(),
+            "advisor" => self.advisor.clone().into(),
+            "escalated_by" => self.escalated_by.clone().into(),
+            "last_signal" => self.last_signal.map(|day| day.to_string()).into(),
+            "summary" => self.summary().into(),
+            _ => serde_json::Value::Null,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SignalTypeSummary {
+    pub signal_type: String,
+    pub count: usize,
+    pub scholar_count: usize,
+    pub avg_severity: f64,
+}
+
+/// Two signal types logged for the same scholars, from `report::co_occurrences`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignalPair {
+    /// The alphabetically earlier type.
+    pub first: String,
+    pub second: String,
+    pub scholar_count: usize,
+    /// Mean risk score of those scholars.
+    pub avg_score: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct SignalTrend {
+    pub week_start: NaiveDate,
+    pub signal_count: i64,
+    pub avg_severity: f64,
+    pub scholar_count: i64,
+}
+
+#[derive(Debug, Clone)]
+pub struct ImportBatch {
+    pub id: Uuid,
+    pub source: String,
+    pub started_at: DateTime<Utc>,
+    pub undone_at: Option<DateTime<Utc>>,
+    pub signal_count: i64,
+}
+
+#[derive(Debug, Clone)]
+pub struct CohortAggregate {
+    pub cohort: String,
+    pub signal_type: String,
+    pub scholar_count: i64,
+    pub signal_count: i64,
+    pub avg_severity: f64,
+}
+
+/// Roster and signal totals printed by `stats`; soft-deleted rows are left out.
+#[derive(Debug, Clone, Default)]
+pub struct DatabaseStats {
+    /// Cohort, scholars on the roster, and how many of them are active.
+    pub cohorts: Vec<(String, i64, i64)>,
+    pub signal_types: Vec<(String, i64)>,
+    pub signal_count: i64,
+    pub first_occurred_at: Option<NaiveDate>,
+    pub last_occurred_at: Option<NaiveDate>,
+    /// Scholars and signals created in the last 7 and 30 days.
+    pub scholars_added: [i64; 2],
+    pub signals_added: [i64; 2],
+}
+
+/// A persisted scoring run from `score_runs`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScoreRun {
+    pub id: Uuid,
+    pub ran_at: DateTime<Utc>,
+    pub trigger: String,
+}
+
+/// One scholar's saved result within a scoring run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SnapshotScore {
+    pub scholar_name: String,
+    pub scholar_email: String,
+    pub cohort: String,
+    pub score: f64,
+    pub tier: crate::risk::Tier,
+}
+
+/// A scholar's result in one persisted scoring run; runs that left them unscored read as zero.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct ScoreHistoryPoint {
+    pub ran_at: DateTime<Utc>,
+    /// What started the run, e.g. `watch`.
+    pub trigger: String,
+    pub score: f64,
+    pub tier: crate::risk::Tier,
+    pub signal_count: i64,
+}
+
+/// Rows `purge` deleted or anonymized, or would have on a dry run.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PurgeSummary {
+    pub signals: u64,
+    pub archived_signals: u64,
+    pub scholars: u64,
+    /// Live and archived signals whose notes were blanked under the notes cutoff.
+    pub notes: u64,
+    pub interventions: u64,
+}
+
+/// Which data `purge` acts on: for each data class with a retention age, the date before which
+/// it expired, and what happens to it then.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RetentionPlan {
+    /// Signals, live and archived, and the scholars with nothing recorded since.
+    pub signals: Option<(NaiveDate, RetentionMode)>,
+    /// Signal notes, which are blanked along with their attachments; the signals stay.
+    pub notes: Option<NaiveDate>,
+    /// Interventions, whose notes are blanked when anonymizing.
+    pub interventions: Option<(NaiveDate, RetentionMode)>,
+}
+
+impl RetentionPlan {
+    /// Whether no data class has a retention age.
+    pub fn is_empty(&self) -> bool {
+        self.signals.is_none() && self.notes.is_none() && self.interventions.is_none()
+    }
+}
+
+/// Rows `scholar merge` moved from the absorbed record to the kept one.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MergeSummary {
+    /// Live and archived signals.
+    pub signals: u64,
+    pub interventions: u64,
+    pub snapshots: u64,
+    /// Snapshots dropped because the kept record was scored in the same run.
+    pub overlapping_snapshots: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct Organization {
+    pub id: Uuid,
+    pub slug: String,
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CohortTrend {
+    pub cohort: String,
+    pub week_start: NaiveDate,
+    pub signal_count: i64,
+    pub avg_severity: f64,
+    pub scholar_count: i64,
+}
+
+/// One week of a cohort's health index, from `health::weekly`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CohortHealth {
+    pub cohort: String,
+    pub week_start: NaiveDate,
+    /// 0 to 100; 100 when no active scholar is at risk.
+    pub health_index: f64,
+    /// Active scholars on the roster the index was taken over.
+    pub scholars: i64,
+    /// Scholars scored high or critical that week.
+    pub at_risk: i64,
+}
+
+/// An entry in the signal type taxonomy, as shown by `signal-types list`.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct SignalType {
+    pub name: String,
+    pub description: String,
+    /// How heavily the type is meant to count relative to others, 1.0 by default.
+    pub default_weight: f64,
+    /// Inactive types are kept for existing signals but refused for new ones.
+    pub active: bool,
+    pub created_at: DateTime<Utc>,
+    /// Live signals of this type, however their type is cased.
+    pub signals: i64,
+}
+
+/// A roster entry as shown by `scholar list`.
+#[derive(Debug, Clone, serde::Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct Scholar {
+    pub full_name: String,
+    pub email: String,
+    pub cohort: String,
+    pub status: String,
+    pub advisor: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub deleted: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct Advisor {
+    pub id: Uuid,
+    pub full_name: String,
+    pub email: String,
+    pub scholar_count: i64,
+    /// Channel from the config that receives alerts about this advisor's scholars.
+    pub channel: Option<String>,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct Intervention {
+    pub id: Uuid,
+    pub scholar_name: String,
+    pub scholar_email: String,
+    pub occurred_on: NaiveDate,
+    pub intervention_type: String,
+    pub outcome: Option<String>,
+    pub note: String,
+    /// Source keys of the signals this intervention responded to.
+    pub signal_keys: Vec<String>,
+    /// When `intervene close` recorded the final outcome; `None` while open.
+    pub closed_at: Option<DateTime<Utc>>,
+}
+
+/// A milestone recorded with `outcome record`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutcomeType {
+    Withdrawal,
+    Graduation,
+    Gpa,
+}
+
+impl OutcomeType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OutcomeType::Withdrawal => "withdrawal",
+            OutcomeType::Graduation => "graduation",
+            OutcomeType::Gpa => "gpa",
+        }
+    }
+
+    pub fn parse(value: &str) -> anyhow::Result<Self> {
+        <Self as clap::ValueEnum>::from_str(value, true)
+            .map_err(|_| anyhow::anyhow!("unknown outcome type {value}"))
+    }
+
+    /// Roster status the outcome implies, for those that end a scholar's time in the program.
+    pub fn status(&self) -> Option<ScholarStatus> {
+        match self {
+            OutcomeType::Withdrawal => Some(ScholarStatus::Withdrawn),
+            OutcomeType::Graduation => Some(ScholarStatus::Graduated),
+            OutcomeType::Gpa => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct Outcome {
+    pub id: Uuid,
+    pub scholar_name: String,
+    pub scholar_email: String,
+    pub cohort: String,
+    pub outcome_type: OutcomeType,
+    pub occurred_on: NaiveDate,
+    /// Set for GPA milestones only.
+    pub gpa: Option<f64>,
+    pub note: String,
+}
+
+/// A signal whose note matched a `search` query.
+#[derive(Debug, Clone)]
+pub struct SearchHit {
+    pub scholar_name: String,
+    pub scholar_email: String,
+    pub cohort: String,
+    pub signal_type: String,
+    pub severity: i32,
+    pub occurred_at: NaiveDate,
+    pub source_key: String,
+    /// Note excerpt with matched terms wrapped in `**`.
+    pub snippet: String,
+}
+
+/// What `purge` does with data past the retention age.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    clap::ValueEnum,
+    serde::Deserialize,
+    serde::Serialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum RetentionMode {
+    /// Remove expired signals and scholars outright.
+    #[default]
+    Delete,
+    /// Blank signal notes and replace scholar names and emails with pseudonyms, keeping the rows
+    /// for aggregate statistics.
+    Anonymize,
+}
@@ -0,0 +1,167 @@
+//! Application-level encryption of signal notes, so the database only ever holds ciphertext for
+//! them. Once a key is [`install`]ed, [`seal`] encrypts notes on their way into the database and
+//! [`open`] decrypts them on the way out; without one, notes are stored as given and encrypted
+//! ones read as [`PLACEHOLDER`].
+//!
+//! Notes are encrypted with AES-256-GCM-SIV under a fixed nonce, so the same note always
+//! encrypts the same way. That reveals which stored notes are equal, but not what they say, and
+//! keeps re-importing an unchanged row a no-op.
+
+use std::sync::OnceLock;
+
+use aes_gcm_siv::aead::{Aead, KeyInit, OsRng};
+use aes_gcm_siv::{Aes256GcmSiv, Nonce};
+use anyhow::Context;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+
+/// Every encrypted note starts with this, followed by the base64 ciphertext.
+pub const PREFIX: &str = "enc:v1:";
+
+/// What an encrypted note reads as to a process without the key.
+pub const PLACEHOLDER: &str = "[encrypted note]";
+
+static KEY: OnceLock<NoteKey> = OnceLock::new();
+
+/// A 256-bit key for signal notes.
+#[derive(Clone)]
+pub struct NoteKey(Aes256GcmSiv);
+
+impl std::fmt::Debug for NoteKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("NoteKey(..)")
+    }
+}
+
+impl NoteKey {
+    /// Reads a key written as base64, as [`NoteKey::generate`] prints it.
+    pub fn parse(encoded: &str) -> anyhow::Result<Self> {
+        let bytes = STANDARD
+            .decode(encoded.trim())
+            .context("note key is not valid base64")?;
+        let cipher = Aes256GcmSiv::new_from_slice(&bytes)
+            .map_err(|_| anyhow::anyhow!("note key must be 32 bytes, not {}", bytes.len()))?;
+        Ok(Self(cipher))
+    }
+
+    /// A new random key, in base64.
+    pub fn generate() -> String {
+        STANDARD.encode(Aes256GcmSiv::generate_key(&mut OsRng))
+    }
+
+    /// `note` encrypted, or unchanged when it is empty. Text that merely starts with [`PREFIX`] is
+    /// encrypted like any other note.
+    pub fn seal(&self, note: &str) -> String {
+        if note.is_empty() {
+            return note.to_string();
+        }
+        let ciphertext = self
+            .0
+            .encrypt(&Nonce::default(), note.as_bytes())
+            .expect("AES-GCM-SIV encrypts any note that fits in memory");
+        format!("{PREFIX}{}", STANDARD.encode(ciphertext))
+    }
+
+    /// `note` decrypted, or unchanged when it was never encrypted. Fails for notes encrypted
+    /// under another key.
+    pub fn open(&self, note: String) -> anyhow::Result<String> {
+        let Some(encoded) = note.strip_prefix(PREFIX) else {
+            return Ok(note);
+        };
+        let ciphertext = STANDARD
+            .decode(encoded)
+            .context("encrypted note is not valid base64")?;
+        let plaintext = self
+            .0
+            .decrypt(&Nonce::default(), ciphertext.as_slice())
+            .map_err(|_| {
+                anyhow::anyhow!("a note could not be decrypted; it was encrypted with another key")
+            })?;
+        String::from_utf8(plaintext).context("decrypted note is not UTF-8")
+    }
+
+    /// A stored `note` encrypted, or unchanged when it already decrypts under this key. Unlike
+    /// checking for [`PREFIX`], this still encrypts a plaintext note that happens to start with it.
+    pub fn reseal(&self, note: String) -> String {
+        let already_sealed = is_sealed(&note) && self.open(note.clone()).is_ok();
+        if already_sealed {
+            note
+        } else {
+            self.seal(&note)
+        }
+    }
+}
+
+/// Whether `note` is stored encrypted.
+pub fn is_sealed(note: &str) -> bool {
+    note.starts_with(PREFIX)
+}
+
+/// Makes `key` the one notes are encrypted and decrypted with for the rest of the process. A
+/// second call leaves the first key in place.
+pub fn install(key: NoteKey) {
+    let _ = KEY.set(key);
+}
+
+/// The installed key, if any.
+pub fn key() -> Option<&'static NoteKey> {
+    KEY.get()
+}
+
+/// `note` as it should be stored: encrypted with the installed key, or as given without one.
+pub fn seal(note: &str) -> String {
+    match key() {
+        Some(key) => key.seal(note),
+        None => note.to_string(),
+    }
+}
+
+/// A stored note as it should be shown: decrypted with the installed key, or [`PLACEHOLDER`]
+/// when it is encrypted and there is no key.
+pub fn open(note: String) -> anyhow::Result<String> {
+    match key() {
+        Some(key) => key.open(note),
+        None if is_sealed(&note) => Ok(PLACEHOLDER.to_string()),
+        None => Ok(note),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seals_notes_deterministically_and_opens_them_with_the_same_key() {
+        let key = NoteKey::parse(&NoteKey::generate()).unwrap();
+        let sealed = key.seal("Family moved; missed two weeks");
+        assert!(is_sealed(&sealed));
+        assert!(!sealed.contains("Family"));
+        assert_eq!(key.seal("Family moved; missed two weeks"), sealed);
+        assert_eq!(key.seal(""), "");
+        assert_eq!(key.reseal(sealed.clone()), sealed);
+        assert_eq!(
+            key.open(sealed.clone()).unwrap(),
+            "Family moved; missed two weeks"
+        );
+        assert_eq!(key.open("plain".to_string()).unwrap(), "plain");
+
+        let other = NoteKey::parse(&NoteKey::generate()).unwrap();
+        assert!(other.open(sealed).is_err());
+        assert!(NoteKey::parse("c2hvcnQ=").is_err());
+    }
+
+    #[test]
+    fn notes_that_look_encrypted_are_still_sealed() {
+        let key = NoteKey::parse(&NoteKey::generate()).unwrap();
+        let lookalike = format!("{PREFIX}Family moved; missed two weeks");
+
+        let sealed = key.seal(&lookalike);
+        assert_ne!(sealed, lookalike);
+        assert!(!sealed.contains("Family"));
+        assert_eq!(key.open(sealed.clone()).unwrap(), lookalike);
+
+        let resealed = key.reseal(lookalike.clone());
+        assert_ne!(resealed, lookalike);
+        assert_eq!(key.open(resealed).unwrap(), lookalike);
+    }
+}
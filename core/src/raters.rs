@@ -0,0 +1,147 @@
+use std::collections::BTreeMap;
+
+/// One manually entered signal as seen by the consistency analysis.
+#[derive(Debug, Clone)]
+pub struct RatedSignal {
+    pub submitted_by: String,
+    pub signal_type: String,
+    pub severity: i32,
+}
+
+#[derive(Debug, Clone)]
+pub struct RaterOptions {
+    /// Raters with fewer signals of a type than this are reported but never flagged.
+    pub min_signals: usize,
+    /// Flag raters whose mean differs from the type mean by at least this many standard errors.
+    pub z_threshold: f64,
+}
+
+impl Default for RaterOptions {
+    fn default() -> Self {
+        Self {
+            min_signals: 5,
+            z_threshold: 2.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RaterComparison {
+    pub signal_type: String,
+    pub submitted_by: String,
+    pub signal_count: usize,
+    pub mean_severity: f64,
+    /// Mean severity of the same signal type across every other rater.
+    pub peer_mean: f64,
+    /// Standard errors between this rater's mean and the peer mean; `None` without peer variance.
+    pub z_score: Option<f64>,
+    pub flagged: bool,
+}
+
+fn mean_and_sd(values: &[i32]) -> (f64, f64) {
+    let count = values.len() as f64;
+    let mean = values.iter().map(|value| *value as f64).sum::<f64>() / count;
+    if values.len() < 2 {
+        return (mean, 0.0);
+    }
+    let variance = values
+        .iter()
+        .map(|value| (*value as f64 - mean).powi(2))
+        .sum::<f64>()
+        / (count - 1.0);
+    (mean, variance.sqrt())
+}
+
+/// Compares each rater's severity distribution against their peers for the same signal type.
+///
+/// Types rated by a single person are skipped because there is nothing to calibrate against.
+pub fn compare_raters(signals: &[RatedSignal], options: &RaterOptions) -> Vec<RaterComparison> {
+    let mut by_type: BTreeMap<&str, BTreeMap<&str, Vec<i32>>> = BTreeMap::new();
+    for signal in signals {
+        by_type
+            .entry(signal.signal_type.as_str())
+            .or_default()
+            .entry(signal.submitted_by.as_str())
+            .or_default()
+            .push(signal.severity);
+    }
+
+    let mut comparisons = Vec::new();
+    for (signal_type, raters) in by_type {
+        if raters.len() < 2 {
+            continue;
+        }
+
+        for (rater, severities) in &raters {
+            let peers: Vec<i32> = raters
+                .iter()
+                .filter(|(other, _)| *other != rater)
+                .flat_map(|(_, values)| values.iter().copied())
+                .collect();
+            let (mean, _) = mean_and_sd(severities);
+            let (peer_mean, peer_sd) = mean_and_sd(&peers);
+            let z_score = (peer_sd > 0.0)
+                .then(|| (mean - peer_mean) / (peer_sd / (severities.len() as f64).sqrt()));
+            let flagged = severities.len() >= options.min_signals
+                && z_score.is_some_and(|z| z.abs() >= options.z_threshold);
+
+            comparisons.push(RaterComparison {
+                signal_type: signal_type.to_string(),
+                submitted_by: rater.to_string(),
+                signal_count: severities.len(),
+                mean_severity: mean,
+                peer_mean,
+                z_score,
+                flagged,
+            });
+        }
+    }
+
+    comparisons
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rated(rater: &str, signal_type: &str, severities: &[i32]) -> Vec<RatedSignal> {
+        severities
+            .iter()
+            .map(|severity| RatedSignal {
+                submitted_by: rater.to_string(),
+                signal_type: signal_type.to_string(),
+                severity: *severity,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn flags_rater_who_grades_harshly() {
+        let mut signals = rated("harsh@gs.org", "attendance", &[5, 5, 4, 5, 5]);
+        signals.extend(rated("a@gs.org", "attendance", &[2, 3, 2, 3, 2]));
+        signals.extend(rated("b@gs.org", "attendance", &[3, 2, 2, 3, 3]));
+        signals.extend(rated("c@gs.org", "attendance", &[2, 3, 3, 2, 3]));
+        signals.extend(rated("d@gs.org", "attendance", &[3, 3, 2, 2, 3]));
+
+        let comparisons = compare_raters(&signals, &RaterOptions::default());
+        let flagged: Vec<&str> = comparisons
+            .iter()
+            .filter(|comparison| comparison.flagged)
+            .map(|comparison| comparison.submitted_by.as_str())
+            .collect();
+        assert_eq!(flagged, vec!["harsh@gs.org"]);
+    }
+
+    #[test]
+    fn does_not_flag_small_samples_or_single_rater_types() {
+        let mut signals = rated("harsh@gs.org", "attendance", &[5, 5]);
+        signals.extend(rated("a@gs.org", "attendance", &[2, 3, 2, 3, 2]));
+        signals.extend(rated("a@gs.org", "wellbeing", &[4, 4, 4]));
+
+        let comparisons = compare_raters(&signals, &RaterOptions::default());
+        assert!(comparisons.iter().all(|comparison| !comparison.flagged));
+        assert!(comparisons
+            .iter()
+            .all(|comparison| comparison.signal_type == "attendance"));
+    }
+}
@@ -0,0 +1,284 @@
+use chrono::{Duration, NaiveDate};
+
+use crate::import::SeverityBounds;
+use crate::models::{ScholarScore, SignalRecord};
+use crate::risk::Tier;
+
+/// A suggested next step for scholars matching a signal pattern or tier, written as
+/// `[[playbooks]]` in the config; every condition that is set must hold.
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Playbook {
+    pub name: String,
+    /// What to do, e.g. "Schedule a family call".
+    pub action: String,
+    /// Requires signals of this type in the window (combined with `min_severity` if set).
+    pub signal_type: Option<String>,
+    /// Requires signals at or above this severity in the window.
+    pub min_severity: Option<i32>,
+    /// Signals meeting the signal condition the window must hold.
+    pub min_count: usize,
+    /// Days before the scoring date the signal condition looks back over.
+    pub within_days: i64,
+    pub min_tier: Option<Tier>,
+}
+
+impl Default for Playbook {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            action: String::new(),
+            signal_type: None,
+            min_severity: None,
+            min_count: 1,
+            within_days: 14,
+            min_tier: None,
+        }
+    }
+}
+
+impl Playbook {
+    pub fn has_signal_condition(&self) -> bool {
+        self.signal_type.is_some() || self.min_severity.is_some()
+    }
+
+    /// Checks the playbook, with `min_severity` within the configured `severity` bounds.
+    pub fn validate(&self, severity: SeverityBounds) -> anyhow::Result<()> {
+        if self.name.trim().is_empty() {
+            anyhow::bail!("every playbook needs a name");
+        }
+        if self.action.trim().is_empty() {
+            anyhow::bail!("playbook {} needs an action", self.name);
+        }
+        if !self.has_signal_condition() && self.min_tier.is_none() {
+            anyhow::bail!("playbook {} needs at least one condition", self.name);
+        }
+        if let Some(min_severity) = self.min_severity {
+            if !severity.contains(min_severity) {
+                anyhow::bail!("min_severity must be within {severity}, got {min_severity}");
+            }
+        }
+        if self.min_count == 0 {
+            anyhow::bail!("playbook {} needs a min_count of at least 1", self.name);
+        }
+        if self.within_days <= 0 {
+            anyhow::bail!("playbook {} needs a positive within_days", self.name);
+        }
+        Ok(())
+    }
+
+    /// The conditions in words, e.g. "3+ attendance signals within 14 days and tier >= high".
+    pub fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        if self.has_signal_condition() {
+            let kind = self
+                .signal_type
+                .as_deref()
+                .map_or(String::new(), |signal_type| format!("{signal_type} "));
+            let mut condition = match self.min_count {
+                1 => format!("a {kind}signal"),
+                count => format!("{count}+ {kind}signals"),
+            };
+            if let Some(severity) = self.min_severity {
+                condition.push_str(&format!(" with severity >= {severity}"));
+            }
+            condition.push_str(&format!(" within {} days", self.within_days));
+            parts.push(condition);
+        }
+        if let Some(tier) = self.min_tier {
+            parts.push(format!("tier >= {}", tier.as_str()));
+        }
+        parts.join(" and ")
+    }
+
+    fn matches(&self, tier: Tier, signals: &[SignalFact<'_>], today: NaiveDate) -> bool {
+        if self.min_tier.is_some_and(|minimum| tier < minimum) {
+            return false;
+        }
+        if !self.has_signal_condition() {
+            return true;
+        }
+        let since = today - Duration::days(self.within_days);
+        let hits = signals
+            .iter()
+            .filter(|signal| signal.occurred_at > since && signal.occurred_at <= today)
+            .filter(|signal| {
+                self.signal_type
+                    .as_deref()
+                    .is_none_or(|signal_type| signal.signal_type.eq_ignore_ascii_case(signal_type))
+            })
+            .filter(|signal| {
+                self.min_severity
+                    .is_none_or(|severity| signal.severity >= severity)
+            })
+            .count();
+        hits >= self.min_count
+    }
+}
+
+/// The parts of a signal playbooks look at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SignalFact<'a> {
+    pub signal_type: &'a str,
+    pub severity: i32,
+    pub occurred_at: NaiveDate,
+}
+
+impl<'a> From<&'a SignalRecord> for SignalFact<'a> {
+    fn from(signal: &'a SignalRecord) -> Self {
+        Self {
+            signal_type: &signal.signal_type,
+            severity: signal.severity,
+            occurred_at: signal.occurred_at,
+        }
+    }
+}
+
+/// A playbook that matched a scholar.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct Recommendation<'a> {
+    pub playbook: &'a str,
+    pub action: &'a str,
+    /// The playbook's conditions, from [`Playbook::describe`].
+    pub reason: String,
+}
+
+/// The playbooks matching `score` and the scholar's `signals` as of `today`, in config order.
+pub fn recommend<'a>(
+    playbooks: &'a [Playbook],
+    score: &ScholarScore,
+    signals: &[SignalFact<'_>],
+    today: NaiveDate,
+) -> Vec<Recommendation<'a>> {
+    playbooks
+        .iter()
+        .filter(|playbook| playbook.matches(score.tier, signals, today))
+        .map(|playbook| Recommendation {
+            playbook: &playbook.name,
+            action: &playbook.action,
+            reason: playbook.describe(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 3, d).unwrap()
+    }
+
+    fn fact(signal_type: &str, severity: i32, d: u32) -> SignalFact<'_> {
+        SignalFact {
+            signal_type,
+            severity,
+            occurred_at: day(d),
+        }
+    }
+
+    fn score(tier: Tier) -> ScholarScore {
+        ScholarScore {
+            scholar_name: "Ada Park".to_string(),
+            scholar_email: "ada@example.edu".to_string(),
+            cohort: "2026".to_string(),
+            score: 8.0,
+            signal_count: 3,
+            advisor: None,
+            tier,
+            escalated_by: None,
+            collapsed_signals: 0,
+            percentile: 90.0,
+            cohort_percentile: 90.0,
+            last_contact: None,
+            last_signal: None,
+        }
+    }
+
+    #[test]
+    fn matches_signal_runs_inside_the_window_and_tiers() {
+        let playbooks = [
+            Playbook {
+                name: "attendance-run".to_string(),
+                action: "Schedule a family call".to_string(),
+                signal_type: Some("attendance".to_string()),
+                min_count: 3,
+                ..Playbook::default()
+            },
+            Playbook {
+                name: "critical".to_string(),
+                action: "Escalate to the program director".to_string(),
+                min_tier: Some(Tier::Critical),
+                ..Playbook::default()
+            },
+        ];
+        let signals = [
+            fact("attendance", 2, 1),
+            fact("Attendance", 3, 20),
+            fact("attendance", 2, 25),
+            fact("financial", 4, 26),
+        ];
+        // The signal on the 1st falls outside the two weeks before the 28th.
+        assert!(recommend(&playbooks, &score(Tier::High), &signals, day(28)).is_empty());
+
+        let signals = [
+            fact("attendance", 2, 15),
+            fact("Attendance", 3, 20),
+            fact("attendance", 2, 25),
+        ];
+        let steps = recommend(&playbooks, &score(Tier::Critical), &signals, day(28));
+        assert_eq!(
+            steps,
+            [
+                Recommendation {
+                    playbook: "attendance-run",
+                    action: "Schedule a family call",
+                    reason: "3+ attendance signals within 14 days".to_string(),
+                },
+                Recommendation {
+                    playbook: "critical",
+                    action: "Escalate to the program director",
+                    reason: "tier >= critical".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn playbooks_need_an_action_and_a_condition() {
+        let playbook = Playbook {
+            name: "empty".to_string(),
+            action: "Call".to_string(),
+            ..Playbook::default()
+        };
+        assert!(playbook.validate(SeverityBounds::default()).is_err());
+        let playbook = Playbook {
+            min_severity: Some(4),
+            ..playbook
+        };
+        assert!(playbook.validate(SeverityBounds::default()).is_ok());
+        assert!(Playbook {
+            action: " ".to_string(),
+            ..playbook
+        }
+        .validate(SeverityBounds::default())
+        .is_err());
+    }
+
+    #[test]
+    fn playbook_severities_follow_the_configured_bounds() {
+        let playbook = Playbook {
+            name: "crisis".to_string(),
+            action: "Call".to_string(),
+            min_severity: Some(8),
+            ..Playbook::default()
+        };
+        assert!(playbook.validate(SeverityBounds::default()).is_err());
+        assert!(playbook
+            .validate(SeverityBounds { min: 0, max: 10 })
+            .is_ok());
+        assert!(playbook
+            .validate(SeverityBounds { min: 0, max: 7 })
+            .is_err());
+    }
+}
@@ -0,0 +1,1427 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt::Write;
+
+use chrono::NaiveDate;
+use uuid::Uuid;
+
+use crate::calendar::AcademicCalendar;
+use crate::models::{
+    CohortHealth, Intervention, ScholarScore, SignalPair, SignalRecord, SignalTrend,
+    SignalTypeSummary,
+};
+use crate::recommendations::{self, Playbook, SignalFact};
+use crate::survival::{self, Study};
+use crate::{health, risk};
+
+#[derive(Debug, Clone, Default)]
+pub struct ReportOptions {
+    /// Breakdown cells describing fewer scholars than this are replaced with "<N".
+    pub min_cell_size: Option<usize>,
+    /// Start date of the reported cohort; labels trend weeks with their program week.
+    pub cohort_start: Option<NaiveDate>,
+    pub scoring: risk::ScoringConfig,
+    /// Listed under their own heading when non-empty, e.g. for a single-scholar report.
+    pub interventions: Vec<Intervention>,
+    /// Adds a section on the next steps these playbooks suggest for each listed scholar, e.g. for
+    /// a single-scholar report.
+    pub playbooks: Vec<Playbook>,
+    /// Trend weeks whose signals, scholars, or average severity rose by more than this percentage
+    /// over the week before are flagged.
+    pub trend_alert_pct: Option<f64>,
+    /// Adds a section on which signal types the same scholars logged within this many days of
+    /// each other.
+    pub co_occurrence_days: Option<i64>,
+    /// Adds a section grouping scholars into at most this many signal profiles.
+    pub clusters: Option<usize>,
+    /// Labels trend weeks with their season and judges week-over-week changes against the volume
+    /// each season is expected to bring.
+    pub calendar: AcademicCalendar,
+    /// Date each scholar was last reached, by email, from `db::last_contacts`. When set, listed
+    /// scholars show how many days ago that was.
+    pub last_contacts: Option<HashMap<String, NaiveDate>>,
+    /// Adds a section charting these weekly health points, from `db::fetch_cohort_health`, as one
+    /// line per cohort.
+    pub health: Option<Vec<CohortHealth>>,
+    /// Adds a section on how long scholars in each tier stayed after the study's reference date.
+    pub survival: Option<Study>,
+    /// Last day of an explicit date range. The report is then scored as of that day and its
+    /// header names the whole range.
+    pub through: Option<NaiveDate>,
+    /// Scholars scoring below this are left off the risk list; percentiles and the signal mix
+    /// still count them.
+    pub min_score: Option<f64>,
+    /// Names scholars by [`pseudonym`] without their emails, and lists signals and interventions
+    /// by type only, leaving out notes, evidence links, and outcomes.
+    pub redact: bool,
+}
+
+impl ReportOptions {
+    /// Whether a breakdown cell describing `scholar_count` scholars falls under `min_cell_size`.
+    pub fn suppresses(&self, scholar_count: usize) -> bool {
+        self.min_cell_size
+            .is_some_and(|minimum| scholar_count < minimum)
+    }
+}
+
+/// Stable stand-in for a scholar in redacted reports, the same one `scholar anonymize` assigns.
+pub fn pseudonym(scholar_id: Uuid) -> String {
+    format!("Scholar {}", &scholar_id.to_string()[..8])
+}
+
+pub fn summarize_by_type(signals: &[SignalRecord]) -> Vec<SignalTypeSummary> {
+    let mut map: HashMap<String, (usize, i32, HashSet<Uuid>)> = HashMap::new();
+
+    for signal in signals {
+        let entry = map
+            .entry(signal.signal_type.clone())
+            .or_insert_with(|| (0, 0, HashSet::new()));
+        entry.0 += 1;
+        entry.1 += signal.severity;
+        entry.2.insert(signal.scholar_id);
+    }
+
+    let mut summaries: Vec<SignalTypeSummary> = map
+        .into_iter()
+        .map(
+            |(signal_type, (count, total_severity, scholars))| SignalTypeSummary {
+                signal_type,
+                count,
+                scholar_count: scholars.len(),
+                avg_severity: if count == 0 {
+                    0.0
+                } else {
+                    total_severity as f64 / count as f64
+                },
+            },
+        )
+        .collect();
+
+    summaries.sort_by(|a, b| {
+        b.count
+            .cmp(&a.count)
+            .then_with(|| a.signal_type.cmp(&b.signal_type))
+    });
+    summaries
+}
+
+/// Pairs of signal types that one scholar logged within `window_days` of each other, most
+/// widespread first. `scores` supplies each pair's average risk score.
+pub fn co_occurrences(
+    signals: &[SignalRecord],
+    window_days: i64,
+    scores: &[ScholarScore],
+) -> Vec<SignalPair> {
+    let mut by_scholar: HashMap<&str, Vec<(NaiveDate, &str)>> = HashMap::new();
+    for signal in signals {
+        by_scholar
+            .entry(&signal.scholar_email)
+            .or_default()
+            .push((signal.occurred_at, &signal.signal_type));
+    }
+
+    let mut pairs: HashMap<(&str, &str), HashSet<&str>> = HashMap::new();
+    for (email, logged) in &by_scholar {
+        for (index, (date, signal_type)) in logged.iter().enumerate() {
+            for (other_date, other_type) in &logged[index + 1..] {
+                if signal_type == other_type || (*date - *other_date).num_days().abs() > window_days
+                {
+                    continue;
+                }
+                let pair = if signal_type < other_type {
+                    (*signal_type, *other_type)
+                } else {
+                    (*other_type, *signal_type)
+                };
+                pairs.entry(pair).or_default().insert(email);
+            }
+        }
+    }
+
+    let score_of: HashMap<&str, f64> = scores
+        .iter()
+        .map(|score| (score.scholar_email.as_str(), score.score))
+        .collect();
+    let mut pairs: Vec<SignalPair> = pairs
+        .into_iter()
+        .map(|((first, second), scholars)| {
+            let total: f64 = scholars
+                .iter()
+                .map(|email| score_of.get(email).copied().unwrap_or_default())
+                .sum();
+            SignalPair {
+                first: first.to_string(),
+                second: second.to_string(),
+                scholar_count: scholars.len(),
+                avg_score: total / scholars.len() as f64,
+            }
+        })
+        .collect();
+    pairs.sort_by(|a, b| {
+        b.scholar_count
+            .cmp(&a.scholar_count)
+            .then_with(|| b.avg_score.total_cmp(&a.avg_score))
+            .then_with(|| (&a.first, &a.second).cmp(&(&b.first, &b.second)))
+    });
+    pairs
+}
+
+/// The day a report is scored as of, and the scores of every scholar in `signals` with their last
+/// contact when `options.last_contacts` is set. `min_score` is not applied.
+pub fn score_report(
+    since_days: i64,
+    cutoff: NaiveDate,
+    signals: &[SignalRecord],
+    options: &ReportOptions,
+) -> (NaiveDate, Vec<ScholarScore>) {
+    let today = options
+        .through
+        .unwrap_or(cutoff + chrono::Duration::days(since_days.max(1)));
+    let mut scores = risk::score_signals_with(signals, since_days, today, &options.scoring);
+    if let Some(contacts) = &options.last_contacts {
+        for score in &mut scores {
+            score.last_contact = contacts.get(&score.scholar_email).copied();
+        }
+    }
+    (today, scores)
+}
+
+#[tracing::instrument(skip_all, fields(cohort = cohort.unwrap_or("all cohorts"), signals = signals.len()))]
+pub fn build_report(
+    cohort: Option<&str>,
+    since_days: i64,
+    cutoff: NaiveDate,
+    signals: &[SignalRecord],
+    trends: &[SignalTrend],
+    options: &ReportOptions,
+) -> String {
+    let (today, mut scores) = score_report(since_days, cutoff, signals, options);
+    let summaries = summarize_by_type(signals);
+    let scored = scores.len();
+    if let Some(min_score) = options.min_score {
+        scores.retain(|score| score.score >= min_score);
+    }
+
+    let mut output = String::new();
+    let cohort_label = cohort.unwrap_or("all cohorts");
+    let pseudonyms: HashMap<&str, String> = match options.redact {
+        true => signals
+            .iter()
+            .map(|signal| (signal.scholar_email.as_str(), pseudonym(signal.scholar_id)))
+            .collect(),
+        false => HashMap::new(),
+    };
+    let redacted = |email: &str| {
+        pseudonyms
+            .get(email)
+            .cloned()
+            .unwrap_or_else(|| "a redacted scholar".to_string())
+    };
+
+    let _ = writeln!(output, "# Cohort Early Warning Report");
+    let _ = match options.through {
+        Some(through) => writeln!(
+            output,
+            "Generated for {cohort_label} (signals from {cutoff} to {through})"
+        ),
+        None => writeln!(
+            output,
+            "Generated for {} (signals since {})",
+            cohort_label, cutoff
+        ),
+    };
+    let _ = writeln!(output);
+    let _ = writeln!(output, "## Signal Mix");
+
+    if summaries.is_empty() {
+        let _ = writeln!(output, "No signals recorded for this window.");
+    } else {
+        for summary in summaries.iter() {
+            if options.suppresses(summary.scholar_count) {
+                let _ = writeln!(
+                    output,
+                    "- {}: <{} scholars (details suppressed)",
+                    summary.signal_type,
+                    options.min_cell_size.unwrap_or_default()
+                );
+                continue;
+            }
+            let _ = writeln!(
+                output,
+                "- {}: {} signals (avg severity {:.1})",
+                summary.signal_type, summary.count, summary.avg_severity
+            );
+        }
+    }
+
+    let _ = writeln!(output);
+    let _ = writeln!(output, "## Highest Risk Scholars");
+
+    if scores.is_empty() && scored > 0 {
+        let _ = writeln!(
+            output,
+            "No scholars scored at least {:.2}.",
+            options.min_score.unwrap_or_default()
+        );
+    } else if scores.is_empty() {
+        let _ = writeln!(output, "No scholars with signals in this window.");
+    } else {
+        for score in scores.iter().take(10) {
+            let advisor = score
+                .advisor
+                .as_deref()
+                .map(|name| format!(", advisor {name}"))
+                .unwrap_or_default();
+            let contact = match options.last_contacts {
+                Some(_) => format!("; {}", risk::contact_label(score, today)),
+                None => String::new(),
+            };
+            let scholar = match options.redact {
+                true => format!("{} ({}", redacted(&score.scholar_email), score.cohort),
+                false => format!(
+                    "{} ({}, {}",
+                    score.scholar_name, score.scholar_email, score.cohort
+                ),
+            };
+            let _ = writeln!(
+                output,
+                "- {scholar}{advisor}) score {:.2} across {} signals {}{}{contact}",
+                score.score,
+                score.signal_count,
+                risk::tier_label(score),
+                risk::collapse_note(score)
+            );
+        }
+    }
+
+    let mut recent_signals = signals.to_vec();
+    recent_signals.sort_by_key(|signal| std::cmp::Reverse(signal.occurred_at));
+    let _ = writeln!(output);
+    let _ = match options.redact {
+        true => writeln!(output, "## Recent Signals"),
+        false => writeln!(output, "## Recent Signal Notes"),
+    };
+
+    if recent_signals.is_empty() {
+        let _ = writeln!(output, "No signals recorded for this window.");
+    } else {
+        for signal in recent_signals.iter().take(5) {
+            if options.redact {
+                let _ = writeln!(
+                    output,
+                    "- {} ({}) on {}: severity {}",
+                    pseudonym(signal.scholar_id),
+                    signal.signal_type,
+                    signal.occurred_at,
+                    signal.severity
+                );
+                continue;
+            }
+            let _ = writeln!(
+                output,
+                "- {} ({}) on {}: {}{}",
+                signal.scholar_name,
+                signal.signal_type,
+                signal.occurred_at,
+                signal.note,
+                evidence_links(signal)
+            );
+        }
+    }
+
+    if let Some(window_days) = options.co_occurrence_days {
+        let _ = writeln!(output);
+        let _ = writeln!(output, "## Signal Co-occurrence");
+        let pairs = co_occurrences(signals, window_days, &scores);
+        if pairs.is_empty() {
+            let _ = writeln!(output, "No scholar logged two signal types in this window.");
+        } else {
+            let overall = scores.iter().map(|score| score.score).sum::<f64>() / scores.len() as f64;
+            let _ = writeln!(
+                output,
+                "Signal types logged for the same scholar within {window_days} days of each other \
+                 (avg score {overall:.2} across all scholars with signals):"
+            );
+            let type_scholars: HashMap<&str, usize> = summaries
+                .iter()
+                .map(|summary| (summary.signal_type.as_str(), summary.scholar_count))
+                .collect();
+            for pair in pairs.iter().take(10) {
+                if options.suppresses(pair.scholar_count) {
+                    let _ = writeln!(
+                        output,
+                        "- {} + {}: <{} scholars (details suppressed)",
+                        pair.first,
+                        pair.second,
+                        options.min_cell_size.unwrap_or_default()
+                    );
+                    continue;
+                }
+                let share = |signal_type: &str| {
+                    pair.scholar_count as f64 * 100.0
+                        / type_scholars.get(signal_type).copied().unwrap_or(1) as f64
+                };
+                let _ = writeln!(
+                    output,
+                    "- {} + {}: {} scholars (avg score {:.2}); {:.0}% of {} scholars, {:.0}% of {} scholars",
+                    pair.first,
+                    pair.second,
+                    pair.scholar_count,
+                    pair.avg_score,
+                    share(&pair.first),
+                    pair.first,
+                    share(&pair.second),
+                    pair.second
+                );
+            }
+        }
+    }
+
+    if let Some(k) = options.clusters {
+        let _ = writeln!(output);
+        let _ = writeln!(output, "## Signal Profiles");
+        let clusters = crate::clusters::cluster(signals, k);
+        if clusters.is_empty() {
+            let _ = writeln!(output, "No signals recorded for this window.");
+        }
+        for cluster in &clusters {
+            if options.suppresses(cluster.scholars.len()) {
+                let _ = writeln!(
+                    output,
+                    "- {}: <{} scholars (details suppressed)",
+                    cluster.label,
+                    options.min_cell_size.unwrap_or_default()
+                );
+                continue;
+            }
+            let mix: Vec<String> = cluster
+                .ranked_mix()
+                .iter()
+                .map(|(signal_type, share)| format!("{signal_type} {:.0}%", share * 100.0))
+                .collect();
+            let _ = writeln!(
+                output,
+                "- {}: {} scholars (avg score {:.2}); {}; avg severity {:.1}",
+                cluster.label,
+                cluster.scholars.len(),
+                cluster.avg_score(&scores),
+                mix.join(", "),
+                cluster.avg_severity
+            );
+        }
+    }
+
+    if let Some(points) = &options.health {
+        let _ = writeln!(output);
+        let _ = writeln!(output, "## Cohort Health Index");
+        if points.is_empty() {
+            let _ = writeln!(
+                output,
+                "No health index stored for this scope; run `refresh-health` first."
+            );
+        } else {
+            let _ = writeln!(
+                output,
+                "Weekly, from 100 (no active scholar at risk) to 0 (every active scholar critical)."
+            );
+        }
+        let mut cohorts: Vec<&str> = points.iter().map(|p| p.cohort.as_str()).collect();
+        cohorts.dedup();
+        for cohort in cohorts {
+            let weeks: Vec<&CohortHealth> = points.iter().filter(|p| p.cohort == cohort).collect();
+            let (first, latest) = (weeks[0], weeks[weeks.len() - 1]);
+            if options.suppresses(latest.scholars as usize) {
+                let _ = writeln!(
+                    output,
+                    "- {cohort}: <{} scholars (details suppressed)",
+                    options.min_cell_size.unwrap_or_default()
+                );
+                continue;
+            }
+            let values: Vec<f64> = weeks.iter().map(|p| p.health_index).collect();
+            let low = values.iter().copied().fold(f64::INFINITY, f64::min);
+            let _ = writeln!(
+                output,
+                "- {cohort}: {} {:.0} in the week of {} ({:+.0} since {}; low {low:.0}); \
+                 {} of {} scholars at risk",
+                health::sparkline(&values),
+                latest.health_index,
+                latest.week_start,
+                latest.health_index - first.health_index,
+                first.week_start,
+                latest.at_risk,
+                latest.scholars
+            );
+        }
+    }
+
+    if let Some(study) = &options.survival {
+        let _ = writeln!(output);
+        let _ = writeln!(output, "## Time to Attrition");
+        write_survival(&mut output, study, options);
+    }
+
+    if !options.interventions.is_empty() {
+        let _ = writeln!(output);
+        let _ = writeln!(output, "## Interventions");
+        for intervention in &options.interventions {
+            let status = match intervention.closed_at {
+                Some(closed_at) => format!("closed {}", closed_at.date_naive()),
+                None => "open".to_string(),
+            };
+            if options.redact {
+                let _ = writeln!(
+                    output,
+                    "- {} {} for {} ({status})",
+                    intervention.occurred_on,
+                    intervention.intervention_type,
+                    redacted(&intervention.scholar_email)
+                );
+                continue;
+            }
+            let _ = writeln!(
+                output,
+                "- {} {} for {}: {} ({status})",
+                intervention.occurred_on,
+                intervention.intervention_type,
+                intervention.scholar_name,
+                intervention.outcome.as_deref().unwrap_or("pending")
+            );
+        }
+    }
+
+    if !options.playbooks.is_empty() {
+        let _ = writeln!(output);
+        let _ = writeln!(output, "## Suggested Next Steps");
+        let mut suggested = false;
+        for score in scores.iter().take(10) {
+            let facts: Vec<SignalFact> = signals
+                .iter()
+                .filter(|signal| signal.scholar_email == score.scholar_email)
+                .map(SignalFact::from)
+                .collect();
+            let steps = recommendations::recommend(&options.playbooks, score, &facts, today);
+            if steps.is_empty() {
+                continue;
+            }
+            suggested = true;
+            let scholar = match options.redact {
+                true => redacted(&score.scholar_email),
+                false => score.scholar_name.clone(),
+            };
+            for step in steps {
+                let _ = writeln!(output, "- {scholar}: {} ({})", step.action, step.reason);
+            }
+        }
+        if !suggested {
+            let _ = writeln!(output, "No playbook matches these scholars.");
+        }
+    }
+
+    let _ = writeln!(output);
+    let _ = writeln!(output, "## Weekly Signal Trend");
+
+    if trends.is_empty() {
+        let _ = writeln!(output, "No weekly trend data available for this window.");
+    } else {
+        let mut previous: Option<&SignalTrend> = None;
+        for trend in trends {
+            let prior = previous.take();
+            let week = match options.cohort_start {
+                Some(start) => format!(
+                    "{} (Program week {})",
+                    trend.week_start,
+                    crate::trends::program_week(start, trend.week_start)
+                ),
+                None => trend.week_start.to_string(),
+            };
+            if options.suppresses(trend.scholar_count.max(0) as usize) {
+                let _ = writeln!(
+                    output,
+                    "- Week of {}: <{} scholars (details suppressed)",
+                    week,
+                    options.min_cell_size.unwrap_or_default()
+                );
+                continue;
+            }
+            let season = match options.calendar.label(trend.week_start) {
+                label if label.is_empty() => label,
+                label => format!(", {label}"),
+            };
+            let _ = writeln!(
+                output,
+                "- Week of {}: {} signals across {} scholars (avg severity {:.2}){season}",
+                week, trend.signal_count, trend.scholar_count, trend.avg_severity
+            );
+            // Suppressed weeks get no change line either way, as it would reveal their counts.
+            if let Some(prior) = prior {
+                let _ = writeln!(output, "{}", week_change(prior, trend, options));
+            }
+            previous = Some(trend);
+        }
+    }
+
+    output
+}
+
+/// Indented line comparing `current` with the trend row before it. Weeks without signals have no
+/// row, so a gap means the prior week was quiet rather than comparable.
+///
+/// When the two weeks fall in seasons with different expected volume, signal and scholar counts
+/// are also compared after scaling each week to term time, and only that adjusted change is
+/// flagged.
+/// Kaplan-Meier lines per tier held on the reference date, then withdrawal rates at high tier or
+/// above against below it.
+fn write_survival(output: &mut String, study: &Study, options: &ReportOptions) {
+    if study.subjects.is_empty() {
+        let _ = writeln!(output, "No scholars were enrolled on {}.", study.reference);
+        return;
+    }
+    let _ = writeln!(
+        output,
+        "Share of scholars still enrolled after {}, by risk tier on that date (Kaplan-Meier; \
+         graduates and scholars still enrolled are censored).",
+        study.reference
+    );
+    for tier in [
+        risk::Tier::Critical,
+        risk::Tier::High,
+        risk::Tier::Moderate,
+        risk::Tier::Low,
+    ] {
+        let members: Vec<&survival::Subject> =
+            study.subjects.iter().filter(|s| s.tier == tier).collect();
+        if members.is_empty() {
+            continue;
+        }
+        if options.suppresses(members.len()) {
+            let _ = writeln!(
+                output,
+                "- {}: <{} scholars (details suppressed)",
+                tier.as_str(),
+                options.min_cell_size.unwrap_or_default()
+            );
+            continue;
+        }
+        let curve = survival::kaplan_meier(&members);
+        let followed = members.iter().map(|s| s.days).max().unwrap_or(0);
+        let milestones: Vec<String> = survival::MILESTONES
+            .iter()
+            .map(|&day| match day <= followed {
+                true => format!(
+                    "{:.0}% at {day} days",
+                    survival::survival_at(&curve, day) * 100.0
+                ),
+                false => format!("n/a at {day} days"),
+            })
+            .collect();
+        let median = match survival::median_days(&curve) {
+            Some(days) => format!("median time to withdrawal {days} days"),
+            None => "median not reached".to_string(),
+        };
+        let _ = writeln!(
+            output,
+            "- {} ({} scholars, {} withdrew): {}; {median}",
+            tier.as_str(),
+            members.len(),
+            members.iter().filter(|s| s.withdrew).count(),
+            milestones.join(", ")
+        );
+    }
+
+    let (above, below): (Vec<&survival::Subject>, Vec<&survival::Subject>) = study
+        .subjects
+        .iter()
+        .partition(|s| s.tier >= risk::Tier::High);
+    if let (Some(above), Some(below)) = (
+        survival::withdrawal_rate(&above),
+        survival::withdrawal_rate(&below),
+    ) {
+        let ratio = match below > 0.0 {
+            true => format!(" ({:.1}x as fast)", above / below),
+            false => String::new(),
+        };
+        let _ = writeln!(
+            output,
+            "High tier or above withdrew at {above:.1} per 100 scholar-months, against {below:.1} \
+             below it{ratio}."
+        );
+    }
+}
+
+fn week_change(prior: &SignalTrend, current: &SignalTrend, options: &ReportOptions) -> String {
+    if current.week_start - prior.week_start != chrono::Duration::weeks(1) {
+        return "  - Change: no signals the prior week".to_string();
+    }
+
+    let calendar = &options.calendar;
+    let seasonal = calendar.factor(prior.week_start) != calendar.factor(current.week_start);
+    let metrics = [
+        (
+            "signals",
+            prior.signal_count as f64,
+            current.signal_count as f64,
+            0,
+            seasonal,
+        ),
+        (
+            "scholars",
+            prior.scholar_count as f64,
+            current.scholar_count as f64,
+            0,
+            seasonal,
+        ),
+        (
+            "avg severity",
+            prior.avg_severity,
+            current.avg_severity,
+            2,
+            false,
+        ),
+    ];
+    let mut changes = Vec::new();
+    let mut flagged = Vec::new();
+    for (label, before, after, decimals, adjusted) in metrics {
+        let mut change = format!("{label} {:+.*}", decimals, after - before);
+        if before > 0.0 {
+            let mut pct = (after - before) / before * 100.0;
+            if adjusted {
+                let raw = pct;
+                let before = calendar.adjust(prior.week_start, before);
+                pct = (calendar.adjust(current.week_start, after) - before) / before * 100.0;
+                let _ = write!(change, " ({raw:+.0}%; {pct:+.0}% seasonally adjusted)");
+            } else {
+                let _ = write!(change, " ({pct:+.0}%)");
+            }
+            if options.trend_alert_pct.is_some_and(|limit| pct > limit) {
+                flagged.push(label);
+            }
+        }
+        changes.push(change);
+    }
+
+    let mut line = format!("  - Change: {}", changes.join(", "));
+    if let Some(limit) = options.trend_alert_pct.filter(|_| !flagged.is_empty()) {
+        let _ = write!(
+            line,
+            " **[flagged: {} up more than {limit}%]**",
+            flagged.join(", ")
+        );
+    }
+    line
+}
+
+/// Markdown links to a signal's evidence, prefixed for appending to a note line.
+fn evidence_links(signal: &SignalRecord) -> String {
+    if signal.attachments.is_empty() {
+        return String::new();
+    }
+    let links: Vec<String> = signal
+        .attachments
+        .iter()
+        .map(|attachment| {
+            format!(
+                "[{}]({}) ({})",
+                attachment.label, attachment.url, attachment.kind
+            )
+        })
+        .collect();
+    format!(" — evidence: {}", links.join(", "))
+}
+
+/// Splits `items` by the cohort `cohort_of` reads from each, in cohort order, so one fetch can
+/// feed a report per cohort.
+pub fn by_cohort<T>(items: Vec<T>, cohort_of: impl Fn(&T) -> &str) -> BTreeMap<String, Vec<T>> {
+    let mut cohorts: BTreeMap<String, Vec<T>> = BTreeMap::new();
+    for item in items {
+        let cohort = cohort_of(&item).to_string();
+        cohorts.entry(cohort).or_default().push(item);
+    }
+    cohorts
+}
+
+/// Name of `cohort`'s report in an output directory. Characters other than letters, digits, `-`,
+/// `_`, and `.` become `-`, so no cohort name can point outside the directory.
+pub fn report_file_name(cohort: &str) -> String {
+    let stem: String = cohort
+        .chars()
+        .map(|c| match c {
+            c if c.is_alphanumeric() || matches!(c, '-' | '_' | '.') => c,
+            _ => '-',
+        })
+        .collect();
+    format!("{stem}.md")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use uuid::Uuid;
+
+    fn sample_signal(days_ago: i64, severity: i32) -> SignalRecord {
+        let occurred_at = chrono::Utc::now().date_naive() - chrono::Duration::days(days_ago);
+        SignalRecord {
+            scholar_id: Uuid::new_v4(),
+            scholar_name: "Avery Lee".to_string(),
+            scholar_email: "avery@example.com".to_string(),
+            cohort: "2026".to_string(),
+            signal_type: "attendance".to_string(),
+            severity,
+            occurred_at,
+            note: "missed session".to_string(),
+            advisor: None,
+            submitted_by: None,
+            attachments: Vec::new(),
+            note_categories: Vec::new(),
+            note_sentiment: None,
+        }
+    }
+
+    #[test]
+    fn splits_signals_by_cohort() {
+        let mut later = sample_signal(1, 2);
+        later.cohort = "2025".to_string();
+        let signals = vec![sample_signal(2, 3), later, sample_signal(1, 1)];
+        let cohorts = by_cohort(signals, |signal| &signal.cohort);
+        let sizes: Vec<(&str, usize)> = cohorts
+            .iter()
+            .map(|(cohort, signals)| (cohort.as_str(), signals.len()))
+            .collect();
+        assert_eq!(sizes, [("2025", 1), ("2026", 2)]);
+
+        assert_eq!(report_file_name("2026"), "2026.md");
+        assert_eq!(report_file_name("../Fall 2026"), "..-Fall-2026.md");
+    }
+
+    #[test]
+    fn summarizes_signal_types() {
+        let signals = vec![sample_signal(2, 3), sample_signal(1, 1)];
+        let summaries = summarize_by_type(&signals);
+        assert_eq!(summaries.len(), 1);
+        assert_eq!(summaries[0].count, 2);
+        assert!((summaries[0].avg_severity - 2.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn report_includes_weekly_trend_section() {
+        let signals = vec![sample_signal(2, 3)];
+        let trends = vec![SignalTrend {
+            week_start: NaiveDate::from_ymd_opt(2026, 2, 2).unwrap(),
+            signal_count: 2,
+            avg_severity: 2.5,
+            scholar_count: 1,
+        }];
+        let report = build_report(
+            Some("2026"),
+            30,
+            NaiveDate::from_ymd_opt(2026, 1, 1).unwrap(),
+            &signals,
+            &trends,
+            &ReportOptions::default(),
+        );
+        assert!(report.contains("## Weekly Signal Trend"));
+        assert!(report.contains("Week of 2026-02-02"));
+    }
+
+    #[test]
+    fn flags_weeks_rising_past_the_threshold() {
+        let week = |weeks, signal_count, avg_severity, scholar_count| SignalTrend {
+            week_start: NaiveDate::from_ymd_opt(2026, 2, 2).unwrap()
+                + chrono::Duration::weeks(weeks),
+            signal_count,
+            avg_severity,
+            scholar_count,
+        };
+        let trends = vec![
+            week(0, 10, 2.0, 6),
+            week(1, 12, 3.0, 7),
+            week(2, 11, 3.0, 7),
+            // Nothing logged in week 3.
+            week(4, 4, 3.0, 4),
+        ];
+        let render = |trend_alert_pct| {
+            build_report(
+                Some("2026"),
+                30,
+                NaiveDate::from_ymd_opt(2026, 1, 30).unwrap(),
+                &[],
+                &trends,
+                &ReportOptions {
+                    trend_alert_pct,
+                    ..ReportOptions::default()
+                },
+            )
+        };
+
+        let report = render(Some(25.0));
+        assert!(report.contains(
+            "  - Change: signals +2 (+20%), scholars +1 (+17%), avg severity +1.00 (+50%) \
+             **[flagged: avg severity up more than 25%]**"
+        ));
+        assert!(report.contains(
+            "  - Change: signals -1 (-8%), scholars +0 (+0%), avg severity +0.00 (+0%)\n"
+        ));
+        assert!(report.contains("  - Change: no signals the prior week"));
+        assert_eq!(report.matches("flagged").count(), 1);
+        assert_eq!(
+            render(Some(10.0))
+                .matches("flagged: signals, scholars, avg severity")
+                .count(),
+            1
+        );
+        assert!(!render(None).contains("flagged"));
+    }
+
+    #[test]
+    fn lists_days_since_last_contact() {
+        let today = chrono::Utc::now().date_naive();
+        let signal = |email: &str| SignalRecord {
+            scholar_id: Uuid::from_u128(u128::from(email.as_bytes()[0])),
+            scholar_email: email.to_string(),
+            ..sample_signal(2, 4)
+        };
+        let signals = [signal("a@x.org"), signal("b@x.org")];
+        let render = |last_contacts| {
+            build_report(
+                None,
+                30,
+                today - chrono::Duration::days(30),
+                &signals,
+                &[],
+                &ReportOptions {
+                    last_contacts,
+                    ..ReportOptions::default()
+                },
+            )
+        };
+
+        let contacts = HashMap::from([("a@x.org".to_string(), today - chrono::Duration::days(9))]);
+        let report = render(Some(contacts));
+        assert!(report.contains(
+            "(a@x.org, 2026) score 4.00 across 1 signals [moderate]; last contact 9 days ago\n"
+        ));
+        assert!(report.contains(
+            "(b@x.org, 2026) score 4.00 across 1 signals [moderate]; no contact recorded\n"
+        ));
+        assert!(!render(None).contains("contact"));
+    }
+
+    #[test]
+    fn redacts_scholar_identities_and_notes() {
+        let signal = SignalRecord {
+            scholar_id: Uuid::from_u128(0x1a2b3c4d_0000_0000_0000_000000000000),
+            attachments: vec![crate::models::Attachment {
+                url: "https://files.example.org/1".to_string(),
+                label: "advisor email".to_string(),
+                kind: "link".to_string(),
+            }],
+            ..sample_signal(2, 4)
+        };
+        let report = build_report(
+            Some("2026"),
+            30,
+            chrono::Utc::now().date_naive() - chrono::Duration::days(30),
+            &[signal],
+            &[],
+            &ReportOptions {
+                redact: true,
+                ..ReportOptions::default()
+            },
+        );
+
+        assert!(report.contains("- Scholar 1a2b3c4d (2026) score 4.00 across 1 signals"));
+        assert!(report.contains("## Recent Signals\n- Scholar 1a2b3c4d (attendance) on "));
+        assert!(report.contains(": severity 4\n"));
+        for identifying in [
+            "Avery Lee",
+            "avery@example.com",
+            "missed session",
+            "files.example",
+        ] {
+            assert!(!report.contains(identifying), "{identifying} leaked");
+        }
+    }
+
+    #[test]
+    fn leaves_scholars_below_min_score_off_the_risk_list() {
+        let today = chrono::Utc::now().date_naive();
+        let signal = |email: &str, severity| SignalRecord {
+            scholar_id: Uuid::from_u128(u128::from(email.as_bytes()[0])),
+            scholar_email: email.to_string(),
+            ..sample_signal(2, severity)
+        };
+        let signals = [signal("a@x.org", 5), signal("b@x.org", 2)];
+        let render = |min_score| {
+            build_report(
+                None,
+                30,
+                today - chrono::Duration::days(30),
+                &signals,
+                &[],
+                &ReportOptions {
+                    min_score,
+                    ..ReportOptions::default()
+                },
+            )
+        };
+
+        let report = render(Some(4.0));
+        assert!(report.contains("(a@x.org, 2026) score 5.00"));
+        assert!(!report.contains("b@x.org"));
+        assert!(report.contains("- attendance: 2 signals"));
+        assert!(render(Some(9.0)).contains("No scholars scored at least 9.00."));
+    }
+
+    #[test]
+    fn compares_time_to_attrition_by_tier() {
+        let subject = |tier, days, withdrew| survival::Subject {
+            email: format!("{days}@x.org"),
+            tier,
+            days,
+            withdrew,
+        };
+        let study = Study {
+            reference: NaiveDate::from_ymd_opt(2026, 1, 5).unwrap(),
+            subjects: vec![
+                subject(risk::Tier::High, 20, true),
+                subject(risk::Tier::High, 100, false),
+                subject(risk::Tier::Low, 60, true),
+                subject(risk::Tier::Low, 200, false),
+                subject(risk::Tier::Low, 200, false),
+                subject(risk::Tier::Low, 200, false),
+            ],
+        };
+        let report = build_report(
+            None,
+            30,
+            NaiveDate::from_ymd_opt(2026, 7, 1).unwrap(),
+            &[],
+            &[],
+            &ReportOptions {
+                survival: Some(study),
+                ..ReportOptions::default()
+            },
+        );
+        assert!(
+            report.contains(
+                "- high (2 scholars, 1 withdrew): 50% at 30 days, 50% at 90 days, \
+                 n/a at 180 days, n/a at 365 days; median time to withdrawal 20 days\n"
+            ),
+            "{report}"
+        );
+        assert!(report.contains("- low (4 scholars, 1 withdrew): 100% at 30 days, 75% at 90 days"));
+        // 1 withdrawal in 4 scholar-months, against 1 in 22.
+        assert!(report.contains(
+            "High tier or above withdrew at 25.0 per 100 scholar-months, against 4.5 below it \
+             (5.5x as fast)."
+        ));
+    }
+
+    #[test]
+    fn charts_one_health_line_per_cohort() {
+        let point = |cohort: &str, day, health_index, scholars| CohortHealth {
+            cohort: cohort.to_string(),
+            week_start: NaiveDate::from_ymd_opt(2026, 9, day).unwrap(),
+            health_index,
+            scholars,
+            at_risk: 1,
+        };
+        let render = |health| {
+            build_report(
+                None,
+                30,
+                NaiveDate::from_ymd_opt(2026, 9, 1).unwrap(),
+                &[],
+                &[],
+                &ReportOptions {
+                    min_cell_size: Some(5),
+                    health,
+                    ..ReportOptions::default()
+                },
+            )
+        };
+
+        let report = render(Some(vec![
+            point("2025", 7, 90.0, 3),
+            point("2026", 7, 100.0, 12),
+            point("2026", 14, 80.0, 12),
+            point("2026", 21, 85.0, 12),
+        ]));
+        assert!(
+            report.contains(
+                "- 2026: █▇▇ 85 in the week of 2026-09-21 (-15 since 2026-09-07; low 80); \
+                 1 of 12 scholars at risk\n"
+            ),
+            "{report}"
+        );
+        assert!(report.contains("- 2025: <5 scholars (details suppressed)\n"));
+        assert!(render(Some(Vec::new())).contains("run `refresh-health` first"));
+        assert!(!render(None).contains("Health"));
+    }
+
+    #[test]
+    fn judges_exam_week_rises_against_the_calendar() {
+        let monday = |day| NaiveDate::from_ymd_opt(2026, 12, day).unwrap();
+        let week = |day, signal_count, scholar_count| SignalTrend {
+            week_start: monday(day),
+            signal_count,
+            avg_severity: 3.0,
+            scholar_count,
+        };
+        let calendar = AcademicCalendar {
+            exams: vec![crate::calendar::Period {
+                name: "Fall finals".to_string(),
+                start: monday(14),
+                end: monday(18),
+            }],
+            ..AcademicCalendar::default()
+        };
+        let report = build_report(
+            Some("2026"),
+            30,
+            monday(1),
+            &[],
+            &[week(7, 20, 10), week(14, 33, 12)],
+            &ReportOptions {
+                trend_alert_pct: Some(25.0),
+                calendar,
+                ..ReportOptions::default()
+            },
+        );
+
+        assert!(report.contains(
+            "- Week of 2026-12-14: 33 signals across 12 scholars (avg severity 3.00), \
+             exam week: Fall finals\n"
+        ));
+        // +65% raw, but only +10% over what finals week is expected to bring.
+        assert!(report.contains(
+            "  - Change: signals +13 (+65%; +10% seasonally adjusted), \
+             scholars +2 (+20%; -20% seasonally adjusted), avg severity +0.00 (+0%)\n"
+        ));
+        assert!(!report.contains("flagged"));
+    }
+
+    #[test]
+    fn pairs_signal_types_logged_close_together() {
+        let signal = |email: &str, signal_type: &str, days_ago| SignalRecord {
+            scholar_id: Uuid::from_u128(u128::from(email.as_bytes()[0])),
+            scholar_email: email.to_string(),
+            signal_type: signal_type.to_string(),
+            ..sample_signal(days_ago, 3)
+        };
+        let signals = vec![
+            signal("a@x.org", "attendance", 2),
+            signal("a@x.org", "financial", 5),
+            signal("a@x.org", "attendance", 20),
+            signal("b@x.org", "attendance", 3),
+            signal("b@x.org", "financial", 4),
+            // Too far apart for a 7-day window.
+            signal("c@x.org", "academic", 1),
+            signal("c@x.org", "financial", 25),
+        ];
+        let score = |email: &str, score| ScholarScore {
+            scholar_name: "Avery Lee".to_string(),
+            scholar_email: email.to_string(),
+            cohort: "2026".to_string(),
+            score,
+            signal_count: 2,
+            advisor: None,
+            tier: risk::Tier::Moderate,
+            escalated_by: None,
+            collapsed_signals: 0,
+            percentile: 0.0,
+            cohort_percentile: 0.0,
+            last_contact: None,
+            last_signal: None,
+        };
+        let scores = [score("a@x.org", 9.0), score("b@x.org", 5.0)];
+
+        let pairs = co_occurrences(&signals, 7, &scores);
+        assert_eq!(
+            pairs,
+            vec![SignalPair {
+                first: "attendance".to_string(),
+                second: "financial".to_string(),
+                scholar_count: 2,
+                avg_score: 7.0,
+            }]
+        );
+        assert_eq!(co_occurrences(&signals, 30, &scores).len(), 2);
+
+        let render = |co_occurrence_days| {
+            build_report(
+                Some("2026"),
+                30,
+                chrono::Utc::now().date_naive() - chrono::Duration::days(30),
+                &signals,
+                &[],
+                &ReportOptions {
+                    co_occurrence_days,
+                    ..ReportOptions::default()
+                },
+            )
+        };
+        let report = render(Some(7));
+        assert!(report.contains("within 7 days of each other"));
+        assert!(report.contains("; 100% of attendance scholars, 67% of financial scholars"));
+        assert!(!render(None).contains("## Signal Co-occurrence"));
+    }
+
+    #[test]
+    fn suppresses_cells_below_min_size() {
+        let signals = vec![sample_signal(2, 3), sample_signal(3, 4)];
+        let trends = vec![SignalTrend {
+            week_start: NaiveDate::from_ymd_opt(2026, 2, 2).unwrap(),
+            signal_count: 2,
+            avg_severity: 3.5,
+            scholar_count: 2,
+        }];
+        let report = build_report(
+            Some("2026"),
+            30,
+            NaiveDate::from_ymd_opt(2026, 1, 1).unwrap(),
+            &signals,
+            &trends,
+            &ReportOptions {
+                min_cell_size: Some(5),
+                ..ReportOptions::default()
+            },
+        );
+        assert!(report.contains("- attendance: <5 scholars (details suppressed)"));
+        assert!(report.contains("- Week of 2026-02-02: <5 scholars (details suppressed)"));
+        assert!(!report.contains("avg severity 3.50"));
+    }
+
+    #[test]
+    fn lists_interventions_with_their_status() {
+        let intervention = |outcome: Option<&str>, closed: bool| Intervention {
+            id: Uuid::from_u128(7),
+            scholar_name: "Avery Lee".to_string(),
+            scholar_email: "avery@example.org".to_string(),
+            occurred_on: NaiveDate::from_ymd_opt(2026, 2, 3).unwrap(),
+            intervention_type: "called family".to_string(),
+            outcome: outcome.map(str::to_string),
+            note: String::new(),
+            signal_keys: Vec::new(),
+            closed_at: closed.then(|| {
+                NaiveDate::from_ymd_opt(2026, 2, 10)
+                    .unwrap()
+                    .and_hms_opt(9, 0, 0)
+                    .unwrap()
+                    .and_utc()
+            }),
+        };
+        let report = build_report(
+            None,
+            30,
+            NaiveDate::from_ymd_opt(2026, 1, 15).unwrap(),
+            &[sample_signal(1, 3)],
+            &[],
+            &ReportOptions {
+                interventions: vec![
+                    intervention(Some("improved"), true),
+                    intervention(None, false),
+                ],
+                ..ReportOptions::default()
+            },
+        );
+        assert!(report.contains(
+            "## Interventions\n\
+             - 2026-02-03 called family for Avery Lee: improved (closed 2026-02-10)\n\
+             - 2026-02-03 called family for Avery Lee: pending (open)\n"
+        ));
+    }
+
+    #[test]
+    fn suggests_next_steps_from_matching_playbooks() {
+        let signals: Vec<SignalRecord> = [(1, 3), (4, 2), (9, 2)]
+            .into_iter()
+            .map(|(days_ago, severity)| SignalRecord {
+                scholar_id: Uuid::from_u128(1),
+                ..sample_signal(days_ago, severity)
+            })
+            .collect();
+        let playbook = |name: &str, min_count| Playbook {
+            name: name.to_string(),
+            action: format!("{name} step"),
+            signal_type: Some("attendance".to_string()),
+            min_count,
+            ..Playbook::default()
+        };
+        let render = |playbooks| {
+            build_report(
+                None,
+                30,
+                chrono::Utc::now().date_naive() - chrono::Duration::days(30),
+                &signals,
+                &[],
+                &ReportOptions {
+                    playbooks,
+                    ..ReportOptions::default()
+                },
+            )
+        };
+        let report = render(vec![playbook("family-call", 3), playbook("home-visit", 4)]);
+        assert!(report.contains(
+            "## Suggested Next Steps\n\
+             - Avery Lee: family-call step (3+ attendance signals within 14 days)\n\n"
+        ));
+        assert!(render(vec![playbook("home-visit", 4)])
+            .contains("## Suggested Next Steps\nNo playbook matches these scholars.\n"));
+        assert!(!render(Vec::new()).contains("## Suggested Next Steps"));
+    }
+
+    #[test]
+    fn lists_assigned_advisor_with_scholar() {
+        let signals = vec![SignalRecord {
+            advisor: Some("Jordan Kim".to_string()),
+            ..sample_signal(2, 3)
+        }];
+        let report = build_report(
+            Some("2026"),
+            30,
+            chrono::Utc::now().date_naive() - chrono::Duration::days(30),
+            &signals,
+            &[],
+            &ReportOptions::default(),
+        );
+        assert!(report.contains("- Avery Lee (avery@example.com, 2026, advisor Jordan Kim) score"));
+    }
+
+    #[test]
+    fn fixed_range_is_scored_as_of_its_last_day() {
+        let from = NaiveDate::from_ymd_opt(2025, 10, 1).unwrap();
+        let through = NaiveDate::from_ymd_opt(2025, 10, 31).unwrap();
+        let signals = vec![SignalRecord {
+            occurred_at: through - chrono::Duration::days(2),
+            ..sample_signal(0, 3)
+        }];
+        let report = build_report(
+            Some("2026"),
+            31,
+            from,
+            &signals,
+            &[],
+            &ReportOptions {
+                through: Some(through),
+                ..ReportOptions::default()
+            },
+        );
+        assert!(report.contains("(signals from 2025-10-01 to 2025-10-31)"));
+        // Two days before the range ends, so at full recency weight however long ago that is.
+        assert!(report.contains("score 3.00 across 1 signals"));
+    }
+
+    mod snapshots {
+        use super::*;
+
+        const TYPES: [&str; 4] = ["attendance", "engagement", "academic", "wellbeing"];
+
+        fn today() -> NaiveDate {
+            NaiveDate::from_ymd_opt(2026, 2, 2).unwrap()
+        }
+
+        fn signal(scholar: u128, days_ago: i64, signal_type: &str, severity: i32) -> SignalRecord {
+            SignalRecord {
+                scholar_id: Uuid::from_u128(scholar),
+                scholar_name: format!("Scholar {scholar:02}"),
+                scholar_email: format!("scholar{scholar:02}@example.org"),
+                cohort: "2026".to_string(),
+                signal_type: signal_type.to_string(),
+                severity,
+                occurred_at: today() - chrono::Duration::days(days_ago),
+                note: format!("{signal_type} concern logged {days_ago} days ago"),
+                advisor: None,
+                submitted_by: None,
+                attachments: Vec::new(),
+                note_categories: Vec::new(),
+                note_sentiment: None,
+            }
+        }
+
+        fn trend(
+            days_ago: i64,
+            signal_count: i64,
+            avg_severity: f64,
+            scholars: i64,
+        ) -> SignalTrend {
+            SignalTrend {
+                week_start: today() - chrono::Duration::days(days_ago),
+                signal_count,
+                avg_severity,
+                scholar_count: scholars,
+            }
+        }
+
+        fn render(signals: &[SignalRecord], trends: &[SignalTrend]) -> String {
+            build_report(
+                Some("2026"),
+                30,
+                today() - chrono::Duration::days(30),
+                signals,
+                trends,
+                &ReportOptions::default(),
+            )
+        }
+
+        #[test]
+        fn empty_window() {
+            insta::assert_snapshot!(render(&[], &[]));
+        }
+
+        #[test]
+        fn single_scholar() {
+            let signals = vec![
+                signal(1, 2, "attendance", 3),
+                signal(1, 9, "academic", 4),
+                signal(1, 20, "engagement", 2),
+            ];
+            let trends = vec![
+                trend(20, 1, 2.0, 1),
+                trend(9, 1, 4.0, 1),
+                trend(2, 1, 3.0, 1),
+            ];
+            insta::assert_snapshot!(render(&signals, &trends));
+        }
+
+        #[test]
+        fn program_week_labels() {
+            let signals = vec![signal(1, 2, "attendance", 3), signal(2, 9, "academic", 4)];
+            let trends = vec![trend(14, 1, 4.0, 1), trend(7, 1, 3.0, 1)];
+            let report = build_report(
+                Some("2026"),
+                30,
+                today() - chrono::Duration::days(30),
+                &signals,
+                &trends,
+                &ReportOptions {
+                    cohort_start: Some(NaiveDate::from_ymd_opt(2026, 1, 5).unwrap()),
+                    ..ReportOptions::default()
+                },
+            );
+            insta::assert_snapshot!(report);
+        }
+
+        #[test]
+        fn large_cohort() {
+            let signals: Vec<SignalRecord> = (1..=40u128)
+                .flat_map(|scholar| {
+                    (0..(scholar % 4 + 1)).map(move |index| {
+                        let signal_type = TYPES[((scholar + index) % 4) as usize];
+                        let severity = ((scholar * 7 + index * 3) % 5 + 1) as i32;
+                        let days_ago = ((scholar * 5 + index * 11) % 29) as i64;
+                        signal(scholar, days_ago, signal_type, severity)
+                    })
+                })
+                .collect();
+            let trends = vec![
+                trend(28, 18, 2.6, 14),
+                trend(21, 22, 3.1, 17),
+                trend(14, 25, 2.9, 19),
+                trend(7, 19, 3.3, 15),
+                trend(0, 16, 2.8, 12),
+            ];
+            insta::assert_snapshot!(render(&signals, &trends));
+        }
+    }
+}
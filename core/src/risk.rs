@@ -0,0 +1,1207 @@
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{Duration, NaiveDate, Utc};
+use uuid::Uuid;
+
+use crate::import::SeverityBounds;
+use crate::models::{ScholarScore, SignalRecord};
+
+/// Risk tiers in escalating order.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Default,
+    serde::Deserialize,
+    serde::Serialize,
+    clap::ValueEnum,
+)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum Tier {
+    #[default]
+    Low,
+    Moderate,
+    High,
+    Critical,
+}
+
+impl Tier {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Tier::Low => "low",
+            Tier::Moderate => "moderate",
+            Tier::High => "high",
+            Tier::Critical => "critical",
+        }
+    }
+}
+
+/// Minimum weighted score for each tier above `low`.
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TierThresholds {
+    pub moderate: f64,
+    pub high: f64,
+    pub critical: f64,
+}
+
+impl Default for TierThresholds {
+    fn default() -> Self {
+        Self {
+            moderate: 4.0,
+            high: 8.0,
+            critical: 12.0,
+        }
+    }
+}
+
+impl TierThresholds {
+    pub fn tier_for(&self, score: f64) -> Tier {
+        if score >= self.critical {
+            Tier::Critical
+        } else if score >= self.high {
+            Tier::High
+        } else if score >= self.moderate {
+            Tier::Moderate
+        } else {
+            Tier::Low
+        }
+    }
+}
+
+/// Lifts a scholar to at least `tier` when any single signal of `signal_type` reaches
+/// `min_severity`, however low their total score is.
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct Escalation {
+    pub signal_type: String,
+    pub min_severity: i32,
+    pub tier: Tier,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ScoringConfig {
+    pub tiers: TierThresholds,
+    pub escalations: Vec<Escalation>,
+    /// Collapse staff-entered signals of the same type for the same scholar that fall within this
+    /// many days of each other into one, keeping the highest severity. Off when unset.
+    pub staff_dedup_days: Option<i64>,
+    /// Weight multipliers from the categories and sentiment `enrich` stored on signal notes.
+    pub notes: NoteModifiers,
+}
+
+/// Multiplies a signal's weight by what its note says, as tagged by `enrich`.
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct NoteModifiers {
+    /// Factor by note category, e.g. `housing = 1.5`; a note in several categories takes the
+    /// largest.
+    pub categories: BTreeMap<String, f64>,
+    /// Factor for signals whose note sentiment is at or below `negative_below`.
+    pub negative_sentiment: f64,
+    pub negative_below: f64,
+}
+
+impl Default for NoteModifiers {
+    fn default() -> Self {
+        Self {
+            categories: BTreeMap::new(),
+            negative_sentiment: 1.0,
+            negative_below: -0.5,
+        }
+    }
+}
+
+impl NoteModifiers {
+    /// Whether any factor differs from 1, so scores depend on each signal's note.
+    pub fn is_active(&self) -> bool {
+        self.categories.values().any(|factor| *factor != 1.0) || self.negative_sentiment != 1.0
+    }
+
+    /// The multiplier for a signal with these note categories and sentiment.
+    pub fn factor(&self, categories: &[String], sentiment: Option<f64>) -> f64 {
+        let category = categories
+            .iter()
+            .filter_map(|category| self.categories.get(category))
+            .copied()
+            .reduce(f64::max)
+            .unwrap_or(1.0);
+        match sentiment {
+            Some(sentiment) if sentiment <= self.negative_below => {
+                category * self.negative_sentiment
+            }
+            _ => category,
+        }
+    }
+}
+
+impl ScoringConfig {
+    /// Checks the thresholds and factors, and that escalations can fire on a severity within
+    /// `severity`.
+    pub fn validate(&self, severity: SeverityBounds) -> anyhow::Result<()> {
+        let tiers = &self.tiers;
+        if !(0.0 <= tiers.moderate && tiers.moderate <= tiers.high && tiers.high <= tiers.critical)
+        {
+            anyhow::bail!("tier thresholds must satisfy 0 <= moderate <= high <= critical");
+        }
+        if self.staff_dedup_days.is_some_and(|days| days < 0) {
+            anyhow::bail!("staff_dedup_days must not be negative");
+        }
+        let notes = &self.notes;
+        if notes
+            .categories
+            .values()
+            .chain([&notes.negative_sentiment])
+            .any(|factor| !factor.is_finite() || *factor <= 0.0)
+        {
+            anyhow::bail!("[scoring.notes] factors must be positive");
+        }
+        if !(-1.0..=1.0).contains(&notes.negative_below) {
+            anyhow::bail!("[scoring.notes] negative_below must be between -1 and 1");
+        }
+        for escalation in &self.escalations {
+            if !severity.contains(escalation.min_severity) {
+                anyhow::bail!(
+                    "escalation for {} has min_severity {}; expected {severity}",
+                    escalation.signal_type,
+                    escalation.min_severity
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+pub fn score_signals(signals: &[SignalRecord], since_days: i64) -> Vec<ScholarScore> {
+    score_signals_as_of(signals, since_days, Utc::now().date_naive())
+}
+
+/// Scores the window ending on `today`, so historical reports can be reproduced exactly.
+pub fn score_signals_as_of(
+    signals: &[SignalRecord],
+    since_days: i64,
+    today: NaiveDate,
+) -> Vec<ScholarScore> {
+    score_signals_with(signals, since_days, today, &ScoringConfig::default())
+}
+
+/// Scores with configured tier thresholds and escalations.
+///
+/// Scholars are ranked by tier first, so an escalated scholar is never buried under higher raw
+/// scores.
+#[tracing::instrument(skip_all, fields(signals = signals.len()))]
+pub fn score_signals_with(
+    signals: &[SignalRecord],
+    since_days: i64,
+    today: NaiveDate,
+    config: &ScoringConfig,
+) -> Vec<ScholarScore> {
+    let mut accumulator = ScoreAccumulator::new(since_days, today, config);
+    for signal in signals {
+        accumulator.push(signal);
+    }
+    accumulator.finish()
+}
+
+/// A staff signal held back for collapsing: when it occurred, its severity, and its note factor.
+type StaffSignal = (NaiveDate, i32, f64);
+
+/// Folds signals into per-scholar scores one at a time, so callers can stream rows instead of
+/// holding the whole window in memory.
+///
+/// With a staff dedup window, staff-entered signals are held back until [`Self::finish`] so they
+/// can be collapsed regardless of arrival order.
+pub struct ScoreAccumulator<'a> {
+    cutoff: NaiveDate,
+    today: NaiveDate,
+    config: &'a ScoringConfig,
+    scores: HashMap<Uuid, ScholarScore>,
+    staff_signals: HashMap<(Uuid, String), Vec<StaffSignal>>,
+}
+
+impl<'a> ScoreAccumulator<'a> {
+    pub fn new(since_days: i64, today: NaiveDate, config: &'a ScoringConfig) -> Self {
+        Self {
+            cutoff: today - Duration::days(since_days.max(1)),
+            today,
+            config,
+            scores: HashMap::new(),
+            staff_signals: HashMap::new(),
+        }
+    }
+
+    /// Adds `signal` to its scholar's score, unless it falls outside the window ending on `today`.
+    pub fn push(&mut self, signal: &SignalRecord) {
+        if signal.occurred_at < self.cutoff || signal.occurred_at > self.today {
+            return;
+        }
+
+        let entry = self
+            .scores
+            .entry(signal.scholar_id)
+            .or_insert_with(|| ScholarScore {
+                scholar_name: signal.scholar_name.clone(),
+                scholar_email: signal.scholar_email.clone(),
+                cohort: signal.cohort.clone(),
+                score: 0.0,
+                signal_count: 0,
+                advisor: signal.advisor.clone(),
+                tier: Tier::Low,
+                escalated_by: None,
+                collapsed_signals: 0,
+                percentile: 0.0,
+                cohort_percentile: 0.0,
+                last_contact: None,
+                last_signal: None,
+            });
+        entry.last_signal = entry.last_signal.max(Some(signal.occurred_at));
+
+        for escalation in &self.config.escalations {
+            if signal.signal_type == escalation.signal_type
+                && signal.severity >= escalation.min_severity
+                && escalation.tier > entry.tier
+            {
+                entry.tier = escalation.tier;
+                entry.escalated_by = Some(format!(
+                    "{} severity {}",
+                    signal.signal_type, signal.severity
+                ));
+            }
+        }
+
+        let factor = self
+            .config
+            .notes
+            .factor(&signal.note_categories, signal.note_sentiment);
+        if self.config.staff_dedup_days.is_some() && signal.submitted_by.is_some() {
+            self.staff_signals
+                .entry((signal.scholar_id, signal.signal_type.clone()))
+                .or_default()
+                .push((signal.occurred_at, signal.severity, factor));
+            return;
+        }
+
+        let days_ago = (self.today - signal.occurred_at).num_days();
+        entry.score += (signal.severity as f64) * recency_weight(days_ago) * factor;
+        entry.signal_count += 1;
+    }
+
+    /// Applies score thresholds and returns scholars ranked by tier, then score, with percentiles
+    /// among the scholars scored.
+    pub fn finish(mut self) -> Vec<ScholarScore> {
+        let window_days = self.config.staff_dedup_days.unwrap_or(0);
+        for ((scholar_id, _), mut group) in std::mem::take(&mut self.staff_signals) {
+            group.sort_unstable_by_key(|&(occurred_at, severity, _)| (occurred_at, severity));
+            let kept = collapse_window(&group, window_days);
+            let entry = self
+                .scores
+                .get_mut(&scholar_id)
+                .expect("staff signals are only held for scored scholars");
+            for (occurred_at, severity, factor) in &kept {
+                let days_ago = (self.today - *occurred_at).num_days();
+                entry.score += (*severity as f64) * recency_weight(days_ago) * factor;
+            }
+            entry.signal_count += kept.len();
+            entry.collapsed_signals += group.len() - kept.len();
+        }
+
+        rank(self.scores.into_values().collect(), self.config)
+    }
+}
+
+/// One scholar's signals of one type, summed by the database rather than streamed row by row.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeAggregate {
+    pub scholar_id: Uuid,
+    pub scholar_name: String,
+    pub scholar_email: String,
+    pub cohort: String,
+    pub advisor: Option<String>,
+    pub signal_type: String,
+    /// Severity times [`recency_weight`], summed over the type's signals.
+    pub weighted_severity: f64,
+    pub signal_count: usize,
+    pub max_severity: i32,
+    pub last_signal: NaiveDate,
+}
+
+/// Scores from per-type aggregates, matching [`ScoreAccumulator`] over the same signals. An
+/// escalation names the type's highest severity rather than the signal that first crossed it.
+///
+/// Staff dedup and note modifiers need individual signals, so configs with `staff_dedup_days` or
+/// active `[scoring.notes]` factors are rejected.
+pub fn score_aggregates(
+    aggregates: &[TypeAggregate],
+    config: &ScoringConfig,
+) -> anyhow::Result<Vec<ScholarScore>> {
+    if config.staff_dedup_days.is_some() {
+        anyhow::bail!(
+            "staff_dedup_days needs every signal; score without aggregating in the database"
+        );
+    }
+    if config.notes.is_active() {
+        anyhow::bail!(
+            "[scoring.notes] needs every signal; score without aggregating in the database"
+        );
+    }
+    let mut scores: HashMap<Uuid, ScholarScore> = HashMap::new();
+    for aggregate in aggregates {
+        let entry = scores
+            .entry(aggregate.scholar_id)
+            .or_insert_with(|| ScholarScore {
+                scholar_name: aggregate.scholar_name.clone(),
+                scholar_email: aggregate.scholar_email.clone(),
+                cohort: aggregate.cohort.clone(),
+                score: 0.0,
+                signal_count: 0,
+                advisor: aggregate.advisor.clone(),
+                tier: Tier::Low,
+                escalated_by: None,
+                collapsed_signals: 0,
+                percentile: 0.0,
+                cohort_percentile: 0.0,
+                last_contact: None,
+                last_signal: None,
+            });
+        entry.score += aggregate.weighted_severity;
+        entry.signal_count += aggregate.signal_count;
+        entry.last_signal = entry.last_signal.max(Some(aggregate.last_signal));
+        for escalation in &config.escalations {
+            if aggregate.signal_type == escalation.signal_type
+                && aggregate.max_severity >= escalation.min_severity
+                && escalation.tier > entry.tier
+            {
+                entry.tier = escalation.tier;
+                entry.escalated_by = Some(format!(
+                    "{} severity {}",
+                    aggregate.signal_type, aggregate.max_severity
+                ));
+            }
+        }
+    }
+    Ok(rank(scores.into_values().collect(), config))
+}
+
+/// Applies score thresholds and ranks scholars by tier, then score, with percentiles among them.
+fn rank(mut values: Vec<ScholarScore>, config: &ScoringConfig) -> Vec<ScholarScore> {
+    for value in values.iter_mut() {
+        // Weighted severities are decimals, so the order they were summed in can leave equal
+        // totals, or a total on a tier threshold, a few bits apart. Rounding lets them tie.
+        value.score = (value.score * 1e6).round() / 1e6;
+        let by_score = config.tiers.tier_for(value.score);
+        if by_score >= value.tier {
+            value.tier = by_score;
+            value.escalated_by = None;
+        }
+    }
+    values.sort_by(|a, b| {
+        b.tier
+            .cmp(&a.tier)
+            .then_with(|| {
+                b.score
+                    .partial_cmp(&a.score)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })
+            .then_with(|| a.scholar_email.cmp(&b.scholar_email))
+    });
+    let population = values.clone();
+    assign_percentiles(&mut values, &population);
+    values
+}
+
+/// Orders `score --sort` can list scholars in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum ScoreSort {
+    /// Tier, then score
+    #[default]
+    Score,
+    /// Number of signals scored
+    Signals,
+    /// Day of the latest signal
+    Recency,
+    /// Scholar name
+    Name,
+}
+
+impl ScoreSort {
+    /// What listings say scholars are ordered by, e.g. "Top scholars by latest signal".
+    pub fn label(self) -> &'static str {
+        match self {
+            ScoreSort::Score => "risk score",
+            ScoreSort::Signals => "signal count",
+            ScoreSort::Recency => "latest signal",
+            ScoreSort::Name => "name",
+        }
+    }
+
+    /// Names read A to Z; everything else lists the highest first.
+    pub fn descending_by_default(self) -> bool {
+        self != ScoreSort::Name
+    }
+}
+
+/// Reorders scores by `key`. Scholars tied on the key keep their rank order.
+pub fn sort_scores(scores: &mut [ScholarScore], key: ScoreSort, descending: bool) {
+    scores.sort_by(|a, b| {
+        let order = match key {
+            ScoreSort::Score => a.tier.cmp(&b.tier).then(a.score.total_cmp(&b.score)),
+            ScoreSort::Signals => a.signal_count.cmp(&b.signal_count),
+            ScoreSort::Recency => a.last_signal.cmp(&b.last_signal),
+            ScoreSort::Name => a
+                .scholar_name
+                .to_lowercase()
+                .cmp(&b.scholar_name.to_lowercase()),
+        };
+        if descending {
+            order.reverse()
+        } else {
+            order
+        }
+    });
+}
+
+/// Short, medium, and long windows `score --windows` shows side by side.
+pub const DEFAULT_WINDOWS: [i64; 3] = [7, 30, 90];
+
+/// A scholar's score and tier over one window of a [`MultiWindowAccumulator`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WindowScore {
+    pub days: i64,
+    pub score: f64,
+    pub tier: Tier,
+}
+
+/// Scores several windows ending on the same day in one pass over the signals, so an acute
+/// flare-up (a high short-window score) can be told from chronic disengagement (a long-window
+/// score the short window no longer sees).
+pub struct MultiWindowAccumulator<'a> {
+    windows: Vec<(i64, ScoreAccumulator<'a>)>,
+}
+
+impl<'a> MultiWindowAccumulator<'a> {
+    pub fn new(windows: &[i64], today: NaiveDate, config: &'a ScoringConfig) -> Self {
+        Self {
+            windows: windows
+                .iter()
+                .map(|&days| (days, ScoreAccumulator::new(days, today, config)))
+                .collect(),
+        }
+    }
+
+    pub fn push(&mut self, signal: &SignalRecord) {
+        for (_, accumulator) in &mut self.windows {
+            accumulator.push(signal);
+        }
+    }
+
+    /// Every window's score by scholar email, in the order the windows were given. Scholars
+    /// without signals in a window score zero there.
+    pub fn finish(self) -> HashMap<String, Vec<WindowScore>> {
+        let empty: Vec<WindowScore> = self
+            .windows
+            .iter()
+            .map(|(days, _)| WindowScore {
+                days: *days,
+                score: 0.0,
+                tier: Tier::Low,
+            })
+            .collect();
+        let mut by_email: HashMap<String, Vec<WindowScore>> = HashMap::new();
+        for (index, (_, accumulator)) in self.windows.into_iter().enumerate() {
+            for score in accumulator.finish() {
+                let window = &mut by_email
+                    .entry(score.scholar_email)
+                    .or_insert_with(|| empty.clone())[index];
+                window.score = score.score;
+                window.tier = score.tier;
+            }
+        }
+        by_email
+    }
+}
+
+/// Scores side by side for listings, e.g. "7d 0.00 low, 30d 9.40 high, 90d 14.20 critical".
+pub fn windows_label(windows: &[WindowScore]) -> String {
+    windows
+        .iter()
+        .map(|window| {
+            format!(
+                "{}d {:.2} {}",
+                window.days,
+                window.score,
+                window.tier.as_str()
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Sets each score's percentiles against `population`, overall and within its cohort, so a
+/// scoped listing can still be ranked against everyone.
+pub fn assign_percentiles(scores: &mut [ScholarScore], population: &[ScholarScore]) {
+    let mut overall: Vec<f64> = Vec::with_capacity(population.len());
+    let mut by_cohort: HashMap<&str, Vec<f64>> = HashMap::new();
+    for score in population {
+        overall.push(score.score);
+        by_cohort
+            .entry(&score.cohort)
+            .or_default()
+            .push(score.score);
+    }
+    overall.sort_by(f64::total_cmp);
+    for cohort in by_cohort.values_mut() {
+        cohort.sort_by(f64::total_cmp);
+    }
+
+    let rank = |sorted: &[f64], value: f64| match sorted.len() {
+        0 => 100.0,
+        len => sorted.partition_point(|score| *score <= value) as f64 * 100.0 / len as f64,
+    };
+    for score in scores {
+        score.percentile = rank(&overall, score.score);
+        score.cohort_percentile = rank(
+            by_cohort
+                .get(score.cohort.as_str())
+                .map_or(&[], Vec::as_slice),
+            score.score,
+        );
+    }
+}
+
+/// Groups date-sorted `(occurred_at, severity)` pairs into runs that start at a signal and span
+/// `window_days` after it, keeping the most severe (then latest) signal of each run.
+fn collapse_window(signals: &[StaffSignal], window_days: i64) -> Vec<StaffSignal> {
+    let mut kept: Vec<StaffSignal> = Vec::new();
+    let mut run_start: Option<NaiveDate> = None;
+    for &(occurred_at, severity, factor) in signals {
+        match (run_start, kept.last_mut()) {
+            (Some(start), Some(last)) if (occurred_at - start).num_days() <= window_days => {
+                if severity >= last.1 {
+                    *last = (occurred_at, severity, factor);
+                }
+            }
+            _ => {
+                run_start = Some(occurred_at);
+                kept.push((occurred_at, severity, factor));
+            }
+        }
+    }
+    kept
+}
+
+/// Note for listings when the staff dedup window folded signals together.
+pub fn collapse_note(score: &ScholarScore) -> String {
+    match score.collapsed_signals {
+        0 => String::new(),
+        1 => " (1 duplicate staff signal collapsed)".to_string(),
+        n => format!(" ({n} duplicate staff signals collapsed)"),
+    }
+}
+
+/// Percentile ranks for listings, e.g. "p82 in 2026, p64 overall".
+pub fn percentile_label(score: &ScholarScore) -> String {
+    format!(
+        "p{:.0} in {}, p{:.0} overall",
+        score.cohort_percentile, score.cohort, score.percentile
+    )
+}
+
+/// How long since the scholar was last reached, as of `today`, for listings.
+pub fn contact_label(score: &ScholarScore, today: NaiveDate) -> String {
+    match score.last_contact.map(|date| (today - date).num_days()) {
+        None => "no contact recorded".to_string(),
+        Some(0) => "last contact today".to_string(),
+        Some(1) => "last contact 1 day ago".to_string(),
+        Some(days) => format!("last contact {days} days ago"),
+    }
+}
+
+/// Bracketed tier for listings, naming the escalating signal when there was one.
+pub fn tier_label(score: &ScholarScore) -> String {
+    match &score.escalated_by {
+        Some(reason) => format!("[{}: {}]", score.tier.as_str(), reason),
+        None => format!("[{}]", score.tier.as_str()),
+    }
+}
+
+/// Weights by signal age in days; older signals, and any dated in the future, weigh
+/// [`OLDER_WEIGHT`].
+const RECENCY_WEIGHTS: [(i64, i64, f64); 3] = [(0, 7, 1.0), (8, 30, 0.7), (31, 60, 0.4)];
+const OLDER_WEIGHT: f64 = 0.2;
+
+pub fn recency_weight(days_ago: i64) -> f64 {
+    RECENCY_WEIGHTS
+        .iter()
+        .find(|(from, to, _)| (*from..=*to).contains(&days_ago))
+        .map_or(OLDER_WEIGHT, |(_, _, weight)| *weight)
+}
+
+/// [`recency_weight`] as a SQL expression over `days_ago`, an integer expression.
+pub fn recency_weight_sql(days_ago: &str) -> String {
+    let cases: String = RECENCY_WEIGHTS
+        .iter()
+        .map(|(from, to, weight)| {
+            format!(" WHEN {days_ago} BETWEEN {from} AND {to} THEN {weight:?}")
+        })
+        .collect();
+    format!("(CASE{cases} ELSE {OLDER_WEIGHT:?} END)")
+}
+
+pub fn cutoff_date(since_days: i64) -> NaiveDate {
+    Utc::now().date_naive() - Duration::days(since_days.max(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_signal(days_ago: i64, severity: i32) -> SignalRecord {
+        let occurred_at = Utc::now().date_naive() - Duration::days(days_ago);
+        SignalRecord {
+            scholar_id: Uuid::new_v4(),
+            scholar_name: "Avery Lee".to_string(),
+            scholar_email: "avery@example.com".to_string(),
+            cohort: "2026".to_string(),
+            signal_type: "attendance".to_string(),
+            severity,
+            occurred_at,
+            note: "missed session".to_string(),
+            advisor: None,
+            submitted_by: None,
+            attachments: Vec::new(),
+            note_categories: Vec::new(),
+            note_sentiment: None,
+        }
+    }
+
+    #[test]
+    fn weights_follow_expected_tiers() {
+        assert_eq!(recency_weight(2), 1.0);
+        assert_eq!(recency_weight(15), 0.7);
+        assert_eq!(recency_weight(40), 0.4);
+        assert_eq!(recency_weight(90), 0.2);
+    }
+
+    #[test]
+    fn scores_accumulate_by_scholar() {
+        let scholar_id = Uuid::new_v4();
+        let signals = vec![
+            SignalRecord {
+                scholar_id,
+                scholar_name: "Avery Lee".to_string(),
+                scholar_email: "avery@example.com".to_string(),
+                cohort: "2026".to_string(),
+                signal_type: "attendance".to_string(),
+                severity: 3,
+                occurred_at: Utc::now().date_naive() - Duration::days(3),
+                note: "missed session".to_string(),
+                advisor: None,
+                submitted_by: None,
+                attachments: Vec::new(),
+                note_categories: Vec::new(),
+                note_sentiment: None,
+            },
+            SignalRecord {
+                scholar_id,
+                scholar_name: "Avery Lee".to_string(),
+                scholar_email: "avery@example.com".to_string(),
+                cohort: "2026".to_string(),
+                signal_type: "engagement".to_string(),
+                severity: 2,
+                occurred_at: Utc::now().date_naive() - Duration::days(12),
+                note: "no response".to_string(),
+                advisor: None,
+                submitted_by: None,
+                attachments: Vec::new(),
+                note_categories: Vec::new(),
+                note_sentiment: None,
+            },
+        ];
+
+        let scores = score_signals(&signals, 30);
+        assert_eq!(scores.len(), 1);
+        let score = &scores[0];
+        let expected = 3.0 * 1.0 + 2.0 * 0.7;
+        assert!((score.score - expected).abs() < 0.001);
+        assert_eq!(score.signal_count, 2);
+        assert_eq!(
+            score.last_signal,
+            Some(Utc::now().date_naive() - Duration::days(3))
+        );
+    }
+
+    #[test]
+    fn sorts_scores_by_the_requested_key() {
+        let signal = |name: &str, days_ago, severity| SignalRecord {
+            scholar_id: Uuid::from_u128(u128::from(name.as_bytes()[0])),
+            scholar_name: name.to_string(),
+            scholar_email: format!("{}@x.org", name.to_lowercase()),
+            ..sample_signal(days_ago, severity)
+        };
+        let signals = [
+            signal("Avery", 20, 5),
+            signal("Blake", 1, 2),
+            signal("Casey", 10, 5),
+            signal("Casey", 12, 1),
+        ];
+        let mut scores = score_signals(&signals, 30);
+        let names = |scores: &[ScholarScore]| {
+            scores
+                .iter()
+                .map(|score| score.scholar_name.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names(&scores), ["Casey", "Avery", "Blake"]);
+
+        sort_scores(&mut scores, ScoreSort::Recency, true);
+        assert_eq!(names(&scores), ["Blake", "Casey", "Avery"]);
+        sort_scores(&mut scores, ScoreSort::Signals, true);
+        assert_eq!(names(&scores), ["Casey", "Blake", "Avery"]);
+        sort_scores(&mut scores, ScoreSort::Name, false);
+        assert_eq!(names(&scores), ["Avery", "Blake", "Casey"]);
+        sort_scores(&mut scores, ScoreSort::Score, false);
+        assert_eq!(names(&scores), ["Blake", "Avery", "Casey"]);
+    }
+
+    #[test]
+    fn tiers_follow_score_thresholds() {
+        let tiers = TierThresholds::default();
+        assert_eq!(tiers.tier_for(0.0), Tier::Low);
+        assert_eq!(tiers.tier_for(4.0), Tier::Moderate);
+        assert_eq!(tiers.tier_for(9.5), Tier::High);
+        assert_eq!(tiers.tier_for(12.0), Tier::Critical);
+    }
+
+    #[test]
+    fn single_severe_signal_escalates_past_higher_scores() {
+        let config = ScoringConfig {
+            escalations: vec![Escalation {
+                signal_type: "wellbeing".to_string(),
+                min_severity: 4,
+                tier: Tier::Critical,
+            }],
+            ..ScoringConfig::default()
+        };
+        let mut busy = vec![sample_signal(1, 5), sample_signal(2, 5)];
+        for signal in busy.iter_mut() {
+            signal.scholar_id = Uuid::from_u128(1);
+            signal.scholar_email = "busy@example.com".to_string();
+        }
+        let wellbeing = SignalRecord {
+            scholar_id: Uuid::from_u128(2),
+            scholar_email: "quiet@example.com".to_string(),
+            signal_type: "wellbeing".to_string(),
+            ..sample_signal(20, 4)
+        };
+        busy.push(wellbeing);
+
+        let today = Utc::now().date_naive();
+        let scores = score_signals_with(&busy, 30, today, &config);
+        assert_eq!(scores[0].scholar_email, "quiet@example.com");
+        assert_eq!(scores[0].tier, Tier::Critical);
+        assert_eq!(
+            scores[0].escalated_by.as_deref(),
+            Some("wellbeing severity 4")
+        );
+        assert_eq!(scores[1].tier, Tier::High);
+        assert!(scores[1].escalated_by.is_none());
+
+        let unescalated = score_signals_as_of(&busy, 30, today);
+        assert_eq!(unescalated[0].scholar_email, "busy@example.com");
+    }
+
+    #[test]
+    fn rejects_unordered_thresholds() {
+        let config = ScoringConfig {
+            tiers: TierThresholds {
+                moderate: 10.0,
+                high: 5.0,
+                critical: 20.0,
+            },
+            ..ScoringConfig::default()
+        };
+        assert!(config.validate(SeverityBounds::default()).is_err());
+        assert!(ScoringConfig::default()
+            .validate(SeverityBounds::default())
+            .is_ok());
+    }
+
+    #[test]
+    fn escalations_must_fire_within_the_severity_bounds() {
+        let config = |min_severity| ScoringConfig {
+            escalations: vec![Escalation {
+                signal_type: "wellbeing".to_string(),
+                min_severity,
+                tier: Tier::Critical,
+            }],
+            ..ScoringConfig::default()
+        };
+        let wide = SeverityBounds { min: 1, max: 10 };
+        assert!(config(8).validate(wide).is_ok());
+        assert!(config(8).validate(SeverityBounds::default()).is_err());
+
+        let narrow = SeverityBounds { min: 1, max: 3 };
+        let err = config(4).validate(narrow).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "escalation for wellbeing has min_severity 4; expected 1-3"
+        );
+        assert!(config(3).validate(narrow).is_ok());
+    }
+
+    #[test]
+    fn signals_after_the_as_of_date_are_not_scored() {
+        let as_of = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
+        let later = SignalRecord {
+            occurred_at: NaiveDate::from_ymd_opt(2026, 3, 1).unwrap(),
+            ..sample_signal(0, 3)
+        };
+        let earlier = SignalRecord {
+            occurred_at: as_of - Duration::days(2),
+            ..sample_signal(0, 3)
+        };
+
+        assert!(score_signals_as_of(std::slice::from_ref(&later), 30, as_of).is_empty());
+        let scores = score_signals_as_of(&[earlier, later], 30, as_of);
+        assert_eq!(scores.len(), 1);
+        assert_eq!(scores[0].signal_count, 1);
+    }
+
+    #[test]
+    fn cutoff_date_respects_since_days() {
+        let cutoff = cutoff_date(14);
+        let expected = Utc::now().date_naive() - Duration::days(14);
+        assert_eq!(cutoff, expected);
+    }
+
+    #[test]
+    fn equal_totals_tie_whatever_the_summation_order() {
+        let signal = |id: u128, days_ago: i64, severity: i32| SignalRecord {
+            scholar_id: Uuid::from_u128(id),
+            scholar_email: format!("scholar-{id}@example.com"),
+            ..sample_signal(days_ago, severity)
+        };
+        // 0.7 + 0.4 + 1.2 + 0.2 and 0.7 + 0.4 + 0.2 + 1.2 differ in the last bit as f64.
+        let signals = vec![
+            signal(2, 15, 1),
+            signal(2, 40, 1),
+            signal(2, 40, 3),
+            signal(2, 90, 1),
+            signal(1, 15, 1),
+            signal(1, 40, 1),
+            signal(1, 90, 1),
+            signal(1, 40, 3),
+        ];
+        let config = ScoringConfig {
+            tiers: TierThresholds {
+                moderate: 2.5,
+                ..TierThresholds::default()
+            },
+            ..ScoringConfig::default()
+        };
+        let scores = score_signals_with(&signals, 365, Utc::now().date_naive(), &config);
+        assert_eq!(scores[0].score, scores[1].score);
+        assert_eq!(scores[0].tier, Tier::Moderate);
+        assert_eq!(scores[1].tier, Tier::Moderate);
+        assert_eq!(scores[1].percentile, 100.0);
+        assert_eq!(scores[0].scholar_email, "scholar-1@example.com");
+    }
+
+    #[test]
+    fn ignores_signals_outside_window() {
+        let signals = vec![sample_signal(2, 2), sample_signal(90, 5)];
+        let scores = score_signals(&signals, 30);
+        assert_eq!(scores.len(), 1);
+        assert_eq!(scores[0].signal_count, 1);
+    }
+
+    #[test]
+    fn ranks_percentiles_overall_and_within_cohort() {
+        let signal = |id: u128, cohort: &str, severity: i32| SignalRecord {
+            scholar_id: Uuid::from_u128(id),
+            scholar_email: format!("scholar-{id}@example.com"),
+            cohort: cohort.to_string(),
+            ..sample_signal(1, severity)
+        };
+        let signals = vec![
+            signal(1, "2025", 5),
+            signal(2, "2025", 2),
+            signal(3, "2026", 4),
+            signal(4, "2026", 2),
+        ];
+        let scores = score_signals(&signals, 30);
+        let percentiles = |email: &str| {
+            let score = scores.iter().find(|s| s.scholar_email == email).unwrap();
+            (score.percentile, score.cohort_percentile)
+        };
+        assert_eq!(percentiles("scholar-1@example.com"), (100.0, 100.0));
+        assert_eq!(percentiles("scholar-3@example.com"), (75.0, 100.0));
+        // Ties share the higher rank.
+        assert_eq!(percentiles("scholar-2@example.com"), (50.0, 50.0));
+        assert_eq!(percentiles("scholar-4@example.com"), (50.0, 50.0));
+
+        // A scoped listing keeps its place in the wider population.
+        let mut scoped = score_signals(&signals[2..], 30);
+        assert_eq!(scoped[0].percentile, 100.0);
+        assign_percentiles(&mut scoped, &scores);
+        assert_eq!(scoped[0].percentile, 75.0);
+        assert_eq!(percentile_label(&scoped[0]), "p100 in 2026, p75 overall");
+    }
+
+    #[test]
+    fn scores_every_window_in_one_pass() {
+        let signal = |email: &str, days_ago: i64| SignalRecord {
+            scholar_id: Uuid::from_u128(u128::from(email.as_bytes()[0])),
+            scholar_email: email.to_string(),
+            ..sample_signal(days_ago, 5)
+        };
+        // An acute flare-up this week, and steady trouble that has since gone quiet.
+        let signals = [
+            signal("acute@example.com", 1),
+            signal("acute@example.com", 2),
+            signal("chronic@example.com", 40),
+            signal("chronic@example.com", 50),
+            signal("chronic@example.com", 70),
+        ];
+        let config = ScoringConfig::default();
+        let mut accumulator =
+            MultiWindowAccumulator::new(&DEFAULT_WINDOWS, Utc::now().date_naive(), &config);
+        for signal in &signals {
+            accumulator.push(signal);
+        }
+        let windows = accumulator.finish();
+
+        assert_eq!(
+            windows_label(&windows["acute@example.com"]),
+            "7d 10.00 high, 30d 10.00 high, 90d 10.00 high"
+        );
+        assert_eq!(
+            windows_label(&windows["chronic@example.com"]),
+            "7d 0.00 low, 30d 0.00 low, 90d 5.00 moderate"
+        );
+    }
+
+    #[test]
+    fn staff_dedup_window_keeps_the_most_severe_signal() {
+        let scholar_id = Uuid::from_u128(1);
+        let staff = |days_ago: i64, severity: i32, signal_type: &str| SignalRecord {
+            scholar_id,
+            signal_type: signal_type.to_string(),
+            submitted_by: Some("jordan@groupscholar.com".to_string()),
+            ..sample_signal(days_ago, severity)
+        };
+        let signals = vec![
+            staff(3, 4, "attendance"),
+            staff(2, 2, "attendance"),
+            staff(20, 3, "attendance"),
+            staff(2, 1, "academic"),
+            SignalRecord {
+                scholar_id,
+                ..sample_signal(2, 2)
+            },
+        ];
+        let config = ScoringConfig {
+            staff_dedup_days: Some(2),
+            ..ScoringConfig::default()
+        };
+
+        let today = Utc::now().date_naive();
+        let scores = score_signals_with(&signals, 30, today, &config);
+        assert_eq!(scores[0].signal_count, 4);
+        assert_eq!(scores[0].collapsed_signals, 1);
+        assert!((scores[0].score - (4.0 + 3.0 * 0.7 + 1.0 + 2.0)).abs() < 0.001);
+
+        let undeduped = score_signals_with(&signals, 30, today, &ScoringConfig::default());
+        assert_eq!(undeduped[0].signal_count, 5);
+        assert_eq!(undeduped[0].collapsed_signals, 0);
+    }
+
+    #[test]
+    fn note_modifiers_weigh_categories_and_negative_sentiment() {
+        let scholar_id = Uuid::new_v4();
+        let signal = |categories: &[&str], sentiment: Option<f64>| SignalRecord {
+            scholar_id,
+            note_categories: categories.iter().map(|c| c.to_string()).collect(),
+            note_sentiment: sentiment,
+            ..sample_signal(1, 2)
+        };
+        let signals = [
+            signal(&["housing", "health"], Some(0.0)),
+            signal(&["financial"], Some(-1.0)),
+            signal(&[], None),
+        ];
+        let config = ScoringConfig {
+            notes: NoteModifiers {
+                categories: BTreeMap::from([
+                    ("housing".to_string(), 1.5),
+                    ("health".to_string(), 1.25),
+                ]),
+                negative_sentiment: 2.0,
+                ..NoteModifiers::default()
+            },
+            ..ScoringConfig::default()
+        };
+        assert!(config.notes.is_active());
+
+        let today = Utc::now().date_naive();
+        let scores = score_signals_with(&signals, 30, today, &config);
+        assert!((scores[0].score - (2.0 * 1.5 + 2.0 * 2.0 + 2.0)).abs() < 0.001);
+        assert!(!ScoringConfig::default().notes.is_active());
+    }
+
+    mod properties {
+        use std::collections::BTreeMap;
+
+        use super::*;
+        use proptest::prelude::*;
+
+        const SCHOLARS: [u128; 4] = [1, 2, 3, 4];
+
+        fn signals_strategy() -> impl Strategy<Value = Vec<SignalRecord>> {
+            proptest::collection::vec((0..SCHOLARS.len(), 1..=5i32, 0..90i64), 0..40).prop_map(
+                |specs| {
+                    specs
+                        .into_iter()
+                        .map(|(scholar, severity, days_ago)| SignalRecord {
+                            scholar_id: Uuid::from_u128(SCHOLARS[scholar]),
+                            scholar_email: format!("scholar-{scholar}@example.com"),
+                            ..sample_signal(days_ago, severity)
+                        })
+                        .collect()
+                },
+            )
+        }
+
+        fn scores_by_email(signals: &[SignalRecord]) -> Vec<(String, f64, usize)> {
+            let mut scores: Vec<(String, f64, usize)> = score_signals(signals, 60)
+                .into_iter()
+                .map(|score| (score.scholar_email, score.score, score.signal_count))
+                .collect();
+            scores.sort_by(|a, b| a.0.cmp(&b.0));
+            scores
+        }
+
+        proptest! {
+            #[test]
+            fn streaming_matches_a_plain_sum(signals in signals_strategy()) {
+                let today = Utc::now().date_naive();
+                let config = ScoringConfig::default();
+                let mut accumulator = ScoreAccumulator::new(60, today, &config);
+                for signal in &signals {
+                    accumulator.push(signal);
+                }
+                let mut streamed: Vec<(String, f64, usize)> = accumulator
+                    .finish()
+                    .into_iter()
+                    .map(|score| (score.scholar_email, score.score, score.signal_count))
+                    .collect();
+                streamed.sort_by(|a, b| a.0.cmp(&b.0));
+
+                // Severity times recency weight, summed per scholar over the last 60 days.
+                let mut expected: BTreeMap<String, (f64, usize)> = BTreeMap::new();
+                for signal in &signals {
+                    let days_ago = (today - signal.occurred_at).num_days();
+                    if (0..=60).contains(&days_ago) {
+                        let entry = expected.entry(signal.scholar_email.clone()).or_default();
+                        entry.0 += signal.severity as f64 * recency_weight(days_ago);
+                        entry.1 += 1;
+                    }
+                }
+                prop_assert_eq!(streamed.len(), expected.len());
+                for ((email, score, count), (expected_email, (expected_score, expected_count))) in
+                    streamed.iter().zip(&expected)
+                {
+                    prop_assert_eq!(email, expected_email);
+                    prop_assert!((score - expected_score).abs() < 1e-9);
+                    prop_assert_eq!(count, expected_count);
+                }
+            }
+
+            #[test]
+            fn aggregates_match_streaming_scoring(signals in signals_strategy()) {
+                let today = Utc::now().date_naive();
+                let config = ScoringConfig::default();
+                // What the database sums per scholar and type over the window.
+                let mut aggregates: BTreeMap<(Uuid, String), TypeAggregate> = BTreeMap::new();
+                for signal in signals.iter().filter(|s| (today - s.occurred_at).num_days() <= 60) {
+                    let aggregate = aggregates
+                        .entry((signal.scholar_id, signal.signal_type.clone()))
+                        .or_insert_with(|| TypeAggregate {
+                            scholar_id: signal.scholar_id,
+                            scholar_name: signal.scholar_name.clone(),
+                            scholar_email: signal.scholar_email.clone(),
+                            cohort: signal.cohort.clone(),
+                            advisor: signal.advisor.clone(),
+                            signal_type: signal.signal_type.clone(),
+                            weighted_severity: 0.0,
+                            signal_count: 0,
+                            max_severity: 0,
+                            last_signal: signal.occurred_at,
+                        });
+                    let days_ago = (today - signal.occurred_at).num_days();
+                    aggregate.weighted_severity += signal.severity as f64 * recency_weight(days_ago);
+                    aggregate.signal_count += 1;
+                    aggregate.max_severity = aggregate.max_severity.max(signal.severity);
+                    aggregate.last_signal = aggregate.last_signal.max(signal.occurred_at);
+                }
+                let aggregates: Vec<TypeAggregate> = aggregates.into_values().collect();
+                let mut summed = score_aggregates(&aggregates, &config).unwrap();
+                let mut streamed = score_signals_with(&signals, 60, today, &config);
+                // Sums in a different order can break score ties the other way.
+                summed.sort_by(|a, b| a.scholar_email.cmp(&b.scholar_email));
+                streamed.sort_by(|a, b| a.scholar_email.cmp(&b.scholar_email));
+                prop_assert_eq!(summed.len(), streamed.len());
+                for (a, b) in summed.iter().zip(&streamed) {
+                    prop_assert_eq!(&a.scholar_email, &b.scholar_email);
+                    prop_assert!((a.score - b.score).abs() < 1e-9);
+                    prop_assert_eq!((a.signal_count, a.tier), (b.signal_count, b.tier));
+                }
+            }
+
+            #[test]
+            fn scores_are_never_negative(signals in signals_strategy()) {
+                for score in score_signals(&signals, 60) {
+                    prop_assert!(score.score >= 0.0);
+                }
+            }
+
+            #[test]
+            fn scores_ignore_signal_order(signals in signals_strategy()) {
+                let mut reversed = signals.clone();
+                reversed.reverse();
+                let forward = scores_by_email(&signals);
+                let backward = scores_by_email(&reversed);
+                prop_assert_eq!(forward.len(), backward.len());
+                for (a, b) in forward.iter().zip(&backward) {
+                    prop_assert_eq!(&a.0, &b.0);
+                    prop_assert!((a.1 - b.1).abs() < 1e-9);
+                    prop_assert_eq!(a.2, b.2);
+                }
+            }
+
+            #[test]
+            fn raising_severity_never_lowers_a_score(
+                signals in signals_strategy().prop_filter("needs a signal", |s| !s.is_empty()),
+                pick in any::<prop::sample::Index>(),
+            ) {
+                let index = pick.index(signals.len());
+                let mut raised = signals.clone();
+                raised[index].severity = (raised[index].severity + 1).min(5);
+                let email = &signals[index].scholar_email;
+
+                let before = scores_by_email(&signals);
+                let after = scores_by_email(&raised);
+                let score_for = |scores: &[(String, f64, usize)]| {
+                    scores.iter().find(|entry| &entry.0 == email).map(|entry| entry.1).unwrap_or(0.0)
+                };
+                prop_assert!(score_for(&after) >= score_for(&before));
+            }
+        }
+    }
+}
@@ -0,0 +1,269 @@
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+
+use crate::models::{Outcome, OutcomeType, Scholar, SignalRecord};
+use crate::risk::{self, ScoringConfig, Tier};
+
+/// Days after the reference date the report reads each survival curve at.
+pub const MILESTONES: [i64; 4] = [30, 90, 180, 365];
+
+/// One scholar followed from the reference date until they withdrew, graduated, or today.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Subject {
+    pub email: String,
+    /// Tier scored as of the reference date.
+    pub tier: Tier,
+    /// Days followed after the reference date.
+    pub days: i64,
+    /// Whether following ended in a withdrawal; graduation and today are censored.
+    pub withdrew: bool,
+}
+
+/// Scholars still in the program on `reference`, each with the tier they held then.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Study {
+    pub reference: NaiveDate,
+    pub subjects: Vec<Subject>,
+}
+
+/// Follows every scholar on the `roster` from `reference` to their earliest withdrawal or
+/// graduation, or to `today` when they have neither, scoring their tier from the `since_days` of
+/// signals up to `reference`.
+///
+/// Scholars whose outcome came on or before `reference` had already left. Inactive scholars with no
+/// recorded withdrawal or graduation are left out too, as there is no date to follow them to.
+pub fn study(
+    roster: &[Scholar],
+    outcomes: &[Outcome],
+    signals: &[SignalRecord],
+    reference: NaiveDate,
+    today: NaiveDate,
+    since_days: i64,
+    scoring: &ScoringConfig,
+) -> Study {
+    let mut endings: HashMap<&str, &Outcome> = HashMap::new();
+    for outcome in outcomes {
+        if outcome.outcome_type == OutcomeType::Gpa {
+            continue;
+        }
+        let ending = endings.entry(&outcome.scholar_email).or_insert(outcome);
+        if outcome.occurred_on < ending.occurred_on {
+            *ending = outcome;
+        }
+    }
+    let prior: Vec<SignalRecord> = signals
+        .iter()
+        .filter(|signal| signal.occurred_at <= reference)
+        .cloned()
+        .collect();
+    let tiers: HashMap<String, Tier> =
+        risk::score_signals_with(&prior, since_days, reference, scoring)
+            .into_iter()
+            .map(|score| (score.scholar_email, score.tier))
+            .collect();
+
+    let subjects = roster
+        .iter()
+        .filter_map(|scholar| {
+            let (end, withdrew) = match endings.get(scholar.email.as_str()) {
+                Some(ending) if ending.occurred_on <= reference => return None,
+                Some(ending) => (
+                    ending.occurred_on,
+                    ending.outcome_type == OutcomeType::Withdrawal,
+                ),
+                None if scholar.status != "active" => return None,
+                None => (today, false),
+            };
+            Some(Subject {
+                email: scholar.email.clone(),
+                tier: tiers.get(&scholar.email).copied().unwrap_or(Tier::Low),
+                days: (end - reference).num_days(),
+                withdrew,
+            })
+        })
+        .collect();
+    Study {
+        reference,
+        subjects,
+    }
+}
+
+/// A point where a Kaplan-Meier curve drops.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Step {
+    pub day: i64,
+    pub at_risk: usize,
+    pub withdrew: usize,
+    /// Share still enrolled after this day.
+    pub survival: f64,
+}
+
+/// Kaplan-Meier estimate of the share of `subjects` still enrolled over time. Scholars who
+/// graduated or are still enrolled leave the risk set without counting as withdrawals.
+pub fn kaplan_meier(subjects: &[&Subject]) -> Vec<Step> {
+    let mut times: Vec<(i64, bool)> = subjects.iter().map(|s| (s.days, s.withdrew)).collect();
+    times.sort_unstable();
+    let mut at_risk = times.len();
+    let mut survival = 1.0;
+    let mut steps = Vec::new();
+    for group in times.chunk_by(|a, b| a.0 == b.0) {
+        let withdrew = group.iter().filter(|(_, withdrew)| *withdrew).count();
+        if withdrew > 0 {
+            survival *= 1.0 - withdrew as f64 / at_risk as f64;
+            steps.push(Step {
+                day: group[0].0,
+                at_risk,
+                withdrew,
+                survival,
+            });
+        }
+        at_risk -= group.len();
+    }
+    steps
+}
+
+/// Share still enrolled `day` days after the reference date.
+pub fn survival_at(curve: &[Step], day: i64) -> f64 {
+    curve
+        .iter()
+        .take_while(|step| step.day <= day)
+        .last()
+        .map_or(1.0, |step| step.survival)
+}
+
+/// First day on which half or more had withdrawn; `None` while the curve stays above half.
+pub fn median_days(curve: &[Step]) -> Option<i64> {
+    curve
+        .iter()
+        .find(|step| step.survival <= 0.5)
+        .map(|step| step.day)
+}
+
+/// Withdrawals per 100 scholar-months followed.
+pub fn withdrawal_rate(subjects: &[&Subject]) -> Option<f64> {
+    let days: i64 = subjects.iter().map(|s| s.days).sum();
+    let withdrew = subjects.iter().filter(|s| s.withdrew).count();
+    (days > 0).then(|| withdrew as f64 * 100.0 / (days as f64 / 30.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn date(month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, month, day).unwrap()
+    }
+
+    fn subject(days: i64, withdrew: bool) -> Subject {
+        Subject {
+            email: format!("{days}@x.org"),
+            tier: Tier::Low,
+            days,
+            withdrew,
+        }
+    }
+
+    #[test]
+    fn estimates_survival_with_censoring() {
+        let subjects = [
+            subject(10, true),
+            // Graduated on day 20: leaves the risk set without counting.
+            subject(20, false),
+            subject(30, true),
+            subject(30, true),
+            subject(60, false),
+        ];
+        let refs: Vec<&Subject> = subjects.iter().collect();
+        let curve = kaplan_meier(&refs);
+        assert_eq!(curve.len(), 2);
+        assert_eq!((curve[0].day, curve[0].at_risk), (10, 5));
+        assert!((curve[0].survival - 0.8).abs() < 1e-9);
+        // Two of the three left at day 30 withdraw: 0.8 * 1/3.
+        assert!((curve[1].survival - 0.8 / 3.0).abs() < 1e-9);
+        assert_eq!(survival_at(&curve, 5), 1.0);
+        assert_eq!(survival_at(&curve, 29), 0.8);
+        assert_eq!(median_days(&curve), Some(30));
+        // 3 withdrawals over 150 days, or 5 scholar-months.
+        assert_eq!(withdrawal_rate(&refs), Some(60.0));
+    }
+
+    #[test]
+    fn follows_scholars_from_the_reference_date() {
+        let scholar = |email: &str, status: &str| Scholar {
+            full_name: email.to_string(),
+            email: email.to_string(),
+            cohort: "2026".to_string(),
+            status: status.to_string(),
+            advisor: None,
+            created_at: chrono::Utc::now(),
+            deleted: false,
+        };
+        let outcome = |email: &str, outcome_type, occurred_on| Outcome {
+            id: Uuid::new_v4(),
+            scholar_name: email.to_string(),
+            scholar_email: email.to_string(),
+            cohort: "2026".to_string(),
+            outcome_type,
+            occurred_on,
+            gpa: None,
+            note: String::new(),
+        };
+        let signal = |email: &str, occurred_at| SignalRecord {
+            scholar_id: Uuid::from_u128(u128::from(email.as_bytes()[0])),
+            scholar_name: email.to_string(),
+            scholar_email: email.to_string(),
+            cohort: "2026".to_string(),
+            signal_type: "attendance".to_string(),
+            severity: 5,
+            occurred_at,
+            note: String::new(),
+            advisor: None,
+            submitted_by: None,
+            attachments: Vec::new(),
+            note_categories: Vec::new(),
+            note_sentiment: None,
+        };
+        let roster = [
+            scholar("a@x.org", "withdrawn"),
+            scholar("b@x.org", "active"),
+            scholar("c@x.org", "withdrawn"),
+            scholar("d@x.org", "on-leave"),
+        ];
+        let outcomes = [
+            outcome("a@x.org", OutcomeType::Withdrawal, date(3, 31)),
+            // Left before the reference date.
+            outcome("c@x.org", OutcomeType::Withdrawal, date(1, 15)),
+        ];
+        let signals = [
+            signal("a@x.org", date(1, 28)),
+            signal("a@x.org", date(1, 30)),
+            // After the reference date, so it does not set the tier.
+            signal("b@x.org", date(2, 10)),
+        ];
+
+        let study = study(
+            &roster,
+            &outcomes,
+            &signals,
+            date(2, 1),
+            date(5, 2),
+            30,
+            &ScoringConfig::default(),
+        );
+        let followed: Vec<(&str, Tier, i64, bool)> = study
+            .subjects
+            .iter()
+            .map(|s| (s.email.as_str(), s.tier, s.days, s.withdrew))
+            .collect();
+        assert_eq!(
+            followed,
+            [
+                ("a@x.org", Tier::High, 58, true),
+                ("b@x.org", Tier::Low, 90, false),
+            ]
+        );
+    }
+}
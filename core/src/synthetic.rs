@@ -0,0 +1,253 @@
+use chrono::{Datelike, Duration, NaiveDate};
+
+use crate::import::CsvRow;
+
+/// Tag carried by every generated signal, so synthetic data is easy to find and filter out.
+pub const SYNTHETIC_TAG: &str = "synthetic";
+
+const FIRST_NAMES: &[&str] = &[
+    "Avery", "Jules", "Kiara", "Mateo", "Priya", "Noah", "Amara", "Liam", "Sofia", "Ethan", "Zara",
+    "Diego", "Hana", "Omar", "Leila", "Caleb", "Naomi", "Ravi", "Elena", "Malik", "Ines", "Tariq",
+    "Mei", "Jonah", "Aisha", "Felix", "Yara", "Samir", "Clara", "Kofi",
+];
+
+const LAST_NAMES: &[&str] = &[
+    "Lee", "Moreno", "Patel", "Okafor", "Nguyen", "Garcia", "Kim", "Haddad", "Silva", "Novak",
+    "Mensah", "Rossi", "Tanaka", "Ali", "Kowalski", "Johnson", "Reyes", "Chen", "Dubois", "Abebe",
+    "Larsen", "Costa", "Singh", "Ward", "Ibrahim", "Park", "Muller", "Diaz", "Osei", "Bauer",
+];
+
+/// Scale and seed for `seed --scholars`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SyntheticOptions {
+    pub scholars: usize,
+    pub weeks: u32,
+    pub seed: u64,
+    /// Last day signals may fall on; the history covers the `weeks` before it.
+    pub today: NaiveDate,
+}
+
+/// A generated roster entry and the signals logged against it.
+#[derive(Debug, Clone)]
+pub struct SyntheticScholar {
+    pub full_name: String,
+    pub email: String,
+    pub cohort: String,
+    pub signals: Vec<CsvRow>,
+}
+
+/// How a synthetic scholar's signals unfold over the weeks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Pattern {
+    /// Occasional minor engagement or attendance notes.
+    Steady,
+    /// Quiet stretches broken by weeks of repeated absences.
+    AttendanceBursts,
+    /// Academic concerns that grow more frequent and more severe from some week on.
+    AcademicDecline,
+    /// A single serious wellbeing concern with absences around it.
+    WellbeingCrisis,
+}
+
+/// SplitMix64: tiny and fully specified, so a seed yields the same data on every build and
+/// platform, which `rand`'s general-purpose generators don't promise across versions.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in `0..bound`; `bound` must be positive.
+    fn below(&mut self, bound: u64) -> u64 {
+        self.next_u64() % bound
+    }
+
+    fn between(&mut self, low: i64, high: i64) -> i64 {
+        low + self.below((high - low + 1) as u64) as i64
+    }
+
+    fn chance(&mut self, probability: f64) -> bool {
+        ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) < probability
+    }
+
+    fn pick<'a>(&mut self, items: &[&'a str]) -> &'a str {
+        items[self.below(items.len() as u64) as usize]
+    }
+}
+
+/// Generates a reproducible roster with signal histories: the same options always give the same
+/// scholars, signals, and source keys, so reseeding with one seed inserts nothing new.
+pub fn generate(options: &SyntheticOptions) -> Vec<SyntheticScholar> {
+    let mut rng = SplitMix64(options.seed);
+    let year = options.today.year();
+    let cohorts = [year - 1, year, year + 1].map(|year| year.to_string());
+    let start = options.today - Duration::weeks(options.weeks as i64);
+
+    (0..options.scholars)
+        .map(|index| {
+            let first = rng.pick(FIRST_NAMES);
+            let last = rng.pick(LAST_NAMES);
+            let cohort = cohorts[rng.below(cohorts.len() as u64) as usize].clone();
+            let pattern = match rng.below(100) {
+                0..=59 => Pattern::Steady,
+                60..=79 => Pattern::AttendanceBursts,
+                80..=94 => Pattern::AcademicDecline,
+                _ => Pattern::WellbeingCrisis,
+            };
+            let mut scholar = SyntheticScholar {
+                full_name: format!("{first} {last}"),
+                email: format!(
+                    "{}.{}.{index:04}@example.org",
+                    first.to_lowercase(),
+                    last.to_lowercase()
+                ),
+                cohort,
+                signals: Vec::new(),
+            };
+            let events = events(&mut rng, pattern, options.weeks);
+            for (number, (week, signal_type, severity)) in events.into_iter().enumerate() {
+                let occurred_at = (start + Duration::days(week as i64 * 7 + rng.between(0, 6)))
+                    .min(options.today);
+                scholar.signals.push(CsvRow {
+                    full_name: scholar.full_name.clone(),
+                    email: scholar.email.clone(),
+                    cohort: scholar.cohort.clone(),
+                    signal_type: signal_type.to_string(),
+                    severity,
+                    note: note(&mut rng, signal_type, severity),
+                    occurred_at,
+                    source_key: Some(format!("synthetic-{}-{index:04}-{number:03}", options.seed)),
+                    tags: vec![SYNTHETIC_TAG.to_string()],
+                    submitted_by: None,
+                    attachments: Vec::new(),
+                    original_note: None,
+                });
+            }
+            scholar
+        })
+        .collect()
+}
+
+/// Signals for one scholar as `(week, signal type, severity)`, oldest week first.
+fn events(rng: &mut SplitMix64, pattern: Pattern, weeks: u32) -> Vec<(u32, &'static str, i32)> {
+    let mut events = Vec::new();
+    let mut burst_weeks_left = 0;
+    let decline_from = rng.below(weeks.max(1) as u64 / 2 + 1) as u32;
+    let crisis_week = rng.below(weeks.max(1) as u64) as u32;
+
+    for week in 0..weeks {
+        // Background noise every scholar has.
+        if rng.chance(0.06) {
+            events.push((week, "engagement", rng.between(1, 2) as i32));
+        }
+        match pattern {
+            Pattern::Steady => {
+                if rng.chance(0.04) {
+                    events.push((week, "attendance", rng.between(1, 2) as i32));
+                }
+            }
+            Pattern::AttendanceBursts => {
+                if burst_weeks_left == 0 && rng.chance(0.12) {
+                    burst_weeks_left = rng.between(1, 3);
+                }
+                if burst_weeks_left > 0 {
+                    burst_weeks_left -= 1;
+                    for _ in 0..rng.between(2, 4) {
+                        events.push((week, "attendance", rng.between(2, 4) as i32));
+                    }
+                }
+            }
+            Pattern::AcademicDecline => {
+                if week >= decline_from {
+                    // Severity climbs from 1 to 5 over the weeks that remain.
+                    let progress = (week - decline_from) as f64
+                        / (weeks - decline_from).saturating_sub(1).max(1) as f64;
+                    if rng.chance(0.3 + 0.5 * progress) {
+                        let severity = (1.0 + 4.0 * progress).round() as i64;
+                        let severity = (severity + rng.between(-1, 0)).clamp(1, 5);
+                        events.push((week, "academic", severity as i32));
+                    }
+                }
+            }
+            Pattern::WellbeingCrisis => {
+                if week == crisis_week {
+                    events.push((week, "wellbeing", rng.between(4, 5) as i32));
+                }
+                if week.abs_diff(crisis_week) <= 1 && rng.chance(0.7) {
+                    events.push((week, "attendance", rng.between(2, 3) as i32));
+                }
+            }
+        }
+    }
+    events
+}
+
+fn note(rng: &mut SplitMix64, signal_type: &str, severity: i32) -> String {
+    let options: &[&str] = match (signal_type, severity) {
+        ("attendance", 1..=2) => &["Arrived late to session", "Left workshop early"],
+        ("attendance", _) => &["Missed session", "Missed two sessions this week", "No-show"],
+        ("engagement", _) => &["Slow response to outreach", "Skipped check-in survey"],
+        ("academic", 1..=2) => &["Late assignment", "Quiz score below average"],
+        ("academic", 3) => &["Failed midterm", "Several missing assignments"],
+        ("academic", _) => &["Reported GPA dip", "At risk of failing course"],
+        _ => &[
+            "Advisor flagged wellbeing concern",
+            "Reported significant stress",
+        ],
+    };
+    rng.pick(options).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(seed: u64) -> SyntheticOptions {
+        SyntheticOptions {
+            scholars: 200,
+            weeks: 26,
+            seed,
+            today: NaiveDate::from_ymd_opt(2026, 3, 1).unwrap(),
+        }
+    }
+
+    fn fingerprint(scholars: &[SyntheticScholar]) -> Vec<(String, String, i32, NaiveDate)> {
+        scholars
+            .iter()
+            .flat_map(|scholar| &scholar.signals)
+            .map(|row| {
+                (
+                    row.email.clone(),
+                    row.signal_type.clone(),
+                    row.severity,
+                    row.occurred_at,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn same_seed_generates_the_same_data() {
+        let first = generate(&options(42));
+        assert_eq!(first.len(), 200);
+        assert_eq!(fingerprint(&first), fingerprint(&generate(&options(42))));
+        assert_ne!(fingerprint(&first), fingerprint(&generate(&options(7))));
+
+        let signals: Vec<&CsvRow> = first.iter().flat_map(|s| &s.signals).collect();
+        let earliest = NaiveDate::from_ymd_opt(2025, 8, 31).unwrap();
+        assert!(signals.iter().all(|row| (1..=5).contains(&row.severity)
+            && (earliest..=options(42).today).contains(&row.occurred_at)));
+        for signal_type in ["attendance", "engagement", "academic", "wellbeing"] {
+            assert!(signals.iter().any(|row| row.signal_type == signal_type));
+        }
+
+        let mut emails: Vec<&str> = first.iter().map(|s| s.email.as_str()).collect();
+        emails.dedup();
+        assert_eq!(emails.len(), 200);
+    }
+}
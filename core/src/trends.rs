@@ -0,0 +1,132 @@
+use std::collections::HashMap;
+
+use chrono::{Datelike, Duration, NaiveDate};
+
+use crate::models::CohortTrend;
+
+/// One point of a multi-cohort overlay series, ready for a charting tool.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct OverlayPoint {
+    pub cohort: String,
+    pub week_start: NaiveDate,
+    /// 1-based week of the program; empty unless the series is aligned to cohort starts.
+    pub program_week: Option<i64>,
+    pub signal_count: i64,
+    pub scholar_count: i64,
+    pub avg_severity: f64,
+}
+
+/// Monday of the week containing `date`, matching Postgres `date_trunc('week', ...)`.
+pub fn week_start(date: NaiveDate) -> NaiveDate {
+    date - Duration::days(date.weekday().num_days_from_monday() as i64)
+}
+
+/// Program week of `week` for a cohort that started on `start`; the start week is week 1.
+pub fn program_week(start: NaiveDate, week: NaiveDate) -> i64 {
+    (week_start(week) - week_start(start))
+        .num_days()
+        .div_euclid(7)
+        + 1
+}
+
+/// Builds overlay points, numbering weeks from each cohort's start when `align` is set.
+///
+/// Cohorts missing from `starts` keep an empty program week rather than being dropped.
+pub fn overlay(
+    trends: &[CohortTrend],
+    starts: &HashMap<String, NaiveDate>,
+    align: bool,
+) -> Vec<OverlayPoint> {
+    let mut points: Vec<OverlayPoint> = trends
+        .iter()
+        .map(|trend| OverlayPoint {
+            cohort: trend.cohort.clone(),
+            week_start: trend.week_start,
+            program_week: if align {
+                starts
+                    .get(&trend.cohort)
+                    .map(|start| program_week(*start, trend.week_start))
+            } else {
+                None
+            },
+            signal_count: trend.signal_count,
+            scholar_count: trend.scholar_count,
+            avg_severity: trend.avg_severity,
+        })
+        .collect();
+
+    if align {
+        points.sort_by(|a, b| {
+            a.program_week
+                .cmp(&b.program_week)
+                .then_with(|| a.cohort.cmp(&b.cohort))
+        });
+    } else {
+        points.sort_by(|a, b| {
+            a.week_start
+                .cmp(&b.week_start)
+                .then_with(|| a.cohort.cmp(&b.cohort))
+        });
+    }
+    points
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn trend(cohort: &str, week_start: NaiveDate, signal_count: i64) -> CohortTrend {
+        CohortTrend {
+            cohort: cohort.to_string(),
+            week_start,
+            signal_count,
+            avg_severity: 3.0,
+            scholar_count: 1,
+        }
+    }
+
+    #[test]
+    fn program_week_counts_from_start_week() {
+        // 2025-09-03 is a Wednesday; its week starts Monday 2025-09-01.
+        let start = date(2025, 9, 3);
+        assert_eq!(program_week(start, date(2025, 9, 1)), 1);
+        assert_eq!(program_week(start, date(2025, 9, 7)), 1);
+        assert_eq!(program_week(start, date(2025, 10, 6)), 6);
+        assert_eq!(program_week(start, date(2025, 8, 25)), 0);
+    }
+
+    #[test]
+    fn aligns_cohorts_on_program_week() {
+        let trends = vec![
+            trend("2025", date(2025, 10, 6), 4),
+            trend("2026", date(2026, 10, 5), 7),
+            trend("2026", date(2026, 9, 28), 2),
+        ];
+        let starts = HashMap::from([
+            ("2025".to_string(), date(2025, 9, 1)),
+            ("2026".to_string(), date(2026, 8, 31)),
+        ]);
+
+        let points = overlay(&trends, &starts, true);
+        let weeks: Vec<(&str, Option<i64>)> = points
+            .iter()
+            .map(|point| (point.cohort.as_str(), point.program_week))
+            .collect();
+        assert_eq!(
+            weeks,
+            vec![("2026", Some(5)), ("2025", Some(6)), ("2026", Some(6))]
+        );
+    }
+
+    #[test]
+    fn unaligned_overlay_leaves_program_week_empty() {
+        let trends = vec![trend("2025", date(2025, 10, 6), 4)];
+        let starts = HashMap::from([("2025".to_string(), date(2025, 9, 1))]);
+        let points = overlay(&trends, &starts, false);
+        assert_eq!(points[0].program_week, None);
+    }
+}
@@ -0,0 +1,78 @@
+// Risk dashboard for `serve`: lists /scores for the chosen scope and refreshes every minute.
+// The API key is kept in this browser's localStorage only. Add ?api=https://host:8080 to the page
+// URL to read from a server on another origin (it must list this one in [api] cors_origins).
+
+const API = new URLSearchParams(location.search).get("api") ?? "";
+const TIERS = ["critical", "high", "moderate", "low"];
+const REFRESH_MS = 60_000;
+
+const form = document.getElementById("scope");
+const keyInput = document.getElementById("key");
+const status = document.getElementById("status");
+
+keyInput.value = localStorage.getItem("cew-key") ?? "";
+
+function cell(row, text, className) {
+  const td = row.insertCell();
+  td.textContent = text ?? "";
+  if (className) td.className = className;
+}
+
+function render(scores) {
+  const summary = document.getElementById("summary");
+  summary.replaceChildren(
+    ...TIERS.map((tier) => {
+      const badge = document.createElement("span");
+      badge.className = tier;
+      badge.textContent = `${tier}: ${scores.filter((s) => s.tier === tier).length}`;
+      return badge;
+    }),
+  );
+
+  const body = document.getElementById("scores");
+  body.replaceChildren();
+  for (const score of scores) {
+    const row = body.insertRow();
+    row.className = score.tier;
+    cell(row, score.scholar_name);
+    cell(row, score.scholar_email);
+    cell(row, score.cohort);
+    cell(row, score.advisor);
+    cell(row, score.score.toFixed(2), "number");
+    cell(row, score.tier);
+    cell(row, score.signal_count, "number");
+    cell(row, score.escalated_by);
+  }
+}
+
+async function refresh() {
+  localStorage.setItem("cew-key", keyInput.value);
+  const params = new URLSearchParams({
+    since_days: document.getElementById("since_days").value || "30",
+  });
+  const cohort = document.getElementById("cohort").value.trim();
+  if (cohort) params.set("cohort", cohort);
+
+  const headers = keyInput.value ? { Authorization: `Bearer ${keyInput.value}` } : {};
+  try {
+    const response = await fetch(`${API}/scores?${params}`, { headers });
+    if (!response.ok) {
+      const reason = { 401: "missing or unknown API key", 403: "key lacks read scope" };
+      throw new Error(reason[response.status] ?? `HTTP ${response.status}`);
+    }
+    const scores = await response.json();
+    render(scores);
+    status.className = "";
+    status.textContent = `${scores.length} scholars, updated ${new Date().toLocaleTimeString()}`;
+  } catch (err) {
+    status.className = "error";
+    status.textContent = `Could not load scores: ${err.message}`;
+  }
+}
+
+form.addEventListener("submit", (event) => {
+  event.preventDefault();
+  refresh();
+});
+setInterval(refresh, REFRESH_MS);
+refresh();
@@ -0,0 +1,25 @@
+#![no_main]
+
+use groupscholar_cohort_early_warning::import::{ChunkedReader, ImportLimits, NotePolicy};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let limits = ImportLimits {
+        max_rows: 1_000,
+        max_note_chars: 256,
+        chunk_size: 16,
+        note_policy: NotePolicy::Summarize,
+        keep_note_overflow: true,
+        ..ImportLimits::default()
+    };
+    let mut reader = ChunkedReader::new(data, limits);
+
+    while let Ok(chunk) = reader.next_chunk() {
+        if chunk.is_empty() {
+            break;
+        }
+        for row in chunk {
+            assert!(row.note.chars().count() <= 256);
+        }
+    }
+});
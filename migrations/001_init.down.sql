@@ -0,0 +1,3 @@
+DROP TABLE IF EXISTS cohort_early_warning.signals;
+DROP TABLE IF EXISTS cohort_early_warning.scholars;
+DROP SCHEMA IF EXISTS cohort_early_warning;
@@ -0,0 +1,3 @@
+DROP INDEX IF EXISTS cohort_early_warning.idx_cohort_early_warning_batch;
+ALTER TABLE cohort_early_warning.signals DROP COLUMN IF EXISTS batch_id;
+DROP TABLE IF EXISTS cohort_early_warning.import_batches;
@@ -0,0 +1,11 @@
+CREATE TABLE IF NOT EXISTS cohort_early_warning.import_batches (
+    id UUID PRIMARY KEY,
+    source TEXT NOT NULL,
+    started_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    undone_at TIMESTAMPTZ
+);
+
+ALTER TABLE cohort_early_warning.signals
+    ADD COLUMN IF NOT EXISTS batch_id UUID REFERENCES cohort_early_warning.import_batches(id);
+
+CREATE INDEX IF NOT EXISTS idx_cohort_early_warning_batch ON cohort_early_warning.signals(batch_id);
@@ -0,0 +1,5 @@
+CREATE TABLE IF NOT EXISTS cohort_early_warning.signal_note_overflow (
+    signal_id UUID PRIMARY KEY REFERENCES cohort_early_warning.signals(id) ON DELETE CASCADE,
+    full_note TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
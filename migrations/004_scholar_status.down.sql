@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS cohort_early_warning.idx_cohort_early_warning_scholar_status;
+ALTER TABLE cohort_early_warning.scholars DROP COLUMN IF EXISTS status;
@@ -0,0 +1,5 @@
+ALTER TABLE cohort_early_warning.scholars
+    ADD COLUMN IF NOT EXISTS status TEXT NOT NULL DEFAULT 'active'
+    CHECK (status IN ('active', 'withdrawn', 'graduated', 'on-leave'));
+
+CREATE INDEX IF NOT EXISTS idx_cohort_early_warning_scholar_status ON cohort_early_warning.scholars(status);
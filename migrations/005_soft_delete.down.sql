@@ -0,0 +1,2 @@
+ALTER TABLE cohort_early_warning.signals DROP COLUMN IF EXISTS deleted_at;
+ALTER TABLE cohort_early_warning.scholars DROP COLUMN IF EXISTS deleted_at;
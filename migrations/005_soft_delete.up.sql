@@ -0,0 +1,2 @@
+ALTER TABLE cohort_early_warning.scholars ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMPTZ;
+ALTER TABLE cohort_early_warning.signals ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMPTZ;
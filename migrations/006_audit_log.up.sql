@@ -0,0 +1,12 @@
+CREATE TABLE IF NOT EXISTS cohort_early_warning.audit_log (
+    id BIGSERIAL PRIMARY KEY,
+    occurred_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    actor TEXT NOT NULL,
+    command TEXT NOT NULL,
+    action TEXT NOT NULL CHECK (action IN ('insert', 'update', 'delete', 'migrate')),
+    entity TEXT NOT NULL,
+    affected_rows BIGINT NOT NULL,
+    detail TEXT NOT NULL DEFAULT ''
+);
+
+CREATE INDEX IF NOT EXISTS idx_cohort_early_warning_audit_occurred ON cohort_early_warning.audit_log(occurred_at);
@@ -0,0 +1,14 @@
+-- Fails if two organizations share an email or source key, which is the point: merge them first.
+DROP INDEX IF EXISTS cohort_early_warning.idx_cohort_early_warning_signal_org_source;
+ALTER TABLE cohort_early_warning.signals DROP COLUMN IF EXISTS org_id;
+ALTER TABLE cohort_early_warning.signals
+    ADD CONSTRAINT signals_source_key_key UNIQUE (source_key);
+
+ALTER TABLE cohort_early_warning.import_batches DROP COLUMN IF EXISTS org_id;
+
+DROP INDEX IF EXISTS cohort_early_warning.idx_cohort_early_warning_scholar_org_email;
+ALTER TABLE cohort_early_warning.scholars DROP COLUMN IF EXISTS org_id;
+ALTER TABLE cohort_early_warning.scholars
+    ADD CONSTRAINT scholars_email_key UNIQUE (email);
+
+DROP TABLE IF EXISTS cohort_early_warning.organizations;
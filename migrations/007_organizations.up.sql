@@ -0,0 +1,31 @@
+CREATE TABLE IF NOT EXISTS cohort_early_warning.organizations (
+    id UUID PRIMARY KEY,
+    slug TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+INSERT INTO cohort_early_warning.organizations (id, slug, name)
+VALUES ('00000000-0000-0000-0000-000000000001', 'default', 'Group Scholar')
+ON CONFLICT (id) DO NOTHING;
+
+ALTER TABLE cohort_early_warning.scholars
+    ADD COLUMN IF NOT EXISTS org_id UUID NOT NULL DEFAULT '00000000-0000-0000-0000-000000000001'
+    REFERENCES cohort_early_warning.organizations(id);
+
+-- Emails only need to be unique within an organization.
+ALTER TABLE cohort_early_warning.scholars DROP CONSTRAINT IF EXISTS scholars_email_key;
+CREATE UNIQUE INDEX IF NOT EXISTS idx_cohort_early_warning_scholar_org_email
+    ON cohort_early_warning.scholars(org_id, email);
+
+ALTER TABLE cohort_early_warning.import_batches
+    ADD COLUMN IF NOT EXISTS org_id UUID NOT NULL DEFAULT '00000000-0000-0000-0000-000000000001'
+    REFERENCES cohort_early_warning.organizations(id);
+
+-- Source keys come from partner systems, so they are only unique within an organization.
+ALTER TABLE cohort_early_warning.signals
+    ADD COLUMN IF NOT EXISTS org_id UUID NOT NULL DEFAULT '00000000-0000-0000-0000-000000000001'
+    REFERENCES cohort_early_warning.organizations(id);
+ALTER TABLE cohort_early_warning.signals DROP CONSTRAINT IF EXISTS signals_source_key_key;
+CREATE UNIQUE INDEX IF NOT EXISTS idx_cohort_early_warning_signal_org_source
+    ON cohort_early_warning.signals(org_id, source_key);
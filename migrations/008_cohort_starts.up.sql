@@ -0,0 +1,7 @@
+-- Program start dates anchor "week N of the program" comparisons across cohorts.
+CREATE TABLE IF NOT EXISTS cohort_early_warning.cohorts (
+    org_id UUID NOT NULL REFERENCES cohort_early_warning.organizations(id),
+    name TEXT NOT NULL,
+    start_date DATE NOT NULL,
+    PRIMARY KEY (org_id, name)
+);
@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS cohort_early_warning.idx_cohort_early_warning_signal_tags;
+ALTER TABLE cohort_early_warning.signals DROP COLUMN IF EXISTS tags;
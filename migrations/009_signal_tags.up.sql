@@ -0,0 +1,6 @@
+-- Free-form labels (e.g. an initiative such as summer-bridge) kept separate from signal_type.
+ALTER TABLE cohort_early_warning.signals
+    ADD COLUMN IF NOT EXISTS tags TEXT[] NOT NULL DEFAULT '{}';
+
+CREATE INDEX IF NOT EXISTS idx_cohort_early_warning_signal_tags
+    ON cohort_early_warning.signals USING GIN (tags);
@@ -0,0 +1,3 @@
+DROP INDEX IF EXISTS cohort_early_warning.idx_cohort_early_warning_scholar_advisor;
+ALTER TABLE cohort_early_warning.scholars DROP COLUMN IF EXISTS advisor_id;
+DROP TABLE IF EXISTS cohort_early_warning.advisors;
@@ -0,0 +1,15 @@
+CREATE TABLE IF NOT EXISTS cohort_early_warning.advisors (
+    id UUID PRIMARY KEY,
+    org_id UUID NOT NULL REFERENCES cohort_early_warning.organizations(id),
+    full_name TEXT NOT NULL,
+    email TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (org_id, email)
+);
+
+ALTER TABLE cohort_early_warning.scholars
+    ADD COLUMN IF NOT EXISTS advisor_id UUID
+    REFERENCES cohort_early_warning.advisors(id) ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS idx_cohort_early_warning_scholar_advisor
+    ON cohort_early_warning.scholars(advisor_id);
@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS cohort_early_warning.intervention_signals;
+DROP TABLE IF EXISTS cohort_early_warning.interventions;
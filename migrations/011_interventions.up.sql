@@ -0,0 +1,20 @@
+CREATE TABLE IF NOT EXISTS cohort_early_warning.interventions (
+    id UUID PRIMARY KEY,
+    org_id UUID NOT NULL REFERENCES cohort_early_warning.organizations(id),
+    scholar_id UUID NOT NULL REFERENCES cohort_early_warning.scholars(id) ON DELETE CASCADE,
+    occurred_on DATE NOT NULL,
+    intervention_type TEXT NOT NULL,
+    outcome TEXT,
+    note TEXT NOT NULL DEFAULT '',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_cohort_early_warning_intervention_scholar
+    ON cohort_early_warning.interventions(scholar_id);
+
+-- Which warnings an intervention responded to.
+CREATE TABLE IF NOT EXISTS cohort_early_warning.intervention_signals (
+    intervention_id UUID NOT NULL REFERENCES cohort_early_warning.interventions(id) ON DELETE CASCADE,
+    signal_id UUID NOT NULL REFERENCES cohort_early_warning.signals(id) ON DELETE CASCADE,
+    PRIMARY KEY (intervention_id, signal_id)
+);
@@ -0,0 +1 @@
+ALTER TABLE cohort_early_warning.signals DROP COLUMN IF EXISTS submitted_by;
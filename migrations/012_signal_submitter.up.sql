@@ -0,0 +1,2 @@
+-- Staff member who entered the signal by hand; NULL for system-generated signals.
+ALTER TABLE cohort_early_warning.signals ADD COLUMN IF NOT EXISTS submitted_by TEXT;
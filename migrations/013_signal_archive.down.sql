@@ -0,0 +1,3 @@
+-- Archived signals are lost; restore them into signals before reverting if they are still needed.
+DROP VIEW IF EXISTS cohort_early_warning.signals_with_archive;
+DROP TABLE IF EXISTS cohort_early_warning.signals_archive;
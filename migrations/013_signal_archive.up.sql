@@ -0,0 +1,32 @@
+-- Aged signals move here so the hot table stays lean. Columns added to signals later must be
+-- added here and to archive_signals in db.rs as well.
+CREATE TABLE IF NOT EXISTS cohort_early_warning.signals_archive (
+    id UUID PRIMARY KEY,
+    scholar_id UUID NOT NULL REFERENCES cohort_early_warning.scholars(id) ON DELETE CASCADE,
+    signal_type TEXT NOT NULL,
+    severity INT NOT NULL,
+    note TEXT NOT NULL,
+    occurred_at DATE NOT NULL,
+    source_key TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL,
+    batch_id UUID,
+    deleted_at TIMESTAMPTZ,
+    org_id UUID NOT NULL REFERENCES cohort_early_warning.organizations(id),
+    tags TEXT[] NOT NULL DEFAULT '{}',
+    submitted_by TEXT,
+    archived_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (org_id, source_key)
+);
+
+CREATE INDEX IF NOT EXISTS idx_cohort_early_warning_archive_occurred
+    ON cohort_early_warning.signals_archive(occurred_at);
+
+-- Hot and archived signals together, for backtesting and long-range trends.
+CREATE OR REPLACE VIEW cohort_early_warning.signals_with_archive AS
+SELECT id, scholar_id, signal_type, severity, note, occurred_at, source_key, batch_id, deleted_at,
+       org_id, tags, submitted_by
+FROM cohort_early_warning.signals
+UNION ALL
+SELECT id, scholar_id, signal_type, severity, note, occurred_at, source_key, batch_id, deleted_at,
+       org_id, tags, submitted_by
+FROM cohort_early_warning.signals_archive;
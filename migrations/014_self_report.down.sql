@@ -0,0 +1,3 @@
+DROP TABLE IF EXISTS cohort_early_warning.self_report_submissions;
+DROP TABLE IF EXISTS cohort_early_warning.self_report_tokens;
+ALTER TABLE cohort_early_warning.scholars DROP COLUMN IF EXISTS self_report_consent_at;
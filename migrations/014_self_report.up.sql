@@ -0,0 +1,23 @@
+-- Scholars must opt in before a self-report link can be issued or used.
+ALTER TABLE cohort_early_warning.scholars
+    ADD COLUMN IF NOT EXISTS self_report_consent_at TIMESTAMPTZ;
+
+-- Only a SHA-256 hash of each link token is stored.
+CREATE TABLE IF NOT EXISTS cohort_early_warning.self_report_tokens (
+    id UUID PRIMARY KEY,
+    org_id UUID NOT NULL REFERENCES cohort_early_warning.organizations(id),
+    scholar_id UUID NOT NULL REFERENCES cohort_early_warning.scholars(id) ON DELETE CASCADE,
+    token_hash TEXT NOT NULL UNIQUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    revoked_at TIMESTAMPTZ
+);
+
+CREATE TABLE IF NOT EXISTS cohort_early_warning.self_report_submissions (
+    id UUID PRIMARY KEY,
+    token_id UUID NOT NULL REFERENCES cohort_early_warning.self_report_tokens(id) ON DELETE CASCADE,
+    signal_id UUID NOT NULL,
+    submitted_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_cohort_early_warning_self_report_recent
+    ON cohort_early_warning.self_report_submissions(token_id, submitted_at);
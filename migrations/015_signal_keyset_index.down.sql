@@ -0,0 +1 @@
+DROP INDEX IF EXISTS cohort_early_warning.idx_cohort_early_warning_signal_keyset;
@@ -0,0 +1,3 @@
+-- Supports keyset pagination over signals ordered by (occurred_at, id).
+CREATE INDEX IF NOT EXISTS idx_cohort_early_warning_signal_keyset
+    ON cohort_early_warning.signals(occurred_at, id);
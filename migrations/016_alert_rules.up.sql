@@ -0,0 +1,16 @@
+CREATE TABLE IF NOT EXISTS cohort_early_warning.alert_rules (
+    id UUID PRIMARY KEY,
+    org_id UUID NOT NULL REFERENCES cohort_early_warning.organizations(id),
+    name TEXT NOT NULL,
+    -- Every non-null condition must hold for a scholar to match.
+    signal_type TEXT,
+    min_severity INT CHECK (min_severity BETWEEN 1 AND 5),
+    min_score DOUBLE PRECISION,
+    min_tier TEXT CHECK (min_tier IN ('low', 'moderate', 'high', 'critical')),
+    since_days INT NOT NULL DEFAULT 30,
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (org_id, name),
+    CHECK (signal_type IS NOT NULL OR min_severity IS NOT NULL
+           OR min_score IS NOT NULL OR min_tier IS NOT NULL)
+);
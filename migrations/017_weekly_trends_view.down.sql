@@ -0,0 +1 @@
+DROP MATERIALIZED VIEW IF EXISTS cohort_early_warning.weekly_trends_mv;
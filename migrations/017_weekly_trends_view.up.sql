@@ -0,0 +1,18 @@
+-- Pre-aggregated weekly trends, refreshed by `refresh-trends`. Cohorts and statuses partition
+-- scholars, so counts can be summed across rows without double counting.
+CREATE MATERIALIZED VIEW IF NOT EXISTS cohort_early_warning.weekly_trends_mv AS
+SELECT sc.org_id,
+       sc.cohort,
+       sc.status,
+       date_trunc('week', s.occurred_at)::date AS week_start,
+       COUNT(*) AS signal_count,
+       SUM(s.severity) AS severity_sum,
+       COUNT(DISTINCT sc.id) AS scholar_count
+FROM cohort_early_warning.signals s
+JOIN cohort_early_warning.scholars sc ON sc.id = s.scholar_id
+WHERE s.deleted_at IS NULL AND sc.deleted_at IS NULL
+GROUP BY sc.org_id, sc.cohort, sc.status, week_start;
+
+-- Required for REFRESH MATERIALIZED VIEW CONCURRENTLY.
+CREATE UNIQUE INDEX IF NOT EXISTS idx_cohort_early_warning_weekly_trends_mv
+    ON cohort_early_warning.weekly_trends_mv(org_id, cohort, status, week_start);
@@ -0,0 +1,14 @@
+-- Links to the evidence behind a signal (LMS page, case note, email thread). There is no foreign
+-- key to signals so attachments follow a signal into signals_archive.
+CREATE TABLE IF NOT EXISTS cohort_early_warning.signal_attachments (
+    id UUID PRIMARY KEY,
+    org_id UUID NOT NULL REFERENCES cohort_early_warning.organizations(id),
+    signal_id UUID NOT NULL,
+    url TEXT NOT NULL,
+    label TEXT NOT NULL,
+    kind TEXT NOT NULL DEFAULT 'link',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_cohort_early_warning_attachment_signal
+    ON cohort_early_warning.signal_attachments(signal_id);
@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS cohort_early_warning.idx_cohort_early_warning_archive_note_search;
+DROP INDEX IF EXISTS cohort_early_warning.idx_cohort_early_warning_signal_note_search;
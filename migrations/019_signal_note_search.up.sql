@@ -0,0 +1,6 @@
+-- Full-text search over notes for `search`; queries must use the same expression to hit the index.
+CREATE INDEX IF NOT EXISTS idx_cohort_early_warning_signal_note_search
+    ON cohort_early_warning.signals USING GIN (to_tsvector('english', note));
+
+CREATE INDEX IF NOT EXISTS idx_cohort_early_warning_archive_note_search
+    ON cohort_early_warning.signals_archive USING GIN (to_tsvector('english', note));
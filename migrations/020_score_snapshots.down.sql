@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS cohort_early_warning.score_snapshots;
+DROP TABLE IF EXISTS cohort_early_warning.score_runs;
@@ -0,0 +1,23 @@
+-- One row per scoring run persisted by `watch`, with each scholar's result in score_snapshots.
+CREATE TABLE IF NOT EXISTS cohort_early_warning.score_runs (
+    id UUID PRIMARY KEY,
+    org_id UUID NOT NULL REFERENCES cohort_early_warning.organizations(id),
+    ran_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    since_days INT NOT NULL,
+    trigger TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_cohort_early_warning_score_run_org
+    ON cohort_early_warning.score_runs(org_id, ran_at);
+
+CREATE TABLE IF NOT EXISTS cohort_early_warning.score_snapshots (
+    run_id UUID NOT NULL REFERENCES cohort_early_warning.score_runs(id) ON DELETE CASCADE,
+    scholar_id UUID NOT NULL REFERENCES cohort_early_warning.scholars(id) ON DELETE CASCADE,
+    score DOUBLE PRECISION NOT NULL,
+    tier TEXT NOT NULL,
+    signal_count INT NOT NULL,
+    PRIMARY KEY (run_id, scholar_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_cohort_early_warning_score_snapshot_scholar
+    ON cohort_early_warning.score_snapshots(scholar_id);
@@ -0,0 +1 @@
+ALTER TABLE cohort_early_warning.scholars DROP COLUMN IF EXISTS anonymized_at;
@@ -0,0 +1,2 @@
+-- Set when `purge --mode anonymize` replaces a scholar's name and email with pseudonyms.
+ALTER TABLE cohort_early_warning.scholars ADD COLUMN IF NOT EXISTS anonymized_at TIMESTAMPTZ;
@@ -0,0 +1 @@
+ALTER TABLE cohort_early_warning.interventions DROP COLUMN IF EXISTS closed_at;
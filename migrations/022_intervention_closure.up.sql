@@ -0,0 +1,7 @@
+-- Set by `intervene close` along with the final outcome; NULL while the intervention is open.
+ALTER TABLE cohort_early_warning.interventions ADD COLUMN IF NOT EXISTS closed_at TIMESTAMPTZ;
+
+-- Interventions recorded with an outcome before closing existed were complete when logged.
+UPDATE cohort_early_warning.interventions
+SET closed_at = created_at
+WHERE outcome IS NOT NULL AND outcome <> 'pending';
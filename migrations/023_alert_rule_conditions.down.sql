@@ -0,0 +1,6 @@
+ALTER TABLE cohort_early_warning.alert_rules
+    DROP COLUMN IF EXISTS channels,
+    DROP COLUMN IF EXISTS new_only,
+    DROP COLUMN IF EXISTS min_count;
+
+ALTER TABLE cohort_early_warning.organizations DROP COLUMN IF EXISTS rules_evaluated_at;
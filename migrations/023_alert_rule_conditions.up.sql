@@ -0,0 +1,9 @@
+-- Signals that must match before a rule fires (NULL means one), whether only signals logged since
+-- the previous evaluation count, and where a firing rule is sent (empty means the [watch] channels).
+ALTER TABLE cohort_early_warning.alert_rules
+    ADD COLUMN IF NOT EXISTS min_count INT CHECK (min_count >= 1),
+    ADD COLUMN IF NOT EXISTS new_only BOOLEAN NOT NULL DEFAULT FALSE,
+    ADD COLUMN IF NOT EXISTS channels TEXT[] NOT NULL DEFAULT '{}';
+
+-- When `watch` or `import` last evaluated the rules; signals logged after it count as new.
+ALTER TABLE cohort_early_warning.organizations ADD COLUMN IF NOT EXISTS rules_evaluated_at TIMESTAMPTZ;
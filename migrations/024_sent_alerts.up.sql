@@ -0,0 +1,15 @@
+-- One row per scholar each time an alert about them reaches at least one channel; the cooldown
+-- checks it before alerting again.
+CREATE TABLE IF NOT EXISTS cohort_early_warning.sent_alerts (
+    id UUID PRIMARY KEY,
+    org_id UUID NOT NULL REFERENCES cohort_early_warning.organizations(id),
+    scholar_id UUID NOT NULL REFERENCES cohort_early_warning.scholars(id) ON DELETE CASCADE,
+    -- Alert rule that fired; NULL for the `watch` tier alert.
+    rule TEXT,
+    -- Channels the alert was delivered to.
+    channels TEXT[] NOT NULL,
+    sent_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_cohort_early_warning_sent_alert_scholar
+    ON cohort_early_warning.sent_alerts(org_id, scholar_id, sent_at);
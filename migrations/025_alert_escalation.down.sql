@@ -0,0 +1,5 @@
+ALTER TABLE cohort_early_warning.sent_alerts
+    DROP COLUMN IF EXISTS escalated_at,
+    DROP COLUMN IF EXISTS acknowledged_by,
+    DROP COLUMN IF EXISTS acknowledged_at,
+    DROP COLUMN IF EXISTS tier;
@@ -0,0 +1,7 @@
+-- Tier the scholar was in when alerted (NULL for alerts sent before this column), and the
+-- acknowledgment and escalation state `watch` checks before re-notifying the escalation channels.
+ALTER TABLE cohort_early_warning.sent_alerts
+    ADD COLUMN IF NOT EXISTS tier TEXT CHECK (tier IN ('low', 'moderate', 'high', 'critical')),
+    ADD COLUMN IF NOT EXISTS acknowledged_at TIMESTAMPTZ,
+    ADD COLUMN IF NOT EXISTS acknowledged_by TEXT,
+    ADD COLUMN IF NOT EXISTS escalated_at TIMESTAMPTZ;
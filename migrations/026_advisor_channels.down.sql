@@ -0,0 +1 @@
+ALTER TABLE cohort_early_warning.advisors DROP COLUMN IF EXISTS channel;
@@ -0,0 +1,3 @@
+-- Config channel that receives alerts about the advisor's caseload; NULL sends them to the
+-- org-wide channels.
+ALTER TABLE cohort_early_warning.advisors ADD COLUMN IF NOT EXISTS channel TEXT;
@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS cohort_early_warning.notification_scholars;
+DROP TABLE IF EXISTS cohort_early_warning.notifications;
@@ -0,0 +1,31 @@
+-- One row per alert message sent to a channel, delivered or not, so `notifications list` can show
+-- what reached whom.
+CREATE TABLE IF NOT EXISTS cohort_early_warning.notifications (
+    id UUID PRIMARY KEY,
+    org_id UUID NOT NULL REFERENCES cohort_early_warning.organizations(id),
+    channel TEXT NOT NULL,
+    reason TEXT NOT NULL CHECK (reason IN ('tier', 'rule', 'escalation')),
+    -- Alert rule that fired, for rule alerts.
+    rule TEXT,
+    -- Headline of the message; scholar details live in notification_scholars.
+    summary TEXT NOT NULL,
+    status TEXT NOT NULL CHECK (status IN ('sent', 'failed')),
+    attempts INT NOT NULL CHECK (attempts >= 1),
+    -- Last error, for failed deliveries.
+    error TEXT,
+    sent_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Scholars each notification was about.
+CREATE TABLE IF NOT EXISTS cohort_early_warning.notification_scholars (
+    notification_id UUID NOT NULL
+        REFERENCES cohort_early_warning.notifications(id) ON DELETE CASCADE,
+    scholar_id UUID NOT NULL REFERENCES cohort_early_warning.scholars(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_cohort_early_warning_notification_sent
+    ON cohort_early_warning.notifications(org_id, sent_at);
+CREATE INDEX IF NOT EXISTS idx_cohort_early_warning_notification_scholar
+    ON cohort_early_warning.notification_scholars(scholar_id);
+CREATE INDEX IF NOT EXISTS idx_cohort_early_warning_notification_scholar_notification
+    ON cohort_early_warning.notification_scholars(notification_id);
@@ -0,0 +1,15 @@
+-- Keys for the HTTP API. Only a SHA-256 hash of each key is stored; the key is shown once.
+CREATE TABLE IF NOT EXISTS cohort_early_warning.api_keys (
+    id UUID PRIMARY KEY,
+    org_id UUID NOT NULL REFERENCES cohort_early_warning.organizations(id),
+    name TEXT NOT NULL,
+    scope TEXT NOT NULL CHECK (scope IN ('read', 'write', 'admin')),
+    key_hash TEXT NOT NULL UNIQUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_used_at TIMESTAMPTZ,
+    revoked_at TIMESTAMPTZ
+);
+
+-- Names identify live keys for `api-key revoke`; a revoked key's name can be reused.
+CREATE UNIQUE INDEX IF NOT EXISTS idx_cohort_early_warning_api_key_name
+    ON cohort_early_warning.api_keys(org_id, name) WHERE revoked_at IS NULL;
@@ -0,0 +1,2 @@
+DROP TRIGGER IF EXISTS signal_inserted ON cohort_early_warning.signals;
+DROP FUNCTION IF EXISTS cohort_early_warning.notify_signal_inserted();
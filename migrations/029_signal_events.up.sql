@@ -0,0 +1,28 @@
+-- Announce every new signal on a notification channel, whichever process inserted it, so `serve`
+-- can stream imports and API submissions to dashboards. Notes stay out of the payload: they can
+-- be long and are fetched with the signal when a dashboard needs them.
+CREATE OR REPLACE FUNCTION cohort_early_warning.notify_signal_inserted() RETURNS trigger AS $$
+BEGIN
+    PERFORM pg_notify(
+        'cohort_early_warning_signals',
+        json_build_object(
+            'org_id', NEW.org_id,
+            'email', sc.email,
+            'cohort', sc.cohort,
+            'signal_type', NEW.signal_type,
+            'severity', NEW.severity,
+            'occurred_at', NEW.occurred_at,
+            'source_key', NEW.source_key,
+            'submitted_by', NEW.submitted_by
+        )::text
+    )
+    FROM cohort_early_warning.scholars sc
+    WHERE sc.id = NEW.scholar_id;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS signal_inserted ON cohort_early_warning.signals;
+CREATE TRIGGER signal_inserted
+    AFTER INSERT ON cohort_early_warning.signals
+    FOR EACH ROW EXECUTE FUNCTION cohort_early_warning.notify_signal_inserted();
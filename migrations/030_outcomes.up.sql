@@ -0,0 +1,19 @@
+-- Milestones recorded by `outcome record`, which `analytics outcomes` compares with the risk tiers
+-- scholars held beforehand.
+CREATE TABLE IF NOT EXISTS cohort_early_warning.outcomes (
+    id UUID PRIMARY KEY,
+    org_id UUID NOT NULL REFERENCES cohort_early_warning.organizations(id),
+    scholar_id UUID NOT NULL REFERENCES cohort_early_warning.scholars(id) ON DELETE CASCADE,
+    outcome_type TEXT NOT NULL CHECK (outcome_type IN ('withdrawal', 'graduation', 'gpa')),
+    occurred_on DATE NOT NULL,
+    -- Set for GPA milestones only.
+    gpa DOUBLE PRECISION CHECK (gpa >= 0 AND gpa <= 5),
+    note TEXT NOT NULL DEFAULT '',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CHECK ((outcome_type = 'gpa') = (gpa IS NOT NULL))
+);
+
+CREATE INDEX IF NOT EXISTS idx_cohort_early_warning_outcome_scholar
+    ON cohort_early_warning.outcomes(scholar_id);
+CREATE INDEX IF NOT EXISTS idx_cohort_early_warning_outcome_org
+    ON cohort_early_warning.outcomes(org_id, occurred_on);
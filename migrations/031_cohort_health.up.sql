@@ -0,0 +1,12 @@
+-- Weekly cohort health index written by `refresh-health` and charted by `report --health`.
+-- Rows are recomputed in place, so a week can be refreshed after late signals arrive.
+CREATE TABLE IF NOT EXISTS cohort_early_warning.cohort_health (
+    org_id UUID NOT NULL REFERENCES cohort_early_warning.organizations(id),
+    cohort TEXT NOT NULL,
+    week_start DATE NOT NULL,
+    health_index DOUBLE PRECISION NOT NULL CHECK (health_index >= 0 AND health_index <= 100),
+    scholars INT NOT NULL,
+    at_risk INT NOT NULL,
+    computed_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (org_id, cohort, week_start)
+);
@@ -0,0 +1,17 @@
+DROP TABLE IF EXISTS cohort_early_warning.score_cache;
+
+DO $$
+DECLARE
+    source TEXT;
+BEGIN
+    FOREACH source IN ARRAY ARRAY[
+        'signals', 'signals_archive', 'signal_attachments', 'scholars', 'advisors', 'cohorts',
+        'interventions', 'self_report_submissions', 'outcomes', 'cohort_health'
+    ] LOOP
+        EXECUTE format('DROP TRIGGER IF EXISTS data_changed ON cohort_early_warning.%I', source);
+    END LOOP;
+END;
+$$;
+
+DROP FUNCTION IF EXISTS cohort_early_warning.bump_data_version();
+DROP SEQUENCE IF EXISTS cohort_early_warning.data_version;
@@ -0,0 +1,40 @@
+-- Scores and reports computed within `[cache] ttl_secs` are reused while the data they were read
+-- from is unchanged. Any write to a table they read moves `data_version` on, so entries computed
+-- before it no longer match. One counter serves every organization: a write in one invalidates
+-- the others' entries too, which costs a recompute but never serves a stale result.
+CREATE SEQUENCE IF NOT EXISTS cohort_early_warning.data_version;
+
+CREATE OR REPLACE FUNCTION cohort_early_warning.bump_data_version() RETURNS trigger AS $$
+BEGIN
+    PERFORM nextval('cohort_early_warning.data_version');
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+DO $$
+DECLARE
+    source TEXT;
+BEGIN
+    FOREACH source IN ARRAY ARRAY[
+        'signals', 'signals_archive', 'signal_attachments', 'scholars', 'advisors', 'cohorts',
+        'interventions', 'self_report_submissions', 'outcomes', 'cohort_health'
+    ] LOOP
+        EXECUTE format(
+            'DROP TRIGGER IF EXISTS data_changed ON cohort_early_warning.%I; '
+            'CREATE TRIGGER data_changed '
+            'AFTER INSERT OR UPDATE OR DELETE OR TRUNCATE ON cohort_early_warning.%I '
+            'FOR EACH STATEMENT EXECUTE FUNCTION cohort_early_warning.bump_data_version()',
+            source, source
+        );
+    END LOOP;
+END;
+$$;
+
+CREATE TABLE IF NOT EXISTS cohort_early_warning.score_cache (
+    org_id UUID NOT NULL REFERENCES cohort_early_warning.organizations(id),
+    cache_key TEXT NOT NULL,
+    data_version BIGINT NOT NULL,
+    body TEXT NOT NULL,
+    computed_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (org_id, cache_key)
+);
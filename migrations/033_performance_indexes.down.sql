@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS cohort_early_warning.idx_cohort_early_warning_scholar_cohort;
+DROP INDEX IF EXISTS cohort_early_warning.idx_cohort_early_warning_signal_scholar_occurred;
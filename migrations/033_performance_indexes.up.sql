@@ -0,0 +1,8 @@
+-- Indexes for org-wide scoring over large signal tables. signals(occurred_at) already has
+-- idx_cohort_early_warning_occurred (001) and the keyset index (015); per-scholar reads by date and
+-- cohort-scoped scores had nothing better than scanning every row. `doctor --explain` shows
+-- whether the planner uses them.
+CREATE INDEX IF NOT EXISTS idx_cohort_early_warning_signal_scholar_occurred
+    ON cohort_early_warning.signals(scholar_id, occurred_at);
+CREATE INDEX IF NOT EXISTS idx_cohort_early_warning_scholar_cohort
+    ON cohort_early_warning.scholars(cohort);
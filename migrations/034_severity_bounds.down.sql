@@ -0,0 +1,2 @@
+ALTER TABLE cohort_early_warning.signals
+    RENAME CONSTRAINT signals_severity_range TO signals_severity_check;
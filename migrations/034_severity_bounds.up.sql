@@ -0,0 +1,4 @@
+-- Names the severity check from 001 so `migrate up` and `init-db` can replace it when [severity]
+-- in the config sets other bounds. It still enforces 1-5 until then.
+ALTER TABLE cohort_early_warning.signals
+    RENAME CONSTRAINT signals_severity_check TO signals_severity_range;
@@ -0,0 +1,2 @@
+DROP FUNCTION IF EXISTS cohort_early_warning.canonical_cohort(UUID, TEXT);
+DROP TABLE IF EXISTS cohort_early_warning.cohort_aliases;
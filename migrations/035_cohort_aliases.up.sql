@@ -0,0 +1,28 @@
+-- Other spellings of a cohort's name ("Class of 2026", "c2026") mapped to the one scholars are
+-- stored under ("2026"). Aliases are kept trimmed and lowercased, so matching ignores case and
+-- surrounding spaces. Imports store the canonical name, and cohort filters go through
+-- canonical_cohort, so either spelling selects the same scholars.
+CREATE TABLE IF NOT EXISTS cohort_early_warning.cohort_aliases (
+    org_id UUID NOT NULL REFERENCES cohort_early_warning.organizations(id),
+    alias TEXT NOT NULL CHECK (alias = lower(btrim(alias)) AND alias <> ''),
+    cohort TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (org_id, alias)
+);
+
+-- STABLE with constant arguments, so the planner evaluates it once and can still use the cohort
+-- index from 033.
+CREATE OR REPLACE FUNCTION cohort_early_warning.canonical_cohort(p_org_id UUID, p_cohort TEXT)
+RETURNS TEXT AS $$
+    SELECT COALESCE(
+        (SELECT cohort FROM cohort_early_warning.cohort_aliases
+         WHERE org_id = p_org_id AND alias = lower(btrim(p_cohort))),
+        p_cohort
+    );
+$$ LANGUAGE sql STABLE;
+
+-- Adding or removing an alias changes which scholars a cached cohort scope covers.
+DROP TRIGGER IF EXISTS data_changed ON cohort_early_warning.cohort_aliases;
+CREATE TRIGGER data_changed
+AFTER INSERT OR UPDATE OR DELETE OR TRUNCATE ON cohort_early_warning.cohort_aliases
+FOR EACH STATEMENT EXECUTE FUNCTION cohort_early_warning.bump_data_version();
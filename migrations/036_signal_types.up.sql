@@ -0,0 +1,12 @@
+-- The signal types an organization recognizes. Once an organization has any, imports and
+-- `signal add` refuse types that are missing or inactive here, so a typo such as "attendence"
+-- cannot start a type of its own. Names are kept trimmed and lowercased and matched that way.
+CREATE TABLE IF NOT EXISTS cohort_early_warning.signal_types (
+    org_id UUID NOT NULL REFERENCES cohort_early_warning.organizations(id),
+    name TEXT NOT NULL CHECK (name = lower(btrim(name)) AND name <> ''),
+    description TEXT NOT NULL DEFAULT '',
+    default_weight DOUBLE PRECISION NOT NULL DEFAULT 1.0 CHECK (default_weight >= 0),
+    active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (org_id, name)
+);
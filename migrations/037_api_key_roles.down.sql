@@ -0,0 +1,4 @@
+ALTER TABLE cohort_early_warning.api_keys
+    DROP CONSTRAINT IF EXISTS api_keys_advisor_role,
+    DROP COLUMN IF EXISTS advisor_id,
+    DROP COLUMN IF EXISTS role;
@@ -0,0 +1,12 @@
+-- Who a key acts for. Viewers read everything, advisors read and write only for the scholars
+-- assigned to `advisor_id`, and admins do anything. Existing read keys become viewers and the
+-- rest admins, so no key sees less than before.
+ALTER TABLE cohort_early_warning.api_keys
+    ADD COLUMN IF NOT EXISTS role TEXT NOT NULL DEFAULT 'viewer'
+        CHECK (role IN ('viewer', 'advisor', 'admin')),
+    ADD COLUMN IF NOT EXISTS advisor_id UUID REFERENCES cohort_early_warning.advisors(id);
+
+UPDATE cohort_early_warning.api_keys SET role = 'admin' WHERE scope <> 'read';
+
+ALTER TABLE cohort_early_warning.api_keys
+    ADD CONSTRAINT api_keys_advisor_role CHECK ((role = 'advisor') = (advisor_id IS NOT NULL));
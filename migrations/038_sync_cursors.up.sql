@@ -0,0 +1,9 @@
+-- How far each outbound sync, such as `sync bigquery`, has pushed an organization's data. The next
+-- run sends only rows logged at or after `synced_through`.
+CREATE TABLE IF NOT EXISTS cohort_early_warning.sync_cursors (
+    org_id UUID NOT NULL REFERENCES cohort_early_warning.organizations(id),
+    target TEXT NOT NULL,
+    synced_through TIMESTAMPTZ NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (org_id, target)
+);
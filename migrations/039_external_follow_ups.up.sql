@@ -0,0 +1,11 @@
+-- Follow-up statuses last pulled from external trackers by `sync airtable`, per tracker row, so a
+-- status is recorded as an intervention once, when it changes.
+CREATE TABLE IF NOT EXISTS cohort_early_warning.external_follow_ups (
+    org_id UUID NOT NULL REFERENCES cohort_early_warning.organizations(id),
+    source TEXT NOT NULL,
+    record_id TEXT NOT NULL,
+    status TEXT NOT NULL,
+    intervention_id UUID REFERENCES cohort_early_warning.interventions(id) ON DELETE SET NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (org_id, source, record_id)
+);
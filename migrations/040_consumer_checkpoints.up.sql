@@ -0,0 +1,10 @@
+-- Last stream sequence `consume` finished with for each stream and durable consumer: every message
+-- at or below it was recorded or rejected. A consumer recreated on the broker resumes after it.
+CREATE TABLE IF NOT EXISTS cohort_early_warning.consumer_checkpoints (
+    org_id UUID NOT NULL REFERENCES cohort_early_warning.organizations(id),
+    stream TEXT NOT NULL,
+    consumer TEXT NOT NULL,
+    sequence BIGINT NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (org_id, stream, consumer)
+);
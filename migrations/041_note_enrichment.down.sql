@@ -0,0 +1,21 @@
+DROP VIEW IF EXISTS cohort_early_warning.signals_with_archive;
+CREATE VIEW cohort_early_warning.signals_with_archive AS
+SELECT id, scholar_id, signal_type, severity, note, occurred_at, source_key, batch_id, deleted_at,
+       org_id, tags, submitted_by
+FROM cohort_early_warning.signals
+UNION ALL
+SELECT id, scholar_id, signal_type, severity, note, occurred_at, source_key, batch_id, deleted_at,
+       org_id, tags, submitted_by
+FROM cohort_early_warning.signals_archive;
+
+DROP INDEX IF EXISTS cohort_early_warning.idx_cohort_early_warning_signal_note_categories;
+
+ALTER TABLE cohort_early_warning.signals_archive
+    DROP COLUMN IF EXISTS note_categories,
+    DROP COLUMN IF EXISTS note_sentiment,
+    DROP COLUMN IF EXISTS enriched_at;
+
+ALTER TABLE cohort_early_warning.signals
+    DROP COLUMN IF EXISTS note_categories,
+    DROP COLUMN IF EXISTS note_sentiment,
+    DROP COLUMN IF EXISTS enriched_at;
@@ -0,0 +1,23 @@
+-- Keyword categories and sentiment that `enrich` derives from each signal's note. `enriched_at`
+-- stays NULL until the note has been read, so a run picks up where the last one stopped.
+ALTER TABLE cohort_early_warning.signals
+    ADD COLUMN IF NOT EXISTS note_categories TEXT[] NOT NULL DEFAULT '{}',
+    ADD COLUMN IF NOT EXISTS note_sentiment DOUBLE PRECISION,
+    ADD COLUMN IF NOT EXISTS enriched_at TIMESTAMPTZ;
+
+ALTER TABLE cohort_early_warning.signals_archive
+    ADD COLUMN IF NOT EXISTS note_categories TEXT[] NOT NULL DEFAULT '{}',
+    ADD COLUMN IF NOT EXISTS note_sentiment DOUBLE PRECISION,
+    ADD COLUMN IF NOT EXISTS enriched_at TIMESTAMPTZ;
+
+CREATE INDEX IF NOT EXISTS idx_cohort_early_warning_signal_note_categories
+    ON cohort_early_warning.signals USING GIN (note_categories);
+
+CREATE OR REPLACE VIEW cohort_early_warning.signals_with_archive AS
+SELECT id, scholar_id, signal_type, severity, note, occurred_at, source_key, batch_id, deleted_at,
+       org_id, tags, submitted_by, note_categories, note_sentiment
+FROM cohort_early_warning.signals
+UNION ALL
+SELECT id, scholar_id, signal_type, severity, note, occurred_at, source_key, batch_id, deleted_at,
+       org_id, tags, submitted_by, note_categories, note_sentiment
+FROM cohort_early_warning.signals_archive;
@@ -0,0 +1,2 @@
+ALTER TABLE cohort_early_warning.alert_rules
+    ADD CONSTRAINT alert_rules_min_severity_check CHECK (min_severity BETWEEN 1 AND 5);
@@ -0,0 +1,4 @@
+-- Rule severities are checked against [severity] in the config when a rule is added, as the
+-- bounds there can differ from 1-5.
+ALTER TABLE cohort_early_warning.alert_rules
+    DROP CONSTRAINT IF EXISTS alert_rules_min_severity_check;
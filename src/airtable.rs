@@ -0,0 +1,324 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use anyhow::Context;
+use chrono::Utc;
+use serde_json::{json, Map, Value};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::audit::{self, AuditAction};
+use crate::config::AirtableConfig;
+use crate::db;
+use crate::import::normalize_email;
+use crate::models::ScholarScore;
+
+/// Airtable's REST API root.
+const AIRTABLE_API: &str = "https://api.airtable.com/v0";
+
+/// Records Airtable accepts per write request.
+const RECORDS_PER_REQUEST: usize = 10;
+
+/// Pause between requests, keeping under Airtable's limit of five per second per base.
+const REQUEST_GAP: Duration = Duration::from_millis(250);
+
+/// `source` of the follow-up statuses pulled from Airtable.
+const SOURCE: &str = "airtable";
+
+/// A row's follow-up status as advisors left it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FollowUp {
+    pub record_id: String,
+    pub email: String,
+    pub status: String,
+}
+
+/// The scholar email and follow-up status of a row from a list response, or `None` when either
+/// is blank. Single selects come back as text; anything else is written out as JSON.
+pub fn follow_up(config: &AirtableConfig, record: &Value) -> Option<FollowUp> {
+    let text = |field: &str| match &record["fields"][field] {
+        Value::Null => None,
+        Value::String(text) => Some(text.trim().to_string()),
+        other => Some(other.to_string()),
+    };
+    let email = text(&config.email_field).filter(|email| !email.is_empty())?;
+    let status = text(&config.status_field).filter(|status| !status.is_empty())?;
+    Some(FollowUp {
+        record_id: record["id"].as_str()?.to_string(),
+        email: normalize_email(&email),
+        status,
+    })
+}
+
+/// Follow-ups whose status differs from the one pulled last time, keyed by record ID.
+pub fn changed<'a>(
+    follow_ups: &'a [FollowUp],
+    pulled: &HashMap<String, String>,
+) -> Vec<&'a FollowUp> {
+    follow_ups
+        .iter()
+        .filter(|follow_up| pulled.get(&follow_up.record_id) != Some(&follow_up.status))
+        .collect()
+}
+
+/// The fields written for `score`: its email and the configured score fields.
+pub fn row(config: &AirtableConfig, score: &ScholarScore) -> Map<String, Value> {
+    let mut fields: Map<String, Value> = config
+        .fields
+        .iter()
+        .map(|(field, name)| (field.clone(), score.field(name)))
+        .collect();
+    fields.insert(
+        config.email_field.clone(),
+        score.scholar_email.clone().into(),
+    );
+    fields
+}
+
+struct Table<'a> {
+    client: reqwest::Client,
+    token: String,
+    url: reqwest::Url,
+    config: &'a AirtableConfig,
+}
+
+impl<'a> Table<'a> {
+    fn new(config: &'a AirtableConfig) -> anyhow::Result<Self> {
+        let token = std::env::var(&config.token_env)
+            .with_context(|| format!("{} must be set", config.token_env))?;
+        let mut url = reqwest::Url::parse(AIRTABLE_API)?;
+        url.path_segments_mut()
+            .map_err(|_| anyhow::anyhow!("{AIRTABLE_API} cannot take a path"))?
+            .extend([&config.base_id, &config.table]);
+        Ok(Self {
+            client: reqwest::Client::new(),
+            token,
+            url,
+            config,
+        })
+    }
+
+    /// Every row with an email and a follow-up status, a page at a time.
+    async fn follow_ups(&self) -> anyhow::Result<Vec<FollowUp>> {
+        let mut follow_ups = Vec::new();
+        let mut offset: Option<String> = None;
+        loop {
+            let mut request = self
+                .client
+                .get(self.url.clone())
+                .bearer_auth(&self.token)
+                .query(&[
+                    ("fields[]", self.config.email_field.as_str()),
+                    ("fields[]", self.config.status_field.as_str()),
+                    ("pageSize", "100"),
+                ]);
+            if let Some(offset) = &offset {
+                request = request.query(&[("offset", offset)]);
+            }
+            let page: Value = request
+                .send()
+                .await?
+                .error_for_status()
+                .with_context(|| format!("could not list Airtable table {}", self.config.table))?
+                .json()
+                .await?;
+            follow_ups.extend(
+                page["records"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|record| follow_up(self.config, record)),
+            );
+            offset = page["offset"].as_str().map(str::to_string);
+            if offset.is_none() {
+                return Ok(follow_ups);
+            }
+            tokio::time::sleep(REQUEST_GAP).await;
+        }
+    }
+
+    /// Upserts a row per score, matched on the email field; rows for new scholars are created.
+    async fn push(&self, scores: &[ScholarScore]) -> anyhow::Result<()> {
+        for batch in scores.chunks(RECORDS_PER_REQUEST) {
+            let records: Vec<Value> = batch
+                .iter()
+                .map(|score| json!({ "fields": row(self.config, score) }))
+                .collect();
+            self.client
+                .patch(self.url.clone())
+                .bearer_auth(&self.token)
+                .json(&json!({
+                    "performUpsert": { "fieldsToMergeOn": [self.config.email_field] },
+                    "typecast": true,
+                    "records": records,
+                }))
+                .send()
+                .await?
+                .error_for_status()
+                .with_context(|| {
+                    format!("could not update Airtable table {}", self.config.table)
+                })?;
+            tokio::time::sleep(REQUEST_GAP).await;
+        }
+        Ok(())
+    }
+}
+
+/// What `sync airtable` did.
+#[derive(Debug, Default)]
+pub struct AirtableSummary {
+    /// Scholars whose row was written.
+    pub pushed: usize,
+    /// Status changes recorded as interventions.
+    pub recorded: usize,
+    /// Status changes on rows whose email matches no scholar, or that failed to record.
+    pub skipped: Vec<String>,
+}
+
+/// Pulls follow-up statuses changed since the last sync into interventions, then writes `scores`
+/// to the table. Statuses are pulled first so a failed push still keeps what advisors entered.
+pub async fn sync(
+    pool: &PgPool,
+    org_id: Uuid,
+    config: &AirtableConfig,
+    scores: &[ScholarScore],
+) -> anyhow::Result<AirtableSummary> {
+    let table = Table::new(config)?;
+    let mut summary = AirtableSummary::default();
+
+    let follow_ups = table.follow_ups().await?;
+    let pulled = db::external_follow_ups(pool, org_id, SOURCE).await?;
+    for follow_up in changed(&follow_ups, &pulled) {
+        let intervention = db::NewIntervention {
+            scholar_email: follow_up.email.clone(),
+            occurred_on: Utc::now().date_naive(),
+            intervention_type: config.intervention_type.clone(),
+            outcome: Some(follow_up.status.clone()),
+            note: format!("Follow-up status set in Airtable ({})", config.table),
+            signal_keys: Vec::new(),
+        };
+        match db::add_intervention(pool, org_id, &intervention).await {
+            Ok(id) => {
+                db::set_external_follow_up(
+                    pool,
+                    org_id,
+                    SOURCE,
+                    &follow_up.record_id,
+                    &follow_up.status,
+                    id,
+                )
+                .await?;
+                summary.recorded += 1;
+            }
+            Err(err) => summary.skipped.push(format!(
+                "{} ({}): {err:#}",
+                follow_up.email, follow_up.record_id
+            )),
+        }
+    }
+    if summary.recorded > 0 {
+        audit::record(
+            pool,
+            "sync airtable",
+            AuditAction::Insert,
+            "interventions",
+            summary.recorded as u64,
+            &format!("follow-up statuses from {}", config.table),
+        )
+        .await?;
+    }
+
+    table.push(scores).await?;
+    summary.pushed = scores.len();
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{AirtableConfig, Config};
+    use crate::risk::Tier;
+
+    fn config() -> AirtableConfig {
+        Config::parse("[airtable]\nbase_id = \"appTest\"")
+            .unwrap()
+            .airtable
+            .unwrap()
+    }
+
+    #[test]
+    fn reads_follow_ups_with_an_email_and_status() {
+        let config = config();
+        let record = |fields: Value| json!({ "id": "rec1", "fields": fields });
+        assert_eq!(
+            follow_up(
+                &config,
+                &record(json!({ "Email": "Ada@Example.edu ", "Follow-up Status": "Called" }))
+            ),
+            Some(FollowUp {
+                record_id: "rec1".to_string(),
+                email: "ada@example.edu".to_string(),
+                status: "Called".to_string(),
+            })
+        );
+        assert_eq!(
+            follow_up(&config, &record(json!({ "Email": "ada@example.edu" }))),
+            None
+        );
+        assert_eq!(
+            follow_up(
+                &config,
+                &record(json!({ "Email": "ada@example.edu", "Follow-up Status": " " }))
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn only_changed_statuses_are_recorded() {
+        let follow_up = |record_id: &str, status: &str| FollowUp {
+            record_id: record_id.to_string(),
+            email: "ada@example.edu".to_string(),
+            status: status.to_string(),
+        };
+        let follow_ups = [
+            follow_up("rec1", "Called"),
+            follow_up("rec2", "Met"),
+            follow_up("rec3", "Called"),
+        ];
+        let pulled = HashMap::from([
+            ("rec1".to_string(), "Called".to_string()),
+            ("rec2".to_string(), "Called".to_string()),
+        ]);
+        let changed: Vec<&str> = changed(&follow_ups, &pulled)
+            .into_iter()
+            .map(|follow_up| follow_up.record_id.as_str())
+            .collect();
+        assert_eq!(changed, ["rec2", "rec3"]);
+    }
+
+    #[test]
+    fn rows_carry_the_email_and_score_fields() {
+        let score = ScholarScore {
+            scholar_name: "Ada Park".to_string(),
+            scholar_email: "ada@example.edu".to_string(),
+            cohort: "2026".to_string(),
+            score: 9.25,
+            signal_count: 3,
+            advisor: None,
+            tier: Tier::High,
+            escalated_by: None,
+            collapsed_signals: 0,
+            percentile: 90.0,
+            cohort_percentile: 95.0,
+            last_contact: None,
+            last_signal: None,
+        };
+        let row = row(&config(), &score);
+        assert_eq!(row["Email"], "ada@example.edu");
+        assert_eq!(row["Name"], "Ada Park");
+        assert_eq!(row["Risk Score"], 9.25);
+        assert_eq!(row["Tier"], "high");
+        assert!(!row.contains_key("Follow-up Status"));
+    }
+}
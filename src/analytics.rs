@@ -0,0 +1,507 @@
+use std::collections::{BTreeMap, HashMap};
+
+#[cfg(feature = "postgres")]
+use sqlx::PgPool;
+#[cfg(feature = "postgres")]
+use uuid::Uuid;
+
+use crate::clusters::Cluster;
+use crate::models::{Scholar, ScholarScore};
+use crate::risk::Tier;
+#[cfg(feature = "postgres")]
+use crate::risk::{ScoreAccumulator, ScoringConfig};
+
+/// Metric `analytics cohorts` ranks by, highest first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum RankBy {
+    /// Share of active scholars at or above the risk threshold
+    AtRisk,
+    SignalsPerScholar,
+    AvgSeverity,
+}
+
+impl RankBy {
+    fn label(self) -> &'static str {
+        match self {
+            RankBy::AtRisk => "% at risk",
+            RankBy::SignalsPerScholar => "signals per scholar",
+            RankBy::AvgSeverity => "avg severity",
+        }
+    }
+}
+
+/// One cohort's row in `analytics cohorts`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CohortAnalytics {
+    pub cohort: String,
+    /// Active scholars on the roster, whether or not they have signals in the window.
+    pub scholars: i64,
+    pub signals: i64,
+    pub avg_severity: f64,
+    /// Scholars scored at or above the risk threshold.
+    pub at_risk: usize,
+}
+
+impl CohortAnalytics {
+    pub fn new(
+        cohort: &str,
+        scholars: i64,
+        signals: i64,
+        severity_total: i64,
+        scores: &[ScholarScore],
+        threshold: Tier,
+    ) -> Self {
+        Self {
+            cohort: cohort.to_string(),
+            scholars,
+            signals,
+            avg_severity: ratio(severity_total as f64, signals),
+            at_risk: scores
+                .iter()
+                .filter(|score| score.tier >= threshold)
+                .count(),
+        }
+    }
+
+    pub fn signals_per_scholar(&self) -> f64 {
+        ratio(self.signals as f64, self.scholars)
+    }
+
+    pub fn at_risk_pct(&self) -> f64 {
+        ratio(self.at_risk as f64 * 100.0, self.scholars)
+    }
+
+    fn metric(&self, by: RankBy) -> f64 {
+        match by {
+            RankBy::AtRisk => self.at_risk_pct(),
+            RankBy::SignalsPerScholar => self.signals_per_scholar(),
+            RankBy::AvgSeverity => self.avg_severity,
+        }
+    }
+}
+
+fn ratio(value: f64, count: i64) -> f64 {
+    if count > 0 {
+        value / count as f64
+    } else {
+        0.0
+    }
+}
+
+/// Orders cohorts by `by`, highest first, then by name.
+pub fn rank(cohorts: &mut [CohortAnalytics], by: RankBy) {
+    cohorts.sort_by(|a, b| {
+        b.metric(by)
+            .total_cmp(&a.metric(by))
+            .then_with(|| a.cohort.cmp(&b.cohort))
+    });
+}
+
+/// Renders cohorts already ordered by [`rank`] as a table. Tied cohorts share a rank.
+pub fn render(cohorts: &[CohortAnalytics], by: RankBy, threshold: Tier, since_days: i64) -> String {
+    let mut rows = vec![[
+        "rank",
+        "cohort",
+        "scholars",
+        "signals",
+        "signals/scholar",
+        "avg severity",
+        "at risk",
+    ]
+    .map(String::from)];
+    for cohort in cohorts {
+        let rank = 1 + cohorts
+            .iter()
+            .filter(|other| other.metric(by) > cohort.metric(by))
+            .count();
+        rows.push([
+            rank.to_string(),
+            cohort.cohort.clone(),
+            cohort.scholars.to_string(),
+            cohort.signals.to_string(),
+            format!("{:.2}", cohort.signals_per_scholar()),
+            format!("{:.2}", cohort.avg_severity),
+            format!("{} ({:.0}%)", cohort.at_risk, cohort.at_risk_pct()),
+        ]);
+    }
+
+    let widths: Vec<usize> = (0..rows[0].len())
+        .map(|column| rows.iter().map(|row| row[column].len()).max().unwrap_or(0))
+        .collect();
+    let mut out = vec![
+        format!(
+            "Cohorts over the last {since_days} days ranked by {} (at risk = {} tier or above):",
+            by.label(),
+            threshold.as_str()
+        ),
+        String::new(),
+    ];
+    for row in &rows {
+        let line: Vec<String> = row
+            .iter()
+            .zip(&widths)
+            .enumerate()
+            .map(|(column, (cell, width))| match column {
+                1 => format!("{cell:<width$}"),
+                _ => format!("{cell:>width$}"),
+            })
+            .collect();
+        out.push(line.join("  ").trim_end().to_string());
+    }
+    out.join("\n")
+}
+
+/// Renders signal profile clusters with their members, largest first.
+pub fn render_clusters(clusters: &[Cluster], scores: &[ScholarScore], since_days: i64) -> String {
+    let scholars: usize = clusters.iter().map(|cluster| cluster.scholars.len()).sum();
+    let mut out = vec![format!(
+        "{} signal profiles among {scholars} scholars with signals in the last {since_days} days:",
+        clusters.len()
+    )];
+    for (index, cluster) in clusters.iter().enumerate() {
+        let mix: Vec<String> = cluster
+            .ranked_mix()
+            .iter()
+            .map(|(signal_type, share)| format!("{signal_type} {:.0}%", share * 100.0))
+            .collect();
+        out.push(String::new());
+        out.push(format!(
+            "{}. {}: {} scholars (avg score {:.2})",
+            index + 1,
+            cluster.label,
+            cluster.scholars.len(),
+            cluster.avg_score(scores)
+        ));
+        out.push(format!(
+            "   mix: {}; avg severity {:.1}",
+            mix.join(", "),
+            cluster.avg_severity
+        ));
+        out.push(format!("   scholars: {}", cluster.scholars.join(", ")));
+    }
+    out.join("\n")
+}
+
+/// One advisor's row in `analytics caseloads`.
+#[derive(Debug, Clone)]
+pub struct Caseload {
+    /// Advisor name; `None` for scholars nobody is assigned to.
+    pub advisor: Option<String>,
+    pub scholars: usize,
+    /// Scholars in each tier, critical first. Scholars without signals count as low.
+    pub tiers: [usize; 4],
+    /// The three highest-scoring scholars, highest first.
+    pub top: Vec<ScholarScore>,
+}
+
+impl Caseload {
+    pub fn at_risk(&self, threshold: Tier) -> usize {
+        TIERS
+            .iter()
+            .zip(self.tiers)
+            .filter(|(tier, _)| **tier >= threshold)
+            .map(|(_, count)| count)
+            .sum()
+    }
+
+    fn at_risk_pct(&self, threshold: Tier) -> f64 {
+        ratio(self.at_risk(threshold) as f64 * 100.0, self.scholars as i64)
+    }
+}
+
+const TIERS: [Tier; 4] = [Tier::Critical, Tier::High, Tier::Moderate, Tier::Low];
+
+/// Groups the active `roster` by advisor and tallies each caseload's tiers from `scores`.
+/// Caseloads with the largest share at or above `threshold` come first; unassigned scholars last.
+pub fn caseloads(roster: &[Scholar], scores: &[ScholarScore], threshold: Tier) -> Vec<Caseload> {
+    let by_email: HashMap<&str, &ScholarScore> = scores
+        .iter()
+        .map(|score| (score.scholar_email.as_str(), score))
+        .collect();
+    let mut grouped: BTreeMap<Option<&str>, Vec<&Scholar>> = BTreeMap::new();
+    for scholar in roster {
+        grouped
+            .entry(scholar.advisor.as_deref())
+            .or_default()
+            .push(scholar);
+    }
+
+    let mut caseloads: Vec<Caseload> = grouped
+        .into_iter()
+        .map(|(advisor, scholars)| {
+            let mut tiers = [0; 4];
+            let mut top: Vec<ScholarScore> = Vec::new();
+            for scholar in &scholars {
+                let score = by_email.get(scholar.email.as_str());
+                let tier = score.map_or(Tier::Low, |score| score.tier);
+                tiers[TIERS.iter().position(|t| *t == tier).unwrap_or(3)] += 1;
+                top.extend(score.map(|score| (*score).clone()));
+            }
+            top.sort_by(|a, b| {
+                b.score
+                    .total_cmp(&a.score)
+                    .then_with(|| a.scholar_email.cmp(&b.scholar_email))
+            });
+            top.truncate(3);
+            Caseload {
+                advisor: advisor.map(str::to_string),
+                scholars: scholars.len(),
+                tiers,
+                top,
+            }
+        })
+        .collect();
+    caseloads.sort_by(|a, b| {
+        a.advisor
+            .is_none()
+            .cmp(&b.advisor.is_none())
+            .then_with(|| {
+                b.at_risk_pct(threshold)
+                    .total_cmp(&a.at_risk_pct(threshold))
+            })
+            .then_with(|| a.advisor.cmp(&b.advisor))
+    });
+    caseloads
+}
+
+/// Renders caseloads from [`caseloads`] as a table, then names advisors carrying at least twice
+/// the overall share of at-risk scholars.
+pub fn render_caseloads(caseloads: &[Caseload], threshold: Tier, since_days: i64) -> String {
+    let mut rows = vec![[
+        "advisor", "scholars", "critical", "high", "moderate", "low", "at risk", "top risk",
+    ]
+    .map(String::from)];
+    for caseload in caseloads {
+        let top: Vec<String> = caseload
+            .top
+            .iter()
+            .map(|score| format!("{} ({:.2})", score.scholar_email, score.score))
+            .collect();
+        rows.push([
+            caseload
+                .advisor
+                .clone()
+                .unwrap_or_else(|| "(unassigned)".to_string()),
+            caseload.scholars.to_string(),
+            caseload.tiers[0].to_string(),
+            caseload.tiers[1].to_string(),
+            caseload.tiers[2].to_string(),
+            caseload.tiers[3].to_string(),
+            format!(
+                "{} ({:.0}%)",
+                caseload.at_risk(threshold),
+                caseload.at_risk_pct(threshold)
+            ),
+            top.join(", "),
+        ]);
+    }
+
+    let widths: Vec<usize> = (0..rows[0].len())
+        .map(|column| rows.iter().map(|row| row[column].len()).max().unwrap_or(0))
+        .collect();
+    let mut out = vec![
+        format!(
+            "Active scholars by advisor over the last {since_days} days (at risk = {} tier or above):",
+            threshold.as_str()
+        ),
+        String::new(),
+    ];
+    for row in &rows {
+        let line: Vec<String> = row
+            .iter()
+            .zip(&widths)
+            .enumerate()
+            .map(|(column, (cell, width))| match column {
+                0 | 7 => format!("{cell:<width$}"),
+                _ => format!("{cell:>width$}"),
+            })
+            .collect();
+        out.push(line.join("  ").trim_end().to_string());
+    }
+
+    let scholars: usize = caseloads.iter().map(|c| c.scholars).sum();
+    let at_risk: usize = caseloads.iter().map(|c| c.at_risk(threshold)).sum();
+    let overall = ratio(at_risk as f64 * 100.0, scholars as i64);
+    out.push(String::new());
+    out.push(format!(
+        "Overall: {at_risk} of {scholars} scholars at risk ({overall:.0}%)."
+    ));
+    let heavy: Vec<&str> = caseloads
+        .iter()
+        .filter(|c| overall > 0.0 && c.at_risk_pct(threshold) >= overall * 2.0)
+        .filter_map(|c| c.advisor.as_deref())
+        .collect();
+    if !heavy.is_empty() {
+        out.push(format!(
+            "At least twice the overall share at risk: {}.",
+            heavy.join(", ")
+        ));
+    }
+    out.join("\n")
+}
+
+/// Scores every cohort with active scholars in one pass over the window's signals. Inactive
+/// scholars are left out, as in `score`.
+#[cfg(feature = "postgres")]
+pub async fn summarize(
+    pool: &PgPool,
+    org_id: Uuid,
+    since_days: i64,
+    threshold: Tier,
+    scoring: &ScoringConfig,
+) -> anyhow::Result<Vec<CohortAnalytics>> {
+    let stats = crate::db::database_stats(pool, org_id).await?;
+    let filter = crate::db::SignalFilter {
+        org_id: Some(org_id),
+        ..crate::db::SignalFilter::new(crate::risk::cutoff_date(since_days))
+    };
+    let today = chrono::Utc::now().date_naive();
+    let mut accumulator = ScoreAccumulator::new(since_days, today, scoring);
+    // Signal count and severity total per cohort.
+    let mut totals: BTreeMap<String, (i64, i64)> = BTreeMap::new();
+    crate::db::for_each_signal(pool, &filter, crate::db::DEFAULT_PAGE_SIZE, |signal| {
+        let (signals, severity) = totals.entry(signal.cohort.clone()).or_default();
+        *signals += 1;
+        *severity += i64::from(signal.severity);
+        accumulator.push(&signal);
+    })
+    .await?;
+
+    let mut scores: BTreeMap<String, Vec<ScholarScore>> = BTreeMap::new();
+    for score in accumulator.finish() {
+        scores.entry(score.cohort.clone()).or_default().push(score);
+    }
+    Ok(stats
+        .cohorts
+        .into_iter()
+        .filter(|(_, _, active)| *active > 0)
+        .map(|(cohort, _, active)| {
+            let (signals, severity) = totals.get(&cohort).copied().unwrap_or_default();
+            CohortAnalytics::new(
+                &cohort,
+                active,
+                signals,
+                severity,
+                scores.get(&cohort).map_or(&[], Vec::as_slice),
+                threshold,
+            )
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn score(tier: Tier) -> ScholarScore {
+        ScholarScore {
+            scholar_name: "Avery Lee".to_string(),
+            scholar_email: "avery@example.org".to_string(),
+            cohort: "2026".to_string(),
+            score: 5.0,
+            signal_count: 2,
+            advisor: None,
+            tier,
+            escalated_by: None,
+            collapsed_signals: 0,
+            percentile: 0.0,
+            cohort_percentile: 0.0,
+            last_contact: None,
+            last_signal: None,
+        }
+    }
+
+    #[test]
+    fn summarizes_caseloads_by_advisor() {
+        let scholar = |email: &str, advisor: Option<&str>| Scholar {
+            full_name: email.to_string(),
+            email: email.to_string(),
+            cohort: "2026".to_string(),
+            status: "active".to_string(),
+            advisor: advisor.map(str::to_string),
+            created_at: chrono::Utc::now(),
+            deleted: false,
+        };
+        let scored = |email: &str, tier, value| ScholarScore {
+            scholar_email: email.to_string(),
+            score: value,
+            ..score(tier)
+        };
+        let roster = [
+            scholar("a@x.org", Some("Bo Diaz")),
+            scholar("b@x.org", Some("Bo Diaz")),
+            scholar("c@x.org", Some("Ana Ruiz")),
+            scholar("d@x.org", Some("Ana Ruiz")),
+            scholar("e@x.org", Some("Ana Ruiz")),
+            scholar("f@x.org", Some("Ana Ruiz")),
+            scholar("g@x.org", None),
+        ];
+        let scores = [
+            scored("a@x.org", Tier::Critical, 12.0),
+            scored("b@x.org", Tier::High, 7.5),
+            scored("c@x.org", Tier::Moderate, 4.0),
+            scored("g@x.org", Tier::High, 8.0),
+        ];
+
+        let caseloads = caseloads(&roster, &scores, Tier::High);
+        let advisors: Vec<Option<&str>> = caseloads.iter().map(|c| c.advisor.as_deref()).collect();
+        assert_eq!(advisors, [Some("Bo Diaz"), Some("Ana Ruiz"), None]);
+        assert_eq!(caseloads[1].tiers, [0, 0, 1, 3]);
+        assert_eq!(caseloads[0].top.len(), 2);
+
+        let table = render_caseloads(&caseloads, Tier::High, 30);
+        assert!(
+            table.contains(
+                "Bo Diaz              2         1     1         0    0  2 (100%)  \
+                 a@x.org (12.00), b@x.org (7.50)\n"
+            ),
+            "{table}"
+        );
+        assert!(table.contains("(unassigned)         1         0     1"));
+        assert!(table.contains("Overall: 3 of 7 scholars at risk (43%)."));
+        assert!(table.ends_with("At least twice the overall share at risk: Bo Diaz."));
+    }
+
+    #[test]
+    fn ranks_cohorts_with_shared_places_for_ties() {
+        let mut cohorts = vec![
+            CohortAnalytics::new("2024", 10, 12, 24, &[score(Tier::High)], Tier::High),
+            CohortAnalytics::new(
+                "2025",
+                4,
+                10,
+                35,
+                &[score(Tier::Critical), score(Tier::Moderate)],
+                Tier::High,
+            ),
+            CohortAnalytics::new("2026", 5, 0, 0, &[], Tier::High),
+            CohortAnalytics::new(
+                "2027",
+                20,
+                30,
+                60,
+                &[score(Tier::High), score(Tier::High)],
+                Tier::High,
+            ),
+        ];
+        assert_eq!(cohorts[1].avg_severity, 3.5);
+        assert_eq!(cohorts[1].at_risk_pct(), 25.0);
+
+        rank(&mut cohorts, RankBy::AtRisk);
+        let table = render(&cohorts, RankBy::AtRisk, Tier::High, 30);
+        let expected = [
+            "Cohorts over the last 30 days ranked by % at risk (at risk = high tier or above):",
+            "",
+            "rank  cohort  scholars  signals  signals/scholar  avg severity  at risk",
+            "   1  2025           4       10             2.50          3.50  1 (25%)",
+            "   2  2024          10       12             1.20          2.00  1 (10%)",
+            "   2  2027          20       30             1.50          2.00  2 (10%)",
+            "   4  2026           5        0             0.00          0.00   0 (0%)",
+        ];
+        assert_eq!(table, expected.join("\n"));
+
+        rank(&mut cohorts, RankBy::SignalsPerScholar);
+        let order: Vec<&str> = cohorts.iter().map(|c| c.cohort.as_str()).collect();
+        assert_eq!(order, ["2025", "2027", "2024", "2026"]);
+    }
+}
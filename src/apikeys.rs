@@ -0,0 +1,299 @@
+use axum::http::Method;
+use chrono::{DateTime, Utc};
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+use sqlx::{PgPool, Row};
+use uuid::Uuid;
+
+/// Every key starts with this, so bearer tokens that cannot be keys are rejected without a lookup.
+pub const KEY_PREFIX: &str = "cew_";
+
+/// What a key may do; each scope includes the ones before it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, clap::ValueEnum)]
+pub enum Scope {
+    /// Every `GET` endpoint and GraphQL queries.
+    Read,
+    /// Also `POST /signals` and `POST /alerts/ack`.
+    Write,
+    /// Everything, like `CEW_API_TOKEN`.
+    Admin,
+}
+
+impl Scope {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Scope::Read => "read",
+            Scope::Write => "write",
+            Scope::Admin => "admin",
+        }
+    }
+
+    fn parse(value: &str) -> anyhow::Result<Self> {
+        <Self as clap::ValueEnum>::from_str(value, true)
+            .map_err(|_| anyhow::anyhow!("unknown API key scope {value}"))
+    }
+}
+
+/// Whose data a key sees. A key's scope can't exceed what its role allows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Role {
+    /// Reads everything.
+    Viewer,
+    /// Reads and writes only for the scholars assigned to one advisor.
+    Advisor,
+    /// Everything.
+    Admin,
+}
+
+impl Role {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Role::Viewer => "viewer",
+            Role::Advisor => "advisor",
+            Role::Admin => "admin",
+        }
+    }
+
+    fn parse(value: &str) -> anyhow::Result<Self> {
+        <Self as clap::ValueEnum>::from_str(value, true)
+            .map_err(|_| anyhow::anyhow!("unknown API key role {value}"))
+    }
+
+    /// The widest scope a key with this role may have.
+    pub fn max_scope(self) -> Scope {
+        match self {
+            Role::Viewer => Scope::Read,
+            Role::Advisor => Scope::Write,
+            Role::Admin => Scope::Admin,
+        }
+    }
+}
+
+/// The role and scope `api-key create` gives a key. Without a role, read keys are viewers and
+/// others admins, as keys made before roles were; without a scope, the key gets all its role
+/// allows.
+pub fn role_and_scope(role: Option<Role>, scope: Option<Scope>) -> anyhow::Result<(Role, Scope)> {
+    let role = role.unwrap_or(match scope {
+        None | Some(Scope::Read) => Role::Viewer,
+        Some(_) => Role::Admin,
+    });
+    let scope = scope.unwrap_or(role.max_scope());
+    anyhow::ensure!(
+        scope <= role.max_scope(),
+        "{} keys can have at most {} scope, not {}",
+        role.as_str(),
+        role.max_scope().as_str(),
+        scope.as_str()
+    );
+    Ok((role, scope))
+}
+
+/// Scope a request needs: reads for `GET` and GraphQL (which only has queries), writes otherwise.
+pub fn required_scope(method: &Method, path: &str) -> Scope {
+    if method == Method::GET || method == Method::HEAD || path == "/graphql" {
+        Scope::Read
+    } else {
+        Scope::Write
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ApiKey {
+    pub name: String,
+    pub scope: Scope,
+    pub role: Role,
+    /// Email of the advisor whose scholars an advisor key is limited to.
+    pub advisor: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+}
+
+fn hash_key(key: &str) -> String {
+    Sha256::digest(key.as_bytes())
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+fn new_key() -> String {
+    let mut bytes = [0u8; 24];
+    rand::rng().fill_bytes(&mut bytes);
+    let hex: String = bytes.iter().map(|byte| format!("{byte:02x}")).collect();
+    format!("{KEY_PREFIX}{hex}")
+}
+
+/// A live key's name, scope, role, and for advisor keys the advisor's email.
+#[derive(Debug, Clone)]
+pub struct Grant {
+    pub name: String,
+    pub scope: Scope,
+    pub role: Role,
+    pub advisor: Option<String>,
+}
+
+/// Creates a key and returns it; it can't be recovered later. Advisor keys need `advisor`, the
+/// email of a registered advisor; other roles must not have one.
+pub async fn create(
+    pool: &PgPool,
+    org_id: Uuid,
+    name: &str,
+    scope: Scope,
+    role: Role,
+    advisor: Option<&str>,
+) -> anyhow::Result<String> {
+    let advisor_id: Option<Uuid> = match (role, advisor) {
+        (Role::Advisor, Some(email)) => Some(
+            sqlx::query_scalar(
+                "SELECT id FROM cohort_early_warning.advisors WHERE org_id = $1 AND email = $2",
+            )
+            .bind(org_id)
+            .bind(email)
+            .fetch_optional(pool)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("no advisor found with email {email}"))?,
+        ),
+        (Role::Advisor, None) => anyhow::bail!("advisor keys need --advisor"),
+        (_, Some(_)) => anyhow::bail!("only advisor keys take --advisor"),
+        (_, None) => None,
+    };
+    let key = new_key();
+    sqlx::query(
+        "INSERT INTO cohort_early_warning.api_keys \
+         (id, org_id, name, scope, role, advisor_id, key_hash) \
+         VALUES ($1, $2, $3, $4, $5, $6, $7)",
+    )
+    .bind(Uuid::new_v4())
+    .bind(org_id)
+    .bind(name)
+    .bind(scope.as_str())
+    .bind(role.as_str())
+    .bind(advisor_id)
+    .bind(hash_key(&key))
+    .execute(pool)
+    .await
+    .map_err(|err| match &err {
+        sqlx::Error::Database(db) if db.is_unique_violation() => {
+            anyhow::anyhow!("an API key named {name} already exists; revoke it first")
+        }
+        _ => err.into(),
+    })?;
+    Ok(key)
+}
+
+/// Keys that haven't been revoked, by name.
+pub async fn list(pool: &PgPool, org_id: Uuid) -> anyhow::Result<Vec<ApiKey>> {
+    let rows = sqlx::query(
+        "SELECT k.name, k.scope, k.role, a.email AS advisor, k.created_at, k.last_used_at \
+         FROM cohort_early_warning.api_keys k \
+         LEFT JOIN cohort_early_warning.advisors a ON a.id = k.advisor_id \
+         WHERE k.org_id = $1 AND k.revoked_at IS NULL ORDER BY k.name",
+    )
+    .bind(org_id)
+    .fetch_all(pool)
+    .await?;
+    rows.into_iter()
+        .map(|row| {
+            Ok(ApiKey {
+                name: row.get("name"),
+                scope: Scope::parse(row.get("scope"))?,
+                role: Role::parse(row.get("role"))?,
+                advisor: row.get("advisor"),
+                created_at: row.get("created_at"),
+                last_used_at: row.get("last_used_at"),
+            })
+        })
+        .collect()
+}
+
+/// Revokes the named key; returns how many keys were revoked (0 or 1).
+pub async fn revoke(pool: &PgPool, org_id: Uuid, name: &str) -> anyhow::Result<u64> {
+    Ok(sqlx::query(
+        "UPDATE cohort_early_warning.api_keys SET revoked_at = NOW() \
+         WHERE org_id = $1 AND name = $2 AND revoked_at IS NULL",
+    )
+    .bind(org_id)
+    .bind(name)
+    .execute(pool)
+    .await?
+    .rows_affected())
+}
+
+/// Whether the organization has any live key, in which case `serve` requires one.
+pub async fn any_active(pool: &PgPool, org_id: Uuid) -> anyhow::Result<bool> {
+    Ok(sqlx::query_scalar(
+        "SELECT EXISTS (SELECT 1 FROM cohort_early_warning.api_keys \
+         WHERE org_id = $1 AND revoked_at IS NULL)",
+    )
+    .bind(org_id)
+    .fetch_one(pool)
+    .await?)
+}
+
+/// What a live key grants, noting that it was used; `None` for unknown or revoked keys.
+pub async fn authenticate(pool: &PgPool, org_id: Uuid, key: &str) -> anyhow::Result<Option<Grant>> {
+    if !key.starts_with(KEY_PREFIX) {
+        return Ok(None);
+    }
+    let row = sqlx::query(
+        "UPDATE cohort_early_warning.api_keys SET last_used_at = NOW() \
+         WHERE org_id = $1 AND key_hash = $2 AND revoked_at IS NULL \
+         RETURNING name, scope, role, \
+         (SELECT email FROM cohort_early_warning.advisors WHERE id = advisor_id) AS advisor",
+    )
+    .bind(org_id)
+    .bind(hash_key(key))
+    .fetch_optional(pool)
+    .await?;
+    row.map(|row| {
+        Ok(Grant {
+            name: row.get("name"),
+            scope: Scope::parse(row.get("scope"))?,
+            role: Role::parse(row.get("role"))?,
+            advisor: row.get("advisor"),
+        })
+    })
+    .transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn writes_need_a_write_key_and_queries_a_read_key() {
+        assert_eq!(required_scope(&Method::GET, "/scores"), Scope::Read);
+        assert_eq!(required_scope(&Method::POST, "/graphql"), Scope::Read);
+        assert_eq!(required_scope(&Method::POST, "/signals"), Scope::Write);
+        assert!(Scope::Admin > Scope::Write && Scope::Write > Scope::Read);
+
+        let key = new_key();
+        assert!(key.starts_with(KEY_PREFIX));
+        assert_ne!(key, new_key());
+        assert_eq!(hash_key(&key).len(), 64);
+    }
+
+    #[test]
+    fn roles_cap_the_scope_and_default_from_it() {
+        assert_eq!(
+            role_and_scope(None, None).unwrap(),
+            (Role::Viewer, Scope::Read)
+        );
+        assert_eq!(
+            role_and_scope(None, Some(Scope::Write)).unwrap(),
+            (Role::Admin, Scope::Write)
+        );
+        assert_eq!(
+            role_and_scope(Some(Role::Advisor), None).unwrap(),
+            (Role::Advisor, Scope::Write)
+        );
+        assert_eq!(
+            role_and_scope(Some(Role::Advisor), Some(Scope::Read)).unwrap(),
+            (Role::Advisor, Scope::Read)
+        );
+        let err = role_and_scope(Some(Role::Viewer), Some(Scope::Write)).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "viewer keys can have at most read scope, not write"
+        );
+    }
+}
@@ -0,0 +1,119 @@
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, Postgres, QueryBuilder, Row};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuditAction {
+    Insert,
+    Update,
+    Delete,
+    Migrate,
+}
+
+impl AuditAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditAction::Insert => "insert",
+            AuditAction::Update => "update",
+            AuditAction::Delete => "delete",
+            AuditAction::Migrate => "migrate",
+        }
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct AuditEntry {
+    pub id: i64,
+    pub occurred_at: DateTime<Utc>,
+    pub actor: String,
+    pub command: String,
+    pub action: String,
+    pub entity: String,
+    pub affected_rows: i64,
+    pub detail: String,
+}
+
+/// Identifies who ran the CLI: `CEW_ACTOR` when set, otherwise the OS user.
+pub fn current_actor() -> String {
+    ["CEW_ACTOR", "USER", "USERNAME"]
+        .iter()
+        .find_map(|key| std::env::var(key).ok().filter(|value| !value.is_empty()))
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+pub async fn record(
+    pool: &PgPool,
+    command: &str,
+    action: AuditAction,
+    entity: &str,
+    affected_rows: u64,
+    detail: &str,
+) -> anyhow::Result<()> {
+    sqlx::query(
+        r#"
+        INSERT INTO cohort_early_warning.audit_log
+        (actor, command, action, entity, affected_rows, detail)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        "#,
+    )
+    .bind(current_actor())
+    .bind(command)
+    .bind(action.as_str())
+    .bind(entity)
+    .bind(affected_rows as i64)
+    .bind(detail)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct AuditQuery {
+    pub since: Option<DateTime<Utc>>,
+    pub actor: Option<String>,
+    pub command: Option<String>,
+    /// Only entries whose detail contains this text, ignoring case.
+    pub mentioning: Option<String>,
+    pub limit: i64,
+}
+
+pub async fn list(pool: &PgPool, filter: &AuditQuery) -> anyhow::Result<Vec<AuditEntry>> {
+    let mut query: QueryBuilder<Postgres> = QueryBuilder::new(
+        "SELECT id, occurred_at, actor, command, action, entity, affected_rows, detail \
+         FROM cohort_early_warning.audit_log WHERE TRUE",
+    );
+
+    if let Some(since) = filter.since {
+        query.push(" AND occurred_at >= ");
+        query.push_bind(since);
+    }
+    if let Some(actor) = &filter.actor {
+        query.push(" AND actor = ");
+        query.push_bind(actor.clone());
+    }
+    if let Some(command) = &filter.command {
+        query.push(" AND command = ");
+        query.push_bind(command.clone());
+    }
+    if let Some(text) = &filter.mentioning {
+        query.push(" AND strpos(lower(detail), lower(");
+        query.push_bind(text.clone());
+        query.push(")) > 0");
+    }
+    query.push(" ORDER BY occurred_at DESC, id DESC LIMIT ");
+    query.push_bind(filter.limit);
+
+    let rows = query.build().fetch_all(pool).await?;
+    Ok(rows
+        .into_iter()
+        .map(|row| AuditEntry {
+            id: row.get("id"),
+            occurred_at: row.get("occurred_at"),
+            actor: row.get("actor"),
+            command: row.get("command"),
+            action: row.get("action"),
+            entity: row.get("entity"),
+            affected_rows: row.get("affected_rows"),
+            detail: row.get("detail"),
+        })
+        .collect())
+}
@@ -0,0 +1,473 @@
+use std::time::Duration;
+
+use anyhow::Context;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::config::BigQueryConfig;
+use crate::db;
+use crate::export::{Column, ColumnType, ExportRow, SnapshotRow, EXPORT_COLUMNS, SNAPSHOT_COLUMNS};
+use crate::google::ServiceAccount;
+
+/// BigQuery's REST API root.
+const BIGQUERY_API: &str = "https://bigquery.googleapis.com/bigquery/v2";
+
+/// OAuth scope the service account asks for.
+const BIGQUERY_SCOPE: &str = "https://www.googleapis.com/auth/bigquery";
+
+/// Tries per insert request before the sync fails, waiting `RETRY_DELAY` times the attempt number
+/// in between. Inserts into a table created moments ago can 404 for a short while.
+const INSERT_ATTEMPTS: u32 = 4;
+const RETRY_DELAY: Duration = Duration::from_secs(5);
+
+/// Table the new signals go to.
+pub const SIGNALS_TABLE: &str = "signals";
+/// Table the new score snapshots go to.
+pub const SNAPSHOTS_TABLE: &str = "score_snapshots";
+
+/// Columns of the signals table: those of `export`, minus the note, so free text about scholars
+/// stays in this system.
+pub fn signal_columns() -> Vec<Column> {
+    EXPORT_COLUMNS
+        .into_iter()
+        .filter(|(name, _)| *name != "note")
+        .collect()
+}
+
+/// BigQuery schema fields for `columns`: lists become repeated fields and only optional text is
+/// nullable.
+pub fn table_fields(columns: &[Column]) -> Value {
+    columns
+        .iter()
+        .map(|(name, column)| {
+            let (kind, mode) = match column {
+                ColumnType::Text => ("STRING", "REQUIRED"),
+                ColumnType::OptionalText => ("STRING", "NULLABLE"),
+                ColumnType::Int32 => ("INTEGER", "REQUIRED"),
+                ColumnType::Float64 => ("FLOAT", "REQUIRED"),
+                ColumnType::Boolean => ("BOOLEAN", "REQUIRED"),
+                ColumnType::Date => ("DATE", "REQUIRED"),
+                ColumnType::Timestamp => ("TIMESTAMP", "REQUIRED"),
+                ColumnType::TextList => ("STRING", "REPEATED"),
+                ColumnType::Attachments => {
+                    let fields: Vec<Value> = ["url", "label", "kind"]
+                        .into_iter()
+                        .map(|name| json!({ "name": name, "type": "STRING", "mode": "REQUIRED" }))
+                        .collect();
+                    return json!({
+                        "name": name,
+                        "type": "RECORD",
+                        "mode": "REPEATED",
+                        "fields": fields,
+                    });
+                }
+            };
+            json!({ "name": name, "type": kind, "mode": mode })
+        })
+        .collect()
+}
+
+/// A streaming insert row: `insert_id` lets BigQuery drop a row sent twice by a retried request.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct InsertRow {
+    #[serde(rename = "insertId")]
+    pub insert_id: String,
+    pub json: Value,
+}
+
+/// A signal as inserted into [`SIGNALS_TABLE`], keyed by its source key.
+pub fn signal_row(row: &ExportRow) -> anyhow::Result<InsertRow> {
+    let mut json = serde_json::to_value(row)?;
+    if let Some(object) = json.as_object_mut() {
+        object.remove("note");
+    }
+    Ok(InsertRow {
+        insert_id: row.source_key.clone(),
+        json,
+    })
+}
+
+/// A score snapshot as inserted into [`SNAPSHOTS_TABLE`], keyed by its run and scholar.
+pub fn snapshot_row(row: &SnapshotRow) -> anyhow::Result<InsertRow> {
+    Ok(InsertRow {
+        insert_id: format!("{}:{}", row.run_id, row.email),
+        json: serde_json::to_value(row)?,
+    })
+}
+
+/// An authenticated connection to one BigQuery dataset.
+pub struct Dataset {
+    client: reqwest::Client,
+    token: String,
+    project: String,
+    dataset: String,
+}
+
+impl Dataset {
+    pub async fn connect(
+        account: &ServiceAccount,
+        project: Option<&str>,
+        dataset: &str,
+    ) -> anyhow::Result<Self> {
+        let client = reqwest::Client::new();
+        let token = account.access_token(&client, BIGQUERY_SCOPE).await?;
+        Ok(Self {
+            client,
+            token,
+            project: project.unwrap_or(&account.project_id).to_string(),
+            dataset: dataset.to_string(),
+        })
+    }
+
+    /// `project.dataset`, as BigQuery names it.
+    pub fn name(&self) -> String {
+        format!("{}.{}", self.project, self.dataset)
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{BIGQUERY_API}/projects/{}/{path}", self.project)
+    }
+
+    /// Whether GETting `url` finds something, as opposed to a 404.
+    async fn exists(&self, url: &str) -> anyhow::Result<bool> {
+        let response = self.client.get(url).bearer_auth(&self.token).send().await?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(false);
+        }
+        response.error_for_status()?;
+        Ok(true)
+    }
+
+    /// Creates the dataset unless it exists.
+    pub async fn ensure(&self) -> anyhow::Result<()> {
+        if self
+            .exists(&self.url(&format!("datasets/{}", self.dataset)))
+            .await?
+        {
+            return Ok(());
+        }
+        self.client
+            .post(self.url("datasets"))
+            .bearer_auth(&self.token)
+            .json(&json!({
+                "datasetReference": { "projectId": self.project, "datasetId": self.dataset },
+            }))
+            .send()
+            .await?
+            .error_for_status()
+            .with_context(|| format!("could not create dataset {}", self.name()))?;
+        tracing::info!(dataset = self.name(), "created BigQuery dataset");
+        Ok(())
+    }
+
+    /// Creates `table` with `columns` unless it exists. An existing table's schema is left as is.
+    pub async fn ensure_table(&self, table: &str, columns: &[Column]) -> anyhow::Result<()> {
+        let tables = format!("datasets/{}/tables", self.dataset);
+        if self.exists(&self.url(&format!("{tables}/{table}"))).await? {
+            return Ok(());
+        }
+        self.client
+            .post(self.url(&tables))
+            .bearer_auth(&self.token)
+            .json(&json!({
+                "tableReference": {
+                    "projectId": self.project,
+                    "datasetId": self.dataset,
+                    "tableId": table,
+                },
+                "schema": { "fields": table_fields(columns) },
+            }))
+            .send()
+            .await?
+            .error_for_status()
+            .with_context(|| format!("could not create table {}.{table}", self.name()))?;
+        tracing::info!(dataset = self.name(), table, "created BigQuery table");
+        Ok(())
+    }
+
+    /// Streams `rows` into `table`, `batch_rows` per request, retrying throttled, failed, and
+    /// not-yet-visible requests.
+    pub async fn insert(
+        &self,
+        table: &str,
+        rows: &[InsertRow],
+        batch_rows: usize,
+    ) -> anyhow::Result<()> {
+        let url = self.url(&format!(
+            "datasets/{}/tables/{table}/insertAll",
+            self.dataset
+        ));
+        for batch in rows.chunks(batch_rows) {
+            let body = json!({ "rows": batch });
+            let mut attempts = 1;
+            let response: Value = loop {
+                let result = self
+                    .client
+                    .post(&url)
+                    .bearer_auth(&self.token)
+                    .json(&body)
+                    .send()
+                    .await
+                    .and_then(|response| response.error_for_status());
+                match result {
+                    Err(err) if attempts < INSERT_ATTEMPTS && is_retryable(&err) => {
+                        tokio::time::sleep(RETRY_DELAY * attempts).await;
+                        attempts += 1;
+                    }
+                    result => {
+                        break result
+                            .with_context(|| format!("insert into {}.{table}", self.name()))?
+                            .json()
+                            .await?
+                    }
+                }
+            };
+            if let Some(message) = insert_errors(&response) {
+                anyhow::bail!(
+                    "BigQuery rejected rows for {}.{table}: {message}",
+                    self.name()
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+fn is_retryable(err: &reqwest::Error) -> bool {
+    err.status().is_none_or(|status| {
+        status.is_server_error()
+            || status == reqwest::StatusCode::TOO_MANY_REQUESTS
+            || status == reqwest::StatusCode::NOT_FOUND
+    })
+}
+
+/// The first row error in an `insertAll` response, as `row <index>: <message>`. BigQuery answers
+/// 200 even when it rejects rows, listing them under `insertErrors`.
+pub fn insert_errors(response: &Value) -> Option<String> {
+    let failed = response["insertErrors"].as_array()?.first()?;
+    let message = failed["errors"][0]["message"]
+        .as_str()
+        .unwrap_or("no reason given");
+    let count = response["insertErrors"].as_array().map_or(0, Vec::len);
+    Some(format!(
+        "row {}: {message} ({count} rows rejected)",
+        failed["index"]
+    ))
+}
+
+/// Rows `sync bigquery` sent, or would send on a dry run.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SyncSummary {
+    pub signals: usize,
+    pub snapshots: usize,
+}
+
+/// Rows logged since the last sync to a dataset, and the database time to resume from next.
+struct Pending {
+    signals: Vec<InsertRow>,
+    snapshots: Vec<InsertRow>,
+    synced_through: DateTime<Utc>,
+}
+
+fn cursor_target(dataset: &str, table: &str) -> String {
+    format!("bigquery:{dataset}.{table}")
+}
+
+async fn pending(
+    pool: &PgPool,
+    org_id: Uuid,
+    dataset: &str,
+    page_size: i64,
+) -> anyhow::Result<Pending> {
+    let synced_through = db::database_now(pool).await?;
+
+    let filter = db::SignalFilter {
+        org_id: Some(org_id),
+        include_inactive: true,
+        created_after: db::sync_cursor(pool, org_id, &cursor_target(dataset, SIGNALS_TABLE))
+            .await?,
+        ..db::SignalFilter::new(NaiveDate::from_ymd_opt(1, 1, 1).expect("year 1 is a valid date"))
+    };
+    let mut signals = Vec::new();
+    db::for_each_export_row(pool, &filter, page_size, |row| {
+        signals.push(signal_row(&row)?);
+        Ok(())
+    })
+    .await?;
+
+    let since = db::sync_cursor(pool, org_id, &cursor_target(dataset, SNAPSHOTS_TABLE))
+        .await?
+        .unwrap_or(DateTime::UNIX_EPOCH);
+    let mut snapshots = Vec::new();
+    db::for_each_snapshot_row(pool, org_id, since, None, |row| {
+        snapshots.push(snapshot_row(&row)?);
+        Ok(())
+    })
+    .await?;
+
+    Ok(Pending {
+        signals,
+        snapshots,
+        synced_through,
+    })
+}
+
+/// Pushes the organization's signals and score snapshots logged since the last sync into
+/// `dataset`, creating it and its tables on the first run. Each table's cursor only moves once
+/// all its rows are in, so a failed sync resends them next time. With `dry_run`, counts what
+/// would be sent without contacting BigQuery.
+pub async fn sync(
+    pool: &PgPool,
+    org_id: Uuid,
+    config: &BigQueryConfig,
+    dataset: &str,
+    page_size: i64,
+    dry_run: bool,
+) -> anyhow::Result<SyncSummary> {
+    let pending = pending(pool, org_id, dataset, page_size).await?;
+    let summary = SyncSummary {
+        signals: pending.signals.len(),
+        snapshots: pending.snapshots.len(),
+    };
+    if dry_run {
+        return Ok(summary);
+    }
+
+    let account = ServiceAccount::from_env(&config.credentials_env)?;
+    let target = Dataset::connect(&account, config.project.as_deref(), dataset).await?;
+    target.ensure().await?;
+    for (table, columns, rows) in [
+        (SIGNALS_TABLE, signal_columns(), &pending.signals),
+        (
+            SNAPSHOTS_TABLE,
+            SNAPSHOT_COLUMNS.to_vec(),
+            &pending.snapshots,
+        ),
+    ] {
+        target.ensure_table(table, &columns).await?;
+        target.insert(table, rows, config.batch_rows).await?;
+        db::set_sync_cursor(
+            pool,
+            org_id,
+            &cursor_target(dataset, table),
+            pending.synced_through,
+        )
+        .await?;
+        tracing::info!(dataset = target.name(), table, rows = rows.len(), "synced");
+    }
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::SCHOLAR_COLUMNS;
+    use crate::models::Attachment;
+    use crate::risk::Tier;
+
+    fn export_row() -> ExportRow {
+        ExportRow {
+            full_name: "Ada Park".to_string(),
+            email: "ada@example.edu".to_string(),
+            cohort: "2026".to_string(),
+            signal_type: "attendance".to_string(),
+            severity: 3,
+            note: "Missed lab".to_string(),
+            occurred_at: NaiveDate::from_ymd_opt(2026, 2, 10).unwrap(),
+            source_key: "sis-1".to_string(),
+            tags: vec!["tutoring".to_string()],
+            submitted_by: None,
+            attachments: vec![Attachment {
+                url: "https://lms.example.edu/a/1".to_string(),
+                label: "LMS log".to_string(),
+                kind: "lms".to_string(),
+            }],
+            status: "active".to_string(),
+            advisor: Some("Lee".to_string()),
+        }
+    }
+
+    #[test]
+    fn signal_rows_leave_out_notes_and_match_the_table() {
+        let row = signal_row(&export_row()).unwrap();
+        assert_eq!(row.insert_id, "sis-1");
+        let mut keys: Vec<&str> = row
+            .json
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(String::as_str)
+            .collect();
+        let mut columns: Vec<&str> = signal_columns().iter().map(|(name, _)| *name).collect();
+        keys.sort_unstable();
+        columns.sort_unstable();
+        assert_eq!(keys, columns);
+        assert_eq!(row.json["occurred_at"], "2026-02-10");
+        assert_eq!(row.json["attachments"][0]["kind"], "lms");
+    }
+
+    #[test]
+    fn snapshot_rows_are_keyed_by_run_and_scholar() {
+        let run_id = Uuid::from_u128(7);
+        let row = snapshot_row(&SnapshotRow {
+            run_id,
+            ran_at: DateTime::UNIX_EPOCH,
+            trigger: "watch".to_string(),
+            since_days: 90,
+            full_name: "Ada Park".to_string(),
+            email: "ada@example.edu".to_string(),
+            cohort: "2026".to_string(),
+            score: 4.5,
+            tier: Tier::High,
+            signal_count: 2,
+        })
+        .unwrap();
+        assert_eq!(row.insert_id, format!("{run_id}:ada@example.edu"));
+        assert_eq!(row.json["tier"], "high");
+        assert_eq!(row.json["ran_at"], "1970-01-01T00:00:00Z");
+    }
+
+    #[test]
+    fn schema_maps_column_types() {
+        let fields = table_fields(&signal_columns());
+        let field = |name: &str| {
+            fields
+                .as_array()
+                .unwrap()
+                .iter()
+                .find(|field| field["name"] == name)
+                .unwrap()
+                .clone()
+        };
+        assert_eq!(field("severity")["type"], "INTEGER");
+        assert_eq!(field("occurred_at")["type"], "DATE");
+        assert_eq!(field("tags")["mode"], "REPEATED");
+        assert_eq!(field("advisor")["mode"], "NULLABLE");
+        assert_eq!(field("attachments")["type"], "RECORD");
+        assert_eq!(field("attachments")["fields"].as_array().unwrap().len(), 3);
+
+        let scholars = table_fields(&SCHOLAR_COLUMNS);
+        assert_eq!(scholars[5]["type"], "TIMESTAMP");
+        assert_eq!(scholars[6]["type"], "BOOLEAN");
+    }
+
+    #[test]
+    fn reports_rejected_rows() {
+        let response = json!({
+            "insertErrors": [
+                { "index": 3, "errors": [{ "reason": "invalid", "message": "no such field" }] },
+                { "index": 4, "errors": [{ "reason": "stopped", "message": "" }] },
+            ],
+        });
+        assert_eq!(
+            insert_errors(&response).as_deref(),
+            Some("row 3: no such field (2 rows rejected)")
+        );
+        assert_eq!(
+            insert_errors(&json!({ "kind": "bigquery#tableDataInsertAllResponse" })),
+            None
+        );
+    }
+}
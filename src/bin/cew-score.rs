@@ -0,0 +1,123 @@
+//! Standalone scorer for partner environments: reads signals from a CSV or JSON file and prints
+//! risk scores as JSON. Builds without Postgres support:
+//!
+//! ```text
+//! cargo build --release --no-default-features --bin cew-score
+//! ```
+
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use chrono::{NaiveDate, Utc};
+use clap::{Parser, ValueEnum};
+use groupscholar_cohort_early_warning::config::Config;
+use groupscholar_cohort_early_warning::import::CsvRow;
+use groupscholar_cohort_early_warning::models::{ScholarScore, SignalRecord};
+use groupscholar_cohort_early_warning::risk;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum InputFormat {
+    Csv,
+    Json,
+}
+
+#[derive(Parser)]
+#[command(name = "cew-score")]
+#[command(about = "Score cohort early warning signals from a local file", long_about = None)]
+struct Args {
+    /// Signals in the import CSV layout, or a JSON array of objects with the same fields; `-` reads stdin
+    input: PathBuf,
+    /// Input format; inferred from the file extension when omitted
+    #[arg(long, value_enum)]
+    format: Option<InputFormat>,
+    #[arg(long, default_value_t = 30)]
+    since_days: i64,
+    /// Score as of this date instead of today (YYYY-MM-DD)
+    #[arg(long)]
+    as_of: Option<NaiveDate>,
+    /// TOML config file; defaults to ./cew.toml when present
+    #[arg(long, env = "CEW_CONFIG")]
+    config: Option<PathBuf>,
+    /// Only emit the top N scholars
+    #[arg(long)]
+    limit: Option<usize>,
+}
+
+#[derive(serde::Serialize)]
+struct Output {
+    as_of: NaiveDate,
+    since_days: i64,
+    signal_count: usize,
+    scores: Vec<ScholarScore>,
+}
+
+fn read_input(args: &Args) -> anyhow::Result<Vec<CsvRow>> {
+    let format = match args.format {
+        Some(format) => format,
+        None if args.input.extension().is_some_and(|ext| ext == "json") => InputFormat::Json,
+        None => InputFormat::Csv,
+    };
+
+    let mut data = Vec::new();
+    if args.input.as_os_str() == "-" {
+        std::io::stdin().read_to_end(&mut data)?;
+    } else {
+        data = std::fs::read(&args.input)
+            .with_context(|| format!("failed to read {}", args.input.display()))?;
+    }
+
+    match format {
+        InputFormat::Json => Ok(serde_json::from_slice(&data)?),
+        InputFormat::Csv => csv::Reader::from_reader(data.as_slice())
+            .deserialize()
+            .enumerate()
+            .map(|(index, row)| row.with_context(|| format!("invalid CSV row {}", index + 1)))
+            .collect(),
+    }
+}
+
+/// Scholars are identified by email, as in the database.
+fn to_signals(rows: Vec<CsvRow>) -> Vec<SignalRecord> {
+    let mut ids: HashMap<String, Uuid> = HashMap::new();
+    rows.into_iter()
+        .map(|row| SignalRecord {
+            scholar_id: *ids.entry(row.email.clone()).or_insert_with(Uuid::new_v4),
+            scholar_name: row.full_name,
+            scholar_email: row.email,
+            cohort: row.cohort,
+            signal_type: row.signal_type,
+            severity: row.severity,
+            occurred_at: row.occurred_at,
+            note: row.note,
+            advisor: None,
+            submitted_by: row.submitted_by,
+            attachments: row.attachments,
+            note_categories: Vec::new(),
+            note_sentiment: None,
+        })
+        .collect()
+}
+
+fn main() -> anyhow::Result<()> {
+    let args = Args::parse();
+    let config = Config::load(args.config.as_deref())?;
+    let signals = to_signals(read_input(&args)?);
+    let as_of = args.as_of.unwrap_or_else(|| Utc::now().date_naive());
+
+    let mut scores = risk::score_signals_with(&signals, args.since_days, as_of, &config.scoring);
+    if let Some(limit) = args.limit {
+        scores.truncate(limit);
+    }
+
+    let output = Output {
+        as_of,
+        since_days: args.since_days,
+        signal_count: signals.len(),
+        scores,
+    };
+    println!("{}", serde_json::to_string_pretty(&output)?);
+    Ok(())
+}
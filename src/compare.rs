@@ -0,0 +1,257 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+#[cfg(feature = "postgres")]
+use sqlx::PgPool;
+#[cfg(feature = "postgres")]
+use uuid::Uuid;
+
+use crate::models::ScholarScore;
+use crate::risk::Tier;
+#[cfg(feature = "postgres")]
+use crate::risk::{ScoreAccumulator, ScoringConfig};
+
+/// One cohort's column in `compare`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CohortSummary {
+    pub cohort: String,
+    /// Active scholars on the roster, whether or not they have signals in the window.
+    pub scholars: i64,
+    pub signals: i64,
+    /// Signal count per type.
+    pub signal_mix: BTreeMap<String, i64>,
+    /// Mean score across active scholars; those without signals count as zero.
+    pub avg_score: f64,
+    /// Scholars in the high tier or above.
+    pub high_risk: usize,
+}
+
+impl CohortSummary {
+    pub fn new(
+        cohort: &str,
+        scholars: i64,
+        signal_mix: BTreeMap<String, i64>,
+        scores: &[ScholarScore],
+    ) -> Self {
+        let total: f64 = scores.iter().map(|score| score.score).sum();
+        Self {
+            cohort: cohort.to_string(),
+            scholars,
+            signals: signal_mix.values().sum(),
+            signal_mix,
+            avg_score: if scholars > 0 {
+                total / scholars as f64
+            } else {
+                0.0
+            },
+            high_risk: scores
+                .iter()
+                .filter(|score| score.tier >= Tier::High)
+                .count(),
+        }
+    }
+
+    /// Percentage of the cohort's signals that have `signal_type`.
+    fn share(&self, signal_type: &str) -> f64 {
+        match (self.signal_mix.get(signal_type), self.signals) {
+            (Some(count), total) if total > 0 => *count as f64 * 100.0 / total as f64,
+            _ => 0.0,
+        }
+    }
+}
+
+/// Formats one metric per cohort as a value and, after the first cohort, its delta against the
+/// first.
+fn cells(values: &[f64], decimals: usize, suffix: &str, delta_suffix: &str) -> Vec<[String; 2]> {
+    let baseline = values.first().copied().unwrap_or_default();
+    values
+        .iter()
+        .enumerate()
+        .map(|(index, value)| {
+            let delta = match index {
+                0 => String::new(),
+                _ => format!("({:+.decimals$}{delta_suffix})", value - baseline),
+            };
+            [format!("{value:.decimals$}{suffix}"), delta]
+        })
+        .collect()
+}
+
+/// Renders cohorts side by side with deltas against the first one.
+pub fn render(summaries: &[CohortSummary], since_days: i64) -> String {
+    let Some(baseline) = summaries.first() else {
+        return String::new();
+    };
+    let metric =
+        |value: fn(&CohortSummary) -> f64| -> Vec<f64> { summaries.iter().map(value).collect() };
+
+    let mut rows = vec![
+        (
+            "active scholars".to_string(),
+            cells(&metric(|s| s.scholars as f64), 0, "", ""),
+        ),
+        (
+            "signals".to_string(),
+            cells(&metric(|s| s.signals as f64), 0, "", ""),
+        ),
+        (
+            "avg score".to_string(),
+            cells(&metric(|s| s.avg_score), 2, "", ""),
+        ),
+        (
+            "high risk".to_string(),
+            cells(&metric(|s| s.high_risk as f64), 0, "", ""),
+        ),
+        (
+            "signal mix".to_string(),
+            vec![Default::default(); summaries.len()],
+        ),
+    ];
+    let signal_types: BTreeSet<&String> = summaries
+        .iter()
+        .flat_map(|summary| summary.signal_mix.keys())
+        .collect();
+    for signal_type in signal_types {
+        let shares: Vec<f64> = summaries.iter().map(|s| s.share(signal_type)).collect();
+        rows.push((format!("  {signal_type}"), cells(&shares, 0, "%", " pts")));
+    }
+
+    let label_width = rows.iter().map(|(label, _)| label.len()).max().unwrap_or(0);
+    // Value and delta column widths per cohort.
+    let widths: Vec<[usize; 2]> = summaries
+        .iter()
+        .enumerate()
+        .map(|(index, summary)| {
+            let width = |part: usize| rows.iter().map(|(_, cells)| cells[index][part].len()).max();
+            [
+                width(0).unwrap_or(0).max(summary.cohort.len()),
+                width(1).unwrap_or(0),
+            ]
+        })
+        .collect();
+    let line = |label: &str, cells: &[[String; 2]]| {
+        let mut line = format!("{label:<label_width$}");
+        for ([value, delta], [value_width, delta_width]) in cells.iter().zip(&widths) {
+            line.push_str(&format!("  {value:>value_width$}"));
+            if *delta_width > 0 {
+                line.push_str(&format!(" {delta:<delta_width$}"));
+            }
+        }
+        line.trim_end().to_string()
+    };
+
+    let header: Vec<[String; 2]> = summaries
+        .iter()
+        .map(|s| [s.cohort.clone(), String::new()])
+        .collect();
+    let mut out = vec![
+        format!(
+            "Cohort comparison over the last {since_days} days (deltas against {}):",
+            baseline.cohort
+        ),
+        String::new(),
+        line("", &header),
+    ];
+    out.extend(rows.iter().map(|(label, cells)| line(label, cells)));
+    out.join("\n")
+}
+
+/// Scores each cohort and tallies its signal mix in one pass over its signals. Inactive scholars
+/// are left out, as in `score`.
+#[cfg(feature = "postgres")]
+pub async fn summarize(
+    pool: &PgPool,
+    org_id: Uuid,
+    cohorts: &[String],
+    since_days: i64,
+    scoring: &ScoringConfig,
+) -> anyhow::Result<Vec<CohortSummary>> {
+    let stats = crate::db::database_stats(pool, org_id).await?;
+    let today = chrono::Utc::now().date_naive();
+
+    let mut summaries = Vec::new();
+    for cohort in cohorts {
+        let active = stats
+            .cohorts
+            .iter()
+            .find(|(name, _, _)| name == cohort)
+            .map(|(_, _, active)| *active)
+            .ok_or_else(|| anyhow::anyhow!("no scholars found in cohort {cohort}"))?;
+
+        let filter = crate::db::SignalFilter {
+            org_id: Some(org_id),
+            cohorts: vec![cohort.clone()],
+            ..crate::db::SignalFilter::new(crate::risk::cutoff_date(since_days))
+        };
+        let mut accumulator = ScoreAccumulator::new(since_days, today, scoring);
+        let mut signal_mix = BTreeMap::new();
+        crate::db::for_each_signal(pool, &filter, crate::db::DEFAULT_PAGE_SIZE, |signal| {
+            *signal_mix.entry(signal.signal_type.clone()).or_insert(0) += 1;
+            accumulator.push(&signal);
+        })
+        .await?;
+        summaries.push(CohortSummary::new(
+            cohort,
+            active,
+            signal_mix,
+            &accumulator.finish(),
+        ));
+    }
+    Ok(summaries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn score(value: f64, tier: Tier) -> ScholarScore {
+        ScholarScore {
+            scholar_name: "Avery Lee".to_string(),
+            scholar_email: "avery@example.org".to_string(),
+            cohort: "2026".to_string(),
+            score: value,
+            signal_count: 2,
+            advisor: None,
+            tier,
+            escalated_by: None,
+            collapsed_signals: 0,
+            percentile: 0.0,
+            cohort_percentile: 0.0,
+            last_contact: None,
+            last_signal: None,
+        }
+    }
+
+    #[test]
+    fn renders_deltas_against_the_first_cohort() {
+        let older = CohortSummary::new(
+            "2025",
+            4,
+            BTreeMap::from([("attendance".to_string(), 6), ("wellbeing".to_string(), 2)]),
+            &[score(8.0, Tier::High), score(2.0, Tier::Low)],
+        );
+        let newer = CohortSummary::new(
+            "2026",
+            5,
+            BTreeMap::from([("academic".to_string(), 1), ("attendance".to_string(), 3)]),
+            &[score(1.5, Tier::Low)],
+        );
+        assert_eq!(older.avg_score, 2.5);
+        assert_eq!(older.high_risk, 1);
+
+        let table = render(&[older, newer], 60);
+        let expected = [
+            "Cohort comparison over the last 60 days (deltas against 2025):",
+            "",
+            "                 2025  2026",
+            "active scholars     4     5 (+1)",
+            "signals             8     4 (-4)",
+            "avg score        2.50  0.30 (-2.20)",
+            "high risk           1     0 (-1)",
+            "signal mix",
+            "  academic         0%   25% (+25 pts)",
+            "  attendance      75%   75% (+0 pts)",
+            "  wellbeing       25%    0% (-25 pts)",
+        ];
+        assert_eq!(table, expected.join("\n"));
+    }
+}
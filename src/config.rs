@@ -0,0 +1,1488 @@
+use std::collections::BTreeMap;
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use chrono::{NaiveDate, Utc};
+
+use crate::calendar::AcademicCalendar;
+use crate::enrich::EnrichmentConfig;
+use crate::import::{
+    DateWindow, ImportLimits, NotePolicy, OnConflict, SeverityBounds, SourceMapping,
+};
+use crate::models::SCORE_FIELDS;
+pub use crate::models::{RetentionMode, RetentionPlan};
+use crate::recommendations::Playbook;
+use crate::risk::{ScoringConfig, Tier};
+use crate::rules::AlertRule;
+use crate::schedule::Schedule;
+
+/// Config file picked up from the working directory when `--config` is not given.
+pub const DEFAULT_CONFIG_FILE: &str = "cew.toml";
+
+/// Config file looked for after [`DEFAULT_CONFIG_FILE`], first in the working directory and then
+/// in the XDG config directory (`$XDG_CONFIG_HOME`, else `~/.config`).
+pub const CONFIG_FILE_NAME: &str = "cohort-early-warning.toml";
+
+/// `[database] ssl_mode` values, as the `--db-ssl-mode` flag spells them.
+pub const SSL_MODES: [&str; 6] = [
+    "disable",
+    "allow",
+    "prefer",
+    "require",
+    "verify-ca",
+    "verify-full",
+];
+
+/// Annotated config listing every tunable at its default, written by `config init`.
+pub const CONFIG_TEMPLATE: &str = include_str!("config_template.toml");
+
+#[derive(Debug, Clone, Default, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub scoring: ScoringConfig,
+    /// Severities imports, `signal add`, and the database accept.
+    pub severity: SeverityBounds,
+    /// How far from today imports, `signal add`, and `POST /signals` accept signal dates.
+    pub dates: DateWindow,
+    /// Named databases that `--profile` connects to and `promote` copies between, e.g.
+    /// `[environments.staging]`.
+    pub environments: BTreeMap<String, Environment>,
+    /// Partner CSV layouts that `import --source <name>` reads, e.g. `[sources.sis]`.
+    pub sources: BTreeMap<String, SourceMapping>,
+    /// Scheduled scoring run by `watch`.
+    pub watch: WatchConfig,
+    /// Where alerts are sent, e.g. `[channels.ops]`.
+    pub channels: BTreeMap<String, ChannelConfig>,
+    /// Alert rules evaluated after imports and `watch` runs, alongside those from `rules add`.
+    pub rules: Vec<AlertRule>,
+    /// Next steps suggested for scholars matching a signal pattern or tier, in single-scholar
+    /// reports and alert payloads.
+    pub playbooks: Vec<Playbook>,
+    /// Keyword categories and sentiment words `enrich` tags signal notes with.
+    pub enrichment: EnrichmentConfig,
+    /// Where `watch` re-sends alerts nobody acknowledged in time.
+    pub escalation: EscalationConfig,
+    /// How long signals and scholars are kept before `purge` removes them.
+    pub retention: RetentionConfig,
+    /// How `report` and `GET /reports` render their sections.
+    pub report: ReportConfig,
+    /// How long `score`, `report`, and the API reuse results computed from unchanged data.
+    pub cache: CacheConfig,
+    /// How `serve` limits ingestion, answers browsers, and hosts the dashboard.
+    pub api: ApiConfig,
+    /// Terms, exam weeks, and breaks that `trends` and `report` expect seasonal swings around.
+    pub calendar: AcademicCalendar,
+    /// Defaults for flags many commands share; the flags override them.
+    pub defaults: DefaultsConfig,
+    /// Defaults for `import` flags; the flags override them.
+    pub import: ImportConfig,
+    /// Connection pool settings; the `--db-*` flags and their environment variables override them.
+    pub database: DatabaseConfig,
+    /// Where the key that encrypts signal notes comes from.
+    pub notes: NotesConfig,
+    /// Google Cloud project and credentials `sync bigquery` pushes to.
+    pub bigquery: BigQueryConfig,
+    /// Credentials `report --publish-sheet` writes to Google Sheets with.
+    pub sheets: SheetsConfig,
+    /// NATS JetStream stream `consume` reads signal events from.
+    pub consume: ConsumeConfig,
+    /// Salesforce org where `watch` opens a record for each scholar entering the critical tier.
+    pub salesforce: Option<SalesforceConfig>,
+    /// Airtable base `sync airtable` pushes scores to and pulls follow-up statuses from.
+    pub airtable: Option<AirtableConfig>,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct WatchConfig {
+    /// Cron expression evaluated in UTC, e.g. `"0 7 * * 1-5"`; `watch` needs one to start.
+    pub schedule: Option<String>,
+    pub since_days: i64,
+    /// Alert when a scholar reaches this tier or a higher one than at the previous run.
+    pub alert_tier: Tier,
+    /// Names from `[channels]` that receive alerts.
+    pub channels: Vec<String>,
+    /// Days after alerting about a scholar during which the same rule (or the tier alert) stays
+    /// quiet about them. No cooldown when unset.
+    pub cooldown_days: Option<i64>,
+}
+
+impl Default for WatchConfig {
+    fn default() -> Self {
+        Self {
+            schedule: None,
+            since_days: 30,
+            alert_tier: Tier::High,
+            channels: Vec::new(),
+            cooldown_days: None,
+        }
+    }
+}
+
+impl WatchConfig {
+    pub fn schedule(&self) -> anyhow::Result<Schedule> {
+        self.schedule
+            .as_deref()
+            .context("set [watch] schedule in the config, e.g. schedule = \"0 7 * * 1-5\"")?
+            .parse()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EscalationConfig {
+    /// Hours an alert may go unacknowledged (`signal ack`) before it is escalated.
+    pub after_hours: i64,
+    /// Only alerts sent while the scholar was at or above this tier escalate.
+    pub tier: Tier,
+    /// Names from `[channels]` for the secondary contact; escalation is off while empty.
+    pub channels: Vec<String>,
+}
+
+impl Default for EscalationConfig {
+    fn default() -> Self {
+        Self {
+            after_hours: 24,
+            tier: Tier::Critical,
+            channels: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RetentionConfig {
+    /// Age such as `"3y"` past which `purge` acts on signals and the scholars left without
+    /// recent activity; `--older-than` overrides it.
+    pub max_age: Option<String>,
+    pub mode: RetentionMode,
+    /// Cron expression evaluated in UTC on which `watch` enforces this policy, e.g.
+    /// `"0 3 * * *"`. Unset leaves enforcement to `purge`.
+    pub schedule: Option<String>,
+    pub notes: NoteRetention,
+    pub interventions: InterventionRetention,
+}
+
+/// `[retention.notes]`: signal notes can be kept for less time than the signals they belong to.
+#[derive(Debug, Clone, Default, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct NoteRetention {
+    /// Age past which signal notes and attachments are blanked; the signals stay.
+    pub max_age: Option<String>,
+}
+
+/// `[retention.interventions]`
+#[derive(Debug, Clone, Default, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct InterventionRetention {
+    pub max_age: Option<String>,
+    /// Defaults to `[retention] mode`; anonymizing blanks intervention notes.
+    pub mode: Option<RetentionMode>,
+}
+
+impl RetentionConfig {
+    /// The configured maximum age of signals in days, if any.
+    pub fn max_age_days(&self) -> anyhow::Result<Option<i64>> {
+        age_days("[retention]", self.max_age.as_deref())
+    }
+
+    /// The cron schedule `watch` enforces this policy on, if set.
+    pub fn schedule(&self) -> anyhow::Result<Option<Schedule>> {
+        self.schedule
+            .as_deref()
+            .map(|schedule| {
+                schedule
+                    .parse()
+                    .map_err(|err| anyhow::anyhow!("[retention] schedule: {err}"))
+            })
+            .transpose()
+    }
+
+    /// What is expired as of `today`. `older_than` and `mode` override the signals age and mode,
+    /// as `purge --older-than` and `--mode` do.
+    pub fn plan(
+        &self,
+        today: NaiveDate,
+        older_than: Option<i64>,
+        mode: Option<RetentionMode>,
+    ) -> anyhow::Result<RetentionPlan> {
+        let cutoff = |days: i64| today - chrono::Duration::days(days);
+        let signals = match older_than {
+            Some(days) => Some(days),
+            None => self.max_age_days()?,
+        };
+        let notes = age_days("[retention.notes]", self.notes.max_age.as_deref())?;
+        let interventions = age_days(
+            "[retention.interventions]",
+            self.interventions.max_age.as_deref(),
+        )?;
+        Ok(RetentionPlan {
+            signals: signals.map(|days| (cutoff(days), mode.unwrap_or(self.mode))),
+            notes: notes.map(cutoff),
+            interventions: interventions
+                .map(|days| (cutoff(days), self.interventions.mode.unwrap_or(self.mode))),
+        })
+    }
+
+    fn validate(&self) -> anyhow::Result<()> {
+        let plan = self.plan(Utc::now().date_naive(), None, None)?;
+        if self.schedule()?.is_some() && plan.is_empty() {
+            anyhow::bail!(
+                "[retention] schedule needs a max_age for signals, [retention.notes], or \
+                 [retention.interventions]"
+            );
+        }
+        Ok(())
+    }
+}
+
+fn age_days(section: &str, age: Option<&str>) -> anyhow::Result<Option<i64>> {
+    age.map(|age| parse_age_days(age).map_err(|err| anyhow::anyhow!("{section} {err}")))
+        .transpose()
+}
+
+/// Parses ages like `365d`, `52w`, or `3y` into a number of days; a year counts as 365 days.
+pub fn parse_age_days(value: &str) -> Result<i64, String> {
+    let value = value.trim();
+    let unit_start = value.char_indices().last().map_or(0, |(index, _)| index);
+    let (number, unit) = value.split_at(unit_start);
+    let multiplier = match unit {
+        "d" => 1,
+        "w" => 7,
+        "y" => 365,
+        _ => {
+            return Err(format!(
+                "expected an age like 365d, 52w, or 3y, got `{value}`"
+            ))
+        }
+    };
+    number
+        .parse::<i64>()
+        .ok()
+        .filter(|days| *days > 0)
+        .and_then(|days| days.checked_mul(multiplier))
+        .ok_or_else(|| format!("expected a positive age like 365d, 52w, or 3y, got `{value}`"))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChannelKind {
+    /// POSTs a JSON body listing the alerts.
+    Webhook,
+    /// Posts the summary text to a Slack incoming webhook.
+    Slack,
+    /// Posts an Adaptive Card listing the alerts to a Microsoft Teams incoming webhook.
+    Teams,
+    /// Texts each recipient through Twilio, for critical-tier alerts only.
+    Sms,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ChannelConfig {
+    pub kind: ChannelKind,
+    /// Environment variable holding the endpoint URL, which often embeds a secret. Required except
+    /// for SMS, where it overrides the Twilio Messages endpoint.
+    pub url_env: Option<String>,
+    /// Environment variable holding a key to sign webhook bodies with; see `notify::sign`.
+    pub secret_env: Option<String>,
+    /// SMS only: environment variables holding the Twilio account SID and auth token.
+    pub account_sid_env: Option<String>,
+    pub auth_token_env: Option<String>,
+    /// SMS only: the Twilio number messages come from, in E.164 form.
+    pub from: Option<String>,
+    /// SMS only: who gets texted.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub recipients: Vec<SmsRecipient>,
+}
+
+impl ChannelConfig {
+    fn validate(&self) -> anyhow::Result<()> {
+        if self.secret_env.is_some() && self.kind != ChannelKind::Webhook {
+            anyhow::bail!("secret_env only applies to webhook channels");
+        }
+        if self.kind != ChannelKind::Sms {
+            if self.url_env.is_none() {
+                anyhow::bail!("url_env is required");
+            }
+            if self.account_sid_env.is_some()
+                || self.auth_token_env.is_some()
+                || self.from.is_some()
+                || !self.recipients.is_empty()
+            {
+                anyhow::bail!(
+                    "account_sid_env, auth_token_env, from, and recipients only apply to sms \
+                     channels"
+                );
+            }
+            return Ok(());
+        }
+        if self.account_sid_env.is_none() || self.auth_token_env.is_none() {
+            anyhow::bail!("sms channels need account_sid_env and auth_token_env");
+        }
+        if !self.from.as_deref().is_some_and(is_e164) {
+            anyhow::bail!("sms channels need a from number like +15551234567");
+        }
+        if self.recipients.is_empty() {
+            anyhow::bail!("sms channels need at least one recipient");
+        }
+        for recipient in &self.recipients {
+            if !is_e164(&recipient.number) {
+                anyhow::bail!(
+                    "recipient {} is not a number like +15551234567",
+                    recipient.number
+                );
+            }
+            if let Some(hours) = &recipient.quiet_hours {
+                parse_quiet_hours(hours)
+                    .map_err(anyhow::Error::msg)
+                    .with_context(|| format!("recipient {}", recipient.number))?;
+            }
+            recipient
+                .offset()
+                .with_context(|| format!("recipient {}", recipient.number))?;
+        }
+        Ok(())
+    }
+}
+
+/// Someone an SMS channel texts.
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct SmsRecipient {
+    /// Phone number in E.164 form.
+    pub number: String,
+    /// Local window like `22:00-07:00` during which this recipient is not texted.
+    pub quiet_hours: Option<String>,
+    /// The recipient's offset from UTC, like `-05:00`, that quiet hours are read in; UTC when unset.
+    pub utc_offset: Option<String>,
+}
+
+impl SmsRecipient {
+    fn offset(&self) -> anyhow::Result<chrono::FixedOffset> {
+        match &self.utc_offset {
+            Some(offset) => offset
+                .parse()
+                .map_err(|_| anyhow::anyhow!("expected a UTC offset like -05:00, got `{offset}`")),
+            None => Ok(chrono::FixedOffset::east_opt(0).expect("zero offset")),
+        }
+    }
+
+    /// Whether `now` falls inside this recipient's quiet hours, in their local time.
+    pub fn is_quiet(&self, now: chrono::DateTime<chrono::Utc>) -> bool {
+        let Some((start, end)) = self
+            .quiet_hours
+            .as_deref()
+            .and_then(|hours| parse_quiet_hours(hours).ok())
+        else {
+            return false;
+        };
+        let Ok(offset) = self.offset() else {
+            return false;
+        };
+        let local = now.with_timezone(&offset).time();
+        if start <= end {
+            start <= local && local < end
+        } else {
+            // The window wraps past midnight.
+            local >= start || local < end
+        }
+    }
+}
+
+/// Parses a window like `22:00-07:00` into its start and end times.
+pub fn parse_quiet_hours(value: &str) -> Result<(chrono::NaiveTime, chrono::NaiveTime), String> {
+    let invalid = || format!("expected quiet hours like 22:00-07:00, got `{value}`");
+    let (start, end) = value.trim().split_once('-').ok_or_else(invalid)?;
+    let time = |text: &str| chrono::NaiveTime::parse_from_str(text.trim(), "%H:%M");
+    match (time(start), time(end)) {
+        (Ok(start), Ok(end)) if start != end => Ok((start, end)),
+        _ => Err(invalid()),
+    }
+}
+
+fn is_e164(number: &str) -> bool {
+    number.strip_prefix('+').is_some_and(|digits| {
+        (8..=15).contains(&digits.len()) && digits.bytes().all(|b| b.is_ascii_digit())
+    })
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ReportConfig {
+    /// Trend weeks rising more than this percentage over the prior week are flagged; 0 turns
+    /// flagging off.
+    pub trend_alert_pct: f64,
+}
+
+impl Default for ReportConfig {
+    fn default() -> Self {
+        Self {
+            trend_alert_pct: 25.0,
+        }
+    }
+}
+
+impl ReportConfig {
+    /// `trend_alert_pct` as `ReportOptions` takes it.
+    pub fn trend_alert_pct(&self) -> Option<f64> {
+        (self.trend_alert_pct > 0.0).then_some(self.trend_alert_pct)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CacheConfig {
+    /// Seconds a cached result is served for; 0 turns the cache off. Any new or changed data
+    /// invalidates it sooner.
+    pub ttl_secs: u64,
+}
+
+impl CacheConfig {
+    pub fn ttl(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.ttl_secs)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ApiConfig {
+    /// Largest `POST /signals` body accepted, in bytes.
+    pub max_body_bytes: usize,
+    /// `POST /signals` requests each API key may make per minute; 0 turns the limit off.
+    pub signals_per_minute: u32,
+    /// Per-minute limits for particular keys, by the name given to `api-key create`.
+    pub key_limits: BTreeMap<String, u32>,
+    /// Browser origins such as `"https://dash.example.org"` allowed to call the API from another
+    /// site, or `["*"]` for any. No cross-origin access when empty.
+    pub cors_origins: Vec<String>,
+    /// Directory served at `/dashboard/` in place of the bundled dashboard.
+    pub static_dir: Option<PathBuf>,
+}
+
+impl Default for ApiConfig {
+    fn default() -> Self {
+        Self {
+            max_body_bytes: 64 * 1024,
+            signals_per_minute: 600,
+            key_limits: BTreeMap::new(),
+            cors_origins: Vec::new(),
+            static_dir: None,
+        }
+    }
+}
+
+impl ApiConfig {
+    /// `POST /signals` requests per minute allowed for the key named `key`; 0 means unlimited.
+    pub fn signals_per_minute(&self, key: &str) -> u32 {
+        self.key_limits
+            .get(key)
+            .copied()
+            .unwrap_or(self.signals_per_minute)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct Environment {
+    /// Environment variable holding the database URL, so credentials stay out of the file.
+    pub database_url_env: String,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DefaultsConfig {
+    /// Days of signals scored when a command's `--since-days` is left out. Commands with their
+    /// own longer windows, such as `export` and `trends`, keep them.
+    pub since_days: i64,
+}
+
+impl Default for DefaultsConfig {
+    fn default() -> Self {
+        Self { since_days: 30 }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ImportConfig {
+    pub jobs: u16,
+    pub max_file_mb: u64,
+    pub max_rows: usize,
+    pub max_note_length: usize,
+    pub note_policy: NotePolicy,
+    pub summary_sentences: usize,
+    pub chunk_size: usize,
+    pub on_conflict: OnConflict,
+}
+
+impl Default for ImportConfig {
+    fn default() -> Self {
+        Self {
+            jobs: 4,
+            max_file_mb: 64,
+            max_rows: 100_000,
+            max_note_length: 2_000,
+            note_policy: NotePolicy::Reject,
+            summary_sentences: 2,
+            chunk_size: 500,
+            on_conflict: OnConflict::Skip,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DatabaseConfig {
+    pub max_connections: u32,
+    /// Seconds to wait for a free pooled connection.
+    pub acquire_timeout_secs: u64,
+    /// Abort any single statement running longer than this many seconds. No limit when unset.
+    pub statement_timeout_secs: Option<u64>,
+    /// One of [`SSL_MODES`]; the driver's default (prefer) when unset.
+    pub ssl_mode: Option<String>,
+    pub ssl_root_cert: Option<PathBuf>,
+    /// Times an import chunk is retried after a transient database error.
+    pub retries: u32,
+}
+
+impl Default for DatabaseConfig {
+    fn default() -> Self {
+        Self {
+            max_connections: 5,
+            acquire_timeout_secs: 30,
+            statement_timeout_secs: None,
+            ssl_mode: None,
+            ssl_root_cert: None,
+            retries: 5,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct NotesConfig {
+    /// Variable holding the base64 key, as `notes keygen` prints it.
+    pub key_env: String,
+    /// Shell command printing the key, such as a KMS decrypt call; used when `key_env` is unset.
+    pub key_command: Option<String>,
+}
+
+impl Default for NotesConfig {
+    fn default() -> Self {
+        Self {
+            key_env: "CEW_NOTE_KEY".to_string(),
+            key_command: None,
+        }
+    }
+}
+
+impl NotesConfig {
+    /// The note key in base64, from `key_env` or else `key_command`; `None` when neither gives one,
+    /// in which case notes are stored unencrypted.
+    pub fn key(&self) -> anyhow::Result<Option<String>> {
+        if let Some(key) = std::env::var(&self.key_env)
+            .ok()
+            .filter(|key| !key.trim().is_empty())
+        {
+            return Ok(Some(key));
+        }
+        let Some(command) = &self.key_command else {
+            return Ok(None);
+        };
+        let output = std::process::Command::new("sh")
+            .args(["-c", command])
+            .stderr(std::process::Stdio::inherit())
+            .output()
+            .with_context(|| format!("could not run [notes] key_command {command:?}"))?;
+        anyhow::ensure!(
+            output.status.success(),
+            "[notes] key_command {command:?} failed with {}",
+            output.status
+        );
+        let key = String::from_utf8(output.stdout)
+            .context("[notes] key_command printed something other than text")?;
+        Ok(Some(key.trim().to_string()))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BigQueryConfig {
+    /// Project holding the dataset; defaults to the service account's own project.
+    pub project: Option<String>,
+    /// Variable holding the path of the service account's JSON key file.
+    pub credentials_env: String,
+    /// Rows sent per streaming insert request.
+    pub batch_rows: usize,
+}
+
+impl Default for BigQueryConfig {
+    fn default() -> Self {
+        Self {
+            project: None,
+            credentials_env: "GOOGLE_APPLICATION_CREDENTIALS".to_string(),
+            batch_rows: 500,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SheetsConfig {
+    /// Variable holding the path of the service account's JSON key file. The spreadsheet must be
+    /// shared with the account's email as an editor.
+    pub credentials_env: String,
+}
+
+impl Default for SheetsConfig {
+    fn default() -> Self {
+        Self {
+            credentials_env: "GOOGLE_APPLICATION_CREDENTIALS".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ConsumeConfig {
+    /// NATS server, e.g. `nats://nats.internal:4222`.
+    pub url: String,
+    /// Variable holding the path of a NATS credentials (`.creds`) file; unset connects without.
+    pub credentials_env: Option<String>,
+    /// JetStream stream holding the signal events.
+    pub stream: String,
+    /// Only read events published to this subject, which may hold wildcards; unset reads the whole
+    /// stream.
+    pub subject: Option<String>,
+    /// Durable consumer whose acknowledgements track what has been recorded.
+    pub durable: String,
+    /// Events fetched per batch; the checkpoint moves once a batch is handled.
+    pub batch: usize,
+    /// Seconds an event may go unacknowledged before the broker redelivers it.
+    pub ack_wait_secs: u64,
+}
+
+impl Default for ConsumeConfig {
+    fn default() -> Self {
+        Self {
+            url: "nats://localhost:4222".to_string(),
+            credentials_env: None,
+            stream: "SIGNALS".to_string(),
+            subject: None,
+            durable: "cohort-early-warning".to_string(),
+            batch: 100,
+            ack_wait_secs: 30,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct SalesforceConfig {
+    /// The org's My Domain URL, e.g. `https://acme.my.salesforce.com`.
+    pub instance_url: String,
+    /// Variables holding the connected app's consumer key and secret, used in the client
+    /// credentials flow.
+    #[serde(default = "SalesforceConfig::default_client_id_env")]
+    pub client_id_env: String,
+    #[serde(default = "SalesforceConfig::default_client_secret_env")]
+    pub client_secret_env: String,
+    #[serde(default = "SalesforceConfig::default_api_version")]
+    pub api_version: String,
+    /// Object records are upserted into, e.g. `Case` or a custom `Scholar_Alert__c`.
+    #[serde(default = "SalesforceConfig::default_object")]
+    pub object: String,
+    /// External ID field on `object` holding the scholar's email, so a scholar who turns critical
+    /// again updates their record instead of opening another.
+    pub external_id_field: String,
+    /// Salesforce field = one of [`SCORE_FIELDS`], filled from the scholar's score.
+    #[serde(default)]
+    pub fields: BTreeMap<String, String>,
+    /// Salesforce field = fixed text, e.g. `Origin = "Early Warning"`.
+    #[serde(default)]
+    pub constants: BTreeMap<String, String>,
+}
+
+impl SalesforceConfig {
+    fn default_client_id_env() -> String {
+        "CEW_SALESFORCE_CLIENT_ID".to_string()
+    }
+
+    fn default_client_secret_env() -> String {
+        "CEW_SALESFORCE_CLIENT_SECRET".to_string()
+    }
+
+    fn default_api_version() -> String {
+        "v61.0".to_string()
+    }
+
+    fn default_object() -> String {
+        "Case".to_string()
+    }
+
+    fn validate(&self) -> anyhow::Result<()> {
+        if !self.instance_url.starts_with("https://") {
+            anyhow::bail!(
+                "[salesforce] instance_url must be an https URL like \"https://acme.my.salesforce.com\""
+            );
+        }
+        if self.external_id_field.trim().is_empty() {
+            anyhow::bail!("[salesforce] external_id_field is required");
+        }
+        validate_score_fields("[salesforce.fields]", &self.fields)?;
+        for field in self.fields.keys() {
+            if self.constants.contains_key(field) || *field == self.external_id_field {
+                anyhow::bail!("[salesforce] sets {field} more than once");
+            }
+        }
+        if self.constants.contains_key(&self.external_id_field) {
+            anyhow::bail!(
+                "[salesforce] sets {} more than once",
+                self.external_id_field
+            );
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct AirtableConfig {
+    /// Base ID, like `appXXXXXXXXXXXXXX`.
+    pub base_id: String,
+    /// Table name or ID holding one row per scholar.
+    #[serde(default = "AirtableConfig::default_table")]
+    pub table: String,
+    /// Variable holding a personal access token with read and write access to the base.
+    #[serde(default = "AirtableConfig::default_token_env")]
+    pub token_env: String,
+    /// Field holding the scholar's email, which rows are matched on.
+    #[serde(default = "AirtableConfig::default_email_field")]
+    pub email_field: String,
+    /// Field advisors set to the follow-up status; each change is recorded as an intervention.
+    #[serde(default = "AirtableConfig::default_status_field")]
+    pub status_field: String,
+    /// Type of the interventions recorded from status changes.
+    #[serde(default = "AirtableConfig::default_intervention_type")]
+    pub intervention_type: String,
+    /// Airtable field = one of [`SCORE_FIELDS`], overwritten on every sync.
+    #[serde(default = "AirtableConfig::default_fields")]
+    pub fields: BTreeMap<String, String>,
+}
+
+impl AirtableConfig {
+    fn default_table() -> String {
+        "Scholars".to_string()
+    }
+
+    fn default_token_env() -> String {
+        "CEW_AIRTABLE_TOKEN".to_string()
+    }
+
+    fn default_email_field() -> String {
+        "Email".to_string()
+    }
+
+    fn default_status_field() -> String {
+        "Follow-up Status".to_string()
+    }
+
+    fn default_intervention_type() -> String {
+        "follow-up".to_string()
+    }
+
+    fn default_fields() -> BTreeMap<String, String> {
+        [
+            ("Name", "full_name"),
+            ("Cohort", "cohort"),
+            ("Risk Score", "score"),
+            ("Tier", "tier"),
+        ]
+        .into_iter()
+        .map(|(field, value)| (field.to_string(), value.to_string()))
+        .collect()
+    }
+
+    fn validate(&self) -> anyhow::Result<()> {
+        if self.base_id.trim().is_empty() {
+            anyhow::bail!("[airtable] base_id is required");
+        }
+        validate_score_fields("[airtable.fields]", &self.fields)?;
+        for field in [&self.email_field, &self.status_field] {
+            if self.fields.contains_key(field) {
+                anyhow::bail!("[airtable.fields] can't overwrite {field}");
+            }
+        }
+        if self.email_field == self.status_field {
+            anyhow::bail!("[airtable] email_field and status_field must differ");
+        }
+        Ok(())
+    }
+}
+
+/// Checks that every entry of a `fields` table names one of [`SCORE_FIELDS`].
+fn validate_score_fields(section: &str, fields: &BTreeMap<String, String>) -> anyhow::Result<()> {
+    for (field, value) in fields {
+        if !SCORE_FIELDS.contains(&value.as_str()) {
+            anyhow::bail!(
+                "{section} {field} = {value:?} is not one of {}",
+                SCORE_FIELDS.join(", ")
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Where [`Config::load`] looks for a config file when `--config` is not given, in order:
+/// `cew.toml` and `cohort-early-warning.toml` in the working directory, then
+/// `cohort-early-warning.toml` in `$XDG_CONFIG_HOME`, or `~/.config` when that is unset or not an
+/// absolute path.
+pub fn search_paths(xdg_config_home: Option<OsString>, home: Option<OsString>) -> Vec<PathBuf> {
+    let mut paths = vec![
+        PathBuf::from(DEFAULT_CONFIG_FILE),
+        PathBuf::from(CONFIG_FILE_NAME),
+    ];
+    let config_dir = xdg_config_home
+        .map(PathBuf::from)
+        .filter(|dir| dir.is_absolute())
+        .or_else(|| home.map(|home| PathBuf::from(home).join(".config")));
+    paths.extend(config_dir.map(|dir| dir.join(CONFIG_FILE_NAME)));
+    paths
+}
+
+impl Config {
+    pub fn parse(text: &str) -> anyhow::Result<Self> {
+        let config: Config = toml::from_str(text)?;
+        config.severity.validate()?;
+        config.scoring.validate(config.severity)?;
+        config.validate_watch()?;
+        config.validate_rules()?;
+        config.validate_playbooks()?;
+        config.validate_enrichment()?;
+        config.retention.validate()?;
+        let pct = config.report.trend_alert_pct;
+        if !pct.is_finite() || pct < 0.0 {
+            anyhow::bail!("[report] trend_alert_pct must be a percentage of 0 or more, got {pct}");
+        }
+        config.validate_api()?;
+        config.calendar.validate()?;
+        config.validate_defaults()?;
+        if let Some(salesforce) = &config.salesforce {
+            salesforce.validate()?;
+        }
+        if let Some(airtable) = &config.airtable {
+            airtable.validate()?;
+        }
+        Ok(config)
+    }
+
+    /// The file `load` reads: `path` if given, else the first of [`search_paths`] that exists.
+    /// `None` means the built-in defaults apply.
+    pub fn resolve_path(path: Option<&Path>) -> Option<PathBuf> {
+        match path {
+            Some(path) => Some(path.to_path_buf()),
+            None => search_paths(
+                std::env::var_os("XDG_CONFIG_HOME"),
+                std::env::var_os("HOME"),
+            )
+            .into_iter()
+            .find(|path| path.exists()),
+        }
+    }
+
+    /// Loads `path` if given, else the first config file found by [`search_paths`], else
+    /// defaults.
+    pub fn load(path: Option<&Path>) -> anyhow::Result<Self> {
+        let Some(path) = Self::resolve_path(path) else {
+            return Ok(Self::default());
+        };
+
+        let text = std::fs::read_to_string(&path)
+            .with_context(|| format!("failed to read config {}", path.display()))?;
+        Self::parse(&text).with_context(|| format!("invalid config {}", path.display()))
+    }
+
+    /// The `[import]` note and size limits with the `[severity]` bounds and `[dates]` window, as
+    /// imports, `signal add`, `POST /signals`, and `consume` apply them unless flags override.
+    pub fn import_limits(&self) -> ImportLimits {
+        let import = &self.import;
+        ImportLimits {
+            max_file_bytes: import.max_file_mb.saturating_mul(1024 * 1024),
+            max_rows: import.max_rows,
+            max_note_chars: import.max_note_length,
+            chunk_size: import.chunk_size,
+            note_policy: import.note_policy,
+            summary_sentences: import.summary_sentences,
+            severity: self.severity,
+            dates: Some(self.dates),
+            on_conflict: import.on_conflict,
+            ..ImportLimits::default()
+        }
+    }
+
+    fn validate_api(&self) -> anyhow::Result<()> {
+        if self.api.max_body_bytes == 0 {
+            anyhow::bail!("[api] max_body_bytes must be positive");
+        }
+        let origins = &self.api.cors_origins;
+        if origins.len() > 1 && origins.iter().any(|origin| origin == "*") {
+            anyhow::bail!("[api] cors_origins can't mix \"*\" with specific origins");
+        }
+        for origin in origins.iter().filter(|origin| *origin != "*") {
+            let host = origin
+                .strip_prefix("https://")
+                .or_else(|| origin.strip_prefix("http://"));
+            if !host.is_some_and(|host| {
+                !host.is_empty()
+                    && !host.contains('/')
+                    && host.bytes().all(|b| b.is_ascii_graphic())
+            }) {
+                anyhow::bail!(
+                    "[api] cors_origins entry {origin:?} is not an origin like \
+                     \"https://dash.example.org\" (no path or trailing slash)"
+                );
+            }
+        }
+        Ok(())
+    }
+
+    fn validate_defaults(&self) -> anyhow::Result<()> {
+        if self.defaults.since_days <= 0 {
+            anyhow::bail!("[defaults] since_days must be positive");
+        }
+        let import = &self.import;
+        if import.jobs == 0 {
+            anyhow::bail!("[import] jobs must be positive");
+        }
+        if import.chunk_size == 0 {
+            anyhow::bail!("[import] chunk_size must be positive");
+        }
+        if !(1..=50_000).contains(&self.bigquery.batch_rows) {
+            anyhow::bail!("[bigquery] batch_rows must be between 1 and 50000");
+        }
+        if !(1..=1_000).contains(&self.consume.batch) {
+            anyhow::bail!("[consume] batch must be between 1 and 1000");
+        }
+        if self.consume.ack_wait_secs == 0 {
+            anyhow::bail!("[consume] ack_wait_secs must be positive");
+        }
+        if self.database.max_connections == 0 {
+            anyhow::bail!("[database] max_connections must be positive");
+        }
+        if let Some(mode) = &self.database.ssl_mode {
+            if !SSL_MODES.contains(&mode.as_str()) {
+                anyhow::bail!(
+                    "[database] ssl_mode must be one of {}, got {mode:?}",
+                    SSL_MODES.join(", ")
+                );
+            }
+        }
+        Ok(())
+    }
+
+    fn validate_watch(&self) -> anyhow::Result<()> {
+        if self.watch.schedule.is_some() {
+            self.watch.schedule()?;
+        }
+        if self.watch.since_days <= 0 {
+            anyhow::bail!("[watch] since_days must be positive");
+        }
+        if self.watch.cooldown_days.is_some_and(|days| days <= 0) {
+            anyhow::bail!("[watch] cooldown_days must be positive");
+        }
+        for name in &self.watch.channels {
+            self.channel(name)?;
+        }
+        if self.escalation.after_hours <= 0 {
+            anyhow::bail!("[escalation] after_hours must be positive");
+        }
+        for name in &self.escalation.channels {
+            self.channel(name).context("[escalation]")?;
+        }
+        for (name, channel) in &self.channels {
+            channel
+                .validate()
+                .with_context(|| format!("[channels.{name}]"))?;
+        }
+        Ok(())
+    }
+
+    fn validate_rules(&self) -> anyhow::Result<()> {
+        let mut names = std::collections::HashSet::new();
+        for rule in &self.rules {
+            rule.validate(self.severity).context("[[rules]]")?;
+            if !names.insert(rule.name.as_str()) {
+                anyhow::bail!("[[rules]] name {} is used twice", rule.name);
+            }
+            self.check_rule_channels(rule)
+                .with_context(|| format!("[[rules]] {}", rule.name))?;
+        }
+        Ok(())
+    }
+
+    fn validate_enrichment(&self) -> anyhow::Result<()> {
+        self.enrichment.validate().context("[enrichment]")?;
+        for category in self.scoring.notes.categories.keys() {
+            if !self.enrichment.categories.contains_key(category) {
+                anyhow::bail!(
+                    "[scoring.notes] category {category} is not one of [enrichment] categories"
+                );
+            }
+        }
+        Ok(())
+    }
+
+    fn validate_playbooks(&self) -> anyhow::Result<()> {
+        let mut names = std::collections::HashSet::new();
+        for playbook in &self.playbooks {
+            playbook.validate(self.severity).context("[[playbooks]]")?;
+            if !names.insert(playbook.name.as_str()) {
+                anyhow::bail!("[[playbooks]] name {} is used twice", playbook.name);
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks that `rule`'s channels exist, and that only critical-tier rules text anyone.
+    pub fn check_rule_channels(&self, rule: &AlertRule) -> anyhow::Result<()> {
+        for name in &rule.channels {
+            if self.channel(name)?.kind == ChannelKind::Sms && rule.min_tier != Some(Tier::Critical)
+            {
+                anyhow::bail!("sms channel {name} only takes rules with min_tier = \"critical\"");
+            }
+        }
+        Ok(())
+    }
+
+    pub fn channel(&self, name: &str) -> anyhow::Result<&ChannelConfig> {
+        self.channels
+            .get(name)
+            .with_context(|| format!("unknown channel {name}; add [channels.{name}] to the config"))
+    }
+
+    /// Renders the config back to TOML, e.g. after `onboard` adds a source. Comments are not kept.
+    pub fn to_toml(&self) -> anyhow::Result<String> {
+        Ok(toml::to_string(self)?)
+    }
+
+    pub fn source(&self, name: &str) -> anyhow::Result<&SourceMapping> {
+        self.sources
+            .get(name)
+            .with_context(|| format!("unknown source {name}; add [sources.{name}] to the config"))
+    }
+
+    /// Database URL of a configured environment, read from its environment variable.
+    pub fn database_url(&self, environment: &str) -> anyhow::Result<String> {
+        let configured = self.environments.get(environment).with_context(|| {
+            format!(
+                "unknown environment {environment}; add [environments.{environment}] to the config"
+            )
+        })?;
+        std::env::var(&configured.database_url_env).with_context(|| {
+            format!(
+                "{} must be set for environment {environment}",
+                configured.database_url_env
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_tiers_and_escalations() {
+        let config = Config::parse(
+            r#"
+            [scoring.tiers]
+            moderate = 3.0
+            high = 6.0
+            critical = 9.0
+
+            [[scoring.escalations]]
+            signal_type = "wellbeing"
+            min_severity = 4
+            tier = "critical"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.scoring.tiers.high, 6.0);
+        assert_eq!(config.scoring.escalations.len(), 1);
+        assert_eq!(config.scoring.escalations[0].tier, Tier::Critical);
+    }
+
+    #[test]
+    fn parses_environments() {
+        let config = Config::parse(
+            r#"
+            [environments.staging]
+            database_url_env = "CEW_STAGING_DATABASE_URL"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.environments["staging"].database_url_env,
+            "CEW_STAGING_DATABASE_URL"
+        );
+        assert!(config.database_url("prod").is_err());
+    }
+
+    #[test]
+    fn parses_watch_schedule_and_channels() {
+        let config = Config::parse(
+            r#"
+            [watch]
+            schedule = "0 7 * * 1-5"
+            alert_tier = "critical"
+            channels = ["ops"]
+            cooldown_days = 7
+
+            [channels.ops]
+            kind = "webhook"
+            url_env = "CEW_OPS_WEBHOOK_URL"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.watch.schedule().unwrap().to_string(), "0 7 * * 1-5");
+        assert_eq!(config.watch.since_days, 30);
+        assert_eq!(config.watch.alert_tier, Tier::Critical);
+        assert_eq!(config.watch.cooldown_days, Some(7));
+        assert_eq!(config.channel("ops").unwrap().kind, ChannelKind::Webhook);
+
+        assert!(Config::parse("[watch]\nschedule = \"every morning\"").is_err());
+        assert!(Config::parse("[watch]\nchannels = [\"missing\"]").is_err());
+        assert!(Config::parse("[watch]\ncooldown_days = 0").is_err());
+        assert!(Config::default().watch.schedule().is_err());
+    }
+
+    #[test]
+    fn parses_escalation() {
+        let config = Config::parse(
+            r#"
+            [escalation]
+            after_hours = 8
+            channels = ["director"]
+
+            [channels.director]
+            kind = "teams"
+            url_env = "CEW_DIRECTOR_TEAMS_URL"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.escalation.after_hours, 8);
+        assert_eq!(config.escalation.tier, Tier::Critical);
+
+        assert!(Config::parse("[escalation]\nchannels = [\"missing\"]").is_err());
+        assert!(Config::parse("[escalation]\nafter_hours = 0").is_err());
+    }
+
+    #[test]
+    fn parses_alert_rules() {
+        let config = Config::parse(
+            r#"
+            [[rules]]
+            name = "attendance-run"
+            signal_type = "attendance"
+            min_count = 3
+            since_days = 14
+            channels = ["ops"]
+
+            [[rules]]
+            name = "critical-signal"
+            min_severity = 5
+            new_only = true
+
+            [channels.ops]
+            kind = "slack"
+            url_env = "CEW_OPS_SLACK_URL"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.rules.len(), 2);
+        assert_eq!(
+            config.rules[0].describe(),
+            "3+ attendance signals within 14 days"
+        );
+        assert_eq!(
+            config.rules[1].describe(),
+            "a new signal with severity >= 5 within 30 days"
+        );
+        assert!(config.rules[1].enabled);
+        assert_eq!(Config::parse(&config.to_toml().unwrap()).unwrap(), config);
+
+        assert!(Config::parse("[[rules]]\nname = \"empty\"").is_err());
+        assert!(
+            Config::parse("[[rules]]\nname = \"r\"\nmin_score = 8.0\nnew_only = true").is_err()
+        );
+        assert!(Config::parse(
+            "[[rules]]\nname = \"r\"\nmin_score = 8.0\nchannels = [\"missing\"]"
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn sms_channels_take_only_critical_rules_and_respect_quiet_hours() {
+        let sms = r#"
+            [channels.oncall]
+            kind = "sms"
+            account_sid_env = "CEW_TWILIO_SID"
+            auth_token_env = "CEW_TWILIO_TOKEN"
+            from = "+15550001111"
+            recipients = [{ number = "+15552223333", quiet_hours = "22:00-07:00", utc_offset = "-05:00" }]
+            "#;
+        let config = Config::parse(&format!(
+            "{sms}\n[[rules]]\nname = \"critical\"\nmin_tier = \"critical\"\nchannels = [\"oncall\"]"
+        ))
+        .unwrap();
+        let recipient = &config.channel("oncall").unwrap().recipients[0];
+        let at = |time: &str| format!("2026-03-07T{time}:00Z").parse().unwrap();
+        // 03:30 and 22:30 in UTC-5.
+        assert!(recipient.is_quiet(at("08:30")));
+        assert!(recipient.is_quiet(at("03:30")));
+        assert!(!recipient.is_quiet(at("12:00")));
+        assert!(!recipient.is_quiet(at("02:30")));
+
+        assert!(Config::parse(&format!(
+            "{sms}\n[[rules]]\nname = \"high\"\nmin_tier = \"high\"\nchannels = [\"oncall\"]"
+        ))
+        .is_err());
+        assert!(Config::parse(&sms.replace("22:00-07:00", "late")).is_err());
+        assert!(Config::parse(&sms.replace("+15550001111", "555-0001")).is_err());
+        assert!(Config::parse("[channels.ops]\nkind = \"slack\"").is_err());
+        assert_eq!(
+            parse_quiet_hours("7:00-7:00"),
+            Err("expected quiet hours like 22:00-07:00, got `7:00-7:00`".to_string())
+        );
+    }
+
+    #[test]
+    fn parses_retention_ages() {
+        let config = Config::parse("[retention]\nmax_age = \"3y\"\nmode = \"anonymize\"").unwrap();
+        assert_eq!(config.retention.max_age_days().unwrap(), Some(3 * 365));
+        assert_eq!(config.retention.mode, RetentionMode::Anonymize);
+        assert_eq!(Config::default().retention.max_age_days().unwrap(), None);
+
+        assert_eq!(parse_age_days("52w"), Ok(364));
+        assert!(parse_age_days("3 years").is_err());
+        assert!(parse_age_days("0d").is_err());
+        assert!(parse_age_days("3é").is_err());
+        assert!(parse_age_days("é").is_err());
+        assert!(parse_age_days("").is_err());
+        assert!(parse_age_days(&format!("{}y", i64::MAX)).is_err());
+        assert!(Config::parse("[retention]\nmax_age = \"forever\"").is_err());
+
+        let config = Config::parse(
+            "[retention]\nmax_age = \"3y\"\nschedule = \"0 3 * * *\"\n\
+             [retention.notes]\nmax_age = \"1y\"\n\
+             [retention.interventions]\nmax_age = \"5y\"\nmode = \"anonymize\"",
+        )
+        .unwrap();
+        let today = NaiveDate::from_ymd_opt(2026, 3, 2).unwrap();
+        let plan = config.retention.plan(today, None, None).unwrap();
+        assert_eq!(
+            plan.signals,
+            Some((
+                today - chrono::Duration::days(3 * 365),
+                RetentionMode::Delete
+            ))
+        );
+        assert_eq!(plan.notes, Some(today - chrono::Duration::days(365)));
+        assert_eq!(
+            plan.interventions,
+            Some((
+                today - chrono::Duration::days(5 * 365),
+                RetentionMode::Anonymize
+            ))
+        );
+        let plan = config
+            .retention
+            .plan(today, Some(30), Some(RetentionMode::Anonymize))
+            .unwrap();
+        assert_eq!(
+            plan.signals,
+            Some((today - chrono::Duration::days(30), RetentionMode::Anonymize))
+        );
+        assert!(Config::default()
+            .retention
+            .plan(today, None, None)
+            .unwrap()
+            .is_empty());
+        assert!(Config::parse("[retention]\nschedule = \"0 3 * * *\"").is_err());
+        assert!(Config::parse("[retention.notes]\nmax_age = \"soon\"").is_err());
+    }
+
+    #[test]
+    fn round_trips_through_toml() {
+        let mut config = Config::parse(
+            r#"
+            [scoring]
+            staff_dedup_days = 3
+
+            [[scoring.escalations]]
+            signal_type = "wellbeing"
+            min_severity = 4
+            tier = "critical"
+
+            [environments.prod]
+            database_url_env = "CEW_PROD_DATABASE_URL"
+            "#,
+        )
+        .unwrap();
+        config.sources.insert(
+            "sis".to_string(),
+            SourceMapping {
+                columns: [("email".to_string(), "Student Email".to_string())].into(),
+                cohort: Some("2027".to_string()),
+            },
+        );
+
+        assert_eq!(Config::parse(&config.to_toml().unwrap()).unwrap(), config);
+    }
+
+    #[test]
+    fn cors_origins_must_be_bare_origins() {
+        let parse = |origins: &str| Config::parse(&format!("[api]\ncors_origins = {origins}"));
+        assert!(parse(r#"["https://dash.example.org", "http://localhost:5173"]"#).is_ok());
+        assert!(parse(r#"["*"]"#).is_ok());
+        assert!(parse(r#"["https://dash.example.org/"]"#).is_err());
+        assert!(parse(r#"["dash.example.org"]"#).is_err());
+        assert!(parse(r#"["*", "https://dash.example.org"]"#).is_err());
+    }
+
+    #[test]
+    fn example_config_is_valid() {
+        let config = Config::parse(include_str!("../examples/cew.toml")).unwrap();
+        let finals = NaiveDate::from_ymd_opt(2026, 12, 14).unwrap();
+        assert_eq!(config.calendar.label(finals), "exam week: Fall finals");
+        assert!(Config::parse("[calendar]\nbreak_factor = 0").is_err());
+    }
+
+    #[test]
+    fn template_spells_out_the_defaults() {
+        assert_eq!(Config::parse(CONFIG_TEMPLATE).unwrap(), Config::default());
+    }
+
+    #[test]
+    fn parses_salesforce_mapping() {
+        let config = Config::parse(
+            r#"
+            [salesforce]
+            instance_url = "https://acme.my.salesforce.com"
+            external_id_field = "Scholar_Email__c"
+
+            [salesforce.fields]
+            Subject = "summary"
+
+            [salesforce.constants]
+            Origin = "Early Warning"
+            "#,
+        )
+        .unwrap();
+        let salesforce = config.salesforce.unwrap();
+        assert_eq!(salesforce.object, "Case");
+        assert_eq!(salesforce.client_id_env, "CEW_SALESFORCE_CLIENT_ID");
+        assert_eq!(salesforce.fields["Subject"], "summary");
+
+        let invalid = |extra: &str| {
+            Config::parse(&format!(
+                "[salesforce]\ninstance_url = \"https://acme.my.salesforce.com\"\n\
+                 external_id_field = \"Scholar_Email__c\"\n{extra}"
+            ))
+            .is_err()
+        };
+        assert!(!invalid(""));
+        assert!(invalid("[salesforce.fields]\nSubject = \"notes\""));
+        assert!(invalid(
+            "[salesforce.fields]\nSubject = \"summary\"\n[salesforce.constants]\nSubject = \"x\""
+        ));
+        assert!(invalid("[salesforce.constants]\nScholar_Email__c = \"x\""));
+        assert!(Config::parse(
+            "[salesforce]\ninstance_url = \"http://acme.example\"\nexternal_id_field = \"E__c\""
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn parses_defaults_import_and_database() {
+        let config = Config::parse(
+            r#"
+            [defaults]
+            since_days = 60
+
+            [import]
+            max_rows = 5000
+            note_policy = "truncate"
+            on_conflict = "update"
+
+            [database]
+            max_connections = 12
+            ssl_mode = "verify-full"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.defaults.since_days, 60);
+        assert_eq!(config.import.max_rows, 5000);
+        assert_eq!(config.import.chunk_size, 500);
+        assert_eq!(config.import.note_policy, NotePolicy::Truncate);
+        assert_eq!(config.import.on_conflict, OnConflict::Update);
+        assert_eq!(config.database.max_connections, 12);
+        assert_eq!(config.database.ssl_mode.as_deref(), Some("verify-full"));
+        assert_eq!(config.database.retries, 5);
+
+        assert!(Config::parse(
+            "[defaults]
+since_days = 0"
+        )
+        .is_err());
+        assert!(Config::parse(
+            "[import]
+chunk_size = 0"
+        )
+        .is_err());
+        assert!(Config::parse(
+            "[database]
+ssl_mode = \"strict\""
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn searches_working_directory_then_xdg_config_dir() {
+        assert_eq!(
+            search_paths(Some("/etc/xdg-home".into()), Some("/home/ada".into())),
+            [
+                PathBuf::from("cew.toml"),
+                PathBuf::from("cohort-early-warning.toml"),
+                PathBuf::from("/etc/xdg-home/cohort-early-warning.toml"),
+            ]
+        );
+        // A relative $XDG_CONFIG_HOME is ignored, as the spec requires.
+        assert_eq!(
+            search_paths(Some("config".into()), Some("/home/ada".into()))[2],
+            PathBuf::from("/home/ada/.config/cohort-early-warning.toml")
+        );
+        assert_eq!(search_paths(None, None).len(), 2);
+    }
+
+    #[test]
+    fn empty_config_uses_defaults() {
+        assert_eq!(Config::parse("").unwrap(), Config::default());
+    }
+
+    #[test]
+    fn rejects_unknown_keys_and_bad_severity() {
+        assert!(Config::parse("[scoring]\nthreshold = 2").is_err());
+        assert!(Config::parse(
+            "[[scoring.escalations]]\nsignal_type = \"wellbeing\"\nmin_severity = 9\ntier = \"high\""
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,322 @@
+use std::time::{Duration, Instant};
+
+use anyhow::Context;
+use async_nats::jetstream::consumer::{pull, AckPolicy, DeliverPolicy, PullConsumer};
+use async_nats::jetstream::{self, AckKind};
+use futures_util::StreamExt;
+use serde_json::Value;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::audit::{self, AuditAction};
+use crate::config::{Config, ConsumeConfig};
+use crate::db;
+use crate::import;
+use crate::models::NewSignal;
+
+/// JSON Schema every event must satisfy before it is recorded.
+pub const EVENT_SCHEMA: &str = include_str!("signal_event.schema.json");
+
+/// How long a fetch waits for events before returning what it has.
+const FETCH_WAIT: Duration = Duration::from_secs(5);
+
+/// `source` of the import batches events are recorded in.
+const SOURCE: &str = "consume";
+
+/// Compiles [`EVENT_SCHEMA`], checking `format`s such as `email` and `date` too.
+pub fn event_validator() -> jsonschema::Validator {
+    let schema: Value = serde_json::from_str(EVENT_SCHEMA).expect("the event schema is JSON");
+    jsonschema::options()
+        .should_validate_formats(true)
+        .build(&schema)
+        .expect("the event schema is a valid JSON Schema")
+}
+
+/// The signal in an event's payload, or every way it breaks the schema.
+pub fn parse_event(validator: &jsonschema::Validator, payload: &[u8]) -> anyhow::Result<NewSignal> {
+    let event: Value = serde_json::from_slice(payload).context("event is not JSON")?;
+    let errors: Vec<String> = validator
+        .iter_errors(&event)
+        .map(|err| match err.instance_path.as_str() {
+            "" => err.to_string(),
+            path => format!("{path}: {err}"),
+        })
+        .collect();
+    if !errors.is_empty() {
+        anyhow::bail!("event breaks the schema: {}", errors.join("; "));
+    }
+    Ok(serde_json::from_value(event)?)
+}
+
+/// Source key for an event that didn't bring one, so a redelivered event is recorded once.
+pub fn event_source_key(stream: &str, sequence: u64) -> String {
+    format!("{stream}-{sequence}")
+}
+
+/// What `consume` has done since it started.
+#[derive(Debug, Default)]
+pub struct ConsumeSummary {
+    pub recorded: u64,
+    /// Events whose source key was already recorded, e.g. redeliveries.
+    pub duplicates: u64,
+    /// Events that broke the schema or were refused, which are not redelivered.
+    pub rejected: u64,
+}
+
+async fn connect(config: &ConsumeConfig) -> anyhow::Result<async_nats::Client> {
+    let mut options = async_nats::ConnectOptions::new().name("cohort-early-warning consume");
+    if let Some(env) = &config.credentials_env {
+        let path = std::env::var_os(env)
+            .with_context(|| format!("{env} must be set to the path of a NATS credentials file"))?;
+        options = options
+            .credentials_file(&path)
+            .await
+            .with_context(|| format!("failed to read NATS credentials from {env}"))?;
+    }
+    options
+        .connect(&config.url)
+        .await
+        .with_context(|| format!("could not connect to NATS at {}", config.url))
+}
+
+/// The durable consumer, created on first use to start after the last checkpoint, so a consumer
+/// deleted on the broker picks up where the database says it left off.
+async fn durable_consumer(
+    client: async_nats::Client,
+    config: &ConsumeConfig,
+    checkpoint: Option<i64>,
+) -> anyhow::Result<PullConsumer> {
+    let stream = jetstream::new(client)
+        .get_stream(&config.stream)
+        .await
+        .with_context(|| format!("could not open JetStream stream {}", config.stream))?;
+    let deliver_policy = match checkpoint {
+        Some(sequence) => DeliverPolicy::ByStartSequence {
+            start_sequence: sequence as u64 + 1,
+        },
+        None => DeliverPolicy::All,
+    };
+    stream
+        .get_or_create_consumer(
+            &config.durable,
+            pull::Config {
+                durable_name: Some(config.durable.clone()),
+                description: Some("cohort-early-warning consume".to_string()),
+                deliver_policy,
+                ack_policy: AckPolicy::Explicit,
+                ack_wait: Duration::from_secs(config.ack_wait_secs),
+                filter_subject: config.subject.clone().unwrap_or_default(),
+                ..Default::default()
+            },
+        )
+        .await
+        .with_context(|| format!("could not open consumer {}", config.durable))
+}
+
+/// Records `signal`, retrying while the database is unreachable. The inner result says whether it
+/// was new, or why it was refused for good; the outer error means the retries gave up.
+#[tracing::instrument(skip_all, fields(source_key = signal.source_key.as_deref()))]
+async fn record(
+    pool: &PgPool,
+    org_id: Uuid,
+    config: &Config,
+    signal: NewSignal,
+    policy: &db::RetryPolicy,
+) -> anyhow::Result<Result<bool, anyhow::Error>> {
+    let mut attempt = 0;
+    loop {
+        match import::add_signal(
+            pool,
+            org_id,
+            signal.clone(),
+            SOURCE,
+            &config.import_limits(),
+        )
+        .await
+        {
+            Ok((_, inserted)) => return Ok(Ok(inserted)),
+            Err(err) => match policy.backoff(&err, attempt) {
+                Some(delay) => {
+                    tracing::warn!(error = format!("{err:#}"), "retrying event");
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                None if db::is_transient(&err) => return Err(err),
+                None => return Ok(Err(err)),
+            },
+        }
+    }
+}
+
+/// Records events from the configured stream until interrupted, or until it is drained when
+/// `once` is set. Each event is acknowledged only after it is recorded or rejected, so a crash
+/// redelivers it; events carry or are given a source key, so redeliveries are no-ops. Once a
+/// batch is handled its last stream sequence is checkpointed. When the database stays
+/// unreachable the unhandled events are released for redelivery and `consume` fails.
+pub async fn run(
+    pool: &PgPool,
+    org_id: Uuid,
+    config: &Config,
+    once: bool,
+) -> anyhow::Result<ConsumeSummary> {
+    let settings = &config.consume;
+    let validator = event_validator();
+    let checkpoint =
+        db::consumer_checkpoint(pool, org_id, &settings.stream, &settings.durable).await?;
+    let consumer = durable_consumer(connect(settings).await?, settings, checkpoint).await?;
+    let policy = db::RetryPolicy::default();
+    let mut summary = ConsumeSummary::default();
+    println!(
+        "Consuming {} as {}{}.",
+        settings.stream,
+        settings.durable,
+        match checkpoint {
+            Some(sequence) => format!(", checkpointed through {sequence}"),
+            None => String::new(),
+        }
+    );
+
+    loop {
+        let fetch = consumer
+            .fetch()
+            .max_messages(settings.batch)
+            .expires(FETCH_WAIT)
+            .messages();
+        let mut messages = tokio::select! {
+            messages = fetch => messages.map_err(|err| anyhow::anyhow!("fetch failed: {err}"))?,
+            _ = tokio::signal::ctrl_c() => {
+                println!("Stopping consume.");
+                return Ok(summary);
+            }
+        };
+
+        let started = Instant::now();
+        let mut handled = 0;
+        let mut last_sequence = None;
+        let mut recorded = 0;
+        while let Some(message) = messages.next().await {
+            let message = message.map_err(|err| anyhow::anyhow!("fetch failed: {err}"))?;
+            let sequence = message
+                .info()
+                .map_err(|err| anyhow::anyhow!("not a JetStream message: {err}"))?
+                .stream_sequence;
+            let outcome = match parse_event(&validator, &message.payload) {
+                Ok(signal) => {
+                    let signal = NewSignal {
+                        source_key: signal
+                            .source_key
+                            .or_else(|| Some(event_source_key(&settings.stream, sequence))),
+                        submitted_by: signal.submitted_by.or_else(|| Some(SOURCE.to_string())),
+                        ..signal
+                    };
+                    match record(pool, org_id, config, signal, &policy).await {
+                        Ok(outcome) => outcome,
+                        Err(err) => {
+                            // Leave this event and the rest of the batch for redelivery.
+                            let _ = message.ack_with(AckKind::Nak(None)).await;
+                            return Err(err.context(format!(
+                                "could not record event {sequence} of {}",
+                                settings.stream
+                            )));
+                        }
+                    }
+                }
+                Err(err) => Err(err),
+            };
+            match outcome {
+                Ok(true) => recorded += 1,
+                Ok(false) => summary.duplicates += 1,
+                Err(err) => {
+                    tracing::warn!(sequence, error = format!("{err:#}"), "rejected event");
+                    println!("Rejected event {sequence}: {err:#}");
+                    summary.rejected += 1;
+                    message
+                        .ack_with(AckKind::Term)
+                        .await
+                        .map_err(|err| anyhow::anyhow!("could not reject event: {err}"))?;
+                    handled += 1;
+                    last_sequence = Some(sequence);
+                    continue;
+                }
+            }
+            message
+                .ack()
+                .await
+                .map_err(|err| anyhow::anyhow!("could not acknowledge event: {err}"))?;
+            handled += 1;
+            last_sequence = Some(sequence);
+        }
+
+        if let Some(sequence) = last_sequence {
+            db::set_consumer_checkpoint(
+                pool,
+                org_id,
+                &settings.stream,
+                &settings.durable,
+                sequence as i64,
+            )
+            .await?;
+            if recorded > 0 {
+                audit::record(
+                    pool,
+                    "consume",
+                    AuditAction::Insert,
+                    "signals",
+                    recorded,
+                    &format!("{} through {sequence}", settings.stream),
+                )
+                .await?;
+            }
+            summary.recorded += recorded;
+            tracing::info!(
+                handled,
+                recorded,
+                through = sequence,
+                elapsed_ms = started.elapsed().as_millis() as u64,
+                "consumed batch"
+            );
+        } else if once {
+            return Ok(summary);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validates_events_against_the_schema() {
+        let validator = event_validator();
+        let signal = parse_event(
+            &validator,
+            br#"{"email": "ada@example.edu", "signal_type": "attendance", "severity": 3,
+                "note": "Missed two sessions", "occurred_at": "2026-03-02", "tags": ["sis"]}"#,
+        )
+        .unwrap();
+        assert_eq!(signal.email, "ada@example.edu");
+        assert_eq!(
+            signal.occurred_at,
+            chrono::NaiveDate::from_ymd_opt(2026, 3, 2)
+        );
+        assert_eq!(signal.source_key, None);
+
+        let err = parse_event(
+            &validator,
+            br#"{"email": "not an email", "signal_type": "attendance", "severity": "high",
+                "note": "", "room": "B12"}"#,
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(err.contains("/email"), "{err}");
+        assert!(err.contains("/severity"), "{err}");
+        assert!(err.contains("room"), "{err}");
+
+        assert!(parse_event(&validator, b"attendance,3").is_err());
+    }
+
+    #[test]
+    fn events_without_a_source_key_are_keyed_by_sequence() {
+        assert_eq!(event_source_key("SIGNALS", 42), "SIGNALS-42");
+    }
+}
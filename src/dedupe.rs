@@ -0,0 +1,185 @@
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+use crate::models::Scholar;
+
+/// Two roster entries that may be one person, with the record `scholar merge` should keep.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct Suggestion {
+    /// The earlier of the two records.
+    pub keep: Scholar,
+    pub absorb: Scholar,
+    /// Higher of the name and email similarity, from 0 to 1.
+    pub similarity: f64,
+    pub reasons: Vec<String>,
+}
+
+/// Name reduced to what survives a change of source: accents, case, punctuation, and word order
+/// are dropped, so `Lee, Renée` and `Renee Lee` match.
+pub fn normalize_name(name: &str) -> String {
+    let cleaned: String = name
+        .nfd()
+        .filter(|c| !is_combining_mark(*c))
+        .flat_map(char::to_lowercase)
+        .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+        .collect();
+    let mut words: Vec<&str> = cleaned.split_whitespace().collect();
+    words.sort_unstable();
+    words.join(" ")
+}
+
+/// Part of an email before the `@`, without a `+tag` or the dots, dashes, and underscores
+/// providers treat loosely.
+pub fn email_local_part(email: &str) -> String {
+    let local = email.split('@').next().unwrap_or_default();
+    let local = local.split('+').next().unwrap_or_default();
+    local
+        .nfd()
+        .filter(|c| !is_combining_mark(*c))
+        .flat_map(char::to_lowercase)
+        .filter(|c| !matches!(c, '.' | '-' | '_'))
+        .collect()
+}
+
+/// One minus the edit distance over the longer length: 1 for equal strings, 0 for nothing shared.
+fn similarity(a: &str, b: &str) -> f64 {
+    let (a, b): (Vec<char>, Vec<char>) = (a.chars().collect(), b.chars().collect());
+    let longest = a.len().max(b.len());
+    if longest == 0 {
+        return 0.0;
+    }
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.iter().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            current[j + 1] = (previous[j] + usize::from(ca != cb))
+                .min(previous[j + 1] + 1)
+                .min(current[j] + 1);
+        }
+        previous = current;
+    }
+    1.0 - previous[b.len()] as f64 / longest as f64
+}
+
+/// Pairs of `scholars` whose normalized names or email local parts are at least `min_similarity`
+/// alike, most similar first. Anonymized records are left out, as their pseudonyms look alike.
+pub fn suggest(scholars: &[Scholar], min_similarity: f64) -> Vec<Suggestion> {
+    let keys: Vec<(String, String)> = scholars
+        .iter()
+        .map(|s| (normalize_name(&s.full_name), email_local_part(&s.email)))
+        .collect();
+    let mut suggestions = Vec::new();
+    for (i, a) in scholars.iter().enumerate() {
+        for (j, b) in scholars.iter().enumerate().skip(i + 1) {
+            if a.email.ends_with("@invalid") || b.email.ends_with("@invalid") {
+                continue;
+            }
+            let name = similarity(&keys[i].0, &keys[j].0);
+            let email = similarity(&keys[i].1, &keys[j].1);
+            if name.max(email) < min_similarity {
+                continue;
+            }
+            let mut reasons = Vec::new();
+            match name {
+                1.0 => reasons.push("same name".to_string()),
+                n if n >= min_similarity => reasons.push(format!("similar names ({n:.2})")),
+                _ => {}
+            }
+            match email {
+                1.0 => reasons.push("same email before the @".to_string()),
+                e if e >= min_similarity => reasons.push(format!("similar emails ({e:.2})")),
+                _ => {}
+            }
+            let (keep, absorb) = if (b.created_at, &b.email) < (a.created_at, &a.email) {
+                (b, a)
+            } else {
+                (a, b)
+            };
+            if keep.cohort != absorb.cohort {
+                reasons.push(format!(
+                    "different cohorts ({} and {})",
+                    keep.cohort, absorb.cohort
+                ));
+            }
+            suggestions.push(Suggestion {
+                keep: keep.clone(),
+                absorb: absorb.clone(),
+                similarity: name.max(email),
+                reasons,
+            });
+        }
+    }
+    suggestions.sort_by(|a, b| {
+        b.similarity
+            .total_cmp(&a.similarity)
+            .then_with(|| a.keep.email.cmp(&b.keep.email))
+            .then_with(|| a.absorb.email.cmp(&b.absorb.email))
+    });
+    suggestions
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::*;
+
+    fn scholar(name: &str, email: &str, cohort: &str, day: u32) -> Scholar {
+        Scholar {
+            full_name: name.to_string(),
+            email: email.to_string(),
+            cohort: cohort.to_string(),
+            status: "active".to_string(),
+            advisor: None,
+            created_at: Utc.with_ymd_and_hms(2026, 1, day, 0, 0, 0).unwrap(),
+            deleted: false,
+        }
+    }
+
+    #[test]
+    fn suggests_likely_duplicates_for_review() {
+        let scholars = [
+            scholar("Renée Lee", "renee.lee@groupscholar.com", "2026", 2),
+            scholar("Lee, Renee", "rlee@partner.org", "2026", 5),
+            scholar("Jo Park", "jo.park@groupscholar.com", "2026", 3),
+            scholar("Joe Park", "jo_park+sis@gs-alumni.org", "2025", 1),
+            scholar("Avery Stone", "avery.stone@groupscholar.com", "2026", 4),
+            scholar("Scholar 1a2b3c4d", "anonymized-1@invalid", "2026", 6),
+            scholar("Scholar 1a2b3c4e", "anonymized-2@invalid", "2026", 7),
+        ];
+        assert_eq!(normalize_name(" Lee,  RENÉE "), "lee renee");
+        assert_eq!(email_local_part("Jo_Park+sis@gs-alumni.org"), "jopark");
+
+        let suggestions = suggest(&scholars, 0.85);
+        let pairs: Vec<(&str, &str, Vec<&str>)> = suggestions
+            .iter()
+            .map(|s| {
+                (
+                    s.keep.email.as_str(),
+                    s.absorb.email.as_str(),
+                    s.reasons.iter().map(String::as_str).collect(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            pairs,
+            [
+                (
+                    "jo_park+sis@gs-alumni.org",
+                    "jo.park@groupscholar.com",
+                    vec![
+                        "similar names (0.88)",
+                        "same email before the @",
+                        "different cohorts (2025 and 2026)"
+                    ]
+                ),
+                (
+                    "renee.lee@groupscholar.com",
+                    "rlee@partner.org",
+                    vec!["same name"]
+                ),
+            ]
+        );
+        assert_eq!(suggest(&scholars, 0.95).len(), 2);
+    }
+}
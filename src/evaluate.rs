@@ -0,0 +1,298 @@
+use std::collections::BTreeMap;
+
+use chrono::{Duration, NaiveDate};
+
+use crate::models::{Outcome, OutcomeType, SignalRecord};
+use crate::risk::{self, ScoringConfig, Tier};
+
+/// Weeks of warning `render` reports recall for.
+const WARNING_WEEKS: [i64; 5] = [1, 2, 4, 8, 12];
+
+/// How the scoring treated one scholar whose time in the program has ended.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Evaluated {
+    pub email: String,
+    pub ended_on: NaiveDate,
+    pub withdrew: bool,
+    /// Highest tier reached at any weekly check before the outcome.
+    pub peak: Tier,
+    /// First weekly check at or above the threshold tier.
+    pub first_flagged: Option<NaiveDate>,
+}
+
+impl Evaluated {
+    /// Whole weeks between the first flag and the outcome.
+    pub fn lead_weeks(&self) -> Option<i64> {
+        self.first_flagged
+            .map(|flagged| (self.ended_on - flagged).num_days() / 7)
+    }
+}
+
+/// Replays scoring weekly over the `horizon_weeks` before each scholar's earliest withdrawal or
+/// graduation, scoring the `since_days` of signals up to each check. Scholars with only GPA
+/// outcomes are left out, as they have not ended either way.
+pub fn evaluate(
+    outcomes: &[Outcome],
+    signals: &[SignalRecord],
+    horizon_weeks: i64,
+    since_days: i64,
+    threshold: Tier,
+    scoring: &ScoringConfig,
+) -> Vec<Evaluated> {
+    let mut endings: BTreeMap<&str, &Outcome> = BTreeMap::new();
+    for outcome in outcomes {
+        if outcome.outcome_type == OutcomeType::Gpa {
+            continue;
+        }
+        let ending = endings.entry(&outcome.scholar_email).or_insert(outcome);
+        if outcome.occurred_on < ending.occurred_on {
+            *ending = outcome;
+        }
+    }
+    let mut signals_of: BTreeMap<&str, Vec<SignalRecord>> = BTreeMap::new();
+    for signal in signals {
+        if endings.contains_key(signal.scholar_email.as_str()) {
+            signals_of
+                .entry(&signal.scholar_email)
+                .or_default()
+                .push(signal.clone());
+        }
+    }
+
+    endings
+        .into_iter()
+        .map(|(email, ending)| {
+            let signals = signals_of.get(email).map_or(&[][..], Vec::as_slice);
+            let mut peak = Tier::Low;
+            let mut first_flagged = None;
+            // Oldest check first, so the first flag found is the earliest.
+            for weeks_before in (1..=horizon_weeks).rev() {
+                let as_of = ending.occurred_on - Duration::weeks(weeks_before);
+                let prior: Vec<SignalRecord> = signals
+                    .iter()
+                    .filter(|signal| signal.occurred_at <= as_of)
+                    .cloned()
+                    .collect();
+                let Some(score) = risk::score_signals_with(&prior, since_days, as_of, scoring)
+                    .into_iter()
+                    .next()
+                else {
+                    continue;
+                };
+                peak = peak.max(score.tier);
+                if score.tier >= threshold && first_flagged.is_none() {
+                    first_flagged = Some(as_of);
+                }
+            }
+            Evaluated {
+                email: email.to_string(),
+                ended_on: ending.occurred_on,
+                withdrew: ending.outcome_type == OutcomeType::Withdrawal,
+                peak,
+                first_flagged,
+            }
+        })
+        .collect()
+}
+
+fn percent(count: usize, total: usize) -> String {
+    if total == 0 {
+        "n/a".to_string()
+    } else {
+        format!("{:.0}%", count as f64 * 100.0 / total as f64)
+    }
+}
+
+fn median(sorted: &[i64]) -> f64 {
+    let middle = sorted.len() / 2;
+    if sorted.len().is_multiple_of(2) {
+        (sorted[middle - 1] + sorted[middle]) as f64 / 2.0
+    } else {
+        sorted[middle] as f64
+    }
+}
+
+/// Precision and recall of flagging at `threshold`, lead times, recall by weeks of warning, and
+/// withdrawal rates by peak tier.
+pub fn render(
+    evaluated: &[Evaluated],
+    threshold: Tier,
+    horizon_weeks: i64,
+    since_days: i64,
+) -> String {
+    let flagged = evaluated
+        .iter()
+        .filter(|e| e.first_flagged.is_some())
+        .count();
+    let withdrawals = evaluated.iter().filter(|e| e.withdrew).count();
+    let mut leads: Vec<i64> = evaluated
+        .iter()
+        .filter(|e| e.withdrew)
+        .filter_map(Evaluated::lead_weeks)
+        .collect();
+    leads.sort_unstable();
+    let caught = leads.len();
+
+    let mut out = vec![
+        format!(
+            "Evaluated {} scholars with a recorded withdrawal or graduation, scored weekly over the \
+             {horizon_weeks} weeks before it ({since_days}-day window; flagged = {} tier or above).",
+            evaluated.len(),
+            threshold.as_str()
+        ),
+        String::new(),
+        format!(
+            "Precision: {} ({caught} of {flagged} flagged scholars withdrew)",
+            percent(caught, flagged)
+        ),
+        format!(
+            "Recall: {} ({caught} of {withdrawals} withdrawals were flagged)",
+            percent(caught, withdrawals)
+        ),
+    ];
+    match (leads.first(), leads.last()) {
+        (Some(shortest), Some(longest)) => out.push(format!(
+            "Lead time: median {:.1} weeks before withdrawal (range {shortest}-{longest})",
+            median(&leads)
+        )),
+        _ => out.push("Lead time: no withdrawals were flagged".to_string()),
+    }
+
+    out.push(String::new());
+    out.push("Recall by warning time:".to_string());
+    for weeks in WARNING_WEEKS
+        .into_iter()
+        .filter(|weeks| *weeks <= horizon_weeks)
+    {
+        let early = leads.iter().filter(|lead| **lead >= weeks).count();
+        out.push(format!(
+            "- Flagged {weeks}+ weeks ahead: {} ({early} of {withdrawals})",
+            percent(early, withdrawals)
+        ));
+    }
+
+    let mut rows = vec![["peak tier", "scholars", "withdrew", "withdrawal rate"].map(String::from)];
+    for tier in [Tier::Critical, Tier::High, Tier::Moderate, Tier::Low] {
+        let members: Vec<&Evaluated> = evaluated.iter().filter(|e| e.peak == tier).collect();
+        let withdrew = members.iter().filter(|e| e.withdrew).count();
+        rows.push([
+            tier.as_str().to_string(),
+            members.len().to_string(),
+            withdrew.to_string(),
+            percent(withdrew, members.len()),
+        ]);
+    }
+    let widths: Vec<usize> = (0..rows[0].len())
+        .map(|column| rows.iter().map(|row| row[column].len()).max().unwrap_or(0))
+        .collect();
+    out.push(String::new());
+    out.push("Calibration by peak tier:".to_string());
+    for row in &rows {
+        let line: Vec<String> = row
+            .iter()
+            .zip(&widths)
+            .enumerate()
+            .map(|(column, (cell, width))| match column {
+                0 => format!("{cell:<width$}"),
+                _ => format!("{cell:>width$}"),
+            })
+            .collect();
+        out.push(line.join("  ").trim_end().to_string());
+    }
+    out.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn date(month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, month, day).unwrap()
+    }
+
+    fn outcome(email: &str, outcome_type: OutcomeType, occurred_on: NaiveDate) -> Outcome {
+        Outcome {
+            id: Uuid::new_v4(),
+            scholar_name: email.to_string(),
+            scholar_email: email.to_string(),
+            cohort: "2026".to_string(),
+            outcome_type,
+            occurred_on,
+            gpa: (outcome_type == OutcomeType::Gpa).then_some(3.0),
+            note: String::new(),
+        }
+    }
+
+    fn signal(email: &str, occurred_at: NaiveDate, severity: i32) -> SignalRecord {
+        SignalRecord {
+            scholar_id: Uuid::from_u128(u128::from(email.as_bytes()[0])),
+            scholar_name: email.to_string(),
+            scholar_email: email.to_string(),
+            cohort: "2026".to_string(),
+            signal_type: "attendance".to_string(),
+            severity,
+            occurred_at,
+            note: String::new(),
+            advisor: None,
+            submitted_by: None,
+            attachments: Vec::new(),
+            note_categories: Vec::new(),
+            note_sentiment: None,
+        }
+    }
+
+    #[test]
+    fn measures_flags_against_outcomes() {
+        let outcomes = [
+            // Flagged five weeks out.
+            outcome("a@x.org", OutcomeType::Withdrawal, date(6, 1)),
+            // Never flagged.
+            outcome("b@x.org", OutcomeType::Withdrawal, date(6, 1)),
+            // Flagged, yet graduated.
+            outcome("c@x.org", OutcomeType::Graduation, date(6, 1)),
+            outcome("d@x.org", OutcomeType::Graduation, date(6, 1)),
+            // Still enrolled: not evaluated.
+            outcome("e@x.org", OutcomeType::Gpa, date(6, 1)),
+        ];
+        let signals = [
+            signal("a@x.org", date(4, 20), 5),
+            signal("a@x.org", date(4, 24), 5),
+            // After the last weekly check, so never seen.
+            signal("b@x.org", date(5, 30), 5),
+            signal("c@x.org", date(3, 2), 5),
+            signal("c@x.org", date(3, 3), 5),
+            signal("d@x.org", date(5, 1), 1),
+            signal("e@x.org", date(5, 1), 5),
+        ];
+
+        let evaluated = evaluate(
+            &outcomes,
+            &signals,
+            12,
+            30,
+            Tier::High,
+            &ScoringConfig::default(),
+        );
+        assert_eq!(evaluated.len(), 4);
+        assert_eq!(evaluated[0].first_flagged, Some(date(4, 27)));
+        assert_eq!(evaluated[0].lead_weeks(), Some(5));
+        assert_eq!(evaluated[1].peak, Tier::Low);
+        assert!(evaluated[2].first_flagged.is_some() && !evaluated[2].withdrew);
+
+        let text = render(&evaluated, Tier::High, 12, 30);
+        assert!(
+            text.contains("Precision: 50% (1 of 2 flagged scholars withdrew)"),
+            "{text}"
+        );
+        assert!(text.contains("Recall: 50% (1 of 2 withdrawals were flagged)"));
+        assert!(text.contains("Lead time: median 5.0 weeks before withdrawal (range 5-5)"));
+        assert!(text.contains("- Flagged 4+ weeks ahead: 50% (1 of 2)"));
+        assert!(text.contains("- Flagged 8+ weeks ahead: 0% (0 of 2)"));
+        assert!(
+            text.contains("low               2         1              50%"),
+            "{text}"
+        );
+    }
+}
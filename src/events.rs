@@ -0,0 +1,229 @@
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Arc;
+
+use chrono::NaiveDate;
+use sqlx::postgres::PgListener;
+use sqlx::PgPool;
+use tokio::sync::{broadcast, mpsc};
+use uuid::Uuid;
+
+use crate::models::ScholarScore;
+use crate::risk::{self, ScoringConfig, Tier};
+use crate::{db, watch};
+
+/// Channel `029_signal_events` notifies for every inserted signal.
+pub const CHANNEL: &str = "cohort_early_warning_signals";
+
+/// Events held for each `/events` subscriber; one that falls further behind skips ahead.
+pub const BUFFER: usize = 1024;
+
+/// A signal as announced by the insert trigger, without its note.
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct SignalEvent {
+    #[serde(skip_serializing)]
+    pub org_id: Uuid,
+    pub email: String,
+    pub cohort: String,
+    pub signal_type: String,
+    pub severity: i32,
+    pub occurred_at: NaiveDate,
+    pub source_key: String,
+    pub submitted_by: Option<String>,
+}
+
+/// A scholar whose tier moved after new signals, in either direction.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct TierChange {
+    pub email: String,
+    pub full_name: String,
+    pub cohort: String,
+    pub from: Tier,
+    pub to: Tier,
+    pub score: f64,
+    pub escalated_by: Option<String>,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(untagged)]
+pub enum Event {
+    Signal(SignalEvent),
+    TierChange(TierChange),
+}
+
+impl Event {
+    /// Email of the scholar the event is about.
+    pub fn email(&self) -> &str {
+        match self {
+            Event::Signal(signal) => &signal.email,
+            Event::TierChange(change) => &change.email,
+        }
+    }
+
+    /// SSE event name.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Event::Signal(_) => "signal",
+            Event::TierChange(_) => "tier_change",
+        }
+    }
+}
+
+/// Last tier seen for each scholar. Scholars never seen count as `low`, as in `watch`.
+#[derive(Debug, Default)]
+pub struct TierTracker {
+    tiers: HashMap<String, Tier>,
+}
+
+impl TierTracker {
+    pub fn new(scores: &[ScholarScore]) -> Self {
+        Self {
+            tiers: scores
+                .iter()
+                .map(|score| (score.scholar_email.clone(), score.tier))
+                .collect(),
+        }
+    }
+
+    /// Records `score`, returning the change when its tier differs from the last one seen.
+    pub fn update(&mut self, score: &ScholarScore) -> Option<TierChange> {
+        let from = self
+            .tiers
+            .insert(score.scholar_email.clone(), score.tier)
+            .unwrap_or(Tier::Low);
+        (from != score.tier).then(|| TierChange {
+            email: score.scholar_email.clone(),
+            full_name: score.scholar_name.clone(),
+            cohort: score.cohort.clone(),
+            from,
+            to: score.tier,
+            score: score.score,
+            escalated_by: score.escalated_by.clone(),
+        })
+    }
+}
+
+/// Publishes every signal inserted for `org_id`, by any process, to `sender`, followed by the tier
+/// changes it causes over the last `since_days`. Runs until the database connection fails for
+/// good.
+pub async fn relay(
+    pool: PgPool,
+    org_id: Uuid,
+    scoring: Arc<ScoringConfig>,
+    since_days: i64,
+    sender: broadcast::Sender<Event>,
+) -> anyhow::Result<()> {
+    let mut listener = PgListener::connect_with(&pool).await?;
+    listener.listen(CHANNEL).await?;
+    // Scored after listening, so a signal inserted in between still gets rescored.
+    let tracker = TierTracker::new(&watch::score_org(&pool, org_id, since_days, &scoring).await?);
+    let (touched, rescore) = mpsc::unbounded_channel();
+    tokio::try_join!(
+        forward(&mut listener, org_id, &sender, touched),
+        rescore_touched(&pool, org_id, &scoring, since_days, tracker, rescore, &sender)
+    )?;
+    Ok(())
+}
+
+/// Publishes each notification for `org_id` and queues its scholar for rescoring.
+async fn forward(
+    listener: &mut PgListener,
+    org_id: Uuid,
+    sender: &broadcast::Sender<Event>,
+    touched: mpsc::UnboundedSender<String>,
+) -> anyhow::Result<()> {
+    loop {
+        let notification = listener.recv().await?;
+        let signal: SignalEvent = match serde_json::from_str(notification.payload()) {
+            Ok(signal) => signal,
+            Err(err) => {
+                tracing::warn!(error = %err, "skipping unreadable signal event");
+                continue;
+            }
+        };
+        if signal.org_id != org_id {
+            continue;
+        }
+        // Nobody may be connected; the event is simply dropped then.
+        let _ = sender.send(Event::Signal(signal.clone()));
+        touched.send(signal.email)?;
+    }
+}
+
+/// Rescores scholars as their signals arrive. An import announces many signals per scholar, so
+/// everything queued while one round ran is scored once in the next.
+async fn rescore_touched(
+    pool: &PgPool,
+    org_id: Uuid,
+    scoring: &ScoringConfig,
+    since_days: i64,
+    mut tracker: TierTracker,
+    mut touched: mpsc::UnboundedReceiver<String>,
+    sender: &broadcast::Sender<Event>,
+) -> anyhow::Result<()> {
+    while let Some(email) = touched.recv().await {
+        let mut emails = BTreeSet::from([email]);
+        while let Ok(email) = touched.try_recv() {
+            emails.insert(email);
+        }
+        for email in emails {
+            let filter = db::SignalFilter {
+                org_id: Some(org_id),
+                emails: vec![email],
+                ..db::SignalFilter::new(risk::cutoff_date(since_days))
+            };
+            let scores =
+                db::score_scope(pool, &filter, since_days, scoring, db::DEFAULT_PAGE_SIZE).await?;
+            for change in scores.iter().filter_map(|score| tracker.update(score)) {
+                let _ = sender.send(Event::TierChange(change));
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn score(email: &str, tier: Tier) -> ScholarScore {
+        ScholarScore {
+            scholar_name: "Avery Lee".to_string(),
+            scholar_email: email.to_string(),
+            cohort: "2026".to_string(),
+            score: 9.0,
+            signal_count: 3,
+            advisor: None,
+            tier,
+            escalated_by: None,
+            collapsed_signals: 0,
+            percentile: 0.0,
+            cohort_percentile: 0.0,
+            last_contact: None,
+            last_signal: None,
+        }
+    }
+
+    #[test]
+    fn reports_only_tiers_that_moved() {
+        let mut tracker = TierTracker::new(&[score("a@x.org", Tier::Moderate)]);
+        assert_eq!(tracker.update(&score("a@x.org", Tier::Moderate)), None);
+
+        let change = tracker.update(&score("a@x.org", Tier::High)).unwrap();
+        assert_eq!((change.from, change.to), (Tier::Moderate, Tier::High));
+        assert_eq!(tracker.update(&score("a@x.org", Tier::High)), None);
+
+        assert_eq!(tracker.update(&score("new@x.org", Tier::Low)), None);
+        let change = tracker.update(&score("b@x.org", Tier::Critical)).unwrap();
+        assert_eq!(change.from, Tier::Low);
+    }
+
+    #[test]
+    fn reads_the_trigger_payload() {
+        let payload = r#"{"org_id" : "00000000-0000-0000-0000-000000000001", "email" : "a@x.org", "cohort" : "2026", "signal_type" : "attendance", "severity" : 3, "occurred_at" : "2026-03-02", "source_key" : "k-1", "submitted_by" : null}"#;
+        let signal: SignalEvent = serde_json::from_str(payload).unwrap();
+        assert_eq!(signal.org_id, db::DEFAULT_ORG_ID);
+        let event = serde_json::to_value(Event::Signal(signal)).unwrap();
+        assert_eq!(event["severity"], 3);
+        assert!(event.get("org_id").is_none());
+    }
+}
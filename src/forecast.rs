@@ -0,0 +1,214 @@
+use std::collections::BTreeMap;
+
+use chrono::{Duration, NaiveDate};
+
+use crate::models::CohortTrend;
+use crate::trends::week_start;
+
+/// Level smoothing for Holt's method; higher follows recent weeks more closely.
+const ALPHA: f64 = 0.5;
+/// Trend smoothing for Holt's method; higher lets the slope turn faster.
+const BETA: f64 = 0.3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Method {
+    /// Holt's linear trend (double exponential smoothing): follows rising or falling volume
+    Holt,
+    /// Mean of the most recent weeks, projected flat
+    MovingAverage,
+}
+
+impl Method {
+    fn label(self) -> &'static str {
+        match self {
+            Method::Holt => "Holt's linear trend",
+            Method::MovingAverage => "moving average",
+        }
+    }
+}
+
+/// One projected week.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct ForecastWeek {
+    pub week_start: NaiveDate,
+    pub signal_count: f64,
+    /// `None` when the cohort had no signals to average over.
+    pub avg_severity: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct CohortForecast {
+    pub cohort: String,
+    /// Signals in the last complete week, for comparison.
+    pub last_week_signals: i64,
+    pub weeks: Vec<ForecastWeek>,
+}
+
+/// Projects `horizon` more values of `series`, oldest first. Empty series project nothing.
+pub fn project(series: &[f64], method: Method, window: usize, horizon: usize) -> Vec<f64> {
+    if series.is_empty() {
+        return Vec::new();
+    }
+    match method {
+        Method::MovingAverage => {
+            let recent = &series[series.len().saturating_sub(window.max(1))..];
+            let mean = recent.iter().sum::<f64>() / recent.len() as f64;
+            vec![mean; horizon]
+        }
+        Method::Holt => {
+            let mut level = series[0];
+            let mut trend = series.get(1).map_or(0.0, |second| second - series[0]);
+            for value in &series[1..] {
+                let previous = level;
+                level = ALPHA * value + (1.0 - ALPHA) * (level + trend);
+                trend = BETA * (level - previous) + (1.0 - BETA) * trend;
+            }
+            (1..=horizon)
+                .map(|step| level + step as f64 * trend)
+                .collect()
+        }
+    }
+}
+
+/// Forecasts each cohort's weekly signal count and average severity from the `history` complete
+/// weeks before `today`'s. Weeks without signals count as zero signals and are skipped for
+/// severity.
+pub fn forecast(
+    trends: &[CohortTrend],
+    cohorts: &[String],
+    today: NaiveDate,
+    history: usize,
+    method: Method,
+    window: usize,
+    horizon: usize,
+) -> Vec<CohortForecast> {
+    let current = week_start(today);
+    let first = current - Duration::weeks(history as i64);
+    let mut by_cohort: BTreeMap<&str, BTreeMap<NaiveDate, &CohortTrend>> = BTreeMap::new();
+    for trend in trends {
+        if trend.week_start >= first && trend.week_start < current {
+            by_cohort
+                .entry(&trend.cohort)
+                .or_default()
+                .insert(trend.week_start, trend);
+        }
+    }
+
+    cohorts
+        .iter()
+        .map(|cohort| {
+            let weeks = by_cohort.get(cohort.as_str());
+            let week = |index: usize| {
+                weeks.and_then(|weeks| weeks.get(&(first + Duration::weeks(index as i64))))
+            };
+            let counts: Vec<f64> = (0..history)
+                .map(|index| week(index).map_or(0.0, |trend| trend.signal_count as f64))
+                .collect();
+            let severities: Vec<f64> = (0..history)
+                .filter_map(|index| week(index).map(|trend| trend.avg_severity))
+                .collect();
+            let counts = project(&counts, method, window, horizon);
+            let severities = project(&severities, method, window, horizon);
+            CohortForecast {
+                cohort: cohort.clone(),
+                last_week_signals: history
+                    .checked_sub(1)
+                    .and_then(week)
+                    .map_or(0, |trend| trend.signal_count),
+                weeks: (0..horizon)
+                    .map(|step| ForecastWeek {
+                        week_start: current + Duration::weeks(step as i64),
+                        signal_count: counts.get(step).map_or(0.0, |count| count.max(0.0)),
+                        avg_severity: severities
+                            .get(step)
+                            .map(|severity| severity.clamp(1.0, 5.0)),
+                    })
+                    .collect(),
+            }
+        })
+        .collect()
+}
+
+pub fn render(forecasts: &[CohortForecast], method: Method, history: usize) -> String {
+    let mut out = Vec::new();
+    for forecast in forecasts {
+        if !out.is_empty() {
+            out.push(String::new());
+        }
+        out.push(format!(
+            "{} ({} over {history} weeks; last week {} signals):",
+            forecast.cohort,
+            method.label(),
+            forecast.last_week_signals
+        ));
+        for week in &forecast.weeks {
+            let severity = week
+                .avg_severity
+                .map(|severity| format!(" (avg severity {severity:.1})"))
+                .unwrap_or_default();
+            out.push(format!(
+                "- Week of {}: ~{:.0} signals{severity}",
+                week.week_start, week.signal_count
+            ));
+        }
+    }
+    out.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trend(cohort: &str, week_start: NaiveDate, signal_count: i64) -> CohortTrend {
+        CohortTrend {
+            cohort: cohort.to_string(),
+            week_start,
+            signal_count,
+            avg_severity: 3.0,
+            scholar_count: signal_count,
+        }
+    }
+
+    #[test]
+    fn holt_follows_a_steady_rise_and_moving_average_stays_flat() {
+        let rising = [10.0, 12.0, 14.0, 16.0, 18.0];
+        let projected = project(&rising, Method::Holt, 4, 3);
+        for (value, expected) in projected.iter().zip([20.0, 22.0, 24.0]) {
+            assert!((value - expected).abs() < 1e-9, "{projected:?}");
+        }
+        assert_eq!(
+            project(&rising, Method::MovingAverage, 2, 2),
+            vec![17.0, 17.0]
+        );
+        assert!(project(&[], Method::Holt, 4, 2).is_empty());
+    }
+
+    #[test]
+    fn fills_quiet_weeks_and_skips_the_current_one() {
+        // Wednesday; the week of 2026-03-02 is still in progress.
+        let today = NaiveDate::from_ymd_opt(2026, 3, 4).unwrap();
+        let week = |day| NaiveDate::from_ymd_opt(2026, 2, day).unwrap();
+        let trends = [
+            trend("2026", week(2), 8),
+            trend("2026", week(16), 4),
+            trend("2026", week(23), 2),
+            trend("2026", NaiveDate::from_ymd_opt(2026, 3, 2).unwrap(), 40),
+        ];
+        let cohorts = ["2026".to_string(), "2027".to_string()];
+
+        let forecasts = forecast(&trends, &cohorts, today, 4, Method::MovingAverage, 4, 2);
+        assert_eq!(forecasts[0].last_week_signals, 2);
+        // (8 + 0 + 4 + 2) / 4, with the in-progress week left out.
+        assert_eq!(forecasts[0].weeks[0].signal_count, 3.5);
+        assert_eq!(
+            forecasts[0].weeks[0].week_start,
+            NaiveDate::from_ymd_opt(2026, 3, 2).unwrap()
+        );
+        assert_eq!(forecasts[0].weeks[1].avg_severity, Some(3.0));
+
+        assert_eq!(forecasts[1].weeks[0].signal_count, 0.0);
+        assert_eq!(forecasts[1].weeks[0].avg_severity, None);
+        assert!(render(&forecasts, Method::MovingAverage, 4)
+            .contains("2027 (moving average over 4 weeks; last week 0 signals):"));
+    }
+}
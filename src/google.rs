@@ -0,0 +1,79 @@
+use std::path::Path;
+
+use anyhow::Context;
+use chrono::Utc;
+
+/// The fields read from a Google service account's JSON key file.
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct ServiceAccount {
+    pub client_email: String,
+    pub private_key: String,
+    pub token_uri: String,
+    pub project_id: String,
+}
+
+impl ServiceAccount {
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read service account key {}", path.display()))?;
+        serde_json::from_str(&text)
+            .with_context(|| format!("{} is not a service account key file", path.display()))
+    }
+
+    /// The key file named by the variable `env`.
+    pub fn from_env(env: &str) -> anyhow::Result<Self> {
+        let path = std::env::var_os(env).with_context(|| {
+            format!("{env} must be set to the path of a service account key file")
+        })?;
+        Self::load(Path::new(&path))
+    }
+
+    /// Trades a JWT signed with the account's key for an hour-long access token to `scope`.
+    pub async fn access_token(
+        &self,
+        client: &reqwest::Client,
+        scope: &str,
+    ) -> anyhow::Result<String> {
+        #[derive(serde::Serialize)]
+        struct Claims<'a> {
+            iss: &'a str,
+            scope: &'a str,
+            aud: &'a str,
+            iat: i64,
+            exp: i64,
+        }
+        #[derive(serde::Deserialize)]
+        struct TokenResponse {
+            access_token: String,
+        }
+
+        let now = Utc::now().timestamp();
+        let claims = Claims {
+            iss: &self.client_email,
+            scope,
+            aud: &self.token_uri,
+            iat: now,
+            exp: now + 3600,
+        };
+        let key = jsonwebtoken::EncodingKey::from_rsa_pem(self.private_key.as_bytes())
+            .context("the service account's private_key is not an RSA key")?;
+        let assertion = jsonwebtoken::encode(
+            &jsonwebtoken::Header::new(jsonwebtoken::Algorithm::RS256),
+            &claims,
+            &key,
+        )?;
+        let response: TokenResponse = client
+            .post(&self.token_uri)
+            .form(&[
+                ("grant_type", "urn:ietf:params:oauth:grant-type:jwt-bearer"),
+                ("assertion", &assertion),
+            ])
+            .send()
+            .await?
+            .error_for_status()
+            .with_context(|| format!("Google refused a token for {}", self.client_email))?
+            .json()
+            .await?;
+        Ok(response.access_token)
+    }
+}
@@ -0,0 +1,484 @@
+use async_graphql::{
+    ComplexObject, Context, EmptyMutation, EmptySubscription, Object, Schema, SimpleObject,
+};
+use axum::{Extension, Json};
+use chrono::{DateTime, NaiveDate, Utc};
+use uuid::Uuid;
+
+use crate::db;
+use crate::export::ExportRow;
+use crate::models::{Intervention, Scholar, ScholarScore, ScholarStatus, SignalTrend};
+use crate::risk;
+use crate::server::{AppState, Caseload};
+
+/// Deepest nesting a query may use; scholar → signals → interventions needs four levels.
+const MAX_DEPTH: usize = 8;
+
+pub type ApiSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+/// Read-only schema over the organization `state` serves.
+pub fn schema(state: AppState) -> ApiSchema {
+    Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .data(state)
+        .limit_depth(MAX_DEPTH)
+        .finish()
+}
+
+/// `POST /graphql`. Queries made with an advisor key only reach that advisor's scholars.
+pub async fn handler(
+    Extension(schema): Extension<ApiSchema>,
+    caseload: Option<Extension<Caseload>>,
+    Json(request): Json<async_graphql::Request>,
+) -> Json<async_graphql::Response> {
+    let request = match caseload {
+        Some(Extension(caseload)) => request.data(caseload),
+        None => request,
+    };
+    Json(schema.execute(request).await)
+}
+
+/// Email of the advisor the request's key is limited to, if any.
+fn caseload(ctx: &Context<'_>) -> Option<String> {
+    ctx.data_opt::<Caseload>()
+        .map(|Caseload(advisor)| advisor.clone())
+}
+
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// The roster, as `GET /scholars`.
+    async fn scholars(
+        &self,
+        ctx: &Context<'_>,
+        cohort: Option<String>,
+        #[graphql(desc = "active, withdrawn, graduated, or on-leave")] status: Option<String>,
+        #[graphql(default = false)] include_deleted: bool,
+    ) -> async_graphql::Result<Vec<ScholarNode>> {
+        let status = status
+            .map(|status| {
+                <ScholarStatus as clap::ValueEnum>::from_str(&status, true)
+                    .map_err(|_| format!("unknown status {status}"))
+            })
+            .transpose()?;
+        let filter = db::ScholarFilter {
+            cohort,
+            email: None,
+            status,
+            advisor: caseload(ctx),
+            include_deleted,
+        };
+        Ok(scholar_nodes(ctx, &filter).await?)
+    }
+
+    async fn scholar(
+        &self,
+        ctx: &Context<'_>,
+        email: String,
+    ) -> async_graphql::Result<Option<ScholarNode>> {
+        let filter = db::ScholarFilter {
+            email: Some(email),
+            advisor: caseload(ctx),
+            ..db::ScholarFilter::default()
+        };
+        Ok(scholar_nodes(ctx, &filter).await?.pop())
+    }
+
+    /// Scholars ranked by risk score, as `GET /scores`.
+    async fn scores(
+        &self,
+        ctx: &Context<'_>,
+        cohort: Option<String>,
+        #[graphql(desc = "Advisor email; advisor keys always get their own scholars")]
+        advisor: Option<String>,
+        #[graphql(default = 30)] since_days: i64,
+        #[graphql(default = 1000)] limit: usize,
+    ) -> async_graphql::Result<Vec<Score>> {
+        let state = ctx.data_unchecked::<AppState>();
+        let filter = db::SignalFilter {
+            org_id: Some(state.org_id),
+            cohorts: cohort.into_iter().collect(),
+            advisor: caseload(ctx).or(advisor),
+            ..db::SignalFilter::new(risk::cutoff_date(since_days))
+        };
+        let mut scores = state.scores(&filter, since_days).await?;
+        scores.truncate(limit);
+        Ok(scores.into_iter().map(Score::from).collect())
+    }
+
+    /// Signals per week, oldest first.
+    async fn trends(
+        &self,
+        ctx: &Context<'_>,
+        cohort: Option<String>,
+        email: Option<String>,
+        #[graphql(default = 90)] since_days: i64,
+    ) -> async_graphql::Result<Vec<Trend>> {
+        let state = ctx.data_unchecked::<AppState>();
+        let filter = db::SignalFilter {
+            org_id: Some(state.org_id),
+            cohorts: cohort.into_iter().collect(),
+            emails: email.into_iter().collect(),
+            advisor: caseload(ctx),
+            ..db::SignalFilter::new(risk::cutoff_date(since_days))
+        };
+        let trends = db::fetch_weekly_trends(&state.pool, &filter).await?;
+        Ok(trends.into_iter().map(Trend::from).collect())
+    }
+
+    /// Most recent interventions first.
+    async fn interventions(
+        &self,
+        ctx: &Context<'_>,
+        cohort: Option<String>,
+        email: Option<String>,
+        #[graphql(default = 50)] limit: i64,
+    ) -> async_graphql::Result<Vec<InterventionNode>> {
+        let state = ctx.data_unchecked::<AppState>();
+        let caseload = match ctx.data_opt::<Caseload>() {
+            Some(caseload) => Some(caseload.emails(state).await?),
+            None => None,
+        };
+        let interventions = db::list_interventions(
+            &state.pool,
+            state.org_id,
+            email.as_deref(),
+            cohort.as_deref(),
+            // The caseload is applied afterwards, so it can't be limited up front.
+            if caseload.is_some() { i64::MAX } else { limit },
+        )
+        .await?;
+        Ok(interventions
+            .into_iter()
+            .filter(|intervention| {
+                caseload
+                    .as_ref()
+                    .is_none_or(|emails| emails.contains(&intervention.scholar_email))
+            })
+            .take(usize::try_from(limit).unwrap_or(0))
+            .map(InterventionNode::from)
+            .collect())
+    }
+}
+
+async fn scholar_nodes(
+    ctx: &Context<'_>,
+    filter: &db::ScholarFilter,
+) -> anyhow::Result<Vec<ScholarNode>> {
+    let state = ctx.data_unchecked::<AppState>();
+    let scholars = db::list_scholars(&state.pool, state.org_id, filter).await?;
+    Ok(scholars.into_iter().map(ScholarNode).collect())
+}
+
+/// Signals about one scholar in the window, newest first.
+async fn scholar_signals(
+    state: &AppState,
+    email: &str,
+    since_days: i64,
+) -> anyhow::Result<Vec<ExportRow>> {
+    let filter = db::SignalFilter {
+        org_id: Some(state.org_id),
+        emails: vec![email.to_string()],
+        include_inactive: true,
+        ..db::SignalFilter::new(risk::cutoff_date(since_days))
+    };
+    let mut rows = Vec::new();
+    db::for_each_export_row(&state.pool, &filter, db::DEFAULT_PAGE_SIZE, |row| {
+        rows.push(row);
+        Ok(())
+    })
+    .await?;
+    rows.reverse();
+    Ok(rows)
+}
+
+pub struct ScholarNode(Scholar);
+
+#[Object(name = "Scholar")]
+impl ScholarNode {
+    async fn full_name(&self) -> &str {
+        &self.0.full_name
+    }
+
+    async fn email(&self) -> &str {
+        &self.0.email
+    }
+
+    async fn cohort(&self) -> &str {
+        &self.0.cohort
+    }
+
+    async fn status(&self) -> &str {
+        &self.0.status
+    }
+
+    /// Name of the assigned advisor.
+    async fn advisor(&self) -> Option<&str> {
+        self.0.advisor.as_deref()
+    }
+
+    async fn created_at(&self) -> DateTime<Utc> {
+        self.0.created_at
+    }
+
+    async fn deleted(&self) -> bool {
+        self.0.deleted
+    }
+
+    /// Signals in the window, newest first.
+    async fn signals(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = 30)] since_days: i64,
+    ) -> async_graphql::Result<Vec<SignalNode>> {
+        let state = ctx.data_unchecked::<AppState>();
+        let rows = scholar_signals(state, &self.0.email, since_days).await?;
+        Ok(rows.into_iter().map(SignalNode).collect())
+    }
+
+    /// Current score over the window; null when the scholar has no signals in it.
+    async fn score(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = 30)] since_days: i64,
+    ) -> async_graphql::Result<Option<Score>> {
+        let state = ctx.data_unchecked::<AppState>();
+        let filter = db::SignalFilter {
+            org_id: Some(state.org_id),
+            emails: vec![self.0.email.clone()],
+            include_inactive: true,
+            ..db::SignalFilter::new(risk::cutoff_date(since_days))
+        };
+        let scores = db::score_scope(
+            &state.pool,
+            &filter,
+            since_days,
+            &state.scoring,
+            db::DEFAULT_PAGE_SIZE,
+        )
+        .await?;
+        Ok(scores.into_iter().next().map(Score::from))
+    }
+
+    /// Most recent interventions first.
+    async fn interventions(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = 20)] limit: i64,
+    ) -> async_graphql::Result<Vec<InterventionNode>> {
+        let state = ctx.data_unchecked::<AppState>();
+        let interventions =
+            db::list_interventions(&state.pool, state.org_id, Some(&self.0.email), None, limit)
+                .await?;
+        Ok(interventions
+            .into_iter()
+            .map(InterventionNode::from)
+            .collect())
+    }
+}
+
+pub struct SignalNode(ExportRow);
+
+#[Object(name = "Signal")]
+impl SignalNode {
+    async fn signal_type(&self) -> &str {
+        &self.0.signal_type
+    }
+
+    async fn severity(&self) -> i32 {
+        self.0.severity
+    }
+
+    async fn note(&self) -> &str {
+        &self.0.note
+    }
+
+    async fn occurred_at(&self) -> NaiveDate {
+        self.0.occurred_at
+    }
+
+    async fn source_key(&self) -> &str {
+        &self.0.source_key
+    }
+
+    async fn tags(&self) -> &[String] {
+        &self.0.tags
+    }
+
+    async fn submitted_by(&self) -> Option<&str> {
+        self.0.submitted_by.as_deref()
+    }
+
+    async fn scholar_email(&self) -> &str {
+        &self.0.email
+    }
+
+    /// Interventions recorded in response to this signal.
+    async fn interventions(
+        &self,
+        ctx: &Context<'_>,
+    ) -> async_graphql::Result<Vec<InterventionNode>> {
+        let state = ctx.data_unchecked::<AppState>();
+        let interventions = db::list_interventions(
+            &state.pool,
+            state.org_id,
+            Some(&self.0.email),
+            None,
+            i64::MAX,
+        )
+        .await?;
+        Ok(interventions
+            .into_iter()
+            .filter(|intervention| intervention.signal_keys.contains(&self.0.source_key))
+            .map(InterventionNode::from)
+            .collect())
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct Score {
+    pub scholar_name: String,
+    pub scholar_email: String,
+    pub cohort: String,
+    pub score: f64,
+    pub signal_count: usize,
+    pub advisor: Option<String>,
+    /// low, moderate, high, or critical.
+    pub tier: String,
+    pub escalated_by: Option<String>,
+    /// Share of all scored scholars, 0-100, at or below this score.
+    pub percentile: f64,
+    /// Share of scored scholars in the same cohort, 0-100, at or below this score.
+    pub cohort_percentile: f64,
+    /// Latest intervention or self-report check-in.
+    pub last_contact: Option<NaiveDate>,
+}
+
+impl From<ScholarScore> for Score {
+    fn from(score: ScholarScore) -> Self {
+        Self {
+            tier: score.tier.as_str().to_string(),
+            scholar_name: score.scholar_name,
+            scholar_email: score.scholar_email,
+            cohort: score.cohort,
+            score: score.score,
+            signal_count: score.signal_count,
+            advisor: score.advisor,
+            escalated_by: score.escalated_by,
+            percentile: score.percentile,
+            cohort_percentile: score.cohort_percentile,
+            last_contact: score.last_contact,
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct Trend {
+    pub week_start: NaiveDate,
+    pub signal_count: i64,
+    pub avg_severity: f64,
+    pub scholar_count: i64,
+}
+
+impl From<SignalTrend> for Trend {
+    fn from(trend: SignalTrend) -> Self {
+        Self {
+            week_start: trend.week_start,
+            signal_count: trend.signal_count,
+            avg_severity: trend.avg_severity,
+            scholar_count: trend.scholar_count,
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+#[graphql(name = "Intervention", complex)]
+pub struct InterventionNode {
+    pub id: Uuid,
+    pub scholar_email: String,
+    pub occurred_on: NaiveDate,
+    pub intervention_type: String,
+    pub outcome: Option<String>,
+    pub note: String,
+    /// Source keys of the signals this intervention responded to.
+    pub signal_keys: Vec<String>,
+    pub closed_at: Option<DateTime<Utc>>,
+}
+
+#[ComplexObject]
+impl InterventionNode {
+    /// Whether the final outcome is still to be recorded.
+    async fn open(&self) -> bool {
+        self.closed_at.is_none()
+    }
+}
+
+impl From<Intervention> for InterventionNode {
+    fn from(intervention: Intervention) -> Self {
+        Self {
+            id: intervention.id,
+            scholar_email: intervention.scholar_email,
+            occurred_on: intervention.occurred_on,
+            intervention_type: intervention.intervention_type,
+            outcome: intervention.outcome,
+            note: intervention.note,
+            signal_keys: intervention.signal_keys,
+            closed_at: intervention.closed_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use sqlx::PgPool;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn exposes_nested_scholar_signals_and_interventions() {
+        let schema = schema(AppState {
+            // Never connected: introspection doesn't touch the database.
+            pool: PgPool::connect_lazy("postgres://nobody@127.0.0.1:1/none").unwrap(),
+            org_id: db::DEFAULT_ORG_ID,
+            scoring: Arc::default(),
+            limits: Default::default(),
+            token: None,
+            api_keys: false,
+            events: tokio::sync::broadcast::channel(1).0,
+            api: Arc::default(),
+            report: Arc::default(),
+            calendar: Arc::default(),
+            playbooks: Arc::new([]),
+            rate_limiter: Arc::default(),
+            cache_ttl: std::time::Duration::ZERO,
+        });
+        let response = schema
+            .execute(
+                r#"{
+                    scholar: __type(name: "Scholar") { fields { name } }
+                    signal: __type(name: "Signal") { fields { name } }
+                }"#,
+            )
+            .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let data = response.data.into_json().unwrap();
+        let fields = |kind: &str| -> Vec<String> {
+            data[kind]["fields"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|field| field["name"].as_str().unwrap().to_string())
+                .collect()
+        };
+        assert!(fields("scholar").contains(&"signals".to_string()));
+        assert!(fields("scholar").contains(&"interventions".to_string()));
+        assert!(fields("signal").contains(&"interventions".to_string()));
+
+        let too_deep = schema
+            .execute("{ __schema { types { fields { type { ofType { ofType { ofType { ofType { name } } } } } } } } }")
+            .await;
+        assert!(too_deep.errors[0].message.contains("nested too deep"));
+    }
+}
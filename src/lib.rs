@@ -0,0 +1,66 @@
+#[cfg(feature = "postgres")]
+pub mod airtable;
+pub mod analytics;
+#[cfg(feature = "server")]
+pub mod apikeys;
+#[cfg(feature = "postgres")]
+pub mod audit;
+#[cfg(feature = "bigquery")]
+pub mod bigquery;
+pub mod compare;
+pub mod config;
+#[cfg(feature = "consume")]
+pub mod consume;
+pub mod dedupe;
+#[cfg(feature = "postgres")]
+pub mod doctor;
+pub mod evaluate;
+#[cfg(feature = "server")]
+pub mod events;
+pub mod forecast;
+#[cfg(feature = "google")]
+pub mod google;
+#[cfg(feature = "graphql")]
+pub mod graphql;
+pub mod logging;
+pub mod movers;
+#[cfg(feature = "postgres")]
+pub mod notify;
+pub mod onboard;
+pub mod outcomes;
+#[cfg(feature = "privacy-export")]
+pub mod privacy;
+#[cfg(feature = "postgres")]
+pub mod profile;
+#[cfg(feature = "postgres")]
+pub mod promote;
+#[cfg(feature = "postgres")]
+pub mod retention;
+pub mod rules;
+#[cfg(feature = "postgres")]
+pub mod salesforce;
+pub mod schedule;
+pub mod scoreboard;
+#[cfg(feature = "self-report")]
+pub mod selfreport;
+#[cfg(feature = "postgres")]
+pub mod selftest;
+#[cfg(feature = "server")]
+pub mod server;
+#[cfg(feature = "sheets")]
+pub mod sheets;
+#[cfg(feature = "postgres")]
+pub mod subject;
+#[cfg(feature = "otel")]
+pub mod telemetry;
+pub mod validate;
+#[cfg(feature = "postgres")]
+pub mod watch;
+pub mod whatif;
+
+pub use groupscholar_early_warning_core::{
+    calendar, clusters, enrich, export, health, ics, import, models, raters, recommendations,
+    report, risk, survival, synthetic, trends,
+};
+#[cfg(feature = "postgres")]
+pub use groupscholar_early_warning_core::{db, notes};
@@ -0,0 +1,253 @@
+use std::fmt;
+use std::io::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde_json::{Map, Value};
+use tracing::field::{Field, Visit};
+use tracing::level_filters::LevelFilter;
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Level, Metadata, Subscriber};
+
+/// How log lines are written to stderr.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum LogFormat {
+    /// `2026-03-02T07:00:00.000Z  INFO import finished rows=120 write_ms=85`
+    Text,
+    /// One JSON object per line, for log collectors
+    Json,
+}
+
+/// Crates logged at the level asked for; others, such as the database driver, only log warnings
+/// and errors.
+pub(crate) const OWN_TARGETS: [&str; 2] = [
+    "groupscholar_cohort_early_warning",
+    "groupscholar_early_warning_core",
+];
+
+/// The level `--quiet` and `--verbose` ask for: warnings by default, errors only when quiet, and
+/// info, debug, then trace for each `-v`.
+pub fn level(quiet: bool, verbose: u8) -> Level {
+    match (quiet, verbose) {
+        (true, _) => Level::ERROR,
+        (false, 0) => Level::WARN,
+        (false, 1) => Level::INFO,
+        (false, 2) => Level::DEBUG,
+        _ => Level::TRACE,
+    }
+}
+
+/// A tracing subscriber writing each event at `level` or below to stderr as one line. Spans are
+/// not tracked; stage timings are logged as fields of the event that ends the stage. With the
+/// `otel` feature it is also a layer, logging alongside [`crate::telemetry`]'s span export.
+pub struct Logger {
+    level: Level,
+    format: LogFormat,
+    next_span: AtomicU64,
+}
+
+impl Logger {
+    pub fn new(level: Level, format: LogFormat) -> Self {
+        Self {
+            level,
+            format,
+            next_span: AtomicU64::new(1),
+        }
+    }
+
+    /// Makes this the logger for the rest of the process. A second call leaves the first logger
+    /// in place.
+    pub fn install(self) {
+        let _ = tracing::subscriber::set_global_default(self);
+    }
+}
+
+impl Logger {
+    fn wants(&self, metadata: &Metadata<'_>) -> bool {
+        let own = OWN_TARGETS
+            .iter()
+            .any(|target| metadata.target().starts_with(target));
+        let max = if own {
+            self.level
+        } else {
+            self.level.min(Level::WARN)
+        };
+        *metadata.level() <= max
+    }
+
+    fn write(&self, event: &Event<'_>) {
+        let mut fields = Fields::default();
+        event.record(&mut fields);
+        let metadata = event.metadata();
+        let line = format_line(
+            self.format,
+            Utc::now(),
+            *metadata.level(),
+            metadata.target(),
+            &fields,
+        );
+        let _ = writeln!(std::io::stderr().lock(), "{line}");
+    }
+}
+
+impl Subscriber for Logger {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        self.wants(metadata)
+    }
+
+    fn max_level_hint(&self) -> Option<LevelFilter> {
+        Some(LevelFilter::from_level(self.level))
+    }
+
+    fn new_span(&self, _: &Attributes<'_>) -> Id {
+        Id::from_u64(self.next_span.fetch_add(1, Ordering::Relaxed))
+    }
+
+    fn record(&self, _: &Id, _: &Record<'_>) {}
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        self.write(event);
+    }
+
+    fn enter(&self, _: &Id) {}
+
+    fn exit(&self, _: &Id) {}
+}
+
+/// Filters in `on_event` rather than `enabled`, which would also hide spans from the other layers.
+#[cfg(feature = "otel")]
+impl<S: Subscriber> tracing_subscriber::Layer<S> for Logger {
+    fn on_event(&self, event: &Event<'_>, _: tracing_subscriber::layer::Context<'_, S>) {
+        if self.wants(event.metadata()) {
+            self.write(event);
+        }
+    }
+}
+
+/// An event's message and its other fields, numbers and booleans kept as JSON values.
+#[derive(Debug, Default)]
+struct Fields {
+    message: String,
+    values: Map<String, Value>,
+}
+
+impl Fields {
+    fn insert(&mut self, field: &Field, value: Value) {
+        match (field.name(), value) {
+            ("message", Value::String(message)) => self.message = message,
+            (name, value) => {
+                self.values.insert(name.to_string(), value);
+            }
+        }
+    }
+}
+
+impl Visit for Fields {
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.insert(field, value.into());
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.insert(field, value.into());
+    }
+
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.insert(field, value.into());
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.insert(field, value.into());
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.insert(field, value.into());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.insert(field, format!("{value:?}").into());
+    }
+}
+
+fn format_line(
+    format: LogFormat,
+    at: DateTime<Utc>,
+    level: Level,
+    target: &str,
+    fields: &Fields,
+) -> String {
+    let timestamp = at.to_rfc3339_opts(SecondsFormat::Millis, true);
+    match format {
+        LogFormat::Text => {
+            let mut line = format!("{timestamp} {level:>5} {}", fields.message);
+            for (name, value) in &fields.values {
+                let value = match value {
+                    Value::String(text)
+                        if !text.is_empty() && !text.contains(char::is_whitespace) =>
+                    {
+                        text.clone()
+                    }
+                    value => value.to_string(),
+                };
+                line.push_str(&format!(" {name}={value}"));
+            }
+            line
+        }
+        LogFormat::Json => {
+            let mut object = Map::new();
+            object.insert("timestamp".into(), timestamp.into());
+            object.insert("level".into(), level.as_str().into());
+            object.insert("target".into(), target.into());
+            object.insert("message".into(), fields.message.clone().into());
+            for (name, value) in &fields.values {
+                object.entry(name.clone()).or_insert_with(|| value.clone());
+            }
+            Value::Object(object).to_string()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_text_and_json_lines() {
+        let mut values = Map::new();
+        values.insert("file".into(), "spring intake.csv".into());
+        values.insert("rows".into(), 120.into());
+        values.insert("write_ms".into(), 85.into());
+        let fields = Fields {
+            message: "import finished".into(),
+            values,
+        };
+        let at = "2026-03-02T07:00:00Z".parse().unwrap();
+
+        assert_eq!(
+            format_line(LogFormat::Text, at, Level::INFO, "cew", &fields),
+            "2026-03-02T07:00:00.000Z  INFO import finished file=\"spring intake.csv\" rows=120 \
+             write_ms=85"
+        );
+        let json: Value = serde_json::from_str(&format_line(
+            LogFormat::Json,
+            at,
+            Level::INFO,
+            "cew",
+            &fields,
+        ))
+        .unwrap();
+        assert_eq!(json["level"], "INFO");
+        assert_eq!(json["message"], "import finished");
+        assert_eq!(json["rows"], 120);
+        assert_eq!(json["timestamp"], "2026-03-02T07:00:00.000Z");
+    }
+
+    #[test]
+    fn verbosity_flags_pick_the_level() {
+        assert_eq!(level(false, 0), Level::WARN);
+        assert_eq!(level(true, 0), Level::ERROR);
+        assert_eq!(level(false, 1), Level::INFO);
+        assert_eq!(level(false, 5), Level::TRACE);
+    }
+}
@@ -1,32 +1,261 @@
+use std::collections::BTreeMap;
+use std::io::IsTerminal;
 use std::path::PathBuf;
+use std::time::{Duration, Instant};
 
 use anyhow::Context;
-use clap::{ArgGroup, Parser, Subcommand};
-use sqlx::postgres::PgPoolOptions;
-
-mod db;
-mod models;
-mod report;
-mod risk;
+use clap::{ArgGroup, Args, CommandFactory, FromArgMatches, Parser, Subcommand};
+use groupscholar_cohort_early_warning::audit::{self, AuditAction};
+#[cfg(feature = "bigquery")]
+use groupscholar_cohort_early_warning::bigquery;
+use groupscholar_cohort_early_warning::config::{self, Config, RetentionMode, DEFAULT_CONFIG_FILE};
+#[cfg(feature = "consume")]
+use groupscholar_cohort_early_warning::consume;
+use groupscholar_cohort_early_warning::models::{self, NewSignal, OutcomeType, ScholarStatus};
+#[cfg(feature = "privacy-export")]
+use groupscholar_cohort_early_warning::privacy;
+#[cfg(feature = "self-report")]
+use groupscholar_cohort_early_warning::selfreport;
+#[cfg(feature = "otel")]
+use groupscholar_cohort_early_warning::telemetry;
+use groupscholar_cohort_early_warning::{
+    airtable, analytics, clusters, compare, db, dedupe, doctor, enrich, evaluate, export, forecast,
+    health, ics, import, logging, movers, notes, notify, onboard, outcomes, profile, promote,
+    raters, report, retention, risk, rules, scoreboard, selftest, subject, survival, synthetic,
+    trends, validate, watch, whatif,
+};
+#[cfg(feature = "server")]
+use groupscholar_cohort_early_warning::{apikeys, events, server};
+#[cfg(feature = "sheets")]
+use groupscholar_cohort_early_warning::{google, sheets};
+use sqlx::postgres::PgSslMode;
+use tracing::Instrument;
+use uuid::Uuid;
 
 #[derive(Parser)]
 #[command(name = "cohort-early-warning")]
 #[command(about = "Cohort early warning signal tracker for Group Scholar", long_about = None)]
 struct Cli {
+    /// Organization whose data the command reads and writes
+    #[arg(long, global = true, env = "CEW_ORG", default_value = "default")]
+    org: String,
+    /// TOML config file; defaults to ./cew.toml, ./cohort-early-warning.toml, or
+    /// cohort-early-warning.toml in the XDG config directory, whichever exists first
+    #[arg(long, global = true, env = "CEW_CONFIG")]
+    config: Option<PathBuf>,
+    /// Postgres URL to connect to; defaults to the DATABASE_URL environment variable
+    #[arg(long, global = true, conflicts_with = "profile")]
+    database_url: Option<String>,
+    /// Connect to the database of this [environments] entry in the config instead of DATABASE_URL
+    #[arg(long, global = true)]
+    profile: Option<String>,
+    #[command(flatten)]
+    pool: PoolArgs,
+    #[command(flatten)]
+    log: LogArgs,
     #[command(subcommand)]
     command: Commands,
 }
 
+#[derive(Args)]
+struct LogArgs {
+    /// Log more on stderr: -v adds stage timings, -vv each import chunk, -vvv everything
+    #[arg(short, long, global = true, action = clap::ArgAction::Count)]
+    verbose: u8,
+    /// Log only errors on stderr
+    #[arg(short, long, global = true, conflicts_with = "verbose")]
+    quiet: bool,
+    /// Format of log lines on stderr
+    #[arg(
+        long,
+        global = true,
+        value_enum,
+        env = "CEW_LOG_FORMAT",
+        default_value_t = logging::LogFormat::Text
+    )]
+    log_format: logging::LogFormat,
+    /// Export spans for database queries, scoring, reports, and API requests to this OTLP/HTTP
+    /// collector, e.g. http://localhost:4318
+    #[cfg(feature = "otel")]
+    #[arg(long, global = true, env = "OTEL_EXPORTER_OTLP_ENDPOINT")]
+    otlp_endpoint: Option<String>,
+}
+
+impl Cli {
+    /// The URL from `--database-url`, else from the `--profile` environment's variable, else from
+    /// DATABASE_URL. `None` when none of them is given.
+    fn database_url(&self, config: &Config) -> anyhow::Result<Option<String>> {
+        if let Some(url) = &self.database_url {
+            return Ok(Some(url.clone()));
+        }
+        if let Some(profile) = &self.profile {
+            return config.database_url(profile).map(Some);
+        }
+        Ok(std::env::var("DATABASE_URL").ok())
+    }
+}
+
+#[derive(Args)]
+struct PoolArgs {
+    /// Maximum number of pooled Postgres connections; defaults to [database] max_connections
+    #[arg(long, global = true, env = "CEW_DB_MAX_CONNECTIONS")]
+    db_max_connections: Option<u32>,
+    /// Seconds to wait for a free pooled connection; defaults to [database] acquire_timeout_secs
+    #[arg(long, global = true, env = "CEW_DB_ACQUIRE_TIMEOUT")]
+    db_acquire_timeout: Option<u64>,
+    /// Abort any single statement running longer than this many seconds
+    #[arg(long, global = true, env = "CEW_DB_STATEMENT_TIMEOUT")]
+    db_statement_timeout: Option<u64>,
+    /// TLS mode: disable, allow, prefer, require, verify-ca, or verify-full
+    #[arg(long, global = true, env = "CEW_DB_SSL_MODE")]
+    db_ssl_mode: Option<PgSslMode>,
+    /// CA certificate used to verify the server in verify-ca/verify-full modes
+    #[arg(long, global = true, env = "CEW_DB_SSL_ROOT_CERT")]
+    db_ssl_root_cert: Option<PathBuf>,
+    /// Times an import chunk is retried, with exponential backoff, after a dropped connection or
+    /// other transient database error; defaults to [database] retries
+    #[arg(long, global = true, env = "CEW_DB_RETRIES")]
+    db_retries: Option<u32>,
+}
+
+impl PoolArgs {
+    /// The pool settings from these flags, falling back to the config's `[database]` table for
+    /// any left out.
+    fn settings(&self, database: &config::DatabaseConfig) -> anyhow::Result<db::PoolSettings> {
+        let ssl_mode = match (self.db_ssl_mode, &database.ssl_mode) {
+            (Some(mode), _) => Some(mode),
+            (None, Some(mode)) => Some(
+                mode.parse()
+                    .with_context(|| format!("invalid [database] ssl_mode {mode:?}"))?,
+            ),
+            (None, None) => None,
+        };
+        Ok(db::PoolSettings {
+            max_connections: self.db_max_connections.unwrap_or(database.max_connections),
+            acquire_timeout: Duration::from_secs(
+                self.db_acquire_timeout
+                    .unwrap_or(database.acquire_timeout_secs),
+            ),
+            statement_timeout: self
+                .db_statement_timeout
+                .or(database.statement_timeout_secs)
+                .map(Duration::from_secs),
+            ssl_mode,
+            ssl_root_cert: self
+                .db_ssl_root_cert
+                .clone()
+                .or_else(|| database.ssl_root_cert.clone()),
+        })
+    }
+
+    fn retry(&self, database: &config::DatabaseConfig) -> db::RetryPolicy {
+        db::RetryPolicy {
+            attempts: self.db_retries.unwrap_or(database.retries),
+            ..db::RetryPolicy::default()
+        }
+    }
+}
+
 #[derive(Subcommand)]
 enum Commands {
     /// Create or upgrade the database schema
     InitDb,
+    /// Check the database connection, schema, and clock, with fixes for anything wrong
+    Doctor {
+        /// Also plan the scoring and report queries and warn about full scans of large tables
+        #[arg(long)]
+        explain: bool,
+    },
+    /// Write a starter config file or print the configuration in effect
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+    /// Inspect, apply, or roll back schema migrations
+    Migrate {
+        #[command(subcommand)]
+        action: MigrateAction,
+    },
     /// Load realistic seed data
-    Seed,
+    Seed {
+        /// Generate this many synthetic scholars with signal histories instead of the three
+        /// sample rows
+        #[arg(long)]
+        scholars: Option<usize>,
+        /// Weeks of history to generate, ending today
+        #[arg(long, default_value_t = 26, requires = "scholars")]
+        weeks: u32,
+        /// Random seed; the same seed, scale, and day produce the same data
+        #[arg(long, default_value_t = 42, requires = "scholars")]
+        seed: u64,
+    },
     /// Import signals from a CSV file
+    #[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
     Import {
+        #[command(subcommand)]
+        action: Option<ImportAction>,
+        /// CSV file, or a directory whose .csv files are each imported as their own batch
+        #[arg(long, required = true)]
+        csv: Option<PathBuf>,
+        /// Files from a --csv directory imported at once, capped at --db-max-connections; defaults
+        /// to [import] jobs
+        #[arg(long, value_parser = clap::value_parser!(u16).range(1..))]
+        jobs: Option<u16>,
+        /// Reject files larger than this many megabytes; defaults to [import] max_file_mb
+        #[arg(long)]
+        max_file_mb: Option<u64>,
+        /// Reject files with more data rows than this; defaults to [import] max_rows
+        #[arg(long)]
+        max_rows: Option<usize>,
+        /// Maximum note length in characters; defaults to [import] max_note_length
+        #[arg(long)]
+        max_note_length: Option<usize>,
+        /// How to handle notes longer than --max-note-length; defaults to [import] note_policy
+        #[arg(long, value_enum)]
+        note_policy: Option<import::NotePolicy>,
+        /// Sentences kept by the summarize note policy; defaults to [import] summary_sentences
+        #[arg(long)]
+        summary_sentences: Option<usize>,
+        /// Store the full text of shortened notes in the overflow table
+        #[arg(long)]
+        keep_note_overflow: bool,
+        /// Number of rows parsed and written per transaction; defaults to [import] chunk_size
+        #[arg(long)]
+        chunk_size: Option<usize>,
+        /// Tag added to every imported signal (repeatable)
+        #[arg(long = "tag")]
+        tags: Vec<String>,
+        /// Write a JSON summary of the import (counts, date range, warnings) to this path
+        #[arg(long)]
+        summary_out: Option<PathBuf>,
+        /// Read the file with a partner column mapping from the config's [sources] table
+        #[arg(long)]
+        source: Option<String>,
+        /// If the database stays unreachable after retrying, save the unwritten rows here instead
+        /// of failing; `import resume` writes them later
+        #[arg(long)]
+        spool_dir: Option<PathBuf>,
+        /// Import each file in one transaction, so any error or out-of-range row leaves none of
+        /// it behind
+        #[arg(long, conflicts_with = "spool_dir")]
+        atomic: bool,
+        /// Import rows dated outside the [dates] window from the config instead of leaving them out
+        #[arg(long)]
+        allow_any_date: bool,
+        /// What to do with rows whose source key is already stored; defaults to [import]
+        /// on_conflict
+        #[arg(long, value_enum)]
+        on_conflict: Option<import::OnConflict>,
+        /// Register signal types missing from `signal-types` instead of leaving their rows out
         #[arg(long)]
-        csv: PathBuf,
+        register_types: bool,
+        /// Fail on an empty file or any row that would be left out for its severity or date
+        #[arg(long, conflicts_with = "lenient")]
+        strict: bool,
+        /// Trim whitespace, clamp severities into range, and read dates such as 2026/02/03,
+        /// 02/03/2026 (month first), 03.02.2026, or 3 Feb 2026
+        #[arg(long)]
+        lenient: bool,
     },
     /// Score risk across scholars
     #[command(group(
@@ -35,14 +264,97 @@ enum Commands {
             .multiple(false)
     ))]
     Score {
+        /// Cohorts to score; repeat the flag or pass a comma-separated list
+        #[arg(long, value_delimiter = ',')]
+        cohort: Vec<String>,
+        /// Scholar emails to score; repeat the flag or pass a comma-separated list
+        #[arg(long, value_delimiter = ',')]
+        email: Vec<String>,
+        /// Only scholars assigned to the advisor with this email
         #[arg(long)]
-        cohort: Option<String>,
+        advisor: Option<String>,
+        /// Days of signals scored; defaults to [defaults] since_days
         #[arg(long)]
-        email: Option<String>,
-        #[arg(long, default_value_t = 30)]
-        since_days: i64,
+        since_days: Option<i64>,
+        /// First day of a fixed date range to score instead of --since-days, e.g. 2026-10-01
+        #[arg(long, requires = "to", conflicts_with = "since_days")]
+        from: Option<chrono::NaiveDate>,
+        /// Last day of the --from range, inclusive; scores are computed as of this day, so the
+        /// range scores the same whenever it is rerun
+        #[arg(long, requires = "from")]
+        to: Option<chrono::NaiveDate>,
         #[arg(long, default_value_t = 10)]
         limit: usize,
+        /// Print the table without tier colors; also off when NO_COLOR is set or output is not
+        /// a terminal
+        #[arg(long)]
+        no_color: bool,
+        /// Order scholars are listed in, before --limit is applied
+        #[arg(long, value_enum, default_value_t, conflicts_with_all = ["dry_run", "rank_in_db"])]
+        sort: risk::ScoreSort,
+        /// List the lowest first, or names A to Z; the default for --sort name
+        #[arg(long, conflicts_with_all = ["desc", "rank_in_db"])]
+        asc: bool,
+        /// List the highest first, or names Z to A; the default for every other --sort
+        #[arg(long)]
+        desc: bool,
+        /// Include withdrawn, graduated, and on-leave scholars
+        #[arg(long)]
+        include_inactive: bool,
+        /// Only count signals carrying one of these tags (repeatable)
+        #[arg(long = "tag")]
+        tags: Vec<String>,
+        /// Only count signals of this type (repeatable)
+        #[arg(long = "type")]
+        signal_types: Vec<String>,
+        /// Leave out signals of this type (repeatable)
+        #[arg(long = "exclude-type")]
+        exclude_types: Vec<String>,
+        /// Only count signals at least this severe
+        #[arg(long, allow_negative_numbers = true)]
+        min_severity: Option<i32>,
+        /// Only count signals whose notes `enrich` tagged with this category (repeatable)
+        #[arg(long = "note-category")]
+        note_categories: Vec<String>,
+        /// Only count signals whose note sentiment, from -1 to 1, is at or below this
+        #[arg(long, allow_negative_numbers = true)]
+        max_sentiment: Option<f64>,
+        /// Only list scholars scoring at least this much
+        #[arg(long, conflicts_with = "dry_run")]
+        min_score: Option<f64>,
+        /// Exit non-zero when any scholar scores above this, or with --fail-if-count, when more
+        /// than that many do
+        #[arg(long, conflicts_with = "dry_run")]
+        fail_if_above: Option<f64>,
+        /// Exit non-zero when more than this many scholars are scored (above --fail-if-above, if
+        /// set)
+        #[arg(long, conflicts_with_all = ["dry_run", "rank_in_db"])]
+        fail_if_count: Option<usize>,
+        /// Signals fetched per database round trip
+        #[arg(long, default_value_t = db::DEFAULT_PAGE_SIZE)]
+        page_size: i64,
+        /// Score under --config and print how the ranking differs from the active config
+        #[arg(long)]
+        dry_run: bool,
+        /// Active config to compare against; defaults to the file found as for --config
+        #[arg(long, requires = "dry_run")]
+        baseline: Option<PathBuf>,
+        /// Also show each scholar's 7-, 30-, and 90-day scores, to tell a recent flare-up from
+        /// long-running disengagement
+        #[arg(long, conflicts_with = "dry_run")]
+        windows: bool,
+        /// Sum weighted severities in the database instead of streaming every signal, for
+        /// org-wide scoring over very large signal tables
+        #[arg(long, conflicts_with = "dry_run")]
+        aggregate_in_db: bool,
+        /// Score every cohort in one pass and list the top --limit scholars of each
+        #[arg(long, conflicts_with_all = ["cohort", "email", "dry_run"])]
+        all_cohorts: bool,
+        /// Rank scholars in the database and return only the top --limit by score, for quick checks
+        /// over very large signal tables; other orders need every scholar, so --sort and --asc
+        /// refuse it
+        #[arg(long, conflicts_with_all = ["dry_run", "aggregate_in_db", "all_cohorts"])]
+        rank_in_db: bool,
     },
     /// Generate a markdown report
     #[command(group(
@@ -51,105 +363,5077 @@ enum Commands {
             .multiple(false)
     ))]
     Report {
+        /// Cohorts to report on; repeat the flag or pass a comma-separated list
+        #[arg(long, value_delimiter = ',')]
+        cohort: Vec<String>,
+        /// Scholar emails to report on; repeat the flag or pass a comma-separated list
+        #[arg(long, value_delimiter = ',')]
+        email: Vec<String>,
+        /// Only scholars assigned to the advisor with this email
+        #[arg(long)]
+        advisor: Option<String>,
+        /// Days of signals scored; defaults to [defaults] since_days
+        #[arg(long)]
+        since_days: Option<i64>,
+        /// First day of a fixed date range to score instead of --since-days, e.g. 2026-10-01
+        #[arg(long, requires = "to", conflicts_with = "since_days")]
+        from: Option<chrono::NaiveDate>,
+        /// Last day of the --from range, inclusive; scores are computed as of this day, so the
+        /// range scores the same whenever it is rerun
+        #[arg(long, requires = "from")]
+        to: Option<chrono::NaiveDate>,
+        #[arg(long, default_value = "report.md")]
+        out: PathBuf,
+        /// Replace breakdown cells describing fewer scholars than this with "<N"
+        #[arg(long)]
+        min_cell_size: Option<usize>,
+        /// Include withdrawn, graduated, and on-leave scholars
+        #[arg(long)]
+        include_inactive: bool,
+        /// Only count signals carrying one of these tags (repeatable)
+        #[arg(long = "tag")]
+        tags: Vec<String>,
+        /// Only count signals of this type (repeatable)
+        #[arg(long = "type")]
+        signal_types: Vec<String>,
+        /// Leave out signals of this type (repeatable)
+        #[arg(long = "exclude-type")]
+        exclude_types: Vec<String>,
+        /// Only count signals at least this severe
+        #[arg(long, allow_negative_numbers = true)]
+        min_severity: Option<i32>,
+        /// Only count signals whose notes `enrich` tagged with this category (repeatable)
+        #[arg(long = "note-category")]
+        note_categories: Vec<String>,
+        /// Only count signals whose note sentiment, from -1 to 1, is at or below this
+        #[arg(long, allow_negative_numbers = true)]
+        max_sentiment: Option<f64>,
+        /// Only list scholars scoring at least this much
+        #[arg(long)]
+        min_score: Option<f64>,
+        /// Signals fetched per database round trip
+        #[arg(long, default_value_t = db::DEFAULT_PAGE_SIZE)]
+        page_size: i64,
+        /// Also read signals moved out by `archive`
         #[arg(long)]
+        include_archived: bool,
+        /// Read weekly trends from the cache rebuilt by `refresh-trends`
+        #[arg(long)]
+        cached_trends: bool,
+        /// Flag trend weeks rising more than this percentage over the prior week; 0 turns flagging
+        /// off. Defaults to [report] trend_alert_pct in the config
+        #[arg(long)]
+        trend_alert_pct: Option<f64>,
+        /// Add a section on signal types the same scholars logged within DAYS of each other, or
+        /// anywhere in the window when DAYS is omitted
+        #[arg(long, value_name = "DAYS", num_args = 0..=1)]
+        co_occurrence: Option<Option<i64>>,
+        /// Add a section grouping scholars into at most this many signal profiles
+        #[arg(long, value_name = "K", value_parser = clap::value_parser!(u8).range(1..=20))]
+        clusters: Option<u8>,
+        /// Add a section charting the stored weekly cohort health index over the past year
+        #[arg(long)]
+        health: bool,
+        /// Add a section on how long scholars stayed after DATE, by the risk tier they held then;
+        /// DATE defaults to a year ago
+        #[arg(long, value_name = "DATE", num_args = 0..=1)]
+        survival: Option<Option<chrono::NaiveDate>>,
+        /// Write one report per cohort into --out-dir from a single fetch, instead of --out
+        #[arg(long, conflicts_with_all = ["cohort", "email", "cached_trends"])]
+        all_cohorts: bool,
+        /// Directory --all-cohorts writes its <cohort>.md reports to
+        #[arg(long, default_value = "reports", requires = "all_cohorts")]
+        out_dir: PathBuf,
+        /// Replace scholar names and emails with stable pseudonyms and leave out notes, evidence
+        /// links, and intervention outcomes, for sharing outside the program
+        #[arg(long)]
+        redact: bool,
+        /// Write the scores, signal mix, and weekly trends to this Google Sheet, one tab per
+        /// cohort, instead of --out
+        #[cfg(feature = "sheets")]
+        #[arg(long, value_name = "SPREADSHEET_ID", conflicts_with_all = ["all_cohorts", "cached_trends"])]
+        publish_sheet: Option<String>,
+    },
+    /// Show weekly signal trends or export a multi-cohort overlay
+    Trends {
+        #[arg(long, conflicts_with = "overlay")]
         cohort: Option<String>,
+        /// Comma-separated cohorts to export as one overlay series (e.g. 2025,2026)
+        #[arg(long, value_delimiter = ',')]
+        overlay: Vec<String>,
+        /// Number overlay weeks from each cohort's start date instead of the calendar
+        #[arg(long, requires = "overlay")]
+        align_to_cohort_start: bool,
+        /// Defaults to 30 days, or the full history for overlays
         #[arg(long)]
-        email: Option<String>,
-        #[arg(long, default_value_t = 30)]
+        since_days: Option<i64>,
+        /// Where the overlay CSV is written
+        #[arg(long, default_value = "trends-overlay.csv")]
+        out: PathBuf,
+        /// Include withdrawn, graduated, and on-leave scholars
+        #[arg(long)]
+        include_inactive: bool,
+        /// Also read signals moved out by `archive`
+        #[arg(long)]
+        include_archived: bool,
+        /// Read weekly trends from the cache rebuilt by `refresh-trends`
+        #[arg(long, conflicts_with = "overlay")]
+        cached_trends: bool,
+    },
+    /// Compare severity grading across staff who enter signals by hand
+    Raters {
+        #[arg(long)]
+        cohort: Option<String>,
+        #[arg(long, default_value_t = 180)]
         since_days: i64,
-        #[arg(long, default_value = "report.md")]
+        /// Raters need at least this many signals of a type before they can be flagged
+        #[arg(long, default_value_t = 5)]
+        min_signals: usize,
+        /// Flag raters this many standard errors away from their peers
+        #[arg(long, default_value_t = 2.0)]
+        threshold: f64,
+        /// Only print flagged raters
+        #[arg(long)]
+        flagged_only: bool,
+    },
+    /// Find signals whose notes mention a topic
+    Search {
+        /// Search terms; quote phrases ("financial aid"), `or` for either, `-word` to exclude
+        query: String,
+        #[arg(long)]
+        cohort: Option<String>,
+        #[arg(long, default_value_t = 365)]
+        since_days: i64,
+        #[arg(long, default_value_t = 50)]
+        limit: i64,
+        /// Include withdrawn, graduated, and on-leave scholars
+        #[arg(long)]
+        include_inactive: bool,
+        /// Search archived signals too
+        #[arg(long)]
+        include_archived: bool,
+    },
+    /// Show a scholar's score over the last scoring runs saved by `watch`
+    History {
+        #[arg(long)]
+        email: String,
+        /// How many of the most recent runs to show
+        #[arg(long, default_value_t = 10)]
+        runs: i64,
+    },
+    /// List the largest score increases and decreases between two saved scoring runs
+    Movers {
+        /// Compare the latest run with the last one at least this many days older, instead of the
+        /// run right before it
+        #[arg(long)]
+        since_days: Option<i64>,
+        /// How many scholars to list in each direction
+        #[arg(long, default_value_t = 10)]
+        limit: usize,
+    },
+    /// Compare cohorts side by side: signal mix, average score, and high-risk counts
+    Compare {
+        /// Comma-separated cohorts; deltas are against the first (e.g. 2025,2026)
+        #[arg(long, value_delimiter = ',', num_args = 1.., required = true)]
+        cohorts: Vec<String>,
+        /// Days of signals scored; defaults to [defaults] since_days
+        #[arg(long)]
+        since_days: Option<i64>,
+    },
+    /// Project weekly signal volume and average severity per cohort from recent weeks
+    Forecast {
+        /// Comma-separated cohorts; defaults to every cohort with active scholars
+        #[arg(long, value_delimiter = ',')]
+        cohorts: Vec<String>,
+        /// Weeks to project, starting with the current one
+        #[arg(long, default_value_t = 4, value_parser = clap::value_parser!(u8).range(1..=12))]
+        weeks: u8,
+        /// Complete weeks of history to fit
+        #[arg(long, default_value_t = 12, value_parser = clap::value_parser!(u8).range(2..=104))]
+        history_weeks: u8,
+        #[arg(long, value_enum, default_value_t = forecast::Method::Holt)]
+        method: forecast::Method,
+        /// Weeks averaged by --method moving-average
+        #[arg(long, default_value_t = 4, value_parser = clap::value_parser!(u8).range(1..))]
+        window: u8,
+        /// Print the forecasts as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Measure how well the scoring config predicted recorded withdrawals
+    Evaluate {
+        #[arg(long)]
+        cohort: Option<String>,
+        /// Weeks before each withdrawal or graduation to replay scoring over, one check a week
+        #[arg(long, default_value_t = 26, value_parser = clap::value_parser!(u8).range(1..=104))]
+        horizon_weeks: u8,
+        /// Days of signals scored; defaults to [defaults] since_days
+        #[arg(long)]
+        since_days: Option<i64>,
+        /// A scholar counts as flagged once scored at this tier or above
+        #[arg(long, value_enum, default_value_t = risk::Tier::High)]
+        at_risk_tier: risk::Tier,
+    },
+    /// Aggregates across cohorts for directors
+    Analytics {
+        #[command(subcommand)]
+        action: AnalyticsAction,
+    },
+    /// Dump signals, the roster, or score snapshots for analysis outside the database
+    Export {
+        #[arg(long)]
+        cohort: Option<String>,
+        /// Days of signals, of scoring runs for snapshots, or scored for ics reminders
+        #[arg(long, default_value_t = 90)]
+        since_days: i64,
+        /// csv, jsonl, and parquet write --dataset; ics writes follow-up reminders instead
+        #[arg(long, value_enum, default_value_t = export::ExportFormat::Csv)]
+        format: export::ExportFormat,
+        #[arg(long)]
+        out: PathBuf,
+        /// Include withdrawn, graduated, and on-leave scholars
+        #[arg(long)]
+        include_inactive: bool,
+        /// Export archived signals too
+        #[arg(long)]
+        include_archived: bool,
+        /// Only export signals at least this severe
+        #[arg(long, allow_negative_numbers = true)]
+        min_severity: Option<i32>,
+        /// Signals fetched per database round trip
+        #[arg(long, default_value_t = db::DEFAULT_PAGE_SIZE)]
+        page_size: i64,
+        #[arg(long, value_enum, default_value_t = export::ExportDataset::Signals)]
+        dataset: export::ExportDataset,
+        /// ics: remind about scholars at or above this tier
+        #[arg(long, value_enum, default_value_t = risk::Tier::High)]
+        follow_up_tier: risk::Tier,
+        /// ics: days after the last intervention or check-in that a follow-up comes due
+        #[arg(long, default_value_t = 14)]
+        contact_days: i64,
+        /// ics: only scholars assigned to the advisor with this email
+        #[arg(long)]
+        advisor: Option<String>,
+        /// Replace names, emails, and staff with salted hashes, bucket dates to weeks, and drop
+        /// notes, source keys, and attachments, for research partners
+        #[cfg(feature = "privacy-export")]
+        #[arg(long, requires = "salt")]
+        research: bool,
+        /// Secret salt for --research hashes; reuse it for exports that must join up
+        #[cfg(feature = "privacy-export")]
+        #[arg(long, env = "CEW_RESEARCH_SALT", hide_env_values = true)]
+        salt: Option<String>,
+    },
+    /// Push data to warehouses and trackers, and pull follow-ups back from trackers
+    Sync {
+        #[command(subcommand)]
+        action: SyncAction,
+    },
+    /// Record signal events from the config's [consume] NATS JetStream stream as they arrive
+    #[cfg(feature = "consume")]
+    Consume {
+        /// Exit once the stream has no more events waiting instead of following it
+        #[arg(long)]
+        once: bool,
+    },
+    /// Score on the config's [watch] schedule, saving snapshots and sending alerts
+    Watch {
+        /// Run one scoring cycle now and exit instead of following the schedule
+        #[arg(long)]
+        once: bool,
+        /// Also serve /healthz and /readyz on this address, e.g. 0.0.0.0:8081
+        #[cfg(feature = "server")]
+        #[arg(long)]
+        health_bind: Option<std::net::SocketAddr>,
+    },
+    /// Score now and send scholars above a threshold to alert channels
+    Notify {
+        /// Channel from [channels] in the config; repeatable. Defaults to [watch] channels
+        #[arg(long = "channel")]
+        channels: Vec<String>,
+        /// Minimum score to alert on; defaults to the [watch] alert tier
+        #[arg(long)]
+        threshold: Option<f64>,
+        /// Defaults to [watch] since_days
+        #[arg(long)]
+        since_days: Option<i64>,
+        /// Print the alert instead of sending it
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Serve scholars, signals, scores, and reports as a JSON/HTTP API
+    #[cfg(feature = "server")]
+    Serve {
+        /// Address to listen on
+        #[arg(long, default_value = "127.0.0.1:8080")]
+        bind: std::net::SocketAddr,
+    },
+    /// Manage keys for the HTTP API
+    #[cfg(feature = "server")]
+    ApiKey {
+        #[command(subcommand)]
+        action: ApiKeyAction,
+    },
+    /// Summarize roster and signal counts, e.g. as a sanity check after an import
+    Stats,
+    /// Check data integrity: orphans, severities, future dates, duplicate scholars, cohort typos
+    Validate,
+    /// Move aged signals into the archive table
+    Archive {
+        /// Age threshold such as 365d or 52w
+        #[arg(long, value_parser = config::parse_age_days)]
+        older_than: i64,
+        /// Report how many signals would move without changing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Delete or anonymize data past the retention periods in [retention]
+    Purge {
+        /// Signal age threshold such as 3y or 730d; defaults to [retention] max_age in the config
+        #[arg(long, value_parser = config::parse_age_days)]
+        older_than: Option<i64>,
+        /// Defaults to [retention] mode in the config
+        #[arg(long, value_enum)]
+        mode: Option<RetentionMode>,
+        /// Report what would be purged without changing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Manage encryption of signal notes
+    Notes {
+        #[command(subcommand)]
+        action: NotesAction,
+    },
+    /// Tag signal notes with the [enrichment] keyword categories and a sentiment score
+    Enrich {
+        /// Re-enrich every note, e.g. after changing [enrichment]; by default only notes not
+        /// enriched yet
+        #[arg(long)]
+        all: bool,
+        /// Signals read and updated per database round trip
+        #[arg(long, default_value_t = db::DEFAULT_PAGE_SIZE)]
+        page_size: i64,
+    },
+    /// Rebuild the cached weekly trends used by --cached-trends
+    RefreshTrends,
+    /// Recompute and store the weekly cohort health index charted by `report --health`
+    RefreshHealth {
+        #[arg(long)]
+        cohort: Option<String>,
+        /// Complete weeks to recompute, counting back from last week; none before a cohort started
+        #[arg(long, default_value_t = 52, value_parser = clap::value_parser!(u8).range(1..=104))]
+        weeks: u8,
+        /// Days of signals scored; defaults to [defaults] since_days
+        #[arg(long)]
+        since_days: Option<i64>,
+    },
+    /// Manage cohort metadata
+    Cohort {
+        #[command(subcommand)]
+        action: CohortAction,
+    },
+    /// Run a canned end-to-end scenario against the target database
+    #[command(hide = true)]
+    Selftest {
+        /// Leave this run's selftest cohort in place for inspection
+        #[arg(long)]
+        keep_data: bool,
+    },
+    /// Manage scholar records
+    Scholar {
+        #[command(subcommand)]
+        action: ScholarAction,
+    },
+    /// Manage individual signals
+    Signal {
+        #[command(subcommand)]
+        action: SignalAction,
+    },
+    /// Manage the signal types imports and `signal add` accept
+    SignalTypes {
+        #[command(subcommand)]
+        action: SignalTypesAction,
+    },
+    /// Query the audit log of changes made through the CLI
+    Audit {
+        #[command(subcommand)]
+        action: AuditCommand,
+    },
+    /// Record and review interventions taken in response to signals
+    Intervene {
+        #[command(subcommand)]
+        action: InterveneAction,
+    },
+    /// Record withdrawals, graduations, and GPA milestones per scholar
+    Outcome {
+        #[command(subcommand)]
+        action: OutcomeAction,
+    },
+    /// Manage alert rules evaluated against current data
+    Rules {
+        #[command(subcommand)]
+        action: RulesAction,
+    },
+    /// Review the alerts sent to each channel and whether they were delivered
+    Notifications {
+        #[command(subcommand)]
+        action: NotificationsAction,
+    },
+    /// Manage scholar self-report check-in links
+    #[cfg(feature = "self-report")]
+    SelfReport {
+        #[command(subcommand)]
+        action: SelfReportAction,
+    },
+    /// Manage advisors and their caseloads
+    Advisor {
+        #[command(subcommand)]
+        action: AdvisorAction,
+    },
+    /// Manage partner organizations
+    Org {
+        #[command(subcommand)]
+        action: OrgAction,
+    },
+    /// Set up a new cohort interactively: source mapping, scoring, advisors, and a test import
+    Onboard {
+        /// Config file to write; defaults to --config, else ./cew.toml
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+    /// Copy a cohort's roster and signals from one configured database to another
+    Promote {
+        /// Source environment from the config's [environments] table
+        #[arg(long)]
+        from: String,
+        /// Target environment from the config's [environments] table
+        #[arg(long)]
+        to: String,
+        #[arg(long)]
+        cohort: String,
+        /// Show what would be copied and any conflicts without writing
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Export cohort-level aggregates for external sharing
+    #[cfg(feature = "privacy-export")]
+    ExportAggregates {
+        #[arg(long)]
+        cohort: Option<String>,
+        /// Days of signals scored; defaults to [defaults] since_days
+        #[arg(long)]
+        since_days: Option<i64>,
+        #[arg(long, default_value = "aggregates.csv")]
         out: PathBuf,
+        /// Suppress cells describing fewer scholars than this
+        #[arg(long, default_value_t = 5)]
+        min_cell_size: i64,
+        /// Add Laplace noise with this privacy budget per released value
+        #[arg(long)]
+        epsilon: Option<f64>,
+        /// Seed the noise generator for reproducible exports
+        #[arg(long)]
+        seed: Option<u64>,
     },
 }
 
-#[tokio::main]
-async fn main() -> anyhow::Result<()> {
-    let cli = Cli::parse();
-    let database_url = std::env::var("DATABASE_URL")
-        .context("DATABASE_URL must be set to a production Postgres instance")?;
+#[derive(Subcommand)]
+enum MigrateAction {
+    /// List every migration and whether it is applied
+    Status,
+    /// Apply pending migrations
+    Up {
+        /// Stop after this version instead of applying everything
+        #[arg(long)]
+        to: Option<i64>,
+    },
+    /// Run down migrations, newest first
+    Revert {
+        /// Revert every migration newer than this version; defaults to only the latest one
+        #[arg(long)]
+        to: Option<i64>,
+    },
+}
 
-    let pool = PgPoolOptions::new()
-        .max_connections(5)
-        .connect(&database_url)
-        .await
-        .context("failed to connect to Postgres")?;
+#[derive(Subcommand)]
+enum ScholarAction {
+    /// Add a scholar to the roster
+    Add {
+        #[arg(long)]
+        email: String,
+        #[arg(long)]
+        name: String,
+        #[arg(long)]
+        cohort: String,
+    },
+    /// List scholars on the roster
+    List {
+        #[arg(long)]
+        cohort: Option<String>,
+        #[arg(long, value_enum)]
+        status: Option<ScholarStatus>,
+        /// Include soft-deleted scholars
+        #[arg(long)]
+        include_deleted: bool,
+        /// Print a JSON array instead of one line per scholar
+        #[arg(long)]
+        json: bool,
+    },
+    /// Show a scholar's current score and a timeline of their signals, score changes,
+    /// interventions, and alerts
+    Show {
+        #[arg(long)]
+        email: String,
+        /// Timeline entries to print, most recent kept
+        #[arg(long, default_value_t = 50)]
+        limit: usize,
+        /// Print the whole profile as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Change a scholar's name, cohort, or email
+    Update {
+        #[arg(long)]
+        email: String,
+        #[arg(long)]
+        name: Option<String>,
+        #[arg(long)]
+        cohort: Option<String>,
+        #[arg(long)]
+        new_email: Option<String>,
+    },
+    /// Mark a scholar inactive so they drop out of scoring and reports
+    Deactivate {
+        #[arg(long)]
+        email: String,
+        /// Why the scholar is inactive
+        #[arg(long, value_enum, default_value_t = ScholarStatus::Withdrawn)]
+        status: ScholarStatus,
+    },
+    /// Change a scholar's lifecycle status
+    SetStatus {
+        #[arg(long)]
+        email: String,
+        #[arg(long, value_enum)]
+        status: ScholarStatus,
+    },
+    /// Hide a scholar and their signals without destroying history
+    Delete {
+        #[arg(long)]
+        email: String,
+    },
+    /// Undo a soft delete
+    Restore {
+        #[arg(long)]
+        email: String,
+    },
+    /// Fold a duplicate scholar's signals, interventions, and score history into another record
+    Merge {
+        /// Record to keep
+        #[arg(long)]
+        keep: String,
+        /// Duplicate to fold in; it is soft-deleted afterwards
+        #[arg(long)]
+        absorb: String,
+    },
+    /// Propose likely duplicate scholars to review before running `scholar merge`
+    Dedupe {
+        /// Only list suggestions; nothing is merged
+        #[arg(long, required = true)]
+        suggest: bool,
+        #[arg(long)]
+        cohort: Option<String>,
+        /// Name or email local-part similarity, from 0 to 1, a pair needs to be suggested
+        #[arg(long, default_value_t = 0.85)]
+        min_similarity: f64,
+        /// Print a JSON array instead of one block per pair
+        #[arg(long)]
+        json: bool,
+    },
+    /// Irreversibly replace a scholar's name and email with pseudonyms, keeping their signals
+    Anonymize {
+        #[arg(long)]
+        email: String,
+    },
+}
 
-    match cli.command {
-        Commands::InitDb => {
-            db::init_db(&pool).await?;
-            println!("Schema ready.");
-        }
-        Commands::Seed => {
-            db::seed(&pool).await?;
-            println!("Seed data inserted.");
-        }
-        Commands::Import { csv } => {
-            let inserted = db::import_csv(&pool, &csv).await?;
-            println!("Inserted {inserted} signals from {}.", csv.display());
-        }
-        Commands::Score {
-            cohort,
-            email,
-            since_days,
-            limit,
-        } => {
-            let since_date = risk::cutoff_date(since_days);
-            let signals = db::fetch_signals(
-                &pool,
-                since_date,
-                cohort.as_deref(),
-                email.as_deref(),
-            )
-            .await?;
-            let scores = risk::score_signals(&signals, since_days);
+#[derive(Subcommand)]
+enum ConfigAction {
+    /// Write an annotated config listing every setting at its default
+    Init {
+        /// File to write; defaults to --config, else ./cew.toml
+        #[arg(long)]
+        out: Option<PathBuf>,
+        /// Replace the file if it already exists
+        #[arg(long)]
+        force: bool,
+    },
+    /// Print the configuration commands will use: the config file merged over the defaults
+    Show,
+}
 
-            if scores.is_empty() {
-                println!("No signals found for this window.");
-                return Ok(());
-            }
+#[derive(Subcommand)]
+enum CohortAction {
+    /// Record the date a cohort's program started
+    SetStart {
+        #[arg(long)]
+        cohort: String,
+        /// Start date as YYYY-MM-DD
+        #[arg(long)]
+        date: chrono::NaiveDate,
+    },
+    /// Map other spellings of cohort names, such as "Class of 2026", onto one cohort
+    Aliases {
+        #[command(subcommand)]
+        action: CohortAliasAction,
+    },
+}
 
-            println!("Top scholars by risk score:");
-            for score in scores.iter().take(limit) {
-                println!(
-                    "- {} ({}, {}) score {:.2} across {} signals",
-                    score.scholar_name,
-                    score.scholar_email,
-                    score.cohort,
-                    score.score,
-                    score.signal_count
-                );
-            }
-        }
-        Commands::Report {
-            cohort,
-            email,
-            since_days,
-            out,
-        } => {
-            let since_date = risk::cutoff_date(since_days);
-            let signals = db::fetch_signals(
-                &pool,
-                since_date,
-                cohort.as_deref(),
-                email.as_deref(),
-            )
-            .await?;
-            let trends = db::fetch_weekly_trends(
-                &pool,
-                since_date,
-                cohort.as_deref(),
-                email.as_deref(),
-            )
-            .await?;
-            let report = report::build_report(
-                cohort.as_deref().or(email.as_deref()),
-                since_days,
-                since_date,
-                &signals,
-                &trends,
-            );
-            std::fs::write(&out, report)?;
-            println!("Report written to {}.", out.display());
+#[derive(Subcommand)]
+enum CohortAliasAction {
+    /// List aliases and the cohort each maps to
+    List,
+    /// Map an alias to a cohort and move scholars stored under the alias into it
+    Add {
+        /// Matched ignoring case and surrounding spaces
+        #[arg(long)]
+        alias: String,
+        #[arg(long)]
+        cohort: String,
+    },
+    /// Stop mapping an alias; scholars already moved stay in the cohort
+    Remove {
+        #[arg(long)]
+        alias: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum AnalyticsAction {
+    /// Rank every cohort by signals per scholar, average severity, and share of scholars at risk
+    Cohorts {
+        /// Days of signals scored; defaults to [defaults] since_days
+        #[arg(long)]
+        since_days: Option<i64>,
+        /// Scholars scored at this tier or above count as at risk
+        #[arg(long, value_enum, default_value_t = risk::Tier::High)]
+        at_risk_tier: risk::Tier,
+        #[arg(long, value_enum, default_value_t = analytics::RankBy::AtRisk)]
+        rank_by: analytics::RankBy,
+    },
+    /// Summarize each advisor's active caseload by risk tier, to spot lists needing rebalancing
+    Caseloads {
+        #[arg(long)]
+        cohort: Option<String>,
+        /// Days of signals scored; defaults to [defaults] since_days
+        #[arg(long)]
+        since_days: Option<i64>,
+        /// Scholars scored at this tier or above count as at risk
+        #[arg(long, value_enum, default_value_t = risk::Tier::High)]
+        at_risk_tier: risk::Tier,
+    },
+    /// Group scholars with similar signal types and severity, to target interventions
+    Clusters {
+        #[arg(long)]
+        cohort: Option<String>,
+        /// Days of signals scored; defaults to [defaults] since_days
+        #[arg(long)]
+        since_days: Option<i64>,
+        /// Most groups to split scholars into (the k of k-means)
+        #[arg(long, default_value_t = 4, value_parser = clap::value_parser!(u8).range(1..=20))]
+        groups: u8,
+        /// Print the groups and their members as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Compare the risk tier scholars had before their outcomes with how they turned out
+    Outcomes {
+        #[arg(long)]
+        cohort: Option<String>,
+        /// Score each scholar as of this many days before their outcome
+        #[arg(long, default_value_t = 30)]
+        lead_days: i64,
+        /// Signals scored, counting back from that date; defaults to [defaults] since_days
+        #[arg(long)]
+        since_days: Option<i64>,
+        /// Scholars scored at this tier or above count as at risk
+        #[arg(long, value_enum, default_value_t = risk::Tier::High)]
+        at_risk_tier: risk::Tier,
+    },
+}
+
+#[derive(Subcommand)]
+enum OutcomeAction {
+    /// Record an outcome; withdrawals and graduations also update the scholar's status
+    Record {
+        #[arg(long)]
+        email: String,
+        #[arg(long = "type", value_enum)]
+        outcome_type: OutcomeType,
+        /// GPA on a 0-5 scale; required with --type gpa
+        #[arg(long)]
+        gpa: Option<f64>,
+        /// Date of the outcome (YYYY-MM-DD); defaults to today
+        #[arg(long)]
+        date: Option<chrono::NaiveDate>,
+        #[arg(long, default_value = "")]
+        note: String,
+    },
+    /// List recorded outcomes, newest first
+    #[command(group(
+        ArgGroup::new("scope")
+            .args(["cohort", "email"])
+            .multiple(false)
+    ))]
+    List {
+        #[arg(long)]
+        cohort: Option<String>,
+        #[arg(long)]
+        email: Option<String>,
+        #[arg(long, default_value_t = 50)]
+        limit: i64,
+    },
+}
+
+#[derive(Subcommand)]
+enum InterveneAction {
+    /// Record an intervention for a scholar
+    #[command(visible_alias = "log")]
+    Add {
+        #[arg(long)]
+        email: String,
+        /// Kind of intervention or action taken, e.g. advisor-call or "called family"
+        #[arg(long = "type", visible_alias = "action")]
+        intervention_type: String,
+        /// Date the intervention happened (YYYY-MM-DD); defaults to today
+        #[arg(long)]
+        date: Option<chrono::NaiveDate>,
+        #[arg(long)]
+        outcome: Option<String>,
+        #[arg(long, default_value = "")]
+        note: String,
+        /// Source key of a signal this intervention responds to (repeatable)
+        #[arg(long = "signal")]
+        signals: Vec<String>,
+    },
+    /// Record the final outcome of an open intervention
+    Close {
+        #[arg(long)]
+        id: Uuid,
+        /// e.g. improved, no-change, or escalated
+        #[arg(long)]
+        outcome: String,
+    },
+    /// List recent interventions
+    #[command(group(
+        ArgGroup::new("scope")
+            .args(["cohort", "email"])
+            .multiple(false)
+    ))]
+    List {
+        #[arg(long)]
+        cohort: Option<String>,
+        #[arg(long)]
+        email: Option<String>,
+        #[arg(long, default_value_t = 50)]
+        limit: i64,
+    },
+}
+
+#[derive(Subcommand)]
+enum RulesAction {
+    /// List alert rules
+    List,
+    /// Add an alert rule; every condition given must hold for a scholar to match
+    Add {
+        #[arg(long)]
+        name: String,
+        #[arg(long)]
+        signal_type: Option<String>,
+        #[arg(long)]
+        min_severity: Option<i32>,
+        /// Require at least this many signals meeting the type/severity condition
+        #[arg(long)]
+        min_count: Option<i64>,
+        /// Count only signals logged since the rules were last evaluated
+        #[arg(long)]
+        new_only: bool,
+        #[arg(long)]
+        min_score: Option<f64>,
+        #[arg(long, value_enum)]
+        min_tier: Option<risk::Tier>,
+        /// Days of signals scored; defaults to [defaults] since_days
+        #[arg(long)]
+        since_days: Option<i64>,
+        /// Channel from the config to alert (repeatable); defaults to the [watch] channels
+        #[arg(long = "channel")]
+        channels: Vec<String>,
+    },
+    /// Stop evaluating a rule without deleting it
+    Disable {
+        #[arg(long)]
+        name: String,
+    },
+    /// Re-enable a disabled rule
+    Enable {
+        #[arg(long)]
+        name: String,
+    },
+    /// Dry-run a rule against current data
+    Test {
+        #[arg(long)]
+        name: String,
+        /// Only check this scholar
+        #[arg(long)]
+        email: Option<String>,
+        /// Treat signals logged within this many hours as new, for rules that count only new ones
+        #[arg(long, default_value_t = 24)]
+        new_within_hours: i64,
+    },
+}
+
+#[derive(Subcommand)]
+enum NotificationsAction {
+    /// List recorded alert deliveries, newest first
+    List {
+        #[arg(long)]
+        since_days: Option<i64>,
+        /// Only alerts about this scholar
+        #[arg(long)]
+        email: Option<String>,
+        #[arg(long)]
+        channel: Option<String>,
+        #[arg(long, value_enum)]
+        status: Option<notify::NotificationStatus>,
+        #[arg(long, default_value_t = 50)]
+        limit: i64,
+    },
+}
+
+#[derive(Subcommand)]
+enum SyncAction {
+    /// Stream signals and score snapshots logged since the last sync into BigQuery tables,
+    /// creating the dataset and tables on the first run
+    #[cfg(feature = "bigquery")]
+    Bigquery {
+        #[arg(long, default_value = "early_warning")]
+        dataset: String,
+        /// Signals fetched per database round trip
+        #[arg(long, default_value_t = db::DEFAULT_PAGE_SIZE)]
+        page_size: i64,
+        /// Count the rows that would be sent without contacting BigQuery
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Record follow-up statuses advisors changed in the [airtable] table as interventions, then
+    /// write every active scholar's current score and tier to it
+    Airtable {
+        #[arg(long)]
+        cohort: Option<String>,
+        /// Days of signals scored; defaults to [defaults] since_days
+        #[arg(long)]
+        since_days: Option<i64>,
+    },
+}
+
+#[cfg(feature = "self-report")]
+#[derive(Subcommand)]
+enum SelfReportAction {
+    /// Record that a scholar opted in (or, with --withdraw, out) of self-reporting
+    Consent {
+        #[arg(long)]
+        email: String,
+        /// Withdraw consent and revoke any active link
+        #[arg(long)]
+        withdraw: bool,
+    },
+    /// Issue a new check-in link, revoking the scholar's previous one
+    Issue {
+        #[arg(long)]
+        email: String,
+        /// Public base URL the link is served under
+        #[arg(long, env = "CEW_SELF_REPORT_BASE_URL")]
+        base_url: Option<String>,
+    },
+    /// Revoke a scholar's active check-in link
+    Revoke {
+        #[arg(long)]
+        email: String,
+    },
+    /// Submit a check-in with a link token
+    Submit {
+        #[arg(long)]
+        token: String,
+        /// How the scholar is doing, from 1 (struggling) to 5 (great)
+        #[arg(long)]
+        rating: i32,
+        #[arg(long, default_value = "")]
+        note: String,
+        /// Check-ins accepted per link in any rolling 24 hours
+        #[arg(long, default_value_t = 3)]
+        max_per_day: i64,
+    },
+}
+
+#[derive(Subcommand)]
+enum SignalTypesAction {
+    /// List registered signal types with how many signals use each
+    List {
+        /// Print a JSON array instead of one line per type
+        #[arg(long)]
+        json: bool,
+    },
+    /// Register a signal type; once any are registered, others are refused
+    Add {
+        /// Matched ignoring case and surrounding spaces
+        #[arg(long)]
+        name: String,
+        #[arg(long, default_value = "")]
+        description: String,
+        #[arg(long, default_value_t = 1.0)]
+        default_weight: f64,
+    },
+    /// Change a signal type's description or default weight
+    Update {
+        #[arg(long)]
+        name: String,
+        #[arg(long)]
+        description: Option<String>,
+        #[arg(long)]
+        default_weight: Option<f64>,
+    },
+    /// Refuse new signals of a type while keeping existing ones
+    Disable {
+        #[arg(long)]
+        name: String,
+    },
+    /// Accept a disabled signal type again
+    Enable {
+        #[arg(long)]
+        name: String,
+    },
+    /// Register every type stored signals already use
+    Adopt,
+}
+
+#[derive(Subcommand)]
+enum AdvisorAction {
+    /// Register an advisor or rename an existing one
+    Add {
+        #[arg(long)]
+        email: String,
+        #[arg(long)]
+        name: String,
+        /// Channel from the config that receives alerts about this advisor's scholars
+        #[arg(long, conflicts_with = "no_channel")]
+        channel: Option<String>,
+        /// Send this advisor's alerts to the org-wide channels again
+        #[arg(long)]
+        no_channel: bool,
+    },
+    /// List advisors with their active caseload size
+    List,
+    /// Assign a scholar to an advisor
+    Assign {
+        /// Advisor email
+        #[arg(long)]
+        advisor: String,
+        /// Scholar email
+        #[arg(long)]
+        scholar: String,
+    },
+    /// Remove a scholar's advisor assignment
+    Unassign {
+        /// Scholar email
+        #[arg(long)]
+        scholar: String,
+    },
+}
+
+#[cfg(feature = "server")]
+#[derive(Subcommand)]
+enum ApiKeyAction {
+    /// Create a key and print it once
+    Create {
+        /// Label such as `dashboard` or `lms-sync`, used to revoke it later
+        #[arg(long)]
+        name: String,
+        /// Whose data the key sees; defaults to viewer for read keys and admin for others
+        #[arg(long, value_enum)]
+        role: Option<apikeys::Role>,
+        /// Advisor email an advisor key is limited to
+        #[arg(long, required_if_eq("role", "advisor"))]
+        advisor: Option<String>,
+        /// What the key may do; defaults to the most its role allows
+        #[arg(long, value_enum)]
+        scope: Option<apikeys::Scope>,
+    },
+    /// List live keys
+    List,
+    /// Revoke a key by name
+    Revoke {
+        #[arg(long)]
+        name: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum NotesAction {
+    /// Print a new random key for CEW_NOTE_KEY
+    Keygen,
+    /// Encrypt every note stored unencrypted, such as those from before the key was set
+    Encrypt,
+    /// Decrypt every stored note, e.g. before encrypting them again under a new key
+    Decrypt,
+}
+
+#[derive(Subcommand)]
+enum OrgAction {
+    /// Register a new organization
+    Add {
+        #[arg(long)]
+        slug: String,
+        #[arg(long)]
+        name: String,
+    },
+    /// List organizations
+    List,
+}
+
+#[derive(Subcommand)]
+enum SignalAction {
+    /// Record one signal without building a CSV, with the same upsert rules as import
+    Add {
+        /// Scholar email; unknown scholars also need --name and --cohort
+        #[arg(long)]
+        email: String,
+        #[arg(long = "type")]
+        signal_type: String,
+        /// Within the [severity] bounds from the config, 1-5 by default
+        #[arg(long, allow_negative_numbers = true)]
+        severity: i32,
+        #[arg(long)]
+        note: String,
+        /// Date the concern was observed as YYYY-MM-DD; defaults to today
+        #[arg(long)]
+        date: Option<chrono::NaiveDate>,
+        /// Name for a scholar not on the roster yet
+        #[arg(long)]
+        name: Option<String>,
+        /// Cohort for a scholar not on the roster yet, or to move an existing one
+        #[arg(long)]
+        cohort: Option<String>,
+        /// Tag added to the signal (repeatable)
+        #[arg(long = "tag")]
+        tags: Vec<String>,
+        /// Staff member entering the signal; defaults to CEW_ACTOR or the OS user
+        #[arg(long)]
+        submitted_by: Option<String>,
+        /// Stable key for the signal; re-running with the same key is a no-op
+        #[arg(long)]
+        source_key: Option<String>,
+        /// Accept a --date outside the [dates] window from the config
+        #[arg(long)]
+        allow_any_date: bool,
+    },
+    /// Add tags to a signal
+    Tag {
+        #[arg(long)]
+        source_key: String,
+        #[arg(long = "tag", required = true)]
+        tags: Vec<String>,
+    },
+    /// Remove tags from a signal
+    Untag {
+        #[arg(long)]
+        source_key: String,
+        #[arg(long = "tag", required = true)]
+        tags: Vec<String>,
+    },
+    /// Hide a signal without destroying history
+    Delete {
+        #[arg(long)]
+        source_key: String,
+    },
+    /// Undo a soft delete
+    Restore {
+        #[arg(long)]
+        source_key: String,
+    },
+    /// Acknowledge the open alerts about a scholar so they don't escalate
+    Ack {
+        #[arg(long)]
+        email: String,
+        /// Staff member acknowledging; defaults to CEW_ACTOR or the OS user
+        #[arg(long)]
+        by: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum AuditCommand {
+    /// List recent audit entries
+    List {
+        #[arg(long)]
+        since_days: Option<i64>,
+        #[arg(long)]
+        actor: Option<String>,
+        /// Only entries recorded by this subcommand (e.g. import, scholar)
+        #[arg(long)]
+        command: Option<String>,
+        #[arg(long, default_value_t = 50)]
+        limit: i64,
+    },
+    /// Bundle everything stored about one scholar, for a data-subject access request
+    Export {
+        #[arg(long)]
+        scholar: String,
+        #[arg(long, value_enum, default_value_t = subject::BundleFormat::Json)]
+        format: subject::BundleFormat,
+        /// File to write; prints to stdout when left out
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+enum ImportAction {
+    /// List recent import batches
+    Batches {
+        #[arg(long, default_value_t = 20)]
+        limit: i64,
+    },
+    /// Soft-delete every signal written by an import batch
+    Undo {
+        #[arg(long)]
+        batch: Uuid,
+    },
+    /// Write the rows spooled by `import --spool-dir` into their original batches
+    Resume {
+        #[arg(long)]
+        spool_dir: PathBuf,
+        /// Number of rows written per transaction; defaults to [import] chunk_size
+        #[arg(long)]
+        chunk_size: Option<usize>,
+    },
+}
+
+fn print_rank_diff(experimental: &str, diff: &[whatif::RankChange], limit: usize) {
+    if diff.is_empty() {
+        println!("No signals found for this window.");
+        return;
+    }
+    println!("Ranking under {experimental} compared with the active config:");
+    for change in diff.iter().take(limit) {
+        let moved = match change.places_gained() {
+            0 => "same".to_string(),
+            places => format!("{places:+}"),
+        };
+        println!(
+            "#{} (was #{}, {moved}) {} ({}, {}) score {:.2} [{}] (was {:.2} [{}])",
+            change.rank_after,
+            change.rank_before,
+            change.scholar_name,
+            change.scholar_email,
+            change.cohort,
+            change.score_after,
+            change.tier_after.as_str(),
+            change.score_before,
+            change.tier_before.as_str()
+        );
+    }
+
+    let retiered: Vec<&whatif::RankChange> = diff
+        .iter()
+        .filter(|change| change.tier_before != change.tier_after)
+        .collect();
+    let raised = retiered
+        .iter()
+        .filter(|change| change.tier_after > change.tier_before)
+        .count();
+    println!(
+        "Tier changes: {} scholars ({raised} up, {} down).",
+        retiered.len(),
+        retiered.len() - raised
+    );
+    for change in retiered.iter().take(limit) {
+        println!(
+            "- {} ({}): {} -> {}",
+            change.scholar_name,
+            change.scholar_email,
+            change.tier_before.as_str(),
+            change.tier_after.as_str()
+        );
+    }
+}
+
+/// A bar of `#` proportional to `score`, `width` long at `max`; any positive score shows at least one.
+fn score_bar(score: f64, max: f64, width: usize) -> String {
+    if score <= 0.0 || max <= 0.0 {
+        return String::new();
+    }
+    let filled = ((score / max) * width as f64).round().max(1.0) as usize;
+    "#".repeat(filled.min(width))
+}
+
+/// Applies the import tag rules to tags passed on the command line.
+fn normalize_tags(tags: Vec<String>) -> Vec<String> {
+    import::parse_tags(&tags.join(";"))
+}
+
+/// The first day scored, the number of days covered, and the last day of a `--from`/`--to` range
+/// when one was given; otherwise the `--since-days` window (or `[defaults] since_days`) back from
+/// today.
+fn date_range(
+    since_days: Option<i64>,
+    from: Option<chrono::NaiveDate>,
+    to: Option<chrono::NaiveDate>,
+    config: &Config,
+) -> anyhow::Result<(chrono::NaiveDate, i64, Option<chrono::NaiveDate>)> {
+    match (from, to) {
+        (Some(from), Some(to)) => {
+            anyhow::ensure!(from <= to, "--from {from} is after --to {to}");
+            Ok((from, (to - from).num_days() + 1, Some(to)))
+        }
+        _ => {
+            let since_days = since_days.unwrap_or(config.defaults.since_days);
+            Ok((risk::cutoff_date(since_days), since_days, None))
+        }
+    }
+}
+
+/// The roster, outcomes, and signals a survival study reads, for scholars in `cohort` or with
+/// `email` when given. Signals start `since_days` before `reference`, so each scholar can be
+/// scored as of it.
+async fn survival_inputs(
+    pool: &sqlx::PgPool,
+    org_id: Uuid,
+    cohorts: &[String],
+    emails: &[String],
+    reference: chrono::NaiveDate,
+    since_days: i64,
+    page_size: i64,
+) -> anyhow::Result<(
+    Vec<models::Scholar>,
+    Vec<models::Outcome>,
+    Vec<models::SignalRecord>,
+)> {
+    // The roster and outcome lookups take one cohort or email at a time.
+    let scopes: Vec<(Option<&str>, Option<&str>)> = match cohorts.is_empty() && emails.is_empty() {
+        true => vec![(None, None)],
+        false => cohorts
+            .iter()
+            .map(|cohort| (Some(cohort.as_str()), None))
+            .chain(emails.iter().map(|email| (None, Some(email.as_str()))))
+            .collect(),
+    };
+    let mut roster = Vec::new();
+    let mut recorded = Vec::new();
+    for (cohort, email) in scopes {
+        let filter = db::ScholarFilter {
+            cohort: cohort.map(str::to_string),
+            email: email.map(str::to_string),
+            status: None,
+            advisor: None,
+            include_deleted: false,
+        };
+        roster.extend(db::list_scholars(pool, org_id, &filter).await?);
+        recorded.extend(db::list_outcomes(pool, org_id, email, cohort, i64::MAX).await?);
+    }
+    // Scholars who have since left are usually inactive, with archived signals.
+    let filter = db::SignalFilter {
+        org_id: Some(org_id),
+        cohorts: cohorts.to_vec(),
+        emails: emails.to_vec(),
+        include_inactive: true,
+        include_archived: true,
+        ..db::SignalFilter::new(reference - chrono::Duration::days(since_days))
+    };
+    let signals = db::fetch_signals(pool, &filter, page_size).await?;
+    Ok((roster, recorded, signals))
+}
+
+/// Sets the database's severity check to the configured bounds, recording any change in the audit
+/// log.
+async fn apply_severity_bounds(
+    pool: &sqlx::PgPool,
+    command: &str,
+    bounds: import::SeverityBounds,
+) -> anyhow::Result<()> {
+    let Some(previous) = db::apply_severity_bounds(pool, bounds).await? else {
+        return Ok(());
+    };
+    audit::record(
+        pool,
+        command,
+        AuditAction::Migrate,
+        "signals",
+        0,
+        &format!("severity bounds {previous} -> {bounds}"),
+    )
+    .await?;
+    println!("Severity check changed from {previous} to {bounds}.");
+    Ok(())
+}
+
+fn format_versions(versions: &[i64]) -> String {
+    versions
+        .iter()
+        .map(|version| format!("{version:03}"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+async fn promote_cohort(
+    cli: &Cli,
+    config: &Config,
+    from: &str,
+    to: &str,
+    cohort: &str,
+    dry_run: bool,
+) -> anyhow::Result<()> {
+    anyhow::ensure!(
+        from != to,
+        "--from and --to must name different environments"
+    );
+    let settings = cli.pool.settings(&config.database)?;
+    let source = db::connect(&config.database_url(from)?, &settings).await?;
+    let target = db::connect(&config.database_url(to)?, &settings).await?;
+
+    let (target_org, plan) =
+        promote::prepare(&source, &target, &cli.org, cohort, &config.import_limits()).await?;
+    println!(
+        "Cohort {cohort} from {from} to {to}: {} new scholars ({} already present), {} new signals \
+         ({} already present).",
+        plan.new_scholars.len(),
+        plan.existing_scholars,
+        plan.new_signals.len(),
+        plan.existing_signals
+    );
+    if let Some(start_date) = plan.start_date {
+        println!("Cohort start date {start_date} will be copied.");
+    }
+    if !plan.conflicts.is_empty() {
+        for conflict in &plan.conflicts {
+            println!("Conflict: {conflict}");
+        }
+        anyhow::bail!("{} conflicts; nothing was promoted", plan.conflicts.len());
+    }
+    if dry_run {
+        println!("Dry run; nothing was written.");
+        return Ok(());
+    }
+
+    let batch_id = promote::apply(&target, target_org, cohort, from, &plan).await?;
+    audit::record(
+        &target,
+        "promote",
+        AuditAction::Insert,
+        "signals",
+        plan.new_signals.len() as u64,
+        &format!("cohort {cohort} from {from}, batch {batch_id}"),
+    )
+    .await?;
+    println!("Promoted as import batch {batch_id}; `import undo --batch {batch_id}` reverts the signals.");
+    Ok(())
+}
+
+async fn onboard_cohort(
+    pool: &sqlx::PgPool,
+    org: &str,
+    config: &Config,
+    out: &std::path::Path,
+) -> anyhow::Result<()> {
+    let stdin = std::io::stdin();
+    let mut prompter = onboard::Prompter::new(stdin.lock(), std::io::stdout());
+    let plan = onboard::interview(&mut prompter, config)?;
+
+    prompter.say("== Review ==")?;
+    match onboard::check_sample(&plan) {
+        Ok(rows) => prompter.say(&format!(
+            "{} parses cleanly through the mapping ({rows} rows).",
+            plan.sample.display()
+        ))?,
+        Err(err) => {
+            prompter.say(&format!(
+                "{} does not import: {err:#}",
+                plan.sample.display()
+            ))?;
+            anyhow::bail!("fix the mapping or the sample and run onboard again; nothing was saved");
+        }
+    }
+    let updated = plan.apply_to(config);
+    prompter.say(&updated.to_toml()?)?;
+    let question = if out.exists() {
+        format!(
+            "Overwrite {} with this config? Comments in it are not kept",
+            out.display()
+        )
+    } else {
+        format!("Write this config to {}?", out.display())
+    };
+    if !prompter.confirm(&question, true)? {
+        anyhow::bail!("onboarding cancelled; nothing was saved");
+    }
+    std::fs::write(out, updated.to_toml()?)
+        .with_context(|| format!("failed to write {}", out.display()))?;
+
+    let org_id = db::resolve_org(pool, org).await?;
+    if let Some(date) = plan.start_date {
+        db::set_cohort_start(pool, org_id, &plan.cohort, date).await?;
+        audit::record(
+            pool,
+            "onboard",
+            AuditAction::Update,
+            "cohorts",
+            1,
+            &format!("{} -> {date}", plan.cohort),
+        )
+        .await?;
+    }
+    for (email, name) in &plan.advisors {
+        db::upsert_advisor(pool, org_id, email, name, &db::AdvisorChannel::Keep).await?;
+    }
+    if !plan.advisors.is_empty() {
+        audit::record(
+            pool,
+            "onboard",
+            AuditAction::Insert,
+            "advisors",
+            plan.advisors.len() as u64,
+            &format!("cohort {}", plan.cohort),
+        )
+        .await?;
+    }
+
+    let import_command = format!("import --source {} --csv <file>", plan.source_name);
+    if prompter.confirm("Import the sample now?", false)? {
+        let summary = import::import_csv(
+            pool,
+            org_id,
+            &plan.sample,
+            import::ImportLimits::default(),
+            &plan.mapping,
+            &[],
+            &import::Recovery::default(),
+        )
+        .await?;
+        audit::record(
+            pool,
+            "onboard",
+            AuditAction::Insert,
+            "signals",
+            summary.inserted as u64,
+            &format!("batch {} from {}", summary.batch_id, plan.sample.display()),
+        )
+        .await?;
+        println!(
+            "Imported {} signals as batch {} ({} already present).",
+            summary.inserted, summary.batch_id, summary.skipped
+        );
+    }
+    println!(
+        "Cohort {} is ready. Import new files with `{import_command}`.",
+        plan.cohort
+    );
+    Ok(())
+}
+
+/// Prints the effective config as TOML, followed by the database settings in effect once the
+/// `--db-*` flags and their environment variables are applied over `[database]`.
+fn print_config(cli: &Cli, config: &Config) -> anyhow::Result<()> {
+    match Config::resolve_path(cli.config.as_deref()) {
+        Some(path) => println!("# {} merged over the built-in defaults", path.display()),
+        None => println!(
+            "# Built-in defaults; no {DEFAULT_CONFIG_FILE} or {} found",
+            config::CONFIG_FILE_NAME
+        ),
+    }
+    println!();
+    println!("{}", config.to_toml()?.trim_end());
+
+    let settings = cli.pool.settings(&config.database)?;
+    let optional = |value: Option<String>| value.unwrap_or_else(|| "unset".to_string());
+    println!();
+    println!("# Database, after flags and environment variables:");
+    let url = match (&cli.database_url, &cli.profile) {
+        (Some(_), _) => "from --database-url".to_string(),
+        (None, Some(profile)) => match config.environments.get(profile) {
+            Some(environment) => {
+                format!("profile {profile}, from {}", environment.database_url_env)
+            }
+            None => format!("profile {profile} is not in [environments]"),
+        },
+        (None, None) if std::env::var_os("DATABASE_URL").is_some() => {
+            "from DATABASE_URL".to_string()
+        }
+        (None, None) => "unset".to_string(),
+    };
+    println!("# Database URL: {url}");
+    println!("# --db-max-connections: {}", settings.max_connections);
+    println!(
+        "# --db-acquire-timeout: {}s",
+        settings.acquire_timeout.as_secs()
+    );
+    println!(
+        "# --db-statement-timeout: {}",
+        optional(
+            settings
+                .statement_timeout
+                .map(|timeout| format!("{}s", timeout.as_secs()))
+        )
+    );
+    println!(
+        "# --db-ssl-mode: {}",
+        optional(settings.ssl_mode.map(|mode| match mode {
+            PgSslMode::Disable => "disable".to_string(),
+            PgSslMode::Allow => "allow".to_string(),
+            PgSslMode::Prefer => "prefer".to_string(),
+            PgSslMode::Require => "require".to_string(),
+            PgSslMode::VerifyCa => "verify-ca".to_string(),
+            PgSslMode::VerifyFull => "verify-full".to_string(),
+        }))
+    );
+    println!(
+        "# --db-ssl-root-cert: {}",
+        optional(
+            settings
+                .ssl_root_cert
+                .map(|path| path.display().to_string())
+        )
+    );
+    Ok(())
+}
+
+/// Commands that run until stopped.
+const LONG_RUNNING: [&str; 3] = ["consume", "serve", "watch"];
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let matches = Cli::command().get_matches();
+    let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|err| err.exit());
+    let format = cli.log.log_format;
+    let logger = logging::Logger::new(logging::level(cli.log.quiet, cli.log.verbose), format);
+    #[cfg(feature = "otel")]
+    let telemetry = match &cli.log.otlp_endpoint {
+        Some(endpoint) => Some(telemetry::install(logger, endpoint)?),
+        None => {
+            logger.install();
+            None
+        }
+    };
+    #[cfg(not(feature = "otel"))]
+    logger.install();
+    let command = matches.subcommand_name().unwrap_or_default().to_string();
+    let started = Instant::now();
+
+    // Long-running commands would hold one span open until they stop; their cycles, batches, and
+    // requests start traces of their own instead.
+    let result = if LONG_RUNNING.contains(&command.as_str()) {
+        run(cli).await
+    } else {
+        run(cli)
+            .instrument(tracing::info_span!("command", otel.name = command))
+            .await
+    };
+    let elapsed_ms = started.elapsed().as_millis() as u64;
+    #[cfg(feature = "otel")]
+    if let Some(provider) = telemetry {
+        if let Err(err) = provider.shutdown() {
+            tracing::warn!(error = %err, "could not export the last spans");
+        }
+    }
+    match &result {
+        Ok(()) => tracing::info!(command, elapsed_ms, "command finished"),
+        // A log collector reading JSON lines gets the failure as one of them too.
+        Err(err) if format == logging::LogFormat::Json => {
+            tracing::error!(
+                command,
+                elapsed_ms,
+                error = format!("{err:#}"),
+                "command failed"
+            );
+            std::process::exit(1);
+        }
+        Err(_) => {}
+    }
+    result
+}
+
+async fn run(cli: Cli) -> anyhow::Result<()> {
+    // Init runs before loading, since the file it writes may not exist yet.
+    if let Commands::Config {
+        action: ConfigAction::Init { out, force },
+    } = &cli.command
+    {
+        let out = out
+            .clone()
+            .or_else(|| cli.config.clone())
+            .unwrap_or_else(|| PathBuf::from(DEFAULT_CONFIG_FILE));
+        if out.exists() && !force {
+            anyhow::bail!(
+                "{} already exists; pass --force to replace it",
+                out.display()
+            );
+        }
+        std::fs::write(&out, config::CONFIG_TEMPLATE)
+            .with_context(|| format!("failed to write {}", out.display()))?;
+        println!(
+            "Wrote {}; `config show` prints the settings in effect.",
+            out.display()
+        );
+        return Ok(());
+    }
+    let config = Config::load(cli.config.as_deref())?;
+    if let Commands::Config {
+        action: ConfigAction::Show,
+    } = &cli.command
+    {
+        print_config(&cli, &config)?;
+        return Ok(());
+    }
+    if let Commands::Notes {
+        action: NotesAction::Keygen,
+    } = &cli.command
+    {
+        println!("{}", notes::NoteKey::generate());
+        return Ok(());
+    }
+    if let Some(key) = config.notes.key()? {
+        let key = notes::NoteKey::parse(&key).with_context(|| {
+            format!(
+                "the note key from {} or [notes] key_command is invalid",
+                config.notes.key_env
+            )
+        })?;
+        notes::install(key);
+    }
+    let settings = cli.pool.settings(&config.database)?;
+    // Promote talks to its two configured databases instead of DATABASE_URL.
+    if let Commands::Promote {
+        from,
+        to,
+        cohort,
+        dry_run,
+    } = &cli.command
+    {
+        return promote_cohort(&cli, &config, from, to, cohort, *dry_run).await;
+    }
+    // Doctor reports a missing or unreachable database instead of failing on it.
+    if let Commands::Doctor { explain } = cli.command {
+        let database_url = cli.database_url(&config)?;
+        let diagnoses = doctor::run(
+            database_url.as_deref(),
+            &settings,
+            &cli.org,
+            config.severity,
+            explain,
+        )
+        .await;
+        for diagnosis in &diagnoses {
+            println!("{diagnosis}");
+        }
+        let failed = diagnoses
+            .iter()
+            .filter(|diagnosis| diagnosis.status == doctor::Status::Fail)
+            .count();
+        if failed > 0 {
+            anyhow::bail!("{failed} check(s) failed");
+        }
+        return Ok(());
+    }
+    let database_url = cli.database_url(&config)?.context(
+        "DATABASE_URL must be set to a production Postgres instance, or pass --database-url or \
+         --profile",
+    )?;
+
+    let pool = db::connect(&database_url, &settings).await?;
+    let org = cli.org;
+
+    match cli.command {
+        Commands::Promote { .. } => unreachable!("promote runs before connecting to DATABASE_URL"),
+        Commands::Doctor { .. } => unreachable!("doctor runs before connecting to DATABASE_URL"),
+        Commands::Config { .. } => unreachable!("config runs before connecting to DATABASE_URL"),
+        Commands::Onboard { out } => {
+            let out = out
+                .or_else(|| cli.config.clone())
+                .unwrap_or_else(|| PathBuf::from(DEFAULT_CONFIG_FILE));
+            onboard_cohort(&pool, &org, &config, &out).await?;
+        }
+        Commands::InitDb => {
+            db::init_db(&pool).await?;
+            audit::record(&pool, "init-db", AuditAction::Migrate, "schema", 0, "").await?;
+            apply_severity_bounds(&pool, "init-db", config.severity).await?;
+            println!("Schema ready.");
+        }
+        Commands::Migrate {
+            action: MigrateAction::Status,
+        } => {
+            let statuses = db::migration_status(&pool).await?;
+            for status in &statuses {
+                println!(
+                    "{:03} {:<9} {:<28} {}{}",
+                    status.version,
+                    status.state.as_str(),
+                    status.description,
+                    status
+                        .installed_on
+                        .map(|installed_on| installed_on.format("%Y-%m-%d %H:%M").to_string())
+                        .unwrap_or_else(|| "-".to_string()),
+                    if status.reversible {
+                        ""
+                    } else {
+                        " (irreversible)"
+                    }
+                );
+            }
+            let pending = statuses
+                .iter()
+                .filter(|status| status.state == db::MigrationState::Pending)
+                .count();
+            println!("{} migrations, {pending} pending.", statuses.len());
+        }
+        Commands::Migrate {
+            action: MigrateAction::Up { to },
+        } => {
+            let applied = db::migrate_up(&pool, to).await?;
+            audit::record(
+                &pool,
+                "migrate up",
+                AuditAction::Migrate,
+                "schema",
+                applied.len() as u64,
+                &format_versions(&applied),
+            )
+            .await?;
+            if applied.is_empty() {
+                println!("Schema already up to date.");
+            } else {
+                println!("Applied migrations {}.", format_versions(&applied));
+            }
+            apply_severity_bounds(&pool, "migrate up", config.severity).await?;
+        }
+        Commands::Migrate {
+            action: MigrateAction::Revert { to },
+        } => {
+            let reverted = db::migrate_revert(&pool, to).await?;
+            let lowest = reverted.last().copied().unwrap_or(i64::MAX);
+            if lowest > db::AUDIT_LOG_MIGRATION {
+                audit::record(
+                    &pool,
+                    "migrate revert",
+                    AuditAction::Migrate,
+                    "schema",
+                    reverted.len() as u64,
+                    &format_versions(&reverted),
+                )
+                .await?;
+            }
+            if reverted.is_empty() {
+                println!("Nothing to revert.");
+            } else {
+                println!("Reverted migrations {}.", format_versions(&reverted));
+            }
+        }
+        Commands::Seed { scholars: None, .. } => {
+            let org_id = db::resolve_org(&pool, &org).await?;
+            let inserted = db::seed(&pool, org_id).await?;
+            audit::record(&pool, "seed", AuditAction::Insert, "signals", inserted, "").await?;
+            println!("Seed data inserted.");
+        }
+        Commands::Seed {
+            scholars: Some(scholars),
+            weeks,
+            seed,
+        } => {
+            let org_id = db::resolve_org(&pool, &org).await?;
+            let generated = synthetic::generate(&synthetic::SyntheticOptions {
+                scholars,
+                weeks,
+                seed,
+                today: chrono::Utc::now().date_naive(),
+            });
+            let source = format!("synthetic seed {seed} ({scholars} scholars, {weeks} weeks)");
+            let (batch_id, inserted) =
+                db::seed_synthetic(&pool, org_id, &generated, &source).await?;
+            audit::record(
+                &pool,
+                "seed",
+                AuditAction::Insert,
+                "signals",
+                inserted,
+                &format!("batch {batch_id}: {source}"),
+            )
+            .await?;
+            println!(
+                "Generated {scholars} scholars and inserted {inserted} signals tagged `{}` as \
+                 batch {batch_id}; `import undo --batch {batch_id}` removes the signals.",
+                synthetic::SYNTHETIC_TAG
+            );
+        }
+        Commands::Import {
+            action: Some(ImportAction::Batches { limit }),
+            ..
+        } => {
+            let org_id = db::resolve_org(&pool, &org).await?;
+            let batches = db::list_import_batches(&pool, org_id, limit).await?;
+            if batches.is_empty() {
+                println!("No import batches recorded.");
+                return Ok(());
+            }
+
+            for batch in batches {
+                let status = match batch.undone_at {
+                    Some(at) => format!("undone {}", at.format("%Y-%m-%d %H:%M")),
+                    None => "active".to_string(),
+                };
+                println!(
+                    "- {} {} from {}: {} signals ({})",
+                    batch.id,
+                    batch.started_at.format("%Y-%m-%d %H:%M"),
+                    batch.source,
+                    batch.signal_count,
+                    status
+                );
+            }
+        }
+        Commands::Import {
+            action: Some(ImportAction::Undo { batch }),
+            ..
+        } => {
+            let org_id = db::resolve_org(&pool, &org).await?;
+            let deleted = db::undo_import_batch(&pool, org_id, batch).await?;
+            audit::record(
+                &pool,
+                "import undo",
+                AuditAction::Delete,
+                "signals",
+                deleted,
+                &format!("batch {batch}"),
+            )
+            .await?;
+            println!("Removed {deleted} signals from import batch {batch}.");
+        }
+        Commands::Import {
+            action:
+                Some(ImportAction::Resume {
+                    spool_dir,
+                    chunk_size,
+                }),
+            ..
+        } => {
+            let org_id = db::resolve_org(&pool, &org).await?;
+            let mut spools = Vec::new();
+            for entry in std::fs::read_dir(&spool_dir)
+                .with_context(|| format!("failed to read {}", spool_dir.display()))?
+            {
+                let path = entry?.path();
+                if path
+                    .extension()
+                    .is_some_and(|extension| extension == "jsonl")
+                {
+                    spools.push(path);
+                }
+            }
+            spools.sort();
+            if spools.is_empty() {
+                println!("No spooled imports in {}.", spool_dir.display());
+                return Ok(());
+            }
+
+            let retry = cli.pool.retry(&config.database);
+            let chunk_size = chunk_size.unwrap_or(config.import.chunk_size);
+            let mut inserted = 0;
+            for path in spools {
+                let summary =
+                    import::resume_spool(&pool, org_id, &path, chunk_size, &retry).await?;
+                println!(
+                    "Inserted {} signals from {} ({} rows spooled, batch {}).",
+                    summary.inserted,
+                    path.display(),
+                    summary.rows_read,
+                    summary.batch_id
+                );
+                if summary.updated > 0 {
+                    println!("Updated {} signals already stored.", summary.updated);
+                }
+                for warning in &summary.warnings {
+                    println!("Warning: {warning}");
+                }
+                inserted += summary.inserted + summary.updated;
+                let recorded = audit::record(
+                    &pool,
+                    "import resume",
+                    AuditAction::Insert,
+                    "signals",
+                    summary.inserted as u64,
+                    &format!("batch {} from {}", summary.batch_id, path.display()),
+                )
+                .await;
+                if summary.spool.is_some() {
+                    if let Err(err) = recorded {
+                        tracing::warn!(
+                            error = format!("{err:#}"),
+                            "could not record the import in the audit log"
+                        );
+                    }
+                    anyhow::bail!(
+                        "the database became unreachable again; run `import resume` once it is \
+                         back"
+                    );
+                }
+                recorded?;
+            }
+            let active_rules = rules::active_rules(&pool, org_id, &config).await?;
+            if inserted > 0 && !active_rules.is_empty() {
+                let firings =
+                    rules::fire_rules(&pool, &org, org_id, &config, &active_rules).await?;
+                println!(
+                    "Evaluated {} alert rules, {} fired.",
+                    active_rules.len(),
+                    rules::fired_count(&firings)
+                );
+                rules::print_firings(&firings);
+            }
+        }
+        Commands::Import {
+            action: None,
+            csv,
+            max_file_mb,
+            max_rows,
+            max_note_length,
+            chunk_size,
+            note_policy,
+            summary_sentences,
+            keep_note_overflow,
+            tags,
+            summary_out,
+            source,
+            jobs,
+            spool_dir,
+            atomic,
+            allow_any_date,
+            strict,
+            lenient,
+            on_conflict,
+            register_types,
+        } => {
+            let defaults = config.import_limits();
+            let limits = import::ImportLimits {
+                max_file_bytes: max_file_mb
+                    .map_or(defaults.max_file_bytes, |mb| mb.saturating_mul(1024 * 1024)),
+                max_rows: max_rows.unwrap_or(defaults.max_rows),
+                max_note_chars: max_note_length.unwrap_or(defaults.max_note_chars),
+                chunk_size: chunk_size.unwrap_or(defaults.chunk_size),
+                note_policy: note_policy.unwrap_or(defaults.note_policy),
+                summary_sentences: summary_sentences.unwrap_or(defaults.summary_sentences),
+                keep_note_overflow,
+                dates: defaults.dates.filter(|_| !allow_any_date),
+                validation: match (strict, lenient) {
+                    (true, _) => import::Validation::Strict,
+                    (_, true) => import::Validation::Lenient,
+                    _ => import::Validation::Standard,
+                },
+                on_conflict: on_conflict.unwrap_or(defaults.on_conflict),
+                register_types,
+                ..defaults
+            };
+            let csv = csv.context("--csv is required")?;
+            let org_id = db::resolve_org(&pool, &org).await?;
+            let mapping = match &source {
+                Some(name) => config.source(name)?.clone(),
+                None => import::SourceMapping::default(),
+            };
+            let files = import::csv_files(&csv)?;
+            let tags = normalize_tags(tags);
+            if let Some(dir) = &spool_dir {
+                std::fs::create_dir_all(dir)
+                    .with_context(|| format!("failed to create {}", dir.display()))?;
+            }
+            let recovery = import::Recovery {
+                retry: cli.pool.retry(&config.database),
+                spool_dir,
+            };
+            // Each running import holds at most one connection, for its current chunk.
+            let jobs = usize::from(jobs.unwrap_or(config.import.jobs))
+                .min(settings.max_connections.max(1) as usize);
+
+            let mut running = tokio::task::JoinSet::new();
+            let mut finished = Vec::with_capacity(files.len());
+            for path in files {
+                if running.len() == jobs {
+                    finished.extend(running.join_next().await.transpose()?);
+                }
+                let (pool, limits, mapping, tags, recovery) = (
+                    pool.clone(),
+                    limits.clone(),
+                    mapping.clone(),
+                    tags.clone(),
+                    recovery.clone(),
+                );
+                running.spawn(async move {
+                    let summary = if atomic {
+                        import::import_csv_atomic(&pool, org_id, &path, limits, &mapping, &tags)
+                            .await
+                    } else {
+                        import::import_csv(&pool, org_id, &path, limits, &mapping, &tags, &recovery)
+                            .await
+                    };
+                    (path, summary)
+                });
+            }
+            while let Some(done) = running.join_next().await {
+                finished.push(done?);
+            }
+            finished.sort_by(|a, b| a.0.cmp(&b.0));
+
+            let mut summaries = BTreeMap::new();
+            let mut failed = 0;
+            for (path, summary) in finished {
+                let summary = match summary {
+                    Ok(summary) => summary,
+                    Err(err) if !csv.is_dir() => return Err(err),
+                    Err(err) => {
+                        tracing::error!(
+                            file = %path.display(),
+                            error = format!("{err:#}"),
+                            "import failed"
+                        );
+                        failed += 1;
+                        continue;
+                    }
+                };
+                let recorded = audit::record(
+                    &pool,
+                    "import",
+                    AuditAction::Insert,
+                    "signals",
+                    summary.inserted as u64,
+                    &format!("batch {} from {}", summary.batch_id, path.display()),
+                )
+                .await;
+                match recorded {
+                    // The database that made the import spool is likely still unreachable.
+                    Err(err) if summary.spool.is_some() => {
+                        tracing::warn!(
+                            error = format!("{err:#}"),
+                            "could not record the import in the audit log"
+                        )
+                    }
+                    recorded => recorded?,
+                }
+                println!(
+                    "Inserted {} signals from {} ({} rows read, batch {}).",
+                    summary.inserted,
+                    path.display(),
+                    summary.rows_read,
+                    summary.batch_id
+                );
+                if summary.updated > 0 {
+                    audit::record(
+                        &pool,
+                        "import",
+                        AuditAction::Update,
+                        "signals",
+                        summary.updated as u64,
+                        &format!("batch {} from {}", summary.batch_id, path.display()),
+                    )
+                    .await?;
+                    println!(
+                        "Updated {} signals already stored with a changed severity, note, or date.",
+                        summary.updated
+                    );
+                }
+                for warning in &summary.warnings {
+                    println!("Warning: {warning}");
+                }
+                summaries.insert(path.display().to_string(), summary);
+            }
+            let inserted: usize = summaries
+                .values()
+                .map(|summary| summary.inserted + summary.updated)
+                .sum();
+            let spooled: usize = summaries.values().map(|summary| summary.spooled).sum();
+            // Once rows are spooled the rules wait for `import resume`, which fires them.
+            let active_rules = match spooled {
+                0 => rules::active_rules(&pool, org_id, &config).await?,
+                _ => Vec::new(),
+            };
+            if inserted > 0 && !active_rules.is_empty() {
+                let firings =
+                    rules::fire_rules(&pool, &org, org_id, &config, &active_rules).await?;
+                println!(
+                    "Evaluated {} alert rules, {} fired.",
+                    active_rules.len(),
+                    rules::fired_count(&firings)
+                );
+                rules::print_firings(&firings);
+            }
+            if let Some(path) = summary_out {
+                // A single file keeps its flat summary; a directory gets one per file, by path.
+                let json = match (csv.is_dir(), summaries.values().next()) {
+                    (false, Some(summary)) => serde_json::to_string_pretty(summary)?,
+                    _ => serde_json::to_string_pretty(&summaries)?,
+                };
+                std::fs::write(&path, json)
+                    .with_context(|| format!("failed to write {}", path.display()))?;
+                println!("Summary written to {}.", path.display());
+            }
+            if failed > 0 {
+                anyhow::bail!(
+                    "{failed} of {} files failed to import",
+                    failed + summaries.len()
+                );
+            }
+            if spooled > 0 {
+                anyhow::bail!(
+                    "{spooled} rows were spooled while the database was unreachable; run \
+                     `import resume` once it is back"
+                );
+            }
+        }
+        Commands::Score {
+            cohort,
+            email,
+            advisor,
+            since_days,
+            from,
+            to,
+            limit,
+            no_color,
+            sort,
+            asc,
+            desc,
+            include_inactive,
+            tags,
+            signal_types,
+            exclude_types,
+            min_severity,
+            note_categories,
+            max_sentiment,
+            min_score,
+            fail_if_above,
+            fail_if_count,
+            page_size,
+            dry_run,
+            baseline,
+            windows,
+            aggregate_in_db,
+            all_cohorts,
+            rank_in_db,
+        } => {
+            let (since_date, since_days, until_date) = date_range(since_days, from, to, &config)?;
+            let org_id = db::resolve_org(&pool, &org).await?;
+            let filter = db::SignalFilter {
+                org_id: Some(org_id),
+                cohorts: cohort,
+                emails: email,
+                advisor,
+                include_inactive,
+                tags: normalize_tags(tags),
+                signal_types,
+                exclude_signal_types: exclude_types,
+                min_severity,
+                note_categories,
+                max_note_sentiment: max_sentiment,
+                until_date,
+                ..db::SignalFilter::new(since_date)
+            };
+            if dry_run {
+                let experimental_path = cli.config.as_deref().context(
+                    "--dry-run scores under --config; pass the experimental config file",
+                )?;
+                let active = Config::load(baseline.as_deref())?;
+                let (before, after) = whatif::score_both(
+                    &pool,
+                    &filter,
+                    since_days,
+                    &active.scoring,
+                    &config.scoring,
+                    page_size,
+                )
+                .await?;
+                print_rank_diff(
+                    &experimental_path.display().to_string(),
+                    &whatif::rank_diff(&before, &after),
+                    limit,
+                );
+                return Ok(());
+            }
+            let scope = db::ScoreScope {
+                filter: &filter,
+                since_days,
+                scoring: &config.scoring,
+            };
+            let mut scores = match (rank_in_db, aggregate_in_db) {
+                (true, _) => {
+                    db::cached(
+                        &pool,
+                        org_id,
+                        config.cache.ttl(),
+                        "top-scores",
+                        &(scope, limit),
+                        db::top_scores(&pool, &filter, &config.scoring, limit as i64),
+                    )
+                    .await?
+                }
+                (false, true) => {
+                    db::cached(
+                        &pool,
+                        org_id,
+                        config.cache.ttl(),
+                        "aggregate-scores",
+                        &scope,
+                        db::aggregate_scope(&pool, &filter, &config.scoring),
+                    )
+                    .await?
+                }
+                // Shares entries with `GET /scores` over the same scope.
+                (false, false) => {
+                    db::cached(
+                        &pool,
+                        org_id,
+                        config.cache.ttl(),
+                        "scores",
+                        &scope,
+                        db::score_scope(&pool, &filter, since_days, &config.scoring, page_size),
+                    )
+                    .await?
+                }
+            };
+
+            if scores.is_empty() {
+                println!("No signals found for this window.");
+                return Ok(());
+            }
+            if let Some(min_score) = min_score {
+                scores.retain(|score| score.score >= min_score);
+                if scores.is_empty() {
+                    println!("No scholars scored at least {min_score:.2}.");
+                    return Ok(());
+                }
+            }
+            let descending = desc || (!asc && sort.descending_by_default());
+            risk::sort_scores(&mut scores, sort, descending);
+            let windows = match windows {
+                true => Some(
+                    db::score_windows(
+                        &pool,
+                        &filter,
+                        &risk::DEFAULT_WINDOWS,
+                        &config.scoring,
+                        page_size,
+                    )
+                    .await?,
+                ),
+                false => None,
+            };
+
+            let listing = scoreboard::Listing {
+                windows: windows.as_ref(),
+                new_entrants: scoreboard::new_entrants(
+                    &db::latest_run_tiers(&pool, org_id).await?,
+                    &scores,
+                ),
+                today: chrono::Utc::now().date_naive(),
+                color: !no_color
+                    && std::env::var_os("NO_COLOR").is_none()
+                    && std::io::stdout().is_terminal(),
+            };
+            let breach = scoreboard::breach(&scores, fail_if_above, fail_if_count);
+            if !all_cohorts {
+                println!("Top scholars by {}:", sort.label());
+                let shown = &scores[..limit.min(scores.len())];
+                println!("{}", scoreboard::render(shown, &listing));
+            } else {
+                for (index, (cohort, scores)) in report::by_cohort(scores, |score| &score.cohort)
+                    .into_iter()
+                    .enumerate()
+                {
+                    if index > 0 {
+                        println!();
+                    }
+                    println!("Top scholars in {cohort} by {}:", sort.label());
+                    let shown = &scores[..limit.min(scores.len())];
+                    println!("{}", scoreboard::render(shown, &listing));
+                }
+            }
+            if let Some(breach) = breach {
+                anyhow::bail!(breach);
+            }
+        }
+        Commands::Report {
+            cohort,
+            email,
+            advisor,
+            since_days,
+            from,
+            to,
+            out,
+            min_cell_size,
+            include_inactive,
+            tags,
+            signal_types,
+            exclude_types,
+            min_severity,
+            note_categories,
+            max_sentiment,
+            min_score,
+            page_size,
+            include_archived,
+            cached_trends,
+            trend_alert_pct,
+            co_occurrence,
+            clusters,
+            health,
+            survival,
+            all_cohorts,
+            out_dir,
+            redact,
+            #[cfg(feature = "sheets")]
+            publish_sheet,
+        } => {
+            let (since_date, since_days, until_date) = date_range(since_days, from, to, &config)?;
+            let org_id = db::resolve_org(&pool, &org).await?;
+            let filter = db::SignalFilter {
+                org_id: Some(org_id),
+                cohorts: cohort.clone(),
+                emails: email.clone(),
+                advisor: advisor.clone(),
+                include_inactive,
+                tags: normalize_tags(tags),
+                signal_types,
+                exclude_signal_types: exclude_types,
+                min_severity,
+                note_categories,
+                max_note_sentiment: max_sentiment,
+                include_archived,
+                until_date,
+                ..db::SignalFilter::new(since_date)
+            };
+            let today = filter.as_of();
+            let survival = match survival {
+                Some(reference) => {
+                    let reference = reference.unwrap_or(today - chrono::Duration::days(365));
+                    if reference >= today {
+                        anyhow::bail!("--survival needs a date in the past, got {reference}");
+                    }
+                    Some(reference)
+                }
+                None => None,
+            };
+            let options = report::ReportOptions {
+                min_cell_size,
+                scoring: config.scoring.clone(),
+                trend_alert_pct: trend_alert_pct.map_or(config.report.trend_alert_pct(), |pct| {
+                    (pct > 0.0).then_some(pct)
+                }),
+                co_occurrence_days: co_occurrence.map(|days| days.unwrap_or(since_days)),
+                clusters: clusters.map(usize::from),
+                calendar: config.calendar.clone(),
+                through: until_date,
+                min_score,
+                redact,
+                ..report::ReportOptions::default()
+            };
+            let scope = (
+                &filter,
+                since_days,
+                (min_cell_size, cached_trends, trend_alert_pct, co_occurrence),
+                (min_score, redact),
+                (clusters, health, survival),
+                (
+                    &config.scoring,
+                    &config.report,
+                    &config.calendar,
+                    &config.playbooks,
+                ),
+            );
+            let health_since = today - chrono::Duration::weeks(52);
+
+            #[cfg(feature = "sheets")]
+            if let Some(spreadsheet_id) = publish_sheet {
+                let signals = report::by_cohort(
+                    db::fetch_signals(&pool, &filter, page_size).await?,
+                    |signal| &signal.cohort,
+                );
+                if signals.is_empty() {
+                    println!("No signals found for this window.");
+                    return Ok(());
+                }
+                let names: Vec<String> = signals.keys().cloned().collect();
+                let mut trends = report::by_cohort(
+                    db::fetch_cohort_weekly_trends(&pool, &filter, &names).await?,
+                    |trend| &trend.cohort,
+                );
+                let options = report::ReportOptions {
+                    last_contacts: Some(db::last_contacts(&pool, org_id).await?),
+                    ..options
+                };
+                let tabs: Vec<(String, Vec<Vec<serde_json::Value>>)> = signals
+                    .iter()
+                    .map(|(cohort, signals)| {
+                        let trends: Vec<models::SignalTrend> = trends
+                            .remove(cohort)
+                            .unwrap_or_default()
+                            .into_iter()
+                            .map(|trend| models::SignalTrend {
+                                week_start: trend.week_start,
+                                signal_count: trend.signal_count,
+                                avg_severity: trend.avg_severity,
+                                scholar_count: trend.scholar_count,
+                            })
+                            .collect();
+                        let rows =
+                            sheets::cohort_rows(since_days, since_date, signals, &trends, &options);
+                        (sheets::tab_title(cohort), rows)
+                    })
+                    .collect();
+                let account = google::ServiceAccount::from_env(&config.sheets.credentials_env)?;
+                sheets::Spreadsheet::connect(&account, &spreadsheet_id)
+                    .await?
+                    .publish(&tabs)
+                    .await?;
+                println!(
+                    "Published {} cohort tabs to spreadsheet {spreadsheet_id}.",
+                    tabs.len()
+                );
+                return Ok(());
+            }
+
+            if all_cohorts {
+                let reports = db::cached(
+                    &pool,
+                    org_id,
+                    config.cache.ttl(),
+                    "cohort-reports",
+                    &scope,
+                    async {
+                        let signals = report::by_cohort(
+                            db::fetch_signals(&pool, &filter, page_size).await?,
+                            |signal| &signal.cohort,
+                        );
+                        let names: Vec<String> = signals.keys().cloned().collect();
+                        let mut trends = report::by_cohort(
+                            db::fetch_cohort_weekly_trends(&pool, &filter, &names).await?,
+                            |trend| &trend.cohort,
+                        );
+                        let starts = db::fetch_cohort_starts(&pool, org_id, &names).await?;
+                        let last_contacts = db::last_contacts(&pool, org_id).await?;
+                        let mut health = match health {
+                            true => Some(report::by_cohort(
+                                db::fetch_cohort_health(&pool, org_id, &[], health_since).await?,
+                                |point| &point.cohort,
+                            )),
+                            false => None,
+                        };
+                        let mut survival = match survival {
+                            Some(reference) => {
+                                let (roster, recorded, signals) = survival_inputs(
+                                    &pool,
+                                    org_id,
+                                    &[],
+                                    &[],
+                                    reference,
+                                    since_days,
+                                    page_size,
+                                )
+                                .await?;
+                                let mut recorded =
+                                    report::by_cohort(recorded, |outcome| &outcome.cohort);
+                                let mut signals =
+                                    report::by_cohort(signals, |signal| &signal.cohort);
+                                report::by_cohort(roster, |scholar| &scholar.cohort)
+                                    .into_iter()
+                                    .map(|(cohort, roster)| {
+                                        let study = survival::study(
+                                            &roster,
+                                            &recorded.remove(&cohort).unwrap_or_default(),
+                                            &signals.remove(&cohort).unwrap_or_default(),
+                                            reference,
+                                            today,
+                                            since_days,
+                                            &config.scoring,
+                                        );
+                                        (cohort, study)
+                                    })
+                                    .collect()
+                            }
+                            None => BTreeMap::new(),
+                        };
+
+                        let mut reports = BTreeMap::new();
+                        for (cohort, signals) in signals {
+                            let trends: Vec<models::SignalTrend> = trends
+                                .remove(&cohort)
+                                .unwrap_or_default()
+                                .into_iter()
+                                .map(|trend| models::SignalTrend {
+                                    week_start: trend.week_start,
+                                    signal_count: trend.signal_count,
+                                    avg_severity: trend.avg_severity,
+                                    scholar_count: trend.scholar_count,
+                                })
+                                .collect();
+                            let options = report::ReportOptions {
+                                cohort_start: starts.get(&cohort).copied(),
+                                last_contacts: Some(last_contacts.clone()),
+                                health: health
+                                    .as_mut()
+                                    .map(|health| health.remove(&cohort).unwrap_or_default()),
+                                survival: survival.remove(&cohort),
+                                ..options.clone()
+                            };
+                            let text = report::build_report(
+                                Some(&cohort),
+                                since_days,
+                                since_date,
+                                &signals,
+                                &trends,
+                                &options,
+                            );
+                            reports.insert(cohort, text);
+                        }
+                        Ok(reports)
+                    },
+                )
+                .await?;
+
+                if reports.is_empty() {
+                    println!("No signals found for this window.");
+                    return Ok(());
+                }
+                std::fs::create_dir_all(&out_dir)
+                    .with_context(|| format!("failed to create {}", out_dir.display()))?;
+                for (cohort, text) in &reports {
+                    let path = out_dir.join(report::report_file_name(cohort));
+                    std::fs::write(&path, text)
+                        .with_context(|| format!("failed to write {}", path.display()))?;
+                }
+                println!(
+                    "Wrote {} cohort reports to {}.",
+                    reports.len(),
+                    out_dir.display()
+                );
+                return Ok(());
+            }
+
+            let report = db::cached(&pool, org_id, config.cache.ttl(), "report", &scope, async {
+                let signals = db::fetch_signals(&pool, &filter, page_size).await?;
+                let trends = if cached_trends {
+                    db::fetch_cached_weekly_trends(&pool, &filter).await?
+                } else {
+                    db::fetch_weekly_trends(&pool, &filter).await?
+                };
+                // Program weeks only make sense when the report covers a single cohort.
+                let cohort_start = match cohort.as_slice() {
+                    [name] => db::fetch_cohort_starts(&pool, org_id, std::slice::from_ref(name))
+                        .await?
+                        .remove(name),
+                    _ => None,
+                };
+                let mut interventions = Vec::new();
+                for email in &email {
+                    interventions.extend(
+                        db::list_interventions(&pool, org_id, Some(email), None, 20).await?,
+                    );
+                }
+                let survival = match survival {
+                    Some(reference) => {
+                        let (roster, recorded, signals) = survival_inputs(
+                            &pool, org_id, &cohort, &email, reference, since_days, page_size,
+                        )
+                        .await?;
+                        Some(survival::study(
+                            &roster,
+                            &recorded,
+                            &signals,
+                            reference,
+                            today,
+                            since_days,
+                            &config.scoring,
+                        ))
+                    }
+                    None => None,
+                };
+                let label = match email.len() {
+                    1 if redact => Some("1 selected scholar".to_string()),
+                    count if redact && count > 0 => Some(format!("{count} selected scholars")),
+                    _ => [cohort.join(", "), email.join(", ")]
+                        .into_iter()
+                        .find(|label| !label.is_empty()),
+                };
+                Ok(report::build_report(
+                    label.as_deref().or(advisor.as_deref()),
+                    since_days,
+                    since_date,
+                    &signals,
+                    &trends,
+                    &report::ReportOptions {
+                        cohort_start,
+                        interventions,
+                        last_contacts: Some(db::last_contacts(&pool, org_id).await?),
+                        health: match health {
+                            true => Some(
+                                db::fetch_cohort_health(&pool, org_id, &cohort, health_since)
+                                    .await?,
+                            ),
+                            false => None,
+                        },
+                        survival,
+                        // Next steps only make sense when the report is about chosen scholars.
+                        playbooks: match email.is_empty() {
+                            true => Vec::new(),
+                            false => config.playbooks.clone(),
+                        },
+                        ..options
+                    },
+                ))
+            })
+            .await?;
+            std::fs::write(&out, report)?;
+            println!("Report written to {}.", out.display());
+        }
+        Commands::Trends {
+            cohort,
+            overlay,
+            align_to_cohort_start,
+            since_days,
+            out,
+            include_inactive,
+            include_archived,
+            cached_trends,
+        } => {
+            let org_id = db::resolve_org(&pool, &org).await?;
+            let since_date = match since_days {
+                Some(days) => risk::cutoff_date(days),
+                None if overlay.is_empty() => risk::cutoff_date(30),
+                // Overlays compare whole programs, so default to the full history.
+                None => chrono::NaiveDate::from_ymd_opt(1970, 1, 1).expect("valid date"),
+            };
+            let filter = db::SignalFilter {
+                org_id: Some(org_id),
+                cohorts: cohort.into_iter().collect(),
+                include_inactive,
+                include_archived,
+                ..db::SignalFilter::new(since_date)
+            };
+
+            if overlay.is_empty() {
+                let weekly = if cached_trends {
+                    db::fetch_cached_weekly_trends(&pool, &filter).await?
+                } else {
+                    db::fetch_weekly_trends(&pool, &filter).await?
+                };
+                if weekly.is_empty() {
+                    println!("No signals found for this window.");
+                    return Ok(());
+                }
+                let cohort_start = match filter.cohorts.as_slice() {
+                    [name] => db::fetch_cohort_starts(&pool, org_id, std::slice::from_ref(name))
+                        .await?
+                        .remove(name),
+                    _ => None,
+                };
+                for trend in weekly {
+                    let week = match cohort_start {
+                        Some(start) => format!(
+                            "{} (Program week {})",
+                            trend.week_start,
+                            trends::program_week(start, trend.week_start)
+                        ),
+                        None => trend.week_start.to_string(),
+                    };
+                    let calendar = &config.calendar;
+                    let season = match calendar.label(trend.week_start) {
+                        label if label.is_empty() => label,
+                        label => format!(
+                            ", {label} ({:.0} signals seasonally adjusted)",
+                            calendar.adjust(trend.week_start, trend.signal_count as f64)
+                        ),
+                    };
+                    println!(
+                        "- Week of {}: {} signals across {} scholars (avg severity {:.2}){season}",
+                        week, trend.signal_count, trend.scholar_count, trend.avg_severity
+                    );
+                }
+                return Ok(());
+            }
+
+            let cohort_trends = db::fetch_cohort_weekly_trends(&pool, &filter, &overlay).await?;
+            let starts = if align_to_cohort_start {
+                db::fetch_cohort_starts(&pool, org_id, &overlay).await?
+            } else {
+                Default::default()
+            };
+            let points = trends::overlay(&cohort_trends, &starts, align_to_cohort_start);
+
+            let mut writer = csv::Writer::from_path(&out)?;
+            for point in &points {
+                writer.serialize(point)?;
+            }
+            writer.flush()?;
+            println!(
+                "Wrote {} overlay points for {} cohorts to {}.",
+                points.len(),
+                overlay.len(),
+                out.display()
+            );
+        }
+        Commands::Export {
+            cohort,
+            since_days,
+            format,
+            out,
+            include_inactive,
+            include_archived,
+            min_severity,
+            page_size,
+            dataset,
+            follow_up_tier,
+            contact_days,
+            advisor,
+            #[cfg(feature = "privacy-export")]
+            research,
+            #[cfg(feature = "privacy-export")]
+            salt,
+        } => {
+            let org_id = db::resolve_org(&pool, &org).await?;
+            if format == export::ExportFormat::Ics {
+                if dataset != export::ExportDataset::Signals || include_archived {
+                    anyhow::bail!("--format ics writes follow-up reminders; drop --dataset and --include-archived");
+                }
+                #[cfg(feature = "privacy-export")]
+                if research {
+                    anyhow::bail!("--research doesn't apply to --format ics");
+                }
+                anyhow::ensure!(contact_days > 0, "--contact-days must be positive");
+                let filter = db::SignalFilter {
+                    org_id: Some(org_id),
+                    cohorts: cohort.into_iter().collect(),
+                    advisor,
+                    include_inactive,
+                    min_severity,
+                    ..db::SignalFilter::new(risk::cutoff_date(since_days))
+                };
+                let scores =
+                    db::score_scope(&pool, &filter, since_days, &config.scoring, page_size).await?;
+                let advisor_emails = db::scholar_advisor_emails(&pool, org_id).await?;
+                let options = ics::FollowUpOptions {
+                    min_tier: follow_up_tier,
+                    contact_days,
+                };
+                let now = chrono::Utc::now();
+                let follow_ups =
+                    ics::follow_ups(&scores, &advisor_emails, now.date_naive(), options);
+                let file = std::fs::File::create(&out)
+                    .with_context(|| format!("failed to create {}", out.display()))?;
+                let mut output = std::io::BufWriter::new(file);
+                ics::write_calendar(&mut output, &org, &follow_ups, now)?;
+                std::io::Write::flush(&mut output)?;
+                println!(
+                    "Exported {} follow-up reminders to {}.",
+                    follow_ups.len(),
+                    out.display()
+                );
+                return Ok(());
+            }
+            #[cfg(feature = "privacy-export")]
+            let salt = salt.filter(|_| research);
+            #[cfg(feature = "privacy-export")]
+            if salt.is_some() && dataset != export::ExportDataset::Signals {
+                anyhow::bail!("--research only applies to --dataset signals");
+            }
+            #[cfg(feature = "privacy-export")]
+            if salt
+                .as_ref()
+                .is_some_and(|salt| salt.len() < privacy::MIN_SALT_LEN)
+            {
+                anyhow::bail!(
+                    "--salt must be at least {} characters",
+                    privacy::MIN_SALT_LEN
+                );
+            }
+            let columns = dataset.columns();
+            #[cfg(feature = "privacy-export")]
+            let columns = match salt {
+                Some(_) => &privacy::RESEARCH_COLUMNS,
+                None => columns,
+            };
+            let file = std::fs::File::create(&out)
+                .with_context(|| format!("failed to create {}", out.display()))?;
+            let mut writer =
+                export::ExportWriter::with_columns(std::io::BufWriter::new(file), format, columns)?;
+            let exported = match dataset {
+                export::ExportDataset::Signals => {
+                    let filter = db::SignalFilter {
+                        org_id: Some(org_id),
+                        cohorts: cohort.into_iter().collect(),
+                        include_inactive,
+                        include_archived,
+                        min_severity,
+                        ..db::SignalFilter::new(risk::cutoff_date(since_days))
+                    };
+                    db::for_each_export_row(&pool, &filter, page_size, |row| {
+                        #[cfg(feature = "privacy-export")]
+                        if let Some(salt) = &salt {
+                            return writer.write(&privacy::research_row(&row, salt.as_bytes()));
+                        }
+                        writer.write(&row)
+                    })
+                    .await?
+                }
+                export::ExportDataset::Scholars => {
+                    let filter = db::ScholarFilter {
+                        cohort,
+                        email: None,
+                        status: (!include_inactive).then_some(ScholarStatus::Active),
+                        advisor: None,
+                        include_deleted: false,
+                    };
+                    let scholars = db::list_scholars(&pool, org_id, &filter).await?;
+                    for scholar in &scholars {
+                        writer.write(scholar)?;
+                    }
+                    scholars.len() as u64
+                }
+                export::ExportDataset::Snapshots => {
+                    let since = chrono::Utc::now() - chrono::Duration::days(since_days);
+                    db::for_each_snapshot_row(&pool, org_id, since, cohort.as_deref(), |row| {
+                        writer.write(&row)
+                    })
+                    .await?
+                }
+            };
+            writer.finish()?;
+            let rows = match dataset {
+                export::ExportDataset::Signals => "signals",
+                export::ExportDataset::Scholars => "scholars",
+                export::ExportDataset::Snapshots => "score snapshots",
+            };
+            println!("Exported {exported} {rows} to {}.", out.display());
+        }
+        #[cfg(feature = "bigquery")]
+        Commands::Sync {
+            action:
+                SyncAction::Bigquery {
+                    dataset,
+                    page_size,
+                    dry_run,
+                },
+        } => {
+            let org_id = db::resolve_org(&pool, &org).await?;
+            let summary = bigquery::sync(
+                &pool,
+                org_id,
+                &config.bigquery,
+                &dataset,
+                page_size,
+                dry_run,
+            )
+            .await?;
+            println!(
+                "{} {} signals and {} score snapshots to BigQuery dataset {dataset}.",
+                match dry_run {
+                    true => "Would sync",
+                    false => "Synced",
+                },
+                summary.signals,
+                summary.snapshots
+            );
+        }
+        Commands::Sync {
+            action: SyncAction::Airtable { cohort, since_days },
+        } => {
+            let airtable = config
+                .airtable
+                .as_ref()
+                .context("add an [airtable] section with the base_id to the config")?;
+            let since_days = since_days.unwrap_or(config.defaults.since_days);
+            let org_id = db::resolve_org(&pool, &org).await?;
+            let filter = db::SignalFilter {
+                org_id: Some(org_id),
+                cohorts: cohort.into_iter().collect(),
+                ..db::SignalFilter::new(risk::cutoff_date(since_days))
+            };
+            let scores = db::score_scope(
+                &pool,
+                &filter,
+                since_days,
+                &config.scoring,
+                db::DEFAULT_PAGE_SIZE,
+            )
+            .await?;
+            let summary = airtable::sync(&pool, org_id, airtable, &scores).await?;
+            println!(
+                "Recorded {} follow-up statuses as interventions and wrote {} scholars to Airtable \
+                 table {}.",
+                summary.recorded, summary.pushed, airtable.table
+            );
+            for skipped in &summary.skipped {
+                eprintln!("Skipped {skipped}");
+            }
+        }
+        Commands::Search {
+            query,
+            cohort,
+            since_days,
+            limit,
+            include_inactive,
+            include_archived,
+        } => {
+            let filter = db::SignalFilter {
+                org_id: Some(db::resolve_org(&pool, &org).await?),
+                cohorts: cohort.into_iter().collect(),
+                include_inactive,
+                include_archived,
+                ..db::SignalFilter::new(risk::cutoff_date(since_days))
+            };
+            let hits = db::search_signals(&pool, &filter, &query, limit).await?;
+            if hits.is_empty() {
+                println!("No notes in the last {since_days} days match {query:?}.");
+                return Ok(());
+            }
+
+            for hit in &hits {
+                println!(
+                    "- {} ({}, {}) {} severity {} on {} [{}]: {}",
+                    hit.scholar_name,
+                    hit.scholar_email,
+                    hit.cohort,
+                    hit.signal_type,
+                    hit.severity,
+                    hit.occurred_at,
+                    hit.source_key,
+                    hit.snippet
+                );
+            }
+            let scholars: std::collections::HashSet<&str> =
+                hits.iter().map(|hit| hit.scholar_email.as_str()).collect();
+            println!(
+                "{} matching signals across {} scholars{}.",
+                hits.len(),
+                scholars.len(),
+                if hits.len() as i64 == limit {
+                    " (limit reached; raise --limit for more)"
+                } else {
+                    ""
+                }
+            );
+        }
+        Commands::Raters {
+            cohort,
+            since_days,
+            min_signals,
+            threshold,
+            flagged_only,
+        } => {
+            let filter = db::SignalFilter {
+                org_id: Some(db::resolve_org(&pool, &org).await?),
+                cohorts: cohort.into_iter().collect(),
+                include_inactive: true,
+                ..db::SignalFilter::new(risk::cutoff_date(since_days))
+            };
+            let signals = db::fetch_rated_signals(&pool, &filter).await?;
+            let options = raters::RaterOptions {
+                min_signals,
+                z_threshold: threshold,
+            };
+            let comparisons = raters::compare_raters(&signals, &options);
+            if comparisons.is_empty() {
+                println!("No signal types were rated by more than one submitter in this window.");
+                return Ok(());
+            }
+
+            let mut current_type = None;
+            for comparison in comparisons
+                .iter()
+                .filter(|comparison| comparison.flagged || !flagged_only)
+            {
+                if current_type != Some(&comparison.signal_type) {
+                    println!("{}:", comparison.signal_type);
+                    current_type = Some(&comparison.signal_type);
+                }
+                println!(
+                    "  - {}: mean {:.2} vs peers {:.2} across {} signals (z {}){}",
+                    comparison.submitted_by,
+                    comparison.mean_severity,
+                    comparison.peer_mean,
+                    comparison.signal_count,
+                    comparison
+                        .z_score
+                        .map(|z| format!("{z:+.2}"))
+                        .unwrap_or_else(|| "n/a".to_string()),
+                    if comparison.flagged { " FLAGGED" } else { "" }
+                );
+            }
+        }
+        Commands::Notify {
+            channels,
+            threshold,
+            since_days,
+            dry_run,
+        } => {
+            let channels = if channels.is_empty() {
+                config.watch.channels.clone()
+            } else {
+                channels
+            };
+            anyhow::ensure!(
+                !channels.is_empty(),
+                "pass --channel or set [watch] channels in the config"
+            );
+            for name in &channels {
+                config.channel(name)?;
+            }
+
+            let org_id = db::resolve_org(&pool, &org).await?;
+            let since_days = since_days.unwrap_or(config.watch.since_days);
+            let scores = watch::score_org(&pool, org_id, since_days, &config.scoring).await?;
+            let alerts: Vec<_> = scores
+                .into_iter()
+                .filter(|score| match threshold {
+                    Some(threshold) => score.score >= threshold,
+                    None => score.tier >= config.watch.alert_tier,
+                })
+                .collect();
+            if alerts.is_empty() {
+                println!("No scholars above the threshold; nothing sent.");
+                return Ok(());
+            }
+            if dry_run {
+                println!(
+                    "{}",
+                    notify::alert_text(&org, notify::AlertReason::Tier, &alerts)
+                );
+                println!("Would send to {}.", channels.join(", "));
+                return Ok(());
+            }
+
+            let signals = notify::contributing_signals(&pool, org_id, since_days, &alerts).await?;
+            let payload = notify::AlertPayload::new(
+                &org,
+                notify::AlertReason::Tier,
+                &alerts,
+                &signals,
+                &config.playbooks,
+            );
+            let deliveries = notify::dispatch(&pool, org_id, &config, &channels, &payload).await?;
+            let mut failed = 0;
+            for (channel, result) in &deliveries {
+                match result {
+                    Ok(()) => println!("Sent {} alerts to {channel}.", alerts.len()),
+                    Err(err) => {
+                        failed += 1;
+                        eprintln!("Failed to send to {channel}: {err:#}");
+                    }
+                }
+            }
+            anyhow::ensure!(
+                failed == 0,
+                "{failed} of {} channels failed",
+                deliveries.len()
+            );
+        }
+        #[cfg(feature = "consume")]
+        Commands::Consume { once } => {
+            let org_id = db::resolve_org(&pool, &org).await?;
+            let summary = consume::run(&pool, org_id, &config, once).await?;
+            println!(
+                "Recorded {} events ({} already recorded, {} rejected).",
+                summary.recorded, summary.duplicates, summary.rejected
+            );
+        }
+        Commands::Watch {
+            once,
+            #[cfg(feature = "server")]
+            health_bind,
+        } => {
+            #[cfg(feature = "server")]
+            if let Some(addr) = health_bind {
+                server::spawn_probes(pool.clone(), addr).await?;
+            }
+            watch::run(&pool, &org, &config, once).await?;
+        }
+        #[cfg(feature = "server")]
+        Commands::Serve { bind } => {
+            let org_id = db::resolve_org(&pool, &org).await?;
+            let scoring = std::sync::Arc::new(config.scoring.clone());
+            let (events, _) = tokio::sync::broadcast::channel(events::BUFFER);
+            let relay = events::relay(
+                pool.clone(),
+                org_id,
+                scoring.clone(),
+                config.watch.since_days,
+                events.clone(),
+            );
+            tokio::spawn(async move {
+                if let Err(err) = relay.await {
+                    tracing::error!(error = format!("{err:#}"), "/events stopped");
+                }
+            });
+            let state = server::AppState {
+                pool: pool.clone(),
+                org_id,
+                scoring,
+                limits: config.import_limits(),
+                token: std::env::var(server::API_TOKEN_ENV)
+                    .ok()
+                    .filter(|token| !token.is_empty())
+                    .map(std::sync::Arc::from),
+                api_keys: apikeys::any_active(&pool, org_id).await?,
+                events,
+                api: std::sync::Arc::new(config.api.clone()),
+                report: std::sync::Arc::new(config.report.clone()),
+                calendar: std::sync::Arc::new(config.calendar.clone()),
+                playbooks: config.playbooks.clone().into(),
+                rate_limiter: std::sync::Arc::default(),
+                cache_ttl: config.cache.ttl(),
+            };
+            server::serve(state, bind).await?;
+        }
+        #[cfg(feature = "server")]
+        Commands::ApiKey {
+            action:
+                ApiKeyAction::Create {
+                    name,
+                    role,
+                    advisor,
+                    scope,
+                },
+        } => {
+            let org_id = db::resolve_org(&pool, &org).await?;
+            let (role, scope) = apikeys::role_and_scope(role, scope)?;
+            let key =
+                apikeys::create(&pool, org_id, &name, scope, role, advisor.as_deref()).await?;
+            let granted = match &advisor {
+                Some(advisor) => format!("{} {} for {advisor}", role.as_str(), scope.as_str()),
+                None => format!("{} {}", role.as_str(), scope.as_str()),
+            };
+            audit::record(
+                &pool,
+                "api-key create",
+                AuditAction::Insert,
+                "api_keys",
+                1,
+                &format!("{name} ({granted})"),
+            )
+            .await?;
+            println!("Created {granted} key {name}. It won't be shown again:");
+            println!("{key}");
+        }
+        #[cfg(feature = "server")]
+        Commands::ApiKey {
+            action: ApiKeyAction::List,
+        } => {
+            let org_id = db::resolve_org(&pool, &org).await?;
+            let keys = apikeys::list(&pool, org_id).await?;
+            if keys.is_empty() {
+                println!("No API keys.");
+                return Ok(());
+            }
+            for key in keys {
+                let granted = match &key.advisor {
+                    Some(advisor) => {
+                        format!("{} {} for {advisor}", key.role.as_str(), key.scope.as_str())
+                    }
+                    None => format!("{} {}", key.role.as_str(), key.scope.as_str()),
+                };
+                println!(
+                    "- {} ({granted}), created {}, {}",
+                    key.name,
+                    key.created_at.format("%Y-%m-%d"),
+                    match key.last_used_at {
+                        Some(used) => format!("last used {}", used.format("%Y-%m-%d %H:%M")),
+                        None => "never used".to_string(),
+                    }
+                );
+            }
+        }
+        #[cfg(feature = "server")]
+        Commands::ApiKey {
+            action: ApiKeyAction::Revoke { name },
+        } => {
+            let org_id = db::resolve_org(&pool, &org).await?;
+            let revoked = apikeys::revoke(&pool, org_id, &name).await?;
+            anyhow::ensure!(revoked > 0, "no live API key named {name}");
+            audit::record(
+                &pool,
+                "api-key revoke",
+                AuditAction::Update,
+                "api_keys",
+                revoked,
+                &name,
+            )
+            .await?;
+            println!("Revoked API key {name}.");
+        }
+        Commands::Stats => {
+            let org_id = db::resolve_org(&pool, &org).await?;
+            let stats = db::database_stats(&pool, org_id).await?;
+            if stats.cohorts.is_empty() {
+                println!("No scholars recorded.");
+                return Ok(());
+            }
+
+            println!("Scholars by cohort:");
+            for (cohort, scholars, active) in &stats.cohorts {
+                println!("- {cohort}: {scholars} ({active} active)");
+            }
+            println!("Signals by type:");
+            for (signal_type, signals) in &stats.signal_types {
+                println!("- {signal_type}: {signals}");
+            }
+            match (stats.first_occurred_at, stats.last_occurred_at) {
+                (Some(first), Some(last)) => {
+                    println!("{} signals dated {first} to {last}.", stats.signal_count)
+                }
+                _ => println!("No signals recorded."),
+            }
+            for (days, index) in [(7, 0), (30, 1)] {
+                println!(
+                    "Added in the last {days} days: {} scholars, {} signals",
+                    stats.scholars_added[index], stats.signals_added[index]
+                );
+            }
+        }
+        Commands::RefreshTrends => {
+            db::refresh_weekly_trends(&pool).await?;
+            println!("Weekly trend cache refreshed.");
+        }
+        Commands::RefreshHealth {
+            cohort,
+            weeks,
+            since_days,
+        } => {
+            let since_days = since_days.unwrap_or(config.defaults.since_days);
+            let org_id = db::resolve_org(&pool, &org).await?;
+            let last_week =
+                trends::week_start(chrono::Utc::now().date_naive()) - chrono::Duration::weeks(1);
+            let first_week = last_week - chrono::Duration::weeks(i64::from(weeks) - 1);
+            let cohorts: Vec<(String, i64)> = db::database_stats(&pool, org_id)
+                .await?
+                .cohorts
+                .into_iter()
+                .filter(|(name, _, active)| {
+                    *active > 0 && cohort.as_ref().is_none_or(|only| only == name)
+                })
+                .map(|(name, _, active)| (name, active))
+                .collect();
+            if cohorts.is_empty() {
+                anyhow::bail!("no cohort with active scholars to compute a health index for");
+            }
+            let names: Vec<String> = cohorts.iter().map(|(name, _)| name.clone()).collect();
+            let starts = db::fetch_cohort_starts(&pool, org_id, &names).await?;
+            let filter = db::SignalFilter {
+                org_id: Some(org_id),
+                cohorts: cohort.clone().into_iter().collect(),
+                ..db::SignalFilter::new(first_week - chrono::Duration::days(since_days))
+            };
+            let signals = db::fetch_signals(&pool, &filter, db::DEFAULT_PAGE_SIZE).await?;
+
+            let mut points = Vec::new();
+            for (name, active) in &cohorts {
+                let start = starts.get(name).map_or(first_week, |start| {
+                    trends::week_start(*start).max(first_week)
+                });
+                let weeks: Vec<chrono::NaiveDate> = std::iter::successors(Some(start), |week| {
+                    Some(*week + chrono::Duration::weeks(1))
+                })
+                .take_while(|week| *week <= last_week)
+                .collect();
+                points.extend(health::weekly(
+                    name,
+                    &signals,
+                    *active,
+                    &weeks,
+                    since_days,
+                    &config.scoring,
+                ));
+            }
+            db::save_cohort_health(&pool, org_id, &points).await?;
+            println!(
+                "Stored {} weekly health points for {} cohorts, through the week of {last_week}.",
+                points.len(),
+                cohorts.len()
+            );
+        }
+        Commands::History { email, runs } => {
+            let org_id = db::resolve_org(&pool, &org).await?;
+            let (name, cohort) = db::scholar_profile(&pool, org_id, &email)
+                .await?
+                .with_context(|| format!("no scholar found with email {email}"))?;
+            let points = db::score_history(&pool, org_id, &email, runs).await?;
+            let (Some(first), Some(last)) = (points.first(), points.last()) else {
+                println!("No scoring runs saved since {email} joined; `watch` saves one per run.");
+                return Ok(());
+            };
+
+            println!(
+                "Score history for {name} ({email}, {cohort}) over the last {} runs:",
+                points.len()
+            );
+            let max = points.iter().map(|point| point.score).fold(0.0, f64::max);
+            for point in &points {
+                println!(
+                    "{}  {:>6.2} {:<8}  {:<30}  {} signals ({})",
+                    point.ran_at.format("%Y-%m-%d %H:%M"),
+                    point.score,
+                    point.tier.as_str(),
+                    score_bar(point.score, max, 30),
+                    point.signal_count,
+                    point.trigger
+                );
+            }
+            if points.len() > 1 {
+                println!(
+                    "Change since {}: {:+.2} ({} -> {}).",
+                    first.ran_at.format("%Y-%m-%d"),
+                    last.score - first.score,
+                    first.tier.as_str(),
+                    last.tier.as_str()
+                );
+            }
+        }
+        Commands::Movers { since_days, limit } => {
+            let org_id = db::resolve_org(&pool, &org).await?;
+            let latest = db::score_run_before(&pool, org_id, chrono::Utc::now())
+                .await?
+                .context("no scoring runs saved yet; `watch` saves one per run")?;
+            let before = match since_days {
+                Some(days) => chrono::Utc::now() - chrono::Duration::days(days),
+                None => latest.ran_at,
+            };
+            let baseline = db::score_run_before(&pool, org_id, before)
+                .await?
+                .context("no earlier scoring run to compare against")?;
+
+            let (increases, decreases) = movers::movers(
+                &db::run_snapshot(&pool, baseline.id).await?,
+                &db::run_snapshot(&pool, latest.id).await?,
+            );
+            println!(
+                "Score changes from {} to {}:",
+                baseline.ran_at.format("%Y-%m-%d %H:%M"),
+                latest.ran_at.format("%Y-%m-%d %H:%M")
+            );
+            for (heading, movers) in [
+                ("Largest increases", &increases),
+                ("Largest decreases", &decreases),
+            ] {
+                println!("{heading}:");
+                if movers.is_empty() {
+                    println!("- none");
+                }
+                for mover in movers.iter().take(limit) {
+                    println!(
+                        "- {} ({}, {}) {:.2} -> {:.2} ({:+.2}) {} -> {}",
+                        mover.scholar_name,
+                        mover.scholar_email,
+                        mover.cohort,
+                        mover.before,
+                        mover.after,
+                        mover.change(),
+                        mover.tier_before.as_str(),
+                        mover.tier_after.as_str()
+                    );
+                }
+            }
+        }
+        Commands::Compare {
+            cohorts,
+            since_days,
+        } => {
+            let since_days = since_days.unwrap_or(config.defaults.since_days);
+            let org_id = db::resolve_org(&pool, &org).await?;
+            let summaries =
+                compare::summarize(&pool, org_id, &cohorts, since_days, &config.scoring).await?;
+            println!("{}", compare::render(&summaries, since_days));
+        }
+        Commands::Analytics {
+            action:
+                AnalyticsAction::Clusters {
+                    cohort,
+                    since_days,
+                    groups,
+                    json,
+                },
+        } => {
+            let since_days = since_days.unwrap_or(config.defaults.since_days);
+            let org_id = db::resolve_org(&pool, &org).await?;
+            let filter = db::SignalFilter {
+                org_id: Some(org_id),
+                cohorts: cohort.into_iter().collect(),
+                ..db::SignalFilter::new(risk::cutoff_date(since_days))
+            };
+            let signals = db::fetch_signals(&pool, &filter, db::DEFAULT_PAGE_SIZE).await?;
+            let clusters = clusters::cluster(&signals, usize::from(groups));
+            if json {
+                println!("{}", serde_json::to_string_pretty(&clusters)?);
+            } else if clusters.is_empty() {
+                println!("No signals found for this window.");
+            } else {
+                let scores = risk::score_signals_with(
+                    &signals,
+                    since_days,
+                    chrono::Utc::now().date_naive(),
+                    &config.scoring,
+                );
+                println!(
+                    "{}",
+                    analytics::render_clusters(&clusters, &scores, since_days)
+                );
+            }
+        }
+        Commands::Forecast {
+            cohorts,
+            weeks,
+            history_weeks,
+            method,
+            window,
+            json,
+        } => {
+            let org_id = db::resolve_org(&pool, &org).await?;
+            let cohorts = if cohorts.is_empty() {
+                db::database_stats(&pool, org_id)
+                    .await?
+                    .cohorts
+                    .into_iter()
+                    .filter(|(_, _, active)| *active > 0)
+                    .map(|(cohort, _, _)| cohort)
+                    .collect()
+            } else {
+                cohorts
+            };
+            let today = chrono::Utc::now().date_naive();
+            let filter = db::SignalFilter {
+                org_id: Some(org_id),
+                ..db::SignalFilter::new(
+                    trends::week_start(today) - chrono::Duration::weeks(i64::from(history_weeks)),
+                )
+            };
+            let weekly = db::fetch_cohort_weekly_trends(&pool, &filter, &cohorts).await?;
+            let forecasts = forecast::forecast(
+                &weekly,
+                &cohorts,
+                today,
+                usize::from(history_weeks),
+                method,
+                usize::from(window),
+                usize::from(weeks),
+            );
+            if json {
+                println!("{}", serde_json::to_string_pretty(&forecasts)?);
+            } else if forecasts.is_empty() {
+                println!("No cohorts with active scholars.");
+            } else {
+                println!(
+                    "{}",
+                    forecast::render(&forecasts, method, usize::from(history_weeks))
+                );
+            }
+        }
+        Commands::Analytics {
+            action:
+                AnalyticsAction::Cohorts {
+                    since_days,
+                    at_risk_tier,
+                    rank_by,
+                },
+        } => {
+            let since_days = since_days.unwrap_or(config.defaults.since_days);
+            let org_id = db::resolve_org(&pool, &org).await?;
+            let mut cohorts =
+                analytics::summarize(&pool, org_id, since_days, at_risk_tier, &config.scoring)
+                    .await?;
+            if cohorts.is_empty() {
+                println!("No cohorts with active scholars.");
+                return Ok(());
+            }
+            analytics::rank(&mut cohorts, rank_by);
+            println!(
+                "{}",
+                analytics::render(&cohorts, rank_by, at_risk_tier, since_days)
+            );
+        }
+        Commands::Analytics {
+            action:
+                AnalyticsAction::Caseloads {
+                    cohort,
+                    since_days,
+                    at_risk_tier,
+                },
+        } => {
+            let since_days = since_days.unwrap_or(config.defaults.since_days);
+            let org_id = db::resolve_org(&pool, &org).await?;
+            let roster = db::list_scholars(
+                &pool,
+                org_id,
+                &db::ScholarFilter {
+                    cohort: cohort.clone(),
+                    email: None,
+                    status: Some(ScholarStatus::Active),
+                    advisor: None,
+                    include_deleted: false,
+                },
+            )
+            .await?;
+            if roster.is_empty() {
+                println!("No active scholars.");
+                return Ok(());
+            }
+            let filter = db::SignalFilter {
+                org_id: Some(org_id),
+                cohorts: cohort.into_iter().collect(),
+                ..db::SignalFilter::new(risk::cutoff_date(since_days))
+            };
+            let scores = db::score_scope(
+                &pool,
+                &filter,
+                since_days,
+                &config.scoring,
+                db::DEFAULT_PAGE_SIZE,
+            )
+            .await?;
+            let caseloads = analytics::caseloads(&roster, &scores, at_risk_tier);
+            println!(
+                "{}",
+                analytics::render_caseloads(&caseloads, at_risk_tier, since_days)
+            );
+        }
+        Commands::Analytics {
+            action:
+                AnalyticsAction::Outcomes {
+                    cohort,
+                    lead_days,
+                    since_days,
+                    at_risk_tier,
+                },
+        } => {
+            let since_days = since_days.unwrap_or(config.defaults.since_days);
+            let org_id = db::resolve_org(&pool, &org).await?;
+            let recorded =
+                db::list_outcomes(&pool, org_id, None, cohort.as_deref(), i64::MAX).await?;
+            let Some(earliest) = recorded.iter().map(|outcome| outcome.occurred_on).min() else {
+                println!("No outcomes recorded.");
+                return Ok(());
+            };
+            // Outcomes end scholars' time in the program, so their signals are usually archived
+            // or belong to inactive scholars by now.
+            let filter = db::SignalFilter {
+                org_id: Some(org_id),
+                cohorts: cohort.into_iter().collect(),
+                include_inactive: true,
+                include_archived: true,
+                ..db::SignalFilter::new(earliest - chrono::Duration::days(lead_days + since_days))
+            };
+            let signals = db::fetch_signals(&pool, &filter, db::DEFAULT_PAGE_SIZE).await?;
+            let assessed =
+                outcomes::assess(&recorded, &signals, lead_days, since_days, &config.scoring);
+            println!(
+                "{}",
+                outcomes::render(&assessed, at_risk_tier, lead_days, since_days)
+            );
+        }
+        Commands::Evaluate {
+            cohort,
+            horizon_weeks,
+            since_days,
+            at_risk_tier,
+        } => {
+            let since_days = since_days.unwrap_or(config.defaults.since_days);
+            let org_id = db::resolve_org(&pool, &org).await?;
+            let recorded =
+                db::list_outcomes(&pool, org_id, None, cohort.as_deref(), i64::MAX).await?;
+            let horizon_weeks = i64::from(horizon_weeks);
+            let Some(earliest) = recorded
+                .iter()
+                .filter(|outcome| outcome.outcome_type != OutcomeType::Gpa)
+                .map(|outcome| outcome.occurred_on)
+                .min()
+            else {
+                println!("No withdrawals or graduations recorded.");
+                return Ok(());
+            };
+            let filter = db::SignalFilter {
+                org_id: Some(org_id),
+                cohorts: cohort.into_iter().collect(),
+                include_inactive: true,
+                include_archived: true,
+                ..db::SignalFilter::new(
+                    earliest
+                        - chrono::Duration::weeks(horizon_weeks)
+                        - chrono::Duration::days(since_days),
+                )
+            };
+            let signals = db::fetch_signals(&pool, &filter, db::DEFAULT_PAGE_SIZE).await?;
+            let evaluated = evaluate::evaluate(
+                &recorded,
+                &signals,
+                horizon_weeks,
+                since_days,
+                at_risk_tier,
+                &config.scoring,
+            );
+            println!(
+                "{}",
+                evaluate::render(&evaluated, at_risk_tier, horizon_weeks, since_days)
+            );
+        }
+        Commands::Validate => {
+            let org_id = db::resolve_org(&pool, &org).await?;
+            let findings = validate::scan(
+                &pool,
+                org_id,
+                chrono::Utc::now().date_naive(),
+                config.severity,
+            )
+            .await?;
+            if findings.is_empty() {
+                println!("No problems found.");
+                return Ok(());
+            }
+            for check in findings.chunk_by(|a, b| a.check == b.check) {
+                println!("{} ({}):", check[0].check.as_str(), check.len());
+                for finding in check {
+                    println!("- {finding}");
+                }
+            }
+            anyhow::bail!("{} problems found", findings.len());
+        }
+        Commands::Archive {
+            older_than,
+            dry_run,
+        } => {
+            let org_id = db::resolve_org(&pool, &org).await?;
+            let before = chrono::Utc::now().date_naive() - chrono::Duration::days(older_than);
+            if dry_run {
+                let count = db::count_archivable_signals(&pool, org_id, before).await?;
+                println!("Would archive {count} signals from before {before}.");
+                return Ok(());
+            }
+
+            let archived = db::archive_signals(&pool, org_id, before).await?;
+            audit::record(
+                &pool,
+                "archive",
+                AuditAction::Delete,
+                "signals",
+                archived,
+                &format!("older than {before}"),
+            )
+            .await?;
+            println!("Archived {archived} signals from before {before}.");
+        }
+        Commands::Notes {
+            action: NotesAction::Keygen,
+        } => unreachable!("notes keygen runs before connecting to DATABASE_URL"),
+        Commands::Notes { action } => {
+            let key = notes::key().with_context(|| {
+                format!(
+                    "set {} or [notes] key_command to the note key",
+                    config.notes.key_env
+                )
+            })?;
+            let org_id = db::resolve_org(&pool, &org).await?;
+            let (command, done, changed) = match action {
+                NotesAction::Encrypt => (
+                    "notes encrypt",
+                    "Encrypted",
+                    db::rewrite_notes(&pool, org_id, |note| Ok(key.reseal(note))).await?,
+                ),
+                NotesAction::Decrypt => (
+                    "notes decrypt",
+                    "Decrypted",
+                    db::rewrite_notes(&pool, org_id, |note| key.open(note)).await?,
+                ),
+                NotesAction::Keygen => unreachable!("handled above"),
+            };
+            audit::record(
+                &pool,
+                command,
+                AuditAction::Update,
+                "signals",
+                changed,
+                "hot, archived, and overflowed notes",
+            )
+            .await?;
+            let notes = match changed {
+                1 => "note",
+                _ => "notes",
+            };
+            println!("{done} {changed} {notes}.");
+        }
+        Commands::Enrich { all, page_size } => {
+            let org_id = db::resolve_org(&pool, &org).await?;
+            let enriched = db::enrich_signals(&pool, org_id, all, page_size, |note| {
+                enrich::enrich_note(note, &config.enrichment)
+            })
+            .await?;
+            audit::record(
+                &pool,
+                "enrich",
+                AuditAction::Update,
+                "signals",
+                enriched,
+                if all {
+                    "every note"
+                } else {
+                    "notes not enriched yet"
+                },
+            )
+            .await?;
+            let signals = match enriched {
+                1 => "signal",
+                _ => "signals",
+            };
+            println!("Enriched the notes of {enriched} {signals}.");
+        }
+        Commands::Purge {
+            older_than,
+            mode,
+            dry_run,
+        } => {
+            let plan = config
+                .retention
+                .plan(chrono::Utc::now().date_naive(), older_than, mode)?;
+            if plan.is_empty() {
+                anyhow::bail!(
+                    "pass --older-than or set [retention] max_age in the config, e.g. max_age = \"3y\""
+                );
+            }
+            let org_id = db::resolve_org(&pool, &org).await?;
+            let summary = retention::enforce(&pool, org_id, &plan, "purge", dry_run).await?;
+
+            println!(
+                "{}",
+                match dry_run {
+                    true => "Would purge:",
+                    false => "Purged:",
+                }
+            );
+            for line in retention::summary_lines(&plan, &summary) {
+                println!("- {line}");
+            }
+        }
+        Commands::Cohort {
+            action: CohortAction::SetStart { cohort, date },
+        } => {
+            let org_id = db::resolve_org(&pool, &org).await?;
+            db::set_cohort_start(&pool, org_id, &cohort, date).await?;
+            audit::record(
+                &pool,
+                "cohort set-start",
+                AuditAction::Update,
+                "cohorts",
+                1,
+                &format!("{cohort} -> {date}"),
+            )
+            .await?;
+            println!("Cohort {cohort} starts {date}.");
+        }
+        Commands::Cohort {
+            action:
+                CohortAction::Aliases {
+                    action: CohortAliasAction::List,
+                },
+        } => {
+            let org_id = db::resolve_org(&pool, &org).await?;
+            let aliases = db::cohort_aliases(&pool, org_id).await?;
+            if aliases.is_empty() {
+                println!("No cohort aliases.");
+            }
+            for (alias, cohort) in aliases.iter() {
+                println!("{alias} -> {cohort}");
+            }
+        }
+        Commands::Cohort {
+            action:
+                CohortAction::Aliases {
+                    action: CohortAliasAction::Add { alias, cohort },
+                },
+        } => {
+            let org_id = db::resolve_org(&pool, &org).await?;
+            let moved = db::add_cohort_alias(&pool, org_id, &alias, &cohort).await?;
+            audit::record(
+                &pool,
+                "cohort aliases add",
+                AuditAction::Insert,
+                "cohort_aliases",
+                moved,
+                &format!("{alias} -> {cohort}"),
+            )
+            .await?;
+            println!("{alias} now means cohort {cohort}; moved {moved} scholars into it.");
+        }
+        Commands::Cohort {
+            action:
+                CohortAction::Aliases {
+                    action: CohortAliasAction::Remove { alias },
+                },
+        } => {
+            let org_id = db::resolve_org(&pool, &org).await?;
+            if !db::remove_cohort_alias(&pool, org_id, &alias).await? {
+                anyhow::bail!("{alias} is not a cohort alias");
+            }
+            audit::record(
+                &pool,
+                "cohort aliases remove",
+                AuditAction::Delete,
+                "cohort_aliases",
+                1,
+                &alias,
+            )
+            .await?;
+            println!("Removed cohort alias {alias}.");
+        }
+        Commands::Selftest { keep_data } => {
+            let cohort = selftest::run(&pool, &org, keep_data).await?;
+            audit::record(
+                &pool,
+                "selftest",
+                AuditAction::Insert,
+                "signals",
+                0,
+                &format!("cohort {cohort}"),
+            )
+            .await?;
+        }
+        Commands::Scholar {
+            action:
+                ScholarAction::Add {
+                    email,
+                    name,
+                    cohort,
+                },
+        } => {
+            let email = import::parse_email(&email)?;
+            let org_id = db::resolve_org(&pool, &org).await?;
+            db::add_scholar(&pool, org_id, &email, &name, &cohort).await?;
+            audit::record(
+                &pool,
+                "scholar add",
+                AuditAction::Insert,
+                "scholars",
+                1,
+                &format!("{email} in {cohort}"),
+            )
+            .await?;
+            println!("Added {name} ({email}) to cohort {cohort}.");
+        }
+        Commands::Scholar {
+            action:
+                ScholarAction::List {
+                    cohort,
+                    status,
+                    include_deleted,
+                    json,
+                },
+        } => {
+            let org_id = db::resolve_org(&pool, &org).await?;
+            let filter = db::ScholarFilter {
+                cohort,
+                status,
+                include_deleted,
+                email: None,
+                advisor: None,
+            };
+            let scholars = db::list_scholars(&pool, org_id, &filter).await?;
+            if json {
+                println!("{}", serde_json::to_string_pretty(&scholars)?);
+                return Ok(());
+            }
+            if scholars.is_empty() {
+                println!("No scholars match.");
+                return Ok(());
+            }
+
+            for scholar in scholars {
+                let advisor = scholar
+                    .advisor
+                    .map(|name| format!(", advisor {name}"))
+                    .unwrap_or_default();
+                let deleted = if scholar.deleted { " [deleted]" } else { "" };
+                println!(
+                    "- {} ({}, {}) {}{advisor}{deleted}",
+                    scholar.full_name, scholar.email, scholar.cohort, scholar.status
+                );
+            }
+        }
+        Commands::Scholar {
+            action: ScholarAction::Show { email, limit, json },
+        } => {
+            let org_id = db::resolve_org(&pool, &org).await?;
+            let profile = profile::load(&pool, org_id, &email, &config).await?;
+            if json {
+                println!("{}", serde_json::to_string_pretty(&profile)?);
+            } else {
+                println!(
+                    "{}",
+                    profile::render(&profile, limit, chrono::Utc::now().date_naive())
+                );
+            }
+        }
+        Commands::Scholar {
+            action:
+                ScholarAction::Update {
+                    email,
+                    name,
+                    cohort,
+                    new_email,
+                },
+        } => {
+            if name.is_none() && cohort.is_none() && new_email.is_none() {
+                anyhow::bail!("nothing to update; pass --name, --cohort, or --new-email");
+            }
+            let new_email = new_email.as_deref().map(import::parse_email).transpose()?;
+            let org_id = db::resolve_org(&pool, &org).await?;
+            db::update_scholar(
+                &pool,
+                org_id,
+                &email,
+                name.as_deref(),
+                cohort.as_deref(),
+                new_email.as_deref(),
+            )
+            .await?;
+            let changes: Vec<String> = [
+                name.map(|name| format!("name {name}")),
+                cohort.map(|cohort| format!("cohort {cohort}")),
+                new_email.map(|new_email| format!("email {new_email}")),
+            ]
+            .into_iter()
+            .flatten()
+            .collect();
+            audit::record(
+                &pool,
+                "scholar update",
+                AuditAction::Update,
+                "scholars",
+                1,
+                &format!("{email}: {}", changes.join(", ")),
+            )
+            .await?;
+            println!("Updated {email}: {}.", changes.join(", "));
+        }
+        Commands::Scholar {
+            action: ScholarAction::Deactivate { email, status },
+        } => {
+            if status == ScholarStatus::Active {
+                anyhow::bail!("deactivate needs an inactive status; use `scholar set-status --status active` to reactivate");
+            }
+            let org_id = db::resolve_org(&pool, &org).await?;
+            db::set_scholar_status(&pool, org_id, &email, status).await?;
+            audit::record(
+                &pool,
+                "scholar deactivate",
+                AuditAction::Update,
+                "scholars",
+                1,
+                &format!("{email} -> {}", status.as_str()),
+            )
+            .await?;
+            println!("Deactivated {email} ({}).", status.as_str());
+        }
+        Commands::Scholar {
+            action: ScholarAction::SetStatus { email, status },
+        } => {
+            let org_id = db::resolve_org(&pool, &org).await?;
+            db::set_scholar_status(&pool, org_id, &email, status).await?;
+            audit::record(
+                &pool,
+                "scholar set-status",
+                AuditAction::Update,
+                "scholars",
+                1,
+                &format!("{email} -> {}", status.as_str()),
+            )
+            .await?;
+            println!("Set {email} to {}.", status.as_str());
+        }
+        Commands::Scholar {
+            action: ScholarAction::Delete { email },
+        } => {
+            let org_id = db::resolve_org(&pool, &org).await?;
+            db::set_scholar_deleted(&pool, org_id, &email, true).await?;
+            audit::record(
+                &pool,
+                "scholar delete",
+                AuditAction::Delete,
+                "scholars",
+                1,
+                &email,
+            )
+            .await?;
+            println!("Deleted {email}; restore with `scholar restore`.");
+        }
+        Commands::Scholar {
+            action: ScholarAction::Merge { keep, absorb },
+        } => {
+            let org_id = db::resolve_org(&pool, &org).await?;
+            let summary = db::merge_scholars(&pool, org_id, &keep, &absorb).await?;
+            audit::record(
+                &pool,
+                "scholar merge",
+                AuditAction::Update,
+                "scholars",
+                2,
+                &format!("{absorb} into {keep}"),
+            )
+            .await?;
+            println!(
+                "Merged {absorb} into {keep}: moved {} signals, {} interventions, and {} score \
+                 snapshots.",
+                summary.signals, summary.interventions, summary.snapshots
+            );
+            if summary.overlapping_snapshots > 0 {
+                println!(
+                    "Dropped {} snapshots from runs that scored both records; {keep}'s were kept.",
+                    summary.overlapping_snapshots
+                );
+            }
+            println!("{absorb} is soft-deleted; `scholar restore` brings back the empty record.");
+        }
+        Commands::Scholar {
+            action:
+                ScholarAction::Dedupe {
+                    suggest: _,
+                    cohort,
+                    min_similarity,
+                    json,
+                },
+        } => {
+            anyhow::ensure!(
+                (0.0..=1.0).contains(&min_similarity),
+                "--min-similarity must be between 0 and 1"
+            );
+            let org_id = db::resolve_org(&pool, &org).await?;
+            let filter = db::ScholarFilter {
+                cohort,
+                ..Default::default()
+            };
+            let scholars = db::list_scholars(&pool, org_id, &filter).await?;
+            let suggestions = dedupe::suggest(&scholars, min_similarity);
+            if json {
+                println!("{}", serde_json::to_string_pretty(&suggestions)?);
+                return Ok(());
+            }
+            if suggestions.is_empty() {
+                println!(
+                    "No likely duplicates among {} scholars at similarity {min_similarity}.",
+                    scholars.len()
+                );
+                return Ok(());
+            }
+
+            for suggestion in &suggestions {
+                println!(
+                    "- {} ({}) and {} ({}): {:.2}, {}",
+                    suggestion.keep.full_name,
+                    suggestion.keep.email,
+                    suggestion.absorb.full_name,
+                    suggestion.absorb.email,
+                    suggestion.similarity,
+                    suggestion.reasons.join(", ")
+                );
+                println!(
+                    "    scholar merge --keep {} --absorb {}",
+                    suggestion.keep.email, suggestion.absorb.email
+                );
+            }
+            println!("The older record of each pair is proposed as the one to keep.");
+        }
+        Commands::Scholar {
+            action: ScholarAction::Anonymize { email },
+        } => {
+            let org_id = db::resolve_org(&pool, &org).await?;
+            let (pseudonym, signals) = db::anonymize_scholar(&pool, org_id, &email).await?;
+            // The audit trail names the pseudonym only, so the erased email isn't kept there.
+            audit::record(
+                &pool,
+                "scholar anonymize",
+                AuditAction::Update,
+                "scholars",
+                1,
+                &pseudonym,
+            )
+            .await?;
+            println!("Anonymized {email} as {pseudonym}; cleared notes on {signals} signals.");
+        }
+        Commands::Scholar {
+            action: ScholarAction::Restore { email },
+        } => {
+            let org_id = db::resolve_org(&pool, &org).await?;
+            db::set_scholar_deleted(&pool, org_id, &email, false).await?;
+            audit::record(
+                &pool,
+                "scholar restore",
+                AuditAction::Update,
+                "scholars",
+                1,
+                &email,
+            )
+            .await?;
+            println!("Restored {email}.");
+        }
+        Commands::Signal {
+            action:
+                SignalAction::Add {
+                    email,
+                    signal_type,
+                    severity,
+                    note,
+                    date,
+                    name,
+                    cohort,
+                    tags,
+                    submitted_by,
+                    source_key,
+                    allow_any_date,
+                },
+        } => {
+            let org_id = db::resolve_org(&pool, &org).await?;
+            let signal = NewSignal {
+                email,
+                signal_type,
+                severity,
+                note,
+                occurred_at: date,
+                full_name: name,
+                cohort,
+                tags,
+                submitted_by: Some(submitted_by.unwrap_or_else(audit::current_actor)),
+                source_key,
+            };
+            let (row, inserted) = import::add_signal(
+                &pool,
+                org_id,
+                signal,
+                "signal add",
+                &import::ImportLimits {
+                    dates: (!allow_any_date).then_some(config.dates),
+                    ..config.import_limits()
+                },
+            )
+            .await?;
+            let source_key = row.source_key.clone().unwrap_or_default();
+            audit::record(
+                &pool,
+                "signal add",
+                AuditAction::Insert,
+                "signals",
+                u64::from(inserted),
+                &format!("{source_key} for {}", row.email),
+            )
+            .await?;
+            if inserted {
+                println!(
+                    "Recorded {} severity {} for {} as {source_key}.",
+                    row.signal_type, row.severity, row.email
+                );
+            } else {
+                println!("Signal {source_key} already exists; nothing recorded.");
+            }
+        }
+        Commands::Signal {
+            action: SignalAction::Tag { source_key, tags },
+        } => {
+            let org_id = db::resolve_org(&pool, &org).await?;
+            let tags = normalize_tags(tags);
+            let current = db::update_signal_tags(&pool, org_id, &source_key, &tags, true).await?;
+            audit::record(
+                &pool,
+                "signal tag",
+                AuditAction::Update,
+                "signals",
+                1,
+                &format!("{source_key} +{}", tags.join(",")),
+            )
+            .await?;
+            println!("Signal {source_key} tags: {}.", current.join(", "));
+        }
+        Commands::Signal {
+            action: SignalAction::Untag { source_key, tags },
+        } => {
+            let org_id = db::resolve_org(&pool, &org).await?;
+            let tags = normalize_tags(tags);
+            let current = db::update_signal_tags(&pool, org_id, &source_key, &tags, false).await?;
+            audit::record(
+                &pool,
+                "signal untag",
+                AuditAction::Update,
+                "signals",
+                1,
+                &format!("{source_key} -{}", tags.join(",")),
+            )
+            .await?;
+            if current.is_empty() {
+                println!("Signal {source_key} has no tags.");
+            } else {
+                println!("Signal {source_key} tags: {}.", current.join(", "));
+            }
+        }
+        Commands::Signal {
+            action: SignalAction::Delete { source_key },
+        } => {
+            let org_id = db::resolve_org(&pool, &org).await?;
+            db::set_signal_deleted(&pool, org_id, &source_key, true).await?;
+            audit::record(
+                &pool,
+                "signal delete",
+                AuditAction::Delete,
+                "signals",
+                1,
+                &source_key,
+            )
+            .await?;
+            println!("Deleted signal {source_key}; restore with `signal restore`.");
+        }
+        Commands::Signal {
+            action: SignalAction::Restore { source_key },
+        } => {
+            let org_id = db::resolve_org(&pool, &org).await?;
+            db::set_signal_deleted(&pool, org_id, &source_key, false).await?;
+            audit::record(
+                &pool,
+                "signal restore",
+                AuditAction::Update,
+                "signals",
+                1,
+                &source_key,
+            )
+            .await?;
+            println!("Restored signal {source_key}.");
+        }
+        Commands::Signal {
+            action: SignalAction::Ack { email, by },
+        } => {
+            let org_id = db::resolve_org(&pool, &org).await?;
+            let by = by.unwrap_or_else(audit::current_actor);
+            let acknowledged = notify::acknowledge(&pool, org_id, &email, &by).await?;
+            audit::record(
+                &pool,
+                "signal ack",
+                AuditAction::Update,
+                "sent_alerts",
+                acknowledged,
+                &email,
+            )
+            .await?;
+            if acknowledged == 0 {
+                println!("No open alerts about {email}.");
+            } else {
+                println!("Acknowledged {acknowledged} alerts about {email}.");
+            }
+        }
+        Commands::Notifications {
+            action:
+                NotificationsAction::List {
+                    since_days,
+                    email,
+                    channel,
+                    status,
+                    limit,
+                },
+        } => {
+            let org_id = db::resolve_org(&pool, &org).await?;
+            let filter = notify::NotificationQuery {
+                since: since_days.map(|days| chrono::Utc::now() - chrono::Duration::days(days)),
+                email,
+                channel,
+                status,
+                limit,
+            };
+            let notifications = notify::list_notifications(&pool, org_id, &filter).await?;
+            if notifications.is_empty() {
+                println!("No notifications found.");
+                return Ok(());
+            }
+
+            for notification in notifications {
+                println!(
+                    "- {} {} to {} after {} attempt(s): {}",
+                    notification.sent_at.format("%Y-%m-%d %H:%M:%S"),
+                    notification.status,
+                    notification.channel,
+                    notification.attempts,
+                    notification.summary
+                );
+                if !notification.scholars.is_empty() {
+                    println!("    about {}", notification.scholars.join(", "));
+                }
+                if let Some(error) = &notification.error {
+                    println!("    error: {error}");
+                }
+            }
+        }
+        Commands::Audit {
+            action:
+                AuditCommand::List {
+                    since_days,
+                    actor,
+                    command,
+                    limit,
+                },
+        } => {
+            let filter = audit::AuditQuery {
+                since: since_days.map(|days| chrono::Utc::now() - chrono::Duration::days(days)),
+                actor,
+                command,
+                mentioning: None,
+                limit,
+            };
+            let entries = audit::list(&pool, &filter).await?;
+            if entries.is_empty() {
+                println!("No audit entries found.");
+                return Ok(());
+            }
+
+            for entry in entries {
+                println!(
+                    "- #{} {} {} ran `{}`: {} {} row(s) in {}{}",
+                    entry.id,
+                    entry.occurred_at.format("%Y-%m-%d %H:%M:%S"),
+                    entry.actor,
+                    entry.command,
+                    entry.action,
+                    entry.affected_rows,
+                    entry.entity,
+                    if entry.detail.is_empty() {
+                        String::new()
+                    } else {
+                        format!(" ({})", entry.detail)
+                    }
+                );
+            }
+        }
+        Commands::Audit {
+            action:
+                AuditCommand::Export {
+                    scholar,
+                    format,
+                    out,
+                },
+        } => {
+            let org_id = db::resolve_org(&pool, &org).await?;
+            let bundle = subject::bundle(&pool, &org, org_id, &scholar, &config).await?;
+            let document = match format {
+                subject::BundleFormat::Json => serde_json::to_string_pretty(&bundle)?,
+            };
+            match out {
+                Some(out) => {
+                    std::fs::write(&out, document + "\n")
+                        .with_context(|| format!("failed to write {}", out.display()))?;
+                    println!(
+                        "Exported {} signals, {} interventions, {} notifications, and {} audit \
+                         entries about {scholar} to {}.",
+                        bundle.signals.len(),
+                        bundle.interventions.len(),
+                        bundle.notifications.len(),
+                        bundle.audit_entries.len(),
+                        out.display()
+                    );
+                }
+                None => println!("{document}"),
+            }
+        }
+        Commands::Intervene {
+            action:
+                InterveneAction::Add {
+                    email,
+                    intervention_type,
+                    date,
+                    outcome,
+                    note,
+                    signals,
+                },
+        } => {
+            let org_id = db::resolve_org(&pool, &org).await?;
+            let intervention = db::NewIntervention {
+                scholar_email: email.clone(),
+                occurred_on: date.unwrap_or_else(|| chrono::Utc::now().date_naive()),
+                intervention_type,
+                outcome,
+                note,
+                signal_keys: signals,
+            };
+            let id = db::add_intervention(&pool, org_id, &intervention).await?;
+            audit::record(
+                &pool,
+                "intervene add",
+                AuditAction::Insert,
+                "interventions",
+                1,
+                &format!("{email}: {}", intervention.intervention_type),
+            )
+            .await?;
+            println!(
+                "Recorded {} for {email} linked to {} signals ({id}).",
+                intervention.intervention_type,
+                intervention.signal_keys.len()
+            );
+        }
+        Commands::Intervene {
+            action: InterveneAction::Close { id, outcome },
+        } => {
+            anyhow::ensure!(
+                outcome != "pending",
+                "closing needs a final outcome, e.g. improved or no-change"
+            );
+            let org_id = db::resolve_org(&pool, &org).await?;
+            db::close_intervention(&pool, org_id, id, &outcome).await?;
+            audit::record(
+                &pool,
+                "intervene close",
+                AuditAction::Update,
+                "interventions",
+                1,
+                &format!("{id}: {outcome}"),
+            )
+            .await?;
+            println!("Closed intervention {id} as {outcome}.");
+        }
+        Commands::Intervene {
+            action:
+                InterveneAction::List {
+                    cohort,
+                    email,
+                    limit,
+                },
+        } => {
+            let org_id = db::resolve_org(&pool, &org).await?;
+            let interventions =
+                db::list_interventions(&pool, org_id, email.as_deref(), cohort.as_deref(), limit)
+                    .await?;
+            if interventions.is_empty() {
+                println!("No interventions recorded.");
+                return Ok(());
+            }
+
+            for intervention in interventions {
+                let status = match intervention.closed_at {
+                    Some(closed_at) => format!("closed {}", closed_at.date_naive()),
+                    None => "open".to_string(),
+                };
+                println!(
+                    "- {} {} for {} ({}): {} ({status}, {}){}{}",
+                    intervention.occurred_on,
+                    intervention.intervention_type,
+                    intervention.scholar_name,
+                    intervention.scholar_email,
+                    intervention.outcome.as_deref().unwrap_or("outcome pending"),
+                    intervention.id,
+                    if intervention.signal_keys.is_empty() {
+                        String::new()
+                    } else {
+                        format!(" [signals: {}]", intervention.signal_keys.join(", "))
+                    },
+                    if intervention.note.is_empty() {
+                        String::new()
+                    } else {
+                        format!(" — {}", intervention.note)
+                    }
+                );
+            }
+        }
+        Commands::Outcome {
+            action:
+                OutcomeAction::Record {
+                    email,
+                    outcome_type,
+                    gpa,
+                    date,
+                    note,
+                },
+        } => {
+            let org_id = db::resolve_org(&pool, &org).await?;
+            let outcome = db::NewOutcome {
+                scholar_email: email.clone(),
+                outcome_type,
+                occurred_on: date.unwrap_or_else(|| chrono::Utc::now().date_naive()),
+                gpa,
+                note,
+            };
+            let id = db::add_outcome(&pool, org_id, &outcome).await?;
+            audit::record(
+                &pool,
+                "outcome record",
+                AuditAction::Insert,
+                "outcomes",
+                1,
+                &format!("{email}: {}", outcome_type.as_str()),
+            )
+            .await?;
+            let status = outcome_type
+                .status()
+                .map(|status| format!("; status set to {}", status.as_str()))
+                .unwrap_or_default();
+            println!(
+                "Recorded {} for {email} on {} ({id}){status}.",
+                outcome_type.as_str(),
+                outcome.occurred_on
+            );
+        }
+        Commands::Outcome {
+            action:
+                OutcomeAction::List {
+                    cohort,
+                    email,
+                    limit,
+                },
+        } => {
+            let org_id = db::resolve_org(&pool, &org).await?;
+            let recorded =
+                db::list_outcomes(&pool, org_id, email.as_deref(), cohort.as_deref(), limit)
+                    .await?;
+            if recorded.is_empty() {
+                println!("No outcomes recorded.");
+                return Ok(());
+            }
+            for outcome in recorded {
+                println!(
+                    "- {} {}{} for {} ({}, {}) ({}){}",
+                    outcome.occurred_on,
+                    outcome.outcome_type.as_str(),
+                    outcome
+                        .gpa
+                        .map(|gpa| format!(" {gpa:.2}"))
+                        .unwrap_or_default(),
+                    outcome.scholar_name,
+                    outcome.scholar_email,
+                    outcome.cohort,
+                    outcome.id,
+                    if outcome.note.is_empty() {
+                        String::new()
+                    } else {
+                        format!(" — {}", outcome.note)
+                    }
+                );
+            }
+        }
+        Commands::Rules {
+            action: RulesAction::List,
+        } => {
+            let org_id = db::resolve_org(&pool, &org).await?;
+            let stored = rules::list_rules(&pool, org_id, false).await?;
+            if stored.is_empty() && config.rules.is_empty() {
+                println!("No alert rules defined.");
+                return Ok(());
+            }
+
+            let listed = config
+                .rules
+                .iter()
+                .map(|rule| (rule, " (config)"))
+                .chain(stored.iter().map(|rule| (rule, "")));
+            for (rule, origin) in listed {
+                println!(
+                    "- {}{origin}{}: {}{}",
+                    rule.name,
+                    if rule.enabled { "" } else { " (disabled)" },
+                    rule.describe(),
+                    if rule.channels.is_empty() {
+                        String::new()
+                    } else {
+                        format!(" -> {}", rule.channels.join(", "))
+                    }
+                );
+            }
+        }
+        Commands::Rules {
+            action:
+                RulesAction::Add {
+                    name,
+                    signal_type,
+                    min_severity,
+                    min_count,
+                    new_only,
+                    min_score,
+                    min_tier,
+                    since_days,
+                    channels,
+                },
+        } => {
+            let since_days = since_days.unwrap_or(config.defaults.since_days);
+            let org_id = db::resolve_org(&pool, &org).await?;
+            if config.rules.iter().any(|rule| rule.name == name) {
+                anyhow::bail!("the config already defines a rule named {name}");
+            }
+            let rule = rules::AlertRule {
+                name,
+                signal_type,
+                min_severity,
+                min_count,
+                new_only,
+                min_score,
+                min_tier,
+                since_days,
+                enabled: true,
+                channels,
+            };
+            config.check_rule_channels(&rule)?;
+            rules::add_rule(&pool, org_id, &rule, config.severity).await?;
+            audit::record(
+                &pool,
+                "rules add",
+                AuditAction::Insert,
+                "alert_rules",
+                1,
+                &rule.name,
+            )
+            .await?;
+            println!("Added rule {}: {}.", rule.name, rule.describe());
+        }
+        Commands::Rules {
+            action: RulesAction::Disable { name },
+        } => {
+            let org_id = db::resolve_org(&pool, &org).await?;
+            rules::set_enabled(&pool, org_id, &name, false).await?;
+            audit::record(
+                &pool,
+                "rules disable",
+                AuditAction::Update,
+                "alert_rules",
+                1,
+                &name,
+            )
+            .await?;
+            println!("Disabled rule {name}.");
+        }
+        Commands::Rules {
+            action: RulesAction::Enable { name },
+        } => {
+            let org_id = db::resolve_org(&pool, &org).await?;
+            rules::set_enabled(&pool, org_id, &name, true).await?;
+            audit::record(
+                &pool,
+                "rules enable",
+                AuditAction::Update,
+                "alert_rules",
+                1,
+                &name,
+            )
+            .await?;
+            println!("Enabled rule {name}.");
+        }
+        Commands::Rules {
+            action:
+                RulesAction::Test {
+                    name,
+                    email,
+                    new_within_hours,
+                },
+        } => {
+            let org_id = db::resolve_org(&pool, &org).await?;
+            let rule = rules::find_rule(&pool, org_id, &config, &name).await?;
+            let new_since = chrono::Utc::now() - chrono::Duration::hours(new_within_hours);
+            let matches = rules::evaluate_rule(
+                &pool,
+                org_id,
+                &rule,
+                &config.scoring,
+                email,
+                Some(new_since),
+            )
+            .await?
+            .matches;
+
+            println!("Rule {}: {}", rule.name, rule.describe());
+            if matches.is_empty() {
+                println!("No scholars match.");
+                return Ok(());
+            }
+            for hit in &matches {
+                println!(
+                    "- {} ({}) score {:.2} [{}]{}",
+                    hit.scholar_name,
+                    hit.scholar_email,
+                    hit.score,
+                    hit.tier.as_str(),
+                    if hit.matching_signals > 0 {
+                        format!(", {} matching signals", hit.matching_signals)
+                    } else {
+                        String::new()
+                    }
+                );
+            }
+            println!("{} scholars would alert.", matches.len());
+        }
+        #[cfg(feature = "self-report")]
+        Commands::SelfReport {
+            action: SelfReportAction::Consent { email, withdraw },
+        } => {
+            let org_id = db::resolve_org(&pool, &org).await?;
+            selfreport::set_consent(&pool, org_id, &email, !withdraw).await?;
+            audit::record(
+                &pool,
+                "self-report consent",
+                AuditAction::Update,
+                "scholars",
+                1,
+                &format!("{email} {}", if withdraw { "withdrew" } else { "granted" }),
+            )
+            .await?;
+            if withdraw {
+                println!("Withdrew self-report consent for {email} and revoked their link.");
+            } else {
+                println!("Recorded self-report consent for {email}.");
+            }
+        }
+        #[cfg(feature = "self-report")]
+        Commands::SelfReport {
+            action: SelfReportAction::Issue { email, base_url },
+        } => {
+            let org_id = db::resolve_org(&pool, &org).await?;
+            let token = selfreport::issue_token(&pool, org_id, &email).await?;
+            audit::record(
+                &pool,
+                "self-report issue",
+                AuditAction::Insert,
+                "self_report_tokens",
+                1,
+                &email,
+            )
+            .await?;
+            match base_url {
+                Some(base) => println!(
+                    "Check-in link for {email}: {}/check-in/{token}",
+                    base.trim_end_matches('/')
+                ),
+                None => println!("Check-in token for {email}: {token}"),
+            }
+        }
+        #[cfg(feature = "self-report")]
+        Commands::SelfReport {
+            action: SelfReportAction::Revoke { email },
+        } => {
+            let org_id = db::resolve_org(&pool, &org).await?;
+            let revoked = selfreport::revoke(&pool, org_id, &email).await?;
+            audit::record(
+                &pool,
+                "self-report revoke",
+                AuditAction::Update,
+                "self_report_tokens",
+                revoked,
+                &email,
+            )
+            .await?;
+            println!("Revoked {revoked} check-in links for {email}.");
+        }
+        #[cfg(feature = "self-report")]
+        Commands::SelfReport {
+            action:
+                SelfReportAction::Submit {
+                    token,
+                    rating,
+                    note,
+                    max_per_day,
+                },
+        } => {
+            let check_in = selfreport::CheckIn { rating, note };
+            let limit = selfreport::RateLimit { max_per_day };
+            let signal_id = selfreport::submit(&pool, &token, &check_in, &limit).await?;
+            audit::record(
+                &pool,
+                "self-report submit",
+                AuditAction::Insert,
+                "signals",
+                1,
+                &signal_id.to_string(),
+            )
+            .await?;
+            println!("Check-in recorded.");
+        }
+        Commands::Advisor {
+            action:
+                AdvisorAction::Add {
+                    email,
+                    name,
+                    channel,
+                    no_channel,
+                },
+        } => {
+            let org_id = db::resolve_org(&pool, &org).await?;
+            let channel = match channel {
+                Some(channel) => {
+                    config.channel(&channel)?;
+                    db::AdvisorChannel::Set(channel)
+                }
+                None if no_channel => db::AdvisorChannel::Clear,
+                None => db::AdvisorChannel::Keep,
+            };
+            db::upsert_advisor(&pool, org_id, &email, &name, &channel).await?;
+            audit::record(
+                &pool,
+                "advisor add",
+                AuditAction::Insert,
+                "advisors",
+                1,
+                &email,
+            )
+            .await?;
+            println!("Advisor {name} ({email}) ready.");
+        }
+        Commands::Advisor {
+            action: AdvisorAction::List,
+        } => {
+            let org_id = db::resolve_org(&pool, &org).await?;
+            let advisors = db::list_advisors(&pool, org_id).await?;
+            if advisors.is_empty() {
+                println!("No advisors registered.");
+                return Ok(());
+            }
+
+            for advisor in advisors {
+                println!(
+                    "- {} ({}): {} scholars{}",
+                    advisor.full_name,
+                    advisor.email,
+                    advisor.scholar_count,
+                    advisor
+                        .channel
+                        .map(|channel| format!(", alerts to {channel}"))
+                        .unwrap_or_default()
+                );
+            }
+        }
+        Commands::SignalTypes {
+            action: SignalTypesAction::List { json },
+        } => {
+            let org_id = db::resolve_org(&pool, &org).await?;
+            let types = db::list_signal_types(&pool, org_id).await?;
+            if json {
+                println!("{}", serde_json::to_string_pretty(&types)?);
+                return Ok(());
+            }
+            if types.is_empty() {
+                println!("No signal types registered; imports accept any type.");
+                return Ok(());
+            }
+
+            for signal_type in types {
+                let description = match signal_type.description.as_str() {
+                    "" => String::new(),
+                    description => format!(": {description}"),
+                };
+                let inactive = if signal_type.active {
+                    ""
+                } else {
+                    " [disabled]"
+                };
+                println!(
+                    "- {} (weight {}, {} signals){description}{inactive}",
+                    signal_type.name, signal_type.default_weight, signal_type.signals
+                );
+            }
+        }
+        Commands::SignalTypes {
+            action:
+                SignalTypesAction::Add {
+                    name,
+                    description,
+                    default_weight,
+                },
+        } => {
+            let org_id = db::resolve_org(&pool, &org).await?;
+            db::add_signal_type(&pool, org_id, &name, &description, default_weight).await?;
+            audit::record(
+                &pool,
+                "signal-types add",
+                AuditAction::Insert,
+                "signal_types",
+                1,
+                &name,
+            )
+            .await?;
+            println!(
+                "Registered signal type {}.",
+                import::SignalTypes::key(&name)
+            );
+        }
+        Commands::SignalTypes {
+            action:
+                SignalTypesAction::Update {
+                    name,
+                    description,
+                    default_weight,
+                },
+        } => {
+            if description.is_none() && default_weight.is_none() {
+                anyhow::bail!("nothing to update; pass --description or --default-weight");
+            }
+            let org_id = db::resolve_org(&pool, &org).await?;
+            db::update_signal_type(
+                &pool,
+                org_id,
+                &name,
+                description.as_deref(),
+                default_weight,
+                None,
+            )
+            .await?;
+            audit::record(
+                &pool,
+                "signal-types update",
+                AuditAction::Update,
+                "signal_types",
+                1,
+                &name,
+            )
+            .await?;
+            println!("Updated signal type {name}.");
+        }
+        Commands::SignalTypes {
+            action: SignalTypesAction::Disable { name },
+        } => {
+            let org_id = db::resolve_org(&pool, &org).await?;
+            db::update_signal_type(&pool, org_id, &name, None, None, Some(false)).await?;
+            audit::record(
+                &pool,
+                "signal-types disable",
+                AuditAction::Update,
+                "signal_types",
+                1,
+                &name,
+            )
+            .await?;
+            println!("Disabled signal type {name}; existing signals keep it.");
+        }
+        Commands::SignalTypes {
+            action: SignalTypesAction::Enable { name },
+        } => {
+            let org_id = db::resolve_org(&pool, &org).await?;
+            db::update_signal_type(&pool, org_id, &name, None, None, Some(true)).await?;
+            audit::record(
+                &pool,
+                "signal-types enable",
+                AuditAction::Update,
+                "signal_types",
+                1,
+                &name,
+            )
+            .await?;
+            println!("Enabled signal type {name}.");
+        }
+        Commands::SignalTypes {
+            action: SignalTypesAction::Adopt,
+        } => {
+            let org_id = db::resolve_org(&pool, &org).await?;
+            let added = db::adopt_signal_types(&pool, org_id).await?;
+            audit::record(
+                &pool,
+                "signal-types adopt",
+                AuditAction::Insert,
+                "signal_types",
+                added.len() as u64,
+                &added.join(", "),
+            )
+            .await?;
+            if added.is_empty() {
+                println!("Every signal type in use is already registered.");
+            } else {
+                println!(
+                    "Registered {} signal types: {}.",
+                    added.len(),
+                    added.join(", ")
+                );
+            }
+        }
+        Commands::Advisor {
+            action: AdvisorAction::Assign { advisor, scholar },
+        } => {
+            let org_id = db::resolve_org(&pool, &org).await?;
+            db::assign_advisor(&pool, org_id, &scholar, Some(&advisor)).await?;
+            audit::record(
+                &pool,
+                "advisor assign",
+                AuditAction::Update,
+                "scholars",
+                1,
+                &format!("{scholar} -> {advisor}"),
+            )
+            .await?;
+            println!("Assigned {scholar} to {advisor}.");
+        }
+        Commands::Advisor {
+            action: AdvisorAction::Unassign { scholar },
+        } => {
+            let org_id = db::resolve_org(&pool, &org).await?;
+            db::assign_advisor(&pool, org_id, &scholar, None).await?;
+            audit::record(
+                &pool,
+                "advisor unassign",
+                AuditAction::Update,
+                "scholars",
+                1,
+                &scholar,
+            )
+            .await?;
+            println!("Cleared the advisor for {scholar}.");
+        }
+        Commands::Org {
+            action: OrgAction::Add { slug, name },
+        } => {
+            let id = db::create_org(&pool, &slug, &name).await?;
+            audit::record(
+                &pool,
+                "org add",
+                AuditAction::Insert,
+                "organizations",
+                1,
+                &slug,
+            )
+            .await?;
+            println!("Created organization {slug} ({id}).");
+        }
+        Commands::Org {
+            action: OrgAction::List,
+        } => {
+            for organization in db::list_orgs(&pool).await? {
+                println!(
+                    "- {} ({}) created {}",
+                    organization.slug,
+                    organization.name,
+                    organization.created_at.format("%Y-%m-%d")
+                );
+            }
+        }
+        #[cfg(feature = "privacy-export")]
+        Commands::ExportAggregates {
+            cohort,
+            since_days,
+            out,
+            min_cell_size,
+            epsilon,
+            seed,
+        } => {
+            let since_days = since_days.unwrap_or(config.defaults.since_days);
+            let since_date = risk::cutoff_date(since_days);
+            let org_id = db::resolve_org(&pool, &org).await?;
+            let aggregates =
+                db::fetch_cohort_aggregates(&pool, org_id, since_date, cohort.as_deref()).await?;
+            let options = privacy::PrivacyOptions {
+                min_cell_size,
+                epsilon,
+                seed,
+            };
+            let released = privacy::protect_aggregates(&aggregates, &options);
+            let suppressed = released
+                .iter()
+                .filter(|cell| cell.scholar_count.is_none())
+                .count();
+
+            let mut writer = csv::Writer::from_path(&out)?;
+            for cell in &released {
+                writer.serialize(cell)?;
+            }
+            writer.flush()?;
+            println!(
+                "Wrote {} aggregate cells ({} suppressed) to {}.",
+                released.len(),
+                suppressed,
+                out.display()
+            );
         }
     }
 
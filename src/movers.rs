@@ -0,0 +1,113 @@
+use std::collections::HashMap;
+
+use crate::models::SnapshotScore;
+use crate::risk::Tier;
+
+/// A scholar whose score changed between two scoring runs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mover {
+    pub scholar_name: String,
+    pub scholar_email: String,
+    pub cohort: String,
+    pub before: f64,
+    pub after: f64,
+    pub tier_before: Tier,
+    pub tier_after: Tier,
+}
+
+impl Mover {
+    pub fn change(&self) -> f64 {
+        self.after - self.before
+    }
+}
+
+/// Score changes from `before` to `after`, split into increases (largest first) and decreases
+/// (largest drop first). A scholar missing from one run scored zero in it.
+pub fn movers(before: &[SnapshotScore], after: &[SnapshotScore]) -> (Vec<Mover>, Vec<Mover>) {
+    let previous: HashMap<&str, &SnapshotScore> = before
+        .iter()
+        .map(|score| (score.scholar_email.as_str(), score))
+        .collect();
+    let current: HashMap<&str, &SnapshotScore> = after
+        .iter()
+        .map(|score| (score.scholar_email.as_str(), score))
+        .collect();
+
+    let mut changes: Vec<Mover> = after
+        .iter()
+        .chain(
+            before
+                .iter()
+                .filter(|score| !current.contains_key(score.scholar_email.as_str())),
+        )
+        .map(|scholar| {
+            let email = scholar.scholar_email.as_str();
+            let (before, tier_before) = previous
+                .get(email)
+                .map_or((0.0, Tier::Low), |score| (score.score, score.tier));
+            let (after, tier_after) = current
+                .get(email)
+                .map_or((0.0, Tier::Low), |score| (score.score, score.tier));
+            Mover {
+                scholar_name: scholar.scholar_name.clone(),
+                scholar_email: scholar.scholar_email.clone(),
+                cohort: scholar.cohort.clone(),
+                before,
+                after,
+                tier_before,
+                tier_after,
+            }
+        })
+        .filter(|mover| mover.change() != 0.0)
+        .collect();
+
+    changes.sort_by(|a, b| {
+        b.change()
+            .total_cmp(&a.change())
+            .then_with(|| a.scholar_email.cmp(&b.scholar_email))
+    });
+    let split = changes.partition_point(|mover| mover.change() > 0.0);
+    let mut decreases = changes.split_off(split);
+    decreases.reverse();
+    (changes, decreases)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(email: &str, score: f64, tier: Tier) -> SnapshotScore {
+        SnapshotScore {
+            scholar_name: email.to_string(),
+            scholar_email: email.to_string(),
+            cohort: "2026".to_string(),
+            score,
+            tier,
+        }
+    }
+
+    #[test]
+    fn ranks_increases_and_decreases_including_scholars_in_one_run() {
+        let before = vec![
+            snapshot("steady@x.org", 4.0, Tier::Moderate),
+            snapshot("rising@x.org", 3.0, Tier::Low),
+            snapshot("easing@x.org", 9.0, Tier::High),
+            snapshot("gone@x.org", 5.0, Tier::Moderate),
+        ];
+        let after = vec![
+            snapshot("steady@x.org", 4.0, Tier::Moderate),
+            snapshot("rising@x.org", 9.5, Tier::High),
+            snapshot("easing@x.org", 6.0, Tier::Moderate),
+            snapshot("new@x.org", 2.0, Tier::Low),
+        ];
+
+        let (increases, decreases) = movers(&before, &after);
+        let emails = |movers: &[Mover]| -> Vec<String> {
+            movers.iter().map(|m| m.scholar_email.clone()).collect()
+        };
+        assert_eq!(emails(&increases), vec!["rising@x.org", "new@x.org"]);
+        assert_eq!(emails(&decreases), vec!["gone@x.org", "easing@x.org"]);
+        assert_eq!(increases[0].change(), 6.5);
+        assert_eq!(decreases[0].tier_after, Tier::Low);
+    }
+}
@@ -0,0 +1,993 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use anyhow::Context;
+use chrono::{DateTime, NaiveDate, Utc};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use sqlx::{PgPool, Postgres, QueryBuilder, Row};
+use uuid::Uuid;
+
+use crate::config::{ChannelConfig, ChannelKind, Config};
+use crate::db;
+use crate::models::ScholarScore;
+use crate::recommendations::{self, Playbook, Recommendation, SignalFact};
+use crate::risk::Tier;
+use crate::rules::AlertRule;
+
+/// Header carrying `sha256=<hex HMAC-SHA256 of the body>` on webhook channels with a secret.
+pub const SIGNATURE_HEADER: &str = "X-CEW-Signature";
+
+/// Why a batch of alerts is being sent.
+/// Twilio's REST API root; SMS channels POST to its Messages resource.
+const TWILIO_API: &str = "https://api.twilio.com/2010-04-01";
+
+/// Tries per channel before a delivery counts as failed, waiting `RETRY_DELAY` times the attempt
+/// number in between.
+const SEND_ATTEMPTS: u32 = 3;
+const RETRY_DELAY: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// Longest message body Twilio accepts.
+const SMS_MAX_CHARS: usize = 1600;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AlertReason<'a> {
+    /// Scholars at or past the `[watch]` alert tier, or `notify`'s threshold.
+    Tier,
+    /// Scholars an alert rule matched.
+    Rule(&'a AlertRule),
+    /// Alerts nobody acknowledged within this many hours, sent to the escalation channels.
+    Unacknowledged(i64),
+}
+
+impl AlertReason<'_> {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Tier => "tier",
+            Self::Rule(_) => "rule",
+            Self::Unacknowledged(_) => "escalation",
+        }
+    }
+}
+
+/// Body POSTed to webhook channels. `text` is a one-paragraph summary for chat tools that only
+/// display that field.
+#[derive(Debug, serde::Serialize)]
+pub struct AlertPayload<'a> {
+    pub org: &'a str,
+    #[serde(skip)]
+    pub reason: AlertReason<'a>,
+    /// Alert rule that matched these scholars; absent for `[watch]` tier alerts.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rule: Option<&'a str>,
+    /// Set on escalations: hours the original alerts went unacknowledged.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unacknowledged_hours: Option<i64>,
+    pub generated_at: DateTime<Utc>,
+    pub text: String,
+    pub alerts: Vec<Alert<'a>>,
+}
+
+/// One scholar who crossed the alert threshold: the score fields plus the signals behind them.
+#[derive(Debug, serde::Serialize)]
+pub struct Alert<'a> {
+    #[serde(flatten)]
+    pub score: &'a ScholarScore,
+    pub signals: &'a [AlertSignal],
+    /// What the `[[playbooks]]` matching the scholar suggest; absent when none match.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub next_steps: Vec<Recommendation<'a>>,
+}
+
+/// A signal that contributed to an alert. Notes are left out so free text about the scholar
+/// stays in this system.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct AlertSignal {
+    pub signal_type: String,
+    pub severity: i32,
+    pub occurred_at: NaiveDate,
+}
+
+impl<'a> From<&'a AlertSignal> for SignalFact<'a> {
+    fn from(signal: &'a AlertSignal) -> Self {
+        Self {
+            signal_type: &signal.signal_type,
+            severity: signal.severity,
+            occurred_at: signal.occurred_at,
+        }
+    }
+}
+
+impl<'a> AlertPayload<'a> {
+    /// `signals` holds each alerted scholar's contributing signals by email, which `playbooks` are
+    /// matched against.
+    pub fn new(
+        org: &'a str,
+        reason: AlertReason<'a>,
+        alerts: &'a [ScholarScore],
+        signals: &'a HashMap<String, Vec<AlertSignal>>,
+        playbooks: &'a [Playbook],
+    ) -> Self {
+        let today = Utc::now().date_naive();
+        Self {
+            org,
+            reason,
+            rule: match reason {
+                AlertReason::Rule(rule) => Some(&rule.name),
+                _ => None,
+            },
+            unacknowledged_hours: match reason {
+                AlertReason::Unacknowledged(hours) => Some(hours),
+                _ => None,
+            },
+            generated_at: Utc::now(),
+            text: alert_text(org, reason, alerts),
+            alerts: alerts
+                .iter()
+                .map(|score| {
+                    let signals: &[AlertSignal] =
+                        signals.get(&score.scholar_email).map_or(&[], Vec::as_slice);
+                    let facts: Vec<SignalFact> = signals.iter().map(SignalFact::from).collect();
+                    Alert {
+                        score,
+                        signals,
+                        next_steps: recommendations::recommend(playbooks, score, &facts, today),
+                    }
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Each alerted scholar's signals in the last `since_days`, newest first, keyed by email.
+pub async fn contributing_signals(
+    pool: &PgPool,
+    org_id: Uuid,
+    since_days: i64,
+    alerts: &[ScholarScore],
+) -> anyhow::Result<HashMap<String, Vec<AlertSignal>>> {
+    let mut signals = HashMap::new();
+    for alert in alerts {
+        let filter = db::SignalFilter {
+            org_id: Some(org_id),
+            emails: vec![alert.scholar_email.clone()],
+            ..db::SignalFilter::new(crate::risk::cutoff_date(since_days))
+        };
+        let mut records: Vec<AlertSignal> = db::fetch_signals(pool, &filter, db::DEFAULT_PAGE_SIZE)
+            .await?
+            .into_iter()
+            .map(|record| AlertSignal {
+                signal_type: record.signal_type,
+                severity: record.severity,
+                occurred_at: record.occurred_at,
+            })
+            .collect();
+        records.sort_by_key(|signal| std::cmp::Reverse(signal.occurred_at));
+        signals.insert(alert.scholar_email.clone(), records);
+    }
+    Ok(signals)
+}
+
+/// Splits off scholars already alerted for `rule` (`None` is the `watch` tier alert) within the last
+/// `cooldown_days`, returning the rest and how many were held back. No cooldown keeps everyone.
+pub async fn apply_cooldown(
+    pool: &PgPool,
+    org_id: Uuid,
+    rule: Option<&str>,
+    cooldown_days: Option<i64>,
+    alerts: Vec<ScholarScore>,
+) -> anyhow::Result<(Vec<ScholarScore>, usize)> {
+    let Some(cooldown_days) = cooldown_days else {
+        return Ok((alerts, 0));
+    };
+    let emails: Vec<&str> = alerts
+        .iter()
+        .map(|score| score.scholar_email.as_str())
+        .collect();
+    let recent: HashSet<String> = sqlx::query_scalar(
+        "SELECT DISTINCT sc.email FROM cohort_early_warning.sent_alerts sa \
+         JOIN cohort_early_warning.scholars sc ON sc.id = sa.scholar_id \
+         WHERE sa.org_id = $1 AND sa.rule IS NOT DISTINCT FROM $2 AND sc.email = ANY($3) \
+         AND sa.sent_at > NOW() - make_interval(days => $4)",
+    )
+    .bind(org_id)
+    .bind(rule)
+    .bind(&emails)
+    .bind(cooldown_days as i32)
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .collect();
+
+    let total = alerts.len();
+    let kept: Vec<ScholarScore> = alerts
+        .into_iter()
+        .filter(|score| !recent.contains(&score.scholar_email))
+        .collect();
+    let held_back = total - kept.len();
+    Ok((kept, held_back))
+}
+
+/// Records that `alerts` went out for `rule`, against the channels that accepted them. Nothing is
+/// recorded when every channel failed, so the next run tries again.
+pub async fn record_sent(
+    pool: &PgPool,
+    org_id: Uuid,
+    rule: Option<&str>,
+    alerts: &[ScholarScore],
+    deliveries: &[(String, anyhow::Result<()>)],
+) -> anyhow::Result<()> {
+    let channels: Vec<&str> = deliveries
+        .iter()
+        .filter(|(_, result)| result.is_ok())
+        .map(|(channel, _)| channel.as_str())
+        .collect();
+    if channels.is_empty() || alerts.is_empty() {
+        return Ok(());
+    }
+    let ids: Vec<Uuid> = alerts.iter().map(|_| Uuid::new_v4()).collect();
+    let emails: Vec<&str> = alerts
+        .iter()
+        .map(|score| score.scholar_email.as_str())
+        .collect();
+    let tiers: Vec<&str> = alerts.iter().map(|score| score.tier.as_str()).collect();
+    sqlx::query(
+        "INSERT INTO cohort_early_warning.sent_alerts \
+         (id, org_id, scholar_id, rule, channels, tier) \
+         SELECT sent.id, $1, sc.id, $2, $3, sent.tier \
+         FROM UNNEST($4::uuid[], $5::text[], $6::text[]) AS sent(id, email, tier) \
+         JOIN cohort_early_warning.scholars sc \
+           ON sc.org_id = $1 AND sc.email = sent.email AND sc.deleted_at IS NULL",
+    )
+    .bind(org_id)
+    .bind(rule)
+    .bind(&channels)
+    .bind(&ids)
+    .bind(&emails)
+    .bind(&tiers)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Marks every unacknowledged alert about the scholar as seen by `by`, which stops it from
+/// escalating. Returns how many alerts were open.
+pub async fn acknowledge(
+    pool: &PgPool,
+    org_id: Uuid,
+    email: &str,
+    by: &str,
+) -> anyhow::Result<u64> {
+    let scholar_id: Uuid = sqlx::query_scalar(
+        "SELECT id FROM cohort_early_warning.scholars \
+         WHERE org_id = $1 AND email = $2 AND deleted_at IS NULL",
+    )
+    .bind(org_id)
+    .bind(email)
+    .fetch_optional(pool)
+    .await?
+    .with_context(|| format!("no active scholar found with email {email}"))?;
+
+    let result = sqlx::query(
+        "UPDATE cohort_early_warning.sent_alerts \
+         SET acknowledged_at = NOW(), acknowledged_by = $2 \
+         WHERE scholar_id = $1 AND acknowledged_at IS NULL",
+    )
+    .bind(scholar_id)
+    .bind(by)
+    .execute(pool)
+    .await?;
+    Ok(result.rows_affected())
+}
+
+/// Condition shared by the escalation queries: alerts at or above `$2` tiers, sent more than `$3`
+/// hours ago, that nobody acknowledged and that haven't been escalated yet.
+const DUE_FOR_ESCALATION: &str = "sa.org_id = $1 AND sa.tier = ANY($2) \
+     AND sa.sent_at <= NOW() - make_interval(hours => $3) \
+     AND sa.acknowledged_at IS NULL AND sa.escalated_at IS NULL";
+
+fn tiers_from(minimum: Tier) -> Vec<&'static str> {
+    <Tier as clap::ValueEnum>::value_variants()
+        .iter()
+        .filter(|tier| **tier >= minimum)
+        .map(|tier| tier.as_str())
+        .collect()
+}
+
+/// Emails of scholars with an alert at or above `tier` left unacknowledged for `after_hours`.
+pub async fn due_escalations(
+    pool: &PgPool,
+    org_id: Uuid,
+    tier: Tier,
+    after_hours: i64,
+) -> anyhow::Result<Vec<String>> {
+    Ok(sqlx::query_scalar(&format!(
+        "SELECT DISTINCT sc.email FROM cohort_early_warning.sent_alerts sa \
+         JOIN cohort_early_warning.scholars sc ON sc.id = sa.scholar_id \
+         WHERE {DUE_FOR_ESCALATION} ORDER BY sc.email"
+    ))
+    .bind(org_id)
+    .bind(tiers_from(tier))
+    .bind(after_hours as i32)
+    .fetch_all(pool)
+    .await?)
+}
+
+/// Stamps the due alerts about `emails` as escalated, so each alert escalates once.
+pub async fn mark_escalated(
+    pool: &PgPool,
+    org_id: Uuid,
+    tier: Tier,
+    after_hours: i64,
+    emails: &[&str],
+) -> anyhow::Result<()> {
+    sqlx::query(&format!(
+        "UPDATE cohort_early_warning.sent_alerts sa SET escalated_at = NOW() \
+         FROM cohort_early_warning.scholars sc \
+         WHERE sc.id = sa.scholar_id AND sc.email = ANY($4) AND {DUE_FOR_ESCALATION}"
+    ))
+    .bind(org_id)
+    .bind(tiers_from(tier))
+    .bind(after_hours as i32)
+    .bind(emails)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Signature sent in [`SIGNATURE_HEADER`]: the HMAC-SHA256 of `body` keyed with `secret`.
+pub fn sign(secret: &[u8], body: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("HMAC accepts keys of any length");
+    mac.update(body);
+    let digest: String = mac
+        .finalize()
+        .into_bytes()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect();
+    format!("sha256={digest}")
+}
+
+pub fn alert_text(org: &str, reason: AlertReason<'_>, alerts: &[ScholarScore]) -> String {
+    let lines: Vec<String> = alerts
+        .iter()
+        .map(|score| {
+            format!(
+                "- {} ({}, {}) score {:.2} {}",
+                score.scholar_name,
+                score.scholar_email,
+                score.cohort,
+                score.score,
+                crate::risk::tier_label(score)
+            )
+        })
+        .collect();
+    format!(
+        "{}\n{}",
+        headline(org, reason, alerts.len()),
+        lines.join("\n")
+    )
+}
+
+fn headline(org: &str, reason: AlertReason<'_>, count: usize) -> String {
+    match reason {
+        AlertReason::Tier => format!("Early warning for {org}: {count} scholars need attention"),
+        AlertReason::Rule(rule) => {
+            format!(
+                "Early warning for {org}: {count} scholars matched rule {}",
+                rule.name
+            )
+        }
+        AlertReason::Unacknowledged(hours) => format!(
+            "Escalation for {org}: alerts about {count} scholars unacknowledged after {hours} hours"
+        ),
+    }
+}
+
+/// Teams message wrapping an Adaptive Card: a heading plus one fact per scholar.
+pub fn teams_card(payload: &AlertPayload<'_>) -> serde_json::Value {
+    let facts: Vec<serde_json::Value> = payload
+        .alerts
+        .iter()
+        .map(|Alert { score, .. }| {
+            serde_json::json!({
+                "title": format!("{} ({}, {})", score.scholar_name, score.scholar_email, score.cohort),
+                "value": format!("score {:.2} {}", score.score, crate::risk::tier_label(score)),
+            })
+        })
+        .collect();
+    serde_json::json!({
+        "type": "message",
+        "attachments": [{
+            "contentType": "application/vnd.microsoft.card.adaptive",
+            "content": {
+                "$schema": "http://adaptivecards.io/schemas/adaptive-card.json",
+                "type": "AdaptiveCard",
+                "version": "1.4",
+                "body": [
+                    {
+                        "type": "TextBlock",
+                        "text": headline(payload.org, payload.reason, payload.alerts.len()),
+                        "weight": "Bolder",
+                        "size": "Medium",
+                        "wrap": true,
+                    },
+                    { "type": "FactSet", "facts": facts },
+                ],
+            },
+        }],
+    })
+}
+
+async fn send(
+    client: &reqwest::Client,
+    channel: &ChannelConfig,
+    payload: &AlertPayload<'_>,
+) -> anyhow::Result<()> {
+    if channel.kind == ChannelKind::Sms {
+        return send_sms(client, channel, payload).await;
+    }
+    let url_env = channel.url_env.as_deref().context("url_env is not set")?;
+    let url = std::env::var(url_env).with_context(|| format!("{url_env} must be set"))?;
+    match channel.kind {
+        ChannelKind::Webhook => {
+            let body = serde_json::to_vec(payload)?;
+            let mut request = client
+                .post(&url)
+                .header(reqwest::header::CONTENT_TYPE, "application/json");
+            if let Some(secret_env) = &channel.secret_env {
+                let secret = std::env::var(secret_env)
+                    .with_context(|| format!("{secret_env} must be set"))?;
+                request = request.header(SIGNATURE_HEADER, sign(secret.as_bytes(), &body));
+            }
+            request.body(body).send().await?.error_for_status()?;
+        }
+        ChannelKind::Teams => {
+            client
+                .post(&url)
+                .json(&teams_card(payload))
+                .send()
+                .await?
+                .error_for_status()?;
+        }
+        ChannelKind::Slack => {
+            client
+                .post(&url)
+                .json(&serde_json::json!({ "text": payload.text }))
+                .send()
+                .await?
+                .error_for_status()?;
+        }
+        ChannelKind::Sms => unreachable!("handled above"),
+    }
+    Ok(())
+}
+
+/// Texts the critical-tier scholars in `payload` to each recipient outside their quiet hours.
+/// Succeeds without sending when nobody is critical or every recipient is quiet.
+async fn send_sms(
+    client: &reqwest::Client,
+    channel: &ChannelConfig,
+    payload: &AlertPayload<'_>,
+) -> anyhow::Result<()> {
+    let Some(body) = sms_text(payload) else {
+        return Ok(());
+    };
+    let env = |name: &Option<String>| -> anyhow::Result<String> {
+        let name = name
+            .as_deref()
+            .context("sms channel is missing a Twilio setting")?;
+        std::env::var(name).with_context(|| format!("{name} must be set"))
+    };
+    let account_sid = env(&channel.account_sid_env)?;
+    let auth_token = env(&channel.auth_token_env)?;
+    let url = match &channel.url_env {
+        Some(_) => env(&channel.url_env)?,
+        None => format!("{TWILIO_API}/Accounts/{account_sid}/Messages.json"),
+    };
+    let from = channel
+        .from
+        .as_deref()
+        .context("sms channel has no from number")?;
+
+    let now = Utc::now();
+    for recipient in &channel.recipients {
+        if recipient.is_quiet(now) {
+            continue;
+        }
+        client
+            .post(&url)
+            .basic_auth(&account_sid, Some(&auth_token))
+            .form(&[
+                ("To", recipient.number.as_str()),
+                ("From", from),
+                ("Body", &body),
+            ])
+            .send()
+            .await?
+            .error_for_status()
+            .with_context(|| format!("texting {}", recipient.number))?;
+    }
+    Ok(())
+}
+
+/// Short message listing only the critical-tier scholars, or `None` when there are none.
+fn sms_text(payload: &AlertPayload<'_>) -> Option<String> {
+    let critical: Vec<&ScholarScore> = payload
+        .alerts
+        .iter()
+        .map(|alert| alert.score)
+        .filter(|score| score.tier == Tier::Critical)
+        .collect();
+    if critical.is_empty() {
+        return None;
+    }
+    let mut text = headline(payload.org, payload.reason, critical.len());
+    for score in critical {
+        text.push_str(&format!(
+            "\n{} ({}) {:.1}",
+            score.scholar_name, score.cohort, score.score
+        ));
+    }
+    if text.chars().count() > SMS_MAX_CHARS {
+        text = text.chars().take(SMS_MAX_CHARS - 1).collect();
+        text.push('…');
+    }
+    Some(text)
+}
+
+/// Groups `alerts` by destination: scholars whose advisor has a channel go to that channel alone,
+/// and everyone else to the org-wide `fallback` channels. Groups come fallback first, then by
+/// advisor channel name.
+pub async fn route(
+    pool: &PgPool,
+    org_id: Uuid,
+    alerts: &[ScholarScore],
+    fallback: &[String],
+) -> anyhow::Result<Vec<(Vec<String>, Vec<ScholarScore>)>> {
+    let emails: Vec<&str> = alerts
+        .iter()
+        .map(|score| score.scholar_email.as_str())
+        .collect();
+    let advisor_channels: HashMap<String, String> = sqlx::query_as(
+        "SELECT sc.email, a.channel FROM cohort_early_warning.scholars sc \
+         JOIN cohort_early_warning.advisors a ON a.id = sc.advisor_id \
+         WHERE sc.org_id = $1 AND sc.email = ANY($2) AND sc.deleted_at IS NULL \
+         AND a.channel IS NOT NULL",
+    )
+    .bind(org_id)
+    .bind(&emails)
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .collect();
+    Ok(split_by_advisor(alerts, &advisor_channels, fallback))
+}
+
+/// [`route`]'s grouping, given each routed scholar's advisor channel by email.
+fn split_by_advisor(
+    alerts: &[ScholarScore],
+    advisor_channels: &HashMap<String, String>,
+    fallback: &[String],
+) -> Vec<(Vec<String>, Vec<ScholarScore>)> {
+    let mut unassigned = Vec::new();
+    let mut by_channel: BTreeMap<&str, Vec<ScholarScore>> = BTreeMap::new();
+    for score in alerts {
+        match advisor_channels.get(&score.scholar_email) {
+            Some(channel) => by_channel.entry(channel).or_default().push(score.clone()),
+            None => unassigned.push(score.clone()),
+        }
+    }
+    let mut groups = Vec::new();
+    if !unassigned.is_empty() {
+        groups.push((fallback.to_vec(), unassigned));
+    }
+    groups.extend(
+        by_channel
+            .into_iter()
+            .map(|(channel, scores)| (vec![channel.to_string()], scores)),
+    );
+    groups
+}
+
+/// Sends tier or rule alerts the way `watch` does: routed by advisor (see [`route`]) with each
+/// scholar's signals over the scoring window, and recorded for the cooldown and escalation.
+pub async fn deliver(
+    pool: &PgPool,
+    config: &Config,
+    org: &str,
+    org_id: Uuid,
+    reason: AlertReason<'_>,
+    alerts: &[ScholarScore],
+) -> anyhow::Result<Vec<(String, anyhow::Result<()>)>> {
+    // Rules without channels of their own fall back to the [watch] ones.
+    let (rule, fallback, since_days) = match reason {
+        AlertReason::Rule(rule) if !rule.channels.is_empty() => {
+            (Some(rule.name.as_str()), &rule.channels, rule.since_days)
+        }
+        AlertReason::Rule(rule) => (
+            Some(rule.name.as_str()),
+            &config.watch.channels,
+            rule.since_days,
+        ),
+        _ => (None, &config.watch.channels, config.watch.since_days),
+    };
+    let signals = contributing_signals(pool, org_id, since_days, alerts).await?;
+    let mut deliveries = Vec::new();
+    for (channels, group) in route(pool, org_id, alerts, fallback).await? {
+        let payload = AlertPayload::new(org, reason, &group, &signals, &config.playbooks);
+        let sent = dispatch(pool, org_id, config, &channels, &payload).await?;
+        record_sent(pool, org_id, rule, &group, &sent).await?;
+        deliveries.extend(sent);
+    }
+    Ok(deliveries)
+}
+
+/// Sends `payload` to each named channel, returning every channel's outcome so one failing
+/// endpoint doesn't stop the others. Network failures and server errors are retried with a
+/// growing delay, and every delivery is recorded for `notifications list`.
+pub async fn dispatch(
+    pool: &PgPool,
+    org_id: Uuid,
+    config: &Config,
+    channels: &[String],
+    payload: &AlertPayload<'_>,
+) -> anyhow::Result<Vec<(String, anyhow::Result<()>)>> {
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(15))
+        .build()
+        .unwrap_or_default();
+
+    let mut outcomes = Vec::new();
+    for name in channels {
+        let mut attempts = 1;
+        let outcome = match config.channel(name) {
+            Ok(channel) => loop {
+                let result = send(&client, channel, payload).await;
+                match result {
+                    Err(err) if attempts < SEND_ATTEMPTS && is_transient(&err) => {
+                        tokio::time::sleep(RETRY_DELAY * attempts).await;
+                        attempts += 1;
+                    }
+                    result => break result.with_context(|| format!("channel {name}")),
+                }
+            },
+            Err(err) => Err(err),
+        };
+        record_notification(pool, org_id, name, payload, attempts, &outcome).await?;
+        outcomes.push((name.clone(), outcome));
+    }
+    Ok(outcomes)
+}
+
+/// Whether a failed send is worth retrying: the endpoint was unreachable, timed out, throttled us,
+/// or failed on its side. Missing settings and rejected requests fail the same way every time.
+fn is_transient(err: &anyhow::Error) -> bool {
+    err.chain()
+        .find_map(|cause| cause.downcast_ref::<reqwest::Error>())
+        .is_some_and(|err| {
+            err.status().is_none_or(|status| {
+                status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS
+            })
+        })
+}
+
+async fn record_notification(
+    pool: &PgPool,
+    org_id: Uuid,
+    channel: &str,
+    payload: &AlertPayload<'_>,
+    attempts: u32,
+    outcome: &anyhow::Result<()>,
+) -> anyhow::Result<()> {
+    let emails: Vec<&str> = payload
+        .alerts
+        .iter()
+        .map(|alert| alert.score.scholar_email.as_str())
+        .collect();
+    let (status, error) = match outcome {
+        Ok(()) => (NotificationStatus::Sent, None),
+        Err(err) => (NotificationStatus::Failed, Some(format!("{err:#}"))),
+    };
+    sqlx::query(
+        "WITH notification AS ( \
+             INSERT INTO cohort_early_warning.notifications \
+             (id, org_id, channel, reason, rule, summary, status, attempts, error) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9) RETURNING id \
+         ) \
+         INSERT INTO cohort_early_warning.notification_scholars (notification_id, scholar_id) \
+         SELECT notification.id, sc.id \
+         FROM notification \
+         JOIN cohort_early_warning.scholars sc ON sc.org_id = $2 AND sc.email = ANY($10)",
+    )
+    .bind(Uuid::new_v4())
+    .bind(org_id)
+    .bind(channel)
+    .bind(payload.reason.as_str())
+    .bind(payload.rule)
+    .bind(payload.text.lines().next().unwrap_or_default())
+    .bind(status.as_str())
+    .bind(attempts as i32)
+    .bind(error)
+    .bind(&emails)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum NotificationStatus {
+    Sent,
+    Failed,
+}
+
+impl NotificationStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Sent => "sent",
+            Self::Failed => "failed",
+        }
+    }
+}
+
+/// One recorded delivery of an alert to a channel.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct Notification {
+    pub sent_at: DateTime<Utc>,
+    pub channel: String,
+    pub reason: String,
+    pub rule: Option<String>,
+    pub summary: String,
+    pub status: String,
+    pub attempts: i32,
+    pub error: Option<String>,
+    /// Emails of the scholars the alert was about.
+    pub scholars: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct NotificationQuery {
+    pub since: Option<DateTime<Utc>>,
+    /// Only notifications about this scholar.
+    pub email: Option<String>,
+    pub channel: Option<String>,
+    pub status: Option<NotificationStatus>,
+    pub limit: i64,
+}
+
+/// Recorded deliveries matching `filter`, newest first.
+pub async fn list_notifications(
+    pool: &PgPool,
+    org_id: Uuid,
+    filter: &NotificationQuery,
+) -> anyhow::Result<Vec<Notification>> {
+    let mut query: QueryBuilder<Postgres> = QueryBuilder::new(
+        "SELECT n.sent_at, n.channel, n.reason, n.rule, n.summary, n.status, n.attempts, n.error, \
+         ARRAY(SELECT sc.email FROM cohort_early_warning.notification_scholars ns \
+               JOIN cohort_early_warning.scholars sc ON sc.id = ns.scholar_id \
+               WHERE ns.notification_id = n.id ORDER BY sc.email) AS scholars \
+         FROM cohort_early_warning.notifications n WHERE n.org_id = ",
+    );
+    query.push_bind(org_id);
+    if let Some(since) = filter.since {
+        query.push(" AND n.sent_at >= ");
+        query.push_bind(since);
+    }
+    if let Some(email) = &filter.email {
+        query.push(
+            " AND n.id IN (SELECT ns.notification_id \
+             FROM cohort_early_warning.notification_scholars ns \
+             JOIN cohort_early_warning.scholars sc ON sc.id = ns.scholar_id WHERE sc.email = ",
+        );
+        query.push_bind(email.clone());
+        query.push(")");
+    }
+    if let Some(channel) = &filter.channel {
+        query.push(" AND n.channel = ");
+        query.push_bind(channel.clone());
+    }
+    if let Some(status) = filter.status {
+        query.push(" AND n.status = ");
+        query.push_bind(status.as_str());
+    }
+    query.push(" ORDER BY n.sent_at DESC, n.id LIMIT ");
+    query.push_bind(filter.limit);
+
+    let rows = query.build().fetch_all(pool).await?;
+    Ok(rows
+        .into_iter()
+        .map(|row| Notification {
+            sent_at: row.get("sent_at"),
+            channel: row.get("channel"),
+            reason: row.get("reason"),
+            rule: row.get("rule"),
+            summary: row.get("summary"),
+            status: row.get("status"),
+            attempts: row.get("attempts"),
+            error: row.get("error"),
+            scholars: row.get("scholars"),
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn alert(email: &str) -> ScholarScore {
+        ScholarScore {
+            scholar_name: "Avery Lee".to_string(),
+            scholar_email: email.to_string(),
+            cohort: "2026".to_string(),
+            score: 12.5,
+            signal_count: 4,
+            advisor: None,
+            tier: Tier::Critical,
+            escalated_by: None,
+            collapsed_signals: 0,
+            percentile: 0.0,
+            cohort_percentile: 0.0,
+            last_contact: None,
+            last_signal: None,
+        }
+    }
+
+    #[test]
+    fn routes_each_caseload_to_its_advisor_and_the_rest_to_the_fallback() {
+        let alerts = [
+            alert("a@example.org"),
+            alert("b@example.org"),
+            alert("c@example.org"),
+            alert("d@example.org"),
+        ];
+        let advisor_channels = HashMap::from([
+            ("a@example.org".to_string(), "jordan".to_string()),
+            ("c@example.org".to_string(), "jordan".to_string()),
+            ("d@example.org".to_string(), "amara".to_string()),
+        ]);
+        let groups: Vec<(String, String)> =
+            split_by_advisor(&alerts, &advisor_channels, &["ops".to_string()])
+                .into_iter()
+                .map(|(channels, scores)| {
+                    let emails: Vec<String> = scores
+                        .into_iter()
+                        .map(|score| score.scholar_email)
+                        .collect();
+                    (channels.join(","), emails.join(","))
+                })
+                .collect();
+        let expected = [
+            ("ops", "b@example.org"),
+            ("amara", "d@example.org"),
+            ("jordan", "a@example.org,c@example.org"),
+        ];
+        assert_eq!(
+            groups,
+            expected.map(|(channel, emails)| (channel.to_string(), emails.to_string()))
+        );
+    }
+
+    #[test]
+    fn teams_card_lists_each_scholar_as_a_fact() {
+        let alerts = [alert("avery@example.org")];
+        let signals = HashMap::new();
+        let card = teams_card(&AlertPayload::new(
+            "district-9",
+            AlertReason::Tier,
+            &alerts,
+            &signals,
+            &[],
+        ));
+        let content = &card["attachments"][0]["content"];
+        assert_eq!(content["type"], "AdaptiveCard");
+        assert_eq!(
+            content["body"][0]["text"],
+            "Early warning for district-9: 1 scholars need attention"
+        );
+        assert_eq!(
+            content["body"][1]["facts"][0],
+            serde_json::json!({
+                "title": "Avery Lee (avery@example.org, 2026)",
+                "value": "score 12.50 [critical]",
+            })
+        );
+    }
+
+    #[test]
+    fn escalations_say_how_long_alerts_went_unacknowledged() {
+        let signals = HashMap::new();
+        let payload = AlertPayload::new(
+            "district-9",
+            AlertReason::Unacknowledged(24),
+            &[],
+            &signals,
+            &[],
+        );
+        let body = serde_json::to_value(&payload).unwrap();
+        assert_eq!(body["unacknowledged_hours"], 24);
+        assert!(body.get("rule").is_none());
+        assert!(payload.text.starts_with(
+            "Escalation for district-9: alerts about 0 scholars unacknowledged after 24 hours"
+        ));
+    }
+
+    #[test]
+    fn payloads_carry_next_steps_from_matching_playbooks() {
+        let today = Utc::now().date_naive();
+        let signals = HashMap::from([(
+            "avery@example.org".to_string(),
+            vec![AlertSignal {
+                signal_type: "wellbeing".to_string(),
+                severity: 5,
+                occurred_at: today,
+            }],
+        )]);
+        let playbooks = [Playbook {
+            name: "wellbeing".to_string(),
+            action: "Refer to counseling".to_string(),
+            signal_type: Some("wellbeing".to_string()),
+            min_severity: Some(4),
+            ..Playbook::default()
+        }];
+        let alerts = [alert("avery@example.org"), alert("jules@example.org")];
+        let payload = AlertPayload::new(
+            "district-9",
+            AlertReason::Tier,
+            &alerts,
+            &signals,
+            &playbooks,
+        );
+        let body = serde_json::to_value(&payload).unwrap();
+        assert_eq!(
+            body["alerts"][0]["next_steps"],
+            serde_json::json!([{
+                "playbook": "wellbeing",
+                "action": "Refer to counseling",
+                "reason": "a wellbeing signal with severity >= 4 within 14 days",
+            }])
+        );
+        assert!(body["alerts"][1].get("next_steps").is_none());
+    }
+
+    #[test]
+    fn texts_list_only_critical_scholars() {
+        let signals = HashMap::new();
+        let high = ScholarScore {
+            tier: Tier::High,
+            ..alert("jules@example.org")
+        };
+        let alerts = [alert("avery@example.org"), high.clone()];
+        let payload = AlertPayload::new("district-9", AlertReason::Tier, &alerts, &signals, &[]);
+        assert_eq!(
+            sms_text(&payload).unwrap(),
+            "Early warning for district-9: 1 scholars need attention\nAvery Lee (2026) 12.5"
+        );
+
+        let alerts = [high];
+        let payload = AlertPayload::new("district-9", AlertReason::Tier, &alerts, &signals, &[]);
+        assert_eq!(sms_text(&payload), None);
+    }
+
+    #[tokio::test]
+    async fn retries_only_failures_that_may_pass_next_time() {
+        let refused = reqwest::Client::new()
+            .get("http://127.0.0.1:1/")
+            .send()
+            .await
+            .unwrap_err();
+        assert!(is_transient(
+            &anyhow::Error::from(refused).context("channel ops")
+        ));
+        assert!(!is_transient(&anyhow::anyhow!(
+            "CEW_OPS_WEBHOOK_URL must be set"
+        )));
+    }
+
+    #[test]
+    fn signs_bodies_with_hmac_sha256() {
+        // RFC 4231, test case 2.
+        assert_eq!(
+            sign(b"Jefe", b"what do ya want for nothing?"),
+            "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+}
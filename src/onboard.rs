@@ -0,0 +1,391 @@
+use std::io::{BufRead, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context};
+use chrono::NaiveDate;
+
+use crate::config::Config;
+use crate::import::{ChunkedReader, ImportLimits, SourceMapping};
+use crate::risk::{ScoringConfig, TierThresholds};
+
+/// Columns every source must provide, either under our name or through a mapping.
+pub const REQUIRED_COLUMNS: [&str; 6] = [
+    "full_name",
+    "email",
+    "signal_type",
+    "severity",
+    "note",
+    "occurred_at",
+];
+
+/// Columns a source may leave out.
+pub const OPTIONAL_COLUMNS: [&str; 4] = ["source_key", "tags", "submitted_by", "attachments"];
+
+/// Asks questions on `output` and reads answers line by line from `input`.
+pub struct Prompter<R: BufRead, W: Write> {
+    input: R,
+    output: W,
+}
+
+impl<R: BufRead, W: Write> Prompter<R, W> {
+    pub fn new(input: R, output: W) -> Self {
+        Self { input, output }
+    }
+
+    /// Prints a line of commentary between questions.
+    pub fn say(&mut self, message: &str) -> anyhow::Result<()> {
+        writeln!(self.output, "{message}")?;
+        Ok(())
+    }
+
+    /// Returns the trimmed answer, or `default` (else an empty string) for a blank line.
+    pub fn ask(&mut self, question: &str, default: Option<&str>) -> anyhow::Result<String> {
+        match default {
+            Some(default) if !default.is_empty() => {
+                write!(self.output, "{question} [{default}]: ")?
+            }
+            _ => write!(self.output, "{question}: ")?,
+        }
+        self.output.flush()?;
+
+        let mut line = String::new();
+        if self.input.read_line(&mut line)? == 0 {
+            bail!("input ended before onboarding finished");
+        }
+        let answer = line.trim();
+        Ok(match (answer.is_empty(), default) {
+            (true, Some(default)) => default.to_string(),
+            _ => answer.to_string(),
+        })
+    }
+
+    /// Asks until `parse` accepts the answer, showing its error after each rejection.
+    pub fn ask_with<T>(
+        &mut self,
+        question: &str,
+        default: Option<&str>,
+        parse: impl Fn(&str) -> Result<T, String>,
+    ) -> anyhow::Result<T> {
+        loop {
+            let answer = self.ask(question, default)?;
+            match parse(&answer) {
+                Ok(value) => return Ok(value),
+                Err(message) => self.say(&format!("  {message}"))?,
+            }
+        }
+    }
+
+    pub fn confirm(&mut self, question: &str, default: bool) -> anyhow::Result<bool> {
+        let default = if default { "y" } else { "n" };
+        self.ask_with(question, Some(default), |answer| {
+            match answer.to_ascii_lowercase().as_str() {
+                "y" | "yes" => Ok(true),
+                "n" | "no" => Ok(false),
+                _ => Err("answer y or n".to_string()),
+            }
+        })
+    }
+}
+
+/// Everything `onboard` collected for a new cohort.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OnboardingPlan {
+    pub cohort: String,
+    pub start_date: Option<NaiveDate>,
+    pub source_name: String,
+    pub sample: PathBuf,
+    pub mapping: SourceMapping,
+    pub scoring: ScoringConfig,
+    /// Advisor emails and names to register.
+    pub advisors: Vec<(String, String)>,
+}
+
+impl OnboardingPlan {
+    /// `config` with this plan's scoring and source added, ready to write out.
+    pub fn apply_to(&self, config: &Config) -> Config {
+        let mut updated = config.clone();
+        updated.scoring = self.scoring.clone();
+        updated
+            .sources
+            .insert(self.source_name.clone(), self.mapping.clone());
+        updated
+    }
+}
+
+/// Walks the operator through the cohort, its source layout, scoring and advisors. Existing
+/// scoring settings in `config` are offered as defaults.
+pub fn interview<R: BufRead, W: Write>(
+    prompter: &mut Prompter<R, W>,
+    config: &Config,
+) -> anyhow::Result<OnboardingPlan> {
+    prompter.say("== Cohort ==")?;
+    let cohort = prompter.ask_with("Cohort name", None, not_blank)?;
+    let start_date = prompter.ask_with(
+        "Program start date (YYYY-MM-DD, blank to skip)",
+        None,
+        |answer| {
+            if answer.is_empty() {
+                return Ok(None);
+            }
+            NaiveDate::parse_from_str(answer, "%Y-%m-%d")
+                .map(Some)
+                .map_err(|_| format!("{answer} is not a YYYY-MM-DD date"))
+        },
+    )?;
+
+    prompter.say("== Source ==")?;
+    let source_name = prompter.ask_with("Source name", Some("sis"), |answer| {
+        let valid = !answer.is_empty()
+            && answer
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if valid {
+            Ok(answer.to_string())
+        } else {
+            Err("use letters, digits, - and _ only".to_string())
+        }
+    })?;
+    if config.sources.contains_key(&source_name)
+        && !prompter.confirm(&format!("Source {source_name} exists; replace it?"), false)?
+    {
+        bail!("onboarding cancelled; source {source_name} left unchanged");
+    }
+    let (sample, headers) = prompter.ask_with("Sample CSV from this source", None, |answer| {
+        let path = PathBuf::from(answer);
+        read_headers(&path)
+            .map(|headers| (path, headers))
+            .map_err(|err| format!("{err:#}"))
+    })?;
+    prompter.say(&format!("Columns found: {}", headers.join(", ")))?;
+    let mapping = ask_mapping(prompter, &headers, &cohort)?;
+
+    prompter.say("== Scoring ==")?;
+    let mut scoring = config.scoring.clone();
+    loop {
+        scoring.tiers = TierThresholds {
+            moderate: ask_threshold(prompter, "moderate", scoring.tiers.moderate)?,
+            high: ask_threshold(prompter, "high", scoring.tiers.high)?,
+            critical: ask_threshold(prompter, "critical", scoring.tiers.critical)?,
+        };
+        match scoring.validate(config.severity) {
+            Ok(()) => break,
+            Err(err) => prompter.say(&format!("  {err}"))?,
+        }
+    }
+    let current_dedup = scoring.staff_dedup_days.map(|days| days.to_string());
+    scoring.staff_dedup_days = prompter.ask_with(
+        "Collapse duplicate staff signals within N days (blank for off)",
+        current_dedup.as_deref(),
+        |answer| match answer {
+            "" => Ok(None),
+            days => days
+                .parse::<i64>()
+                .ok()
+                .filter(|days| *days >= 0)
+                .map(Some)
+                .ok_or_else(|| format!("{days} is not a number of days")),
+        },
+    )?;
+
+    prompter.say("== Advisors ==")?;
+    let mut advisors = Vec::new();
+    loop {
+        let email = prompter.ask("Advisor email (blank when done)", None)?;
+        if email.is_empty() {
+            break;
+        }
+        if !email.contains('@') {
+            prompter.say(&format!("  {email} is not an email address"))?;
+            continue;
+        }
+        let name = prompter.ask_with(&format!("Name for {email}"), None, not_blank)?;
+        advisors.push((email, name));
+    }
+
+    Ok(OnboardingPlan {
+        cohort,
+        start_date,
+        source_name,
+        sample,
+        mapping,
+        scoring,
+        advisors,
+    })
+}
+
+/// Asks which partner header feeds each of our columns, defaulting to a header with our name.
+fn ask_mapping<R: BufRead, W: Write>(
+    prompter: &mut Prompter<R, W>,
+    headers: &[String],
+    cohort: &str,
+) -> anyhow::Result<SourceMapping> {
+    let mut mapping = SourceMapping::default();
+    let pick = |required: bool| {
+        move |answer: &str| -> Result<Option<String>, String> {
+            if answer.is_empty() && !required {
+                return Ok(None);
+            }
+            if headers.iter().any(|header| header == answer) {
+                Ok(Some(answer.to_string()))
+            } else {
+                Err(format!("pick one of: {}", headers.join(", ")))
+            }
+        }
+    };
+    let default_for = |ours: &str| headers.iter().find(|header| *header == ours).cloned();
+
+    for ours in REQUIRED_COLUMNS {
+        let default = default_for(ours);
+        if let Some(theirs) = prompter.ask_with(
+            &format!("Column for {ours}"),
+            default.as_deref(),
+            pick(true),
+        )? {
+            if theirs != ours {
+                mapping.columns.insert(ours.to_string(), theirs);
+            }
+        }
+    }
+
+    let default = default_for("cohort");
+    match prompter.ask_with(
+        &format!("Column for cohort (blank to use {cohort} for every row)"),
+        default.as_deref(),
+        pick(false),
+    )? {
+        Some(theirs) if theirs != "cohort" => {
+            mapping.columns.insert("cohort".to_string(), theirs);
+        }
+        Some(_) => {}
+        None => mapping.cohort = Some(cohort.to_string()),
+    }
+
+    for ours in OPTIONAL_COLUMNS {
+        let default = default_for(ours);
+        if let Some(theirs) = prompter.ask_with(
+            &format!("Column for {ours} (blank if none)"),
+            default.as_deref(),
+            pick(false),
+        )? {
+            if theirs != ours {
+                mapping.columns.insert(ours.to_string(), theirs);
+            }
+        }
+    }
+
+    Ok(mapping)
+}
+
+fn ask_threshold<R: BufRead, W: Write>(
+    prompter: &mut Prompter<R, W>,
+    tier: &str,
+    current: f64,
+) -> anyhow::Result<f64> {
+    prompter.ask_with(
+        &format!("Minimum score for {tier}"),
+        Some(&current.to_string()),
+        |answer| {
+            answer
+                .parse::<f64>()
+                .map_err(|_| format!("{answer} is not a number"))
+        },
+    )
+}
+
+fn not_blank(answer: &str) -> Result<String, String> {
+    if answer.is_empty() {
+        Err("an answer is required".to_string())
+    } else {
+        Ok(answer.to_string())
+    }
+}
+
+fn read_headers(path: &Path) -> anyhow::Result<Vec<String>> {
+    let mut reader = csv::Reader::from_path(path)
+        .with_context(|| format!("failed to open {}", path.display()))?;
+    let headers: Vec<String> = reader.headers()?.iter().map(str::to_string).collect();
+    if headers.iter().all(|header| header.is_empty()) {
+        bail!("{} has no header row", path.display());
+    }
+    Ok(headers)
+}
+
+/// Parses every row of the sample through the plan's mapping, as `import --source` would.
+/// Returns the number of rows read.
+pub fn check_sample(plan: &OnboardingPlan) -> anyhow::Result<usize> {
+    let mut reader = ChunkedReader::open(&plan.sample, ImportLimits::default())?
+        .with_mapping(plan.mapping.clone());
+    while !reader.next_chunk()?.is_empty() {}
+    Ok(reader.rows_read())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    #[test]
+    fn interview_maps_partner_columns_and_collects_advisors() {
+        let sample = std::env::temp_dir().join(format!("cew-onboard-{}.csv", std::process::id()));
+        std::fs::write(
+            &sample,
+            "Student,Email,Kind,Level,Comment,Date,tags\n\
+             Ada Park,ada@example.edu,attendance,3,Missed two sessions,2026-09-01,\n",
+        )
+        .unwrap();
+
+        let answers = [
+            "2027",
+            "2026-09-01",
+            "",
+            "missing.csv",
+            sample.to_str().unwrap(),
+            "Student",
+            "Email",
+            "Kind",
+            "Level",
+            "Comment",
+            "Date",
+            "",
+            "",
+            "",
+            "",
+            "",
+            "10",
+            "",
+            "",
+            "4",
+            "",
+            "",
+            "2",
+            "lee@example.edu",
+            "Lee Moss",
+            "",
+        ]
+        .join("\n")
+            + "\n";
+        let mut output = Vec::new();
+        let mut prompter = Prompter::new(Cursor::new(answers), &mut output);
+        let plan = interview(&mut prompter, &Config::default()).unwrap();
+
+        assert_eq!(plan.cohort, "2027");
+        assert_eq!(plan.source_name, "sis");
+        assert_eq!(plan.mapping.columns["full_name"], "Student");
+        assert_eq!(plan.mapping.columns.len(), 6);
+        assert_eq!(plan.mapping.cohort.as_deref(), Some("2027"));
+        assert_eq!(plan.scoring.tiers.moderate, 4.0);
+        assert_eq!(plan.scoring.staff_dedup_days, Some(2));
+        assert_eq!(
+            plan.advisors,
+            vec![("lee@example.edu".to_string(), "Lee Moss".to_string())]
+        );
+        assert_eq!(check_sample(&plan).unwrap(), 1);
+
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("failed to open missing.csv"));
+        assert!(output.contains("moderate <= high <= critical"));
+
+        std::fs::remove_file(sample).unwrap();
+    }
+}
@@ -0,0 +1,309 @@
+use std::collections::BTreeMap;
+
+use chrono::{Duration, NaiveDate};
+
+use crate::models::{Outcome, OutcomeType, SignalRecord};
+use crate::risk::{self, ScoringConfig, Tier};
+
+/// Where one scholar ended up, next to the risk they showed beforehand.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AssessedScholar {
+    pub email: String,
+    /// Date the tier was scored as of: `lead_days` before the outcome.
+    pub as_of: NaiveDate,
+    pub score: f64,
+    pub tier: Tier,
+    pub withdrew: bool,
+    pub graduated: bool,
+    /// Most recent GPA recorded.
+    pub gpa: Option<f64>,
+}
+
+/// Scores each scholar with outcomes as of `lead_days` before their withdrawal or graduation, or
+/// before their latest GPA when they have neither, using the `since_days` of signals up to then.
+/// Only the earliest withdrawal or graduation counts.
+pub fn assess(
+    outcomes: &[Outcome],
+    signals: &[SignalRecord],
+    lead_days: i64,
+    since_days: i64,
+    scoring: &ScoringConfig,
+) -> Vec<AssessedScholar> {
+    let mut by_scholar: BTreeMap<&str, Vec<&Outcome>> = BTreeMap::new();
+    for outcome in outcomes {
+        by_scholar
+            .entry(&outcome.scholar_email)
+            .or_default()
+            .push(outcome);
+    }
+    let mut signals_of: BTreeMap<&str, Vec<SignalRecord>> = BTreeMap::new();
+    for signal in signals {
+        if by_scholar.contains_key(signal.scholar_email.as_str()) {
+            signals_of
+                .entry(&signal.scholar_email)
+                .or_default()
+                .push(signal.clone());
+        }
+    }
+
+    by_scholar
+        .into_iter()
+        .map(|(email, outcomes)| {
+            let ending = outcomes
+                .iter()
+                .filter(|outcome| outcome.outcome_type != OutcomeType::Gpa)
+                .min_by_key(|outcome| outcome.occurred_on);
+            let latest_gpa = outcomes
+                .iter()
+                .filter(|outcome| outcome.outcome_type == OutcomeType::Gpa)
+                .max_by_key(|outcome| outcome.occurred_on);
+            let reference = ending
+                .or(latest_gpa)
+                .map(|outcome| outcome.occurred_on)
+                .expect("every scholar listed has an outcome");
+            let as_of = reference - Duration::days(lead_days);
+
+            let prior: Vec<SignalRecord> = signals_of
+                .get(email)
+                .into_iter()
+                .flatten()
+                .filter(|signal| signal.occurred_at <= as_of)
+                .cloned()
+                .collect();
+            let score = risk::score_signals_with(&prior, since_days, as_of, scoring)
+                .into_iter()
+                .next();
+            AssessedScholar {
+                email: email.to_string(),
+                as_of,
+                score: score.as_ref().map_or(0.0, |score| score.score),
+                tier: score.map_or(Tier::Low, |score| score.tier),
+                withdrew: ending.is_some_and(|o| o.outcome_type == OutcomeType::Withdrawal),
+                graduated: ending.is_some_and(|o| o.outcome_type == OutcomeType::Graduation),
+                gpa: latest_gpa.and_then(|outcome| outcome.gpa),
+            }
+        })
+        .collect()
+}
+
+/// Pearson correlation of paired values; `None` with fewer than three pairs or no variance.
+pub fn correlation(pairs: &[(f64, f64)]) -> Option<f64> {
+    if pairs.len() < 3 {
+        return None;
+    }
+    let n = pairs.len() as f64;
+    let mean_x = pairs.iter().map(|(x, _)| x).sum::<f64>() / n;
+    let mean_y = pairs.iter().map(|(_, y)| y).sum::<f64>() / n;
+    let (mut covariance, mut var_x, mut var_y) = (0.0, 0.0, 0.0);
+    for (x, y) in pairs {
+        covariance += (x - mean_x) * (y - mean_y);
+        var_x += (x - mean_x).powi(2);
+        var_y += (y - mean_y).powi(2);
+    }
+    (var_x > 0.0 && var_y > 0.0).then(|| covariance / (var_x * var_y).sqrt())
+}
+
+fn share(count: usize, total: usize) -> String {
+    if total == 0 {
+        "0".to_string()
+    } else {
+        format!("{count} ({:.0}%)", count as f64 * 100.0 / total as f64)
+    }
+}
+
+/// Tabulates outcomes by prior tier, then compares withdrawal rates at and below `threshold` and
+/// correlates scores with withdrawal and GPA.
+pub fn render(
+    scholars: &[AssessedScholar],
+    threshold: Tier,
+    lead_days: i64,
+    since_days: i64,
+) -> String {
+    let mut rows = vec![["tier", "scholars", "withdrew", "graduated", "avg GPA"].map(String::from)];
+    for tier in [Tier::Critical, Tier::High, Tier::Moderate, Tier::Low] {
+        let members: Vec<&AssessedScholar> = scholars.iter().filter(|s| s.tier == tier).collect();
+        let gpas: Vec<f64> = members.iter().filter_map(|s| s.gpa).collect();
+        rows.push([
+            tier.as_str().to_string(),
+            members.len().to_string(),
+            share(members.iter().filter(|s| s.withdrew).count(), members.len()),
+            share(
+                members.iter().filter(|s| s.graduated).count(),
+                members.len(),
+            ),
+            match gpas.len() {
+                0 => "-".to_string(),
+                count => format!("{:.2}", gpas.iter().sum::<f64>() / count as f64),
+            },
+        ]);
+    }
+    let widths: Vec<usize> = (0..rows[0].len())
+        .map(|column| rows.iter().map(|row| row[column].len()).max().unwrap_or(0))
+        .collect();
+
+    let mut out = vec![
+        format!(
+            "Risk tier {lead_days} days before each scholar's outcome, from the {since_days} days \
+             of signals before that:"
+        ),
+        String::new(),
+    ];
+    for row in &rows {
+        let line: Vec<String> = row
+            .iter()
+            .zip(&widths)
+            .enumerate()
+            .map(|(column, (cell, width))| match column {
+                0 => format!("{cell:<width$}"),
+                _ => format!("{cell:>width$}"),
+            })
+            .collect();
+        out.push(line.join("  ").trim_end().to_string());
+    }
+
+    let rate = |at_risk: bool| {
+        let group: Vec<&AssessedScholar> = scholars
+            .iter()
+            .filter(|s| (s.tier >= threshold) == at_risk)
+            .collect();
+        (!group.is_empty())
+            .then(|| group.iter().filter(|s| s.withdrew).count() as f64 / group.len() as f64)
+    };
+    out.push(String::new());
+    match (rate(true), rate(false)) {
+        (Some(above), Some(below)) => {
+            let ratio = if below > 0.0 {
+                format!(" ({:.1}x)", above / below)
+            } else {
+                String::new()
+            };
+            out.push(format!(
+                "{} tier or above: {:.0}% withdrew, against {:.0}% below it{ratio}.",
+                capitalize(threshold.as_str()),
+                above * 100.0,
+                below * 100.0
+            ));
+        }
+        _ => out.push(format!(
+            "Every scholar with outcomes is on one side of the {} tier; no comparison possible.",
+            threshold.as_str()
+        )),
+    }
+
+    let withdrawal: Vec<(f64, f64)> = scholars
+        .iter()
+        .filter(|s| s.withdrew || s.graduated)
+        .map(|s| (s.score, if s.withdrew { 1.0 } else { 0.0 }))
+        .collect();
+    let gpa: Vec<(f64, f64)> = scholars
+        .iter()
+        .filter_map(|s| s.gpa.map(|gpa| (s.score, gpa)))
+        .collect();
+    let describe = |pairs: &[(f64, f64)]| match correlation(pairs) {
+        Some(r) => format!("r = {r:.2} (n = {})", pairs.len()),
+        None => format!("not enough data (n = {})", pairs.len()),
+    };
+    out.push(format!(
+        "Correlation of risk score with withdrawal: {}; with GPA: {}.",
+        describe(&withdrawal),
+        describe(&gpa)
+    ));
+    out.join("\n")
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    chars
+        .next()
+        .map(|first| first.to_uppercase().chain(chars).collect())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 5, day).unwrap()
+    }
+
+    fn outcome(email: &str, outcome_type: OutcomeType, day: u32, gpa: Option<f64>) -> Outcome {
+        Outcome {
+            id: Uuid::new_v4(),
+            scholar_name: email.to_string(),
+            scholar_email: email.to_string(),
+            cohort: "2026".to_string(),
+            outcome_type,
+            occurred_on: date(day),
+            gpa,
+            note: String::new(),
+        }
+    }
+
+    fn signal(email: &str, day: u32, severity: i32) -> SignalRecord {
+        SignalRecord {
+            scholar_id: Uuid::from_u128(u128::from(email.as_bytes()[0])),
+            scholar_name: email.to_string(),
+            scholar_email: email.to_string(),
+            cohort: "2026".to_string(),
+            signal_type: "attendance".to_string(),
+            severity,
+            occurred_at: date(day),
+            note: String::new(),
+            advisor: None,
+            submitted_by: None,
+            attachments: Vec::new(),
+            note_categories: Vec::new(),
+            note_sentiment: None,
+        }
+    }
+
+    #[test]
+    fn scores_each_scholar_before_their_outcome() {
+        let outcomes = [
+            outcome("a@x.org", OutcomeType::Withdrawal, 30, None),
+            outcome("a@x.org", OutcomeType::Gpa, 10, Some(1.8)),
+            outcome("b@x.org", OutcomeType::Graduation, 30, None),
+            outcome("b@x.org", OutcomeType::Gpa, 28, Some(3.6)),
+            outcome("c@x.org", OutcomeType::Gpa, 20, Some(3.0)),
+        ];
+        let signals = [
+            signal("a@x.org", 5, 5),
+            signal("a@x.org", 8, 5),
+            signal("a@x.org", 15, 5),
+            // After a's as-of date, so it must not count.
+            signal("a@x.org", 25, 5),
+            signal("b@x.org", 12, 2),
+        ];
+
+        let assessed = assess(&outcomes, &signals, 14, 30, &ScoringConfig::default());
+        assert_eq!(assessed.len(), 3);
+        let a = &assessed[0];
+        assert_eq!(a.as_of, date(16));
+        assert!(a.withdrew && !a.graduated);
+        assert_eq!(a.gpa, Some(1.8));
+        assert_eq!(a.tier, Tier::Critical, "{a:?}");
+        assert!(assessed[1].graduated && assessed[1].tier == Tier::Low);
+        // Only a GPA: scored before it, with no signals.
+        assert_eq!((assessed[2].as_of, assessed[2].score), (date(6), 0.0));
+
+        let table = render(&assessed, Tier::High, 14, 30);
+        assert!(
+            table.contains("critical         1  1 (100%)     0 (0%)     1.80"),
+            "{table}"
+        );
+        assert!(table.contains("High tier or above: 100% withdrew, against 0% below it."));
+        assert!(table.contains("with withdrawal: not enough data (n = 2)"));
+        assert!(table.contains("with GPA: r = -0.88 (n = 3)."), "{table}");
+    }
+
+    #[test]
+    fn correlation_needs_spread() {
+        let pairs = [(1.0, 2.0), (2.0, 4.0), (3.0, 6.0)];
+        assert!((correlation(&pairs).unwrap() - 1.0).abs() < 1e-9);
+        assert_eq!(correlation(&[(1.0, 1.0), (1.0, 2.0), (1.0, 3.0)]), None);
+        assert_eq!(correlation(&pairs[..2]), None);
+    }
+}
@@ -0,0 +1,259 @@
+use chrono::NaiveDate;
+use hmac::{Hmac, Mac};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use sha2::Sha256;
+
+use crate::export::{Column, ColumnType, ExportRecord, ExportRow};
+use crate::models::CohortAggregate;
+use crate::trends;
+
+/// Severity is bounded to 1..=5, so one signal can move a cell average by at most this much.
+const SEVERITY_RANGE: f64 = 4.0;
+
+#[derive(Debug, Clone)]
+pub struct PrivacyOptions {
+    /// Cells describing fewer scholars than this are suppressed entirely.
+    pub min_cell_size: i64,
+    /// Laplace privacy budget per released value; `None` disables noise.
+    pub epsilon: Option<f64>,
+    pub seed: Option<u64>,
+}
+
+impl Default for PrivacyOptions {
+    fn default() -> Self {
+        Self {
+            min_cell_size: 5,
+            epsilon: None,
+            seed: None,
+        }
+    }
+}
+
+/// A released aggregate cell; `None` values were suppressed.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ProtectedAggregate {
+    pub cohort: String,
+    pub signal_type: String,
+    pub scholar_count: Option<i64>,
+    pub signal_count: Option<i64>,
+    pub avg_severity: Option<f64>,
+}
+
+pub fn protect_aggregates(
+    aggregates: &[CohortAggregate],
+    options: &PrivacyOptions,
+) -> Vec<ProtectedAggregate> {
+    let mut rng = match options.seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_os_rng(),
+    };
+
+    aggregates
+        .iter()
+        .map(|aggregate| {
+            if aggregate.scholar_count < options.min_cell_size {
+                return ProtectedAggregate {
+                    cohort: aggregate.cohort.clone(),
+                    signal_type: aggregate.signal_type.clone(),
+                    scholar_count: None,
+                    signal_count: None,
+                    avg_severity: None,
+                };
+            }
+
+            let (scholar_count, signal_count, avg_severity) = match options.epsilon {
+                Some(epsilon) if epsilon > 0.0 => {
+                    let count_scale = 1.0 / epsilon;
+                    let severity_scale =
+                        SEVERITY_RANGE / (epsilon * aggregate.signal_count.max(1) as f64);
+                    (
+                        noisy_count(&mut rng, aggregate.scholar_count, count_scale),
+                        noisy_count(&mut rng, aggregate.signal_count, count_scale),
+                        (aggregate.avg_severity + laplace(&mut rng, severity_scale))
+                            .clamp(1.0, 5.0),
+                    )
+                }
+                _ => (
+                    aggregate.scholar_count,
+                    aggregate.signal_count,
+                    aggregate.avg_severity,
+                ),
+            };
+
+            ProtectedAggregate {
+                cohort: aggregate.cohort.clone(),
+                signal_type: aggregate.signal_type.clone(),
+                scholar_count: Some(scholar_count),
+                signal_count: Some(signal_count),
+                avg_severity: Some(avg_severity),
+            }
+        })
+        .collect()
+}
+
+fn noisy_count(rng: &mut StdRng, value: i64, scale: f64) -> i64 {
+    (value as f64 + laplace(rng, scale)).round().max(0.0) as i64
+}
+
+/// Samples Laplace(0, scale) by inverting the CDF.
+pub fn laplace(rng: &mut impl Rng, scale: f64) -> f64 {
+    let u: f64 = rng.random_range(-0.5..0.5);
+    -scale * u.signum() * (1.0 - 2.0 * u.abs()).ln()
+}
+
+/// Shortest salt `export --research` accepts; shorter ones make the hashed emails guessable.
+pub const MIN_SALT_LEN: usize = 16;
+
+/// Column order of `export --research`, shared by CSV headers, JSONL keys, and Parquet fields.
+pub const RESEARCH_COLUMNS: [Column; 9] = [
+    ("scholar", ColumnType::Text),
+    ("cohort", ColumnType::Text),
+    ("signal_type", ColumnType::Text),
+    ("severity", ColumnType::Int32),
+    ("week_of", ColumnType::Date),
+    ("tags", ColumnType::TextList),
+    ("submitted_by", ColumnType::OptionalText),
+    ("status", ColumnType::Text),
+    ("advisor", ColumnType::OptionalText),
+];
+
+/// A signal stripped of what identifies the people involved, for research partners. Field order
+/// must follow [`RESEARCH_COLUMNS`].
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct ResearchRow {
+    /// Salted hash of the scholar's email, the same on every row about them.
+    pub scholar: String,
+    pub cohort: String,
+    pub signal_type: String,
+    pub severity: i32,
+    /// Monday of the week the signal occurred in.
+    pub week_of: NaiveDate,
+    pub tags: Vec<String>,
+    /// Salted hash of the staff member who entered the signal by hand.
+    pub submitted_by: Option<String>,
+    pub status: String,
+    /// Salted hash of the advisor's name.
+    pub advisor: Option<String>,
+}
+
+impl ExportRecord for ResearchRow {
+    fn csv_record(&self) -> Vec<String> {
+        vec![
+            self.scholar.clone(),
+            self.cohort.clone(),
+            self.signal_type.clone(),
+            self.severity.to_string(),
+            self.week_of.to_string(),
+            self.tags.join(";"),
+            self.submitted_by.clone().unwrap_or_default(),
+            self.status.clone(),
+            self.advisor.clone().unwrap_or_default(),
+        ]
+    }
+}
+
+/// `row` with names, emails, and staff replaced by salted hashes, its date moved to the start of
+/// its week, and its note, source key, and attachments dropped. The same salt always yields the
+/// same hashes, so exports made with it can be joined; without the salt they can't be reversed by
+/// hashing guessed emails.
+pub fn research_row(row: &ExportRow, salt: &[u8]) -> ResearchRow {
+    ResearchRow {
+        scholar: salted_hash(salt, &row.email),
+        cohort: row.cohort.clone(),
+        signal_type: row.signal_type.clone(),
+        severity: row.severity,
+        week_of: trends::week_start(row.occurred_at),
+        tags: row.tags.clone(),
+        submitted_by: row.submitted_by.as_deref().map(|by| salted_hash(salt, by)),
+        status: row.status.clone(),
+        advisor: row.advisor.as_deref().map(|name| salted_hash(salt, name)),
+    }
+}
+
+/// The first 128 bits of the HMAC-SHA256 of `value` keyed with `salt`, in hex.
+fn salted_hash(salt: &[u8], value: &str) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(salt).expect("HMAC accepts keys of any length");
+    mac.update(value.as_bytes());
+    mac.finalize().into_bytes()[..16]
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn aggregate(scholar_count: i64) -> CohortAggregate {
+        CohortAggregate {
+            cohort: "2026".to_string(),
+            signal_type: "attendance".to_string(),
+            scholar_count,
+            signal_count: scholar_count * 2,
+            avg_severity: 3.0,
+        }
+    }
+
+    #[test]
+    fn suppresses_small_cells() {
+        let released =
+            protect_aggregates(&[aggregate(4), aggregate(12)], &PrivacyOptions::default());
+        assert!(released[0].scholar_count.is_none());
+        assert!(released[0].avg_severity.is_none());
+        assert_eq!(released[1].scholar_count, Some(12));
+        assert_eq!(released[1].signal_count, Some(24));
+    }
+
+    #[test]
+    fn seeded_noise_is_reproducible_and_bounded() {
+        let options = PrivacyOptions {
+            min_cell_size: 5,
+            epsilon: Some(0.5),
+            seed: Some(42),
+        };
+        let first = protect_aggregates(&[aggregate(20)], &options);
+        let second = protect_aggregates(&[aggregate(20)], &options);
+        assert_eq!(first[0].scholar_count, second[0].scholar_count);
+        assert!(first[0].scholar_count.unwrap() >= 0);
+        let severity = first[0].avg_severity.unwrap();
+        assert!((1.0..=5.0).contains(&severity));
+    }
+
+    #[test]
+    fn research_rows_hash_identifiers_and_bucket_dates() {
+        let row = ExportRow {
+            full_name: "Ada Park".to_string(),
+            email: "ada@example.edu".to_string(),
+            cohort: "2026".to_string(),
+            signal_type: "attendance".to_string(),
+            severity: 3,
+            note: "Missed lab".to_string(),
+            occurred_at: NaiveDate::from_ymd_opt(2026, 2, 12).unwrap(),
+            source_key: "sis-1".to_string(),
+            tags: vec!["tutoring".to_string()],
+            submitted_by: Some("lee".to_string()),
+            attachments: Vec::new(),
+            status: "active".to_string(),
+            advisor: None,
+        };
+        let salt = b"a salt for the 2026 study";
+        let research = research_row(&row, salt);
+        assert_eq!(research.scholar.len(), 32);
+        assert_eq!(research, research_row(&row, salt));
+        assert_ne!(
+            research.scholar,
+            research_row(&row, b"another salt").scholar
+        );
+        assert_eq!(
+            research.week_of,
+            NaiveDate::from_ymd_opt(2026, 2, 9).unwrap()
+        );
+
+        let cells = research.csv_record().join(",");
+        assert_eq!(cells.split(',').count(), RESEARCH_COLUMNS.len());
+        for identifying in ["Ada", "ada@", "Missed", "sis-1", "lee", "2026-02-12"] {
+            assert!(!cells.contains(identifying), "{identifying} leaked");
+        }
+    }
+}
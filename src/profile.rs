@@ -0,0 +1,390 @@
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::config::Config;
+use crate::db;
+use crate::export::ExportRow;
+use crate::import::truncate_note;
+use crate::models::{Intervention, Scholar, ScholarScore, ScoreHistoryPoint};
+use crate::notify::{self, Notification};
+use crate::risk;
+use crate::watch;
+
+/// Characters of a note shown on its timeline line.
+const NOTE_CHARS: usize = 120;
+
+/// What a timeline entry records.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EntryKind {
+    Signal,
+    Score,
+    Intervention,
+    Notification,
+}
+
+impl EntryKind {
+    fn label(self) -> &'static str {
+        match self {
+            EntryKind::Signal => "signal",
+            EntryKind::Score => "score",
+            EntryKind::Intervention => "intervention",
+            EntryKind::Notification => "alert",
+        }
+    }
+}
+
+/// One line of a scholar's timeline. Signals and interventions are dated by day; scoring runs,
+/// closed interventions, and alerts also carry a UTC time.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct TimelineEntry {
+    pub date: NaiveDate,
+    pub time: Option<NaiveTime>,
+    pub kind: EntryKind,
+    pub summary: String,
+}
+
+impl TimelineEntry {
+    fn at(at: DateTime<Utc>, kind: EntryKind, summary: String) -> Self {
+        Self {
+            date: at.date_naive(),
+            time: Some(at.time()),
+            kind,
+            summary,
+        }
+    }
+}
+
+/// A scholar's roster entry, current score, and timeline, as `scholar show` prints them.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct Profile {
+    pub scholar: Scholar,
+    /// Score over `[defaults] since_days`; `None` without signals in that window.
+    pub score: Option<ScholarScore>,
+    /// Oldest first.
+    pub timeline: Vec<TimelineEntry>,
+}
+
+/// Signals, score changes, interventions, and alerts merged oldest first. Scoring runs only
+/// appear when the scholar's tier or score moved since the run before; day-dated entries come
+/// before timed ones on the same day.
+pub fn timeline(
+    signals: &[ExportRow],
+    snapshots: &[ScoreHistoryPoint],
+    interventions: &[Intervention],
+    notifications: &[Notification],
+) -> Vec<TimelineEntry> {
+    let mut entries = Vec::new();
+    for signal in signals {
+        let mut summary = format!("{} severity {}", signal.signal_type, signal.severity);
+        if !signal.note.is_empty() {
+            summary.push_str(&format!(": {}", truncate_note(&signal.note, NOTE_CHARS)));
+        }
+        if let Some(by) = &signal.submitted_by {
+            summary.push_str(&format!(" (by {by})"));
+        }
+        entries.push(TimelineEntry {
+            date: signal.occurred_at,
+            time: None,
+            kind: EntryKind::Signal,
+            summary,
+        });
+    }
+
+    let mut previous: Option<&ScoreHistoryPoint> = None;
+    for point in snapshots {
+        let summary = match previous {
+            None => format!(
+                "{:.2} ({}) in the first {} run",
+                point.score,
+                point.tier.as_str(),
+                point.trigger
+            ),
+            Some(before)
+                if before.tier == point.tier
+                    && format!("{:.2}", before.score) == format!("{:.2}", point.score) =>
+            {
+                previous = Some(point);
+                continue;
+            }
+            Some(before) if before.tier == point.tier => format!(
+                "{:.2} -> {:.2} ({}) in a {} run",
+                before.score,
+                point.score,
+                point.tier.as_str(),
+                point.trigger
+            ),
+            Some(before) => format!(
+                "{:.2} -> {:.2}, {} -> {} in a {} run",
+                before.score,
+                point.score,
+                before.tier.as_str(),
+                point.tier.as_str(),
+                point.trigger
+            ),
+        };
+        entries.push(TimelineEntry::at(point.ran_at, EntryKind::Score, summary));
+        previous = Some(point);
+    }
+
+    for intervention in interventions {
+        let mut summary = intervention.intervention_type.clone();
+        if !intervention.note.is_empty() {
+            summary.push_str(&format!(
+                ": {}",
+                truncate_note(&intervention.note, NOTE_CHARS)
+            ));
+        }
+        entries.push(TimelineEntry {
+            date: intervention.occurred_on,
+            time: None,
+            kind: EntryKind::Intervention,
+            summary,
+        });
+        if let Some(closed_at) = intervention.closed_at {
+            let outcome = intervention
+                .outcome
+                .as_deref()
+                .unwrap_or("no outcome recorded");
+            entries.push(TimelineEntry::at(
+                closed_at,
+                EntryKind::Intervention,
+                format!("{} closed: {outcome}", intervention.intervention_type),
+            ));
+        }
+    }
+
+    for notification in notifications {
+        let mut summary = format!(
+            "{} via {} ({})",
+            notification.reason, notification.channel, notification.status
+        );
+        if let Some(rule) = &notification.rule {
+            summary.push_str(&format!(", rule {rule}"));
+        }
+        entries.push(TimelineEntry::at(
+            notification.sent_at,
+            EntryKind::Notification,
+            summary,
+        ));
+    }
+
+    // Stable, so entries of one kind at the same moment keep their order.
+    entries.sort_by_key(|entry| (entry.date, entry.time));
+    entries
+}
+
+/// Gathers the profile of the scholar registered under `email`, including archived signals.
+pub async fn load(
+    pool: &PgPool,
+    org_id: Uuid,
+    email: &str,
+    config: &Config,
+) -> anyhow::Result<Profile> {
+    let filter = db::ScholarFilter {
+        cohort: None,
+        email: Some(email.to_string()),
+        status: None,
+        advisor: None,
+        include_deleted: false,
+    };
+    let scholar = db::list_scholars(pool, org_id, &filter)
+        .await?
+        .into_iter()
+        .next()
+        .ok_or_else(|| anyhow::anyhow!("no scholar found with email {email}"))?;
+
+    let all_time = NaiveDate::from_ymd_opt(1, 1, 1).expect("year 1 is a valid date");
+    let filter = db::SignalFilter {
+        org_id: Some(org_id),
+        emails: vec![email.to_string()],
+        include_inactive: true,
+        include_archived: true,
+        ..db::SignalFilter::new(all_time)
+    };
+    let mut signals = Vec::new();
+    db::for_each_export_row(pool, &filter, db::DEFAULT_PAGE_SIZE, |row| {
+        signals.push(row);
+        Ok(())
+    })
+    .await?;
+    // Scored against the whole organization so percentiles match `score` and `watch`.
+    let score = watch::score_org(pool, org_id, config.defaults.since_days, &config.scoring)
+        .await?
+        .into_iter()
+        .find(|score| score.scholar_email == email);
+    let snapshots = db::score_history(pool, org_id, email, i64::MAX).await?;
+    let interventions = db::list_interventions(pool, org_id, Some(email), None, i64::MAX).await?;
+    let notifications = notify::list_notifications(
+        pool,
+        org_id,
+        &notify::NotificationQuery {
+            email: Some(email.to_string()),
+            limit: i64::MAX,
+            ..Default::default()
+        },
+    )
+    .await?;
+
+    Ok(Profile {
+        timeline: timeline(&signals, &snapshots, &interventions, &notifications),
+        scholar,
+        score,
+    })
+}
+
+/// The profile as text: a header on the scholar and their score, then the last `limit` timeline
+/// entries, oldest first.
+pub fn render(profile: &Profile, limit: usize, today: NaiveDate) -> String {
+    let scholar = &profile.scholar;
+    let mut lines = vec![format!(
+        "{} ({}), cohort {}, {}{}",
+        scholar.full_name,
+        scholar.email,
+        scholar.cohort,
+        scholar.status,
+        scholar
+            .advisor
+            .as_deref()
+            .map(|advisor| format!(", advisor {advisor}"))
+            .unwrap_or_default()
+    )];
+    lines.push(match &profile.score {
+        Some(score) => format!(
+            "Score {:.2} {} from {} signals; {}",
+            score.score,
+            risk::tier_label(score),
+            score.signal_count,
+            risk::contact_label(score, today)
+        ),
+        None => "No signals in the scoring window.".to_string(),
+    });
+    lines.push(String::new());
+
+    let shown = profile.timeline.len().min(limit);
+    if shown < profile.timeline.len() {
+        lines.push(format!(
+            "Last {shown} of {} timeline entries:",
+            profile.timeline.len()
+        ));
+    }
+    if profile.timeline.is_empty() {
+        lines.push("Nothing recorded yet.".to_string());
+    }
+    for entry in &profile.timeline[profile.timeline.len() - shown..] {
+        let when = match entry.time {
+            Some(time) => format!("{} {}", entry.date, time.format("%H:%M")),
+            None => format!("{}      ", entry.date),
+        };
+        lines.push(format!(
+            "{when}  {:<12}  {}",
+            entry.kind.label(),
+            entry.summary
+        ));
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::risk::Tier;
+
+    fn day(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 3, d).unwrap()
+    }
+
+    fn run(d: u32, score: f64, tier: Tier) -> ScoreHistoryPoint {
+        ScoreHistoryPoint {
+            ran_at: day(d).and_hms_opt(7, 0, 0).unwrap().and_utc(),
+            trigger: "watch".to_string(),
+            score,
+            tier,
+            signal_count: 1,
+        }
+    }
+
+    #[test]
+    fn merges_sources_in_order_and_skips_unchanged_runs() {
+        let signal = ExportRow {
+            full_name: "Ada Park".to_string(),
+            email: "ada@example.edu".to_string(),
+            cohort: "2026".to_string(),
+            signal_type: "attendance".to_string(),
+            severity: 3,
+            note: "Missed two sessions".to_string(),
+            occurred_at: day(2),
+            source_key: "sis-1".to_string(),
+            tags: Vec::new(),
+            submitted_by: Some("lee".to_string()),
+            attachments: Vec::new(),
+            status: "active".to_string(),
+            advisor: None,
+        };
+        let snapshots = [
+            run(2, 3.0, Tier::Moderate),
+            run(3, 3.0, Tier::Moderate),
+            run(4, 7.5, Tier::High),
+        ];
+        let intervention = Intervention {
+            id: Uuid::nil(),
+            scholar_name: "Ada Park".to_string(),
+            scholar_email: "ada@example.edu".to_string(),
+            occurred_on: day(4),
+            intervention_type: "family-call".to_string(),
+            outcome: Some("reached".to_string()),
+            note: String::new(),
+            signal_keys: Vec::new(),
+            closed_at: Some(day(5).and_hms_opt(9, 30, 0).unwrap().and_utc()),
+        };
+        let notification = Notification {
+            sent_at: day(4).and_hms_opt(7, 1, 0).unwrap().and_utc(),
+            channel: "advisors".to_string(),
+            reason: "entered high".to_string(),
+            rule: None,
+            summary: String::new(),
+            status: "sent".to_string(),
+            attempts: 1,
+            error: None,
+            scholars: vec!["ada@example.edu".to_string()],
+        };
+
+        let entries = timeline(&[signal], &snapshots, &[intervention], &[notification]);
+        let lines: Vec<(NaiveDate, EntryKind, &str)> = entries
+            .iter()
+            .map(|entry| (entry.date, entry.kind, entry.summary.as_str()))
+            .collect();
+        assert_eq!(
+            lines,
+            [
+                (
+                    day(2),
+                    EntryKind::Signal,
+                    "attendance severity 3: Missed two sessions (by lee)"
+                ),
+                (
+                    day(2),
+                    EntryKind::Score,
+                    "3.00 (moderate) in the first watch run"
+                ),
+                (day(4), EntryKind::Intervention, "family-call"),
+                (
+                    day(4),
+                    EntryKind::Score,
+                    "3.00 -> 7.50, moderate -> high in a watch run"
+                ),
+                (
+                    day(4),
+                    EntryKind::Notification,
+                    "entered high via advisors (sent)"
+                ),
+                (
+                    day(5),
+                    EntryKind::Intervention,
+                    "family-call closed: reached"
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,560 @@
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+use sqlx::{PgPool, Row};
+use uuid::Uuid;
+
+use crate::import::ImportLimits;
+use crate::models::Attachment;
+use crate::{db, notes};
+
+/// A scholar on the roster being promoted.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RosterScholar {
+    pub full_name: String,
+    pub email: String,
+    pub cohort: String,
+    pub status: String,
+    /// Soft-deleted; only ever set for target rows.
+    pub deleted: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PromotedSignal {
+    pub scholar_email: String,
+    pub signal_type: String,
+    pub severity: i32,
+    pub note: String,
+    pub occurred_at: NaiveDate,
+    pub source_key: String,
+    pub tags: Vec<String>,
+    pub submitted_by: Option<String>,
+    pub attachments: Vec<Attachment>,
+}
+
+/// One cohort's roster and signals as read from an environment.
+#[derive(Debug, Clone, Default)]
+pub struct CohortSnapshot {
+    pub scholars: Vec<RosterScholar>,
+    pub signals: Vec<PromotedSignal>,
+    pub start_date: Option<NaiveDate>,
+}
+
+/// What promoting would write to the target; nothing is written while `conflicts` is non-empty.
+#[derive(Debug, Clone, Default)]
+pub struct PromotionPlan {
+    pub new_scholars: Vec<RosterScholar>,
+    pub existing_scholars: usize,
+    pub new_signals: Vec<PromotedSignal>,
+    /// Signals whose source key the target already holds with identical content.
+    pub existing_signals: usize,
+    pub start_date: Option<NaiveDate>,
+    pub conflicts: Vec<String>,
+}
+
+/// Rejects source rows that an import into the target would reject under `limits`.
+pub fn validate(snapshot: &CohortSnapshot, limits: &ImportLimits) -> Vec<String> {
+    let mut problems = Vec::new();
+    for scholar in &snapshot.scholars {
+        if !scholar.email.contains('@') {
+            problems.push(format!(
+                "scholar {:?} has no valid email",
+                scholar.full_name
+            ));
+        }
+    }
+    for signal in &snapshot.signals {
+        if !limits.severity.contains(signal.severity) {
+            problems.push(format!(
+                "signal {} has severity {}",
+                signal.source_key, signal.severity
+            ));
+        }
+        if signal.note.chars().count() > limits.max_note_chars {
+            problems.push(format!(
+                "signal {} has a note above the {} character import limit",
+                signal.source_key, limits.max_note_chars
+            ));
+        }
+    }
+    problems
+}
+
+/// Compares the source cohort with what the target already holds.
+///
+/// Scholars match on email and signals on source key. Matching rows must agree, otherwise they are
+/// reported as conflicts rather than overwritten.
+pub fn plan(
+    source: CohortSnapshot,
+    target: &CohortSnapshot,
+    limits: &ImportLimits,
+) -> PromotionPlan {
+    let target_scholars: HashMap<&str, &RosterScholar> = target
+        .scholars
+        .iter()
+        .map(|scholar| (scholar.email.as_str(), scholar))
+        .collect();
+    let target_signals: HashMap<&str, &PromotedSignal> = target
+        .signals
+        .iter()
+        .map(|signal| (signal.source_key.as_str(), signal))
+        .collect();
+
+    let mut plan = PromotionPlan {
+        conflicts: validate(&source, limits),
+        ..PromotionPlan::default()
+    };
+
+    for scholar in source.scholars {
+        match target_scholars.get(scholar.email.as_str()) {
+            None => plan.new_scholars.push(scholar),
+            Some(existing) if existing.deleted => plan.conflicts.push(format!(
+                "scholar {} is soft-deleted on the target; restore or purge them first",
+                scholar.email
+            )),
+            Some(existing)
+                if existing.full_name == scholar.full_name && existing.cohort == scholar.cohort =>
+            {
+                plan.existing_scholars += 1
+            }
+            Some(existing) => plan.conflicts.push(format!(
+                "scholar {} is {} in cohort {} on the target, {} in cohort {} on the source",
+                scholar.email,
+                existing.full_name,
+                existing.cohort,
+                scholar.full_name,
+                scholar.cohort
+            )),
+        }
+    }
+
+    for signal in source.signals {
+        match target_signals.get(signal.source_key.as_str()) {
+            None => plan.new_signals.push(signal),
+            Some(existing)
+                if existing.scholar_email == signal.scholar_email
+                    && existing.signal_type == signal.signal_type
+                    && existing.severity == signal.severity
+                    && existing.occurred_at == signal.occurred_at =>
+            {
+                plan.existing_signals += 1
+            }
+            Some(existing) => plan.conflicts.push(format!(
+                "source key {} is {} severity {} for {} on {} on the target, \
+                 {} severity {} for {} on {} on the source",
+                signal.source_key,
+                existing.signal_type,
+                existing.severity,
+                existing.scholar_email,
+                existing.occurred_at,
+                signal.signal_type,
+                signal.severity,
+                signal.scholar_email,
+                signal.occurred_at
+            )),
+        }
+    }
+
+    plan.start_date = match (source.start_date, target.start_date) {
+        (Some(source_start), Some(target_start)) if source_start != target_start => {
+            plan.conflicts.push(format!(
+                "cohort starts on {target_start} on the target and {source_start} on the source"
+            ));
+            None
+        }
+        (Some(source_start), None) => Some(source_start),
+        _ => None,
+    };
+    plan
+}
+
+/// Reads the live (not soft-deleted) roster and hot signals of `cohort`.
+pub async fn fetch_cohort(
+    pool: &PgPool,
+    org_id: Uuid,
+    cohort: &str,
+) -> anyhow::Result<CohortSnapshot> {
+    let scholars = sqlx::query(
+        "SELECT full_name, email, cohort, status FROM cohort_early_warning.scholars \
+         WHERE org_id = $1 AND cohort = $2 AND deleted_at IS NULL ORDER BY email",
+    )
+    .bind(org_id)
+    .bind(cohort)
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|row| RosterScholar {
+        full_name: row.get("full_name"),
+        email: row.get("email"),
+        cohort: row.get("cohort"),
+        status: row.get("status"),
+        deleted: false,
+    })
+    .collect();
+
+    let rows = sqlx::query(
+        "SELECT s.id, sc.email, s.signal_type, s.severity, s.note, s.occurred_at, s.source_key, \
+         s.tags, s.submitted_by \
+         FROM cohort_early_warning.signals s \
+         JOIN cohort_early_warning.scholars sc ON sc.id = s.scholar_id \
+         WHERE sc.org_id = $1 AND sc.cohort = $2 \
+         AND s.deleted_at IS NULL AND sc.deleted_at IS NULL \
+         ORDER BY s.occurred_at, s.source_key",
+    )
+    .bind(org_id)
+    .bind(cohort)
+    .fetch_all(pool)
+    .await?;
+    let signal_ids: Vec<Uuid> = rows.iter().map(|row| row.get("id")).collect();
+    let mut attachments = fetch_attachments(pool, &signal_ids).await?;
+    let signals = rows
+        .into_iter()
+        .map(|row| {
+            let id: Uuid = row.get("id");
+            Ok(PromotedSignal {
+                scholar_email: row.get("email"),
+                signal_type: row.get("signal_type"),
+                severity: row.get("severity"),
+                note: notes::open(row.get("note"))?,
+                occurred_at: row.get("occurred_at"),
+                source_key: row.get("source_key"),
+                tags: row.get("tags"),
+                submitted_by: row.get("submitted_by"),
+                attachments: attachments.remove(&id).unwrap_or_default(),
+            })
+        })
+        .collect::<anyhow::Result<_>>()?;
+
+    let start_date = sqlx::query_scalar(
+        "SELECT start_date FROM cohort_early_warning.cohorts WHERE org_id = $1 AND name = $2",
+    )
+    .bind(org_id)
+    .bind(cohort)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(CohortSnapshot {
+        scholars,
+        signals,
+        start_date,
+    })
+}
+
+async fn fetch_attachments(
+    pool: &PgPool,
+    signal_ids: &[Uuid],
+) -> anyhow::Result<HashMap<Uuid, Vec<Attachment>>> {
+    let rows = sqlx::query(
+        "SELECT signal_id, url, label, kind FROM cohort_early_warning.signal_attachments \
+         WHERE signal_id = ANY($1) ORDER BY created_at, id",
+    )
+    .bind(signal_ids)
+    .fetch_all(pool)
+    .await?;
+    let mut attachments: HashMap<Uuid, Vec<Attachment>> = HashMap::new();
+    for row in rows {
+        attachments
+            .entry(row.get("signal_id"))
+            .or_default()
+            .push(Attachment {
+                url: row.get("url"),
+                label: row.get("label"),
+                kind: row.get("kind"),
+            });
+    }
+    Ok(attachments)
+}
+
+/// Reads whatever the target holds that the source rows could collide with: scholars with the
+/// same emails in any cohort, and signals (archived or soft-deleted included) with the same source
+/// keys.
+pub async fn fetch_overlap(
+    pool: &PgPool,
+    org_id: Uuid,
+    cohort: &str,
+    source: &CohortSnapshot,
+) -> anyhow::Result<CohortSnapshot> {
+    let emails: Vec<&str> = source
+        .scholars
+        .iter()
+        .map(|scholar| scholar.email.as_str())
+        .collect();
+    let scholars = sqlx::query(
+        "SELECT full_name, email, cohort, status, deleted_at IS NOT NULL AS deleted \
+         FROM cohort_early_warning.scholars WHERE org_id = $1 AND email = ANY($2)",
+    )
+    .bind(org_id)
+    .bind(&emails)
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|row| RosterScholar {
+        full_name: row.get("full_name"),
+        email: row.get("email"),
+        cohort: row.get("cohort"),
+        status: row.get("status"),
+        deleted: row.get("deleted"),
+    })
+    .collect();
+
+    let source_keys: Vec<&str> = source
+        .signals
+        .iter()
+        .map(|signal| signal.source_key.as_str())
+        .collect();
+    let signals = sqlx::query(
+        "SELECT sc.email, s.signal_type, s.severity, s.note, s.occurred_at, s.source_key, \
+         s.tags, s.submitted_by \
+         FROM cohort_early_warning.signals_with_archive s \
+         JOIN cohort_early_warning.scholars sc ON sc.id = s.scholar_id \
+         WHERE s.org_id = $1 AND s.source_key = ANY($2)",
+    )
+    .bind(org_id)
+    .bind(&source_keys)
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|row| {
+        Ok(PromotedSignal {
+            scholar_email: row.get("email"),
+            signal_type: row.get("signal_type"),
+            severity: row.get("severity"),
+            note: notes::open(row.get("note"))?,
+            occurred_at: row.get("occurred_at"),
+            source_key: row.get("source_key"),
+            tags: row.get("tags"),
+            submitted_by: row.get("submitted_by"),
+            attachments: Vec::new(),
+        })
+    })
+    .collect::<anyhow::Result<_>>()?;
+
+    let start_date = sqlx::query_scalar(
+        "SELECT start_date FROM cohort_early_warning.cohorts WHERE org_id = $1 AND name = $2",
+    )
+    .bind(org_id)
+    .bind(cohort)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(CohortSnapshot {
+        scholars,
+        signals,
+        start_date,
+    })
+}
+
+/// Writes `plan` to the target in one transaction, as an import batch that `import undo` can
+/// remove. Returns the batch id.
+pub async fn apply(
+    pool: &PgPool,
+    org_id: Uuid,
+    cohort: &str,
+    source_label: &str,
+    plan: &PromotionPlan,
+) -> anyhow::Result<Uuid> {
+    anyhow::ensure!(
+        plan.conflicts.is_empty(),
+        "refusing to promote with {} unresolved conflicts",
+        plan.conflicts.len()
+    );
+
+    let mut tx = pool.begin().await?;
+    let batch_id = Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO cohort_early_warning.import_batches (id, source, org_id) VALUES ($1, $2, $3)",
+    )
+    .bind(batch_id)
+    .bind(format!("promote {cohort} from {source_label}"))
+    .bind(org_id)
+    .execute(&mut *tx)
+    .await?;
+
+    for scholar in &plan.new_scholars {
+        sqlx::query(
+            "INSERT INTO cohort_early_warning.scholars (id, full_name, email, cohort, status, org_id) \
+             VALUES ($1, $2, $3, $4, $5, $6)",
+        )
+        .bind(Uuid::new_v4())
+        .bind(&scholar.full_name)
+        .bind(&scholar.email)
+        .bind(&scholar.cohort)
+        .bind(&scholar.status)
+        .bind(org_id)
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    for signal in &plan.new_signals {
+        let signal_id = Uuid::new_v4();
+        sqlx::query(
+            "INSERT INTO cohort_early_warning.signals \
+             (id, scholar_id, signal_type, severity, note, occurred_at, source_key, batch_id, org_id, \
+              tags, submitted_by) \
+             SELECT $1, sc.id, $3, $4, $5, $6, $7, $8, $9, $10, $11 \
+             FROM cohort_early_warning.scholars sc WHERE sc.org_id = $9 AND sc.email = $2",
+        )
+        .bind(signal_id)
+        .bind(&signal.scholar_email)
+        .bind(&signal.signal_type)
+        .bind(signal.severity)
+        .bind(notes::seal(&signal.note))
+        .bind(signal.occurred_at)
+        .bind(&signal.source_key)
+        .bind(batch_id)
+        .bind(org_id)
+        .bind(&signal.tags)
+        .bind(&signal.submitted_by)
+        .execute(&mut *tx)
+        .await?;
+
+        for attachment in &signal.attachments {
+            sqlx::query(
+                "INSERT INTO cohort_early_warning.signal_attachments \
+                 (id, org_id, signal_id, url, label, kind) VALUES ($1, $2, $3, $4, $5, $6)",
+            )
+            .bind(Uuid::new_v4())
+            .bind(org_id)
+            .bind(signal_id)
+            .bind(&attachment.url)
+            .bind(&attachment.label)
+            .bind(&attachment.kind)
+            .execute(&mut *tx)
+            .await?;
+        }
+    }
+
+    if let Some(start_date) = plan.start_date {
+        sqlx::query(
+            "INSERT INTO cohort_early_warning.cohorts (org_id, name, start_date) VALUES ($1, $2, $3) \
+             ON CONFLICT (org_id, name) DO NOTHING",
+        )
+        .bind(org_id)
+        .bind(cohort)
+        .bind(start_date)
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    tx.commit().await?;
+    Ok(batch_id)
+}
+
+/// Resolves `org` in both environments, then reads the source cohort and the target overlap.
+pub async fn prepare(
+    source: &PgPool,
+    target: &PgPool,
+    org: &str,
+    cohort: &str,
+    limits: &ImportLimits,
+) -> anyhow::Result<(Uuid, PromotionPlan)> {
+    let source_org = db::resolve_org(source, org).await?;
+    let target_org = db::resolve_org(target, org).await?;
+    let snapshot = fetch_cohort(source, source_org, cohort).await?;
+    anyhow::ensure!(
+        !snapshot.scholars.is_empty(),
+        "cohort {cohort} has no scholars on the source"
+    );
+    let overlap = fetch_overlap(target, target_org, cohort, &snapshot).await?;
+    Ok((target_org, plan(snapshot, &overlap, limits)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::import::SeverityBounds;
+
+    fn scholar(email: &str, cohort: &str) -> RosterScholar {
+        RosterScholar {
+            full_name: "Avery Lee".to_string(),
+            email: email.to_string(),
+            cohort: cohort.to_string(),
+            status: "active".to_string(),
+            deleted: false,
+        }
+    }
+
+    fn signal(source_key: &str, severity: i32) -> PromotedSignal {
+        PromotedSignal {
+            scholar_email: "avery@example.org".to_string(),
+            signal_type: "attendance".to_string(),
+            severity,
+            note: "missed session".to_string(),
+            occurred_at: NaiveDate::from_ymd_opt(2027, 9, 8).unwrap(),
+            source_key: source_key.to_string(),
+            tags: Vec::new(),
+            submitted_by: None,
+            attachments: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn plans_new_rows_and_skips_identical_ones() {
+        let source = CohortSnapshot {
+            scholars: vec![
+                scholar("avery@example.org", "2027"),
+                scholar("new@example.org", "2027"),
+            ],
+            signals: vec![signal("k1", 3), signal("k2", 4)],
+            start_date: NaiveDate::from_ymd_opt(2027, 9, 1),
+        };
+        let target = CohortSnapshot {
+            scholars: vec![scholar("avery@example.org", "2027")],
+            signals: vec![signal("k1", 3)],
+            start_date: None,
+        };
+
+        let plan = plan(source, &target, &ImportLimits::default());
+        assert!(plan.conflicts.is_empty());
+        assert_eq!(plan.new_scholars.len(), 1);
+        assert_eq!(plan.existing_scholars, 1);
+        assert_eq!(plan.new_signals.len(), 1);
+        assert_eq!(plan.new_signals[0].source_key, "k2");
+        assert_eq!(plan.existing_signals, 1);
+        assert_eq!(plan.start_date, NaiveDate::from_ymd_opt(2027, 9, 1));
+    }
+
+    #[test]
+    fn reports_conflicting_rows() {
+        let source = CohortSnapshot {
+            scholars: vec![scholar("avery@example.org", "2027")],
+            signals: vec![signal("k1", 3), signal("k2", 9)],
+            start_date: NaiveDate::from_ymd_opt(2027, 9, 1),
+        };
+        let target = CohortSnapshot {
+            scholars: vec![scholar("avery@example.org", "2026")],
+            signals: vec![signal("k1", 5)],
+            start_date: NaiveDate::from_ymd_opt(2027, 8, 30),
+        };
+
+        let plan = plan(source, &target, &ImportLimits::default());
+        assert_eq!(plan.conflicts.len(), 4);
+        assert!(plan.conflicts[0].contains("severity 9"));
+        assert!(plan.conflicts[1].contains("cohort 2026 on the target"));
+        assert!(plan.conflicts[2].starts_with("source key k1"));
+        assert_eq!(plan.start_date, None);
+    }
+
+    #[test]
+    fn validates_severities_against_the_configured_bounds() {
+        let source = CohortSnapshot {
+            scholars: vec![scholar("avery@example.org", "2027")],
+            signals: vec![signal("k1", 0), signal("k2", 9)],
+            start_date: None,
+        };
+        assert_eq!(validate(&source, &ImportLimits::default()).len(), 2);
+
+        let limits = ImportLimits {
+            severity: SeverityBounds { min: 0, max: 10 },
+            ..ImportLimits::default()
+        };
+        assert!(validate(&source, &limits).is_empty());
+        let narrow = ImportLimits {
+            severity: SeverityBounds { min: 0, max: 3 },
+            ..ImportLimits::default()
+        };
+        let problems = validate(&source, &narrow);
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].contains("severity 9"));
+    }
+}
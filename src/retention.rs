@@ -0,0 +1,116 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::audit::{self, AuditAction};
+use crate::db;
+use crate::models::{PurgeSummary, RetentionMode, RetentionPlan};
+
+fn verb(mode: RetentionMode) -> (&'static str, AuditAction) {
+    match mode {
+        RetentionMode::Delete => ("delete", AuditAction::Delete),
+        RetentionMode::Anonymize => ("anonymize", AuditAction::Update),
+    }
+}
+
+/// Applies `plan` to the organization and, unless `dry_run`, records what each data class lost in
+/// the audit log under `command`.
+pub async fn enforce(
+    pool: &PgPool,
+    org_id: Uuid,
+    plan: &RetentionPlan,
+    command: &str,
+    dry_run: bool,
+) -> anyhow::Result<PurgeSummary> {
+    let summary = db::purge_expired(pool, org_id, plan, dry_run).await?;
+    if dry_run {
+        return Ok(summary);
+    }
+    if let Some((before, mode)) = plan.signals {
+        let (verb, action) = verb(mode);
+        let detail = format!("{verb} older than {before}");
+        let signals = summary.signals + summary.archived_signals;
+        audit::record(pool, command, action, "signals", signals, &detail).await?;
+        audit::record(pool, command, action, "scholars", summary.scholars, &detail).await?;
+    }
+    if let Some(before) = plan.notes {
+        let detail = format!("blank notes older than {before}");
+        audit::record(
+            pool,
+            command,
+            AuditAction::Update,
+            "signals",
+            summary.notes,
+            &detail,
+        )
+        .await?;
+    }
+    if let Some((before, mode)) = plan.interventions {
+        let (verb, action) = verb(mode);
+        let detail = format!("{verb} older than {before}");
+        audit::record(
+            pool,
+            command,
+            action,
+            "interventions",
+            summary.interventions,
+            &detail,
+        )
+        .await?;
+    }
+    Ok(summary)
+}
+
+/// One line per data class in `plan`: what happens to it, its cutoff, and how many rows that
+/// touched.
+pub fn summary_lines(plan: &RetentionPlan, summary: &PurgeSummary) -> Vec<String> {
+    let mut lines = Vec::new();
+    if let Some((before, mode)) = plan.signals {
+        let (verb, _) = verb(mode);
+        lines.push(format!(
+            "signals before {before} ({verb}): {} live, {} archived; scholars: {}",
+            summary.signals, summary.archived_signals, summary.scholars
+        ));
+    }
+    if let Some(before) = plan.notes {
+        lines.push(format!("notes before {before} (blank): {}", summary.notes));
+    }
+    if let Some((before, mode)) = plan.interventions {
+        let (verb, _) = verb(mode);
+        lines.push(format!(
+            "interventions before {before} ({verb}): {}",
+            summary.interventions
+        ));
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::*;
+
+    #[test]
+    fn summarizes_each_class_in_the_plan() {
+        let before = NaiveDate::from_ymd_opt(2023, 3, 2).unwrap();
+        let plan = RetentionPlan {
+            signals: Some((before, RetentionMode::Delete)),
+            notes: None,
+            interventions: Some((before, RetentionMode::Anonymize)),
+        };
+        let summary = PurgeSummary {
+            signals: 4,
+            archived_signals: 10,
+            scholars: 1,
+            notes: 0,
+            interventions: 2,
+        };
+        assert_eq!(
+            summary_lines(&plan, &summary),
+            [
+                "signals before 2023-03-02 (delete): 4 live, 10 archived; scholars: 1",
+                "interventions before 2023-03-02 (anonymize): 2",
+            ]
+        );
+    }
+}
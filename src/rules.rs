@@ -0,0 +1,674 @@
+use std::collections::HashMap;
+#[cfg(feature = "postgres")]
+use std::collections::HashSet;
+
+#[cfg(feature = "postgres")]
+use chrono::{DateTime, Utc};
+#[cfg(feature = "postgres")]
+use sqlx::{PgPool, Row};
+#[cfg(feature = "postgres")]
+use uuid::Uuid;
+
+#[cfg(feature = "postgres")]
+use crate::config::Config;
+use crate::import::SeverityBounds;
+use crate::models::{ScholarScore, SignalRecord};
+use crate::risk::Tier;
+#[cfg(feature = "postgres")]
+use crate::risk::{self, ScoringConfig};
+#[cfg(feature = "postgres")]
+use crate::{db, notify};
+
+/// An alert rule, managed with `rules add` or written as `[[rules]]` in the config; every
+/// condition that is set must hold for a scholar to match.
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AlertRule {
+    pub name: String,
+    /// Requires a signal of this type in the window (combined with `min_severity` if set).
+    pub signal_type: Option<String>,
+    /// Requires a signal at or above this severity in the window.
+    pub min_severity: Option<i32>,
+    /// Requires at least this many signals meeting the signal condition; one when unset.
+    pub min_count: Option<i64>,
+    /// Count only signals logged since the rules were last evaluated, e.g. "a new critical
+    /// signal".
+    pub new_only: bool,
+    pub min_score: Option<f64>,
+    pub min_tier: Option<Tier>,
+    pub since_days: i64,
+    pub enabled: bool,
+    /// Names from `[channels]` a firing rule is sent to; empty means the `[watch]` channels.
+    pub channels: Vec<String>,
+}
+
+impl Default for AlertRule {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            signal_type: None,
+            min_severity: None,
+            min_count: None,
+            new_only: false,
+            min_score: None,
+            min_tier: None,
+            since_days: 30,
+            enabled: true,
+            channels: Vec::new(),
+        }
+    }
+}
+
+impl AlertRule {
+    pub fn has_signal_condition(&self) -> bool {
+        self.signal_type.is_some() || self.min_severity.is_some()
+    }
+
+    /// Checks the rule, with `min_severity` within the configured `severity` bounds.
+    pub fn validate(&self, severity: SeverityBounds) -> anyhow::Result<()> {
+        if self.name.trim().is_empty() {
+            anyhow::bail!("every rule needs a name");
+        }
+        if self.signal_type.is_none()
+            && self.min_severity.is_none()
+            && self.min_score.is_none()
+            && self.min_tier.is_none()
+        {
+            anyhow::bail!("rule {} needs at least one condition", self.name);
+        }
+        if let Some(min_severity) = self.min_severity {
+            if !severity.contains(min_severity) {
+                anyhow::bail!("min severity must be within {severity}, got {min_severity}");
+            }
+        }
+        if let Some(count) = self.min_count {
+            if count < 1 {
+                anyhow::bail!("min count must be at least 1, got {count}");
+            }
+        }
+        if (self.min_count.is_some() || self.new_only) && !self.has_signal_condition() {
+            anyhow::bail!(
+                "rule {} counts signals, so it needs a signal type or min severity",
+                self.name
+            );
+        }
+        if self.since_days < 1 {
+            anyhow::bail!("rule window must be at least one day");
+        }
+        Ok(())
+    }
+
+    /// Plain-language summary of the conditions, for listings.
+    pub fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        if self.has_signal_condition() {
+            let kind = self
+                .signal_type
+                .as_deref()
+                .map_or(String::new(), |signal_type| format!("{signal_type} "));
+            let new = if self.new_only { "new " } else { "" };
+            let mut condition = match self.min_count.unwrap_or(1) {
+                1 if self.new_only => format!("a new {kind}signal"),
+                1 if self.signal_type.is_some() && self.min_severity.is_some() => {
+                    format!("{kind}signal")
+                }
+                1 => format!("any {kind}signal"),
+                count => format!("{count}+ {new}{kind}signals"),
+            };
+            if let Some(severity) = self.min_severity {
+                condition.push_str(&format!(" with severity >= {severity}"));
+            }
+            parts.push(condition);
+        }
+        if let Some(score) = self.min_score {
+            parts.push(format!("score >= {score:.2}"));
+        }
+        if let Some(tier) = self.min_tier {
+            parts.push(format!("tier >= {}", tier.as_str()));
+        }
+        format!("{} within {} days", parts.join(" and "), self.since_days)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuleMatch {
+    pub scholar_name: String,
+    pub scholar_email: String,
+    pub score: f64,
+    pub tier: Tier,
+    /// Signals that satisfied the signal condition; empty when the rule has none.
+    pub matching_signals: usize,
+}
+
+/// Applies `rule` to scored scholars and the signals behind those scores.
+///
+/// `signals` must already be limited to the rule's window, and for `new_only` rules to the signals
+/// logged since the last evaluation.
+pub fn evaluate(
+    rule: &AlertRule,
+    signals: &[SignalRecord],
+    scores: &[ScholarScore],
+) -> Vec<RuleMatch> {
+    let has_signal_condition = rule.has_signal_condition();
+    let min_count = rule.min_count.unwrap_or(1).max(1) as usize;
+    let mut signal_hits: HashMap<&str, usize> = HashMap::new();
+    for signal in signals.iter().filter(|_| has_signal_condition) {
+        let type_ok = rule
+            .signal_type
+            .as_ref()
+            .is_none_or(|signal_type| &signal.signal_type == signal_type);
+        let severity_ok = rule
+            .min_severity
+            .is_none_or(|severity| signal.severity >= severity);
+        if type_ok && severity_ok {
+            *signal_hits
+                .entry(signal.scholar_email.as_str())
+                .or_default() += 1;
+        }
+    }
+
+    scores
+        .iter()
+        .filter(|score| rule.min_score.is_none_or(|minimum| score.score >= minimum))
+        .filter(|score| rule.min_tier.is_none_or(|minimum| score.tier >= minimum))
+        .filter_map(|score| {
+            let hits = signal_hits
+                .get(score.scholar_email.as_str())
+                .copied()
+                .unwrap_or_default();
+            if has_signal_condition && hits < min_count {
+                return None;
+            }
+            Some(RuleMatch {
+                scholar_name: score.scholar_name.clone(),
+                scholar_email: score.scholar_email.clone(),
+                score: score.score,
+                tier: score.tier,
+                matching_signals: hits,
+            })
+        })
+        .collect()
+}
+
+#[cfg(feature = "postgres")]
+fn parse_tier(value: Option<String>) -> anyhow::Result<Option<Tier>> {
+    value
+        .map(|tier| {
+            <Tier as clap::ValueEnum>::from_str(&tier, true)
+                .map_err(|_| anyhow::anyhow!("unknown tier {tier} in alert_rules"))
+        })
+        .transpose()
+}
+
+#[cfg(feature = "postgres")]
+pub async fn add_rule(
+    pool: &PgPool,
+    org_id: Uuid,
+    rule: &AlertRule,
+    severity: SeverityBounds,
+) -> anyhow::Result<()> {
+    rule.validate(severity)?;
+    sqlx::query(
+        "INSERT INTO cohort_early_warning.alert_rules \
+         (id, org_id, name, signal_type, min_severity, min_count, new_only, min_score, min_tier, \
+          since_days, enabled, channels) \
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)",
+    )
+    .bind(Uuid::new_v4())
+    .bind(org_id)
+    .bind(&rule.name)
+    .bind(&rule.signal_type)
+    .bind(rule.min_severity)
+    .bind(rule.min_count.map(|count| count as i32))
+    .bind(rule.new_only)
+    .bind(rule.min_score)
+    .bind(rule.min_tier.map(|tier| tier.as_str()))
+    .bind(rule.since_days as i32)
+    .bind(rule.enabled)
+    .bind(&rule.channels)
+    .execute(pool)
+    .await
+    .map_err(|err| match &err {
+        sqlx::Error::Database(db) if db.is_unique_violation() => {
+            anyhow::anyhow!("a rule named {} already exists", rule.name)
+        }
+        _ => err.into(),
+    })?;
+    Ok(())
+}
+
+#[cfg(feature = "postgres")]
+pub async fn list_rules(
+    pool: &PgPool,
+    org_id: Uuid,
+    enabled_only: bool,
+) -> anyhow::Result<Vec<AlertRule>> {
+    let rows = sqlx::query(
+        "SELECT name, signal_type, min_severity, min_count, new_only, min_score, min_tier, \
+         since_days, enabled, channels FROM cohort_early_warning.alert_rules \
+         WHERE org_id = $1 AND (enabled OR NOT $2) ORDER BY name",
+    )
+    .bind(org_id)
+    .bind(enabled_only)
+    .fetch_all(pool)
+    .await?;
+
+    rows.into_iter()
+        .map(|row| {
+            Ok(AlertRule {
+                name: row.get("name"),
+                signal_type: row.get("signal_type"),
+                min_severity: row.get("min_severity"),
+                min_count: row.get::<Option<i32>, _>("min_count").map(i64::from),
+                new_only: row.get("new_only"),
+                min_score: row.get("min_score"),
+                min_tier: parse_tier(row.get("min_tier"))?,
+                since_days: row.get::<i32, _>("since_days") as i64,
+                enabled: row.get("enabled"),
+                channels: row.get("channels"),
+            })
+        })
+        .collect()
+}
+
+/// Looks a rule up among the config's `[[rules]]` first, then the organization's stored rules.
+#[cfg(feature = "postgres")]
+pub async fn find_rule(
+    pool: &PgPool,
+    org_id: Uuid,
+    config: &Config,
+    name: &str,
+) -> anyhow::Result<AlertRule> {
+    if let Some(rule) = config.rules.iter().find(|rule| rule.name == name) {
+        return Ok(rule.clone());
+    }
+    list_rules(pool, org_id, false)
+        .await?
+        .into_iter()
+        .find(|rule| rule.name == name)
+        .ok_or_else(|| anyhow::anyhow!("no alert rule named {name}"))
+}
+
+/// Enabled rules from the config followed by the organization's enabled stored rules.
+#[cfg(feature = "postgres")]
+pub async fn active_rules(
+    pool: &PgPool,
+    org_id: Uuid,
+    config: &Config,
+) -> anyhow::Result<Vec<AlertRule>> {
+    let mut rules: Vec<AlertRule> = config
+        .rules
+        .iter()
+        .filter(|rule| rule.enabled)
+        .cloned()
+        .collect();
+    rules.extend(list_rules(pool, org_id, true).await?);
+    Ok(rules)
+}
+
+/// A rule's matches, plus what they were computed from.
+#[cfg(feature = "postgres")]
+#[derive(Debug)]
+pub struct RuleEvaluation {
+    pub matches: Vec<RuleMatch>,
+    /// Every scholar scored over the rule's window, highest first.
+    pub scores: Vec<ScholarScore>,
+    /// Scholars with a signal logged after `new_since`.
+    pub changed: HashSet<String>,
+}
+
+/// Evaluates `rule` against current data. Signals logged after `new_since` count as new; with
+/// `None`, every signal in the window does.
+#[cfg(feature = "postgres")]
+pub async fn evaluate_rule(
+    pool: &PgPool,
+    org_id: Uuid,
+    rule: &AlertRule,
+    scoring: &ScoringConfig,
+    email: Option<String>,
+    new_since: Option<DateTime<Utc>>,
+) -> anyhow::Result<RuleEvaluation> {
+    let filter = db::SignalFilter {
+        org_id: Some(org_id),
+        emails: email.into_iter().collect(),
+        ..db::SignalFilter::new(risk::cutoff_date(rule.since_days))
+    };
+    let signals = db::fetch_signals(pool, &filter, db::DEFAULT_PAGE_SIZE).await?;
+    let new_signals = match new_since {
+        Some(since) => {
+            let filter = db::SignalFilter {
+                created_after: Some(since),
+                ..filter
+            };
+            db::fetch_signals(pool, &filter, db::DEFAULT_PAGE_SIZE).await?
+        }
+        None => signals.clone(),
+    };
+    let scores =
+        risk::score_signals_with(&signals, rule.since_days, Utc::now().date_naive(), scoring);
+    let counted = if rule.new_only {
+        &new_signals
+    } else {
+        &signals
+    };
+    Ok(RuleEvaluation {
+        matches: evaluate(rule, counted, &scores),
+        changed: new_signals
+            .into_iter()
+            .map(|signal| signal.scholar_email)
+            .collect(),
+        scores,
+    })
+}
+
+/// A rule that matched at least one scholar, and where its alert went.
+#[cfg(feature = "postgres")]
+#[derive(Debug)]
+pub struct RuleFiring {
+    pub rule: String,
+    /// Matches that were sent; empty when the cooldown held every one back.
+    pub alerts: Vec<ScholarScore>,
+    /// Matches left out because this rule alerted about them within the cooldown.
+    pub held_back: usize,
+    pub deliveries: Vec<(String, anyhow::Result<()>)>,
+}
+
+/// Evaluates `rules` and sends each one's matches to its channels. Only scholars with a signal
+/// logged since the previous evaluation alert, so an unchanged match doesn't repeat every run, and
+/// the `[watch]` cooldown holds back scholars the rule alerted about recently.
+#[cfg(feature = "postgres")]
+pub async fn fire_rules(
+    pool: &PgPool,
+    org: &str,
+    org_id: Uuid,
+    config: &Config,
+    rules: &[AlertRule],
+) -> anyhow::Result<Vec<RuleFiring>> {
+    let (previous, started): (Option<DateTime<Utc>>, DateTime<Utc>) = sqlx::query_as(
+        "SELECT rules_evaluated_at, NOW() FROM cohort_early_warning.organizations WHERE id = $1",
+    )
+    .bind(org_id)
+    .fetch_one(pool)
+    .await?;
+
+    let mut firings = Vec::new();
+    for rule in rules {
+        let evaluation = evaluate_rule(pool, org_id, rule, &config.scoring, None, previous).await?;
+        let matched: HashSet<&str> = evaluation
+            .matches
+            .iter()
+            .map(|hit| hit.scholar_email.as_str())
+            .filter(|email| evaluation.changed.contains(*email))
+            .collect();
+        let alerts: Vec<ScholarScore> = evaluation
+            .scores
+            .iter()
+            .filter(|score| matched.contains(score.scholar_email.as_str()))
+            .cloned()
+            .collect();
+        let (alerts, held_back) = notify::apply_cooldown(
+            pool,
+            org_id,
+            Some(&rule.name),
+            config.watch.cooldown_days,
+            alerts,
+        )
+        .await?;
+        if alerts.is_empty() && held_back == 0 {
+            continue;
+        }
+
+        let deliveries = if alerts.is_empty() {
+            Vec::new()
+        } else {
+            notify::deliver(
+                pool,
+                config,
+                org,
+                org_id,
+                notify::AlertReason::Rule(rule),
+                &alerts,
+            )
+            .await?
+        };
+        firings.push(RuleFiring {
+            rule: rule.name.clone(),
+            alerts,
+            held_back,
+            deliveries,
+        });
+    }
+
+    sqlx::query(
+        "UPDATE cohort_early_warning.organizations SET rules_evaluated_at = $1 WHERE id = $2",
+    )
+    .bind(started)
+    .bind(org_id)
+    .execute(pool)
+    .await?;
+    Ok(firings)
+}
+
+/// How many of `firings` actually sent something.
+#[cfg(feature = "postgres")]
+pub fn fired_count(firings: &[RuleFiring]) -> usize {
+    firings
+        .iter()
+        .filter(|firing| !firing.alerts.is_empty())
+        .count()
+}
+
+/// Suffix for progress lines when the cooldown left scholars out.
+pub fn held_back_note(held_back: usize) -> String {
+    if held_back == 0 {
+        String::new()
+    } else {
+        format!(", {held_back} held back by the cooldown")
+    }
+}
+
+#[cfg(feature = "postgres")]
+pub fn print_firings(firings: &[RuleFiring]) {
+    for firing in firings {
+        println!(
+            "  rule {} matched {} scholars{}",
+            firing.rule,
+            firing.alerts.len(),
+            held_back_note(firing.held_back)
+        );
+        for (channel, result) in &firing.deliveries {
+            match result {
+                Ok(()) => println!("    sent to {channel}"),
+                Err(err) => eprintln!("    failed to send to {channel}: {err:#}"),
+            }
+        }
+    }
+}
+
+#[cfg(feature = "postgres")]
+pub async fn set_enabled(
+    pool: &PgPool,
+    org_id: Uuid,
+    name: &str,
+    enabled: bool,
+) -> anyhow::Result<()> {
+    let result = sqlx::query(
+        "UPDATE cohort_early_warning.alert_rules SET enabled = $1 WHERE org_id = $2 AND name = $3",
+    )
+    .bind(enabled)
+    .bind(org_id)
+    .bind(name)
+    .execute(pool)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        anyhow::bail!("no alert rule named {name}");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    fn rule() -> AlertRule {
+        AlertRule {
+            name: "wellbeing-watch".to_string(),
+            signal_type: None,
+            min_severity: None,
+            min_count: None,
+            new_only: false,
+            min_score: None,
+            min_tier: None,
+            since_days: 30,
+            enabled: true,
+            channels: Vec::new(),
+        }
+    }
+
+    fn signal(email: &str, signal_type: &str, severity: i32) -> SignalRecord {
+        SignalRecord {
+            scholar_id: Uuid::new_v4(),
+            scholar_name: email.to_string(),
+            scholar_email: email.to_string(),
+            cohort: "2026".to_string(),
+            signal_type: signal_type.to_string(),
+            severity,
+            occurred_at: Utc::now().date_naive(),
+            note: String::new(),
+            advisor: None,
+            submitted_by: None,
+            attachments: Vec::new(),
+            note_categories: Vec::new(),
+            note_sentiment: None,
+        }
+    }
+
+    fn score(email: &str, value: f64, tier: Tier) -> ScholarScore {
+        ScholarScore {
+            scholar_name: email.to_string(),
+            scholar_email: email.to_string(),
+            cohort: "2026".to_string(),
+            score: value,
+            signal_count: 1,
+            advisor: None,
+            tier,
+            escalated_by: None,
+            collapsed_signals: 0,
+            percentile: 0.0,
+            cohort_percentile: 0.0,
+            last_contact: None,
+            last_signal: None,
+        }
+    }
+
+    #[test]
+    fn requires_at_least_one_condition() {
+        assert!(rule().validate(SeverityBounds::default()).is_err());
+        let with_tier = AlertRule {
+            min_tier: Some(Tier::High),
+            ..rule()
+        };
+        assert!(with_tier.validate(SeverityBounds::default()).is_ok());
+    }
+
+    #[test]
+    fn min_severity_follows_the_configured_bounds() {
+        let rule = AlertRule {
+            min_severity: Some(8),
+            ..rule()
+        };
+        assert!(rule.validate(SeverityBounds::default()).is_err());
+        assert!(rule.validate(SeverityBounds { min: 0, max: 10 }).is_ok());
+        let low = AlertRule {
+            min_severity: Some(0),
+            ..rule
+        };
+        assert!(low.validate(SeverityBounds { min: 0, max: 10 }).is_ok());
+        assert!(low.validate(SeverityBounds::default()).is_err());
+    }
+
+    #[test]
+    fn signal_condition_needs_matching_type_and_severity() {
+        let rule = AlertRule {
+            signal_type: Some("wellbeing".to_string()),
+            min_severity: Some(4),
+            ..rule()
+        };
+        let signals = vec![
+            signal("a@example.org", "wellbeing", 4),
+            signal("b@example.org", "wellbeing", 2),
+            signal("c@example.org", "attendance", 5),
+        ];
+        let scores = vec![
+            score("a@example.org", 4.0, Tier::Moderate),
+            score("b@example.org", 2.0, Tier::Low),
+            score("c@example.org", 5.0, Tier::Moderate),
+        ];
+
+        let matches = evaluate(&rule, &signals, &scores);
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].scholar_email, "a@example.org");
+        assert_eq!(matches[0].matching_signals, 1);
+    }
+
+    #[test]
+    fn min_count_requires_that_many_matching_signals() {
+        let rule = AlertRule {
+            signal_type: Some("attendance".to_string()),
+            min_count: Some(3),
+            since_days: 14,
+            ..rule()
+        };
+        let signals = vec![
+            signal("a@example.org", "attendance", 2),
+            signal("a@example.org", "attendance", 3),
+            signal("a@example.org", "attendance", 1),
+            signal("b@example.org", "attendance", 4),
+            signal("b@example.org", "attendance", 4),
+            signal("b@example.org", "academic", 4),
+        ];
+        let scores = vec![
+            score("a@example.org", 6.0, Tier::Moderate),
+            score("b@example.org", 12.0, Tier::Critical),
+        ];
+
+        let matches = evaluate(&rule, &signals, &scores);
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].scholar_email, "a@example.org");
+        assert_eq!(matches[0].matching_signals, 3);
+        assert_eq!(rule.describe(), "3+ attendance signals within 14 days");
+
+        let counting_scores = AlertRule {
+            signal_type: None,
+            min_score: Some(8.0),
+            min_count: Some(2),
+            ..rule
+        };
+        assert!(counting_scores.validate(SeverityBounds::default()).is_err());
+    }
+
+    #[test]
+    fn score_and_tier_conditions_combine() {
+        let rule = AlertRule {
+            min_score: Some(5.0),
+            min_tier: Some(Tier::High),
+            ..rule()
+        };
+        let scores = vec![
+            score("a@example.org", 9.0, Tier::High),
+            score("b@example.org", 6.0, Tier::Moderate),
+            score("c@example.org", 3.0, Tier::Critical),
+        ];
+        let matches = evaluate(&rule, &[], &scores);
+        let emails: Vec<&str> = matches.iter().map(|m| m.scholar_email.as_str()).collect();
+        assert_eq!(emails, vec!["a@example.org"]);
+        assert_eq!(matches[0].matching_signals, 0);
+        assert_eq!(
+            rule.describe(),
+            "score >= 5.00 and tier >= high within 30 days"
+        );
+    }
+}
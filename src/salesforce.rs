@@ -0,0 +1,180 @@
+use anyhow::Context;
+use serde_json::{Map, Value};
+
+use crate::config::SalesforceConfig;
+use crate::models::ScholarScore;
+
+/// The fields upserted for `score`: the configured mapping plus the constants. The external ID
+/// goes in the URL instead.
+pub fn record(config: &SalesforceConfig, score: &ScholarScore) -> Map<String, Value> {
+    let mut record: Map<String, Value> = config
+        .fields
+        .iter()
+        .map(|(field, name)| (field.clone(), score.field(name)))
+        .collect();
+    for (field, constant) in &config.constants {
+        record.insert(field.clone(), constant.clone().into());
+    }
+    record
+}
+
+/// A token from the connected app's client credentials, with the instance URL to call.
+async fn access_token(
+    client: &reqwest::Client,
+    config: &SalesforceConfig,
+) -> anyhow::Result<(String, String)> {
+    #[derive(serde::Deserialize)]
+    struct TokenResponse {
+        access_token: String,
+        instance_url: String,
+    }
+
+    let client_id = std::env::var(&config.client_id_env)
+        .with_context(|| format!("{} must be set", config.client_id_env))?;
+    let client_secret = std::env::var(&config.client_secret_env)
+        .with_context(|| format!("{} must be set", config.client_secret_env))?;
+    let token: TokenResponse = client
+        .post(format!(
+            "{}/services/oauth2/token",
+            config.instance_url.trim_end_matches('/')
+        ))
+        .form(&[
+            ("grant_type", "client_credentials"),
+            ("client_id", &client_id),
+            ("client_secret", &client_secret),
+        ])
+        .send()
+        .await?
+        .error_for_status()
+        .context("Salesforce refused the connected app's credentials")?
+        .json()
+        .await?;
+    Ok((token.access_token, token.instance_url))
+}
+
+/// `PATCH`es the scholar's record by external ID, which creates it if none matches.
+async fn upsert(
+    client: &reqwest::Client,
+    config: &SalesforceConfig,
+    (token, instance_url): &(String, String),
+    score: &ScholarScore,
+) -> anyhow::Result<()> {
+    let mut url = reqwest::Url::parse(instance_url)
+        .with_context(|| format!("Salesforce returned an invalid instance URL {instance_url:?}"))?;
+    url.path_segments_mut()
+        .map_err(|_| anyhow::anyhow!("instance URL {instance_url:?} cannot take a path"))?
+        .pop_if_empty()
+        .extend([
+            "services",
+            "data",
+            &config.api_version,
+            "sobjects",
+            &config.object,
+            &config.external_id_field,
+            &score.scholar_email,
+        ]);
+    client
+        .patch(url)
+        .bearer_auth(token)
+        .json(&record(config, score))
+        .send()
+        .await?
+        .error_for_status()?;
+    Ok(())
+}
+
+/// Upserts a record for each of `scholars`, returning the result per scholar email. Failing to
+/// authenticate fails every scholar with the same error.
+pub async fn open_records(
+    config: &SalesforceConfig,
+    scholars: &[ScholarScore],
+) -> Vec<(String, anyhow::Result<()>)> {
+    if scholars.is_empty() {
+        return Vec::new();
+    }
+    let client = reqwest::Client::new();
+    let token = match access_token(&client, config).await {
+        Ok(token) => token,
+        Err(err) => {
+            let message = format!("{err:#}");
+            return scholars
+                .iter()
+                .map(|score| {
+                    (
+                        score.scholar_email.clone(),
+                        Err(anyhow::anyhow!("{message}")),
+                    )
+                })
+                .collect();
+        }
+    };
+    let mut results = Vec::with_capacity(scholars.len());
+    for score in scholars {
+        let result = upsert(&client, config, &token, score)
+            .await
+            .with_context(|| format!("{} {}", config.object, score.scholar_email));
+        results.push((score.scholar_email.clone(), result));
+    }
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use chrono::NaiveDate;
+
+    use super::*;
+    use crate::risk::Tier;
+
+    fn config() -> SalesforceConfig {
+        SalesforceConfig {
+            instance_url: "https://acme.my.salesforce.com".to_string(),
+            client_id_env: "CEW_SALESFORCE_CLIENT_ID".to_string(),
+            client_secret_env: "CEW_SALESFORCE_CLIENT_SECRET".to_string(),
+            api_version: "v61.0".to_string(),
+            object: "Case".to_string(),
+            external_id_field: "Scholar_Email__c".to_string(),
+            fields: BTreeMap::from([
+                ("Subject".to_string(), "summary".to_string()),
+                ("Risk_Score__c".to_string(), "score".to_string()),
+                ("Advisor__c".to_string(), "advisor".to_string()),
+                ("Last_Signal__c".to_string(), "last_signal".to_string()),
+            ]),
+            constants: BTreeMap::from([("Origin".to_string(), "Early Warning".to_string())]),
+        }
+    }
+
+    fn critical() -> ScholarScore {
+        ScholarScore {
+            scholar_name: "Ada Park".to_string(),
+            scholar_email: "ada@example.edu".to_string(),
+            cohort: "2026".to_string(),
+            score: 13.456,
+            signal_count: 4,
+            advisor: None,
+            tier: Tier::Critical,
+            escalated_by: Some("wellbeing severity 5".to_string()),
+            collapsed_signals: 0,
+            percentile: 99.0,
+            cohort_percentile: 100.0,
+            last_contact: None,
+            last_signal: NaiveDate::from_ymd_opt(2026, 3, 2),
+        }
+    }
+
+    #[test]
+    fn maps_configured_fields_and_constants() {
+        let record = record(&config(), &critical());
+        assert_eq!(
+            record["Subject"],
+            "Ada Park (2026) entered the critical tier with a score of 13.5, escalated by \
+             wellbeing severity 5"
+        );
+        assert_eq!(record["Risk_Score__c"], 13.46);
+        assert_eq!(record["Advisor__c"], Value::Null);
+        assert_eq!(record["Last_Signal__c"], "2026-03-02");
+        assert_eq!(record["Origin"], "Early Warning");
+        assert_eq!(record.len(), 5);
+    }
+}
@@ -0,0 +1,200 @@
+use std::collections::BTreeSet;
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::{bail, Context};
+use chrono::{DateTime, Datelike, Duration, DurationRound, Timelike, Utc};
+
+/// A five-field cron expression (`minute hour day-of-month month day-of-week`) evaluated in UTC.
+/// Fields accept `*`, numbers, ranges (`1-5`), lists (`1,15`), and steps (`*/15`, `0-30/10`).
+/// Day of week runs 0-6 from Sunday; 7 is also Sunday.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Schedule {
+    expression: String,
+    minutes: BTreeSet<u32>,
+    hours: BTreeSet<u32>,
+    days_of_month: BTreeSet<u32>,
+    months: BTreeSet<u32>,
+    days_of_week: BTreeSet<u32>,
+    /// Whether either day field was restricted; cron matches either day field when both are.
+    day_of_month_restricted: bool,
+    day_of_week_restricted: bool,
+}
+
+impl FromStr for Schedule {
+    type Err = anyhow::Error;
+
+    fn from_str(expression: &str) -> anyhow::Result<Self> {
+        let fields: Vec<&str> = expression.split_whitespace().collect();
+        let [minute, hour, day_of_month, month, day_of_week] = fields.as_slice() else {
+            bail!(
+                "schedule {expression:?} needs five fields: \
+                 minute hour day-of-month month day-of-week"
+            );
+        };
+        let mut days_of_week = parse_field(day_of_week, 0, 7, "day-of-week")?;
+        if days_of_week.remove(&7) {
+            days_of_week.insert(0);
+        }
+        Ok(Self {
+            expression: fields.join(" "),
+            minutes: parse_field(minute, 0, 59, "minute")?,
+            hours: parse_field(hour, 0, 23, "hour")?,
+            days_of_month: parse_field(day_of_month, 1, 31, "day-of-month")?,
+            months: parse_field(month, 1, 12, "month")?,
+            days_of_week,
+            day_of_month_restricted: *day_of_month != "*",
+            day_of_week_restricted: *day_of_week != "*",
+        })
+    }
+}
+
+impl fmt::Display for Schedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.expression)
+    }
+}
+
+impl Schedule {
+    /// First matching minute strictly after `after`, searching up to five years ahead.
+    pub fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let mut candidate = after.duration_trunc(Duration::minutes(1)).ok()? + Duration::minutes(1);
+        let limit = after + Duration::days(5 * 366);
+        while candidate <= limit {
+            if !self.months.contains(&candidate.month()) || !self.matches_day(candidate) {
+                candidate = (candidate + Duration::days(1))
+                    .with_hour(0)?
+                    .with_minute(0)?;
+                continue;
+            }
+            if !self.hours.contains(&candidate.hour()) {
+                candidate = (candidate + Duration::hours(1)).with_minute(0)?;
+                continue;
+            }
+            if self.minutes.contains(&candidate.minute()) {
+                return Some(candidate);
+            }
+            candidate += Duration::minutes(1);
+        }
+        None
+    }
+
+    fn matches_day(&self, at: DateTime<Utc>) -> bool {
+        let day_of_month = self.days_of_month.contains(&at.day());
+        let day_of_week = self
+            .days_of_week
+            .contains(&at.weekday().num_days_from_sunday());
+        match (self.day_of_month_restricted, self.day_of_week_restricted) {
+            (true, true) => day_of_month || day_of_week,
+            _ => day_of_month && day_of_week,
+        }
+    }
+}
+
+fn parse_field(field: &str, min: u32, max: u32, name: &str) -> anyhow::Result<BTreeSet<u32>> {
+    let mut values = BTreeSet::new();
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step: u32 = step
+                    .parse()
+                    .ok()
+                    .filter(|step| *step > 0)
+                    .with_context(|| format!("invalid step in {name} field {field:?}"))?;
+                (range, step)
+            }
+            None => (part, 1),
+        };
+        let (start, end) = match range {
+            "*" => (min, max),
+            _ => {
+                let parse = |value: &str| -> anyhow::Result<u32> {
+                    value
+                        .parse()
+                        .ok()
+                        .filter(|value| (min..=max).contains(value))
+                        .with_context(|| {
+                            format!("{name} field {field:?} must use values {min}-{max}")
+                        })
+                };
+                match range.split_once('-') {
+                    Some((start, end)) => (parse(start)?, parse(end)?),
+                    // `5/10` means every 10 starting at 5.
+                    None if step > 1 => (parse(range)?, max),
+                    None => {
+                        let value = parse(range)?;
+                        (value, value)
+                    }
+                }
+            }
+        };
+        if start > end {
+            bail!("{name} range {range:?} runs backwards");
+        }
+        values.extend((start..=end).step_by(step as usize));
+    }
+    Ok(values)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn at(y: i32, m: u32, d: u32, h: u32, min: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, m, d, h, min, 0).unwrap()
+    }
+
+    #[test]
+    fn finds_next_weekday_morning() {
+        let schedule: Schedule = "0 7 * * 1-5".parse().unwrap();
+        // Friday 2026-10-16 after 07:00 rolls over the weekend to Monday.
+        assert_eq!(
+            schedule.next_after(at(2026, 10, 16, 7, 0)),
+            Some(at(2026, 10, 19, 7, 0))
+        );
+        assert_eq!(
+            schedule.next_after(at(2026, 10, 16, 6, 59)),
+            Some(at(2026, 10, 16, 7, 0))
+        );
+    }
+
+    #[test]
+    fn supports_steps_lists_and_either_day_field() {
+        let every_quarter_hour: Schedule = "*/15 * * * *".parse().unwrap();
+        assert_eq!(
+            every_quarter_hour.next_after(at(2026, 12, 31, 23, 50)),
+            Some(at(2027, 1, 1, 0, 0))
+        );
+
+        // The 1st of the month or any Sunday (7 is Sunday too).
+        let either: Schedule = "30 6 1 * 7".parse().unwrap();
+        assert_eq!(
+            either.next_after(at(2026, 10, 16, 0, 0)),
+            Some(at(2026, 10, 18, 6, 30))
+        );
+        assert_eq!(
+            either.next_after(at(2026, 10, 25, 7, 0)),
+            Some(at(2026, 11, 1, 6, 30))
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_expressions() {
+        for expression in [
+            "0 7 * *",
+            "60 * * * *",
+            "0 7 * * 1-9",
+            "*/0 * * * *",
+            "5-1 * * * *",
+        ] {
+            assert!(expression.parse::<Schedule>().is_err(), "{expression}");
+        }
+        assert!("0 0 31 2 *"
+            .parse::<Schedule>()
+            .unwrap()
+            .next_after(at(2026, 1, 1, 0, 0))
+            .is_none());
+    }
+}
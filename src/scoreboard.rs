@@ -0,0 +1,246 @@
+use std::collections::{HashMap, HashSet};
+
+use anstyle::{Ansi256Color, AnsiColor, Style};
+use chrono::NaiveDate;
+
+use crate::models::ScholarScore;
+use crate::risk::{self, Tier, WindowScore};
+
+/// What a `score` table shows besides each scholar's own result.
+#[derive(Debug, Clone)]
+pub struct Listing<'a> {
+    /// Scores over [`risk::DEFAULT_WINDOWS`] by email, shown as an extra column.
+    pub windows: Option<&'a HashMap<String, Vec<WindowScore>>>,
+    /// Emails of scholars missing from the last saved scoring run, or in a higher tier than they
+    /// had there. Their rows are marked `new` and set in bold.
+    pub new_entrants: HashSet<String>,
+    pub today: NaiveDate,
+    /// Color each row by tier: red for high and critical, orange for moderate, green for low.
+    pub color: bool,
+}
+
+/// Scores as an aligned table, one scholar per row, in the order given.
+pub fn render(scores: &[ScholarScore], listing: &Listing<'_>) -> String {
+    let mut header = vec![
+        "",
+        "scholar",
+        "email",
+        "cohort",
+        "score",
+        "tier",
+        "percentile",
+        "signals",
+        "contact",
+    ];
+    if listing.windows.is_some() {
+        header.push("windows");
+    }
+    let mut rows = vec![header.into_iter().map(String::from).collect::<Vec<_>>()];
+    for score in scores {
+        let mut row = vec![
+            match listing.new_entrants.contains(&score.scholar_email) {
+                true => "new".to_string(),
+                false => String::new(),
+            },
+            score.scholar_name.clone(),
+            score.scholar_email.clone(),
+            score.cohort.clone(),
+            format!("{:.2}", score.score),
+            match &score.escalated_by {
+                Some(reason) => format!("{} ({reason})", score.tier.as_str()),
+                None => score.tier.as_str().to_string(),
+            },
+            risk::percentile_label(score),
+            match score.collapsed_signals {
+                0 => score.signal_count.to_string(),
+                collapsed => format!("{} ({collapsed} collapsed)", score.signal_count),
+            },
+            match score
+                .last_contact
+                .map(|date| (listing.today - date).num_days())
+            {
+                None => "none".to_string(),
+                Some(0) => "today".to_string(),
+                Some(1) => "1 day ago".to_string(),
+                Some(days) => format!("{days} days ago"),
+            },
+        ];
+        if let Some(windows) = listing.windows {
+            let empty = risk::DEFAULT_WINDOWS.map(|days| WindowScore {
+                days,
+                score: 0.0,
+                tier: Tier::Low,
+            });
+            let scored = windows
+                .get(&score.scholar_email)
+                .map_or(&empty[..], Vec::as_slice);
+            row.push(risk::windows_label(scored));
+        }
+        rows.push(row);
+    }
+    let widths: Vec<usize> = (0..rows[0].len())
+        .map(|column| {
+            rows.iter()
+                .map(|row| row[column].chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    let mut out = Vec::with_capacity(rows.len());
+    for (index, row) in rows.iter().enumerate() {
+        let line: Vec<String> = row
+            .iter()
+            .zip(&widths)
+            .enumerate()
+            .map(|(column, (cell, width))| match column {
+                4 => format!("{cell:>width$}"),
+                _ => format!("{cell:<width$}"),
+            })
+            .collect();
+        let line = line.join("  ").trim_end().to_string();
+        let style = match index {
+            0 => Style::new().underline(),
+            _ => row_style(&scores[index - 1], listing),
+        };
+        out.push(match listing.color {
+            true => format!("{}{line}{}", style.render(), style.render_reset()),
+            false => line,
+        });
+    }
+    out.join("\n")
+}
+
+fn row_style(score: &ScholarScore, listing: &Listing<'_>) -> Style {
+    let color = match score.tier {
+        Tier::Critical | Tier::High => AnsiColor::Red.into(),
+        Tier::Moderate => Ansi256Color(208).into(),
+        Tier::Low => AnsiColor::Green.into(),
+    };
+    let style = Style::new().fg_color(Some(color));
+    match listing.new_entrants.contains(&score.scholar_email) {
+        true => style.bold(),
+        false => style,
+    }
+}
+
+/// Scholars in `scores` who were not in the last saved run, or rose a tier since it. Empty when
+/// no run was saved yet, since then everyone would be new.
+pub fn new_entrants(previous: &HashMap<String, Tier>, scores: &[ScholarScore]) -> HashSet<String> {
+    if previous.is_empty() {
+        return HashSet::new();
+    }
+    scores
+        .iter()
+        .filter(|score| {
+            previous
+                .get(&score.scholar_email)
+                .is_none_or(|tier| score.tier > *tier)
+        })
+        .map(|score| score.scholar_email.clone())
+        .collect()
+}
+
+/// Why `score --fail-if-above` or `--fail-if-count` should fail, if it should: any scholar scoring
+/// above `above`, or more than `count` of them (of everyone scored when `above` is unset).
+pub fn breach(scores: &[ScholarScore], above: Option<f64>, count: Option<usize>) -> Option<String> {
+    let over = match above {
+        Some(above) => scores.iter().filter(|score| score.score > above).count(),
+        None => scores.len(),
+    };
+    let allowed = count.unwrap_or(0);
+    if over <= allowed || (above.is_none() && count.is_none()) {
+        return None;
+    }
+    let scholars = match over {
+        1 => "scholar",
+        _ => "scholars",
+    };
+    let scored = match above {
+        Some(above) => format!("{over} {scholars} scored above {above:.2}"),
+        None => format!("{over} {scholars} scored"),
+    };
+    Some(match count {
+        Some(count) => format!("{scored}, more than --fail-if-count {count}"),
+        None => scored,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn score(name: &str, value: f64, tier: Tier) -> ScholarScore {
+        ScholarScore {
+            scholar_name: name.to_string(),
+            scholar_email: format!("{}@x.org", name.to_lowercase()),
+            cohort: "2026".to_string(),
+            score: value,
+            signal_count: 2,
+            advisor: None,
+            tier,
+            escalated_by: None,
+            collapsed_signals: 0,
+            percentile: 100.0,
+            cohort_percentile: 100.0,
+            last_contact: None,
+            last_signal: None,
+        }
+    }
+
+    #[test]
+    fn renders_aligned_rows_colored_by_tier() {
+        let scores = [
+            score("Avery", 12.5, Tier::High),
+            score("Jo", 2.0, Tier::Low),
+        ];
+        let previous = HashMap::from([
+            ("avery@x.org".to_string(), Tier::Moderate),
+            ("jo@x.org".to_string(), Tier::Low),
+        ]);
+        let mut listing = Listing {
+            windows: None,
+            new_entrants: new_entrants(&previous, &scores),
+            today: NaiveDate::from_ymd_opt(2026, 3, 2).unwrap(),
+            color: false,
+        };
+        assert_eq!(
+            render(&scores, &listing),
+            "     scholar  email        cohort  score  tier  percentile                  signals  contact\n\
+             new  Avery    avery@x.org  2026    12.50  high  p100 in 2026, p100 overall  2        none\n     \
+             Jo       jo@x.org     2026     2.00  low   p100 in 2026, p100 overall  2        none"
+        );
+
+        listing.color = true;
+        let colored = render(&scores, &listing);
+        let lines: Vec<&str> = colored.lines().collect();
+        assert!(lines[1].starts_with("\x1b[1m\x1b[31mnew  Avery"));
+        assert!(lines[2].starts_with("\x1b[32m     Jo"));
+        assert!(lines[2].ends_with("\x1b[0m"));
+        assert!(new_entrants(&HashMap::new(), &scores).is_empty());
+    }
+
+    #[test]
+    fn breaches_score_and_count_thresholds() {
+        let scores = [
+            score("Avery", 12.5, Tier::High),
+            score("Blake", 9.0, Tier::High),
+            score("Jo", 2.0, Tier::Low),
+        ];
+        assert_eq!(breach(&scores, None, None), None);
+        assert_eq!(breach(&scores, Some(12.5), None), None);
+        assert_eq!(
+            breach(&scores, Some(10.0), None).as_deref(),
+            Some("1 scholar scored above 10.00")
+        );
+        assert_eq!(
+            breach(&scores, Some(8.0), None).as_deref(),
+            Some("2 scholars scored above 8.00")
+        );
+        assert_eq!(breach(&scores, Some(8.0), Some(2)), None);
+        assert_eq!(
+            breach(&scores, None, Some(2)).as_deref(),
+            Some("3 scholars scored, more than --fail-if-count 2")
+        );
+    }
+}
@@ -0,0 +1,261 @@
+use anyhow::{bail, Context};
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+use sqlx::{PgPool, Row};
+use uuid::Uuid;
+
+use crate::notes;
+
+/// Signal type recorded for every self-reported check-in.
+pub const SELF_REPORT_SIGNAL_TYPE: &str = "engagement";
+/// Tag that distinguishes self-reports from staff-entered engagement signals.
+pub const SELF_REPORT_TAG: &str = "self-report";
+const MAX_NOTE_CHARS: usize = 500;
+
+#[derive(Debug, Clone)]
+pub struct CheckIn {
+    /// How the scholar says they are doing, from 1 (struggling) to 5 (great).
+    pub rating: i32,
+    pub note: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct RateLimit {
+    /// Check-ins accepted per link in any rolling 24 hours.
+    pub max_per_day: i64,
+}
+
+impl Default for RateLimit {
+    fn default() -> Self {
+        Self { max_per_day: 3 }
+    }
+}
+
+/// A struggling scholar (rating 1) becomes a severity 5 signal.
+pub fn severity_for_rating(rating: i32) -> anyhow::Result<i32> {
+    if !(1..=5).contains(&rating) {
+        bail!("check-in rating must be between 1 and 5, got {rating}");
+    }
+    Ok(6 - rating)
+}
+
+pub fn hash_token(token: &str) -> String {
+    Sha256::digest(token.as_bytes())
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+fn new_token() -> String {
+    let mut bytes = [0u8; 24];
+    rand::rng().fill_bytes(&mut bytes);
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+async fn scholar_id(pool: &PgPool, org_id: Uuid, email: &str) -> anyhow::Result<Uuid> {
+    Ok(sqlx::query(
+        "SELECT id FROM cohort_early_warning.scholars \
+         WHERE org_id = $1 AND email = $2 AND deleted_at IS NULL",
+    )
+    .bind(org_id)
+    .bind(email)
+    .fetch_optional(pool)
+    .await?
+    .with_context(|| format!("no scholar found with email {email}"))?
+    .get("id"))
+}
+
+/// Records or withdraws a scholar's consent; withdrawing also revokes their links.
+pub async fn set_consent(
+    pool: &PgPool,
+    org_id: Uuid,
+    email: &str,
+    granted: bool,
+) -> anyhow::Result<()> {
+    let scholar_id = scholar_id(pool, org_id, email).await?;
+    let mut tx = pool.begin().await?;
+    sqlx::query(
+        "UPDATE cohort_early_warning.scholars \
+         SET self_report_consent_at = CASE WHEN $1 THEN NOW() ELSE NULL END WHERE id = $2",
+    )
+    .bind(granted)
+    .bind(scholar_id)
+    .execute(&mut *tx)
+    .await?;
+
+    if !granted {
+        revoke_for(&mut tx, scholar_id).await?;
+    }
+    tx.commit().await?;
+    Ok(())
+}
+
+async fn revoke_for(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    scholar_id: Uuid,
+) -> anyhow::Result<u64> {
+    Ok(sqlx::query(
+        "UPDATE cohort_early_warning.self_report_tokens SET revoked_at = NOW() \
+         WHERE scholar_id = $1 AND revoked_at IS NULL",
+    )
+    .bind(scholar_id)
+    .execute(&mut **tx)
+    .await?
+    .rows_affected())
+}
+
+/// Issues a fresh link token for a consenting scholar, revoking any earlier one.
+///
+/// The plain token is only returned here; the database keeps its hash.
+pub async fn issue_token(pool: &PgPool, org_id: Uuid, email: &str) -> anyhow::Result<String> {
+    let row = sqlx::query(
+        "SELECT id, self_report_consent_at IS NOT NULL AS consented \
+         FROM cohort_early_warning.scholars \
+         WHERE org_id = $1 AND email = $2 AND deleted_at IS NULL",
+    )
+    .bind(org_id)
+    .bind(email)
+    .fetch_optional(pool)
+    .await?
+    .with_context(|| format!("no scholar found with email {email}"))?;
+    if !row.get::<bool, _>("consented") {
+        bail!("{email} has not opted in to self-reporting");
+    }
+    let scholar_id: Uuid = row.get("id");
+
+    let token = new_token();
+    let mut tx = pool.begin().await?;
+    revoke_for(&mut tx, scholar_id).await?;
+    sqlx::query(
+        "INSERT INTO cohort_early_warning.self_report_tokens (id, org_id, scholar_id, token_hash) \
+         VALUES ($1, $2, $3, $4)",
+    )
+    .bind(Uuid::new_v4())
+    .bind(org_id)
+    .bind(scholar_id)
+    .bind(hash_token(&token))
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await?;
+    Ok(token)
+}
+
+pub async fn revoke(pool: &PgPool, org_id: Uuid, email: &str) -> anyhow::Result<u64> {
+    let scholar_id = scholar_id(pool, org_id, email).await?;
+    let mut tx = pool.begin().await?;
+    let revoked = revoke_for(&mut tx, scholar_id).await?;
+    tx.commit().await?;
+    Ok(revoked)
+}
+
+/// Turns a check-in submitted through a link into an engagement signal.
+///
+/// Rejects unknown or revoked tokens, scholars who withdrew consent, and links that already
+/// reached the rate limit.
+pub async fn submit(
+    pool: &PgPool,
+    token: &str,
+    check_in: &CheckIn,
+    limit: &RateLimit,
+) -> anyhow::Result<Uuid> {
+    let severity = severity_for_rating(check_in.rating)?;
+    let note = check_in.note.trim();
+    if note.chars().count() > MAX_NOTE_CHARS {
+        bail!("check-in notes are limited to {MAX_NOTE_CHARS} characters");
+    }
+
+    let mut tx = pool.begin().await?;
+    let Some(row) = sqlx::query(
+        "SELECT t.id, t.org_id, t.scholar_id, sc.self_report_consent_at IS NOT NULL AS consented \
+         FROM cohort_early_warning.self_report_tokens t \
+         JOIN cohort_early_warning.scholars sc ON sc.id = t.scholar_id \
+         WHERE t.token_hash = $1 AND t.revoked_at IS NULL AND sc.deleted_at IS NULL \
+         FOR UPDATE OF t",
+    )
+    .bind(hash_token(token))
+    .fetch_optional(&mut *tx)
+    .await?
+    else {
+        bail!("self-report link is invalid or has been revoked");
+    };
+    if !row.get::<bool, _>("consented") {
+        bail!("self-report consent has been withdrawn");
+    }
+    let token_id: Uuid = row.get("id");
+
+    let recent: i64 = sqlx::query(
+        "SELECT COUNT(*) AS recent FROM cohort_early_warning.self_report_submissions \
+         WHERE token_id = $1 AND submitted_at > NOW() - INTERVAL '1 day'",
+    )
+    .bind(token_id)
+    .fetch_one(&mut *tx)
+    .await?
+    .get("recent");
+    if recent >= limit.max_per_day {
+        bail!(
+            "this link already submitted {} check-ins in the last 24 hours",
+            limit.max_per_day
+        );
+    }
+
+    let signal_note = if note.is_empty() {
+        format!("Self-reported check-in: {}/5", check_in.rating)
+    } else {
+        format!("Self-reported check-in: {}/5. {note}", check_in.rating)
+    };
+    let signal_id = Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO cohort_early_warning.signals \
+         (id, scholar_id, signal_type, severity, note, occurred_at, source_key, org_id, tags) \
+         VALUES ($1, $2, $3, $4, $5, CURRENT_DATE, $6, $7, $8)",
+    )
+    .bind(signal_id)
+    .bind(row.get::<Uuid, _>("scholar_id"))
+    .bind(SELF_REPORT_SIGNAL_TYPE)
+    .bind(severity)
+    .bind(notes::seal(&signal_note))
+    .bind(format!("self-report-{signal_id}"))
+    .bind(row.get::<Uuid, _>("org_id"))
+    .bind(vec![SELF_REPORT_TAG.to_string()])
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query(
+        "INSERT INTO cohort_early_warning.self_report_submissions (id, token_id, signal_id) \
+         VALUES ($1, $2, $3)",
+    )
+    .bind(Uuid::new_v4())
+    .bind(token_id)
+    .bind(signal_id)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(signal_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn low_ratings_become_high_severity() {
+        assert_eq!(severity_for_rating(1).unwrap(), 5);
+        assert_eq!(severity_for_rating(5).unwrap(), 1);
+        assert!(severity_for_rating(0).is_err());
+        assert!(severity_for_rating(6).is_err());
+    }
+
+    #[test]
+    fn tokens_are_random_and_hashed() {
+        let first = new_token();
+        let second = new_token();
+        assert_ne!(first, second);
+        assert_eq!(first.len(), 48);
+
+        let hashed = hash_token(&first);
+        assert_eq!(hashed.len(), 64);
+        assert_ne!(hashed, first);
+        assert_eq!(hashed, hash_token(&first));
+    }
+}
@@ -0,0 +1,189 @@
+use std::fmt::Write as _;
+
+use chrono::{Duration, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{db, import, report, risk};
+
+struct Check {
+    name: &'static str,
+    passed: bool,
+    detail: String,
+}
+
+/// A cohort, and fixture emails and source keys, that only this run uses, so cleaning up can never
+/// touch a real cohort or an earlier run kept with `--keep-data`.
+struct Fixture {
+    run: Uuid,
+}
+
+impl Fixture {
+    fn new() -> Self {
+        Self {
+            run: Uuid::new_v4(),
+        }
+    }
+
+    fn cohort(&self) -> String {
+        format!("selftest-{}", self.run)
+    }
+
+    fn email(&self, handle: &str) -> String {
+        format!("selftest-{handle}-{}@groupscholar.invalid", self.run)
+    }
+
+    /// Builds the fixture CSV with dates relative to today so the scenario always lands in-window.
+    fn csv(&self) -> String {
+        let today = Utc::now().date_naive();
+        let rows = [
+            ("Selftest Alpha", "alpha", "attendance", 5, 1),
+            ("Selftest Alpha", "alpha", "academic", 4, 3),
+            ("Selftest Beta", "beta", "engagement", 3, 10),
+            ("Selftest Gamma", "gamma", "engagement", 2, 45),
+        ];
+
+        let cohort = self.cohort();
+        let mut csv = String::from(
+            "full_name,email,cohort,signal_type,severity,note,occurred_at,source_key\n",
+        );
+        for (index, (name, handle, signal_type, severity, days_ago)) in rows.iter().enumerate() {
+            let _ = writeln!(
+                csv,
+                "{name},{},{cohort},{signal_type},{severity},selftest fixture,{},selftest-{}-{index:03}",
+                self.email(handle),
+                today - Duration::days(*days_ago),
+                self.run
+            );
+        }
+        csv
+    }
+}
+
+/// Runs init, seed, import, score, and report against the target database and verifies results.
+/// Returns the cohort the fixture went into, which is removed again unless `keep_data` is set.
+pub async fn run(pool: &PgPool, org: &str, keep_data: bool) -> anyhow::Result<String> {
+    db::init_db(pool).await?;
+    let org_id: Uuid = db::resolve_org(pool, org).await?;
+    db::seed(pool, org_id).await?;
+
+    let fixture = Fixture::new();
+    let cohort = fixture.cohort();
+    let checks = run_checks(pool, org_id, &fixture).await;
+    if keep_data {
+        println!("Kept the fixture in cohort {cohort}.");
+    } else {
+        db::delete_cohort(pool, org_id, &cohort).await?;
+    }
+    let checks = checks?;
+
+    let mut failed = 0;
+    for check in &checks {
+        let marker = if check.passed { "PASS" } else { "FAIL" };
+        println!("[{marker}] {} ({})", check.name, check.detail);
+        if !check.passed {
+            failed += 1;
+        }
+    }
+
+    if failed > 0 {
+        anyhow::bail!("{failed} of {} selftest checks failed", checks.len());
+    }
+    println!("All {} selftest checks passed.", checks.len());
+    Ok(cohort)
+}
+
+async fn run_checks(pool: &PgPool, org_id: Uuid, fixture: &Fixture) -> anyhow::Result<Vec<Check>> {
+    let mut checks = Vec::new();
+    let cohort = fixture.cohort();
+
+    let fixture_path =
+        std::env::temp_dir().join(format!("cew-selftest-{}.csv", std::process::id()));
+    std::fs::write(&fixture_path, fixture.csv())?;
+    let imported = import::import_csv(
+        pool,
+        org_id,
+        &fixture_path,
+        import::ImportLimits::default(),
+        &import::SourceMapping::default(),
+        &[],
+        &import::Recovery::default(),
+    )
+    .await;
+    let _ = std::fs::remove_file(&fixture_path);
+    let imported = imported?;
+    checks.push(Check {
+        name: "import writes every fixture row",
+        passed: imported.inserted == 4,
+        detail: format!("{} of 4 rows inserted", imported.inserted),
+    });
+
+    let filter = db::SignalFilter {
+        org_id: Some(org_id),
+        cohorts: vec![cohort.clone()],
+        ..db::SignalFilter::new(risk::cutoff_date(30))
+    };
+    // A tiny page size makes the check cover keyset pagination too.
+    let signals = db::fetch_signals(pool, &filter, 2).await?;
+    checks.push(Check {
+        name: "30-day window excludes stale signals",
+        passed: signals.len() == 3,
+        detail: format!("{} signals in window", signals.len()),
+    });
+
+    let scores = risk::score_signals(&signals, 30);
+    let top = scores.first();
+    checks.push(Check {
+        name: "scoring ranks the expected scholar first",
+        passed: scores.len() == 2
+            && top.is_some_and(|score| {
+                score.scholar_email == fixture.email("alpha") && (score.score - 9.0).abs() < 0.001
+            }),
+        detail: top
+            .map(|score| format!("{} scored {:.2}", score.scholar_email, score.score))
+            .unwrap_or_else(|| "no scores".to_string()),
+    });
+
+    let trends = db::fetch_weekly_trends(pool, &filter).await?;
+    let rendered = report::build_report(
+        Some(&cohort),
+        30,
+        filter.since_date,
+        &signals,
+        &trends,
+        &report::ReportOptions::default(),
+    );
+    checks.push(Check {
+        name: "report renders every section",
+        passed: [
+            "## Signal Mix",
+            "## Highest Risk Scholars",
+            "## Recent Signal Notes",
+            "## Weekly Signal Trend",
+            "Selftest Alpha",
+        ]
+        .iter()
+        .all(|needle| rendered.contains(needle)),
+        detail: format!("{} bytes rendered", rendered.len()),
+    });
+
+    Ok(checks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fixture_parses_with_default_limits() {
+        let fixture = Fixture::new();
+        let csv = fixture.csv();
+        let mut reader =
+            import::ChunkedReader::new(csv.as_bytes(), import::ImportLimits::default());
+        let rows = reader.next_chunk().unwrap();
+        assert_eq!(rows.len(), 4);
+        assert!(rows.iter().all(|row| row.cohort == fixture.cohort()));
+        assert_ne!(fixture.cohort(), Fixture::new().cohort());
+        assert_ne!(fixture.cohort(), "selftest");
+    }
+}
@@ -0,0 +1,1280 @@
+use std::collections::{HashMap, HashSet};
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use axum::extract::{DefaultBodyLimit, FromRef, MatchedPath, Path, Query, Request, State};
+use axum::http::{header, HeaderValue, Method, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::sse::{self, KeepAlive, Sse};
+use axum::response::{Html, IntoResponse, Redirect, Response};
+use axum::routing::{get, post};
+use axum::{Extension, Json, Router};
+use futures_util::Stream;
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use tokio::sync::broadcast;
+use tower_http::cors::{AllowOrigin, CorsLayer};
+use tower_http::services::ServeDir;
+use tracing::Instrument;
+use utoipa::OpenApi;
+use utoipa_swagger_ui::SwaggerUi;
+use uuid::Uuid;
+
+use crate::apikeys::{self, Role, Scope};
+use crate::audit::{self, AuditAction};
+use crate::calendar::AcademicCalendar;
+use crate::config::{ApiConfig, ReportConfig};
+use crate::events::Event;
+use crate::export::ExportRow;
+use crate::import::{normalize_email, ImportLimits};
+use crate::models::{NewSignal, Scholar, ScholarScore, ScholarStatus};
+use crate::recommendations::Playbook;
+use crate::risk::{self, ScoringConfig};
+use crate::{db, import, notify, report};
+
+/// Environment variable holding the bearer token `serve` requires, when set.
+pub const API_TOKEN_ENV: &str = "CEW_API_TOKEN";
+
+/// How long `/readyz` waits on the database before reporting it unavailable.
+const READY_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Bundled risk dashboard served at `/dashboard/` unless `[api] static_dir` replaces it.
+const DASHBOARD_HTML: &str = include_str!("../dashboard/index.html");
+const DASHBOARD_JS: &str = include_str!("../dashboard/dashboard.js");
+
+#[derive(Clone)]
+pub struct AppState {
+    pub pool: PgPool,
+    pub org_id: Uuid,
+    pub scoring: Arc<ScoringConfig>,
+    /// `[severity]` bounds, `[dates]` window, and `[import]` note policy `POST /signals` applies.
+    pub limits: ImportLimits,
+    /// Requests must send `Authorization: Bearer <token>` when set; the token has admin scope.
+    pub token: Option<Arc<str>>,
+    /// Whether bearer tokens are also checked against `api_keys`, which makes a key required.
+    pub api_keys: bool,
+    /// New signals and tier changes for `/events`, fed by [`crate::events::relay`].
+    pub events: broadcast::Sender<Event>,
+    /// `[api]` settings: ingestion limits, CORS origins, and the dashboard directory.
+    pub api: Arc<ApiConfig>,
+    /// `[report]` settings for `/reports`.
+    pub report: Arc<ReportConfig>,
+    /// `[calendar]` seasons `/reports` judges weekly trends against.
+    pub calendar: Arc<AcademicCalendar>,
+    /// `[[playbooks]]` whose next steps `/reports` suggests for a single scholar.
+    pub playbooks: Arc<[Playbook]>,
+    pub rate_limiter: Arc<RateLimiter>,
+    /// `[cache] ttl_secs`: how long computed scores and reports are reused; zero for never.
+    pub cache_ttl: Duration,
+}
+
+impl AppState {
+    /// What [`Self::scores`] is cached under; the same as `score` over the same scope.
+    pub fn score_scope<'a>(
+        &'a self,
+        filter: &'a db::SignalFilter,
+        since_days: i64,
+    ) -> db::ScoreScope<'a> {
+        db::ScoreScope {
+            filter,
+            since_days,
+            scoring: &self.scoring,
+        }
+    }
+
+    /// [`db::score_scope`] over `filter`, reused from the cache while it is fresh.
+    pub async fn scores(
+        &self,
+        filter: &db::SignalFilter,
+        since_days: i64,
+    ) -> anyhow::Result<Vec<ScholarScore>> {
+        db::cached(
+            &self.pool,
+            self.org_id,
+            self.cache_ttl,
+            "scores",
+            &self.score_scope(filter, since_days),
+            db::score_scope(
+                &self.pool,
+                filter,
+                since_days,
+                &self.scoring,
+                db::DEFAULT_PAGE_SIZE,
+            ),
+        )
+        .await
+    }
+}
+
+impl FromRef<AppState> for PgPool {
+    fn from_ref(state: &AppState) -> Self {
+        state.pool.clone()
+    }
+}
+
+/// Per-key rate limits for `POST /signals`. Each key's allowance holds a minute's worth of
+/// requests and refills continuously, so bursts up to the limit pass while a sustained flood gets
+/// 429s.
+#[derive(Debug, Default)]
+pub struct RateLimiter {
+    /// Requests each key may still make right now, as of when it was last topped up.
+    allowances: Mutex<HashMap<String, (f64, Instant)>>,
+}
+
+impl RateLimiter {
+    /// Spends one request from `key`'s allowance of `per_minute` (0 for unlimited), or returns
+    /// how long until one is available.
+    fn take(&self, key: &str, per_minute: u32, now: Instant) -> Result<(), Duration> {
+        if per_minute == 0 {
+            return Ok(());
+        }
+        let capacity = f64::from(per_minute);
+        let per_second = capacity / 60.0;
+        let mut allowances = self.allowances.lock().expect("rate limiter lock poisoned");
+        let (left, topped_up) = allowances.entry(key.to_string()).or_insert((capacity, now));
+        *left = (*left + now.duration_since(*topped_up).as_secs_f64() * per_second).min(capacity);
+        *topped_up = now;
+        if *left >= 1.0 {
+            *left -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - *left) / per_second))
+        }
+    }
+}
+
+/// Name of the key a request authenticated with, for per-key limits. Requests with
+/// `CEW_API_TOKEN` carry that variable's name; unauthenticated ones carry none.
+#[derive(Debug, Clone)]
+struct KeyName(String);
+
+/// Email of the advisor an advisor key acts for. Requests carrying one only reach that advisor's
+/// scholars.
+#[derive(Debug, Clone)]
+pub struct Caseload(pub String);
+
+impl Caseload {
+    /// Emails of the advisor's scholars, deleted ones included.
+    pub async fn emails(&self, state: &AppState) -> anyhow::Result<HashSet<String>> {
+        let filter = db::ScholarFilter {
+            advisor: Some(self.0.clone()),
+            include_deleted: true,
+            ..db::ScholarFilter::default()
+        };
+        let scholars = db::list_scholars(&state.pool, state.org_id, &filter).await?;
+        Ok(scholars.into_iter().map(|scholar| scholar.email).collect())
+    }
+}
+
+/// Whether `email`, as sent, names one of the stored `emails`, which are normalized.
+fn in_caseload(emails: &HashSet<String>, email: &str) -> bool {
+    emails.contains(&normalize_email(email))
+}
+
+/// Fails with a 403 when `caseload` is set and `email` isn't one of its scholars.
+async fn check_caseload(
+    state: &AppState,
+    caseload: Option<&Caseload>,
+    email: &str,
+) -> Result<(), ApiError> {
+    match caseload {
+        Some(caseload) if !in_caseload(&caseload.emails(state).await?, email) => Err(ApiError(
+            StatusCode::FORBIDDEN,
+            anyhow::anyhow!("{email} is not assigned to advisor {}", caseload.0),
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// A failed request, reported as JSON carrying the error message: a 500 for any failure inside
+/// a handler, or the status the handler chose.
+struct ApiError(StatusCode, anyhow::Error);
+
+/// Body of a failed request.
+#[derive(Debug, serde::Serialize, utoipa::ToSchema)]
+struct ErrorBody {
+    error: String,
+}
+
+impl<E: Into<anyhow::Error>> From<E> for ApiError {
+    fn from(err: E) -> Self {
+        Self(StatusCode::INTERNAL_SERVER_ERROR, err.into())
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let ApiError(status, err) = self;
+        if status.is_server_error() {
+            tracing::error!(error = format!("{err:#}"), "request failed");
+        }
+        let body = ErrorBody {
+            error: format!("{err:#}"),
+        };
+        (status, Json(body)).into_response()
+    }
+}
+
+/// OpenAPI document for the REST endpoints, served at `/openapi.json`.
+#[derive(utoipa::OpenApi)]
+#[openapi(
+    info(
+        title = "Cohort early warning API",
+        description = "Scholars, signals, and risk scores for one organization. Send an API key \
+                       as `Authorization: Bearer <key>`; see `api-key create`."
+    ),
+    paths(
+        scholars,
+        scholar_signals,
+        signals,
+        add_signal,
+        scores,
+        cohort_scores,
+        reports,
+        acknowledge,
+        events,
+        healthz,
+        readyz
+    ),
+    modifiers(&BearerAuth),
+    security(("bearer" = []))
+)]
+pub struct ApiDoc;
+
+/// Declares the bearer key every route checks.
+struct BearerAuth;
+
+impl utoipa::Modify for BearerAuth {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        use utoipa::openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme};
+        // utoipa fills this in from Cargo.toml, which names no license.
+        openapi.info.license = None;
+        openapi
+            .components
+            .get_or_insert_with(Default::default)
+            .add_security_scheme(
+                "bearer",
+                SecurityScheme::Http(HttpBuilder::new().scheme(HttpAuthScheme::Bearer).build()),
+            );
+    }
+}
+
+pub fn router(state: AppState) -> Router {
+    let router = Router::new()
+        .route("/scholars", get(scholars))
+        .route("/scholars/{email}/signals", get(scholar_signals))
+        .route(
+            "/signals",
+            get(signals).merge(
+                post(add_signal)
+                    .route_layer(DefaultBodyLimit::max(state.api.max_body_bytes))
+                    .route_layer(middleware::from_fn_with_state(
+                        state.clone(),
+                        limit_ingestion,
+                    )),
+            ),
+        )
+        .route("/scores", get(scores))
+        .route("/cohorts/{cohort}/scores", get(cohort_scores))
+        .route("/reports", get(reports))
+        .route("/alerts/ack", post(acknowledge))
+        .route("/events", get(events));
+    #[cfg(feature = "graphql")]
+    let router = router.route(
+        "/graphql",
+        post(crate::graphql::handler).layer(Extension(crate::graphql::schema(state.clone()))),
+    );
+    let api = state.api.clone();
+    let router = router
+        .route_layer(middleware::from_fn_with_state(state.clone(), require_token))
+        .route_layer(middleware::from_fn(trace_request))
+        // The spec and its viewer describe the API without exposing data, so need no key; nor
+        // do the probes, which orchestrators call without one.
+        .merge(SwaggerUi::new("/docs").url("/openapi.json", ApiDoc::openapi()))
+        .merge(probes())
+        // The dashboard page holds no data; it asks for a key before calling the API.
+        .route(
+            "/dashboard",
+            get(|| async { Redirect::permanent("/dashboard/") }),
+        )
+        .nest_service("/dashboard/", dashboard(api.static_dir.as_deref()))
+        .with_state(state);
+    if api.cors_origins.is_empty() {
+        router
+    } else {
+        router.layer(cors(&api.cors_origins))
+    }
+}
+
+fn dashboard(static_dir: Option<&std::path::Path>) -> Router {
+    match static_dir {
+        Some(dir) => Router::new().fallback_service(ServeDir::new(dir)),
+        None => Router::new()
+            .route("/", get(|| async { Html(DASHBOARD_HTML) }))
+            .route(
+                "/dashboard.js",
+                get(|| async {
+                    (
+                        [(header::CONTENT_TYPE, "text/javascript; charset=utf-8")],
+                        DASHBOARD_JS,
+                    )
+                }),
+            ),
+    }
+}
+
+/// Lets browsers on `origins`, or any origin for `["*"]`, call the API.
+fn cors(origins: &[String]) -> CorsLayer {
+    let allowed = if origins.iter().any(|origin| origin == "*") {
+        AllowOrigin::any()
+    } else {
+        AllowOrigin::list(
+            origins
+                .iter()
+                .filter_map(|origin| HeaderValue::from_str(origin).ok()),
+        )
+    };
+    CorsLayer::new()
+        .allow_origin(allowed)
+        .allow_methods([Method::GET, Method::POST])
+        .allow_headers([header::AUTHORIZATION, header::CONTENT_TYPE])
+        .expose_headers([header::RETRY_AFTER])
+        .max_age(Duration::from_secs(60 * 60))
+}
+
+fn probes<S>() -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+    PgPool: FromRef<S>,
+{
+    Router::new()
+        .route("/healthz", get(healthz))
+        .route("/readyz", get(readyz))
+}
+
+/// Runs each API request in a span named for its route, so traces group requests by endpoint
+/// rather than by the emails and cohorts in their paths.
+async fn trace_request(request: Request, next: Next) -> Response {
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map_or_else(|| request.uri().path(), MatchedPath::as_str)
+        .to_string();
+    let span = tracing::info_span!(
+        "http request",
+        otel.name = format!("{} {route}", request.method()),
+        http.request.method = %request.method(),
+        http.route = route,
+        http.response.status_code = tracing::field::Empty,
+    );
+    let response = next.run(request).instrument(span.clone()).await;
+    span.record("http.response.status_code", response.status().as_u16());
+    response
+}
+
+/// Whether `sent` is the shared token. The SHA-256 digests are compared in constant time, so how
+/// long a rejection takes says nothing about how much of a guess was right.
+fn token_matches(token: &str, sent: &str) -> bool {
+    let (token, sent) = (Sha256::digest(token), Sha256::digest(sent));
+    token
+        .iter()
+        .zip(sent.iter())
+        .fold(0, |diff, (a, b)| diff | (a ^ b))
+        == 0
+}
+
+async fn require_token(
+    State(state): State<AppState>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    if state.token.is_none() && !state.api_keys {
+        return next.run(request).await;
+    }
+    let sent = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+    let granted = match sent {
+        Some(sent)
+            if state
+                .token
+                .as_deref()
+                .is_some_and(|token| token_matches(token, sent)) =>
+        {
+            Some(apikeys::Grant {
+                name: API_TOKEN_ENV.to_string(),
+                scope: Scope::Admin,
+                role: Role::Admin,
+                advisor: None,
+            })
+        }
+        Some(sent) if state.api_keys => {
+            match apikeys::authenticate(&state.pool, state.org_id, sent).await {
+                Ok(key) => key,
+                Err(err) => return ApiError::from(err).into_response(),
+            }
+        }
+        _ => None,
+    };
+    let Some(grant) = granted else {
+        return StatusCode::UNAUTHORIZED.into_response();
+    };
+    let scope = grant.scope.min(grant.role.max_scope());
+    if scope < apikeys::required_scope(request.method(), request.uri().path()) {
+        return StatusCode::FORBIDDEN.into_response();
+    }
+    match (grant.role, grant.advisor) {
+        (Role::Advisor, Some(advisor)) => {
+            request.extensions_mut().insert(Caseload(advisor));
+        }
+        (Role::Advisor, None) => return StatusCode::FORBIDDEN.into_response(),
+        _ => {}
+    }
+    request.extensions_mut().insert(KeyName(grant.name));
+    next.run(request).await
+}
+
+async fn limit_ingestion(State(state): State<AppState>, request: Request, next: Next) -> Response {
+    let key = request
+        .extensions()
+        .get::<KeyName>()
+        .map_or("", |KeyName(name)| name.as_str());
+    let per_minute = state.api.signals_per_minute(key);
+    match state.rate_limiter.take(key, per_minute, Instant::now()) {
+        Ok(()) => next.run(request).await,
+        Err(wait) => {
+            let body = ErrorBody {
+                error: format!("over the limit of {per_minute} signals per minute; retry later"),
+            };
+            let retry_after = wait.as_secs_f64().ceil().to_string();
+            (
+                StatusCode::TOO_MANY_REQUESTS,
+                [(header::RETRY_AFTER, retry_after)],
+                Json(body),
+            )
+                .into_response()
+        }
+    }
+}
+
+#[derive(Debug, Default, serde::Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+#[serde(default)]
+struct ScholarParams {
+    cohort: Option<String>,
+    status: Option<ScholarStatus>,
+    include_deleted: bool,
+}
+
+#[utoipa::path(
+    get,
+    path = "/scholars",
+    params(ScholarParams),
+    responses(
+        (status = 200, description = "Roster, as `scholar list --json`", body = Vec<Scholar>),
+        (status = 500, body = ErrorBody)
+    )
+)]
+async fn scholars(
+    State(state): State<AppState>,
+    caseload: Option<Extension<Caseload>>,
+    Query(params): Query<ScholarParams>,
+) -> Result<Json<Vec<Scholar>>, ApiError> {
+    let filter = db::ScholarFilter {
+        cohort: params.cohort,
+        status: params.status,
+        include_deleted: params.include_deleted,
+        email: None,
+        advisor: caseload.map(|Extension(Caseload(advisor))| advisor),
+    };
+    Ok(Json(
+        db::list_scholars(&state.pool, state.org_id, &filter).await?,
+    ))
+}
+
+/// Scope shared by the signal, score, and report endpoints.
+#[derive(Debug, serde::Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+#[serde(default)]
+struct ScopeParams {
+    cohort: Option<String>,
+    /// Scholar email.
+    email: Option<String>,
+    /// Advisor email. Advisor keys always get their own scholars.
+    advisor: Option<String>,
+    /// Days of signals to include.
+    #[param(default = 30)]
+    since_days: i64,
+    /// Include scholars who withdrew, graduated, or are on leave.
+    include_inactive: bool,
+    #[param(default = 1000)]
+    limit: usize,
+}
+
+impl Default for ScopeParams {
+    fn default() -> Self {
+        Self {
+            cohort: None,
+            email: None,
+            advisor: None,
+            since_days: 30,
+            include_inactive: false,
+            limit: 1_000,
+        }
+    }
+}
+
+impl ScopeParams {
+    /// The filter these parameters ask for, kept to `caseload` when the key has one.
+    fn filter(&self, org_id: Uuid, caseload: Option<&Caseload>) -> db::SignalFilter {
+        db::SignalFilter {
+            org_id: Some(org_id),
+            cohorts: self.cohort.clone().into_iter().collect(),
+            emails: self.email.clone().into_iter().collect(),
+            advisor: match caseload {
+                Some(Caseload(advisor)) => Some(advisor.clone()),
+                None => self.advisor.clone(),
+            },
+            include_inactive: self.include_inactive,
+            ..db::SignalFilter::new(risk::cutoff_date(self.since_days))
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/signals",
+    params(ScopeParams),
+    responses(
+        (status = 200, description = "Signals with scholar columns, newest first", body = Vec<ExportRow>),
+        (status = 500, body = ErrorBody)
+    )
+)]
+async fn signals(
+    State(state): State<AppState>,
+    caseload: Option<Extension<Caseload>>,
+    Query(params): Query<ScopeParams>,
+) -> Result<Json<Vec<ExportRow>>, ApiError> {
+    let mut rows = Vec::new();
+    db::for_each_export_row(
+        &state.pool,
+        &params.filter(state.org_id, caseload.as_deref()),
+        db::DEFAULT_PAGE_SIZE,
+        |row| {
+            rows.push(row);
+            Ok(())
+        },
+    )
+    .await?;
+    // Newest first, so the limit keeps the most recent signals.
+    rows.reverse();
+    rows.truncate(params.limit);
+    Ok(Json(rows))
+}
+
+#[utoipa::path(
+    get,
+    path = "/scholars/{email}/signals",
+    params(("email" = String, Path, description = "Scholar email"), ScopeParams),
+    responses(
+        (status = 200, description = "The scholar's signals, newest first", body = Vec<ExportRow>),
+        (status = 500, body = ErrorBody)
+    )
+)]
+async fn scholar_signals(
+    state: State<AppState>,
+    caseload: Option<Extension<Caseload>>,
+    Path(email): Path<String>,
+    Query(params): Query<ScopeParams>,
+) -> Result<Json<Vec<ExportRow>>, ApiError> {
+    let params = ScopeParams {
+        email: Some(email),
+        ..params
+    };
+    signals(state, caseload, Query(params)).await
+}
+
+#[derive(Debug, serde::Serialize, utoipa::ToSchema)]
+struct AddedSignal {
+    source_key: String,
+    /// False when a signal with this source key already existed.
+    inserted: bool,
+}
+
+/// Records one signal with the same upsert rules as `signal add`; needs a write key.
+#[utoipa::path(
+    post,
+    path = "/signals",
+    request_body = NewSignal,
+    responses(
+        (status = 201, description = "Recorded", body = AddedSignal),
+        (status = 200, description = "A signal with this source key already existed", body = AddedSignal),
+        (status = 403, description = "Advisor key, and the scholar isn't assigned to its advisor", body = ErrorBody),
+        (status = 413, description = "Body over the configured `max_body_bytes`"),
+        (status = 422, description = "Missing or unknown fields"),
+        (status = 429, description = "Key over its per-minute limit; see `Retry-After`", body = ErrorBody),
+        (status = 500, body = ErrorBody)
+    )
+)]
+async fn add_signal(
+    State(state): State<AppState>,
+    caseload: Option<Extension<Caseload>>,
+    Json(signal): Json<NewSignal>,
+) -> Result<(StatusCode, Json<AddedSignal>), ApiError> {
+    check_caseload(&state, caseload.as_deref(), &signal.email).await?;
+    let signal = NewSignal {
+        submitted_by: signal.submitted_by.or_else(|| Some("api".to_string())),
+        ..signal
+    };
+    let (row, inserted) =
+        import::add_signal(&state.pool, state.org_id, signal, "api", &state.limits).await?;
+    let source_key = row.source_key.unwrap_or_default();
+    audit::record(
+        &state.pool,
+        "api signals add",
+        AuditAction::Insert,
+        "signals",
+        u64::from(inserted),
+        &format!("{source_key} for {}", row.email),
+    )
+    .await?;
+    let status = if inserted {
+        StatusCode::CREATED
+    } else {
+        StatusCode::OK
+    };
+    Ok((
+        status,
+        Json(AddedSignal {
+            source_key,
+            inserted,
+        }),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/cohorts/{cohort}/scores",
+    params(("cohort" = String, Path), ScopeParams),
+    responses(
+        (status = 200, description = "The cohort's scholars ranked by risk score", body = Vec<ScholarScore>),
+        (status = 500, body = ErrorBody)
+    )
+)]
+async fn cohort_scores(
+    state: State<AppState>,
+    caseload: Option<Extension<Caseload>>,
+    Path(cohort): Path<String>,
+    Query(params): Query<ScopeParams>,
+) -> Result<Json<Vec<ScholarScore>>, ApiError> {
+    let params = ScopeParams {
+        cohort: Some(cohort),
+        ..params
+    };
+    scores(state, caseload, Query(params)).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/scores",
+    params(ScopeParams),
+    responses(
+        (status = 200, description = "Scholars ranked by risk score", body = Vec<ScholarScore>),
+        (status = 500, body = ErrorBody)
+    )
+)]
+async fn scores(
+    State(state): State<AppState>,
+    caseload: Option<Extension<Caseload>>,
+    Query(params): Query<ScopeParams>,
+) -> Result<Json<Vec<ScholarScore>>, ApiError> {
+    let mut scores = state
+        .scores(
+            &params.filter(state.org_id, caseload.as_deref()),
+            params.since_days,
+        )
+        .await?;
+    scores.truncate(params.limit);
+    Ok(Json(scores))
+}
+
+#[utoipa::path(
+    get,
+    path = "/reports",
+    params(ScopeParams),
+    responses(
+        (status = 200, description = "The markdown report", body = String, content_type = "text/markdown"),
+        (status = 403, description = "Advisor key, and `email` isn't assigned to its advisor", body = ErrorBody),
+        (status = 500, body = ErrorBody)
+    )
+)]
+async fn reports(
+    State(state): State<AppState>,
+    caseload: Option<Extension<Caseload>>,
+    Query(params): Query<ScopeParams>,
+) -> Result<Response, ApiError> {
+    if let Some(email) = &params.email {
+        check_caseload(&state, caseload.as_deref(), email).await?;
+    }
+    let filter = params.filter(state.org_id, caseload.as_deref());
+    let scope = (
+        &filter,
+        params.since_days,
+        &*state.scoring,
+        &*state.report,
+        &*state.calendar,
+        &*state.playbooks,
+    );
+    let markdown = db::cached(
+        &state.pool,
+        state.org_id,
+        state.cache_ttl,
+        "report",
+        &scope,
+        async {
+            let signals = db::fetch_signals(&state.pool, &filter, db::DEFAULT_PAGE_SIZE).await?;
+            let trends = db::fetch_weekly_trends(&state.pool, &filter).await?;
+            let cohort_start = match &params.cohort {
+                Some(name) => {
+                    db::fetch_cohort_starts(&state.pool, state.org_id, std::slice::from_ref(name))
+                        .await?
+                        .remove(name)
+                }
+                None => None,
+            };
+            let interventions = match &params.email {
+                Some(email) => {
+                    db::list_interventions(&state.pool, state.org_id, Some(email), None, 20).await?
+                }
+                None => Vec::new(),
+            };
+            Ok(report::build_report(
+                params
+                    .cohort
+                    .as_deref()
+                    .or(params.email.as_deref())
+                    .or(filter.advisor.as_deref()),
+                params.since_days,
+                filter.since_date,
+                &signals,
+                &trends,
+                &report::ReportOptions {
+                    min_cell_size: None,
+                    cohort_start,
+                    scoring: (*state.scoring).clone(),
+                    interventions,
+                    trend_alert_pct: state.report.trend_alert_pct(),
+                    co_occurrence_days: None,
+                    clusters: None,
+                    calendar: (*state.calendar).clone(),
+                    last_contacts: Some(db::last_contacts(&state.pool, state.org_id).await?),
+                    health: None,
+                    survival: None,
+                    through: None,
+                    min_score: None,
+                    redact: false,
+                    playbooks: match params.email {
+                        Some(_) => state.playbooks.to_vec(),
+                        None => Vec::new(),
+                    },
+                },
+            ))
+        },
+    )
+    .await?;
+    Ok((
+        [(header::CONTENT_TYPE, "text/markdown; charset=utf-8")],
+        markdown,
+    )
+        .into_response())
+}
+
+#[derive(Debug, serde::Deserialize, utoipa::ToSchema)]
+#[serde(deny_unknown_fields)]
+struct AckRequest {
+    email: String,
+    /// Staff member acknowledging; defaults to `api`.
+    by: Option<String>,
+}
+
+/// Acknowledges every open alert about a scholar, as `signal ack`; needs a write key.
+#[utoipa::path(
+    post,
+    path = "/alerts/ack",
+    request_body = AckRequest,
+    responses(
+        (status = 200, description = "`{\"acknowledged\": n}`", body = serde_json::Value),
+        (status = 403, description = "Advisor key, and the scholar isn't assigned to its advisor", body = ErrorBody),
+        (status = 500, body = ErrorBody)
+    )
+)]
+async fn acknowledge(
+    State(state): State<AppState>,
+    caseload: Option<Extension<Caseload>>,
+    Json(request): Json<AckRequest>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    check_caseload(&state, caseload.as_deref(), &request.email).await?;
+    let by = request.by.as_deref().unwrap_or("api");
+    let acknowledged = notify::acknowledge(&state.pool, state.org_id, &request.email, by).await?;
+    audit::record(
+        &state.pool,
+        "api alerts ack",
+        AuditAction::Update,
+        "sent_alerts",
+        acknowledged,
+        &format!("{} by {by}", request.email),
+    )
+    .await?;
+    Ok(Json(serde_json::json!({ "acknowledged": acknowledged })))
+}
+
+pub async fn serve(state: AppState, addr: SocketAddr) -> anyhow::Result<()> {
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    let auth = match (state.token.is_some(), state.api_keys) {
+        (_, true) => "API key required",
+        (true, false) => "bearer token required",
+        (false, false) => {
+            "no authentication; create a key with `api-key create` or set CEW_API_TOKEN"
+        }
+    };
+    println!(
+        "Serving the API on http://{} ({auth}).",
+        listener.local_addr()?
+    );
+    axum::serve(listener, router(state))
+        .with_graceful_shutdown(async {
+            let _ = tokio::signal::ctrl_c().await;
+        })
+        .await?;
+    Ok(())
+}
+
+/// Streams new signals (`signal` events, without notes) and tier changes (`tier_change`) as they
+/// happen. A client too slow to keep up gets a `lagged` event with the number it missed. Advisor
+/// keys only get events about the scholars assigned to their advisor when they connected.
+#[utoipa::path(
+    get,
+    path = "/events",
+    responses(
+        (status = 200, description = "Server-sent events: `signal` and `tier_change`", content_type = "text/event-stream")
+    )
+)]
+async fn events(
+    State(state): State<AppState>,
+    caseload: Option<Extension<Caseload>>,
+) -> Result<Sse<impl Stream<Item = Result<sse::Event, Infallible>>>, ApiError> {
+    let caseload = match caseload {
+        Some(Extension(caseload)) => Some(caseload.emails(&state).await?),
+        None => None,
+    };
+    let stream = futures_util::stream::unfold(
+        (state.events.subscribe(), caseload),
+        |(mut events, caseload)| async move {
+            loop {
+                let message = match events.recv().await {
+                    Ok(event)
+                        if caseload
+                            .as_ref()
+                            .is_some_and(|emails| !emails.contains(event.email())) =>
+                    {
+                        continue
+                    }
+                    Ok(event) => sse::Event::default()
+                        .event(event.name())
+                        .json_data(&event)
+                        .expect("events serialize to JSON"),
+                    Err(broadcast::error::RecvError::Lagged(missed)) => sse::Event::default()
+                        .event("lagged")
+                        .data(missed.to_string()),
+                    Err(broadcast::error::RecvError::Closed) => return None,
+                };
+                return Some((Ok(message), (events, caseload)));
+            }
+        },
+    );
+    Ok(Sse::new(stream).keep_alive(KeepAlive::default()))
+}
+
+/// Liveness: the process is up and answering. Deliberately ignores the database, so an outage
+/// doesn't get the process restarted.
+#[utoipa::path(
+    get,
+    path = "/healthz",
+    security(()),
+    responses((status = 200, body = String, content_type = "text/plain"))
+)]
+async fn healthz() -> &'static str {
+    "ok"
+}
+
+/// `/readyz` body.
+#[derive(Debug, PartialEq, serde::Serialize, utoipa::ToSchema)]
+struct Readiness {
+    ready: bool,
+    /// `ok`, or why the database couldn't be read.
+    database: String,
+    /// Migrations this build has that the database doesn't match, e.g. `029 pending`.
+    migrations: Vec<String>,
+}
+
+impl Readiness {
+    fn from_status(status: anyhow::Result<Vec<db::MigrationStatus>>) -> Self {
+        match status {
+            Ok(statuses) => {
+                let migrations: Vec<String> = statuses
+                    .iter()
+                    .filter(|status| status.state != db::MigrationState::Applied)
+                    .map(|status| format!("{:03} {}", status.version, status.state.as_str()))
+                    .collect();
+                Self {
+                    ready: migrations.is_empty(),
+                    database: "ok".to_string(),
+                    migrations,
+                }
+            }
+            Err(err) => Self {
+                ready: false,
+                database: format!("{err:#}"),
+                migrations: Vec::new(),
+            },
+        }
+    }
+}
+
+/// Readiness: the database answers and its schema matches this build's migrations.
+#[utoipa::path(
+    get,
+    path = "/readyz",
+    security(()),
+    responses(
+        (status = 200, description = "Ready for traffic", body = Readiness),
+        (status = 503, description = "Database unreachable or migrations not applied", body = Readiness)
+    )
+)]
+async fn readyz(State(pool): State<PgPool>) -> (StatusCode, Json<Readiness>) {
+    let status = tokio::time::timeout(READY_TIMEOUT, db::migration_status(&pool))
+        .await
+        .unwrap_or_else(|_| Err(anyhow::anyhow!("no answer within {READY_TIMEOUT:?}")));
+    let readiness = Readiness::from_status(status);
+    let code = if readiness.ready {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (code, Json(readiness))
+}
+
+/// Binds `addr` and serves only `/healthz` and `/readyz` from a background task, for processes
+/// like `watch` that have no API.
+pub async fn spawn_probes(pool: PgPool, addr: SocketAddr) -> anyhow::Result<()> {
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    println!(
+        "Serving health probes on http://{}.",
+        listener.local_addr()?
+    );
+    tokio::spawn(async move {
+        if let Err(err) = axum::serve(listener, probes().with_state(pool)).await {
+            tracing::error!(error = %err, "health probes stopped");
+        }
+    });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::body::Body;
+    use tower::ServiceExt;
+
+    use super::*;
+
+    fn state(token: Option<&str>) -> AppState {
+        AppState {
+            // Never connected: these requests are rejected before touching the database.
+            pool: PgPool::connect_lazy("postgres://nobody@127.0.0.1:1/none").unwrap(),
+            org_id: db::DEFAULT_ORG_ID,
+            scoring: Arc::default(),
+            limits: ImportLimits::default(),
+            token: token.map(Arc::from),
+            api_keys: false,
+            events: broadcast::channel(1).0,
+            api: Arc::default(),
+            report: Arc::default(),
+            calendar: Arc::default(),
+            playbooks: Arc::new([]),
+            rate_limiter: Arc::default(),
+            cache_ttl: Duration::ZERO,
+        }
+    }
+
+    async fn status(app: Router, uri: &str, auth: Option<&str>) -> StatusCode {
+        let mut request = Request::builder().uri(uri);
+        if let Some(auth) = auth {
+            request = request.header(header::AUTHORIZATION, auth);
+        }
+        app.oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+            .status()
+    }
+
+    #[tokio::test]
+    async fn rejects_requests_without_the_configured_token() {
+        let app = router(state(Some("s3cret")));
+        assert_eq!(
+            status(app.clone(), "/scores", None).await,
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            status(app.clone(), "/scores", Some("Bearer wrong")).await,
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            status(app.clone(), "/nothing-here", Some("Bearer s3cret")).await,
+            StatusCode::NOT_FOUND
+        );
+        #[cfg(feature = "graphql")]
+        {
+            let query = Request::post("/graphql")
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from(r#"{"query":"{ scholars { email } }"}"#))
+                .unwrap();
+            assert_eq!(
+                app.clone().oneshot(query).await.unwrap().status(),
+                StatusCode::UNAUTHORIZED
+            );
+        }
+        let ack = Request::post("/alerts/ack")
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(r#"{"email":"a@x.org"}"#))
+            .unwrap();
+        assert_eq!(
+            app.oneshot(ack).await.unwrap().status(),
+            StatusCode::UNAUTHORIZED
+        );
+    }
+
+    #[tokio::test]
+    async fn scores_share_cache_keys_with_the_cli() {
+        let state = state(None);
+        let day = chrono::NaiveDate::from_ymd_opt(2026, 3, 10).unwrap();
+        let filter = db::SignalFilter {
+            org_id: Some(db::DEFAULT_ORG_ID),
+            cohorts: vec!["2026".to_string()],
+            ..db::SignalFilter::new(day - chrono::Duration::days(30))
+        };
+        // As `score` builds it from the config.
+        let cli = db::ScoreScope {
+            filter: &filter,
+            since_days: 30,
+            scoring: &ScoringConfig::default(),
+        };
+        assert_eq!(
+            db::cache_key("scores", &state.score_scope(&filter, 30), day).unwrap(),
+            db::cache_key("scores", &cli, day).unwrap()
+        );
+    }
+
+    #[test]
+    fn caseload_checks_normalize_the_sent_email() {
+        let emails: HashSet<String> = ["avery@example.org".to_string()].into();
+        assert!(in_caseload(&emails, "avery@example.org"));
+        assert!(in_caseload(&emails, " Avery@Example.ORG "));
+        assert!(!in_caseload(&emails, "jo@example.org"));
+    }
+
+    #[test]
+    fn tokens_match_only_exactly() {
+        assert!(token_matches("s3cret", "s3cret"));
+        assert!(!token_matches("s3cret", "s3cre"));
+        assert!(!token_matches("s3cret", "s3cret "));
+        assert!(!token_matches("s3cret", ""));
+    }
+
+    #[tokio::test]
+    async fn rejects_malformed_query_parameters() {
+        let app = router(state(None));
+        assert_eq!(
+            status(app.clone(), "/scholars?status=expelled", None).await,
+            StatusCode::BAD_REQUEST
+        );
+        assert_eq!(
+            status(app.clone(), "/cohorts/2026/scores?since_days=soon", None).await,
+            StatusCode::BAD_REQUEST
+        );
+        let incomplete = Request::post("/signals")
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(r#"{"email":"a@x.org","severity":3}"#))
+            .unwrap();
+        assert_eq!(
+            app.oneshot(incomplete).await.unwrap().status(),
+            StatusCode::UNPROCESSABLE_ENTITY
+        );
+    }
+
+    #[test]
+    fn refills_each_keys_allowance_over_the_minute() {
+        let config = ApiConfig {
+            signals_per_minute: 2,
+            key_limits: [("bulk".to_string(), 0)].into(),
+            ..ApiConfig::default()
+        };
+        let limiter = RateLimiter::default();
+        let take = |key, at| limiter.take(key, config.signals_per_minute(key), at);
+        let start = Instant::now();
+        assert!(take("sync", start).is_ok());
+        assert!(take("sync", start).is_ok());
+        let wait = take("sync", start).unwrap_err();
+        assert_eq!(wait.as_secs(), 30);
+        assert!(take("other", start).is_ok());
+        assert!(take("sync", start + Duration::from_secs(30)).is_ok());
+        assert!((0..100).all(|_| take("bulk", start).is_ok()));
+    }
+
+    #[tokio::test]
+    async fn caps_signal_bodies_and_rates() {
+        let mut state = state(None);
+        state.api = Arc::new(ApiConfig {
+            max_body_bytes: 64,
+            signals_per_minute: 1,
+            ..ApiConfig::default()
+        });
+        let app = router(state);
+        let post = || {
+            let note = "x".repeat(100);
+            Request::post("/signals")
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from(format!(r#"{{"note":"{note}"}}"#)))
+                .unwrap()
+        };
+        assert_eq!(
+            app.clone().oneshot(post()).await.unwrap().status(),
+            StatusCode::PAYLOAD_TOO_LARGE
+        );
+        let limited = app.clone().oneshot(post()).await.unwrap();
+        assert_eq!(limited.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(limited.headers()[header::RETRY_AFTER], "60");
+        // Reads don't count against the ingestion limit.
+        assert_eq!(
+            status(app, "/signals?since_days=soon", None).await,
+            StatusCode::BAD_REQUEST
+        );
+    }
+
+    #[tokio::test]
+    async fn probes_need_no_token_and_report_pending_migrations() {
+        let app = router(state(Some("s3cret")));
+        assert_eq!(status(app, "/healthz", None).await, StatusCode::OK);
+
+        let migration = |version, state| db::MigrationStatus {
+            version,
+            description: String::new(),
+            state,
+            installed_on: None,
+            reversible: true,
+        };
+        let ready = Readiness::from_status(Ok(vec![
+            migration(1, db::MigrationState::Applied),
+            migration(2, db::MigrationState::Applied),
+        ]));
+        assert!(ready.ready);
+        let behind = Readiness::from_status(Ok(vec![
+            migration(1, db::MigrationState::Applied),
+            migration(29, db::MigrationState::Pending),
+        ]));
+        assert!(!behind.ready);
+        assert_eq!(behind.migrations, ["029 pending"]);
+        let down = Readiness::from_status(Err(anyhow::anyhow!("connection refused")));
+        assert_eq!(
+            (down.ready, down.database.as_str()),
+            (false, "connection refused")
+        );
+    }
+
+    #[tokio::test]
+    async fn serves_the_dashboard_and_answers_allowed_origins() {
+        let mut state = state(Some("s3cret"));
+        state.api = Arc::new(ApiConfig {
+            cors_origins: vec!["https://dash.example.org".to_string()],
+            ..ApiConfig::default()
+        });
+        let app = router(state);
+        assert_eq!(
+            status(app.clone(), "/dashboard/", None).await,
+            StatusCode::OK
+        );
+        assert_eq!(
+            status(app.clone(), "/dashboard/dashboard.js", None).await,
+            StatusCode::OK
+        );
+        assert_eq!(
+            status(app.clone(), "/dashboard", None).await,
+            StatusCode::PERMANENT_REDIRECT
+        );
+
+        let preflight = |origin: &str| {
+            Request::builder()
+                .method(Method::OPTIONS)
+                .uri("/scores")
+                .header(header::ORIGIN, origin)
+                .header(header::ACCESS_CONTROL_REQUEST_METHOD, "GET")
+                .header(header::ACCESS_CONTROL_REQUEST_HEADERS, "authorization")
+                .body(Body::empty())
+                .unwrap()
+        };
+        let allowed = app
+            .clone()
+            .oneshot(preflight("https://dash.example.org"))
+            .await
+            .unwrap();
+        assert_eq!(
+            allowed.headers()[header::ACCESS_CONTROL_ALLOW_ORIGIN],
+            "https://dash.example.org"
+        );
+        let other = app
+            .oneshot(preflight("https://evil.example"))
+            .await
+            .unwrap();
+        assert!(!other
+            .headers()
+            .contains_key(header::ACCESS_CONTROL_ALLOW_ORIGIN));
+    }
+
+    #[tokio::test]
+    async fn documents_every_route_without_a_token() {
+        let spec = serde_json::to_value(ApiDoc::openapi()).unwrap();
+        let paths = &spec["paths"];
+        for (path, method) in [
+            ("/scholars", "get"),
+            ("/scholars/{email}/signals", "get"),
+            ("/signals", "get"),
+            ("/signals", "post"),
+            ("/scores", "get"),
+            ("/cohorts/{cohort}/scores", "get"),
+            ("/reports", "get"),
+            ("/alerts/ack", "post"),
+            ("/events", "get"),
+            ("/readyz", "get"),
+        ] {
+            assert!(
+                paths[path][method].is_object(),
+                "{method} {path} undocumented"
+            );
+        }
+        let required = &spec["components"]["schemas"]["NewSignal"]["required"];
+        assert!(required
+            .as_array()
+            .unwrap()
+            .contains(&serde_json::json!("signal_type")));
+
+        let app = router(state(Some("s3cret")));
+        assert_eq!(
+            status(app.clone(), "/openapi.json", None).await,
+            StatusCode::OK
+        );
+        assert_ne!(status(app, "/docs/", None).await, StatusCode::UNAUTHORIZED);
+    }
+}
@@ -0,0 +1,302 @@
+use std::collections::HashMap;
+
+use anyhow::Context;
+use chrono::NaiveDate;
+use serde_json::{json, Value};
+
+use crate::google::ServiceAccount;
+use crate::models::{SignalRecord, SignalTrend};
+use crate::report::{self, ReportOptions};
+
+/// Google Sheets' REST API root.
+const SHEETS_API: &str = "https://sheets.googleapis.com/v4/spreadsheets";
+
+/// OAuth scope the service account asks for.
+const SHEETS_SCOPE: &str = "https://www.googleapis.com/auth/spreadsheets";
+
+/// Longest tab title Sheets accepts, in characters.
+const MAX_TITLE_CHARS: usize = 100;
+
+/// Tab title for `cohort`, trimmed to what Sheets accepts.
+pub fn tab_title(cohort: &str) -> String {
+    cohort.chars().take(MAX_TITLE_CHARS).collect()
+}
+
+/// A1 range of a whole tab, quoted so any title works.
+fn tab_range(title: &str) -> String {
+    format!("'{}'", title.replace('\'', "''"))
+}
+
+/// One cohort's report tables stacked on a tab: scores, the signal mix, then weekly trends, each
+/// under a title row and a header row with a blank row between tables. Follows the report's
+/// `min_score`, `min_cell_size`, and `redact` options.
+pub fn cohort_rows(
+    since_days: i64,
+    cutoff: NaiveDate,
+    signals: &[SignalRecord],
+    trends: &[SignalTrend],
+    options: &ReportOptions,
+) -> Vec<Vec<Value>> {
+    let (today, mut scores) = report::score_report(since_days, cutoff, signals, options);
+    if let Some(min_score) = options.min_score {
+        scores.retain(|score| score.score >= min_score);
+    }
+    let pseudonyms: HashMap<&str, String> = signals
+        .iter()
+        .map(|signal| {
+            (
+                signal.scholar_email.as_str(),
+                report::pseudonym(signal.scholar_id),
+            )
+        })
+        .collect();
+
+    let mut rows = vec![
+        vec![json!("Scores")],
+        [
+            "Scholar",
+            "Email",
+            "Advisor",
+            "Score",
+            "Tier",
+            "Escalated by",
+            "Signals",
+            "Days since contact",
+        ]
+        .map(Value::from)
+        .to_vec(),
+    ];
+    for score in &scores {
+        let (name, email) = match options.redact {
+            true => (
+                json!(pseudonyms.get(score.scholar_email.as_str())),
+                Value::Null,
+            ),
+            false => (json!(score.scholar_name), json!(score.scholar_email)),
+        };
+        rows.push(vec![
+            name,
+            email,
+            json!(score.advisor),
+            json!((score.score * 100.0).round() / 100.0),
+            json!(score.tier.as_str()),
+            json!(score.escalated_by),
+            json!(score.signal_count),
+            json!(score
+                .last_contact
+                .map(|contact| (today - contact).num_days())),
+        ]);
+    }
+
+    rows.push(Vec::new());
+    rows.push(vec![json!("Signal mix")]);
+    rows.push(
+        ["Signal type", "Signals", "Scholars", "Avg severity"]
+            .map(Value::from)
+            .to_vec(),
+    );
+    for summary in report::summarize_by_type(signals) {
+        if options.suppresses(summary.scholar_count) {
+            let suppressed = json!(format!("<{}", options.min_cell_size.unwrap_or_default()));
+            rows.push(vec![
+                json!(summary.signal_type),
+                suppressed.clone(),
+                suppressed,
+                Value::Null,
+            ]);
+            continue;
+        }
+        rows.push(vec![
+            json!(summary.signal_type),
+            json!(summary.count),
+            json!(summary.scholar_count),
+            json!((summary.avg_severity * 10.0).round() / 10.0),
+        ]);
+    }
+
+    rows.push(Vec::new());
+    rows.push(vec![json!("Weekly trends")]);
+    rows.push(
+        ["Week of", "Signals", "Scholars", "Avg severity"]
+            .map(Value::from)
+            .to_vec(),
+    );
+    for trend in trends {
+        rows.push(vec![
+            json!(trend.week_start.to_string()),
+            json!(trend.signal_count),
+            json!(trend.scholar_count),
+            json!((trend.avg_severity * 10.0).round() / 10.0),
+        ]);
+    }
+    rows
+}
+
+/// An authenticated connection to one spreadsheet.
+pub struct Spreadsheet {
+    client: reqwest::Client,
+    token: String,
+    url: String,
+}
+
+impl Spreadsheet {
+    pub async fn connect(account: &ServiceAccount, id: &str) -> anyhow::Result<Self> {
+        let client = reqwest::Client::new();
+        let token = account.access_token(&client, SHEETS_SCOPE).await?;
+        Ok(Self {
+            client,
+            token,
+            url: format!("{SHEETS_API}/{id}"),
+        })
+    }
+
+    async fn post(&self, path: &str, body: Value) -> anyhow::Result<()> {
+        self.client
+            .post(format!("{}{path}", self.url))
+            .bearer_auth(&self.token)
+            .json(&body)
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+
+    /// Titles of the spreadsheet's tabs.
+    async fn titles(&self) -> anyhow::Result<Vec<String>> {
+        let spreadsheet: Value = self
+            .client
+            .get(&self.url)
+            .bearer_auth(&self.token)
+            .query(&[("fields", "sheets.properties.title")])
+            .send()
+            .await?
+            .error_for_status()
+            .context("could not open the spreadsheet; is it shared with the service account?")?
+            .json()
+            .await?;
+        Ok(spreadsheet["sheets"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|sheet| sheet["properties"]["title"].as_str())
+            .map(str::to_string)
+            .collect())
+    }
+
+    /// Replaces the contents of each tab with its rows, adding tabs that don't exist yet. Other
+    /// tabs are left alone, so charts and notes built on the published tabs keep working.
+    pub async fn publish(&self, tabs: &[(String, Vec<Vec<Value>>)]) -> anyhow::Result<()> {
+        let existing = self.titles().await?;
+        let added: Vec<Value> = tabs
+            .iter()
+            .filter(|(title, _)| !existing.contains(title))
+            .map(|(title, _)| json!({ "addSheet": { "properties": { "title": title } } }))
+            .collect();
+        if !added.is_empty() {
+            self.post(":batchUpdate", json!({ "requests": added }))
+                .await
+                .context("could not add tabs to the spreadsheet")?;
+        }
+        let ranges: Vec<String> = tabs.iter().map(|(title, _)| tab_range(title)).collect();
+        self.post("/values:batchClear", json!({ "ranges": ranges }))
+            .await
+            .context("could not clear the published tabs")?;
+        let data: Vec<Value> = tabs
+            .iter()
+            .map(|(title, rows)| json!({ "range": format!("{}!A1", tab_range(title)), "values": rows }))
+            .collect();
+        self.post(
+            "/values:batchUpdate",
+            json!({ "valueInputOption": "RAW", "data": data }),
+        )
+        .await
+        .context("could not write the report tables")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn signal(scholar_id: u128, email: &str, signal_type: &str, severity: i32) -> SignalRecord {
+        SignalRecord {
+            scholar_id: Uuid::from_u128(scholar_id),
+            scholar_name: email.split('@').next().unwrap_or_default().to_string(),
+            scholar_email: email.to_string(),
+            cohort: "2026".to_string(),
+            signal_type: signal_type.to_string(),
+            severity,
+            occurred_at: Utc::now().date_naive(),
+            note: String::new(),
+            advisor: None,
+            submitted_by: None,
+            attachments: Vec::new(),
+            note_categories: Vec::new(),
+            note_sentiment: None,
+        }
+    }
+
+    #[test]
+    fn stacks_the_report_tables() {
+        let signals = [
+            signal(1, "ada@example.edu", "attendance", 4),
+            signal(1, "ada@example.edu", "grades", 3),
+            signal(2, "ben@example.edu", "attendance", 2),
+        ];
+        let trends = [SignalTrend {
+            week_start: NaiveDate::from_ymd_opt(2026, 3, 2).unwrap(),
+            signal_count: 3,
+            avg_severity: 3.0,
+            scholar_count: 2,
+        }];
+        let cutoff = Utc::now().date_naive() - chrono::Duration::days(30);
+        let options = ReportOptions {
+            min_cell_size: Some(2),
+            ..ReportOptions::default()
+        };
+        let rows = cohort_rows(30, cutoff, &signals, &trends, &options);
+        let titles: Vec<usize> = rows
+            .iter()
+            .enumerate()
+            .filter(|(_, row)| row.len() == 1)
+            .map(|(index, _)| index)
+            .collect();
+        assert_eq!(titles, [0, 5, 10]);
+        assert_eq!(rows[2][1], "ada@example.edu");
+        assert_eq!(rows[3][1], "ben@example.edu");
+        assert_eq!(
+            rows[7],
+            [json!("attendance"), json!(2), json!(2), json!(3.0)]
+        );
+        assert_eq!(
+            rows[8],
+            [json!("grades"), json!("<2"), json!("<2"), Value::Null]
+        );
+        assert_eq!(
+            rows[12],
+            [json!("2026-03-02"), json!(3), json!(2), json!(3.0)]
+        );
+
+        let redacted = cohort_rows(
+            30,
+            cutoff,
+            &signals,
+            &trends,
+            &ReportOptions {
+                redact: true,
+                ..options
+            },
+        );
+        assert!(redacted[2][0].as_str().unwrap().starts_with("Scholar "));
+        assert_eq!(redacted[2][1], Value::Null);
+    }
+
+    #[test]
+    fn quotes_tab_ranges() {
+        assert_eq!(tab_range("Class of '26"), "'Class of ''26'");
+        assert_eq!(tab_title(&"x".repeat(150)).len(), MAX_TITLE_CHARS);
+    }
+}
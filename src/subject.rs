@@ -0,0 +1,154 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::audit::{self, AuditEntry};
+use crate::config::Config;
+use crate::export::ExportRow;
+use crate::models::{Intervention, Outcome, Scholar, ScholarScore, ScoreHistoryPoint};
+use crate::notify::{self, Notification};
+use crate::{db, watch};
+
+/// How `audit export` writes the bundle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum BundleFormat {
+    /// One pretty-printed JSON document
+    Json,
+}
+
+/// Everything stored about one scholar, for answering a data-subject access request.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct SubjectBundle {
+    pub generated_at: DateTime<Utc>,
+    pub organization: String,
+    pub scholar: Scholar,
+    /// Live and archived signals, with notes decrypted when the note key is installed.
+    pub signals: Vec<ExportRow>,
+    /// Current score over `[defaults] since_days`; `None` when the scholar is inactive or has no
+    /// signals in that window.
+    pub score: Option<ScholarScore>,
+    /// Results of every persisted scoring run since the scholar was added, oldest first.
+    pub snapshots: Vec<ScoreHistoryPoint>,
+    pub interventions: Vec<Intervention>,
+    pub outcomes: Vec<Outcome>,
+    /// Alert deliveries that named the scholar.
+    pub notifications: Vec<Notification>,
+    /// Audit log entries whose detail names the scholar's email.
+    pub audit_entries: Vec<AuditEntry>,
+}
+
+/// Gathers everything stored about the scholar registered under `email`, soft-deleted or not.
+pub async fn bundle(
+    pool: &PgPool,
+    org: &str,
+    org_id: Uuid,
+    email: &str,
+    config: &Config,
+) -> anyhow::Result<SubjectBundle> {
+    let filter = db::ScholarFilter {
+        cohort: None,
+        email: Some(email.to_string()),
+        status: None,
+        advisor: None,
+        include_deleted: true,
+    };
+    let scholar = db::list_scholars(pool, org_id, &filter)
+        .await?
+        .into_iter()
+        .next()
+        .ok_or_else(|| anyhow::anyhow!("no scholar found with email {email}"))?;
+
+    let all_time = NaiveDate::from_ymd_opt(1, 1, 1).expect("year 1 is a valid date");
+    let filter = db::SignalFilter {
+        org_id: Some(org_id),
+        emails: vec![email.to_string()],
+        include_inactive: true,
+        include_archived: true,
+        ..db::SignalFilter::new(all_time)
+    };
+    let mut signals = Vec::new();
+    db::for_each_export_row(pool, &filter, db::DEFAULT_PAGE_SIZE, |row| {
+        signals.push(row);
+        Ok(())
+    })
+    .await?;
+
+    let score = watch::score_org(pool, org_id, config.defaults.since_days, &config.scoring)
+        .await?
+        .into_iter()
+        .find(|score| score.scholar_email == email);
+    let snapshots = db::score_history(pool, org_id, email, i64::MAX).await?;
+    let interventions = db::list_interventions(pool, org_id, Some(email), None, i64::MAX).await?;
+    let outcomes = db::list_outcomes(pool, org_id, Some(email), None, i64::MAX).await?;
+    let notifications = notify::list_notifications(
+        pool,
+        org_id,
+        &notify::NotificationQuery {
+            email: Some(email.to_string()),
+            limit: i64::MAX,
+            ..Default::default()
+        },
+    )
+    .await?;
+    let audit_entries = audit::list(
+        pool,
+        &audit::AuditQuery {
+            mentioning: Some(email.to_string()),
+            limit: i64::MAX,
+            ..Default::default()
+        },
+    )
+    .await?
+    .into_iter()
+    .filter(|entry| mentions(&entry.detail, email))
+    .collect();
+
+    Ok(SubjectBundle {
+        generated_at: Utc::now(),
+        organization: org.to_string(),
+        scholar,
+        signals,
+        score,
+        snapshots,
+        interventions,
+        outcomes,
+        notifications,
+        audit_entries,
+    })
+}
+
+/// Whether `detail` names `email` as a whole address, so `jo@x.org` doesn't match
+/// `mojo@x.org`.
+fn mentions(detail: &str, email: &str) -> bool {
+    let detail = detail.to_lowercase();
+    let email = email.to_lowercase();
+    let part_of_address = |c: char| c.is_alphanumeric() || "@.+-_".contains(c);
+    detail.match_indices(&email).any(|(start, _)| {
+        let mut after = detail[start + email.len()..].chars();
+        let ends = match after.next() {
+            None => true,
+            // A trailing dot ends a sentence unless more of a domain follows it.
+            Some('.') => !after.next().is_some_and(char::is_alphanumeric),
+            Some(c) => !part_of_address(c),
+        };
+        ends && !detail[..start]
+            .chars()
+            .next_back()
+            .is_some_and(part_of_address)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_whole_addresses_in_audit_details() {
+        assert!(mentions("jo@x.org in 2026", "jo@x.org"));
+        assert!(mentions("Jo@X.org: advisor", "jo@x.org"));
+        assert!(mentions("k-001 for jo@x.org.", "jo@x.org"));
+        assert!(!mentions("mojo@x.org in 2026", "jo@x.org"));
+        assert!(!mentions("jo@x.org.uk in 2026", "jo@x.org"));
+        assert!(!mentions("jo@x.orgs", "jo@x.org"));
+    }
+}
@@ -0,0 +1,70 @@
+use anyhow::Context;
+use opentelemetry::trace::TracerProvider;
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::trace::SdkTracerProvider;
+use opentelemetry_sdk::Resource;
+use tracing::{Level, Metadata};
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::Layer;
+
+use crate::logging::{Logger, OWN_TARGETS};
+
+/// `service.name` of exported spans unless `OTEL_SERVICE_NAME` names another.
+const SERVICE_NAME: &str = "cohort-early-warning";
+
+/// The OTLP/HTTP traces URL for a collector's base `endpoint`, as `OTEL_EXPORTER_OTLP_ENDPOINT`
+/// is read.
+pub fn traces_url(endpoint: &str) -> String {
+    format!("{}/v1/traces", endpoint.trim_end_matches('/'))
+}
+
+/// Spans and events exported: info and above from this program, warnings and errors from other
+/// crates such as the database driver.
+fn exported(metadata: &Metadata<'_>) -> bool {
+    let own = OWN_TARGETS
+        .iter()
+        .any(|target| metadata.target().starts_with(target));
+    *metadata.level() <= if own { Level::INFO } else { Level::WARN }
+}
+
+/// Makes `logger` the logger for the rest of the process and exports spans to the OTLP collector
+/// at `endpoint` in batches. Call `shutdown` on the returned provider before exiting to send the
+/// last batch.
+pub fn install(logger: Logger, endpoint: &str) -> anyhow::Result<SdkTracerProvider> {
+    let exporter = opentelemetry_otlp::SpanExporter::builder()
+        .with_http()
+        .with_endpoint(traces_url(endpoint))
+        .build()
+        .with_context(|| format!("could not set up the OTLP exporter for {endpoint}"))?;
+    let resource = match std::env::var_os("OTEL_SERVICE_NAME") {
+        Some(_) => Resource::builder().build(),
+        None => Resource::builder().with_service_name(SERVICE_NAME).build(),
+    };
+    let provider = SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_resource(resource)
+        .build();
+    let spans = tracing_opentelemetry::layer()
+        .with_tracer(provider.tracer(env!("CARGO_PKG_NAME")))
+        .with_filter(tracing_subscriber::filter::filter_fn(exported));
+    let subscriber = tracing_subscriber::registry().with(logger).with(spans);
+    let _ = tracing::subscriber::set_global_default(subscriber);
+    Ok(provider)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn appends_the_traces_path() {
+        assert_eq!(
+            traces_url("http://collector:4318/"),
+            "http://collector:4318/v1/traces"
+        );
+        assert_eq!(
+            traces_url("https://otlp.example.com/otel"),
+            "https://otlp.example.com/otel/v1/traces"
+        );
+    }
+}
@@ -0,0 +1,340 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+#[cfg(feature = "postgres")]
+use chrono::NaiveDate;
+#[cfg(feature = "postgres")]
+use sqlx::{PgPool, Row};
+#[cfg(feature = "postgres")]
+use uuid::Uuid;
+
+pub use crate::import::normalize_email;
+#[cfg(feature = "postgres")]
+use crate::import::SeverityBounds;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Check {
+    OrphanedRecord,
+    SeverityOutOfRange,
+    FutureDated,
+    DuplicateScholar,
+    CohortTypo,
+}
+
+impl Check {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Check::OrphanedRecord => "orphaned record",
+            Check::SeverityOutOfRange => "severity out of range",
+            Check::FutureDated => "future-dated",
+            Check::DuplicateScholar => "duplicate scholar",
+            Check::CohortTypo => "cohort typo",
+        }
+    }
+}
+
+/// One integrity problem and a suggested way to fix it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+    pub check: Check,
+    pub detail: String,
+    pub fix: String,
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}\n  fix: {}", self.detail, self.fix)
+    }
+}
+
+/// Groups of emails that differ only in case, Unicode normalization form, or surrounding whitespace.
+pub fn duplicate_emails<'a>(emails: impl IntoIterator<Item = &'a str>) -> Vec<Vec<String>> {
+    let mut groups: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for email in emails {
+        groups
+            .entry(normalize_email(email))
+            .or_default()
+            .push(email.to_string());
+    }
+    groups
+        .into_values()
+        .filter(|group| group.len() > 1)
+        .map(|mut group| {
+            group.sort();
+            group
+        })
+        .collect()
+}
+
+/// Cohorts that look like a mistyped name of a larger cohort, paired with that cohort. Takes each
+/// cohort with its scholar count.
+pub fn cohort_typos(cohorts: &[(String, i64)]) -> Vec<(String, String)> {
+    let mut typos = Vec::new();
+    for (suspect, suspect_size) in cohorts {
+        let intended = cohorts
+            .iter()
+            .filter(|(name, size)| name != suspect && size > suspect_size)
+            .filter(|(name, _)| looks_like_typo(suspect, name))
+            .max_by_key(|(_, size)| *size);
+        if let Some((intended, _)) = intended {
+            typos.push((suspect.clone(), intended.clone()));
+        }
+    }
+    typos
+}
+
+/// Whether `a` and `b` differ only by case and punctuation, or by one dropped, added, swapped, or
+/// replaced character. Swapping one digit for another doesn't count, since `2025` and `2026` are
+/// both real cohorts.
+fn looks_like_typo(a: &str, b: &str) -> bool {
+    let normalize = |name: &str| -> Vec<char> {
+        name.chars()
+            .filter(|c| c.is_alphanumeric())
+            .flat_map(char::to_lowercase)
+            .collect()
+    };
+    let (a, b) = (normalize(a), normalize(b));
+    if a == b {
+        return true;
+    }
+
+    let (shorter, longer) = if a.len() <= b.len() {
+        (&a, &b)
+    } else {
+        (&b, &a)
+    };
+    match longer.len() - shorter.len() {
+        0 => {
+            let differing: Vec<usize> = (0..a.len()).filter(|&i| a[i] != b[i]).collect();
+            match differing.as_slice() {
+                [i] => !(a[*i].is_ascii_digit() && b[*i].is_ascii_digit()),
+                [i, j] => *j == i + 1 && a[*i] == b[*j] && a[*j] == b[*i],
+                _ => false,
+            }
+        }
+        1 => (0..longer.len()).any(|skip| {
+            longer
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| *i != skip)
+                .map(|(_, c)| c)
+                .eq(shorter.iter())
+        }),
+        _ => false,
+    }
+}
+
+/// Scans the organization's data for integrity problems. `today` bounds future-dated records and
+/// `bounds` the severities considered in range.
+#[cfg(feature = "postgres")]
+pub async fn scan(
+    pool: &PgPool,
+    org_id: Uuid,
+    today: NaiveDate,
+    bounds: SeverityBounds,
+) -> anyhow::Result<Vec<Finding>> {
+    let mut findings = Vec::new();
+
+    for table in ["signals", "signals_archive"] {
+        let rows = sqlx::query(&format!(
+            "SELECT s.source_key, s.severity, s.occurred_at, \
+                    sc.org_id IS DISTINCT FROM s.org_id AS orphaned \
+             FROM cohort_early_warning.{table} s \
+             LEFT JOIN cohort_early_warning.scholars sc ON sc.id = s.scholar_id \
+             WHERE s.org_id = $1 \
+             AND (sc.org_id IS DISTINCT FROM s.org_id OR s.severity NOT BETWEEN $3 AND $4 \
+                  OR s.occurred_at > $2) \
+             ORDER BY s.occurred_at, s.source_key"
+        ))
+        .bind(org_id)
+        .bind(today)
+        .bind(bounds.min)
+        .bind(bounds.max)
+        .fetch_all(pool)
+        .await?;
+
+        for row in rows {
+            let source_key: String = row.get("source_key");
+            let severity: i32 = row.get("severity");
+            let occurred_at: NaiveDate = row.get("occurred_at");
+            // Archived signals have no CLI command to hide them.
+            let remove = match table {
+                "signals" => format!("`signal delete --source-key {source_key}`"),
+                _ => format!(
+                    "DELETE FROM cohort_early_warning.{table} WHERE source_key = '{source_key}'"
+                ),
+            };
+            if row.get("orphaned") {
+                findings.push(Finding {
+                    check: Check::OrphanedRecord,
+                    detail: format!(
+                        "{table} row {source_key} belongs to no scholar in this organization"
+                    ),
+                    fix: format!("remove it with {remove} and re-import it for the right scholar"),
+                });
+            }
+            if !bounds.contains(severity) {
+                findings.push(Finding {
+                    check: Check::SeverityOutOfRange,
+                    detail: format!("{table} row {source_key} has severity {severity}"),
+                    fix: format!(
+                        "UPDATE cohort_early_warning.{table} SET severity = {} \
+                         WHERE source_key = '{source_key}'",
+                        severity.clamp(bounds.min, bounds.max)
+                    ),
+                });
+            }
+            if occurred_at > today {
+                findings.push(Finding {
+                    check: Check::FutureDated,
+                    detail: format!("{table} row {source_key} occurred on {occurred_at}"),
+                    fix: format!(
+                        "correct the date at the source and re-import, or remove it with {remove}"
+                    ),
+                });
+            }
+        }
+    }
+
+    let rows = sqlx::query(
+        "SELECT a.id, a.signal_id FROM cohort_early_warning.signal_attachments a \
+         WHERE a.org_id = $1 AND NOT EXISTS ( \
+             SELECT 1 FROM cohort_early_warning.signals_with_archive s WHERE s.id = a.signal_id) \
+         ORDER BY a.created_at",
+    )
+    .bind(org_id)
+    .fetch_all(pool)
+    .await?;
+    for row in rows {
+        let id: Uuid = row.get("id");
+        let signal_id: Uuid = row.get("signal_id");
+        findings.push(Finding {
+            check: Check::OrphanedRecord,
+            detail: format!("attachment {id} points at missing signal {signal_id}"),
+            fix: format!("DELETE FROM cohort_early_warning.signal_attachments WHERE id = '{id}'"),
+        });
+    }
+
+    let rows = sqlx::query(
+        "SELECT i.id, sc.email, i.occurred_on FROM cohort_early_warning.interventions i \
+         JOIN cohort_early_warning.scholars sc ON sc.id = i.scholar_id \
+         WHERE i.org_id = $1 AND i.occurred_on > $2 ORDER BY i.occurred_on",
+    )
+    .bind(org_id)
+    .bind(today)
+    .fetch_all(pool)
+    .await?;
+    for row in rows {
+        let id: Uuid = row.get("id");
+        let email: String = row.get("email");
+        let occurred_on: NaiveDate = row.get("occurred_on");
+        findings.push(Finding {
+            check: Check::FutureDated,
+            detail: format!("intervention {id} for {email} occurred on {occurred_on}"),
+            fix: format!(
+                "UPDATE cohort_early_warning.interventions SET occurred_on = '<date>' \
+                 WHERE id = '{id}'"
+            ),
+        });
+    }
+
+    let emails: Vec<String> = sqlx::query_scalar(
+        "SELECT email FROM cohort_early_warning.scholars WHERE org_id = $1 ORDER BY email",
+    )
+    .bind(org_id)
+    .fetch_all(pool)
+    .await?;
+    for group in duplicate_emails(emails.iter().map(String::as_str)) {
+        // Suggest keeping the spelling imports would produce, when one of them already is.
+        let keep = group
+            .iter()
+            .find(|email| normalize_email(email) == **email)
+            .unwrap_or(&group[0]);
+        let absorb = group.iter().find(|email| *email != keep).unwrap_or(keep);
+        findings.push(Finding {
+            check: Check::DuplicateScholar,
+            detail: format!("{} are the same address", group.join(", ")),
+            fix: format!(
+                "fold the others into one record, e.g. `scholar merge --keep {keep} --absorb \
+                 {absorb}`"
+            ),
+        });
+    }
+
+    let rows = sqlx::query(
+        "SELECT cohort, COUNT(*) AS scholars FROM cohort_early_warning.scholars \
+         WHERE org_id = $1 AND deleted_at IS NULL GROUP BY cohort ORDER BY cohort",
+    )
+    .bind(org_id)
+    .fetch_all(pool)
+    .await?;
+    let cohorts: Vec<(String, i64)> = rows
+        .iter()
+        .map(|row| (row.get("cohort"), row.get("scholars")))
+        .collect();
+    for (suspect, intended) in cohort_typos(&cohorts) {
+        let size = cohorts
+            .iter()
+            .find(|(name, _)| *name == suspect)
+            .map_or(0, |(_, size)| *size);
+        findings.push(Finding {
+            check: Check::CohortTypo,
+            detail: format!(
+                "cohort {suspect:?} ({size} scholars) looks like a typo of {intended:?}"
+            ),
+            fix: format!(
+                "move its scholars with `scholar update --email <email> --cohort {intended}`"
+            ),
+        });
+    }
+
+    findings.sort_by_key(|finding| finding.check);
+    Ok(findings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn groups_emails_differing_only_in_case_and_whitespace() {
+        let groups = duplicate_emails([
+            "avery.lee@example.org",
+            "Avery.Lee@example.org ",
+            "jo@example.org",
+        ]);
+        assert_eq!(
+            groups,
+            vec![vec![
+                "Avery.Lee@example.org ".to_string(),
+                "avery.lee@example.org".to_string()
+            ]]
+        );
+    }
+
+    #[test]
+    fn flags_small_cohorts_one_edit_from_a_larger_one() {
+        let cohorts: Vec<(String, i64)> = [
+            ("2025", 40),
+            ("2026", 55),
+            ("2062", 2),
+            ("202", 1),
+            ("Fall 2026", 30),
+            ("fall-2026", 3),
+            ("spring 2026", 4),
+        ]
+        .into_iter()
+        .map(|(name, size)| (name.to_string(), size))
+        .collect();
+
+        let typos = cohort_typos(&cohorts);
+        assert_eq!(
+            typos,
+            vec![
+                ("2062".to_string(), "2026".to_string()),
+                ("202".to_string(), "2026".to_string()),
+                ("fall-2026".to_string(), "Fall 2026".to_string()),
+            ]
+        );
+    }
+}
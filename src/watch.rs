@@ -0,0 +1,395 @@
+use std::collections::{HashMap, HashSet};
+use std::time::Instant;
+
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::audit::{self, AuditAction};
+use crate::config::Config;
+use crate::models::ScholarScore;
+use crate::risk::{self, ScoringConfig, Tier};
+use crate::rules::{self, RuleFiring};
+use crate::schedule::Schedule;
+use crate::{db, notify, retention, salesforce};
+
+/// Scores every active scholar in the organization over the last `since_days`.
+pub async fn score_org(
+    pool: &PgPool,
+    org_id: Uuid,
+    since_days: i64,
+    scoring: &ScoringConfig,
+) -> anyhow::Result<Vec<ScholarScore>> {
+    let filter = db::SignalFilter {
+        org_id: Some(org_id),
+        ..db::SignalFilter::new(risk::cutoff_date(since_days))
+    };
+    db::score_scope(pool, &filter, since_days, scoring, db::DEFAULT_PAGE_SIZE).await
+}
+
+/// Scholars at or above `alert_tier` whose tier rose since the previous run. Scholars missing
+/// from the previous run count as `low` then.
+pub fn new_alerts(
+    previous: &HashMap<String, Tier>,
+    scores: &[ScholarScore],
+    alert_tier: Tier,
+) -> Vec<ScholarScore> {
+    scores
+        .iter()
+        .filter(|score| score.tier >= alert_tier)
+        .filter(|score| {
+            previous
+                .get(&score.scholar_email)
+                .is_none_or(|before| *before < score.tier)
+        })
+        .cloned()
+        .collect()
+}
+
+#[derive(Debug)]
+pub struct CycleOutcome {
+    pub run_id: Uuid,
+    pub scored: usize,
+    /// Tier alerts; left empty when alert rules decide what is sent instead.
+    pub alerts: Vec<ScholarScore>,
+    /// Tier alerts left out because the scholar was alerted about within the cooldown.
+    pub held_back: usize,
+    pub deliveries: Vec<(String, anyhow::Result<()>)>,
+    /// Alert rules evaluated instead of the tier alert; zero when the organization has none.
+    pub rules_evaluated: usize,
+    pub rule_firings: Vec<RuleFiring>,
+    /// Scholars whose unacknowledged alerts went to the `[escalation]` channels.
+    pub escalated: Vec<ScholarScore>,
+    pub escalation_deliveries: Vec<(String, anyhow::Result<()>)>,
+    /// Scholars who entered the critical tier, by email, and whether their `[salesforce]` record
+    /// was upserted. Empty without a `[salesforce]` section.
+    pub salesforce: Vec<(String, anyhow::Result<()>)>,
+}
+
+/// Scores the organization, persists a snapshot, and sends alerts. When the organization has
+/// enabled alert rules, those decide who is alerted and where; otherwise the configured channels
+/// hear about scholars who newly crossed the alert tier. Alerts left unacknowledged past the
+/// `[escalation]` deadline are then re-sent to the escalation channels, and scholars who entered
+/// the critical tier get a `[salesforce]` record when that is configured.
+#[tracing::instrument(skip_all)]
+pub async fn run_cycle(
+    pool: &PgPool,
+    org: &str,
+    org_id: Uuid,
+    config: &Config,
+) -> anyhow::Result<CycleOutcome> {
+    let since_days = config.watch.since_days;
+    let scores = score_org(pool, org_id, since_days, &config.scoring).await?;
+    let previous = db::latest_run_tiers(pool, org_id).await?;
+    let run_id = db::save_score_run(pool, org_id, since_days, "watch", &scores).await?;
+    audit::record(
+        pool,
+        "watch",
+        AuditAction::Insert,
+        "score_snapshots",
+        scores.len() as u64,
+        &format!("run {run_id}"),
+    )
+    .await?;
+
+    let (escalated, escalation_deliveries) = escalate(pool, org, org_id, config, &scores).await?;
+    let salesforce = match &config.salesforce {
+        Some(salesforce) => {
+            salesforce::open_records(salesforce, &new_alerts(&previous, &scores, Tier::Critical))
+                .await
+        }
+        None => Vec::new(),
+    };
+    let mut outcome = CycleOutcome {
+        run_id,
+        scored: scores.len(),
+        alerts: Vec::new(),
+        held_back: 0,
+        deliveries: Vec::new(),
+        rules_evaluated: 0,
+        rule_firings: Vec::new(),
+        escalated,
+        escalation_deliveries,
+        salesforce,
+    };
+
+    let active_rules = rules::active_rules(pool, org_id, config).await?;
+    if !active_rules.is_empty() {
+        outcome.rules_evaluated = active_rules.len();
+        outcome.rule_firings = rules::fire_rules(pool, org, org_id, config, &active_rules).await?;
+        return Ok(outcome);
+    }
+
+    let (alerts, held_back) = notify::apply_cooldown(
+        pool,
+        org_id,
+        None,
+        config.watch.cooldown_days,
+        new_alerts(&previous, &scores, config.watch.alert_tier),
+    )
+    .await?;
+    if !alerts.is_empty() {
+        outcome.deliveries = notify::deliver(
+            pool,
+            config,
+            org,
+            org_id,
+            notify::AlertReason::Tier,
+            &alerts,
+        )
+        .await?;
+    }
+    outcome.alerts = alerts;
+    outcome.held_back = held_back;
+    Ok(outcome)
+}
+
+/// Re-sends alerts at or above the escalation tier that nobody acknowledged in time, with each
+/// scholar's current score. Alerts are stamped as escalated once a channel accepts them.
+async fn escalate(
+    pool: &PgPool,
+    org: &str,
+    org_id: Uuid,
+    config: &Config,
+    scores: &[ScholarScore],
+) -> anyhow::Result<(Vec<ScholarScore>, Vec<(String, anyhow::Result<()>)>)> {
+    let escalation = &config.escalation;
+    if escalation.channels.is_empty() {
+        return Ok((Vec::new(), Vec::new()));
+    }
+    let due: HashSet<String> =
+        notify::due_escalations(pool, org_id, escalation.tier, escalation.after_hours)
+            .await?
+            .into_iter()
+            .collect();
+    // Scholars with no signals left in the window have dropped out of the scores and aren't
+    // escalated.
+    let escalated: Vec<ScholarScore> = scores
+        .iter()
+        .filter(|score| due.contains(&score.scholar_email))
+        .cloned()
+        .collect();
+    if escalated.is_empty() {
+        return Ok((Vec::new(), Vec::new()));
+    }
+
+    let signals =
+        notify::contributing_signals(pool, org_id, config.watch.since_days, &escalated).await?;
+    let payload = notify::AlertPayload::new(
+        org,
+        notify::AlertReason::Unacknowledged(escalation.after_hours),
+        &escalated,
+        &signals,
+        &config.playbooks,
+    );
+    let deliveries = notify::dispatch(pool, org_id, config, &escalation.channels, &payload).await?;
+    if deliveries.iter().any(|(_, result)| result.is_ok()) {
+        let emails: Vec<&str> = escalated
+            .iter()
+            .map(|score| score.scholar_email.as_str())
+            .collect();
+        notify::mark_escalated(
+            pool,
+            org_id,
+            escalation.tier,
+            escalation.after_hours,
+            &emails,
+        )
+        .await?;
+    }
+    Ok((escalated, deliveries))
+}
+
+fn print_outcome(outcome: &CycleOutcome) {
+    let alerted = if outcome.rules_evaluated > 0 {
+        format!(
+            "{} of {} alert rules fired",
+            rules::fired_count(&outcome.rule_firings),
+            outcome.rules_evaluated
+        )
+    } else {
+        format!(
+            "{} new alerts{}",
+            outcome.alerts.len(),
+            rules::held_back_note(outcome.held_back)
+        )
+    };
+    println!(
+        "{} run {}: scored {} scholars, {alerted}.",
+        Utc::now().format("%Y-%m-%d %H:%M"),
+        outcome.run_id,
+        outcome.scored,
+    );
+    rules::print_firings(&outcome.rule_firings);
+    for (channel, result) in &outcome.deliveries {
+        match result {
+            Ok(()) => println!("  sent to {channel}"),
+            Err(err) => eprintln!("  failed to send to {channel}: {err:#}"),
+        }
+    }
+    if !outcome.escalated.is_empty() {
+        println!(
+            "  escalated unacknowledged alerts about {} scholars",
+            outcome.escalated.len()
+        );
+        for (channel, result) in &outcome.escalation_deliveries {
+            match result {
+                Ok(()) => println!("    sent to {channel}"),
+                Err(err) => eprintln!("    failed to send to {channel}: {err:#}"),
+            }
+        }
+    }
+    for (email, result) in &outcome.salesforce {
+        match result {
+            Ok(()) => println!("  upserted Salesforce record for {email}"),
+            Err(err) => eprintln!("  failed to upsert Salesforce record for {email}: {err:#}"),
+        }
+    }
+}
+
+/// Enforces the `[retention]` policy as of now, logging and printing what each data class lost.
+async fn enforce_retention(pool: &PgPool, org_id: Uuid, config: &Config) -> anyhow::Result<()> {
+    let started = Instant::now();
+    let plan = config.retention.plan(Utc::now().date_naive(), None, None)?;
+    let summary = retention::enforce(pool, org_id, &plan, "watch", false).await?;
+    tracing::info!(
+        signals = summary.signals,
+        archived_signals = summary.archived_signals,
+        scholars = summary.scholars,
+        notes = summary.notes,
+        interventions = summary.interventions,
+        elapsed_ms = started.elapsed().as_millis() as u64,
+        "retention run finished"
+    );
+    println!("{} retention run:", Utc::now().format("%Y-%m-%d %H:%M"));
+    for line in retention::summary_lines(&plan, &summary) {
+        println!("  {line}");
+    }
+    Ok(())
+}
+
+/// When `schedule`, if set, next matches after `after`.
+fn next_run(
+    schedule: Option<&Schedule>,
+    after: DateTime<Utc>,
+) -> anyhow::Result<Option<(DateTime<Utc>, &Schedule)>> {
+    schedule
+        .map(|schedule| {
+            schedule
+                .next_after(after)
+                .map(|at| (at, schedule))
+                .ok_or_else(|| anyhow::anyhow!("schedule {schedule} never matches"))
+        })
+        .transpose()
+}
+
+/// Runs a scoring cycle at every time the `[watch]` schedule matches and enforces `[retention]`
+/// at every time its schedule matches, until interrupted. Either schedule may be left unset, but
+/// not both. With `once`, runs one scoring cycle right away instead, followed by retention
+/// enforcement if that is scheduled. A failed run is reported and the next one still runs.
+pub async fn run(pool: &PgPool, org: &str, config: &Config, once: bool) -> anyhow::Result<()> {
+    let org_id = db::resolve_org(pool, org).await?;
+    let retention = config.retention.schedule()?;
+    if once {
+        print_outcome(&run_cycle(pool, org, org_id, config).await?);
+        if retention.is_some() {
+            enforce_retention(pool, org_id, config).await?;
+        }
+        return Ok(());
+    }
+
+    let scoring = match (&config.watch.schedule, &retention) {
+        (None, Some(_)) => None,
+        _ => Some(config.watch.schedule()?),
+    };
+    loop {
+        let now = Utc::now();
+        let next_scoring = next_run(scoring.as_ref(), now)?;
+        let next_retention = next_run(retention.as_ref(), now)?;
+        let ((next, schedule), label) = match (next_scoring, next_retention) {
+            (Some(scoring), Some(retention)) if retention.0 < scoring.0 => {
+                (retention, "retention run")
+            }
+            (Some(scoring), _) => (scoring, "run"),
+            (None, Some(retention)) => (retention, "retention run"),
+            (None, None) => unreachable!("watch starts with at least one schedule"),
+        };
+        println!(
+            "Next {label} at {} UTC ({schedule}).",
+            next.format("%Y-%m-%d %H:%M")
+        );
+        let wait = (next - Utc::now()).to_std().unwrap_or_default();
+        tokio::select! {
+            _ = tokio::time::sleep(wait) => {}
+            _ = tokio::signal::ctrl_c() => {
+                println!("Stopping watch.");
+                return Ok(());
+            }
+        }
+
+        if next_scoring.is_some_and(|(at, _)| at == next) {
+            let started = Instant::now();
+            match run_cycle(pool, org, org_id, config).await {
+                Ok(outcome) => {
+                    tracing::info!(
+                        scored = outcome.scored,
+                        elapsed_ms = started.elapsed().as_millis() as u64,
+                        "watch run finished"
+                    );
+                    print_outcome(&outcome);
+                }
+                Err(err) => tracing::error!(error = format!("{err:#}"), "scoring run failed"),
+            }
+        }
+        if next_retention.is_some_and(|(at, _)| at == next) {
+            if let Err(err) = enforce_retention(pool, org_id, config).await {
+                tracing::error!(error = format!("{err:#}"), "retention run failed");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn score(email: &str, tier: Tier) -> ScholarScore {
+        ScholarScore {
+            scholar_name: email.to_string(),
+            scholar_email: email.to_string(),
+            cohort: "2026".to_string(),
+            score: 9.0,
+            signal_count: 3,
+            advisor: None,
+            tier,
+            escalated_by: None,
+            collapsed_signals: 0,
+            percentile: 0.0,
+            cohort_percentile: 0.0,
+            last_contact: None,
+            last_signal: None,
+        }
+    }
+
+    #[test]
+    fn alerts_only_on_tier_increases_at_or_above_the_alert_tier() {
+        let previous = HashMap::from([
+            ("steady@x.org".to_string(), Tier::High),
+            ("rising@x.org".to_string(), Tier::Moderate),
+            ("peaked@x.org".to_string(), Tier::Critical),
+        ]);
+        let scores = vec![
+            score("steady@x.org", Tier::High),
+            score("rising@x.org", Tier::High),
+            score("peaked@x.org", Tier::High),
+            score("new@x.org", Tier::Critical),
+            score("quiet@x.org", Tier::Moderate),
+        ];
+
+        let emails: Vec<String> = new_alerts(&previous, &scores, Tier::High)
+            .into_iter()
+            .map(|score| score.scholar_email)
+            .collect();
+        assert_eq!(emails, vec!["rising@x.org", "new@x.org"]);
+    }
+}
@@ -0,0 +1,131 @@
+use std::collections::HashMap;
+
+use crate::models::ScholarScore;
+use crate::risk::Tier;
+#[cfg(feature = "postgres")]
+use crate::risk::{ScoreAccumulator, ScoringConfig};
+
+/// A scholar's rank, score, and tier under the active configuration and an experimental one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RankChange {
+    pub scholar_name: String,
+    pub scholar_email: String,
+    pub cohort: String,
+    /// 1-based positions in each ranking.
+    pub rank_before: usize,
+    pub rank_after: usize,
+    pub score_before: f64,
+    pub score_after: f64,
+    pub tier_before: Tier,
+    pub tier_after: Tier,
+}
+
+impl RankChange {
+    /// Places gained under the experimental configuration; negative when the scholar dropped.
+    pub fn places_gained(&self) -> i64 {
+        self.rank_before as i64 - self.rank_after as i64
+    }
+}
+
+/// Pairs each scholar's active and experimental results, in experimental ranking order.
+pub fn rank_diff(active: &[ScholarScore], experimental: &[ScholarScore]) -> Vec<RankChange> {
+    let before: HashMap<&str, (usize, &ScholarScore)> = active
+        .iter()
+        .enumerate()
+        .map(|(index, score)| (score.scholar_email.as_str(), (index + 1, score)))
+        .collect();
+    experimental
+        .iter()
+        .enumerate()
+        .filter_map(|(index, after)| {
+            let (rank_before, before) = before.get(after.scholar_email.as_str())?;
+            Some(RankChange {
+                scholar_name: after.scholar_name.clone(),
+                scholar_email: after.scholar_email.clone(),
+                cohort: after.cohort.clone(),
+                rank_before: *rank_before,
+                rank_after: index + 1,
+                score_before: before.score,
+                score_after: after.score,
+                tier_before: before.tier,
+                tier_after: after.tier,
+            })
+        })
+        .collect()
+}
+
+/// Scores the signals in scope under both configurations in a single pass; nothing is saved.
+#[cfg(feature = "postgres")]
+pub async fn score_both(
+    pool: &sqlx::PgPool,
+    filter: &crate::db::SignalFilter,
+    since_days: i64,
+    active: &ScoringConfig,
+    experimental: &ScoringConfig,
+    page_size: i64,
+) -> anyhow::Result<(Vec<ScholarScore>, Vec<ScholarScore>)> {
+    let today = filter.as_of();
+    let mut before = ScoreAccumulator::new(since_days, today, active);
+    let mut after = ScoreAccumulator::new(since_days, today, experimental);
+    crate::db::for_each_signal(pool, filter, page_size, |signal| {
+        before.push(&signal);
+        after.push(&signal);
+    })
+    .await?;
+    Ok((before.finish(), after.finish()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn score(email: &str, value: f64, tier: Tier) -> ScholarScore {
+        ScholarScore {
+            scholar_name: email.to_string(),
+            scholar_email: email.to_string(),
+            cohort: "2026".to_string(),
+            score: value,
+            signal_count: 2,
+            advisor: None,
+            tier,
+            escalated_by: None,
+            collapsed_signals: 0,
+            percentile: 0.0,
+            cohort_percentile: 0.0,
+            last_contact: None,
+            last_signal: None,
+        }
+    }
+
+    #[test]
+    fn pairs_ranks_in_experimental_order() {
+        let active = vec![
+            score("a@x.org", 9.0, Tier::High),
+            score("b@x.org", 6.0, Tier::Moderate),
+            score("c@x.org", 2.0, Tier::Low),
+        ];
+        let experimental = vec![
+            score("c@x.org", 10.0, Tier::High),
+            score("a@x.org", 7.0, Tier::Moderate),
+            score("b@x.org", 6.0, Tier::Moderate),
+        ];
+
+        let diff = rank_diff(&active, &experimental);
+        let summary: Vec<(&str, usize, i64)> = diff
+            .iter()
+            .map(|change| {
+                (
+                    change.scholar_email.as_str(),
+                    change.rank_after,
+                    change.places_gained(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![("c@x.org", 1, 2), ("a@x.org", 2, -1), ("b@x.org", 3, -1)]
+        );
+        assert_eq!(diff[0].tier_before, Tier::Low);
+        assert_eq!(diff[1].tier_after, Tier::Moderate);
+    }
+}